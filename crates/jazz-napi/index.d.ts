@@ -27,6 +27,8 @@ export declare class NapiRuntime {
   commitBatch(batchId: string): void
   waitForBatch(batchId: string, tier: string): Promise<void>
   query(queryJson: string, sessionJson?: string | undefined | null, tier?: string | undefined | null, optionsJson?: string | undefined | null): Promise<any>
+  /** Parse a SQL `SELECT` into query JSON for `query` or `createSubscription`. */
+  sqlQuery(sql: string, sessionJson?: string | undefined | null): string
   unsubscribe(handle: number): void
   /** Phase 1 of 2-phase subscribe: allocate a handle and store query params. */
   createSubscription(queryJson: string, sessionJson?: string | undefined | null, tier?: string | undefined | null, optionsJson?: string | undefined | null): number
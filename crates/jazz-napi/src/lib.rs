@@ -50,7 +50,9 @@ use jazz_tools::middleware::AuthConfig;
 use jazz_tools::object::ObjectId;
 use jazz_tools::query_manager::query::Query;
 use jazz_tools::query_manager::session::{Session, WriteContext};
-use jazz_tools::query_manager::types::{Schema, SchemaHash, Value};
+use jazz_tools::query_manager::types::{
+    Date, Decimal, GeoPoint, Interval, Schema, SchemaHash, Time, Value, Vector,
+};
 use jazz_tools::runtime_core::{
     MutationErrorCallback, ReadDurabilityOptions, RuntimeCore, Scheduler, SubscriptionDelta,
     SubscriptionHandle,
@@ -74,9 +76,15 @@ enum FfiValue {
     Integer(i32),
     BigInt(i64),
     Double(f64),
+    Decimal(Decimal),
     Boolean(bool),
     Text(String),
     Timestamp(u64),
+    Date(Date),
+    Time(Time),
+    Interval(Interval),
+    GeoPoint(GeoPoint),
+    Vector(Vector),
     Uuid(ObjectId),
     Bytea(#[serde(with = "serde_bytes")] Vec<u8>),
     Array(Vec<FfiValue>),
@@ -97,9 +105,15 @@ impl From<FfiValue> for Value {
             FfiValue::Integer(value) => Value::Integer(value),
             FfiValue::BigInt(value) => Value::BigInt(value),
             FfiValue::Double(value) => Value::Double(value),
+            FfiValue::Decimal(value) => Value::Decimal(value),
             FfiValue::Boolean(value) => Value::Boolean(value),
             FfiValue::Text(value) => Value::Text(value),
             FfiValue::Timestamp(value) => Value::Timestamp(value),
+            FfiValue::Date(value) => Value::Date(value),
+            FfiValue::Time(value) => Value::Time(value),
+            FfiValue::Interval(value) => Value::Interval(value),
+            FfiValue::GeoPoint(value) => Value::GeoPoint(value),
+            FfiValue::Vector(value) => Value::Vector(value),
             FfiValue::Uuid(value) => Value::Uuid(value),
             FfiValue::Bytea(value) => Value::Bytea(value),
             FfiValue::Array(values) => Value::Array(values.into_iter().map(Value::from).collect()),
@@ -728,6 +742,48 @@ impl NapiRuntime {
         Ok(serde_json::Value::Array(json_rows))
     }
 
+    /// Describe how a query compiles; `analyze` adds per-node row counters.
+    #[napi(ts_return_type = "any")]
+    pub fn explain(
+        &self,
+        query_json: String,
+        session_json: Option<String>,
+        analyze: Option<bool>,
+    ) -> napi::Result<serde_json::Value> {
+        let query = parse_query(&query_json)?;
+        let session = parse_session_json(session_json)?;
+
+        let explain = {
+            let core = self
+                .core
+                .lock()
+                .map_err(|_| napi::Error::from_reason("lock"))?;
+            core.explain(&query, session, analyze.unwrap_or(false))
+                .map_err(|e| napi::Error::from_reason(format!("Explain failed: {e}")))?
+        };
+
+        serde_json::to_value(&explain)
+            .map_err(|e| napi::Error::from_reason(format!("Serialization failed: {}", e)))
+    }
+
+    /// Parse a SQL `SELECT` into query JSON for `query` or `createSubscription`.
+    #[napi]
+    pub fn sql_query(&self, sql: String, session_json: Option<String>) -> napi::Result<String> {
+        let session = parse_session_json(session_json)?;
+
+        let query = {
+            let core = self
+                .core
+                .lock()
+                .map_err(|_| napi::Error::from_reason("lock"))?;
+            core.sql_query(&sql, session.as_ref())
+                .map_err(|e| napi::Error::from_reason(format!("SQL query failed: {e}")))?
+        };
+
+        serde_json::to_string(&query)
+            .map_err(|e| napi::Error::from_reason(format!("Serialization failed: {}", e)))
+    }
+
     // =========================================================================
     // Subscriptions
     // =========================================================================
@@ -791,6 +847,7 @@ impl NapiRuntime {
     // Schema Access
     // =========================================================================
 
+    /// Get the current schema as JSON, including table and column annotations.
     #[napi(js_name = "getSchema", ts_return_type = "any")]
     pub fn get_schema(&self) -> napi::Result<serde_json::Value> {
         serde_json::to_value(&self.declared_schema)
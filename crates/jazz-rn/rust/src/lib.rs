@@ -23,7 +23,9 @@ use jazz_tools::binding_support::{
 use jazz_tools::object::ObjectId;
 use jazz_tools::query_manager::query::Query;
 use jazz_tools::query_manager::session::{Session, WriteContext};
-use jazz_tools::query_manager::types::{Schema, SchemaHash, Value};
+use jazz_tools::query_manager::types::{
+    Date, Decimal, GeoPoint, Interval, Schema, SchemaHash, Time, Value, Vector,
+};
 use jazz_tools::runtime_core::{
     MutationErrorCallback as CoreMutationErrorCallback, ReadDurabilityOptions, RuntimeCore,
     Scheduler, SubscriptionDelta, SubscriptionHandle,
@@ -115,9 +117,15 @@ enum FfiJsonValue {
     Integer(i32),
     BigInt(i64),
     Double(f64),
+    Decimal(String),
     Boolean(bool),
     Text(String),
     Timestamp(u64),
+    Date(String),
+    Time(String),
+    Interval(String),
+    GeoPoint(GeoPoint),
+    Vector(Vec<f32>),
     Uuid(ObjectId),
     Bytea(String),
     Array(Vec<FfiJsonValue>),
@@ -143,9 +151,25 @@ fn decode_ffi_json_value(value: FfiJsonValue) -> Result<Value, JazzRnError> {
         FfiJsonValue::Integer(value) => Ok(Value::Integer(value)),
         FfiJsonValue::BigInt(value) => Ok(Value::BigInt(value)),
         FfiJsonValue::Double(value) => Ok(Value::Double(value)),
+        FfiJsonValue::Decimal(value) => Decimal::parse(&value)
+            .map(Value::Decimal)
+            .map_err(|error| ffi_json_err(error.to_string())),
         FfiJsonValue::Boolean(value) => Ok(Value::Boolean(value)),
         FfiJsonValue::Text(value) => Ok(Value::Text(value)),
         FfiJsonValue::Timestamp(value) => Ok(Value::Timestamp(value)),
+        FfiJsonValue::Date(value) => Date::parse(&value)
+            .map(Value::Date)
+            .map_err(|error| ffi_json_err(error.to_string())),
+        FfiJsonValue::Time(value) => Time::parse(&value)
+            .map(Value::Time)
+            .map_err(|error| ffi_json_err(error.to_string())),
+        FfiJsonValue::Interval(value) => Interval::parse(&value)
+            .map(Value::Interval)
+            .map_err(|error| ffi_json_err(error.to_string())),
+        FfiJsonValue::GeoPoint(value) => Ok(Value::GeoPoint(value)),
+        FfiJsonValue::Vector(value) => Vector::new(value)
+            .map(Value::Vector)
+            .map_err(|error| ffi_json_err(error.to_string())),
         FfiJsonValue::Uuid(value) => Ok(Value::Uuid(value)),
         FfiJsonValue::Bytea(value) => hex::decode(value)
             .map(Value::Bytea)
@@ -921,6 +945,34 @@ impl RnRuntime {
         })
     }
 
+    /// The current schema as JSON, including table and column annotations.
+    /// Parse a SQL `SELECT` into query JSON for `query` or `create_subscription`.
+    pub fn sql_query(
+        &self,
+        sql: String,
+        session_json: Option<String>,
+    ) -> Result<String, JazzRnError> {
+        with_panic_boundary("sql_query", || {
+            let session = parse_session(session_json)?;
+            let core = self.core.lock().map_err(|_| JazzRnError::Internal {
+                message: "lock poisoned".into(),
+            })?;
+            let query = core
+                .sql_query(&sql, session.as_ref())
+                .map_err(runtime_err)?;
+            serde_json::to_string(&query).map_err(json_err)
+        })
+    }
+
+    pub fn get_schema(&self) -> Result<String, JazzRnError> {
+        with_panic_boundary("get_schema", || {
+            let core = self.core.lock().map_err(|_| JazzRnError::Internal {
+                message: "lock poisoned".into(),
+            })?;
+            serde_json::to_string(core.current_schema()).map_err(json_err)
+        })
+    }
+
     pub fn on_mutation_error(
         &self,
         callback: Box<dyn MutationErrorCallback>,
@@ -1166,6 +1218,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn geo_points_decode_from_lat_lon_objects() {
+        let values = decode_ffi_json_record(
+            r#"{"home":{"type":"GeoPoint","value":{"lat":52.52,"lon":13.405}}}"#,
+        )
+        .expect("geo point record should decode");
+        let Some(Value::GeoPoint(point)) = values.get("home") else {
+            panic!("expected a geo point, got {values:?}");
+        };
+        assert_eq!((point.lat(), point.lon()), (52.52, 13.405));
+
+        assert!(decode_ffi_json_record(
+            r#"{"home":{"type":"GeoPoint","value":{"lat":91,"lon":0}}}"#
+        )
+        .is_err());
+        assert!(
+            decode_ffi_json_record(r#"{"home":{"type":"GeoPoint","value":"52.52,13.405"}}"#)
+                .is_err()
+        );
+    }
+
     #[test]
     fn shutdown_scheduler_drops_new_ticks_and_clears_the_debounce_flag() {
         // Internal scheduler test: post-shutdown scheduling behavior is not
@@ -7,6 +7,7 @@ use std::time::Duration;
 
 use crate::batch_fate::BatchMode;
 use crate::jazz_tokio::{SubscriptionHandle as RuntimeSubHandle, TokioRuntime};
+use crate::query_manager::graph::explain::QueryExplain;
 use crate::query_manager::manager::LocalUpdates;
 use crate::query_manager::query::Query;
 use crate::query_manager::session::{Session, WriteContext};
@@ -385,6 +386,28 @@ impl JazzClient {
             .map_err(|e| JazzError::Query(format!("{:?}", e)))
     }
 
+    /// Describe how a query compiles: normalized relation IR, graph nodes and
+    /// the index each scan uses.
+    ///
+    /// With `analyze`, the query also runs once against local rows and each
+    /// node reports how many rows it scanned and emitted.
+    pub fn explain(&self, query: &Query, analyze: bool) -> Result<QueryExplain> {
+        self.runtime
+            .explain(query, self.read_session(), analyze)
+            .map_err(|e| JazzError::Query(e.to_string()))
+    }
+
+    /// Parse a SQL `SELECT` into a query for [`Self::query`] or
+    /// [`Self::subscribe`].
+    ///
+    /// `$session.*` parameters bind to this client's session. See
+    /// [`crate::query_manager::sql`] for the supported dialect.
+    pub fn sql_query(&self, sql: &str) -> Result<Query> {
+        self.runtime
+            .sql_query(sql, self.read_session().as_ref())
+            .map_err(|e| JazzError::Query(e.to_string()))
+    }
+
     /// Create a new row in a table.
     pub fn insert(
         &self,
@@ -0,0 +1,151 @@
+//! Migrate command implementations.
+
+use std::path::{Path, PathBuf};
+
+use jazz_tools::query_manager::types::Schema;
+use jazz_tools::schema_manager::{
+    AppId, MigrationRehearsal, ReviewedMigration, rehearse_reviewed_migration,
+};
+use jazz_tools::storage::RocksDBStorage;
+#[cfg(feature = "sqlite")]
+use jazz_tools::storage::SqliteStorage;
+
+const REHEARSAL_CACHE_SIZE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Dry-run a reviewed migration against a copy of a server's store.
+///
+/// The store under `data_dir` is copied to a temporary directory first and
+/// the rehearsal runs against the copy, never writing rows to the original. A
+/// RocksDB store can only be copied while its server is stopped; a SQLite
+/// store can be copied while it runs.
+pub fn rehearse(
+    app_id_str: &str,
+    data_dir: &str,
+    schema_path: &str,
+    migration_path: &str,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let app_id = AppId::from_string(app_id_str)?;
+    let candidate: Schema = serde_json::from_str(&read_file(schema_path)?)
+        .map_err(|err| format!("invalid candidate schema '{schema_path}': {err}"))?;
+    let migration: ReviewedMigration = serde_json::from_str(&read_file(migration_path)?)
+        .map_err(|err| format!("invalid migration '{migration_path}': {err}"))?;
+
+    let snapshot = Snapshot::copy_of(Path::new(data_dir))?;
+    let rehearsal = match &snapshot.store {
+        Store::RocksDb(path) => {
+            let storage = RocksDBStorage::open(path, REHEARSAL_CACHE_SIZE_BYTES)
+                .map_err(|err| format!("failed to open store copy: {err:?}"))?;
+            rehearse_reviewed_migration(&storage, app_id, &candidate, migration)?
+        }
+        #[cfg(feature = "sqlite")]
+        Store::Sqlite(path) => {
+            let storage = SqliteStorage::open(path)
+                .map_err(|err| format!("failed to open store copy: {err:?}"))?;
+            rehearse_reviewed_migration(&storage, app_id, &candidate, migration)?
+        }
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rehearsal)?);
+    } else {
+        print_rehearsal(&rehearsal);
+    }
+    Ok(())
+}
+
+fn read_file(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|err| format!("failed to read '{path}': {err}"))
+}
+
+fn print_rehearsal(rehearsal: &MigrationRehearsal) {
+    println!(
+        "Rehearsed migration {} -> {}",
+        rehearsal.source_hash.short(),
+        rehearsal.target_hash.short()
+    );
+    if rehearsal.tables.is_empty() {
+        println!("No stored rows in the tables this migration touches.");
+        return;
+    }
+    println!(
+        "{:<24} {:>8} {:>12} {:>10} {:>8} {:>12}",
+        "table", "rows", "transformed", "defaulted", "lossy", "unreachable"
+    );
+    for table in &rehearsal.tables {
+        println!(
+            "{:<24} {:>8} {:>12} {:>10} {:>8} {:>12}",
+            table.table,
+            table.rows,
+            table.transformed,
+            table.defaulted_values,
+            table.lossy_values,
+            table.unreachable
+        );
+    }
+}
+
+enum Store {
+    RocksDb(PathBuf),
+    #[cfg(feature = "sqlite")]
+    Sqlite(PathBuf),
+}
+
+/// A throwaway copy of a store, removed on drop.
+struct Snapshot {
+    dir: PathBuf,
+    store: Store,
+}
+
+impl Snapshot {
+    /// Take a consistent copy of the store under `data_dir`.
+    ///
+    /// SQLite stores are copied with `VACUUM INTO`, which works while a
+    /// server keeps writing. RocksDB stores are copied while holding their
+    /// lock, so rehearsing against one refuses to run while its server does.
+    fn copy_of(data_dir: &Path) -> Result<Self, String> {
+        let dir = std::env::temp_dir().join(format!("jazz-rehearsal-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir)
+            .map_err(|err| format!("failed to create '{}': {err}", dir.display()))?;
+        let snapshot = |store| Self {
+            dir: dir.clone(),
+            store,
+        };
+
+        let rocksdb = data_dir.join("jazz.rocksdb");
+        if rocksdb.is_dir() {
+            let copy = dir.join("jazz.rocksdb");
+            return match RocksDBStorage::copy_while_locked(&rocksdb, &copy) {
+                Ok(()) => Ok(snapshot(Store::RocksDb(copy))),
+                Err(err) => {
+                    let _ = std::fs::remove_dir_all(&dir);
+                    Err(format!("failed to copy store: {err}"))
+                }
+            };
+        }
+
+        #[cfg(feature = "sqlite")]
+        {
+            let sqlite = data_dir.join("jazz.sqlite");
+            if sqlite.is_file() {
+                let copy = dir.join("jazz.sqlite");
+                return match SqliteStorage::snapshot_into(&sqlite, &copy) {
+                    Ok(()) => Ok(snapshot(Store::Sqlite(copy))),
+                    Err(err) => {
+                        let _ = std::fs::remove_dir_all(&dir);
+                        Err(format!("failed to copy store: {err}"))
+                    }
+                };
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+        Err(format!("no Jazz store found in '{}'", data_dir.display()))
+    }
+}
+
+impl Drop for Snapshot {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
@@ -1,2 +1,4 @@
 pub mod create;
+pub mod migrate;
+pub mod seed;
 pub mod server;
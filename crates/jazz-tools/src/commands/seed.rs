@@ -0,0 +1,461 @@
+//! Seed command implementation.
+
+use std::collections::BTreeMap;
+
+use jazz_tools::query_manager::types::{Schema, SchemaHash};
+use jazz_tools::schema_manager::AppId;
+use jazz_tools::seed::{Fixtures, SeedRow};
+use jazz_tools::{AppContext, ClientStorage, DurabilityTier, JazzClient};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+/// Load fixture files into a running server.
+///
+/// Fixtures are validated against the schema of the server's current release,
+/// or against `--schema` once the server confirms it knows that schema. Every
+/// row is validated before anything is written. The rows are then upserted in
+/// one transaction over a backend-authenticated connection, with object ids
+/// derived from their fixture keys, so seeding twice doesn't duplicate rows.
+///
+/// A dry run with `--schema` and no admin secret validates offline.
+pub async fn run(
+    app_id_str: &str,
+    server_url: &str,
+    backend_secret: Option<String>,
+    admin_secret: Option<String>,
+    schema_path: Option<&str>,
+    fixture_paths: &[String],
+    dry_run: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let app_id = AppId::from_string(app_id_str)?;
+
+    let mut fixtures = Fixtures::new();
+    for path in fixture_paths {
+        let text = read_file(path)?;
+        if path.ends_with(".ndjson") || path.ends_with(".jsonl") {
+            fixtures.add_ndjson(path, &text)?;
+        } else {
+            fixtures.add_json(path, &text)?;
+        }
+    }
+    if fixtures.is_empty() {
+        return Err("no fixture rows to seed".into());
+    }
+
+    let schema = match (admin_secret, schema_path) {
+        (Some(admin_secret), schema_path) => {
+            ServerSchemas::new(server_url, app_id, admin_secret)
+                .resolve(schema_path)
+                .await?
+        }
+        (None, Some(schema_path)) if dry_run => {
+            println!("No admin secret; '{schema_path}' was not checked against {server_url}.");
+            read_schema(schema_path)?
+        }
+        (None, _) => {
+            return Err(
+                "--admin-secret / JAZZ_ADMIN_SECRET is required to read the server's schema".into(),
+            );
+        }
+    };
+    let rows = fixtures.plan(app_id, &schema)?;
+
+    if dry_run {
+        println!("Fixtures are valid; nothing was written.");
+        print_summary(&rows);
+        return Ok(());
+    }
+    let backend_secret =
+        backend_secret.ok_or("--backend-secret / JAZZ_BACKEND_SECRET is required to seed")?;
+
+    let client = JazzClient::connect(AppContext {
+        app_id,
+        client_id: None,
+        schema,
+        server_url: server_url.to_string(),
+        data_dir: std::env::temp_dir(),
+        storage: ClientStorage::Memory,
+        jwt_token: None,
+        backend_secret: Some(backend_secret),
+        admin_secret: None,
+        sync_tracer: None,
+    })
+    .await?;
+
+    let transaction = client.begin_transaction()?;
+    for row in &rows {
+        transaction.upsert(&row.table, *row.object_id.uuid(), row.values.clone())?;
+    }
+    let batch_id = transaction.commit()?;
+    client
+        .wait_for_batch(batch_id, DurabilityTier::EdgeServer)
+        .await?;
+    client.shutdown().await?;
+
+    println!("Seeded {server_url}");
+    print_summary(&rows);
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct ReleaseHead {
+    release: Option<ReleaseSchema>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseSchema {
+    schema_hash: SchemaHash,
+}
+
+#[derive(Deserialize)]
+struct KnownSchemas {
+    schemas: Vec<KnownSchema>,
+}
+
+#[derive(Deserialize)]
+struct KnownSchema {
+    hash: SchemaHash,
+}
+
+#[derive(Deserialize)]
+struct StoredSchema {
+    schema: Schema,
+}
+
+/// Schemas as a server's admin API reports them.
+struct ServerSchemas {
+    http: reqwest::Client,
+    app_url: String,
+    admin_secret: String,
+}
+
+impl ServerSchemas {
+    fn new(server_url: &str, app_id: AppId, admin_secret: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            app_url: format!("{}/apps/{app_id}", server_url.trim_end_matches('/')),
+            admin_secret,
+        }
+    }
+
+    /// The schema to validate fixtures against.
+    ///
+    /// A schema file must already be known to the server, so rows aren't
+    /// validated against columns the server has never seen. Without one, this
+    /// is the schema of the current release, or the server's only schema when
+    /// nothing has been released.
+    async fn resolve(&self, schema_path: Option<&str>) -> Result<Schema, String> {
+        if let Some(schema_path) = schema_path {
+            let schema = read_schema(schema_path)?;
+            let hash = SchemaHash::compute(&schema);
+            if self.schema(hash).await?.is_none() {
+                return Err(format!(
+                    "schema {} from '{schema_path}' is not published on the server; publish it before seeding",
+                    hash.short()
+                ));
+            }
+            return Ok(schema);
+        }
+
+        let hash = self.current_schema_hash().await?;
+        self.schema(hash)
+            .await?
+            .ok_or_else(|| format!("the server does not have schema {}", hash.short()))
+    }
+
+    async fn current_schema_hash(&self) -> Result<SchemaHash, String> {
+        let head: ReleaseHead = self
+            .get("admin/releases/head")
+            .await?
+            .ok_or_else(|| format!("nothing is served at {}; check the app id", self.app_url))?;
+        if let Some(release) = head.release {
+            return Ok(release.schema_hash);
+        }
+
+        let known: KnownSchemas = self
+            .get("schemas")
+            .await?
+            .ok_or_else(|| format!("nothing is served at {}; check the app id", self.app_url))?;
+        match known.schemas.as_slice() {
+            [only] => Ok(only.hash),
+            [] => Err("the server has no schema yet; publish one before seeding".to_string()),
+            schemas => Err(format!(
+                "the server has {} schemas and no release; pass --schema to pick one",
+                schemas.len()
+            )),
+        }
+    }
+
+    async fn schema(&self, hash: SchemaHash) -> Result<Option<Schema>, String> {
+        let stored: Option<StoredSchema> = self.get(&format!("schema/{hash}")).await?;
+        Ok(stored.map(|stored| stored.schema))
+    }
+
+    /// GET an app-scoped path, with `None` for a 404.
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>, String> {
+        let url = format!("{}/{path}", self.app_url);
+        let response = self
+            .http
+            .get(&url)
+            .header("X-Jazz-Admin-Secret", &self.admin_secret)
+            .send()
+            .await
+            .map_err(|err| format!("failed to reach {url}: {err}"))?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("GET {url} failed with {status}: {body}"));
+        }
+        response
+            .json()
+            .await
+            .map(Some)
+            .map_err(|err| format!("invalid response from {url}: {err}"))
+    }
+}
+
+fn read_file(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|err| format!("failed to read '{path}': {err}"))
+}
+
+fn read_schema(path: &str) -> Result<Schema, String> {
+    serde_json::from_str(&read_file(path)?).map_err(|err| format!("invalid schema '{path}': {err}"))
+}
+
+fn print_summary(rows: &[SeedRow]) {
+    let mut counts = BTreeMap::<&str, usize>::new();
+    for row in rows {
+        *counts.entry(row.table.as_str()).or_default() += 1;
+    }
+    println!("{:<24} {:>8}", "table", "rows");
+    for (table, count) in counts {
+        println!("{table:<24} {count:>8}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jazz_tools::query_manager::types::{ColumnType, SchemaBuilder, TableSchema};
+
+    const APP_ID: &str = "00000000-0000-0000-0000-000000000001";
+    /// Nothing listens here, so any request to it fails.
+    const UNREACHABLE_SERVER: &str = "http://127.0.0.1:9";
+
+    fn todo_schema() -> Schema {
+        SchemaBuilder::new()
+            .table(TableSchema::builder("users").column("name", ColumnType::Text))
+            .table(
+                TableSchema::builder("todos")
+                    .column("title", ColumnType::Text)
+                    .fk_column("owner", "users"),
+            )
+            .build()
+    }
+
+    fn write_file(dir: &tempfile::TempDir, name: &str, contents: &str) -> String {
+        let path = dir.path().join(name);
+        std::fs::write(&path, contents).expect("write test file");
+        path.to_string_lossy().into_owned()
+    }
+
+    fn todo_fixtures(dir: &tempfile::TempDir) -> Vec<String> {
+        vec![
+            write_file(
+                dir,
+                "users.json",
+                r#"{"users": [{"$id": "alice", "name": "Alice"}, {"$id": "bob", "name": "Bob"}]}"#,
+            ),
+            write_file(
+                dir,
+                "todos.ndjson",
+                r#"{"$table": "todos", "$id": "milk", "title": "Buy milk", "owner": {"$ref": "alice"}}"#,
+            ),
+        ]
+    }
+
+    #[tokio::test]
+    async fn dry_run_with_a_schema_file_validates_without_a_server() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let schema_path = write_file(
+            &dir,
+            "schema.json",
+            &serde_json::to_string(&todo_schema()).unwrap(),
+        );
+
+        run(
+            APP_ID,
+            UNREACHABLE_SERVER,
+            None,
+            None,
+            Some(&schema_path),
+            &todo_fixtures(&dir),
+            true,
+        )
+        .await
+        .expect("offline dry run should validate");
+
+        let bad_fixture = write_file(&dir, "bad.json", r#"{"users": [{"$id": "x", "name": 7}]}"#);
+        let error = run(
+            APP_ID,
+            UNREACHABLE_SERVER,
+            None,
+            None,
+            Some(&schema_path),
+            &[bad_fixture],
+            true,
+        )
+        .await
+        .expect_err("invalid fixture should fail the dry run");
+        assert!(error.to_string().contains("column 'name'"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn seeding_without_an_admin_secret_is_refused() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let schema_path = write_file(
+            &dir,
+            "schema.json",
+            &serde_json::to_string(&todo_schema()).unwrap(),
+        );
+        let fixtures = todo_fixtures(&dir);
+
+        for (schema_path, dry_run) in [(Some(schema_path.as_str()), false), (None, true)] {
+            let error = run(
+                APP_ID,
+                UNREACHABLE_SERVER,
+                Some("backend-secret".to_string()),
+                None,
+                schema_path,
+                &fixtures,
+                dry_run,
+            )
+            .await
+            .expect_err("the server's schema can't be read without an admin secret");
+            assert!(error.to_string().contains("--admin-secret"), "{error}");
+        }
+    }
+
+    #[cfg(feature = "test-utils")]
+    mod against_a_server {
+        use super::*;
+        use jazz_tools::server::JazzServer;
+        use jazz_tools::{QueryBuilder, Value};
+
+        async fn user_names(server: &JazzServer) -> Vec<String> {
+            let client = JazzClient::connect(AppContext {
+                app_id: server.app_id(),
+                client_id: None,
+                schema: todo_schema(),
+                server_url: server.base_url(),
+                data_dir: std::env::temp_dir(),
+                storage: ClientStorage::Memory,
+                jwt_token: None,
+                backend_secret: Some(server.backend_secret().to_string()),
+                admin_secret: None,
+                sync_tracer: None,
+            })
+            .await
+            .expect("connect reader");
+            let rows = client
+                .query(
+                    QueryBuilder::new("users").build(),
+                    Some(DurabilityTier::EdgeServer),
+                )
+                .await
+                .expect("query users");
+            client.shutdown().await.expect("shut down reader");
+            let mut names: Vec<String> = rows
+                .into_iter()
+                .map(|(_, values)| match &values[0] {
+                    Value::Text(name) => name.clone(),
+                    other => panic!("expected a user name, got {other:?}"),
+                })
+                .collect();
+            names.sort();
+            names
+        }
+
+        #[tokio::test]
+        async fn seeding_twice_updates_rows_instead_of_duplicating_them() {
+            let server = JazzServer::start_with_schema(todo_schema()).await;
+            let dir = tempfile::tempdir().expect("temp dir");
+            let seed = |fixtures: Vec<String>| {
+                let server_url = server.base_url();
+                let app_id = server.app_id().to_string();
+                let backend_secret = server.backend_secret().to_string();
+                let admin_secret = server.admin_secret().to_string();
+                async move {
+                    run(
+                        &app_id,
+                        &server_url,
+                        Some(backend_secret),
+                        Some(admin_secret),
+                        None,
+                        &fixtures,
+                        false,
+                    )
+                    .await
+                    .expect("seed against the server's schema");
+                }
+            };
+
+            seed(todo_fixtures(&dir)).await;
+            // Reordered, with Alice renamed: keys, not positions, pick the rows.
+            let reseeded = write_file(
+                &dir,
+                "users-again.json",
+                r#"{"users": [{"$id": "bob", "name": "Bob"}, {"$id": "alice", "name": "Alicia"}]}"#,
+            );
+            seed(vec![reseeded]).await;
+
+            assert_eq!(user_names(&server).await, vec!["Alicia", "Bob"]);
+            server.shutdown().await;
+        }
+
+        #[tokio::test]
+        async fn a_schema_file_the_server_does_not_know_is_refused() {
+            let server = JazzServer::start_with_schema(todo_schema()).await;
+            let dir = tempfile::tempdir().expect("temp dir");
+            let unpublished = SchemaBuilder::new()
+                .table(
+                    TableSchema::builder("users")
+                        .column("name", ColumnType::Text)
+                        .nullable_column("email", ColumnType::Text),
+                )
+                .build();
+            let schema_path = write_file(
+                &dir,
+                "schema.json",
+                &serde_json::to_string(&unpublished).unwrap(),
+            );
+            let fixture = write_file(
+                &dir,
+                "users.json",
+                r#"{"users": [{"$id": "alice", "name": "Alice", "email": "a@example.com"}]}"#,
+            );
+
+            let error = run(
+                &server.app_id().to_string(),
+                &server.base_url(),
+                Some(server.backend_secret().to_string()),
+                Some(server.admin_secret().to_string()),
+                Some(&schema_path),
+                &[fixture],
+                false,
+            )
+            .await
+            .expect_err("an unpublished schema should be refused");
+            assert!(
+                error.to_string().contains("is not published on the server"),
+                "{error}"
+            );
+            assert!(user_names(&server).await.is_empty());
+            server.shutdown().await;
+        }
+    }
+}
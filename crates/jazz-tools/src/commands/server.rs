@@ -5,13 +5,18 @@ use std::time::Duration;
 
 use jazz_tools::middleware::AuthConfig;
 use jazz_tools::schema_manager::AppId;
-use jazz_tools::server::{ServerBuilder, ShutdownController, ShutdownPhase, StorageBackend};
+use jazz_tools::server::{
+    RateLimitConfig, ServerBuilder, ShutdownController, ShutdownPhase, StorageBackend,
+    WebhookConfig,
+};
+use jazz_tools::sync_manager::timestamp_skew::TimestampSkewPolicy;
 use tokio::task::JoinHandle;
 use tracing::info;
 
 const STANDALONE_INSPECTOR_URL: &str = "https://jazz2-inspector.vercel.app/";
 
 /// Run the Jazz server.
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     app_id_str: &str,
     port: u16,
@@ -21,6 +26,9 @@ pub async fn run(
     upstream_url: Option<String>,
     bound_port_file: Option<String>,
     shutdown_timeout: Duration,
+    rate_limits: RateLimitConfig,
+    timestamp_skew_policy: Option<TimestampSkewPolicy>,
+    webhooks: WebhookConfig,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let app_id = AppId::from_string(app_id_str)?;
     let app_id_string = app_id.to_string();
@@ -35,11 +43,17 @@ pub async fn run(
 
     let builder = ServerBuilder::new(app_id)
         .with_auth_config(auth_config)
-        .with_shutdown_timeout(shutdown_timeout);
+        .with_shutdown_timeout(shutdown_timeout)
+        .with_rate_limits(rate_limits)
+        .with_webhooks(webhooks);
     let builder = match upstream_url {
         Some(upstream_url) => builder.with_upstream_url(upstream_url),
         None => builder,
     };
+    let builder = match timestamp_skew_policy {
+        Some(policy) => builder.with_timestamp_skew_policy(policy),
+        None => builder,
+    };
     let built = if in_memory {
         builder.with_storage(StorageBackend::InMemory).build().await
     } else {
@@ -14,6 +14,7 @@ pub mod row_format;
 pub mod row_histories;
 pub mod runtime_core;
 pub mod schema_manager;
+pub mod seed;
 #[cfg(any(feature = "cli", feature = "server"))]
 pub mod server;
 pub mod storage;
@@ -53,7 +54,9 @@ pub use client::{JazzClient, JazzTransaction};
 #[cfg(feature = "client")]
 pub use object::ObjectId;
 #[cfg(feature = "client")]
-pub use query_manager::query::{Query, QueryBuilder};
+pub use query_manager::graph::explain::QueryExplain;
+#[cfg(feature = "client")]
+pub use query_manager::query::{PageCursor, Query, QueryBuilder};
 #[cfg(feature = "client")]
 pub use query_manager::session::{Session, WriteContext};
 #[cfg(feature = "client")]
@@ -5,6 +5,8 @@
 //! ```text
 //! jazz-tools create app [--name <NAME>]    # Returns AppId (random or deterministic from name)
 //! jazz-tools server <APP_ID> [--port 1625] [--data-dir ./data] [--in-memory]
+//! jazz-tools migrate rehearse <APP_ID> --schema <FILE> --migration <FILE> [--data-dir ./data]
+//! jazz-tools seed <APP_ID> <FIXTURES>... [--schema <FILE>] [--server-url http://localhost:1625] [--dry-run]
 //! ```
 
 // mimalloc replaces the system allocator for ~12-26% throughput on the server's
@@ -20,6 +22,8 @@ use clap::{Parser, Subcommand};
 use jazz_tools::middleware::AuthConfig;
 #[cfg(feature = "otel")]
 use jazz_tools::otel;
+use jazz_tools::server::{RateLimitConfig, WebhookConfig};
+use jazz_tools::sync_manager::timestamp_skew::{TimestampSkewAction, TimestampSkewPolicy};
 
 const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
 const MAX_SHUTDOWN_TIMEOUT_SECS: u64 = 60 * 60;
@@ -67,6 +71,52 @@ fn resolve_jwt_public_key_input(value: String) -> Result<String, String> {
     Ok(trimmed.to_string())
 }
 
+fn resolve_rate_limits_input(value: &str) -> Result<RateLimitConfig, String> {
+    let trimmed = value.trim();
+    let json = if trimmed.starts_with('{') {
+        trimmed.to_string()
+    } else {
+        std::fs::read_to_string(trimmed)
+            .map_err(|error| format!("failed to read rate limits file '{trimmed}': {error}"))?
+    };
+
+    serde_json::from_str(&json).map_err(|error| format!("invalid rate limits config: {error}"))
+}
+
+fn resolve_webhooks_input(value: &str) -> Result<WebhookConfig, String> {
+    let trimmed = value.trim();
+    let json = if trimmed.starts_with('{') {
+        trimmed.to_string()
+    } else {
+        std::fs::read_to_string(trimmed)
+            .map_err(|error| format!("failed to read webhooks file '{trimmed}': {error}"))?
+    };
+
+    let config: WebhookConfig =
+        serde_json::from_str(&json).map_err(|error| format!("invalid webhooks config: {error}"))?;
+    config.validate()?;
+    Ok(config)
+}
+
+fn resolve_timestamp_skew_policy(
+    max_future_skew_secs: Option<u64>,
+    max_past_skew_secs: Option<u64>,
+    action: Option<TimestampSkewAction>,
+) -> Option<TimestampSkewPolicy> {
+    if max_future_skew_secs.is_none() && max_past_skew_secs.is_none() && action.is_none() {
+        return None;
+    }
+
+    let defaults = TimestampSkewPolicy::default();
+    Some(TimestampSkewPolicy {
+        max_future_skew: max_future_skew_secs
+            .map(std::time::Duration::from_secs)
+            .unwrap_or(defaults.max_future_skew),
+        max_past_skew: max_past_skew_secs.map(std::time::Duration::from_secs),
+        action: action.unwrap_or(defaults.action),
+    })
+}
+
 fn parse_shutdown_timeout_secs(value: &str) -> Result<u64, String> {
     let seconds = value
         .parse::<u64>()
@@ -155,10 +205,102 @@ enum Commands {
         )]
         shutdown_timeout_secs: u64,
 
+        /// Token-bucket limits on inbound client sync traffic, as inline JSON
+        /// or a path to a JSON file.
+        ///
+        /// Example: `{"per_principal":{"rows":{"capacity":500,"refill_per_sec":50}}}`
+        #[arg(long, env = "JAZZ_RATE_LIMITS", value_parser = resolve_rate_limits_input)]
+        rate_limits: Option<RateLimitConfig>,
+
+        /// Maximum seconds a client row timestamp may run ahead of the server
+        /// clock. Setting any clock-skew option enables the skew policy.
+        #[arg(long, env = "JAZZ_MAX_FUTURE_CLOCK_SKEW_SECS")]
+        max_future_clock_skew_secs: Option<u64>,
+
+        /// Maximum seconds a client row timestamp may lag the server clock.
+        /// Unbounded by default so long-offline clients can still sync.
+        #[arg(long, env = "JAZZ_MAX_PAST_CLOCK_SKEW_SECS")]
+        max_past_clock_skew_secs: Option<u64>,
+
+        /// What to do with out-of-range client timestamps: reject, clamp or restamp.
+        #[arg(long, env = "JAZZ_CLOCK_SKEW_ACTION")]
+        clock_skew_action: Option<TimestampSkewAction>,
+
+        /// HTTP webhooks receiving batches settled on this core server, as
+        /// inline JSON or a path to a JSON file.
+        ///
+        /// Example: `{"endpoints":[{"name":"crm","url":"https://example.com/hook","secret":"s","tables":["todos"]}]}`
+        #[arg(long, env = "JAZZ_WEBHOOKS", value_parser = resolve_webhooks_input)]
+        webhooks: Option<WebhookConfig>,
+
         /// Internal testing hook: write the resolved listen port after binding.
         #[arg(long, env = "JAZZ_BOUND_PORT_FILE", hide = true)]
         bound_port_file: Option<String>,
     },
+    /// Work with schema migrations
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+    /// Load JSON or NDJSON fixtures into a development server
+    Seed {
+        /// Application ID (from `jazz-tools create app`)
+        app_id: String,
+
+        /// Fixture files: JSON keyed by table, or NDJSON (`.ndjson` / `.jsonl`)
+        /// with a `$table` on every row
+        #[arg(required = true)]
+        fixtures: Vec<String>,
+
+        /// Schema to validate fixtures against, in the form `POST /admin/schemas` takes.
+        /// The server must know it. Defaults to the schema of the server's current
+        /// release.
+        #[arg(long)]
+        schema: Option<String>,
+
+        /// Server to seed
+        #[arg(long, default_value = "http://localhost:1625")]
+        server_url: String,
+
+        /// Backend secret of the server
+        #[arg(long, env = "JAZZ_BACKEND_SECRET")]
+        backend_secret: Option<String>,
+
+        /// Admin secret of the server, to read its schemas
+        #[arg(long, env = "JAZZ_ADMIN_SECRET")]
+        admin_secret: Option<String>,
+
+        /// Validate the fixtures without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// Dry-run a reviewed migration against a copy of a server's store and
+    /// report what it would do to each table
+    Rehearse {
+        /// Application ID (from `jazz-tools create app`)
+        app_id: String,
+
+        /// Data directory of the server whose store to rehearse against.
+        /// A RocksDB store must not be open in a running server.
+        #[arg(short, long, default_value = "./data")]
+        data_dir: String,
+
+        /// Candidate schema as JSON, in the form `POST /admin/schemas` takes
+        #[arg(long)]
+        schema: String,
+
+        /// Reviewed migration as JSON, in the form `migrations push` sends
+        #[arg(long)]
+        migration: String,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -189,6 +331,49 @@ async fn main() {
                 commands::create::app(name);
             }
         },
+        Commands::Migrate { action } => match action {
+            MigrateAction::Rehearse {
+                app_id,
+                data_dir,
+                schema,
+                migration,
+                json,
+            } => {
+                if let Err(e) =
+                    commands::migrate::rehearse(&app_id, &data_dir, &schema, &migration, json)
+                {
+                    eprintln!("Rehearsal error: {}", e);
+                    shutdown_tracing();
+                    std::process::exit(1);
+                }
+            }
+        },
+        Commands::Seed {
+            app_id,
+            fixtures,
+            schema,
+            server_url,
+            backend_secret,
+            admin_secret,
+            dry_run,
+        } => {
+            if let Err(e) = commands::seed::run(
+                &app_id,
+                &server_url,
+                backend_secret,
+                admin_secret,
+                schema.as_deref(),
+                &fixtures,
+                dry_run,
+            )
+            .await
+            {
+                eprintln!("Seed error: {}", e);
+                shutdown_tracing();
+                std::process::exit(1);
+            }
+            shutdown_tracing();
+        }
         Commands::Server {
             app_id,
             port,
@@ -202,6 +387,11 @@ async fn main() {
             admin_secret,
             upstream_url,
             shutdown_timeout_secs,
+            rate_limits,
+            max_future_clock_skew_secs,
+            max_past_clock_skew_secs,
+            clock_skew_action,
+            webhooks,
             bound_port_file,
         } => {
             let node_env_mode = resolve_node_env_mode();
@@ -247,6 +437,13 @@ async fn main() {
                 upstream_url,
                 bound_port_file,
                 std::time::Duration::from_secs(shutdown_timeout_secs),
+                rate_limits.unwrap_or_default(),
+                resolve_timestamp_skew_policy(
+                    max_future_clock_skew_secs,
+                    max_past_clock_skew_secs,
+                    clock_skew_action,
+                ),
+                webhooks.unwrap_or_default(),
             )
             .await
             {
@@ -508,6 +705,183 @@ mod tests {
         assert!(error.contains("--upstream-url"));
     }
 
+    #[test]
+    fn server_command_parses_inline_rate_limits() {
+        let _lock = ENV_LOCK.lock().expect("env lock");
+        let cli = Cli::try_parse_from([
+            "jazz-tools",
+            "server",
+            "test-app",
+            "--rate-limits",
+            r#"{"per_client":{"rows":{"capacity":10,"refill_per_sec":2}}}"#,
+        ])
+        .expect("server command should parse");
+
+        match cli.command {
+            Commands::Server { rate_limits, .. } => {
+                let rate_limits = rate_limits.expect("rate limits should be set");
+                assert_eq!(
+                    rate_limits.per_client.rows,
+                    Some(jazz_tools::server::TokenBucketConfig::new(10, 2))
+                );
+                assert_eq!(rate_limits.per_app.rows, None);
+            }
+            _ => panic!("expected server command"),
+        }
+    }
+
+    #[test]
+    fn server_command_parses_inline_webhooks() {
+        let _lock = ENV_LOCK.lock().expect("env lock");
+        let cli = Cli::try_parse_from([
+            "jazz-tools",
+            "server",
+            "test-app",
+            "--webhooks",
+            r#"{"endpoints":[{"name":"crm","url":"https://example.com/hook","secret":"s","tables":["todos"]}],"poll_interval_ms":250}"#,
+        ])
+        .expect("server command should parse");
+
+        match cli.command {
+            Commands::Server { webhooks, .. } => {
+                let webhooks = webhooks.expect("webhooks should be set");
+                assert_eq!(webhooks.endpoints.len(), 1);
+                assert_eq!(webhooks.endpoints[0].name, "crm");
+                assert_eq!(webhooks.endpoints[0].tables, vec!["todos".to_string()]);
+                assert_eq!(webhooks.poll_interval_ms, 250);
+                assert_eq!(webhooks.max_events_per_request, 500);
+            }
+            _ => panic!("expected server command"),
+        }
+    }
+
+    #[test]
+    fn server_command_rejects_webhook_without_secret() {
+        let _lock = ENV_LOCK.lock().expect("env lock");
+        let result = Cli::try_parse_from([
+            "jazz-tools",
+            "server",
+            "test-app",
+            "--webhooks",
+            r#"{"endpoints":[{"name":"crm","url":"https://example.com/hook","secret":""}]}"#,
+        ]);
+
+        assert!(
+            result.is_err(),
+            "a webhook without a secret should be rejected"
+        );
+    }
+
+    #[test]
+    fn server_command_parses_clock_skew_policy() {
+        let _lock = ENV_LOCK.lock().expect("env lock");
+        let cli = Cli::try_parse_from([
+            "jazz-tools",
+            "server",
+            "test-app",
+            "--max-future-clock-skew-secs",
+            "60",
+            "--clock-skew-action",
+            "restamp",
+        ])
+        .expect("server command should parse");
+
+        match cli.command {
+            Commands::Server {
+                max_future_clock_skew_secs,
+                max_past_clock_skew_secs,
+                clock_skew_action,
+                ..
+            } => {
+                let policy = resolve_timestamp_skew_policy(
+                    max_future_clock_skew_secs,
+                    max_past_clock_skew_secs,
+                    clock_skew_action,
+                )
+                .expect("skew policy should be enabled");
+                assert_eq!(policy.max_future_skew, std::time::Duration::from_secs(60));
+                assert_eq!(policy.max_past_skew, None);
+                assert_eq!(policy.action, TimestampSkewAction::Restamp);
+            }
+            _ => panic!("expected server command"),
+        }
+    }
+
+    #[test]
+    fn migrate_rehearse_command_parses_files_and_data_dir() {
+        let _lock = ENV_LOCK.lock().expect("env lock");
+        let cli = Cli::try_parse_from([
+            "jazz-tools",
+            "migrate",
+            "rehearse",
+            "test-app",
+            "--data-dir",
+            "./snapshot",
+            "--schema",
+            "schema.json",
+            "--migration",
+            "migration.json",
+        ])
+        .expect("migrate rehearse command should parse");
+
+        match cli.command {
+            Commands::Migrate {
+                action:
+                    MigrateAction::Rehearse {
+                        data_dir,
+                        schema,
+                        migration,
+                        json,
+                        ..
+                    },
+            } => {
+                assert_eq!(data_dir, "./snapshot");
+                assert_eq!(schema, "schema.json");
+                assert_eq!(migration, "migration.json");
+                assert!(!json);
+            }
+            _ => panic!("expected migrate rehearse command"),
+        }
+    }
+
+    #[test]
+    fn seed_command_parses_fixtures_and_schema() {
+        let _lock = ENV_LOCK.lock().expect("env lock");
+        let _env_guard = EnvVarGuard::remove("JAZZ_BACKEND_SECRET");
+        let _admin_env_guard = EnvVarGuard::remove("JAZZ_ADMIN_SECRET");
+        let cli = Cli::try_parse_from([
+            "jazz-tools",
+            "seed",
+            "test-app",
+            "users.json",
+            "todos.ndjson",
+            "--schema",
+            "schema.json",
+            "--dry-run",
+        ])
+        .expect("seed command should parse");
+
+        match cli.command {
+            Commands::Seed {
+                fixtures,
+                schema,
+                server_url,
+                backend_secret,
+                admin_secret,
+                dry_run,
+                ..
+            } => {
+                assert_eq!(fixtures, vec!["users.json", "todos.ndjson"]);
+                assert_eq!(schema.as_deref(), Some("schema.json"));
+                assert_eq!(server_url, "http://localhost:1625");
+                assert_eq!(backend_secret, None);
+                assert_eq!(admin_secret, None);
+                assert!(dry_run);
+            }
+            _ => panic!("expected seed command"),
+        }
+    }
+
     #[test]
     fn dev_defaults_enable_local_first_auth() {
         assert!(resolve_dev_default_flag(
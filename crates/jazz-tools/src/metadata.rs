@@ -29,8 +29,29 @@ pub enum MetadataKey {
     SourceHash,
     /// Target schema hash on catalogue lens objects.
     TargetHash,
+    /// Revision of a catalogue lens object; absent means revision 0.
+    LensRevision,
+    /// Why a catalogue lens revision superseded the previous one.
+    LensRevisionReason,
+    /// Marks a catalogue schema or lens as retired by schema GC.
+    Retired,
+    /// Full schema hash on the client projection of a schema with internal items.
+    ProjectionOf,
+    /// Release object id on catalogue schemas, lenses and permissions
+    /// published as part of a release.
+    Release,
     /// Flag to suppress sync for an object.
     NoSync,
+    /// Client-supplied `updated_at` of a row batch whose timestamp the server adjusted.
+    ClientUpdatedAt,
+    /// Client-supplied `created_at` of a row batch whose timestamp the server adjusted.
+    ClientCreatedAt,
+    /// How the server adjusted a row batch's timestamps — clamp or restamp.
+    TimestampAdjustment,
+    /// Hybrid logical clock timestamp of a row batch, as stored and synced.
+    Hlc,
+    /// Client-supplied HLC of a row batch whose timestamp the server adjusted.
+    ClientHlc,
 }
 
 impl MetadataKey {
@@ -46,7 +67,17 @@ impl MetadataKey {
             Self::SchemaHash => "schema_hash",
             Self::SourceHash => "source_hash",
             Self::TargetHash => "target_hash",
+            Self::LensRevision => "lens_revision",
+            Self::LensRevisionReason => "lens_revision_reason",
+            Self::Retired => "retired",
+            Self::ProjectionOf => "projection_of",
+            Self::Release => "release",
             Self::NoSync => "nosync",
+            Self::ClientUpdatedAt => "client_updated_at",
+            Self::ClientCreatedAt => "client_created_at",
+            Self::TimestampAdjustment => "timestamp_adjustment",
+            Self::Hlc => "hlc",
+            Self::ClientHlc => "client_hlc",
         }
     }
 }
@@ -66,6 +97,8 @@ pub enum ObjectType {
     CataloguePermissionsHead,
     /// Legacy single-object permissions catalogue entry.
     CataloguePermissions,
+    /// Schema, lenses and permissions that activate together.
+    CatalogueRelease,
     Index,
 }
 
@@ -77,6 +110,7 @@ impl ObjectType {
             Self::CataloguePermissionsBundle => "catalogue_permissions_bundle",
             Self::CataloguePermissionsHead => "catalogue_permissions_head",
             Self::CataloguePermissions => "catalogue_permissions",
+            Self::CatalogueRelease => "catalogue_release",
             Self::Index => "index",
         }
     }
@@ -89,6 +123,7 @@ impl ObjectType {
                 | "catalogue_permissions_bundle"
                 | "catalogue_permissions_head"
                 | "catalogue_permissions"
+                | "catalogue_release"
         )
     }
 }
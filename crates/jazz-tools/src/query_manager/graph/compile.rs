@@ -3,8 +3,9 @@ use std::{cmp::Ordering, ops::Bound};
 
 use crate::object::{BranchName, ObjectId};
 use crate::query_manager::types::{
-    ColumnDescriptor, ColumnName, ColumnType, ComposedBranchName, RowDescriptor, RowPolicyMode,
-    Schema, SchemaHash, TableName, TupleDescriptor, Value,
+    ColumnDescriptor, ColumnName, ColumnType, ComposedBranchName, Decimal, GeoPoint, RowDescriptor,
+    RowPolicyMode, Schema, SchemaHash, TableName, TupleDescriptor, Value, Vector,
+    parse_temporal_literal,
 };
 use crate::schema_manager::{
     SchemaContext, translate_column_for_index, translate_table_name_to_schema,
@@ -17,6 +18,7 @@ use super::super::graph_nodes::join::{JoinColumnRef, JoinNode};
 use super::super::graph_nodes::limit_offset::LimitOffsetNode;
 use super::super::graph_nodes::magic_columns::{MagicColumnRequest, MagicColumnsNode};
 use super::super::graph_nodes::materialize::MaterializeNode;
+use super::super::graph_nodes::nearest::NearestNode;
 use super::super::graph_nodes::output::{OutputMode, OutputNode};
 use super::super::graph_nodes::policy_filter::PolicyFilterNode;
 use super::super::graph_nodes::project::ProjectNode;
@@ -29,11 +31,15 @@ use super::super::graph_nodes::subgraph::SubgraphTemplate;
 use super::super::graph_nodes::union::UnionNode;
 use super::super::graph_nodes::{NodeId, RowNode};
 use super::super::index::ScanCondition;
+use super::super::json_path::{self, JsonPath};
 use super::super::magic_columns::{MagicColumnKind, magic_column_descriptor, magic_column_kind};
 use super::super::policy::PolicyExpr;
-use super::super::query::{ArraySubquerySpec, Condition, Conjunction, Query, QueryBuilder};
-use super::super::relation_ir::{ProjectColumn, ProjectExpr, RelExpr};
+use super::super::query::{
+    ArraySubquerySpec, Condition, Conjunction, ExistsSubquerySpec, Query, QueryBuilder,
+};
+use super::super::relation_ir::{JoinKind, ProjectColumn, ProjectExpr, RelExpr, ScalarExpr};
 use super::super::relation_ir_query_plan::{ExecutionQueryPlan, lower_relation_to_execution_plan};
+use super::super::scalar_expr::{BoundCondition, BoundScalarExpr};
 use super::super::session::Session;
 use uuid::Uuid;
 
@@ -147,6 +153,13 @@ fn collect_magic_refs_from_disjuncts(
                 push_unique_magic_ref(&mut refs, condition.column_scope(), kind);
             }
         }
+        for comparison in &disjunct.comparisons {
+            comparison.visit_column_refs(&mut |column_ref| {
+                if let Some(kind) = magic_column_kind(&column_ref.column) {
+                    push_unique_magic_ref(&mut refs, column_ref.scope.as_deref(), kind);
+                }
+            });
+        }
     }
     refs
 }
@@ -160,13 +173,19 @@ fn collect_magic_refs_from_project_columns(
     };
 
     for column in columns {
-        let ProjectExpr::Column(column_ref) = &column.expr else {
-            continue;
-        };
-        let Some(kind) = magic_column_kind(&column_ref.column) else {
-            continue;
-        };
-        push_unique_magic_ref(&mut refs, column_ref.scope.as_deref(), kind);
+        match &column.expr {
+            ProjectExpr::Column(column_ref) => {
+                if let Some(kind) = magic_column_kind(&column_ref.column) {
+                    push_unique_magic_ref(&mut refs, column_ref.scope.as_deref(), kind);
+                }
+            }
+            ProjectExpr::Expr(expr) => expr.visit_column_refs(&mut |column_ref| {
+                if let Some(kind) = magic_column_kind(&column_ref.column) {
+                    push_unique_magic_ref(&mut refs, column_ref.scope.as_deref(), kind);
+                }
+            }),
+            ProjectExpr::RowId(_) => {}
+        }
     }
 
     refs
@@ -294,6 +313,7 @@ impl QueryGraph {
             });
         }
         self.dirty_bitmap.extend(other.dirty_bitmap);
+        self.node_counters.extend(other.node_counters);
         self.index_scan_nodes.extend(
             other
                 .index_scan_nodes
@@ -622,18 +642,20 @@ impl QueryGraph {
 
                 let scan_column_name = ColumnName::new(&translated_column);
 
-                let scan_node = IndexScanNode::new_with_branch(
-                    scan_table_name,
-                    scan_column_name,
-                    branch,
-                    scan_plan.condition.clone(),
-                    descriptor.clone(),
-                );
-                let scan_id = graph.add_node(GraphNode::IndexScan(scan_node));
-                graph
-                    .index_scan_nodes
-                    .push((scan_id, scan_table_name, scan_column_name));
-                phase1_outputs.push(scan_id);
+                for scan_condition in &scan_plan.conditions {
+                    let scan_node = IndexScanNode::new_with_branch(
+                        scan_table_name,
+                        scan_column_name,
+                        branch,
+                        scan_condition.clone(),
+                        descriptor.clone(),
+                    );
+                    let scan_id = graph.add_node(GraphNode::IndexScan(scan_node));
+                    graph
+                        .index_scan_nodes
+                        .push((scan_id, scan_table_name, scan_column_name));
+                    phase1_outputs.push(scan_id);
+                }
             }
 
             // If include_deleted is set, also scan _id_deleted index for this branch
@@ -750,6 +772,21 @@ impl QueryGraph {
             }
         }
 
+        // EXISTS / NOT EXISTS subqueries: semi/anti joins keep the outer tuple shape.
+        for exists_spec in &plan.exists_joins {
+            phase2_input = graph.compile_exists_join(
+                exists_spec,
+                phase2_input,
+                &current_tuple_descriptor,
+                schema,
+                &branches,
+                &branch_schema_map,
+                schema_context,
+                session.as_ref(),
+                row_policy_mode,
+            )?;
+        }
+
         let filter_magic_refs = collect_magic_refs_from_disjuncts(&plan.disjuncts);
         let order_magic_refs = collect_magic_refs_from_order_by(&plan.order_by);
         let project_magic_refs =
@@ -819,27 +856,49 @@ impl QueryGraph {
             phase2_input = filter_id;
         }
 
-        // Sort node (default: id ASC when order_by is omitted)
-        let sort_keys = sort_keys_from_order_by(&plan.order_by, &current_descriptor);
-        if !sort_keys.is_empty() {
-            let sort_node =
-                SortNode::with_tuple_descriptor(current_tuple_descriptor.clone(), sort_keys);
-            let sort_id = graph.add_node(GraphNode::Sort(sort_node));
-            graph.add_edge(sort_id, phase2_input);
-            phase2_input = sort_id;
-        }
-
-        // LimitOffset node (if limit or offset specified)
-        if plan.limit.is_some() || plan.offset > 0 {
-            let limit_offset_node = LimitOffsetNode::with_tuple_descriptor(
+        if let Some((col_index, target, k)) = nearest_plan(&plan, &current_descriptor) {
+            // Nearest node ranks through HNSW in place of Sort + LimitOffset.
+            let nearest_node = NearestNode::with_tuple_descriptor(
                 current_tuple_descriptor.clone(),
-                plan.limit,
-                plan.offset,
+                col_index,
+                target,
+                k,
             );
-            let limit_offset_id = graph.add_node(GraphNode::LimitOffset(limit_offset_node));
-            graph.add_edge(limit_offset_id, phase2_input);
-            graph.pagination_node = Some(limit_offset_id);
-            phase2_input = limit_offset_id;
+            let nearest_id = graph.add_node(GraphNode::Nearest(nearest_node));
+            graph.add_edge(nearest_id, phase2_input);
+            phase2_input = nearest_id;
+        } else {
+            // Sort node (default: id ASC when order_by is omitted)
+            let (sort_keys, computed_keys) = sort_keys_from_order_by(
+                &plan.order_by,
+                &plan.computed_order_by,
+                &current_descriptor,
+                &current_tuple_descriptor,
+            )?;
+            if !sort_keys.is_empty() {
+                let sort_node = SortNode::with_computed_keys(
+                    current_tuple_descriptor.clone(),
+                    sort_keys,
+                    computed_keys,
+                );
+                let sort_id = graph.add_node(GraphNode::Sort(sort_node));
+                graph.add_edge(sort_id, phase2_input);
+                phase2_input = sort_id;
+            }
+
+            // LimitOffset node (if limit or offset specified)
+            if plan.limit.is_some() || plan.offset > 0 {
+                let limit_offset_node = LimitOffsetNode::with_tuple_descriptor(
+                    current_tuple_descriptor.clone(),
+                    plan.limit,
+                    plan.offset,
+                )
+                .with_window_from_end(plan.window_from_end);
+                let limit_offset_id = graph.add_node(GraphNode::LimitOffset(limit_offset_node));
+                graph.add_edge(limit_offset_id, phase2_input);
+                graph.pagination_node = Some(limit_offset_id);
+                phase2_input = limit_offset_id;
+            }
         }
 
         if !needs_magic_before_filter && !project_magic_refs.is_empty() {
@@ -973,7 +1032,7 @@ impl QueryGraph {
         };
         ensure_relation_tables_exist(&query.relation_ir, schema)?;
 
-        let plan = lower_relation_to_execution_plan(
+        let mut plan = lower_relation_to_execution_plan(
             &query.relation_ir,
             &branches,
             query.include_deleted,
@@ -985,6 +1044,7 @@ impl QueryGraph {
                 "unsupported relation_ir shape for schema-context query compilation".to_string(),
             )
         })?;
+        plan.window_from_end = query.pages_backward();
 
         validate_execution_plan(&plan, schema)?;
 
@@ -1126,6 +1186,139 @@ impl QueryGraph {
         Some((node, output_descriptor))
     }
 
+    /// Compile an EXISTS / NOT EXISTS subquery into a semi or anti JoinNode
+    /// fed by `left_id`. The join emits left tuples unchanged, so downstream
+    /// nodes keep using `left_tuple_desc`.
+    #[allow(clippy::too_many_arguments)]
+    fn compile_exists_join(
+        &mut self,
+        spec: &ExistsSubquerySpec,
+        left_id: NodeId,
+        left_tuple_desc: &TupleDescriptor,
+        schema: &Schema,
+        branches: &[String],
+        branch_schema_map: &HashMap<String, SchemaHash>,
+        schema_context: &SchemaContext,
+        session: Option<&Session>,
+        row_policy_mode: RowPolicyMode,
+    ) -> Option<NodeId> {
+        let inner_table_schema = schema.get(&spec.table)?;
+        let inner_descriptor = inner_table_schema.columns.clone();
+        let inner_branches: Vec<&str> = if branches.is_empty() {
+            vec!["main"]
+        } else {
+            branches.iter().map(String::as_str).collect()
+        };
+
+        // Build pipeline for inner table: per-branch IndexScan (+Union) -> Materialize.
+        let mut inner_scan_ids = Vec::new();
+        for branch in &inner_branches {
+            let branch_schema_hash = branch_schema_map
+                .get(*branch)
+                .copied()
+                .or_else(|| resolve_branch_schema_hash(schema_context, branch));
+            let Some(inner_scan_table) =
+                translate_scan_table_name(schema_context, spec.table.as_str(), branch_schema_hash)
+            else {
+                continue;
+            };
+            let id_column = ColumnName::new("_id");
+            let inner_scan = IndexScanNode::new_with_branch(
+                inner_scan_table,
+                id_column,
+                *branch,
+                ScanCondition::All,
+                inner_descriptor.clone(),
+            );
+            let inner_scan_id = self.add_node(GraphNode::IndexScan(inner_scan));
+            self.index_scan_nodes
+                .push((inner_scan_id, inner_scan_table, id_column));
+            inner_scan_ids.push(inner_scan_id);
+        }
+        let inner_scan_output = if inner_scan_ids.len() > 1 {
+            let union_id = self.add_node(GraphNode::Union(UnionNode::new()));
+            for scan_id in inner_scan_ids {
+                self.add_edge(union_id, scan_id);
+            }
+            union_id
+        } else {
+            *inner_scan_ids.first()?
+        };
+
+        let inner_tuple_desc = TupleDescriptor::single_with_materialization(
+            spec.effective_name(),
+            inner_descriptor.clone(),
+            true,
+        );
+        let inner_mat = MaterializeNode::new_all(inner_tuple_desc.clone());
+        let inner_mat_id = self.add_node(GraphNode::Materialize(inner_mat));
+        self.add_edge(inner_mat_id, inner_scan_output);
+        let mut inner_input_id = inner_mat_id;
+
+        // Inner rows the session cannot read must not decide whether an outer row matches.
+        if let (Some(session), Some(policy)) = (
+            session,
+            effective_select_policy(inner_table_schema, row_policy_mode),
+        ) {
+            let branch_for_policy = branches
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "main".to_string());
+            let policy_node = PolicyFilterNode::new_with_branch_and_policy_mode(
+                inner_descriptor.clone(),
+                policy,
+                session.clone(),
+                schema.clone(),
+                spec.table.as_str(),
+                branch_for_policy,
+                row_policy_mode,
+            );
+            let inherits_tables: Vec<TableName> = policy_node
+                .inherits_tables()
+                .iter()
+                .map(TableName::new)
+                .collect();
+            let policy_id = self.add_node(GraphNode::PolicyFilter(policy_node));
+            self.add_edge(policy_id, inner_input_id);
+            for inherits_table in inherits_tables {
+                self.policy_filter_tables.push((policy_id, inherits_table));
+            }
+            inner_input_id = policy_id;
+        }
+
+        if !spec.filters.is_empty() {
+            let predicate = Conjunction {
+                conditions: spec.filters.clone(),
+                comparisons: Vec::new(),
+            }
+            .to_tuple_predicate(&inner_tuple_desc);
+            let filter_node =
+                FilterNode::with_tuple_descriptor(inner_tuple_desc.clone(), predicate);
+            let filter_id = self.add_node(GraphNode::Filter(filter_node));
+            self.add_edge(filter_id, inner_input_id);
+            inner_input_id = filter_id;
+        }
+
+        let kind = if spec.negated {
+            JoinKind::Anti
+        } else {
+            JoinKind::Semi
+        };
+        let join_node = JoinNode::new_with_refs(
+            left_tuple_desc.clone(),
+            inner_tuple_desc,
+            JoinColumnRef::parse(&spec.outer_column),
+            JoinColumnRef::parse(&spec.inner_column),
+        )?
+        .with_kind(kind);
+        let join_id = self.add_node(GraphNode::Join(join_node));
+
+        // Same edge convention as regular joins: first edge is left, second is right.
+        self.add_edge(join_id, left_id);
+        self.add_edge(join_id, inner_input_id);
+        Some(join_id)
+    }
+
     /// Recursively build the element descriptor for a nested array subquery.
     fn build_nested_array_descriptor(
         spec: &crate::query_manager::query::ArraySubquerySpec,
@@ -1600,6 +1793,20 @@ impl QueryGraph {
 
         let mut phase2_input = left_id;
 
+        for exists_spec in &plan.exists_joins {
+            phase2_input = graph.compile_exists_join(
+                exists_spec,
+                phase2_input,
+                &tuple_descriptor,
+                schema,
+                branches,
+                &branch_schema_map,
+                schema_context,
+                session.as_ref(),
+                row_policy_mode,
+            )?;
+        }
+
         let filter_magic_refs = collect_magic_refs_from_disjuncts(&plan.disjuncts);
         let order_magic_refs = collect_magic_refs_from_order_by(&plan.order_by);
         let project_magic_refs =
@@ -1667,10 +1874,18 @@ impl QueryGraph {
         }
 
         // Sort node (default: id ASC when order_by is omitted)
-        let sort_keys = sort_keys_from_order_by(&plan.order_by, &output_descriptor);
+        let (sort_keys, computed_keys) = sort_keys_from_order_by(
+            &plan.order_by,
+            &plan.computed_order_by,
+            &output_descriptor,
+            &output_tuple_descriptor,
+        )?;
         if !sort_keys.is_empty() {
-            let sort_node =
-                SortNode::with_tuple_descriptor(output_tuple_descriptor.clone(), sort_keys);
+            let sort_node = SortNode::with_computed_keys(
+                output_tuple_descriptor.clone(),
+                sort_keys,
+                computed_keys,
+            );
             let sort_id = graph.add_node(GraphNode::Sort(sort_node));
             graph.add_edge(sort_id, phase2_input);
             phase2_input = sort_id;
@@ -1682,7 +1897,8 @@ impl QueryGraph {
                 output_tuple_descriptor.clone(),
                 plan.limit,
                 plan.offset,
-            );
+            )
+            .with_window_from_end(plan.window_from_end);
             let limit_offset_id = graph.add_node(GraphNode::LimitOffset(limit_offset_node));
             graph.add_edge(limit_offset_id, phase2_input);
             graph.pagination_node = Some(limit_offset_id);
@@ -1840,6 +2056,23 @@ fn validate_condition_for_descriptor(
     descriptor: &RowDescriptor,
     condition: &Condition,
 ) -> Result<(), QueryCompileError> {
+    if let Some(path) = condition.json_path() {
+        if condition.geo_region().is_some() {
+            return Err(QueryCompileError::InvalidPlan(format!(
+                "JSON path '{path}' cannot be used in a geo filter"
+            )));
+        }
+        return match descriptor.column(path.column()) {
+            Some(column) if !matches!(column.column_type, ColumnType::Json { .. }) => {
+                Err(QueryCompileError::InvalidPlan(format!(
+                    "JSON path '{path}' requires '{}' to be a json column",
+                    path.column()
+                )))
+            }
+            _ => Ok(()),
+        };
+    }
+
     let column_name = unqualify_column_name(condition.column());
     let Some(column) = descriptor.column(column_name) else {
         return Ok(());
@@ -1862,9 +2095,66 @@ fn validate_condition_for_descriptor(
         )));
     }
 
+    let is_geo = matches!(column.column_type, ColumnType::GeoPoint);
+    if is_geo && (is_ordering_cmp || matches!(condition, Condition::Contains { .. })) {
+        return Err(QueryCompileError::InvalidPlan(format!(
+            "geo column '{column_name}' only supports '=', '!=' and within filters"
+        )));
+    }
+    if !is_geo && condition.geo_region().is_some() {
+        return Err(QueryCompileError::InvalidPlan(format!(
+            "within filters require '{column_name}' to be a geo point column"
+        )));
+    }
+    if matches!(column.column_type, ColumnType::Vector { .. })
+        && !matches!(
+            condition,
+            Condition::IsNull { .. } | Condition::IsNotNull { .. }
+        )
+    {
+        return Err(QueryCompileError::InvalidPlan(format!(
+            "vector column '{column_name}' only supports null checks and nearest queries"
+        )));
+    }
+
+    for literal in condition_literals(condition) {
+        if let Value::Text(raw) = literal
+            && let Some(Err(error)) = parse_temporal_literal(raw, &column.column_type)
+        {
+            return Err(QueryCompileError::InvalidPlan(format!(
+                "{error} for column '{column_name}'"
+            )));
+        }
+        if is_geo
+            && let Value::Text(raw) = literal
+            && let Err(error) = GeoPoint::parse(raw)
+        {
+            return Err(QueryCompileError::InvalidPlan(format!(
+                "{error} for column '{column_name}'"
+            )));
+        }
+    }
+
     Ok(())
 }
 
+fn condition_literals(condition: &Condition) -> Vec<&Value> {
+    match condition {
+        Condition::Eq { value, .. }
+        | Condition::Ne { value, .. }
+        | Condition::Lt { value, .. }
+        | Condition::Le { value, .. }
+        | Condition::Gt { value, .. }
+        | Condition::Ge { value, .. }
+        | Condition::Contains { value, .. } => vec![value],
+        Condition::Between { min, max, .. } => vec![min, max],
+        Condition::IsNull { .. }
+        | Condition::IsNotNull { .. }
+        | Condition::WithinBox { .. }
+        | Condition::WithinRadius { .. } => Vec::new(),
+    }
+}
+
 fn validate_disjuncts_for_descriptor(
     disjuncts: &[Conjunction],
     descriptor: &RowDescriptor,
@@ -1883,14 +2173,19 @@ fn validate_order_by_for_descriptor(
 ) -> Result<(), QueryCompileError> {
     for (column, _direction) in order_by {
         let column_name = unqualify_column_name(column);
-        if descriptor
-            .column(column_name)
-            .is_some_and(|c| matches!(c.column_type, ColumnType::Bytea))
-        {
-            return Err(QueryCompileError::InvalidPlan(format!(
-                "bytea column '{}' cannot be used in ORDER BY",
-                column_name
-            )));
+        match descriptor.column(column_name).map(|c| &c.column_type) {
+            Some(ColumnType::Bytea) => {
+                return Err(QueryCompileError::InvalidPlan(format!(
+                    "bytea column '{}' cannot be used in ORDER BY",
+                    column_name
+                )));
+            }
+            Some(ColumnType::Vector { .. }) => {
+                return Err(QueryCompileError::InvalidPlan(format!(
+                    "vector column '{column_name}' cannot be used in ORDER BY; use nearest()"
+                )));
+            }
+            _ => {}
         }
     }
     Ok(())
@@ -1954,6 +2249,7 @@ fn validate_execution_plan(
     let descriptor = descriptor_for_execution_plan(plan, schema)?;
     validate_disjuncts_for_descriptor(&plan.disjuncts, &descriptor)?;
     validate_order_by_for_descriptor(&plan.order_by, &descriptor)?;
+    validate_scalar_exprs_for_plan(plan, schema)?;
 
     if let Some(recursive) = &plan.recursive {
         let recursive_descriptor =
@@ -1967,9 +2263,90 @@ fn validate_execution_plan(
         validate_array_subquery_spec(subquery, schema)?;
     }
 
+    for exists in &plan.exists_joins {
+        let exists_descriptor = &schema
+            .get(&exists.table)
+            .ok_or(QueryCompileError::UnknownTable(exists.table))?
+            .columns;
+        for condition in &exists.filters {
+            validate_condition_for_descriptor(exists_descriptor, condition)?;
+        }
+    }
+
     Ok(())
 }
 
+/// Bind every computed expression in the plan so type errors surface at
+/// compile time rather than as NULLs at evaluation time. Expressions touching
+/// magic columns are skipped; those columns only exist once the graph appends them.
+fn validate_scalar_exprs_for_plan(
+    plan: &ExecutionQueryPlan,
+    schema: &Schema,
+) -> Result<(), QueryCompileError> {
+    let has_comparisons = plan.disjuncts.iter().any(|d| !d.comparisons.is_empty());
+    let has_project_exprs = plan.project_columns.as_ref().is_some_and(|columns| {
+        columns
+            .iter()
+            .any(|column| matches!(column.expr, ProjectExpr::Expr(_)))
+    });
+    if !has_comparisons && !has_project_exprs && plan.computed_order_by.is_empty() {
+        return Ok(());
+    }
+
+    let mut tables = vec![(
+        plan.base_scope.clone(),
+        schema
+            .get(&plan.table)
+            .ok_or(QueryCompileError::UnknownTable(plan.table))?
+            .columns
+            .clone(),
+    )];
+    for join in &plan.joins {
+        let joined = schema
+            .get(&join.table)
+            .ok_or(QueryCompileError::UnknownTable(join.table))?
+            .columns
+            .clone();
+        tables.push((join.effective_name().to_string(), joined));
+    }
+    let tuple_descriptor = TupleDescriptor::from_tables(&tables).with_all_materialized();
+
+    let invalid = |err: crate::query_manager::scalar_expr::ScalarExprError| {
+        QueryCompileError::InvalidPlan(err.to_string())
+    };
+    for comparison in plan.disjuncts.iter().flat_map(|d| &d.comparisons) {
+        if mentions_magic_column(&comparison.left) || mentions_magic_column(&comparison.right) {
+            continue;
+        }
+        BoundCondition::bind_comparison(comparison, &tuple_descriptor).map_err(invalid)?;
+    }
+    for column in plan.project_columns.iter().flatten() {
+        let ProjectExpr::Expr(expr) = &column.expr else {
+            continue;
+        };
+        if mentions_magic_column(expr) {
+            continue;
+        }
+        BoundScalarExpr::bind(expr, &tuple_descriptor).map_err(invalid)?;
+    }
+    for (_, expr) in &plan.computed_order_by {
+        if mentions_magic_column(expr) {
+            continue;
+        }
+        BoundScalarExpr::bind(expr, &tuple_descriptor).map_err(invalid)?;
+    }
+
+    Ok(())
+}
+
+fn mentions_magic_column(expr: &ScalarExpr) -> bool {
+    let mut found = false;
+    expr.visit_column_refs(&mut |column_ref| {
+        found |= magic_column_kind(&column_ref.column).is_some();
+    });
+    found
+}
+
 fn descriptors_compatible_by_shape(left: &RowDescriptor, right: &RowDescriptor) -> bool {
     if left.columns.len() != right.columns.len() {
         return false;
@@ -1989,10 +2366,7 @@ fn disjuncts_to_predicate(
         return Predicate::True;
     }
 
-    let non_empty: Vec<_> = disjuncts
-        .iter()
-        .filter(|d| !d.conditions.is_empty())
-        .collect();
+    let non_empty: Vec<_> = disjuncts.iter().filter(|d| !d.is_empty()).collect();
     if non_empty.is_empty() {
         return Predicate::True;
     }
@@ -2008,48 +2382,88 @@ fn disjuncts_to_predicate(
     )
 }
 
+/// Resolve `order_by` into sort keys. Entries listed in `computed` sort by a
+/// bound expression instead of a column; returns `None` if one fails to bind.
+/// `nearest()` queries order by a single ascending
+/// `VectorDistance(column, literal)` term and keep the first `k` rows.
+/// Returns the vector column index, the target and `k` for that shape.
+fn nearest_plan(
+    plan: &ExecutionQueryPlan,
+    descriptor: &RowDescriptor,
+) -> Option<(usize, Vector, usize)> {
+    if plan.order_by.len() != 1
+        || plan.order_by[0].1 != SortDirection::Ascending
+        || plan.offset > 0
+        || plan.window_from_end
+    {
+        return None;
+    }
+    let k = plan.limit?;
+    let [(0, ScalarExpr::VectorDistance { left, right })] = plan.computed_order_by.as_slice()
+    else {
+        return None;
+    };
+    let (ScalarExpr::Column(column), ScalarExpr::Literal(Value::Vector(target))) =
+        (left.as_ref(), right.as_ref())
+    else {
+        return None;
+    };
+    let col_index = descriptor.column_index(&column.column)?;
+    match descriptor.columns[col_index].column_type {
+        ColumnType::Vector { dims } if dims as usize == target.dims() => {
+            Some((col_index, target.clone(), k))
+        }
+        _ => None,
+    }
+}
+
 fn sort_keys_from_order_by(
     order_by: &[(String, SortDirection)],
+    computed: &[(usize, ScalarExpr)],
     descriptor: &RowDescriptor,
-) -> Vec<SortKey> {
+    tuple_descriptor: &TupleDescriptor,
+) -> Option<(Vec<SortKey>, Vec<BoundScalarExpr>)> {
     if order_by.is_empty() {
         // Deterministic default ordering when no explicit orderBy is provided.
-        return vec![SortKey {
-            target: SortTarget::RowId,
-            direction: SortDirection::Ascending,
-        }];
+        return Some((
+            vec![SortKey {
+                target: SortTarget::RowId,
+                direction: SortDirection::Ascending,
+            }],
+            Vec::new(),
+        ));
     }
 
-    order_by
-        .iter()
-        .filter_map(|(col, dir)| {
-            if col == "_id" {
-                Some(SortKey {
-                    target: SortTarget::RowId,
-                    direction: *dir,
-                })
-            } else {
-                descriptor
-                    .column_index(col)
-                    .map(|idx| SortKey {
-                        target: SortTarget::Column(idx),
-                        direction: *dir,
-                    })
-                    .or_else(|| {
-                        // Backward compatibility: "id" maps to internal row id when no explicit
-                        // "id" column exists on the descriptor.
-                        if col == "id" {
-                            Some(SortKey {
-                                target: SortTarget::RowId,
-                                direction: *dir,
-                            })
-                        } else {
-                            None
-                        }
-                    })
-            }
-        })
-        .collect()
+    let mut computed_keys = Vec::new();
+    let mut sort_keys = Vec::with_capacity(order_by.len());
+    for (position, (col, dir)) in order_by.iter().enumerate() {
+        if let Some((_, expr)) = computed.iter().find(|(index, _)| *index == position) {
+            let bound = BoundScalarExpr::bind(expr, tuple_descriptor).ok()?;
+            sort_keys.push(SortKey {
+                target: SortTarget::Computed(computed_keys.len()),
+                direction: *dir,
+            });
+            computed_keys.push(bound);
+        } else if col == "_id" {
+            sort_keys.push(SortKey {
+                target: SortTarget::RowId,
+                direction: *dir,
+            });
+        } else if let Some(idx) = descriptor.column_index(col) {
+            sort_keys.push(SortKey {
+                target: SortTarget::Column(idx),
+                direction: *dir,
+            });
+        } else if col == "id" {
+            // Backward compatibility: "id" maps to internal row id when no explicit
+            // "id" column exists on the descriptor.
+            sort_keys.push(SortKey {
+                target: SortTarget::RowId,
+                direction: *dir,
+            });
+        }
+    }
+    Some((sort_keys, computed_keys))
 }
 
 fn build_remaining_predicate_from_disjuncts(
@@ -2073,7 +2487,8 @@ fn build_remaining_predicate_from_disjuncts(
 #[derive(Debug, Clone)]
 struct IndexScanPlan {
     column: String,
-    condition: ScanCondition,
+    /// One index scan per entry; a geo filter needs several cell ranges.
+    conditions: Vec<ScanCondition>,
     fully_covers: bool,
 }
 
@@ -2110,17 +2525,22 @@ fn index_scan_plan(
     if disjunct.conditions.is_empty() {
         return IndexScanPlan {
             column: "_id".to_string(),
-            condition: ScanCondition::All,
-            fully_covers: true,
+            conditions: vec![ScanCondition::All],
+            fully_covers: disjunct.comparisons.is_empty(),
         };
     }
 
+    // Geo keys hold cells rather than exact points, so geo columns are only
+    // scanned through `geo_scan_plan`.
     let mut column_plans = Vec::new();
-    for condition in disjunct
-        .conditions
-        .iter()
-        .filter(|c| c.is_index_scannable() && table_schema.is_indexed_column(c.column()))
-    {
+    for condition in disjunct.conditions.iter().filter(|c| {
+        c.is_index_scannable()
+            && table_schema.is_indexed_column(c.column())
+            && !matches!(
+                column_type_for_scan(table_schema, c.column()),
+                Some(ColumnType::GeoPoint)
+            )
+    }) {
         if !column_plans
             .iter()
             .any(|plan: &ColumnScanPlan| plan.column == condition.column())
@@ -2135,35 +2555,74 @@ fn index_scan_plan(
     {
         return IndexScanPlan {
             column: empty_plan.column.clone(),
-            condition: ScanCondition::Empty,
+            conditions: vec![ScanCondition::Empty],
             fully_covers: true,
         };
     }
 
-    let Some(selected) = column_plans
+    let eq_plan = column_plans
         .iter()
-        .find(|plan| matches!(plan.condition, ScanCondition::Eq(_)))
-        .or_else(|| column_plans.first())
-    else {
+        .find(|plan| matches!(plan.condition, ScanCondition::Eq(_)));
+    if eq_plan.is_none()
+        && let Some(geo_plan) = geo_scan_plan(disjunct, table_schema)
+    {
+        return geo_plan;
+    }
+
+    let Some(selected) = eq_plan.or_else(|| column_plans.first()) else {
         return IndexScanPlan {
             column: "_id".to_string(),
-            condition: ScanCondition::All,
+            conditions: vec![ScanCondition::All],
             fully_covers: false,
         };
     };
 
     let fully_covers = selected.exact
+        && disjunct.comparisons.is_empty()
         && disjunct.conditions.iter().all(|condition| {
             condition.column() == selected.column && condition.is_index_scannable()
         });
 
     IndexScanPlan {
         column: selected.column.clone(),
-        condition: selected.condition.clone(),
+        conditions: vec![selected.condition.clone()],
         fully_covers,
     }
 }
 
+/// Scan the cell ranges covering the first indexed geo filter. Coverings
+/// include points outside the region, so the filter always runs afterwards.
+fn geo_scan_plan(
+    disjunct: &Conjunction,
+    table_schema: &crate::query_manager::types::TableSchema,
+) -> Option<IndexScanPlan> {
+    disjunct.conditions.iter().find_map(|condition| {
+        let region = condition.geo_region()?;
+        let column = condition.column();
+        if !table_schema.is_indexed_column(column)
+            || !matches!(
+                column_type_for_scan(table_schema, column),
+                Some(ColumnType::GeoPoint)
+            )
+        {
+            return None;
+        }
+        let conditions = region
+            .covering_ranges()
+            .into_iter()
+            .map(|(lo, hi)| ScanCondition::Range {
+                min: Bound::Included(Value::GeoPoint(GeoPoint::from_cell(lo))),
+                max: Bound::Included(Value::GeoPoint(GeoPoint::from_cell(hi))),
+            })
+            .collect();
+        Some(IndexScanPlan {
+            column: column.to_string(),
+            conditions,
+            fully_covers: false,
+        })
+    })
+}
+
 fn column_scan_plan(
     disjunct: &Conjunction,
     table_schema: &crate::query_manager::types::TableSchema,
@@ -2395,9 +2854,13 @@ fn compare_index_values(left: &Value, right: &Value) -> Option<Ordering> {
         (Value::Integer(left), Value::Integer(right)) => Some(left.cmp(right)),
         (Value::BigInt(left), Value::BigInt(right)) => Some(left.cmp(right)),
         (Value::Double(left), Value::Double(right)) => Some(left.total_cmp(right)),
+        (Value::Decimal(left), Value::Decimal(right)) => Some(left.cmp(right)),
         (Value::Boolean(left), Value::Boolean(right)) => Some(left.cmp(right)),
         (Value::Text(left), Value::Text(right)) => Some(left.cmp(right)),
         (Value::Timestamp(left), Value::Timestamp(right)) => Some(left.cmp(right)),
+        (Value::Date(left), Value::Date(right)) => Some(left.cmp(right)),
+        (Value::Time(left), Value::Time(right)) => Some(left.cmp(right)),
+        (Value::Interval(left), Value::Interval(right)) => Some(left.cmp(right)),
         (Value::Uuid(left), Value::Uuid(right)) => Some(left.cmp(right)),
         (Value::BatchId(left), Value::BatchId(right)) => Some(left.cmp(right)),
         (Value::Bytea(left), Value::Bytea(right)) => Some(left.cmp(right)),
@@ -2422,6 +2885,8 @@ fn apply_condition_to_builder(mut builder: QueryBuilder, condition: &Condition)
         Condition::Contains { column, value } => builder.filter_contains(column, value.clone()),
         Condition::IsNull { column } => builder.filter_is_null(column),
         Condition::IsNotNull { column } => builder.filter_is_not_null(column),
+        Condition::WithinBox { column, bounds } => builder.filter_within_box(column, *bounds),
+        Condition::WithinRadius { column, circle } => builder.filter_within_radius(column, *circle),
     };
     builder
 }
@@ -2434,6 +2899,10 @@ fn column_type_for_scan<'a>(
     if column == "_id" || column == "id" {
         return Some(&ColumnType::Uuid);
     }
+    // A JSON path index stores extracted scalars rather than column values.
+    if JsonPath::is_selector(column) {
+        return Some(&JSON_PATH_SCAN_TYPE);
+    }
     table_schema
         .columns
         .columns
@@ -2442,11 +2911,27 @@ fn column_type_for_scan<'a>(
         .map(|descriptor| &descriptor.column_type)
 }
 
+/// Scan type standing in for the scalars held by JSON path indices.
+static JSON_PATH_SCAN_TYPE: ColumnType = ColumnType::Json { schema: None };
+
 fn normalize_scan_value(value: &Value, column_type: Option<&ColumnType>) -> Option<Value> {
     match (value, column_type) {
         (Value::Text(raw), Some(ColumnType::Uuid)) => Uuid::parse_str(raw)
             .map(|uuid| Value::Uuid(ObjectId::from_uuid(uuid)))
             .ok(),
+        (value, Some(ColumnType::Json { .. })) => Some(json_path::normalize_literal(value)),
+        // Decimal index keys are scale-independent; integer literals only
+        // need to become decimals.
+        (Value::Integer(number), Some(ColumnType::Decimal { .. })) => {
+            Some(Value::Decimal(Decimal::from(*number)))
+        }
+        (Value::BigInt(number), Some(ColumnType::Decimal { .. })) => {
+            Some(Value::Decimal(Decimal::from(*number)))
+        }
+        (Value::Text(raw), Some(column_type)) => match parse_temporal_literal(raw, column_type) {
+            Some(parsed) => parsed.ok(),
+            None => Some(value.clone()),
+        },
         _ => Some(value.clone()),
     }
 }
@@ -2519,12 +3004,13 @@ mod tests {
                 column: "owner_id".to_string(),
                 value: Value::Text(alice_uuid.to_string()),
             }],
+            comparisons: Vec::new(),
         };
 
         let plan = index_scan_plan(&disjunct, &uuid_owner_schema());
 
         assert_eq!(plan.column, "owner_id");
-        assert_eq!(plan.condition, ScanCondition::Eq(Value::Uuid(alice)));
+        assert_eq!(plan.conditions, vec![ScanCondition::Eq(Value::Uuid(alice))]);
         assert!(plan.fully_covers);
     }
 
@@ -2535,12 +3021,13 @@ mod tests {
                 column: "owner_id".to_string(),
                 value: Value::Text("not-a-uuid".to_string()),
             }],
+            comparisons: Vec::new(),
         };
 
         let plan = index_scan_plan(&disjunct, &uuid_owner_schema());
 
         assert_eq!(plan.column, "owner_id");
-        assert_eq!(plan.condition, ScanCondition::Empty);
+        assert_eq!(plan.conditions, vec![ScanCondition::Empty]);
         assert!(plan.fully_covers);
     }
 }
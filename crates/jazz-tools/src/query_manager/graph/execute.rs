@@ -393,6 +393,7 @@ impl QueryGraph {
         match self.get_node(node_id) {
             Some(GraphNode::Sort(node)) => Some(node.sorted_tuples()),
             Some(GraphNode::LimitOffset(node)) => Some(node.windowed_tuples()),
+            Some(GraphNode::Nearest(node)) => Some(node.ordered_tuples()),
             Some(GraphNode::MagicColumns(node)) => Some(node.ordered_tuples()),
             Some(GraphNode::Project(node)) => Some(node.ordered_tuples()),
             _ => None,
@@ -454,25 +455,10 @@ impl QueryGraph {
         };
 
         for node_id in order {
-            let node_type = match self.get_node(node_id) {
-                Some(GraphNode::IndexScan(_)) => "IndexScan",
-                Some(GraphNode::Union(_)) => "Union",
-                Some(GraphNode::Alias(_)) => "Alias",
-                Some(GraphNode::Join(_)) => "Join",
-                Some(GraphNode::MagicColumns(_)) => "MagicColumns",
-                Some(GraphNode::Project(_)) => "Project",
-                Some(GraphNode::SelectElement(_)) => "SelectElement",
-                Some(GraphNode::RecursiveRelation(_)) => "RecursiveRelation",
-                Some(GraphNode::Materialize(_)) => "Materialize",
-                Some(GraphNode::Filter(_)) => "Filter",
-                Some(GraphNode::PolicyFilter(_)) => "PolicyFilter",
-                Some(GraphNode::Sort(_)) => "Sort",
-                Some(GraphNode::LimitOffset(_)) => "LimitOffset",
-                Some(GraphNode::ArraySubquery(_)) => "ArraySubquery",
-                Some(GraphNode::Output(_)) => "Output",
-                Some(GraphNode::ExistsOutput(_)) => "ExistsOutput",
-                None => "Unknown",
-            };
+            let node_type = self
+                .get_node(node_id)
+                .map(GraphNode::kind_name)
+                .unwrap_or("Unknown");
 
             match self.get_node(node_id) {
                 Some(GraphNode::IndexScan(_)) => {
@@ -535,16 +521,7 @@ impl QueryGraph {
                         .unwrap_or_default();
 
                     if let Some(GraphNode::Join(join_node)) = self.get_node_mut(node_id) {
-                        // Process left side first, then right side
-                        let left_result = join_node.process_left(left_delta);
-                        let right_result = join_node.process_right(right_delta);
-
-                        // Merge results
-                        let mut merged = TupleDelta::new();
-                        merged.added.extend(left_result.added);
-                        merged.added.extend(right_result.added);
-                        merged.removed.extend(left_result.removed);
-                        merged.removed.extend(right_result.removed);
+                        let merged = join_node.process_inputs(left_delta, right_delta);
 
                         tracing::debug!(
                             node_id = node_id.0,
@@ -758,6 +735,25 @@ impl QueryGraph {
                         tuple_deltas.insert(node_id, delta);
                     }
                 }
+                Some(GraphNode::Nearest(_)) => {
+                    let input_delta = self
+                        .get_inputs(node_id)
+                        .first()
+                        .and_then(|dep| tuple_deltas.get(dep).cloned())
+                        .unwrap_or_default();
+
+                    if let Some(GraphNode::Nearest(nearest_node)) = self.get_node_mut(node_id) {
+                        let delta = RowNode::process(nearest_node, input_delta);
+                        tracing::debug!(
+                            node_id = node_id.0,
+                            node_type,
+                            added = delta.added.len(),
+                            removed = delta.removed.len(),
+                            "graph node evaluated"
+                        );
+                        tuple_deltas.insert(node_id, delta);
+                    }
+                }
                 Some(GraphNode::LimitOffset(_)) => {
                     let input_node = self.get_inputs(node_id).first().copied();
                     let delta = input_node
@@ -869,6 +865,8 @@ impl QueryGraph {
                 }
                 None => {}
             }
+
+            self.record_node_counters(node_id, &tuple_deltas);
         }
 
         self.dirty_bitmap.fill(false);
@@ -890,6 +888,31 @@ impl QueryGraph {
             .unwrap_or_default()
     }
 
+    fn record_node_counters(
+        &mut self,
+        node_id: NodeId,
+        tuple_deltas: &AHashMap<NodeId, TupleDelta>,
+    ) {
+        let Some(delta) = tuple_deltas.get(&node_id) else {
+            return;
+        };
+        let rows_emitted = delta_row_count(delta);
+        let rows_scanned = if self.get_inputs(node_id).is_empty() {
+            rows_emitted
+        } else {
+            self.get_inputs(node_id)
+                .iter()
+                .filter_map(|dep| tuple_deltas.get(dep))
+                .map(delta_row_count)
+                .sum()
+        };
+        if let Some(counters) = self.node_counters.get_mut(node_id.0 as usize) {
+            counters.evaluations += 1;
+            counters.rows_scanned += rows_scanned;
+            counters.rows_emitted += rows_emitted;
+        }
+    }
+
     /// Collect tuple sets from input nodes for a transform node.
     fn collect_tuple_inputs(&self, node_id: NodeId) -> Vec<AHashSet<Tuple>> {
         self.get_inputs(node_id)
@@ -902,3 +925,7 @@ impl QueryGraph {
             .collect()
     }
 }
+
+fn delta_row_count(delta: &TupleDelta) -> u64 {
+    (delta.added.len() + delta.removed.len() + delta.moved.len() + delta.updated.len()) as u64
+}
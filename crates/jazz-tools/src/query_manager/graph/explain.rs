@@ -0,0 +1,259 @@
+//! `explain(query)` output: what a compiled [`QueryGraph`] does and what it costs.
+//!
+//! Cost is reported per index scan as the number of committed rows the index
+//! holds for the scan condition; the query's estimated cost is their sum.
+//! Counters are the rows each node read and emitted across settles.
+
+use std::ops::Bound;
+
+use serde::{Deserialize, Serialize};
+
+use crate::query_manager::graph_nodes::sort::{SortDirection, SortTarget};
+use crate::query_manager::graph_nodes::{NodeId, RowNode, ScanCondition};
+use crate::query_manager::relation_ir::RelExpr;
+use crate::query_manager::types::Value;
+use crate::storage::Storage;
+
+use super::{GraphNode, NodeCounters, QueryGraph};
+
+/// Description of a compiled query.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryExplain {
+    /// Relation IR after canonicalization.
+    pub relation_ir: RelExpr,
+    /// Branches the query reads.
+    pub branches: Vec<String>,
+    /// Graph nodes in id order.
+    pub nodes: Vec<ExplainNode>,
+    pub output_node: u64,
+    /// Sum of `estimatedRows` over every index scan.
+    pub estimated_cost: u64,
+}
+
+/// One node of the compiled graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainNode {
+    pub id: u64,
+    /// Node type, e.g. `IndexScan`, `Filter`, `PolicyFilter`, `Sort`.
+    pub kind: String,
+    /// Nodes this one reads from.
+    pub inputs: Vec<u64>,
+    /// Human-readable summary of what the node does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_scan: Option<ExplainIndexScan>,
+    /// Present when the query was explained with runtime counters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counters: Option<NodeCounters>,
+}
+
+/// Index and condition an `IndexScan` node reads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainIndexScan {
+    pub table: String,
+    pub column: String,
+    pub branch: String,
+    pub condition: String,
+    pub estimated_rows: u64,
+}
+
+impl QueryExplain {
+    /// Describe `graph`, costing index scans against `storage`.
+    pub fn new(
+        relation_ir: RelExpr,
+        branches: Vec<String>,
+        graph: &QueryGraph,
+        storage: &dyn Storage,
+        include_counters: bool,
+    ) -> Self {
+        let nodes = graph.explain_nodes(storage, include_counters);
+        let estimated_cost = nodes
+            .iter()
+            .filter_map(|node| node.index_scan.as_ref())
+            .map(|scan| scan.estimated_rows)
+            .sum();
+        Self {
+            relation_ir,
+            branches,
+            nodes,
+            output_node: graph.output_node.0,
+            estimated_cost,
+        }
+    }
+
+    /// Nodes of a given kind, in id order.
+    pub fn nodes_of_kind<'a>(&'a self, kind: &'a str) -> impl Iterator<Item = &'a ExplainNode> {
+        self.nodes.iter().filter(move |node| node.kind == kind)
+    }
+}
+
+impl QueryGraph {
+    /// Describe every node of the graph.
+    pub fn explain_nodes(&self, storage: &dyn Storage, include_counters: bool) -> Vec<ExplainNode> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, compact)| {
+                let id = NodeId(index as u64);
+                ExplainNode {
+                    id: id.0,
+                    kind: compact.node.kind_name().to_string(),
+                    inputs: compact.inputs.iter().map(|input| input.0).collect(),
+                    detail: node_detail(&compact.node),
+                    index_scan: match &compact.node {
+                        GraphNode::IndexScan(scan) => Some(ExplainIndexScan {
+                            table: scan.table.as_str().to_string(),
+                            column: scan.column.as_str().to_string(),
+                            branch: scan.branch.clone(),
+                            condition: describe_scan_condition(&scan.condition),
+                            estimated_rows: scan.lookup_ids(storage).len() as u64,
+                        }),
+                        _ => None,
+                    },
+                    counters: include_counters.then(|| self.node_counters(id)),
+                }
+            })
+            .collect()
+    }
+}
+
+fn node_detail(node: &GraphNode) -> Option<String> {
+    match node {
+        GraphNode::IndexScan(scan) => Some(format!(
+            "{}.{} {}",
+            scan.table.as_str(),
+            scan.column.as_str(),
+            describe_scan_condition(&scan.condition)
+        )),
+        GraphNode::Alias(alias) => Some(format!("{} AS {}", alias.original_table(), alias.alias())),
+        GraphNode::Join(join) => Some(format!("{:?}", join.kind())),
+        GraphNode::Filter(filter) => Some(format!("{:?}", filter.predicate())),
+        GraphNode::PolicyFilter(policy) => Some(format!(
+            "{:?} on {}",
+            policy.operation(),
+            policy.table_name()
+        )),
+        GraphNode::Project(project) => Some(
+            project
+                .output_descriptor()
+                .columns
+                .iter()
+                .map(|column| column.name.as_str().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        GraphNode::Sort(sort) => Some(
+            sort.sort_keys()
+                .iter()
+                .map(|key| {
+                    let target = match key.target {
+                        SortTarget::Column(index) => sort
+                            .output_descriptor()
+                            .columns
+                            .get(index)
+                            .map(|column| column.name.as_str().to_string())
+                            .unwrap_or_else(|| format!("#{index}")),
+                        SortTarget::RowId => "id".to_string(),
+                        SortTarget::Computed(index) => format!("expr#{index}"),
+                    };
+                    match key.direction {
+                        SortDirection::Ascending => format!("{target} ASC"),
+                        SortDirection::Descending => format!("{target} DESC"),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        GraphNode::LimitOffset(window) => {
+            let limit = window
+                .limit()
+                .map(|limit| format!("LIMIT {limit} "))
+                .unwrap_or_default();
+            let from_end = if window.from_end() { " FROM END" } else { "" };
+            Some(format!("{limit}OFFSET {}{from_end}", window.offset()))
+        }
+        GraphNode::Nearest(nearest) => Some(format!(
+            "{} NEAREST {} LIMIT {}",
+            nearest.column_name().unwrap_or("?"),
+            describe_value(&Value::Vector(nearest.target().clone())),
+            nearest.k()
+        )),
+        _ => None,
+    }
+}
+
+/// Render a scan condition in SQL-ish form (`= 'x'`, `>= 1 AND < 5`, `ALL`).
+pub fn describe_scan_condition(condition: &ScanCondition) -> String {
+    match condition {
+        ScanCondition::All => "ALL".to_string(),
+        ScanCondition::Empty => "NONE".to_string(),
+        ScanCondition::Eq(value) => format!("= {}", describe_value(value)),
+        ScanCondition::Range { min, max } => {
+            let lower = match min {
+                Bound::Included(value) => Some(format!(">= {}", describe_value(value))),
+                Bound::Excluded(value) => Some(format!("> {}", describe_value(value))),
+                Bound::Unbounded => None,
+            };
+            let upper = match max {
+                Bound::Included(value) => Some(format!("<= {}", describe_value(value))),
+                Bound::Excluded(value) => Some(format!("< {}", describe_value(value))),
+                Bound::Unbounded => None,
+            };
+            match (lower, upper) {
+                (Some(lower), Some(upper)) => format!("{lower} AND {upper}"),
+                (Some(bound), None) | (None, Some(bound)) => bound,
+                (None, None) => "ALL".to_string(),
+            }
+        }
+    }
+}
+
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::Text(text) => format!("'{text}'"),
+        Value::Integer(number) => number.to_string(),
+        Value::BigInt(number) => number.to_string(),
+        Value::Double(number) => number.to_string(),
+        Value::Decimal(number) => number.to_string(),
+        Value::Date(date) => format!("DATE '{date}'"),
+        Value::Time(time) => format!("TIME '{time}'"),
+        Value::Interval(interval) => format!("INTERVAL '{interval}'"),
+        Value::GeoPoint(point) => format!("GEOPOINT '{point}'"),
+        Value::Vector(vector) => format!("VECTOR '{vector}'"),
+        Value::Boolean(flag) => flag.to_string(),
+        Value::Null => "NULL".to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_scan_condition_renders_ranges_and_equality() {
+        assert_eq!(
+            describe_scan_condition(&ScanCondition::Eq(Value::Text("dark".into()))),
+            "= 'dark'"
+        );
+        assert_eq!(
+            describe_scan_condition(&ScanCondition::Range {
+                min: Bound::Included(Value::Integer(10)),
+                max: Bound::Excluded(Value::Integer(20)),
+            }),
+            ">= 10 AND < 20"
+        );
+        assert_eq!(
+            describe_scan_condition(&ScanCondition::Range {
+                min: Bound::Unbounded,
+                max: Bound::Included(Value::Integer(5)),
+            }),
+            "<= 5"
+        );
+        assert_eq!(describe_scan_condition(&ScanCondition::All), "ALL");
+    }
+}
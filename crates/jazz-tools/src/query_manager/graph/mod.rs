@@ -4,16 +4,19 @@
 //! - [`compile`] turns relation IR into [`QueryGraph`] nodes (pure transform).
 //! - [`execute`] handles dirty tracking, topological settlement, and row I/O via a closure.
 //!
+//! [`explain`] describes a compiled graph (nodes, index choices, counters) for debugging.
+//!
 //! Shared types live here; both submodules add `impl QueryGraph` blocks against them.
 
 use std::collections::HashSet;
 use std::fmt;
 
 use bitvec::prelude::*;
+use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
 use crate::object::{BranchName, ObjectId};
-use crate::query_manager::query::ArraySubquerySpec;
+use crate::query_manager::query::{ArraySubquerySpec, PageCursor};
 use crate::query_manager::types::{Row, RowDelta, RowDescriptor, TableName, Tuple, TupleDelta};
 
 use super::graph_nodes::NodeId;
@@ -26,6 +29,7 @@ use super::graph_nodes::join::JoinNode;
 use super::graph_nodes::limit_offset::LimitOffsetNode;
 use super::graph_nodes::magic_columns::MagicColumnsNode;
 use super::graph_nodes::materialize::MaterializeNode;
+use super::graph_nodes::nearest::NearestNode;
 use super::graph_nodes::output::OutputNode;
 use super::graph_nodes::policy_filter::PolicyFilterNode;
 use super::graph_nodes::project::ProjectNode;
@@ -37,6 +41,7 @@ use super::types::{ColumnName, TupleProvenance};
 
 pub mod compile;
 pub mod execute;
+pub mod explain;
 
 #[cfg(test)]
 mod tests;
@@ -76,11 +81,37 @@ pub enum GraphNode {
     PolicyFilter(PolicyFilterNode),
     Sort(SortNode),
     LimitOffset(LimitOffsetNode),
+    Nearest(NearestNode),
     ArraySubquery(ArraySubqueryNode),
     Output(OutputNode),
     ExistsOutput(ExistsOutputNode),
 }
 
+impl GraphNode {
+    /// Node type name used in traces and `explain` output.
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            GraphNode::IndexScan(_) => "IndexScan",
+            GraphNode::Union(_) => "Union",
+            GraphNode::Alias(_) => "Alias",
+            GraphNode::Join(_) => "Join",
+            GraphNode::Materialize(_) => "Materialize",
+            GraphNode::MagicColumns(_) => "MagicColumns",
+            GraphNode::Project(_) => "Project",
+            GraphNode::SelectElement(_) => "SelectElement",
+            GraphNode::RecursiveRelation(_) => "RecursiveRelation",
+            GraphNode::Filter(_) => "Filter",
+            GraphNode::PolicyFilter(_) => "PolicyFilter",
+            GraphNode::Sort(_) => "Sort",
+            GraphNode::LimitOffset(_) => "LimitOffset",
+            GraphNode::Nearest(_) => "Nearest",
+            GraphNode::ArraySubquery(_) => "ArraySubquery",
+            GraphNode::Output(_) => "Output",
+            GraphNode::ExistsOutput(_) => "ExistsOutput",
+        }
+    }
+}
+
 /// Compact node with inline edge storage.
 /// Most nodes have 0-2 inputs/outputs, so inline storage avoids heap allocation.
 #[derive(Debug)]
@@ -92,6 +123,18 @@ pub struct CompactNode {
     pub outputs: SmallVec<[NodeId; 2]>,
 }
 
+/// Row counters a node accumulates across settles (for `explain`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeCounters {
+    /// Settles that evaluated this node.
+    pub evaluations: u64,
+    /// Delta rows read from inputs; for index scans, the rows the scan produced.
+    pub rows_scanned: u64,
+    /// Delta rows emitted (added, removed, moved and updated).
+    pub rows_emitted: u64,
+}
+
 /// Compiled query graph for a single query.
 #[derive(Debug)]
 pub struct QueryGraph {
@@ -99,6 +142,8 @@ pub struct QueryGraph {
     pub nodes: Vec<CompactNode>,
     /// Dirty tracking bitmap (1 bit per node, indexed by NodeId.0).
     pub(super) dirty_bitmap: BitVec,
+    /// Runtime counters per node (indexed by NodeId.0).
+    pub(super) node_counters: Vec<NodeCounters>,
     /// The output node ID.
     pub output_node: NodeId,
     /// The pagination node, when the query applies limit/offset.
@@ -133,6 +178,7 @@ impl QueryGraph {
         Self {
             nodes: Vec::new(),
             dirty_bitmap: BitVec::new(),
+            node_counters: Vec::new(),
             output_node: NodeId(0),
             pagination_node: None,
             table,
@@ -155,6 +201,7 @@ impl QueryGraph {
         });
         // Grow dirty bitmap to accommodate new node
         self.dirty_bitmap.push(true); // New nodes start dirty
+        self.node_counters.push(NodeCounters::default());
         id
     }
 
@@ -179,6 +226,14 @@ impl QueryGraph {
         self.nodes.get_mut(id.0 as usize).map(|c| &mut c.node)
     }
 
+    /// Counters accumulated by `settle` for a node.
+    pub fn node_counters(&self, id: NodeId) -> NodeCounters {
+        self.node_counters
+            .get(id.0 as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Get input edges for a node.
     pub(super) fn get_inputs(&self, id: NodeId) -> &[NodeId] {
         &self.nodes[id.0 as usize].inputs
@@ -222,6 +277,29 @@ impl QueryGraph {
         self.current_output_scope()
     }
 
+    /// Keyset cursor for a row on the current page.
+    ///
+    /// Only sorted, paginated queries have one: the values come from the sort
+    /// node feeding the pagination window.
+    pub fn page_cursor(&self, row_id: ObjectId) -> Option<PageCursor> {
+        let pagination_id = self.pagination_node?;
+        let Some(GraphNode::LimitOffset(limit_offset)) = self.get_node(pagination_id) else {
+            return None;
+        };
+        let sort_id = *self.get_inputs(pagination_id).first()?;
+        let Some(GraphNode::Sort(sort_node)) = self.get_node(sort_id) else {
+            return None;
+        };
+        let tuple = limit_offset
+            .windowed_tuples()
+            .iter()
+            .find(|tuple| tuple.first_id() == Some(row_id))?;
+        Some(PageCursor {
+            sort_values: sort_node.sort_key_values(tuple),
+            row_id,
+        })
+    }
+
     /// Returns tuples that must be synced for the client to reproduce the current
     /// query result locally.
     pub fn sync_scope_tuples(&self) -> Vec<Tuple> {
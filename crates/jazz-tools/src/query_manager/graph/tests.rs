@@ -1183,6 +1183,7 @@ fn compile_query_with_relation_ir_project_join_order_limit_shape() {
             terms: vec![OrderByExpr {
                 column: ColumnRef::unscoped("name"),
                 direction: OrderDirection::Desc,
+                expr: None,
             }],
         }),
         limit: 5,
@@ -11,8 +11,9 @@ use crate::object::ObjectId;
 use crate::query_manager::encoding::{decode_row, encode_row};
 use crate::query_manager::query::ArraySubqueryRequirement;
 use crate::query_manager::types::{
-    ColumnDescriptor, ColumnType, LoadedRow, RowDescriptor, Schema, TableName, Tuple,
-    TupleBatchProvenance, TupleDelta, TupleDescriptor, TupleElement, TupleProvenance, Value,
+    Annotations, ColumnDescriptor, ColumnLimits, ColumnType, LoadedRow, RowDescriptor, Schema,
+    TableName, Tuple, TupleBatchProvenance, TupleDelta, TupleDescriptor, TupleElement,
+    TupleProvenance, Value, Visibility,
 };
 
 use crate::storage::Storage;
@@ -126,6 +127,9 @@ impl ArraySubqueryNode {
             references: None,
             default: None,
             merge_strategy: None,
+            limits: ColumnLimits::default(),
+            visibility: Visibility::Public,
+            annotations: Annotations::new(),
         });
 
         let output_descriptor = RowDescriptor::new(output_columns);
@@ -5,7 +5,11 @@ use std::collections::HashSet;
 use crate::query_manager::encoding::{
     column_bytes, column_is_null, compare_column_to_value, decode_column,
 };
-use crate::query_manager::types::{RowDescriptor, Tuple, TupleDelta, TupleDescriptor, Value};
+use crate::query_manager::json_path::{JsonPath, JsonPathTest};
+use crate::query_manager::scalar_expr::BoundCondition;
+use crate::query_manager::types::{
+    GeoRegion, RowDescriptor, Tuple, TupleDelta, TupleDescriptor, Value,
+};
 
 use super::RowNode;
 
@@ -30,6 +34,8 @@ pub enum Predicate {
     IsNull { col_index: usize },
     /// Column is not null.
     IsNotNull { col_index: usize },
+    /// Geo point column lies inside the region.
+    Within { col_index: usize, region: GeoRegion },
     /// Tuple element row id equals value.
     RowIdEq {
         element_index: usize,
@@ -64,6 +70,14 @@ pub enum Predicate {
     RowIdIsNull { element_index: usize },
     /// Tuple element row id is not null.
     RowIdIsNotNull { element_index: usize },
+    /// Test on the value at a JSON path inside a `Json` column.
+    JsonPath {
+        col_index: usize,
+        path: JsonPath,
+        test: JsonPathTest,
+    },
+    /// Computed-expression condition; only an outright `true` passes.
+    Scalar(Box<BoundCondition>),
     /// Logical AND of predicates.
     And(Vec<Predicate>),
     /// Logical OR of predicates.
@@ -85,9 +99,10 @@ impl Predicate {
             | Predicate::Gt { col_index, .. }
             | Predicate::Ge { col_index, .. }
             | Predicate::Contains { col_index, .. } => [*col_index].into_iter().collect(),
-            Predicate::IsNull { col_index } | Predicate::IsNotNull { col_index } => {
-                [*col_index].into_iter().collect()
-            }
+            Predicate::IsNull { col_index }
+            | Predicate::IsNotNull { col_index }
+            | Predicate::Within { col_index, .. }
+            | Predicate::JsonPath { col_index, .. } => [*col_index].into_iter().collect(),
             Predicate::RowIdEq { .. }
             | Predicate::RowIdNe { .. }
             | Predicate::RowIdLt { .. }
@@ -96,6 +111,7 @@ impl Predicate {
             | Predicate::RowIdGe { .. }
             | Predicate::RowIdIsNull { .. }
             | Predicate::RowIdIsNotNull { .. } => HashSet::new(),
+            Predicate::Scalar(condition) => condition.required_columns(),
             Predicate::And(preds) | Predicate::Or(preds) => {
                 preds.iter().flat_map(|p| p.required_columns()).collect()
             }
@@ -243,6 +259,12 @@ impl FilterNode {
             Predicate::IsNotNull { col_index } => {
                 !self.is_column_null(tuple, *col_index).unwrap_or(true)
             }
+            Predicate::Within { col_index, region } => {
+                match self.get_column_value(tuple, *col_index) {
+                    Some(Value::GeoPoint(point)) => region.contains(&point),
+                    _ => false,
+                }
+            }
             Predicate::RowIdEq {
                 element_index,
                 value,
@@ -287,6 +309,19 @@ impl FilterNode {
             ),
             Predicate::RowIdIsNull { element_index } => tuple.get(*element_index).is_none(),
             Predicate::RowIdIsNotNull { element_index } => tuple.get(*element_index).is_some(),
+            Predicate::JsonPath {
+                col_index,
+                path,
+                test,
+            } => {
+                let found = self
+                    .get_column_value(tuple, *col_index)
+                    .and_then(|value| path.resolve(&value));
+                test.matches(found.as_ref())
+            }
+            Predicate::Scalar(condition) => {
+                condition.evaluate(tuple, &self.tuple_descriptor) == Some(true)
+            }
             Predicate::And(preds) => preds
                 .iter()
                 .all(|p| self.evaluate_predicate_on_tuple(p, tuple)),
@@ -1222,4 +1257,128 @@ mod tests {
         assert!(contains_id(&result.added, alice_team));
         assert!(!contains_id(&result.added, ops_team));
     }
+
+    #[test]
+    fn filter_scalar_condition_evaluates_computed_expressions() {
+        use crate::query_manager::relation_ir::{
+            ArithmeticOp, ColumnRef, PredicateCmpOp, ScalarComparison, ScalarExpr,
+        };
+
+        let descriptor = test_descriptor();
+        let tuple_desc = TupleDescriptor::single_with_materialization("", descriptor, true);
+        // lower(name) = 'alice' AND score * 2 > 150
+        let condition = BoundCondition::And(vec![
+            BoundCondition::bind_comparison(
+                &ScalarComparison {
+                    left: ScalarExpr::Lower(Box::new(ScalarExpr::Column(ColumnRef::unscoped(
+                        "name",
+                    )))),
+                    op: PredicateCmpOp::Eq,
+                    right: ScalarExpr::Literal(Value::Text("alice".into())),
+                },
+                &tuple_desc,
+            )
+            .unwrap(),
+            BoundCondition::bind_comparison(
+                &ScalarComparison {
+                    left: ScalarExpr::Arithmetic {
+                        op: ArithmeticOp::Mul,
+                        left: Box::new(ScalarExpr::Column(ColumnRef::unscoped("score"))),
+                        right: Box::new(ScalarExpr::Literal(Value::Integer(2))),
+                    },
+                    op: PredicateCmpOp::Gt,
+                    right: ScalarExpr::Literal(Value::Integer(150)),
+                },
+                &tuple_desc,
+            )
+            .unwrap(),
+        ]);
+        let predicate = Predicate::Scalar(Box::new(condition));
+        assert_eq!(predicate.required_columns(), [1, 2].into_iter().collect());
+        let mut node = FilterNode::with_tuple_descriptor(tuple_desc, predicate);
+
+        let alice = ObjectId::new();
+        let low_alice = ObjectId::new();
+        let bob = ObjectId::new();
+        let result = node.process(TupleDelta {
+            added: vec![
+                make_tuple(
+                    alice,
+                    &[
+                        Value::Integer(1),
+                        Value::Text("ALICE".into()),
+                        Value::Integer(100),
+                    ],
+                ),
+                make_tuple(
+                    low_alice,
+                    &[
+                        Value::Integer(2),
+                        Value::Text("Alice".into()),
+                        Value::Integer(50),
+                    ],
+                ),
+                make_tuple(
+                    bob,
+                    &[
+                        Value::Integer(3),
+                        Value::Text("Bob".into()),
+                        Value::Integer(100),
+                    ],
+                ),
+            ],
+            removed: vec![],
+            moved: vec![],
+            updated: vec![],
+        });
+
+        assert_eq!(result.added.len(), 1);
+        assert!(contains_id(&result.added, alice));
+    }
+
+    #[test]
+    fn filter_json_path_compares_value_inside_json_column() {
+        use crate::query_manager::relation_ir::PredicateCmpOp;
+
+        let descriptor = RowDescriptor::new(vec![ColumnDescriptor::new(
+            "settings",
+            ColumnType::Json { schema: None },
+        )]);
+        let make_settings_tuple = |id: ObjectId, json: &str| {
+            let data = encode_row(&descriptor, &[Value::Text(json.into())]).unwrap();
+            Tuple::new(vec![TupleElement::Row {
+                id,
+                content: data.into(),
+                batch_id: BatchId([0; 16]),
+                row_provenance: RowProvenance::for_insert("jazz:test", 0),
+            }])
+        };
+        let tuple_desc = TupleDescriptor::single_with_materialization("", descriptor.clone(), true);
+        let predicate = Predicate::JsonPath {
+            col_index: 0,
+            path: JsonPath::parse("settings->theme").unwrap(),
+            test: JsonPathTest::Compare {
+                op: PredicateCmpOp::Eq,
+                value: Value::Text("dark".into()),
+            },
+        };
+        let mut node = FilterNode::with_tuple_descriptor(tuple_desc, predicate);
+
+        let dark = ObjectId::new();
+        let light = ObjectId::new();
+        let unset = ObjectId::new();
+        let result = node.process(TupleDelta {
+            added: vec![
+                make_settings_tuple(dark, r#"{"theme":"dark"}"#),
+                make_settings_tuple(light, r#"{"theme":"light"}"#),
+                make_settings_tuple(unset, "{}"),
+            ],
+            removed: vec![],
+            moved: vec![],
+            updated: vec![],
+        });
+
+        assert_eq!(result.added.len(), 1);
+        assert!(contains_id(&result.added, dark));
+    }
 }
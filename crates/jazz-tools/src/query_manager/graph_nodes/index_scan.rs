@@ -7,6 +7,7 @@ use crate::query_manager::types::{
     ColumnName, RowDescriptor, TableName, Tuple, TupleDelta, TupleDescriptor, Value,
 };
 use crate::row_format::decode_row;
+use crate::storage::Storage;
 
 use super::{SourceContext, SourceNode};
 
@@ -70,6 +71,31 @@ impl IndexScanNode {
         &self.output_descriptor
     }
 
+    /// Committed row IDs the index holds for this scan's condition.
+    ///
+    /// Excludes local overlay rows; `explain` uses it as the scan's cost.
+    pub fn lookup_ids(&self, storage: &dyn Storage) -> Vec<ObjectId> {
+        match &self.condition {
+            ScanCondition::Empty => Vec::new(),
+            ScanCondition::All => {
+                storage.index_scan_all(self.table.as_str(), self.column.as_str(), &self.branch)
+            }
+            ScanCondition::Eq(value) => storage.index_lookup(
+                self.table.as_str(),
+                self.column.as_str(),
+                &self.branch,
+                value,
+            ),
+            ScanCondition::Range { min, max } => storage.index_range(
+                self.table.as_str(),
+                self.column.as_str(),
+                &self.branch,
+                min.as_ref(),
+                max.as_ref(),
+            ),
+        }
+    }
+
     fn overlay_value_matches_condition(&self, row_id: ObjectId, data: &[u8]) -> bool {
         let Some(value) = self.overlay_index_value(row_id, data) else {
             return false;
@@ -136,9 +162,15 @@ fn compare_values_for_ordering(left: &Value, right: &Value) -> Option<std::cmp::
         (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
         (Value::BigInt(a), Value::BigInt(b)) => Some(a.cmp(b)),
         (Value::Double(a), Value::Double(b)) => Some(a.total_cmp(b)),
+        (Value::Decimal(a), Value::Decimal(b)) => Some(a.cmp(b)),
         (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
         (Value::Text(a), Value::Text(b)) => Some(a.cmp(b)),
         (Value::Timestamp(a), Value::Timestamp(b)) => Some(a.cmp(b)),
+        (Value::Date(a), Value::Date(b)) => Some(a.cmp(b)),
+        (Value::Time(a), Value::Time(b)) => Some(a.cmp(b)),
+        (Value::Interval(a), Value::Interval(b)) => Some(a.cmp(b)),
+        // Geo index entries are ordered by Z-order cell.
+        (Value::GeoPoint(a), Value::GeoPoint(b)) => Some(a.cell().cmp(&b.cell())),
         (Value::Uuid(a), Value::Uuid(b)) => Some(a.cmp(b)),
         (Value::Null, Value::Null) => Some(std::cmp::Ordering::Equal),
         (Value::Null, _) => Some(std::cmp::Ordering::Less),
@@ -179,38 +211,7 @@ fn bound_matches(bound: &Bound<Value>, value: &Value, is_lower: bool) -> bool {
 
 impl SourceNode for IndexScanNode {
     fn scan(&mut self, ctx: &SourceContext) -> TupleDelta {
-        let mut new_ids: AHashSet<ObjectId> = match &self.condition {
-            ScanCondition::Empty => AHashSet::new(),
-            ScanCondition::All => ctx
-                .storage
-                .index_scan_all(self.table.as_str(), self.column.as_str(), &self.branch)
-                .into_iter()
-                .collect(),
-            ScanCondition::Eq(value) => ctx
-                .storage
-                .index_lookup(
-                    self.table.as_str(),
-                    self.column.as_str(),
-                    &self.branch,
-                    value,
-                )
-                .into_iter()
-                .collect(),
-            ScanCondition::Range { min, max } => {
-                let start = min.as_ref();
-                let end = max.as_ref();
-                ctx.storage
-                    .index_range(
-                        self.table.as_str(),
-                        self.column.as_str(),
-                        &self.branch,
-                        start,
-                        end,
-                    )
-                    .into_iter()
-                    .collect()
-            }
-        };
+        let mut new_ids: AHashSet<ObjectId> = self.lookup_ids(ctx.storage).into_iter().collect();
         self.apply_local_overlay_rows(ctx, &mut new_ids);
 
         // Diff against last scan
@@ -2,6 +2,7 @@ use ahash::{AHashMap, AHashSet};
 
 use crate::object::ObjectId;
 use crate::query_manager::encoding::{column_bytes, decode_column, encode_value};
+use crate::query_manager::relation_ir::JoinKind;
 use crate::query_manager::types::{
     ColumnType, RowDescriptor, Tuple, TupleDelta, TupleDescriptor, Value,
 };
@@ -12,8 +13,12 @@ use crate::query_manager::types::{
 /// Combines tuples from left and right inputs where join columns match.
 ///
 /// Example: `users JOIN posts ON users.id = posts.author_id`
+///
+/// With [`JoinKind::Semi`] / [`JoinKind::Anti`] the node instead emits left
+/// tuples unchanged, while they have (or lack) at least one matching right tuple.
 #[derive(Debug)]
 pub struct JoinNode {
+    kind: JoinKind,
     /// Left side tuple descriptor (for element count).
     left_descriptor: TupleDescriptor,
     /// Output tuple descriptor (concat of left and right).
@@ -133,6 +138,7 @@ impl JoinNode {
         let output_descriptor = TupleDescriptor::concat(&left_desc, &right_desc);
 
         Some(Self {
+            kind: JoinKind::Inner,
             left_descriptor: left_desc,
             output_descriptor,
             left_key_spec,
@@ -166,6 +172,20 @@ impl JoinNode {
         Self::new(left_tuple_desc, right_tuple_desc, left_col, right_col)
     }
 
+    /// Join semantics this node applies.
+    pub fn kind(&self) -> JoinKind {
+        self.kind
+    }
+
+    /// Switch to a semi or anti join. Other kinds keep inner-join behaviour.
+    pub fn with_kind(mut self, kind: JoinKind) -> Self {
+        if kind.is_existential() {
+            self.kind = kind;
+            self.output_descriptor = self.left_descriptor.clone();
+        }
+        self
+    }
+
     /// Get the output tuple descriptor.
     pub fn output_tuple_descriptor(&self) -> &TupleDescriptor {
         &self.output_descriptor
@@ -474,25 +494,139 @@ impl JoinNode {
         removed_outputs
     }
 
+    /// Whether a left tuple currently belongs in a semi/anti join's output.
+    fn passes_existential(&self, left: &Tuple) -> bool {
+        let has_match = self
+            .extract_left_keys(left)
+            .iter()
+            .any(|key| self.right_by_key.contains_key(key));
+        has_match == matches!(self.kind, JoinKind::Semi)
+    }
+
+    fn add_left_existential(&mut self, tuple: Tuple) -> Option<Tuple> {
+        for key in self.extract_left_keys(&tuple) {
+            self.left_by_key
+                .entry(key)
+                .or_default()
+                .insert(tuple.clone());
+        }
+        let passes = self.passes_existential(&tuple);
+        self.left_tuples.insert(tuple.clone());
+        if !passes {
+            return None;
+        }
+        self.current_tuples.insert(tuple.clone());
+        Some(tuple)
+    }
+
+    fn remove_left_existential(&mut self, tuple: &Tuple) -> Option<Tuple> {
+        for key in self.extract_left_keys(tuple) {
+            if let Some(set) = self.left_by_key.get_mut(&key) {
+                set.remove(tuple);
+                if set.is_empty() {
+                    self.left_by_key.remove(&key);
+                }
+            }
+        }
+        self.left_tuples.remove(tuple);
+        self.current_tuples.take(tuple)
+    }
+
+    fn index_right_tuple(&mut self, tuple: Tuple, touched_keys: &mut AHashSet<Vec<u8>>) {
+        for key in self.extract_right_keys(&tuple) {
+            self.right_by_key
+                .entry(key.clone())
+                .or_default()
+                .insert(tuple.clone());
+            touched_keys.insert(key);
+        }
+        self.right_tuples.insert(tuple);
+    }
+
+    fn unindex_right_tuple(&mut self, tuple: &Tuple, touched_keys: &mut AHashSet<Vec<u8>>) {
+        for key in self.extract_right_keys(tuple) {
+            if let Some(set) = self.right_by_key.get_mut(&key) {
+                set.remove(tuple);
+                if set.is_empty() {
+                    self.right_by_key.remove(&key);
+                }
+            }
+            touched_keys.insert(key);
+        }
+        self.right_tuples.remove(tuple);
+    }
+
+    /// Apply a right-side delta for a semi/anti join.
+    ///
+    /// The whole delta is folded into the right index first, then every left
+    /// tuple sharing a touched key is re-checked once, so a right update that
+    /// keeps a match alive produces no output churn.
+    fn process_right_existential(&mut self, delta: TupleDelta) -> TupleDelta {
+        let mut touched_keys = AHashSet::new();
+        for tuple in &delta.removed {
+            self.unindex_right_tuple(tuple, &mut touched_keys);
+        }
+        for tuple in delta.added {
+            self.index_right_tuple(tuple, &mut touched_keys);
+        }
+        for (old_tuple, new_tuple) in delta.updated {
+            self.unindex_right_tuple(&old_tuple, &mut touched_keys);
+            self.index_right_tuple(new_tuple, &mut touched_keys);
+        }
+
+        let affected: AHashSet<Tuple> = touched_keys
+            .iter()
+            .filter_map(|key| self.left_by_key.get(key))
+            .flatten()
+            .cloned()
+            .collect();
+        let mut result = TupleDelta::new();
+        for left in affected {
+            let passes = self.passes_existential(&left);
+            if passes && !self.current_tuples.contains(&left) {
+                self.current_tuples.insert(left.clone());
+                result.added.push(left);
+            } else if !passes && let Some(emitted) = self.current_tuples.take(&left) {
+                result.removed.push(emitted);
+            }
+        }
+        result
+    }
+
     /// Process left side delta.
     pub fn process_left(&mut self, delta: TupleDelta) -> TupleDelta {
         let input_size = delta.added.len() + delta.removed.len() + delta.updated.len();
         let mut result = TupleDelta::new();
 
-        // Handle removals first
-        for tuple in delta.removed {
-            result.removed.extend(self.remove_left_tuple(&tuple));
-        }
+        if self.kind.is_existential() {
+            for tuple in delta.removed {
+                result.removed.extend(self.remove_left_existential(&tuple));
+            }
+            for tuple in delta.added {
+                result.added.extend(self.add_left_existential(tuple));
+            }
+            for (old_tuple, new_tuple) in delta.updated {
+                result
+                    .removed
+                    .extend(self.remove_left_existential(&old_tuple));
+                result.added.extend(self.add_left_existential(new_tuple));
+            }
+        } else {
+            // Handle removals first
+            for tuple in delta.removed {
+                result.removed.extend(self.remove_left_tuple(&tuple));
+            }
 
-        // Handle additions
-        for tuple in delta.added {
-            result.added.extend(self.add_left_tuple(tuple));
-        }
+            // Handle additions
+            for tuple in delta.added {
+                result.added.extend(self.add_left_tuple(tuple));
+            }
 
-        // Handle updates (remove old, add new)
-        for (old_tuple, new_tuple) in delta.updated {
-            result.removed.extend(self.remove_left_tuple(&old_tuple));
-            result.added.extend(self.add_left_tuple(new_tuple));
+            // Handle updates (remove old, add new)
+            for (old_tuple, new_tuple) in delta.updated {
+                result.removed.extend(self.remove_left_tuple(&old_tuple));
+                result.added.extend(self.add_left_tuple(new_tuple));
+            }
         }
 
         let output_size = result.added.len() + result.removed.len() + result.updated.len();
@@ -510,23 +644,28 @@ impl JoinNode {
     /// Process right side delta.
     pub fn process_right(&mut self, delta: TupleDelta) -> TupleDelta {
         let input_size = delta.added.len() + delta.removed.len() + delta.updated.len();
-        let mut result = TupleDelta::new();
-
-        // Handle removals first
-        for tuple in delta.removed {
-            result.removed.extend(self.remove_right_tuple(&tuple));
-        }
+        let result = if self.kind.is_existential() {
+            self.process_right_existential(delta)
+        } else {
+            let mut result = TupleDelta::new();
+
+            // Handle removals first
+            for tuple in delta.removed {
+                result.removed.extend(self.remove_right_tuple(&tuple));
+            }
 
-        // Handle additions
-        for tuple in delta.added {
-            result.added.extend(self.add_right_tuple(tuple));
-        }
+            // Handle additions
+            for tuple in delta.added {
+                result.added.extend(self.add_right_tuple(tuple));
+            }
 
-        // Handle updates (remove old, add new)
-        for (old_tuple, new_tuple) in delta.updated {
-            result.removed.extend(self.remove_right_tuple(&old_tuple));
-            result.added.extend(self.add_right_tuple(new_tuple));
-        }
+            // Handle updates (remove old, add new)
+            for (old_tuple, new_tuple) in delta.updated {
+                result.removed.extend(self.remove_right_tuple(&old_tuple));
+                result.added.extend(self.add_right_tuple(new_tuple));
+            }
+            result
+        };
 
         let output_size = result.added.len() + result.removed.len() + result.updated.len();
         tracing::trace!(
@@ -540,6 +679,61 @@ impl JoinNode {
         result
     }
 
+    /// Process both input deltas of one tick and return the combined output.
+    ///
+    /// Semi/anti joins must see the right side's final state before judging
+    /// newly added left tuples; otherwise a left row and its first match
+    /// arriving together would be emitted and retracted in the same delta.
+    pub fn process_inputs(&mut self, left: TupleDelta, right: TupleDelta) -> TupleDelta {
+        let mut result = TupleDelta::new();
+
+        if self.kind.is_existential() {
+            let input_size = left.added.len()
+                + left.removed.len()
+                + left.updated.len()
+                + right.added.len()
+                + right.removed.len()
+                + right.updated.len();
+
+            let mut left_added = left.added;
+            for tuple in left.removed {
+                result.removed.extend(self.remove_left_existential(&tuple));
+            }
+            for (old_tuple, new_tuple) in left.updated {
+                result
+                    .removed
+                    .extend(self.remove_left_existential(&old_tuple));
+                left_added.push(new_tuple);
+            }
+
+            let right_result = self.process_right_existential(right);
+            result.added.extend(right_result.added);
+            result.removed.extend(right_result.removed);
+
+            for tuple in left_added {
+                result.added.extend(self.add_left_existential(tuple));
+            }
+
+            tracing::trace!(
+                input_size,
+                output_size = result.added.len() + result.removed.len(),
+                side = "both",
+                "join node processed"
+            );
+            self.dirty = false;
+            return result;
+        }
+
+        // Process left side first, then right side
+        let left_result = self.process_left(left);
+        let right_result = self.process_right(right);
+        result.added.extend(left_result.added);
+        result.added.extend(right_result.added);
+        result.removed.extend(left_result.removed);
+        result.removed.extend(right_result.removed);
+        result
+    }
+
     /// Get current joined tuples.
     pub fn current_tuples(&self) -> &AHashSet<Tuple> {
         &self.current_tuples
@@ -1021,4 +1215,123 @@ mod tests {
             "Unqualified implicit id should fail when multiple left elements exist"
         );
     }
+
+    fn existential_node(kind: JoinKind) -> JoinNode {
+        JoinNode::from_row_descriptors(
+            "users",
+            users_descriptor(),
+            "posts",
+            posts_descriptor(),
+            "id",
+            "author_id",
+        )
+        .unwrap()
+        .with_kind(kind)
+    }
+
+    #[test]
+    fn semi_join_tracks_whether_any_match_remains() {
+        let mut node = existential_node(JoinKind::Semi);
+        let user = make_user_tuple(ObjectId::new(), 1, "Alice");
+        let first_post = make_post_tuple(ObjectId::new(), 100, "Hello", 1);
+        let second_post = make_post_tuple(ObjectId::new(), 101, "Again", 1);
+
+        let result = node.process_left(TupleDelta {
+            added: vec![user.clone()],
+            removed: vec![],
+            moved: vec![],
+            updated: vec![],
+        });
+        assert!(result.added.is_empty(), "No posts yet, so no match");
+
+        let result = node.process_right(TupleDelta {
+            added: vec![first_post.clone(), second_post.clone()],
+            removed: vec![],
+            moved: vec![],
+            updated: vec![],
+        });
+        assert_eq!(result.added, vec![user.clone()]);
+        assert_eq!(
+            result.added[0].len(),
+            1,
+            "Semi join emits the left tuple unchanged"
+        );
+
+        let result = node.process_right(TupleDelta {
+            added: vec![],
+            removed: vec![first_post],
+            moved: vec![],
+            updated: vec![],
+        });
+        assert!(result.added.is_empty() && result.removed.is_empty());
+
+        let result = node.process_right(TupleDelta {
+            added: vec![],
+            removed: vec![second_post],
+            moved: vec![],
+            updated: vec![],
+        });
+        assert_eq!(result.removed, vec![user]);
+        assert!(node.current_tuples().is_empty());
+    }
+
+    #[test]
+    fn anti_join_retracts_left_when_match_arrives() {
+        let mut node = existential_node(JoinKind::Anti);
+        let alice = make_user_tuple(ObjectId::new(), 1, "Alice");
+        let bob = make_user_tuple(ObjectId::new(), 2, "Bob");
+        let post = make_post_tuple(ObjectId::new(), 100, "Hello", 1);
+
+        let result = node.process_left(TupleDelta {
+            added: vec![alice.clone(), bob.clone()],
+            removed: vec![],
+            moved: vec![],
+            updated: vec![],
+        });
+        assert_eq!(result.added.len(), 2);
+
+        let result = node.process_right(TupleDelta {
+            added: vec![post.clone()],
+            removed: vec![],
+            moved: vec![],
+            updated: vec![],
+        });
+        assert_eq!(result.removed, vec![alice.clone()]);
+        assert!(node.current_tuples().contains(&bob));
+
+        let result = node.process_right(TupleDelta {
+            added: vec![],
+            removed: vec![post],
+            moved: vec![],
+            updated: vec![],
+        });
+        assert_eq!(result.added, vec![alice]);
+        assert_eq!(node.current_tuples().len(), 2);
+    }
+
+    #[test]
+    fn anti_join_nets_out_left_and_match_arriving_together() {
+        let mut node = existential_node(JoinKind::Anti);
+        let user = make_user_tuple(ObjectId::new(), 1, "Alice");
+        let post = make_post_tuple(ObjectId::new(), 100, "Hello", 1);
+
+        let result = node.process_inputs(
+            TupleDelta {
+                added: vec![user],
+                removed: vec![],
+                moved: vec![],
+                updated: vec![],
+            },
+            TupleDelta {
+                added: vec![post],
+                removed: vec![],
+                moved: vec![],
+                updated: vec![],
+            },
+        );
+
+        assert!(result.added.is_empty(), "User already has a post");
+        assert!(result.removed.is_empty(), "Nothing was emitted before");
+        assert!(node.current_tuples().is_empty());
+    }
 }
@@ -15,10 +15,13 @@ pub struct LimitOffsetNode {
     output_tuple_descriptor: TupleDescriptor,
     limit: Option<usize>,
     offset: usize,
+    /// Count the window back from the end of the ordered input instead of
+    /// forward from its start (backward keyset pages).
+    from_end: bool,
     /// Ordered tuples used to compute the current window.
     ///
     /// Ordered full-input rebuilds retain only the prefix through
-    /// `offset + limit` for limited queries. Incremental unordered updates may
+    /// `offset + limit` for limited queries (the suffix when `from_end`). Incremental unordered updates may
     /// retain more, so `sync_input_tuples()` still slices this to the replay
     /// prefix.
     all_tuples: Vec<Tuple>,
@@ -42,6 +45,7 @@ impl LimitOffsetNode {
             output_tuple_descriptor: tuple_descriptor,
             limit,
             offset,
+            from_end: false,
             all_tuples: Vec::new(),
             windowed_tuples: Vec::new(),
             current_tuples: AHashSet::new(),
@@ -49,17 +53,45 @@ impl LimitOffsetNode {
         }
     }
 
+    /// Take the window from the end of the ordered input: `offset` rows are
+    /// skipped from the end and the `limit` rows before them are kept.
+    pub fn with_window_from_end(mut self, from_end: bool) -> Self {
+        self.from_end = from_end;
+        self
+    }
+
     /// Get the output tuple descriptor.
     pub fn output_tuple_descriptor(&self) -> &TupleDescriptor {
         &self.output_tuple_descriptor
     }
 
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether the window is counted back from the end of the ordered input.
+    pub fn from_end(&self) -> bool {
+        self.from_end
+    }
+
     /// Recompute windowed_tuples from all_tuples based on limit/offset.
     fn recompute_tuple_window(&mut self) {
-        let start = self.offset.min(self.all_tuples.len());
-        let end = match self.limit {
-            Some(limit) => (start + limit).min(self.all_tuples.len()),
-            None => self.all_tuples.len(),
+        let len = self.all_tuples.len();
+        let (start, end) = if self.from_end {
+            let end = len - self.offset.min(len);
+            let start = self.limit.map_or(0, |limit| end.saturating_sub(limit));
+            (start, end)
+        } else {
+            let start = self.offset.min(len);
+            let end = match self.limit {
+                Some(limit) => (start + limit).min(len),
+                None => len,
+            };
+            (start, end)
         };
         self.windowed_tuples.clear();
         self.windowed_tuples
@@ -72,7 +104,7 @@ impl LimitOffsetNode {
         let old_tuples = std::mem::take(&mut self.windowed_tuples);
         self.all_tuples.clear();
         self.all_tuples
-            .extend_from_slice(&ordered_tuples[..self.sync_input_len(ordered_tuples.len())]);
+            .extend_from_slice(&ordered_tuples[self.sync_input_range(ordered_tuples.len())]);
         self.recompute_tuple_window();
         self.dirty = false;
         compute_tuple_delta(&old_tuples, &self.windowed_tuples)
@@ -86,22 +118,28 @@ impl LimitOffsetNode {
     /// Tuples that must be present locally to reproduce this paginated window.
     ///
     /// For offset-based pagination, the client must have the ordered prefix up to
-    /// `offset + limit` so it can reapply the same windowing logic locally.
+    /// `offset + limit` so it can reapply the same windowing logic locally
+    /// (the matching suffix for windows taken from the end).
     /// When no limit is present, that means the full ordered input.
     pub fn sync_input_tuples(&self) -> &[Tuple] {
-        &self.all_tuples[..self.sync_input_len(self.all_tuples.len())]
+        &self.all_tuples[self.sync_input_range(self.all_tuples.len())]
     }
 
     /// Tuples from an already-filtered ordered input that must be present locally
     /// to reproduce this paginated window.
     pub fn filtered_sync_input_tuples<'a>(&self, ordered_tuples: &'a [Tuple]) -> &'a [Tuple] {
-        &ordered_tuples[..self.sync_input_len(ordered_tuples.len())]
+        &ordered_tuples[self.sync_input_range(ordered_tuples.len())]
     }
 
-    fn sync_input_len(&self, input_len: usize) -> usize {
-        match self.limit {
+    fn sync_input_range(&self, input_len: usize) -> std::ops::Range<usize> {
+        let len = match self.limit {
             Some(limit) => self.offset.saturating_add(limit).min(input_len),
             None => input_len,
+        };
+        if self.from_end {
+            input_len - len..input_len
+        } else {
+            0..len
         }
     }
 }
@@ -289,6 +327,29 @@ mod tests {
         assert_eq!(windowed_ids[1], ids[2]);
     }
 
+    #[test]
+    fn window_from_end_keeps_rows_just_before_the_tail() {
+        let mut node = make_limit_offset_node(Some(2), 1).with_window_from_end(true);
+
+        let ids: Vec<_> = (0..5).map(|_| ObjectId::new()).collect();
+        let tuples: Vec<_> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| make_tuple(*id, i as i32, &format!("Row{}", i)))
+            .collect();
+
+        let result = node.process_with_ordered_input(&tuples);
+
+        assert_eq!(result.added.len(), 2);
+        assert_eq!(get_windowed_ids(&node), vec![ids[2], ids[3]]);
+        let sync_ids: Vec<_> = node
+            .sync_input_tuples()
+            .iter()
+            .map(|t| t.ids()[0])
+            .collect();
+        assert_eq!(sync_ids, vec![ids[2], ids[3], ids[4]]);
+    }
+
     #[test]
     fn windowed_tuples_keep_row_provenance_separate_from_sync_prefix() {
         let mut node = make_limit_offset_node(Some(2), 1);
@@ -7,6 +7,7 @@ pub mod join;
 pub mod limit_offset;
 pub mod magic_columns;
 pub mod materialize;
+pub mod nearest;
 pub mod output;
 pub(crate) mod policy_eval;
 pub mod policy_filter;
@@ -102,6 +103,7 @@ pub use join::JoinNode;
 pub use limit_offset::LimitOffsetNode;
 pub use magic_columns::MagicColumnsNode;
 pub use materialize::MaterializeNode;
+pub use nearest::NearestNode;
 pub use output::{OutputNode, QuerySubscriptionId};
 pub use policy_filter::PolicyFilterNode;
 pub use project::ProjectNode;
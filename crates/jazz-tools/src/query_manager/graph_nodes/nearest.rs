@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use ahash::AHashSet;
+
+use crate::object::ObjectId;
+use crate::query_manager::encoding::decode_column;
+use crate::query_manager::graph_nodes::tuple_delta::compute_tuple_delta;
+use crate::query_manager::types::{
+    RowDescriptor, Tuple, TupleDelta, TupleDescriptor, Value, Vector,
+};
+use crate::query_manager::vector_index::{DEFAULT_EF_SEARCH, HnswIndex};
+
+use super::RowNode;
+
+/// Top-k rows by cosine distance from a target vector, closest first.
+///
+/// Replaces the `Sort` + `LimitOffset` pair for `nearest()` queries: input
+/// rows are kept in an HNSW graph, and each settle re-ranks the target against
+/// it. Rows whose vector is `NULL` or has the wrong dimension are not ranked.
+#[derive(Debug)]
+pub struct NearestNode {
+    descriptor: RowDescriptor,
+    /// Output tuple descriptor (same as input - pass-through).
+    output_tuple_descriptor: TupleDescriptor,
+    col_index: usize,
+    target: Vector,
+    k: usize,
+    index: HnswIndex,
+    /// Ranked input tuples by row id.
+    tuples: HashMap<ObjectId, Tuple>,
+    /// Current top-k, closest first.
+    ordered_tuples: Vec<Tuple>,
+    /// HashSet view for trait requirement.
+    current_tuples: AHashSet<Tuple>,
+    dirty: bool,
+}
+
+impl NearestNode {
+    pub fn with_tuple_descriptor(
+        tuple_descriptor: TupleDescriptor,
+        col_index: usize,
+        target: Vector,
+        k: usize,
+    ) -> Self {
+        let descriptor = tuple_descriptor.combined_descriptor();
+        Self {
+            descriptor,
+            output_tuple_descriptor: tuple_descriptor,
+            col_index,
+            target,
+            k,
+            index: HnswIndex::new(),
+            tuples: HashMap::new(),
+            ordered_tuples: Vec::new(),
+            current_tuples: AHashSet::new(),
+            dirty: true,
+        }
+    }
+
+    /// Get the output tuple descriptor.
+    pub fn output_tuple_descriptor(&self) -> &TupleDescriptor {
+        &self.output_tuple_descriptor
+    }
+
+    pub fn target(&self) -> &Vector {
+        &self.target
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn column_name(&self) -> Option<&str> {
+        self.descriptor
+            .columns
+            .get(self.col_index)
+            .map(|column| column.name.as_str())
+    }
+
+    /// Current top-k, closest first.
+    pub fn ordered_tuples(&self) -> &[Tuple] {
+        &self.ordered_tuples
+    }
+
+    fn vector_of(&self, tuple: &Tuple) -> Option<Vector> {
+        let content = tuple.get(0)?.content()?;
+        match decode_column(&self.descriptor, content, self.col_index).ok()? {
+            Value::Vector(vector) if vector.dims() == self.target.dims() => Some(vector),
+            _ => None,
+        }
+    }
+
+    fn remember(&mut self, tuple: Tuple) {
+        let Some(id) = tuple.first_id() else {
+            return;
+        };
+        match self.vector_of(&tuple) {
+            Some(vector) => {
+                self.index.insert(id, vector);
+                self.tuples.insert(id, tuple);
+            }
+            None => self.forget(&tuple),
+        }
+    }
+
+    fn forget(&mut self, tuple: &Tuple) {
+        if let Some(id) = tuple.first_id() {
+            self.index.remove(id);
+            self.tuples.remove(&id);
+        }
+    }
+}
+
+impl RowNode for NearestNode {
+    fn output_descriptor(&self) -> &RowDescriptor {
+        &self.descriptor
+    }
+
+    fn process(&mut self, input: TupleDelta) -> TupleDelta {
+        for tuple in &input.removed {
+            self.forget(tuple);
+        }
+        for (old, new) in input.updated {
+            self.forget(&old);
+            self.remember(new);
+        }
+        for tuple in input.added {
+            self.remember(tuple);
+        }
+
+        let old_tuples = std::mem::take(&mut self.ordered_tuples);
+        self.ordered_tuples = self
+            .index
+            .search(&self.target, self.k, DEFAULT_EF_SEARCH)
+            .into_iter()
+            .filter_map(|(id, _)| self.tuples.get(&id).cloned())
+            .collect();
+        self.current_tuples = self.ordered_tuples.iter().cloned().collect();
+        self.dirty = false;
+        compute_tuple_delta(&old_tuples, &self.ordered_tuples)
+    }
+
+    fn current_tuples(&self) -> &AHashSet<Tuple> {
+        &self.current_tuples
+    }
+
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
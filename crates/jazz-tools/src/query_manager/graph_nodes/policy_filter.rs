@@ -239,6 +239,16 @@ impl PolicyFilterNode {
         &self.inherits_tables
     }
 
+    /// Table whose rows this node checks.
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    /// Operation the policy is evaluated for.
+    pub fn operation(&self) -> Operation {
+        self.policy_operation
+    }
+
     /// Mark that a dependency table has changed.
     pub fn mark_inherits_dirty(&mut self) {
         self.inherits_dirty = true;
@@ -590,7 +600,9 @@ mod tests {
     use crate::query_manager::encoding::encode_row;
     use crate::query_manager::policy::{CmpOp, PolicyValue};
     use crate::query_manager::relation_ir::RelExpr;
-    use crate::query_manager::types::{ColumnDescriptor, ColumnType, TableName, Value};
+    use crate::query_manager::types::{
+        Annotations, ColumnDescriptor, ColumnLimits, ColumnType, TableName, Value, Visibility,
+    };
     use serde_json::json;
 
     fn test_descriptor() -> RowDescriptor {
@@ -835,6 +847,9 @@ mod tests {
                 references: Some(TableName::new("folders")),
                 default: None,
                 merge_strategy: None,
+                limits: ColumnLimits::default(),
+                visibility: Visibility::Public,
+                annotations: Annotations::new(),
             },
             ColumnDescriptor::new("title", ColumnType::Text),
         ]);
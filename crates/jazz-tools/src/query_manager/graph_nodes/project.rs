@@ -3,9 +3,10 @@ use ahash::AHashSet;
 use crate::query_manager::encoding::{decode_column, encode_row};
 use crate::query_manager::graph_nodes::tuple_delta::compute_tuple_delta;
 use crate::query_manager::relation_ir::{ProjectColumn, ProjectExpr, RowIdRef};
+use crate::query_manager::scalar_expr::{BoundScalarExpr, render_text};
 use crate::query_manager::types::{
-    ColumnDescriptor, ColumnName, ColumnType, RowDescriptor, Tuple, TupleDelta, TupleDescriptor,
-    TupleElement, Value,
+    Annotations, ColumnDescriptor, ColumnLimits, ColumnName, ColumnType, RowDescriptor, Tuple,
+    TupleDelta, TupleDescriptor, TupleElement, Value, Visibility,
 };
 
 use super::RowNode;
@@ -14,6 +15,7 @@ use super::RowNode;
 enum ProjectionSource {
     Column { global_index: usize },
     RowId { element_index: usize },
+    Expr(BoundScalarExpr),
 }
 
 #[derive(Debug, Clone)]
@@ -98,6 +100,9 @@ impl ProjectNode {
                         .map(|element_index| ProjectionSource::RowId { element_index })
                 }
                 ProjectExpr::RowId(_) => None,
+                ProjectExpr::Expr(expr) => Some(ProjectionSource::Expr(
+                    BoundScalarExpr::bind(expr, &input_tuple_descriptor).ok()?,
+                )),
             }) else {
                 continue;
             };
@@ -117,6 +122,9 @@ impl ProjectNode {
                         references: source_column.references,
                         default: source_column.default.clone(),
                         merge_strategy: source_column.merge_strategy,
+                        limits: source_column.limits,
+                        visibility: source_column.visibility,
+                        annotations: source_column.annotations.clone(),
                     }
                 }
                 ProjectionSource::RowId { .. } => ColumnDescriptor {
@@ -126,6 +134,22 @@ impl ProjectNode {
                     references: None,
                     default: None,
                     merge_strategy: None,
+                    limits: ColumnLimits::default(),
+                    visibility: Visibility::Public,
+                    annotations: Annotations::new(),
+                },
+                // Computed values are always nullable: NULL operands, overflow and
+                // division by zero all evaluate to NULL. An untyped NULL projects as text.
+                ProjectionSource::Expr(expr) => ColumnDescriptor {
+                    name: ColumnName::new(column.alias.clone()),
+                    column_type: expr.output_type().cloned().unwrap_or(ColumnType::Text),
+                    nullable: true,
+                    references: None,
+                    default: None,
+                    merge_strategy: None,
+                    limits: ColumnLimits::default(),
+                    visibility: Visibility::Public,
+                    annotations: Annotations::new(),
                 },
             };
 
@@ -211,6 +235,14 @@ impl ProjectNode {
             ProjectionSource::RowId { element_index } => {
                 Some(Value::Uuid(tuple.get(*element_index)?.id()))
             }
+            ProjectionSource::Expr(expr) => {
+                let value = expr.evaluate(tuple, &self.input_tuple_descriptor);
+                // Untyped results (JSON paths) are declared as text columns.
+                if expr.output_type().is_none() && !value.is_null() {
+                    return Some(render_text(&value).map_or(Value::Null, Value::Text));
+                }
+                Some(value)
+            }
         }
     }
 
@@ -557,4 +589,85 @@ mod tests {
         assert_eq!(node.output_descriptor().columns[0].references, None);
         assert_eq!(node.output_descriptor().columns[0].name, "row_id");
     }
+
+    #[test]
+    fn precise_project_computes_expressions() {
+        use crate::query_manager::relation_ir::{ArithmeticOp, ScalarExpr};
+
+        let descriptor = test_descriptor();
+        let input = TupleDescriptor::single_with_materialization("users", descriptor, true);
+        let mut node = ProjectNode::with_project_columns(
+            input,
+            &[
+                ProjectColumn {
+                    alias: "label".into(),
+                    expr: ProjectExpr::Expr(ScalarExpr::Concat(vec![
+                        ScalarExpr::Upper(Box::new(ScalarExpr::Column(ColumnRef::unscoped(
+                            "name",
+                        )))),
+                        ScalarExpr::Literal(Value::Text(" <".into())),
+                        ScalarExpr::Column(ColumnRef::unscoped("email")),
+                        ScalarExpr::Literal(Value::Text(">".into())),
+                    ])),
+                },
+                ProjectColumn {
+                    alias: "age_next_year".into(),
+                    expr: ProjectExpr::Expr(ScalarExpr::Arithmetic {
+                        op: ArithmeticOp::Add,
+                        left: Box::new(ScalarExpr::Column(ColumnRef::unscoped("age"))),
+                        right: Box::new(ScalarExpr::Literal(Value::Integer(1))),
+                    }),
+                },
+            ],
+        )
+        .expect("computed projection should build");
+
+        let output = node.output_descriptor();
+        assert_eq!(output.columns[0].column_type, ColumnType::Text);
+        assert_eq!(output.columns[1].column_type, ColumnType::Integer);
+        assert!(output.columns[1].nullable);
+
+        let result = node.process(TupleDelta {
+            added: vec![make_tuple(
+                ObjectId::new(),
+                &[
+                    Value::Integer(1),
+                    Value::Text("Alice".into()),
+                    Value::Text("alice@example.com".into()),
+                    Value::Integer(30),
+                ],
+            )],
+            removed: vec![],
+            moved: vec![],
+            updated: vec![],
+        });
+        let row = result.added[0].to_single_row().unwrap();
+        let values = decode_row(node.output_descriptor(), &row.data).unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                Value::Text("ALICE <alice@example.com>".into()),
+                Value::Integer(31),
+            ]
+        );
+    }
+
+    #[test]
+    fn precise_project_rejects_ill_typed_expressions() {
+        use crate::query_manager::relation_ir::ScalarExpr;
+
+        let input = TupleDescriptor::single_with_materialization("users", test_descriptor(), true);
+        let node = ProjectNode::with_project_columns(
+            input,
+            &[ProjectColumn {
+                alias: "shout".into(),
+                expr: ProjectExpr::Expr(ScalarExpr::Upper(Box::new(ScalarExpr::Column(
+                    ColumnRef::unscoped("age"),
+                )))),
+            }],
+        );
+
+        assert!(node.is_none());
+    }
 }
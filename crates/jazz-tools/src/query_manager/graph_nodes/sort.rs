@@ -3,8 +3,9 @@ use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
 use crate::object::ObjectId;
-use crate::query_manager::encoding::compare_column;
-use crate::query_manager::types::{RowDescriptor, Tuple, TupleDelta, TupleDescriptor};
+use crate::query_manager::encoding::{compare_column, decode_column};
+use crate::query_manager::scalar_expr::{BoundScalarExpr, compare_values};
+use crate::query_manager::types::{RowDescriptor, Tuple, TupleDelta, TupleDescriptor, Value};
 
 use super::RowNode;
 
@@ -31,12 +32,38 @@ pub enum SortTarget {
     /// This is needed because object ID is not part of row payload columns,
     /// but query semantics allow `ORDER BY id|_id` (including desc and mixed keys).
     RowId,
+    /// Computed sort key, indexing into the sort node's bound expressions.
+    Computed(usize),
 }
 
 /// Threshold: when adding more than this many tuples, use bulk append + sort
 /// instead of individual binary-search inserts.
 const BULK_ADD_THRESHOLD: usize = 16;
 
+/// Bound expressions referenced by [`SortTarget::Computed`] keys.
+#[derive(Debug, Clone)]
+struct ComputedSortKeys {
+    tuple_descriptor: TupleDescriptor,
+    exprs: Vec<BoundScalarExpr>,
+}
+
+impl ComputedSortKeys {
+    /// NULLs sort first, matching how column keys order missing values.
+    fn compare(&self, index: usize, a: &Tuple, b: &Tuple) -> Ordering {
+        let Some(expr) = self.exprs.get(index) else {
+            return Ordering::Equal;
+        };
+        let a_value = expr.evaluate(a, &self.tuple_descriptor);
+        let b_value = expr.evaluate(b, &self.tuple_descriptor);
+        match (a_value.is_null(), b_value.is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => compare_values(&a_value, &b_value).unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
 /// Compare two tuples by sort keys without borrowing self.
 ///
 /// Extracted as a free function so it can be used inside `sort_unstable_by`
@@ -44,6 +71,7 @@ const BULK_ADD_THRESHOLD: usize = 16;
 fn compare_tuples_with(
     sort_keys: &[SortKey],
     descriptor: &RowDescriptor,
+    computed: &ComputedSortKeys,
     a: &Tuple,
     b: &Tuple,
 ) -> Ordering {
@@ -62,6 +90,7 @@ fn compare_tuples_with(
                 (None, None) => Ordering::Equal,
             },
             SortTarget::RowId => compare_all_ids(a, b),
+            SortTarget::Computed(index) => computed.compare(index, a, b),
         };
 
         let ord = match key.direction {
@@ -101,6 +130,7 @@ pub struct SortNode {
     /// Output tuple descriptor (same as input - pass-through).
     output_tuple_descriptor: TupleDescriptor,
     sort_keys: Vec<SortKey>,
+    computed: ComputedSortKeys,
     /// Current sorted tuples.
     sorted_tuples: Vec<Tuple>,
     /// HashSet view of current tuples (for trait requirement).
@@ -113,10 +143,24 @@ impl SortNode {
     pub fn with_tuple_descriptor(
         tuple_descriptor: TupleDescriptor,
         sort_keys: Vec<SortKey>,
+    ) -> Self {
+        Self::with_computed_keys(tuple_descriptor, sort_keys, Vec::new())
+    }
+
+    /// Create a SortNode whose `SortTarget::Computed(i)` keys evaluate
+    /// `computed_keys[i]` against each tuple.
+    pub fn with_computed_keys(
+        tuple_descriptor: TupleDescriptor,
+        sort_keys: Vec<SortKey>,
+        computed_keys: Vec<BoundScalarExpr>,
     ) -> Self {
         let descriptor = tuple_descriptor.combined_descriptor();
         Self {
             descriptor,
+            computed: ComputedSortKeys {
+                tuple_descriptor: tuple_descriptor.clone(),
+                exprs: computed_keys,
+            },
             output_tuple_descriptor: tuple_descriptor,
             sort_keys,
             sorted_tuples: Vec::new(),
@@ -134,15 +178,41 @@ impl SortNode {
     fn find_tuple_position(&self, tuple: &Tuple) -> usize {
         let sort_keys = &self.sort_keys;
         let descriptor = &self.descriptor;
+        let computed = &self.computed;
         self.sorted_tuples
-            .binary_search_by(|t| compare_tuples_with(sort_keys, descriptor, t, tuple))
+            .binary_search_by(|t| compare_tuples_with(sort_keys, descriptor, computed, t, tuple))
             .unwrap_or_else(|pos| pos)
     }
 
+    /// Keys in priority order.
+    pub fn sort_keys(&self) -> &[SortKey] {
+        &self.sort_keys
+    }
+
     /// Full current ordering after sort has been applied.
     pub fn sorted_tuples(&self) -> &[Tuple] {
         &self.sorted_tuples
     }
+
+    /// Values a tuple sorts by, one per sort key, as read by the comparator.
+    pub fn sort_key_values(&self, tuple: &Tuple) -> Vec<Value> {
+        let content = tuple.get(0).and_then(|e| e.content());
+        self.sort_keys
+            .iter()
+            .map(|key| match key.target {
+                SortTarget::Column(col_index) => content
+                    .and_then(|data| decode_column(&self.descriptor, data, col_index).ok())
+                    .unwrap_or(Value::Null),
+                SortTarget::RowId => tuple.first_id().map(Value::Uuid).unwrap_or(Value::Null),
+                SortTarget::Computed(index) => self
+                    .computed
+                    .exprs
+                    .get(index)
+                    .map(|expr| expr.evaluate(tuple, &self.computed.tuple_descriptor))
+                    .unwrap_or(Value::Null),
+            })
+            .collect()
+    }
 }
 
 impl RowNode for SortNode {
@@ -196,8 +266,10 @@ impl RowNode for SortNode {
                 }
                 let sort_keys = &self.sort_keys;
                 let descriptor = &self.descriptor;
-                self.sorted_tuples
-                    .sort_unstable_by(|a, b| compare_tuples_with(sort_keys, descriptor, a, b));
+                let computed = &self.computed;
+                self.sorted_tuples.sort_unstable_by(|a, b| {
+                    compare_tuples_with(sort_keys, descriptor, computed, a, b)
+                });
             } else {
                 // Incremental path: binary search + insert for small batches.
                 for tuple in input
@@ -600,4 +672,67 @@ mod tests {
         expected.sort();
         assert_eq!(sorted_ids, expected);
     }
+
+    // Scenario: descending sort by a computed expression (score % 7).
+    //
+    // ASCII:
+    // input:   [A:100 -> 2, B:50 -> 1, C:75 -> 5]
+    // sorted:  [C, A, B]
+    #[test]
+    fn sort_by_computed_expression() {
+        use crate::query_manager::relation_ir::{ArithmeticOp, ColumnRef, ScalarExpr};
+
+        let tuple_desc = TupleDescriptor::single_with_materialization("", test_descriptor(), true);
+        let expr = ScalarExpr::Arithmetic {
+            op: ArithmeticOp::Mod,
+            left: Box::new(ScalarExpr::Column(ColumnRef::unscoped("score"))),
+            right: Box::new(ScalarExpr::Literal(Value::Integer(7))),
+        };
+        let bound = BoundScalarExpr::bind(&expr, &tuple_desc).unwrap();
+        let mut node = SortNode::with_computed_keys(
+            tuple_desc,
+            vec![SortKey {
+                target: SortTarget::Computed(0),
+                direction: SortDirection::Descending,
+            }],
+            vec![bound],
+        );
+
+        let id1 = ObjectId::new();
+        let id2 = ObjectId::new();
+        let id3 = ObjectId::new();
+        let tuple1 = make_tuple(
+            id1,
+            &[
+                Value::Integer(1),
+                Value::Text("A".into()),
+                Value::Integer(100),
+            ],
+        );
+        let tuple2 = make_tuple(
+            id2,
+            &[
+                Value::Integer(2),
+                Value::Text("B".into()),
+                Value::Integer(50),
+            ],
+        );
+        let tuple3 = make_tuple(
+            id3,
+            &[
+                Value::Integer(3),
+                Value::Text("C".into()),
+                Value::Integer(75),
+            ],
+        );
+
+        node.process(TupleDelta {
+            added: vec![tuple1, tuple2, tuple3],
+            removed: vec![],
+            moved: vec![],
+            updated: vec![],
+        });
+
+        assert_eq!(get_sorted_ids(&node), vec![id3, id1, id2]);
+    }
 }
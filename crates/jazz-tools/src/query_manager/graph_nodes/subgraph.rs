@@ -132,6 +132,12 @@ impl SubgraphTemplate {
                     crate::query_manager::query::Condition::IsNotNull { column } => {
                         query_builder.filter_is_not_null(column)
                     }
+                    crate::query_manager::query::Condition::WithinBox { column, bounds } => {
+                        query_builder.filter_within_box(column, *bounds)
+                    }
+                    crate::query_manager::query::Condition::WithinRadius { column, circle } => {
+                        query_builder.filter_within_radius(column, *circle)
+                    }
                 };
             }
         }
@@ -5,6 +5,7 @@ use crate::storage::{IndexMutation, Storage, StorageError, validate_index_value_
 use crate::row_format::CompiledRowLayout;
 
 use super::encoding::decode_column;
+use super::json_path::JsonPath;
 use super::manager::{QueryError, QueryManager};
 use super::types::{ColumnDescriptor, ColumnName, ColumnType, RowDescriptor, TableName, Value};
 
@@ -104,13 +105,97 @@ impl QueryManager {
                 Self::validate_column_index_values(table, column, branch, value)?;
             }
         }
+        for (name, path, col_idx) in Self::json_path_indices(descriptor, indexed_columns) {
+            let Some(column_value) = values.get(col_idx) else {
+                continue;
+            };
+            let index_value = path.extract(column_value);
+            if index_value != Value::Null {
+                validate_index_value_size(table, name, branch, &index_value)
+                    .map_err(Self::map_index_storage_error)?;
+            }
+        }
         Ok(())
     }
 
+    /// Declared JSON path indices whose base column is a `Json` column, with
+    /// the index name and the base column position.
+    fn json_path_indices<'a>(
+        descriptor: &RowDescriptor,
+        indexed_columns: Option<&'a [ColumnName]>,
+    ) -> Vec<(&'a str, JsonPath, usize)> {
+        indexed_columns
+            .into_iter()
+            .flatten()
+            .filter_map(|name| {
+                let path = JsonPath::parse(name.as_str())?;
+                let col_idx = descriptor.column_index(path.column())?;
+                matches!(
+                    descriptor.columns[col_idx].column_type,
+                    ColumnType::Json { .. }
+                )
+                .then_some((name.as_str(), path, col_idx))
+            })
+            .collect()
+    }
+
+    /// Scalars for each declared JSON path index (`NULL` when the path is absent).
+    fn json_path_index_values<'a>(
+        descriptor: &RowDescriptor,
+        indexed_columns: Option<&'a [ColumnName]>,
+        data: &[u8],
+    ) -> Vec<(&'a str, Value)> {
+        Self::json_path_indices(descriptor, indexed_columns)
+            .into_iter()
+            .map(|(name, path, col_idx)| {
+                let value = decode_column(descriptor, data, col_idx)
+                    .map(|column_value| path.extract(&column_value))
+                    .unwrap_or(Value::Null);
+                (name, value)
+            })
+            .collect()
+    }
+
+    fn push_json_path_index_mutations<'a>(
+        mutations: &mut Vec<IndexMutation<'a>>,
+        table: &'a str,
+        branch: &'a str,
+        object_id: ObjectId,
+        values: Vec<(&'a str, Value)>,
+        insert: bool,
+    ) {
+        for (column, value) in values {
+            if value == Value::Null {
+                continue;
+            }
+            mutations.push(if insert {
+                IndexMutation::Insert {
+                    table,
+                    column,
+                    branch,
+                    value,
+                    row_id: object_id,
+                }
+            } else {
+                IndexMutation::Remove {
+                    table,
+                    column,
+                    branch,
+                    value,
+                    row_id: object_id,
+                }
+            });
+        }
+    }
+
     fn should_index_column(
         indexed_columns: Option<&[ColumnName]>,
         column: &ColumnDescriptor,
     ) -> bool {
+        // Embeddings have no useful order; nearest queries use HNSW instead.
+        if matches!(column.column_type, ColumnType::Vector { .. }) {
+            return false;
+        }
         indexed_columns.is_none_or(|columns| columns.contains(&column.name))
     }
 
@@ -207,6 +292,14 @@ impl QueryManager {
                 );
             }
         }
+        Self::push_json_path_index_mutations(
+            &mut mutations,
+            table,
+            branch,
+            object_id,
+            Self::json_path_index_values(descriptor, indexed_columns, data),
+            true,
+        );
 
         mutations
     }
@@ -259,6 +352,30 @@ impl QueryManager {
             }
         }
 
+        let old_paths = Self::json_path_index_values(descriptor, indexed_columns, old_data);
+        let new_paths = Self::json_path_index_values(descriptor, indexed_columns, new_data);
+        let (removed, inserted): (Vec<_>, Vec<_>) = old_paths
+            .into_iter()
+            .zip(new_paths)
+            .filter(|(old, new)| old.1 != new.1)
+            .unzip();
+        Self::push_json_path_index_mutations(
+            &mut mutations,
+            table,
+            branch,
+            object_id,
+            removed,
+            false,
+        );
+        Self::push_json_path_index_mutations(
+            &mut mutations,
+            table,
+            branch,
+            object_id,
+            inserted,
+            true,
+        );
+
         mutations
     }
 
@@ -295,6 +412,14 @@ impl QueryManager {
                 );
             }
         }
+        Self::push_json_path_index_mutations(
+            &mut mutations,
+            table,
+            branch,
+            object_id,
+            Self::json_path_index_values(descriptor, indexed_columns, old_data),
+            false,
+        );
 
         mutations.push(IndexMutation::Insert {
             table,
@@ -341,6 +466,14 @@ impl QueryManager {
                     );
                 }
             }
+            Self::push_json_path_index_mutations(
+                &mut mutations,
+                table,
+                branch,
+                object_id,
+                Self::json_path_index_values(descriptor, indexed_columns, data),
+                false,
+            );
         }
 
         mutations.push(IndexMutation::Remove {
@@ -396,6 +529,14 @@ impl QueryManager {
                 );
             }
         }
+        Self::push_json_path_index_mutations(
+            &mut mutations,
+            table,
+            branch,
+            object_id,
+            Self::json_path_index_values(descriptor, indexed_columns, new_data),
+            true,
+        );
 
         mutations
     }
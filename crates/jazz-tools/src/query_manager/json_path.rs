@@ -0,0 +1,247 @@
+//! JSON path selectors over `Json` columns.
+//!
+//! A selector names a `Json` column followed by one or more `->` steps, for
+//! example `settings->theme` or `settings->tags->0`. Object steps look up a
+//! key (optionally single-quoted, `settings->'theme'`); numeric steps also
+//! index into arrays.
+//!
+//! Selectors are accepted wherever a condition or `order_by` takes a column
+//! name, and may be listed in `TableSchema::indexed_columns` to maintain an
+//! expression index on the path. Extraction yields scalar [`Value`]s: strings
+//! become `Text`, numbers `Double`, booleans `Boolean`, and nested objects or
+//! arrays their JSON text. A missing key or a JSON `null` yields `NULL`, so
+//! `IsNotNull` on a path tests that the key is present with a non-null value.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use serde_json::Value as JsonValue;
+
+use crate::query_manager::relation_ir::PredicateCmpOp;
+use crate::query_manager::scalar_expr::compare_values;
+use crate::query_manager::types::Value;
+
+/// Separator between a column and each step of a JSON path selector.
+pub const JSON_PATH_SEPARATOR: &str = "->";
+
+/// A parsed `column->step->...` selector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JsonPath {
+    column: String,
+    steps: Vec<String>,
+}
+
+impl JsonPath {
+    pub fn new(column: impl Into<String>, steps: Vec<String>) -> Self {
+        Self {
+            column: column.into(),
+            steps,
+        }
+    }
+
+    /// Parse a selector; returns `None` for plain column names.
+    pub fn parse(selector: &str) -> Option<Self> {
+        let mut parts = selector.split(JSON_PATH_SEPARATOR).map(str::trim);
+        let column = parts.next()?;
+        let steps: Vec<String> = parts.map(|step| unquote(step).to_string()).collect();
+        if column.is_empty() || steps.is_empty() || steps.iter().any(String::is_empty) {
+            return None;
+        }
+        Some(Self::new(column, steps))
+    }
+
+    /// True when `selector` reaches into a JSON column rather than naming one.
+    pub fn is_selector(selector: &str) -> bool {
+        selector.contains(JSON_PATH_SEPARATOR)
+    }
+
+    /// The `Json` column the path starts from.
+    pub fn column(&self) -> &str {
+        &self.column
+    }
+
+    pub fn steps(&self) -> &[String] {
+        &self.steps
+    }
+
+    /// Walk the path through a parsed document.
+    pub fn lookup<'a>(&self, document: &'a JsonValue) -> Option<&'a JsonValue> {
+        self.steps
+            .iter()
+            .try_fold(document, |current, step| match current {
+                JsonValue::Object(fields) => fields.get(step),
+                JsonValue::Array(items) => step.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => None,
+            })
+    }
+
+    /// Resolve the path against a stored `Json` column value.
+    pub fn resolve(&self, column_value: &Value) -> Option<JsonValue> {
+        let Value::Text(raw) = column_value else {
+            return None;
+        };
+        let document: JsonValue = serde_json::from_str(raw).ok()?;
+        self.lookup(&document).cloned()
+    }
+
+    /// Scalar at this path, or `NULL` when the path is absent.
+    pub fn extract(&self, column_value: &Value) -> Value {
+        self.resolve(column_value)
+            .map(|found| json_scalar(&found))
+            .unwrap_or(Value::Null)
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.column)?;
+        for step in &self.steps {
+            write!(f, "{JSON_PATH_SEPARATOR}{step}")?;
+        }
+        Ok(())
+    }
+}
+
+fn unquote(step: &str) -> &str {
+    step.strip_prefix('\'')
+        .and_then(|inner| inner.strip_suffix('\''))
+        .unwrap_or(step)
+}
+
+/// Map a JSON value onto the scalar used for comparisons, sorting and indexing.
+pub fn json_scalar(value: &JsonValue) -> Value {
+    match value {
+        JsonValue::Null => Value::Null,
+        JsonValue::Bool(flag) => Value::Boolean(*flag),
+        JsonValue::Number(number) => number.as_f64().map(Value::Double).unwrap_or(Value::Null),
+        JsonValue::String(text) => Value::Text(text.clone()),
+        JsonValue::Array(_) | JsonValue::Object(_) => Value::Text(value.to_string()),
+    }
+}
+
+/// Bring a query literal into the shape [`json_scalar`] produces, so integer
+/// literals compare (and seek indices) against JSON numbers.
+pub fn normalize_literal(value: &Value) -> Value {
+    match value {
+        Value::Integer(number) => Value::Double(f64::from(*number)),
+        Value::BigInt(number) => Value::Double(*number as f64),
+        other => other.clone(),
+    }
+}
+
+/// What a filter checks about the value found at a JSON path.
+#[derive(Debug, Clone)]
+pub enum JsonPathTest {
+    Compare {
+        op: PredicateCmpOp,
+        value: Value,
+    },
+    /// The path holds an array with an element equal to the value.
+    Contains(Value),
+    IsNull,
+    IsNotNull,
+}
+
+impl JsonPathTest {
+    /// Evaluate against the (possibly absent) value at the path. Comparisons
+    /// follow column semantics: `NULL` only satisfies `!=`.
+    pub fn matches(&self, found: Option<&JsonValue>) -> bool {
+        let scalar = found.map(json_scalar).unwrap_or(Value::Null);
+        match self {
+            JsonPathTest::Compare { op, value } if value.is_null() => match op {
+                PredicateCmpOp::Eq | PredicateCmpOp::Le => scalar.is_null(),
+                PredicateCmpOp::Ne | PredicateCmpOp::Gt => !scalar.is_null(),
+                PredicateCmpOp::Lt => false,
+                PredicateCmpOp::Ge => true,
+            },
+            JsonPathTest::Compare { op, value } => {
+                if scalar.is_null() {
+                    return matches!(op, PredicateCmpOp::Ne);
+                }
+                let ordering = compare_values(&scalar, &normalize_literal(value));
+                match op {
+                    PredicateCmpOp::Eq => ordering == Some(Ordering::Equal),
+                    PredicateCmpOp::Ne => ordering != Some(Ordering::Equal),
+                    PredicateCmpOp::Lt => ordering == Some(Ordering::Less),
+                    PredicateCmpOp::Le => {
+                        matches!(ordering, Some(Ordering::Less | Ordering::Equal))
+                    }
+                    PredicateCmpOp::Gt => ordering == Some(Ordering::Greater),
+                    PredicateCmpOp::Ge => {
+                        matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+                    }
+                }
+            }
+            JsonPathTest::Contains(value) => {
+                let Some(JsonValue::Array(items)) = found else {
+                    return false;
+                };
+                let needle = normalize_literal(value);
+                items.iter().any(|item| json_scalar(item) == needle)
+            }
+            JsonPathTest::IsNull => scalar.is_null(),
+            JsonPathTest::IsNotNull => !scalar.is_null(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> Value {
+        Value::Text(r#"{"theme":"dark","font":{"size":14},"tags":["a","b"],"beta":null}"#.into())
+    }
+
+    #[test]
+    fn parse_splits_column_and_steps() {
+        let path = JsonPath::parse("settings -> font -> 'size'").unwrap();
+        assert_eq!(path.column(), "settings");
+        assert_eq!(path.steps(), ["font".to_string(), "size".to_string()]);
+        assert_eq!(path.to_string(), "settings->font->size");
+        assert_eq!(JsonPath::parse("settings"), None);
+        assert_eq!(JsonPath::parse("settings->"), None);
+    }
+
+    #[test]
+    fn extract_maps_json_values_to_scalars() {
+        let value = settings();
+        let extract = |selector: &str| JsonPath::parse(selector).unwrap().extract(&value);
+        assert_eq!(extract("settings->theme"), Value::Text("dark".into()));
+        assert_eq!(extract("settings->font->size"), Value::Double(14.0));
+        assert_eq!(extract("settings->tags->1"), Value::Text("b".into()));
+        assert_eq!(
+            extract("settings->tags"),
+            Value::Text(r#"["a","b"]"#.into())
+        );
+        assert_eq!(extract("settings->beta"), Value::Null);
+        assert_eq!(extract("settings->missing"), Value::Null);
+    }
+
+    #[test]
+    fn tests_compare_numbers_and_check_array_membership() {
+        let value = settings();
+        let resolve = |selector: &str| JsonPath::parse(selector).unwrap().resolve(&value);
+
+        let size = resolve("settings->font->size");
+        let at_least_twelve = JsonPathTest::Compare {
+            op: PredicateCmpOp::Ge,
+            value: Value::Integer(12),
+        };
+        assert!(at_least_twelve.matches(size.as_ref()));
+
+        let tags = resolve("settings->tags");
+        assert!(JsonPathTest::Contains(Value::Text("a".into())).matches(tags.as_ref()));
+        assert!(!JsonPathTest::Contains(Value::Text("z".into())).matches(tags.as_ref()));
+
+        let missing = resolve("settings->missing");
+        assert!(JsonPathTest::IsNull.matches(missing.as_ref()));
+        assert!(
+            JsonPathTest::Compare {
+                op: PredicateCmpOp::Ne,
+                value: Value::Text("dark".into()),
+            }
+            .matches(missing.as_ref())
+        );
+    }
+}
@@ -0,0 +1,416 @@
+//! Schema-declared size limits and per-user row quotas.
+//!
+//! Limits live on `TableSchema::limits` and `ColumnDescriptor::limits` and
+//! are checked against the encoded row, so the same check runs on a client's
+//! local write path (for immediate feedback) and on the server before a
+//! client's row batch is persisted (authoritatively). Server-side violations
+//! reject the batch with [`LIMIT_EXCEEDED_CODE`].
+//!
+//! Row quotas count live rows on the write's branch whose `$createdBy` is the
+//! writing user. Counts are kept per table branch in the [`QueryManager`] and
+//! follow row visibility changes; on the server, inserts also count while their
+//! batch is unsettled. Clients only see the rows they have synced, so their
+//! quota check is advisory; the server's count is the one that holds.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::metadata::SYSTEM_PRINCIPAL_ID;
+use crate::object::ObjectId;
+use crate::row_format::column_bytes;
+use crate::row_histories::{BatchId, RowVisibilityChange, StoredRowBatch};
+use crate::storage::Storage;
+
+use super::manager::{QueryError, QueryManager};
+use super::types::{ColumnName, ColumnType, RowDescriptor, TableLimits, TableName, TableSchema};
+
+/// Rejection code used when a client write violates a declared limit.
+pub const LIMIT_EXCEEDED_CODE: &str = "limit_exceeded";
+
+/// A single declared limit that a write would violate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitViolation {
+    /// The encoded row is larger than `TableLimits::max_row_bytes`.
+    RowTooLarge { bytes: usize, max_bytes: u32 },
+    /// A column value is larger than `ColumnLimits::max_bytes`.
+    ValueTooLarge {
+        column: ColumnName,
+        bytes: usize,
+        max_bytes: u32,
+    },
+    /// An ARRAY value has more elements than `ColumnLimits::max_items`.
+    TooManyItems {
+        column: ColumnName,
+        items: usize,
+        max_items: u32,
+    },
+    /// The user already owns `TableLimits::max_rows_per_user` live rows.
+    RowQuotaExceeded {
+        user_id: String,
+        rows: u64,
+        max_rows: u64,
+    },
+}
+
+impl std::fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitViolation::RowTooLarge { bytes, max_bytes } => {
+                write!(f, "row is {bytes} bytes (max {max_bytes})")
+            }
+            LimitViolation::ValueTooLarge {
+                column,
+                bytes,
+                max_bytes,
+            } => write!(f, "column {column} is {bytes} bytes (max {max_bytes})"),
+            LimitViolation::TooManyItems {
+                column,
+                items,
+                max_items,
+            } => write!(f, "column {column} has {items} items (max {max_items})"),
+            LimitViolation::RowQuotaExceeded {
+                user_id,
+                rows,
+                max_rows,
+            } => write!(f, "user {user_id} already has {rows} rows (max {max_rows})"),
+        }
+    }
+}
+
+/// Check an encoded row against the table's row-size and column limits.
+///
+/// Row quotas need storage access and are checked separately by
+/// [`QueryManager::check_row_quota`].
+pub fn check_encoded_row_limits(
+    table_schema: &TableSchema,
+    data: &[u8],
+) -> Result<(), LimitViolation> {
+    check_encoded_row_limits_with_descriptor(&table_schema.columns, &table_schema.limits, data)
+}
+
+pub(crate) fn check_encoded_row_limits_with_descriptor(
+    descriptor: &RowDescriptor,
+    limits: &TableLimits,
+    data: &[u8],
+) -> Result<(), LimitViolation> {
+    if let Some(max_bytes) = limits.max_row_bytes
+        && data.len() > max_bytes as usize
+    {
+        return Err(LimitViolation::RowTooLarge {
+            bytes: data.len(),
+            max_bytes,
+        });
+    }
+
+    for (index, column) in descriptor.columns.iter().enumerate() {
+        if column.limits.is_unbounded() {
+            continue;
+        }
+        // Rows that fail to decode are rejected by the codec elsewhere.
+        let Ok(Some(bytes)) = column_bytes(descriptor, data, index) else {
+            continue;
+        };
+        if let Some(max_bytes) = column.limits.max_bytes
+            && bytes.len() > max_bytes as usize
+        {
+            return Err(LimitViolation::ValueTooLarge {
+                column: column.name,
+                bytes: bytes.len(),
+                max_bytes,
+            });
+        }
+        if let Some(max_items) = column.limits.max_items
+            && matches!(column.column_type, ColumnType::Array { .. })
+            && let Some(count) = bytes.get(0..4)
+        {
+            // Encoded arrays start with their little-endian element count.
+            let items = u32::from_le_bytes(count.try_into().unwrap()) as usize;
+            if items > max_items as usize {
+                return Err(LimitViolation::TooManyItems {
+                    column: column.name,
+                    items,
+                    max_items,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Live rows per creator for one table branch that declares a row quota.
+///
+/// Rows are tracked by id rather than counted, so replaying a visibility
+/// change that a fresh scan already reflects leaves the count unchanged.
+#[derive(Debug, Default)]
+pub(crate) struct RowQuotaCounts {
+    live: HashMap<String, HashSet<ObjectId>>,
+    /// Inserts the server has let through but whose batch hasn't settled yet.
+    pending: HashMap<String, HashMap<ObjectId, BatchId>>,
+}
+
+impl RowQuotaCounts {
+    fn from_visible_rows(rows: Vec<StoredRowBatch>) -> Self {
+        let mut counts = Self::default();
+        for row in rows {
+            if !row.is_deleted {
+                counts
+                    .live
+                    .entry(row.created_by.to_string())
+                    .or_default()
+                    .insert(row.row_id);
+            }
+        }
+        counts
+    }
+
+    fn rows_for(&self, user_id: &str) -> u64 {
+        let live = self.live.get(user_id);
+        let pending = self.pending.get(user_id).map_or(0, |pending| {
+            pending
+                .keys()
+                .filter(|row_id| !live.is_some_and(|live| live.contains(row_id)))
+                .count()
+        });
+        (live.map_or(0, HashSet::len) + pending) as u64
+    }
+
+    fn apply_visibility_change(&mut self, update: &RowVisibilityChange) {
+        if let Some(previous_row) = update.previous_row.as_ref()
+            && previous_row.created_by != update.row.created_by
+            && let Some(live) = self.live.get_mut(previous_row.created_by.as_str())
+        {
+            live.remove(&update.object_id);
+        }
+        let row = &update.row;
+        if row.state.is_visible() && !row.is_deleted {
+            self.live
+                .entry(row.created_by.to_string())
+                .or_default()
+                .insert(update.object_id);
+        } else if let Some(live) = self.live.get_mut(row.created_by.as_str()) {
+            live.remove(&update.object_id);
+        }
+    }
+}
+
+impl QueryManager {
+    /// Check that `user_id` may create one more row in `table` on `branch`.
+    ///
+    /// Writes authored by the system principal are never counted. The first
+    /// check for a table branch scans its visible rows; after that the counts
+    /// follow row visibility changes.
+    pub(crate) fn check_row_quota<H: Storage + ?Sized>(
+        &mut self,
+        storage: &H,
+        table: &str,
+        branch: &str,
+        limits: &TableLimits,
+        user_id: &str,
+    ) -> Result<(), QueryError> {
+        let Some(counts) = self.row_quota_counts_for(storage, table, branch, limits, user_id)?
+        else {
+            return Ok(());
+        };
+        let rows = counts.rows_for(user_id);
+        Self::row_quota_result(table, limits, user_id, rows)
+    }
+
+    /// Server-side variant of [`Self::check_row_quota`] for a client insert.
+    ///
+    /// Inserts that pass are held against the quota until their batch
+    /// settles, so the other rows of the same batch (and other unsettled
+    /// batches) see them before they become visible.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn check_and_reserve_row_quota<H: Storage + ?Sized>(
+        &mut self,
+        storage: &H,
+        table: &str,
+        branch: &str,
+        limits: &TableLimits,
+        user_id: &str,
+        row_id: ObjectId,
+        batch_id: BatchId,
+    ) -> Result<(), QueryError> {
+        let Some(counts) = self.row_quota_counts_for(storage, table, branch, limits, user_id)?
+        else {
+            return Ok(());
+        };
+        let already_counted = counts
+            .live
+            .get(user_id)
+            .is_some_and(|live| live.contains(&row_id))
+            || counts
+                .pending
+                .get(user_id)
+                .is_some_and(|pending| pending.contains_key(&row_id));
+        if !already_counted {
+            Self::row_quota_result(table, limits, user_id, counts.rows_for(user_id))?;
+        }
+        counts
+            .pending
+            .entry(user_id.to_string())
+            .or_default()
+            .insert(row_id, batch_id);
+        Ok(())
+    }
+
+    fn row_quota_counts_for<H: Storage + ?Sized>(
+        &mut self,
+        storage: &H,
+        table: &str,
+        branch: &str,
+        limits: &TableLimits,
+        user_id: &str,
+    ) -> Result<Option<&mut RowQuotaCounts>, QueryError> {
+        if limits.max_rows_per_user.is_none() || user_id == SYSTEM_PRINCIPAL_ID {
+            return Ok(None);
+        }
+        let branches = self.row_quota_counts.entry(table.to_string()).or_default();
+        if !branches.contains_key(branch) {
+            let rows = storage
+                .scan_visible_region(table, branch)
+                .map_err(|err| QueryError::EncodingError(format!("count quota rows: {err}")))?;
+            branches.insert(branch.to_string(), RowQuotaCounts::from_visible_rows(rows));
+        }
+        Ok(branches.get_mut(branch))
+    }
+
+    fn row_quota_result(
+        table: &str,
+        limits: &TableLimits,
+        user_id: &str,
+        rows: u64,
+    ) -> Result<(), QueryError> {
+        match limits.max_rows_per_user {
+            Some(max_rows) if rows >= max_rows => Err(QueryError::LimitExceeded {
+                table: TableName::new(table),
+                violation: LimitViolation::RowQuotaExceeded {
+                    user_id: user_id.to_string(),
+                    rows,
+                    max_rows,
+                },
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Keep row quota counts in step with a row visibility change.
+    ///
+    /// Table branches nobody has checked a quota for yet are left alone; their
+    /// first check scans storage instead.
+    pub(super) fn apply_row_quota_visibility_change(
+        &mut self,
+        table: &str,
+        update: &RowVisibilityChange,
+    ) {
+        if let Some(counts) = self
+            .row_quota_counts
+            .get_mut(table)
+            .and_then(|branches| branches.get_mut(update.row.branch.as_str()))
+        {
+            counts.apply_visibility_change(update);
+        }
+    }
+
+    /// Release quota held for inserts of batches that have settled.
+    pub(super) fn release_row_quota_reservations(&mut self, settled: &[BatchId]) {
+        for counts in self
+            .row_quota_counts
+            .values_mut()
+            .flat_map(HashMap::values_mut)
+        {
+            for pending in counts.pending.values_mut() {
+                pending.retain(|_, batch_id| !settled.contains(batch_id));
+            }
+            counts.pending.retain(|_, pending| !pending.is_empty());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_manager::encoding::encode_row;
+    use crate::query_manager::types::{ColumnDescriptor, Value};
+
+    fn notes_schema() -> TableSchema {
+        TableSchema::builder("notes")
+            .column_descriptor(ColumnDescriptor::new("title", ColumnType::Text).max_bytes(8))
+            .column_descriptor(
+                ColumnDescriptor::new(
+                    "tags",
+                    ColumnType::Array {
+                        element: Box::new(ColumnType::Text),
+                    },
+                )
+                .max_items(2),
+            )
+            .max_row_bytes(64)
+            .build()
+    }
+
+    fn encode(schema: &TableSchema, title: &str, tags: &[&str]) -> Vec<u8> {
+        encode_row(
+            &schema.columns,
+            &[
+                Value::Text(title.to_string()),
+                Value::Array(
+                    tags.iter()
+                        .map(|tag| Value::Text(tag.to_string()))
+                        .collect(),
+                ),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rows_within_limits_pass() {
+        let schema = notes_schema();
+        let data = encode(&schema, "hello", &["a", "b"]);
+
+        assert_eq!(check_encoded_row_limits(&schema, &data), Ok(()));
+    }
+
+    #[test]
+    fn oversized_values_are_reported_per_column() {
+        let schema = notes_schema();
+        let data = encode(&schema, "much too long", &[]);
+
+        assert_eq!(
+            check_encoded_row_limits(&schema, &data),
+            Err(LimitViolation::ValueTooLarge {
+                column: ColumnName::new("title"),
+                bytes: 13,
+                max_bytes: 8,
+            })
+        );
+    }
+
+    #[test]
+    fn array_lengths_are_bounded() {
+        let schema = notes_schema();
+        let data = encode(&schema, "ok", &["a", "b", "c"]);
+
+        assert_eq!(
+            check_encoded_row_limits(&schema, &data),
+            Err(LimitViolation::TooManyItems {
+                column: ColumnName::new("tags"),
+                items: 3,
+                max_items: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn row_size_is_checked_before_columns() {
+        let schema = notes_schema();
+        let long_tag = "x".repeat(80);
+        let data = encode(&schema, "much too long", &[long_tag.as_str()]);
+
+        assert!(matches!(
+            check_encoded_row_limits(&schema, &data),
+            Err(LimitViolation::RowTooLarge { max_bytes: 64, .. })
+        ));
+    }
+}
@@ -9,10 +9,12 @@ use crate::batch_fate::BatchFate;
 use crate::catalogue::CatalogueEntry;
 use crate::metadata::{MetadataKey, ObjectType};
 use crate::object::{BranchName, ObjectId};
-use crate::row_histories::{BatchId, QueryRowBatch, RowState, RowVisibilityChange, StoredRowBatch};
+use crate::row_histories::{
+    BatchId, QueryRowBatch, RowState, RowVisibilityChange, StoredRowBatch, writer_parent_version,
+};
 use crate::schema_manager::{
-    LensTransformer, SchemaContext, encoding::encode_schema, resolve_current_table_name,
-    translate_table_name_to_schema,
+    LensTransformer, SchemaContext, TransformError, TransformResult, encoding::encode_schema,
+    resolve_current_table_name, translate_table_name_to_schema,
 };
 use crate::storage::{RowLocator, Storage, StorageError};
 use crate::sync_manager::{
@@ -23,15 +25,16 @@ use crate::sync_manager::{
 use super::encoding::decode_row;
 use super::graph::{QueryCompileError, QueryGraph};
 use super::graph_nodes::output::QuerySubscriptionId;
+use super::limits::{LimitViolation, RowQuotaCounts};
 use super::policy::{Operation, PolicyExpr};
 use super::policy_graph::PolicyGraph;
-use super::query::Query;
+use super::query::{PageCursor, Query};
 use super::session::Session;
 use super::settlement_eval_cache::SettlementEvalCache;
 use super::types::{
     ColumnName, ComposedBranchName, LoadedRow, OrderedAdded, OrderedRowDelta, Row, RowDelta,
-    RowDescriptor, RowPolicyMode, Schema, SchemaHash, TableName, TablePolicies, Tuple, Value,
-    build_ordered_delta_with_post_ids,
+    RowDescriptor, RowPolicyMode, Schema, SchemaHash, TableLimits, TableName, TablePolicies, Tuple,
+    Value, build_ordered_delta_with_post_ids,
 };
 
 /// Error types for QueryManager operations.
@@ -72,6 +75,11 @@ pub enum QueryError {
     },
     /// Unknown schema hash - client should sync schema first.
     UnknownSchema(SchemaHash),
+    /// Write violates a size limit or row quota declared in the schema.
+    LimitExceeded {
+        table: TableName,
+        violation: LimitViolation,
+    },
 }
 
 impl std::fmt::Display for QueryError {
@@ -118,6 +126,9 @@ impl std::fmt::Display for QueryError {
                     hash.short()
                 )
             }
+            QueryError::LimitExceeded { table, violation } => {
+                write!(f, "limit exceeded on table {table}: {violation}")
+            }
         }
     }
 }
@@ -332,6 +343,11 @@ pub struct QueryUpdate {
     /// Output descriptor for decoding the binary row data.
     /// This matches the query's output schema (handles JOINs, projections, etc).
     pub descriptor: RowDescriptor,
+    /// Cursor of the first row on the current page, for `QueryBuilder::before`.
+    /// Only set for sorted queries with a limit or offset.
+    pub prev_cursor: Option<PageCursor>,
+    /// Cursor of the last row on the current page, for `QueryBuilder::after`.
+    pub next_cursor: Option<PageCursor>,
 }
 
 /// Terminal failure for a local query subscription.
@@ -359,6 +375,7 @@ pub(super) struct PolicyCheckState {
 pub(super) struct WriteTableCacheEntry {
     pub(super) descriptor: Arc<RowDescriptor>,
     pub(super) indexed_columns: Option<Arc<Vec<ColumnName>>>,
+    pub(super) limits: TableLimits,
     pub(super) row_layout: Arc<crate::row_format::CompiledRowLayout>,
     pub(super) row_locator: RowLocator,
     pub(super) insert_policy: Option<Arc<PolicyExpr>>,
@@ -575,6 +592,9 @@ pub struct QueryManager {
     /// Per-schema, per-table write metadata cached to avoid cloning policy
     /// trees and descriptors on every hot write.
     pub(super) write_table_cache: HashMap<(SchemaHash, TableName), Arc<WriteTableCacheEntry>>,
+
+    /// Row quota counts by table and branch, filled on the first quota check.
+    pub(super) row_quota_counts: HashMap<String, HashMap<String, RowQuotaCounts>>,
 }
 
 impl QueryManager {
@@ -665,6 +685,7 @@ impl QueryManager {
             catalogued_storage_namespaces: HashSet::new(),
             catalogue_app_id: None,
             write_table_cache: HashMap::new(),
+            row_quota_counts: HashMap::new(),
         }
     }
 
@@ -764,6 +785,27 @@ impl QueryManager {
         self.mark_subscriptions_for_recompile();
     }
 
+    /// Forget a schema retired by schema GC, along with its branch and every
+    /// lens touching it.
+    pub fn retire_schema(&mut self, hash: SchemaHash) {
+        if hash == self.schema_context.current_hash {
+            return;
+        }
+        self.schema_context.retire_schema(&hash);
+        self.branch_schema_map
+            .retain(|_, branch_hash| *branch_hash != hash);
+        self.authorization_context_cache.clear();
+        self.mark_subscriptions_for_recompile();
+    }
+
+    /// Forget a lens retired by schema GC.
+    pub fn remove_lens(&mut self, source: &SchemaHash, target: &SchemaHash) {
+        if self.schema_context.remove_lens(source, target).is_some() {
+            self.authorization_context_cache.clear();
+            self.mark_subscriptions_for_recompile();
+        }
+    }
+
     /// Register a lens between two schemas.
     ///
     /// Also attempts to activate any pending schemas that may now be reachable.
@@ -1261,6 +1303,7 @@ impl QueryManager {
             .collect::<Vec<_>>();
         batch_ids.sort();
         batch_ids.dedup();
+        self.release_row_quota_reservations(&batch_ids);
 
         let unique_batch_count = batch_ids.len();
         let mut marked_row_count = 0usize;
@@ -1670,6 +1713,8 @@ impl QueryManager {
                     required_tier = ?subscription.durability_tier,
                     "jazz trace subscription first delivery"
                 );
+                let (prev_cursor, next_cursor) =
+                    Self::page_cursors(&subscription.graph, &visible_delta.added);
                 subscription.settled_once = true;
                 subscription.current_ordered_ids = ordered_ids_after;
                 subscription.current_visible_rows = visible_rows_by_id;
@@ -1678,6 +1723,8 @@ impl QueryManager {
                     delta: visible_delta,
                     ordered_delta,
                     descriptor: subscription.graph.combined_descriptor.clone(),
+                    prev_cursor,
+                    next_cursor,
                 });
                 subscription.has_pending_local_updates = false;
                 subscription
@@ -1702,6 +1749,8 @@ impl QueryManager {
                 }
                 let ordered_ids_after: Vec<ObjectId> =
                     visible_rows.iter().map(|row| row.id).collect();
+                let (prev_cursor, next_cursor) =
+                    Self::page_cursors(&subscription.graph, &visible_rows);
                 let ordered = build_ordered_delta_with_post_ids(
                     &subscription.current_ordered_ids,
                     &ordered_ids_after,
@@ -1722,6 +1771,8 @@ impl QueryManager {
                     delta: visible_delta,
                     ordered_delta: ordered.delta,
                     descriptor: subscription.graph.combined_descriptor.clone(),
+                    prev_cursor,
+                    next_cursor,
                 });
                 subscription.has_pending_local_updates = false;
                 subscription
@@ -1882,6 +1933,7 @@ impl QueryManager {
         {
             return None;
         }
+        self.apply_row_quota_visibility_change(&original_table, &update);
 
         if update.row.is_hard_deleted() {
             if apply_index_mutations {
@@ -2674,6 +2726,72 @@ impl QueryManager {
         best.map(|(_, row)| (current_table_name.to_string(), row))
     }
 
+    /// Lens `row`, the winning version of `row_id` stored under the older
+    /// schema `source_hash`, into the current schema.
+    ///
+    /// Writes from older schemas are column diffs against the version their
+    /// author edited, applied onto the row's newest version under another
+    /// schema, so columns the writer can't see survive its update.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn transform_row_onto_newest(
+        storage: &dyn Storage,
+        row_id: ObjectId,
+        table: &str,
+        table_hint: Option<&str>,
+        row: &QueryRowBatch,
+        source_hash: SchemaHash,
+        branches: &[String],
+        durability_tier: Option<DurabilityTier>,
+        schema_context: &SchemaContext,
+        branch_schema_map: &HashMap<String, SchemaHash>,
+    ) -> Result<TransformResult, TransformError> {
+        let schema_of = |branch: &str| {
+            branch_schema_map
+                .get(branch)
+                .copied()
+                .unwrap_or(schema_context.current_hash)
+        };
+        let other_schema_branches: Vec<String> = branches
+            .iter()
+            .filter(|branch| schema_of(branch.as_str()) != source_hash)
+            .cloned()
+            .collect();
+        let newest = Self::load_best_visible_row_batch_with_hint_or_locator(
+            storage,
+            row_id,
+            table_hint,
+            &other_schema_branches,
+            durability_tier,
+            schema_context,
+            branch_schema_map,
+        )
+        .map(|(_, newest)| newest)
+        .filter(|newest| !newest.is_hard_deleted() && !newest.is_soft_deleted());
+
+        let transformer = LensTransformer::new(schema_context, table);
+        let Some(newest) = newest else {
+            return transformer.transform(row_id, &row.data, row.batch_id, source_hash);
+        };
+
+        let history_table = crate::storage::history_table_for_row(storage, row_id, table);
+        let history = storage
+            .scan_history_row_batches(&history_table, row_id)
+            .unwrap_or_default();
+        let parent = history
+            .iter()
+            .find(|version| version.batch_id == row.batch_id && version.branch == row.branch)
+            .and_then(|write| writer_parent_version(&history, write));
+
+        transformer.transform_onto(
+            row_id,
+            &row.data,
+            row.batch_id,
+            source_hash,
+            parent.map(|parent| (&*parent.data, schema_of(parent.branch.as_str()))),
+            (&*newest.data, schema_of(newest.branch.as_str())),
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub(super) fn load_visible_row_for_query(
         storage: &dyn Storage,
@@ -2753,15 +2871,25 @@ impl QueryManager {
             return None;
         }
 
-        let batch_id = row.batch_id;
         let row_provenance = row.row_provenance();
         let source_branch = row.branch.as_str();
 
         if let Some(&source_hash) = branch_schema_map.get(source_branch)
             && source_hash != schema_context.current_hash
         {
-            let transformer = LensTransformer::new(schema_context, &table);
-            match transformer.transform(&row.data, batch_id, source_hash) {
+            let transformed = Self::transform_row_onto_newest(
+                storage,
+                row_id,
+                &table,
+                table_hint,
+                &row,
+                source_hash,
+                branches,
+                durability_tier,
+                schema_context,
+                branch_schema_map,
+            );
+            match transformed {
                 Ok(result) => {
                     return Some(LoadedRow::new(
                         result.data,
@@ -2772,6 +2900,7 @@ impl QueryManager {
                         result.batch_id,
                     ));
                 }
+                Err(TransformError::EmptyExtraction(_)) => return None,
                 Err(err) => {
                     schema_warnings.record(
                         table_for_warnings,
@@ -2872,6 +3001,18 @@ impl QueryManager {
         }
     }
 
+    /// Keyset cursors for the first and last visible rows of a page.
+    fn page_cursors(
+        graph: &QueryGraph,
+        visible_rows: &[Row],
+    ) -> (Option<PageCursor>, Option<PageCursor>) {
+        let cursor_for = |row: Option<&Row>| row.and_then(|row| graph.page_cursor(row.id));
+        (
+            cursor_for(visible_rows.first()),
+            cursor_for(visible_rows.last()),
+        )
+    }
+
     pub(super) fn rows_from_tuples(graph: &QueryGraph, tuples: &[Tuple]) -> Vec<Row> {
         tuples
             .iter()
@@ -18,8 +18,8 @@ use crate::query_manager::manager::{QueryError, QueryManager};
 use crate::query_manager::query::QueryBuilder;
 use crate::query_manager::session::Session as PolicySession;
 use crate::query_manager::types::{
-    ColumnDescriptor, ColumnType, ComposedBranchName, PolicyExpr, RowDescriptor, Schema, TableName,
-    TablePolicies, TableSchema, Value,
+    Annotations, ColumnDescriptor, ColumnType, ComposedBranchName, PolicyExpr, RowDescriptor,
+    Schema, TableLimits, TableName, TablePolicies, TableSchema, Value, Visibility,
 };
 use crate::row_histories::{BatchId, HistoryScan, RowState, StoredRowBatch, VisibleRowEntry};
 use crate::schema_manager::encoding::encode_schema;
@@ -1295,15 +1295,26 @@ mod array_subqueries;
 mod bootstrap;
 mod branches;
 mod client_lifecycle;
+mod computed_expressions;
 mod contributing_ids;
 mod crud_queries;
+mod decimals;
 mod deletes;
 mod e2e_sync;
+mod exists_subqueries;
+mod explain;
+mod geo_points;
 mod joins;
+mod json_paths;
 mod json_storage;
+mod keyset_pagination;
+mod limits;
 mod misc;
 mod policies;
 mod recursive_queries;
 mod server_subscriptions;
+mod sql_queries;
 mod subscriptions;
+mod temporal_columns;
 mod updates;
+mod vector_search;
@@ -0,0 +1,131 @@
+use super::*;
+use crate::query_manager::relation_ir::{
+    ArithmeticOp, ColumnRef, OrderByExpr, OrderDirection, PredicateCmpOp, PredicateExpr,
+    ProjectColumn, ProjectExpr, RelExpr, ScalarComparison, ScalarExpr,
+};
+
+fn column(name: &str) -> ScalarExpr {
+    ScalarExpr::Column(ColumnRef::unscoped(name))
+}
+
+fn integer(value: i32) -> ScalarExpr {
+    ScalarExpr::Literal(Value::Integer(value))
+}
+
+fn arithmetic(op: ArithmeticOp, left: ScalarExpr, right: ScalarExpr) -> ScalarExpr {
+    ScalarExpr::Arithmetic {
+        op,
+        left: Box::new(left),
+        right: Box::new(right),
+    }
+}
+
+fn seed_users(qm: &mut QueryManager, storage: &mut MemoryStorage) {
+    for (name, score) in [("Alice", 50), ("Bob", 90), ("Carol", 70), ("Dave", 10)] {
+        qm.insert(
+            storage,
+            "users",
+            &[Value::Text(name.into()), Value::Integer(score)],
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn computed_expressions_filter_project_and_order_rows() {
+    let sync_manager = SyncManager::new();
+    let schema = test_schema();
+    let (mut qm, mut storage) = create_query_manager(sync_manager, schema);
+    seed_users(&mut qm, &mut storage);
+
+    // WHERE score * 2 >= 100, SELECT name, UPPER(name), score + 5, ORDER BY score % 7.
+    let mut query = qm.query("users").build();
+    query.relation_ir = RelExpr::OrderBy {
+        input: Box::new(RelExpr::Project {
+            input: Box::new(RelExpr::Filter {
+                input: Box::new(RelExpr::TableScan {
+                    table: TableName::new("users"),
+                }),
+                predicate: PredicateExpr::Compare(ScalarComparison {
+                    left: arithmetic(ArithmeticOp::Mul, column("score"), integer(2)),
+                    op: PredicateCmpOp::Ge,
+                    right: integer(100),
+                }),
+            }),
+            columns: vec![
+                ProjectColumn {
+                    alias: "name".into(),
+                    expr: ProjectExpr::Column(ColumnRef::unscoped("name")),
+                },
+                ProjectColumn {
+                    alias: "shout".into(),
+                    expr: ProjectExpr::Expr(ScalarExpr::Upper(Box::new(column("name")))),
+                },
+                ProjectColumn {
+                    alias: "bumped".into(),
+                    expr: ProjectExpr::Expr(arithmetic(
+                        ArithmeticOp::Add,
+                        column("score"),
+                        integer(5),
+                    )),
+                },
+            ],
+        }),
+        terms: vec![OrderByExpr {
+            column: ColumnRef::unscoped("score_mod_7"),
+            direction: OrderDirection::Asc,
+            expr: Some(arithmetic(ArithmeticOp::Mod, column("score"), integer(7))),
+        }],
+    };
+    query.select_columns = None;
+
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    let rows: Vec<Vec<Value>> = results.into_iter().map(|(_, values)| values).collect();
+    assert_eq!(
+        rows,
+        vec![
+            // 70 % 7 == 0
+            vec![
+                Value::Text("Carol".into()),
+                Value::Text("CAROL".into()),
+                Value::Integer(75),
+            ],
+            // 50 % 7 == 1
+            vec![
+                Value::Text("Alice".into()),
+                Value::Text("ALICE".into()),
+                Value::Integer(55),
+            ],
+            // 90 % 7 == 6
+            vec![
+                Value::Text("Bob".into()),
+                Value::Text("BOB".into()),
+                Value::Integer(95),
+            ],
+        ]
+    );
+}
+
+#[test]
+fn ill_typed_computed_expression_fails_to_compile() {
+    let sync_manager = SyncManager::new();
+    let schema = test_schema();
+    let (mut qm, mut storage) = create_query_manager(sync_manager, schema);
+
+    let mut query = qm.query("users").build();
+    query.relation_ir = RelExpr::Project {
+        input: Box::new(RelExpr::TableScan {
+            table: TableName::new("users"),
+        }),
+        columns: vec![ProjectColumn {
+            alias: "broken".into(),
+            expr: ProjectExpr::Expr(ScalarExpr::Upper(Box::new(column("score")))),
+        }],
+    };
+    query.select_columns = None;
+
+    assert!(matches!(
+        execute_query(&mut qm, &mut storage, query),
+        Err(QueryError::QueryCompilationError(_))
+    ));
+}
@@ -0,0 +1,112 @@
+use super::*;
+use crate::query_manager::types::{Decimal, SchemaBuilder};
+
+fn products_schema() -> Schema {
+    SchemaBuilder::new()
+        .table(
+            TableSchema::builder("products")
+                .column("name", ColumnType::Text)
+                .column(
+                    "price",
+                    ColumnType::Decimal {
+                        precision: 10,
+                        scale: 2,
+                    },
+                ),
+        )
+        .build()
+}
+
+fn decimal(text: &str) -> Value {
+    Value::Decimal(Decimal::parse(text).unwrap())
+}
+
+fn insert_product(
+    qm: &mut QueryManager,
+    storage: &mut MemoryStorage,
+    name: &str,
+    price: &str,
+) -> ObjectId {
+    qm.insert(
+        storage,
+        "products",
+        &[Value::Text(name.into()), decimal(price)],
+    )
+    .unwrap()
+    .row_id
+}
+
+fn names(results: &[(ObjectId, Vec<Value>)]) -> Vec<Value> {
+    results
+        .iter()
+        .map(|(_, values)| values[0].clone())
+        .collect()
+}
+
+#[test]
+fn decimal_columns_filter_and_sort_numerically() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), products_schema());
+    insert_product(&mut qm, &mut storage, "Tea", "4.5");
+    insert_product(&mut qm, &mut storage, "Cake", "12.30");
+    insert_product(&mut qm, &mut storage, "Refund", "-0.75");
+
+    let query = qm
+        .query("products")
+        .filter_ge("price", Value::Integer(4))
+        .order_by("price")
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        names(&results),
+        vec![Value::Text("Tea".into()), Value::Text("Cake".into())]
+    );
+
+    let query = qm
+        .query("products")
+        .filter_eq("price", decimal("12.3"))
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    let Value::Decimal(price) = &results[0].1[1] else {
+        panic!("expected a decimal price, got {:?}", results[0].1[1]);
+    };
+    assert_eq!(
+        price.to_string(),
+        "12.30",
+        "Rows read back at the column scale"
+    );
+
+    let query = qm
+        .query("products")
+        .filter_lt("price", decimal("4.505"))
+        .order_by_desc("price")
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        names(&results),
+        vec![Value::Text("Tea".into()), Value::Text("Refund".into())],
+        "Literals finer than the column scale still compare exactly"
+    );
+}
+
+#[test]
+fn decimal_values_outside_column_precision_are_rejected() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), products_schema());
+
+    assert!(
+        qm.insert(
+            &mut storage,
+            "products",
+            &[Value::Text("Gold".into()), decimal("123456789.00")],
+        )
+        .is_err()
+    );
+    assert!(
+        qm.insert(
+            &mut storage,
+            "products",
+            &[Value::Text("Dust".into()), decimal("0.001")],
+        )
+        .is_err(),
+        "Values that cannot be stored exactly at the column scale are rejected"
+    );
+}
@@ -0,0 +1,105 @@
+use super::*;
+
+fn insert_user(qm: &mut QueryManager, storage: &mut MemoryStorage, name: &str) -> ObjectId {
+    qm.insert(storage, "users", &[Value::Text(name.into())])
+        .unwrap()
+        .row_id
+}
+
+fn insert_post(
+    qm: &mut QueryManager,
+    storage: &mut MemoryStorage,
+    title: &str,
+    author: ObjectId,
+) -> ObjectId {
+    qm.insert(
+        storage,
+        "posts",
+        &[Value::Text(title.into()), Value::Uuid(author)],
+    )
+    .unwrap()
+    .row_id
+}
+
+#[test]
+fn where_exists_keeps_outer_rows_with_a_matching_filtered_inner_row() {
+    let sync_manager = SyncManager::new();
+    let schema = join_schema_with_implicit_base_id();
+    let (mut qm, mut storage) = create_query_manager(sync_manager, schema);
+
+    let alice = insert_user(&mut qm, &mut storage, "Alice");
+    let bob = insert_user(&mut qm, &mut storage, "Bob");
+    insert_user(&mut qm, &mut storage, "Carol");
+    insert_post(&mut qm, &mut storage, "Published", alice);
+    insert_post(&mut qm, &mut storage, "Published", alice);
+    insert_post(&mut qm, &mut storage, "Draft", bob);
+
+    let query = qm
+        .query("users")
+        .where_exists(|sub| {
+            sub.from("posts")
+                .correlate("author_id", "users.id")
+                .filter_eq("title", Value::Text("Published".into()))
+        })
+        .build();
+
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        results,
+        vec![(alice, vec![Value::Text("Alice".into())])],
+        "Only Alice has a published post; she appears once despite two matches"
+    );
+}
+
+#[test]
+fn where_not_exists_subscription_follows_inner_side_changes() {
+    let sync_manager = SyncManager::new();
+    let schema = join_schema_with_implicit_base_id();
+    let (mut qm, mut storage) = create_query_manager(sync_manager, schema);
+
+    let alice = insert_user(&mut qm, &mut storage, "Alice");
+    let bob = insert_user(&mut qm, &mut storage, "Bob");
+    let alice_post = insert_post(&mut qm, &mut storage, "Hello", alice);
+
+    let query = qm
+        .query("users")
+        .where_not_exists(|sub| sub.from("posts").correlate("author_id", "users.id"))
+        .build();
+    let sub_id = qm.subscribe(query).unwrap();
+
+    qm.process(&mut storage);
+    let updates = qm.take_updates();
+    let delta = updates
+        .iter()
+        .find(|u| u.subscription_id == sub_id)
+        .map(|u| &u.delta)
+        .expect("Initial settle should report users without posts");
+    let added: Vec<_> = delta.added.iter().map(|row| row.id).collect();
+    assert_eq!(added, vec![bob]);
+
+    // Bob's first post takes him out of the result.
+    insert_post(&mut qm, &mut storage, "First", bob);
+    qm.process(&mut storage);
+    let updates = qm.take_updates();
+    let delta = updates
+        .iter()
+        .find(|u| u.subscription_id == sub_id)
+        .map(|u| &u.delta)
+        .expect("Inner insert should retract the outer row");
+    assert!(delta.added.is_empty());
+    let removed: Vec<_> = delta.removed.iter().map(|row| row.id).collect();
+    assert_eq!(removed, vec![bob]);
+
+    // Deleting Alice's only post brings her back.
+    qm.delete(&mut storage, alice_post).unwrap();
+    qm.process(&mut storage);
+    let updates = qm.take_updates();
+    let delta = updates
+        .iter()
+        .find(|u| u.subscription_id == sub_id)
+        .map(|u| &u.delta)
+        .expect("Inner delete should re-admit the outer row");
+    assert!(delta.removed.is_empty());
+    let added: Vec<_> = delta.added.iter().map(|row| row.id).collect();
+    assert_eq!(added, vec![alice]);
+}
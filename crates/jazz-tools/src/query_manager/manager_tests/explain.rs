@@ -0,0 +1,69 @@
+use super::*;
+
+fn insert_scored_users(qm: &mut QueryManager, storage: &mut MemoryStorage) {
+    for (name, score) in [("Alice", 10), ("Bob", 20), ("Carol", 30)] {
+        qm.insert(
+            storage,
+            "users",
+            &[Value::Text(name.into()), Value::Integer(score)],
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn explain_reports_chosen_index_condition_and_cost() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), test_schema());
+    insert_scored_users(&mut qm, &mut storage);
+
+    let query = qm
+        .query("users")
+        .filter_ge("score", Value::Integer(20))
+        .order_by("name")
+        .build();
+    let explain = qm.explain(&storage, &query, None, false).unwrap();
+
+    let scan = explain
+        .nodes_of_kind("IndexScan")
+        .find_map(|node| node.index_scan.as_ref())
+        .expect("range filter should compile to an index scan");
+    assert_eq!(scan.table, "users");
+    assert_eq!(scan.column, "score");
+    assert_eq!(scan.condition, ">= 20");
+    assert_eq!(scan.estimated_rows, 2);
+    assert_eq!(explain.estimated_cost, 2);
+
+    let sort = explain
+        .nodes_of_kind("Sort")
+        .next()
+        .expect("order_by should compile to a sort node");
+    assert_eq!(sort.detail.as_deref(), Some("name ASC"));
+    assert!(
+        explain.nodes.iter().all(|node| node.counters.is_none()),
+        "Counters are only reported when analyzing"
+    );
+}
+
+#[test]
+fn explain_analyze_counts_rows_scanned_and_emitted_per_node() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), test_schema());
+    insert_scored_users(&mut qm, &mut storage);
+
+    let query = qm
+        .query("users")
+        .filter_eq("name", Value::Text("Bob".into()))
+        .build();
+    let explain = qm.explain(&storage, &query, None, true).unwrap();
+
+    let scan = explain.nodes_of_kind("IndexScan").next().unwrap();
+    let scan_counters = scan.counters.expect("analyze reports scan counters");
+    assert_eq!(scan_counters.evaluations, 1);
+    assert_eq!(scan_counters.rows_scanned, 1);
+
+    let output = &explain.nodes[explain.output_node as usize];
+    assert_eq!(output.kind, "Output");
+    assert_eq!(
+        output.counters.map(|counters| counters.rows_emitted),
+        Some(1)
+    );
+}
@@ -0,0 +1,165 @@
+use super::*;
+use crate::query_manager::types::{GeoBox, GeoCircle, GeoPoint, SchemaBuilder};
+
+fn places_schema(indexed: bool) -> Schema {
+    let table = TableSchema::builder("places")
+        .column("name", ColumnType::Text)
+        .column("location", ColumnType::GeoPoint);
+    let table = if indexed {
+        table
+    } else {
+        table.index_only(["name"])
+    };
+    SchemaBuilder::new().table(table).build()
+}
+
+fn point(lat: f64, lon: f64) -> GeoPoint {
+    GeoPoint::new(lat, lon).unwrap()
+}
+
+fn insert_place(
+    qm: &mut QueryManager,
+    storage: &mut MemoryStorage,
+    name: &str,
+    location: GeoPoint,
+) -> ObjectId {
+    qm.insert(
+        storage,
+        "places",
+        &[Value::Text(name.into()), Value::GeoPoint(location)],
+    )
+    .unwrap()
+    .row_id
+}
+
+fn names(results: &[(ObjectId, Vec<Value>)]) -> Vec<Value> {
+    results
+        .iter()
+        .map(|(_, values)| values[0].clone())
+        .collect()
+}
+
+fn seed(qm: &mut QueryManager, storage: &mut MemoryStorage) {
+    insert_place(qm, storage, "Alexanderplatz", point(52.5219, 13.4132));
+    insert_place(qm, storage, "Brandenburg Gate", point(52.5163, 13.3777));
+    insert_place(qm, storage, "Potsdam", point(52.3906, 13.0645));
+    insert_place(qm, storage, "Fiji", point(-17.7134, 178.065));
+    insert_place(qm, storage, "Samoa", point(-13.759, -172.1046));
+    insert_place(qm, storage, "Sydney", point(-33.8688, 151.2093));
+}
+
+#[test]
+fn radius_filter_matches_with_and_without_index() {
+    let berlin = GeoCircle::new(point(52.52, 13.405), 5_000.0).unwrap();
+
+    for indexed in [true, false] {
+        let (mut qm, mut storage) =
+            create_query_manager(SyncManager::new(), places_schema(indexed));
+        seed(&mut qm, &mut storage);
+
+        let query = qm
+            .query("places")
+            .filter_within_radius("location", berlin)
+            .order_by("name")
+            .build();
+        let results = execute_query(&mut qm, &mut storage, query).unwrap();
+        assert_eq!(
+            names(&results),
+            vec![
+                Value::Text("Alexanderplatz".into()),
+                Value::Text("Brandenburg Gate".into()),
+            ],
+            "indexed = {indexed}"
+        );
+    }
+}
+
+#[test]
+fn box_filter_wraps_across_the_antimeridian() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), places_schema(true));
+    seed(&mut qm, &mut storage);
+
+    let pacific = GeoBox::new(point(-20.0, 170.0), point(-10.0, -170.0)).unwrap();
+    let query = qm
+        .query("places")
+        .filter_within_box("location", pacific)
+        .order_by("name")
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        names(&results),
+        vec![Value::Text("Fiji".into()), Value::Text("Samoa".into())]
+    );
+}
+
+#[test]
+fn geo_columns_reject_ordering_and_non_geo_within_filters() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), places_schema(true));
+    seed(&mut qm, &mut storage);
+
+    let query = qm
+        .query("places")
+        .filter_lt("location", Value::GeoPoint(point(0.0, 0.0)))
+        .build();
+    assert!(execute_query(&mut qm, &mut storage, query).is_err());
+
+    let circle = GeoCircle::new(point(0.0, 0.0), 1_000.0).unwrap();
+    let query = qm
+        .query("places")
+        .filter_within_radius("name", circle)
+        .build();
+    assert!(execute_query(&mut qm, &mut storage, query).is_err());
+
+    let query = qm
+        .query("places")
+        .filter_eq("location", Value::Text("not a point".into()))
+        .build();
+    assert!(execute_query(&mut qm, &mut storage, query).is_err());
+}
+
+#[test]
+fn subscription_tracks_points_moving_in_and_out_of_radius() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), places_schema(true));
+
+    let berlin = GeoCircle::new(point(52.52, 13.405), 5_000.0).unwrap();
+    let query = qm
+        .query("places")
+        .filter_within_radius("location", berlin)
+        .build();
+    let sub_id = qm.subscribe(query).unwrap();
+
+    let courier = insert_place(&mut qm, &mut storage, "Courier", point(52.3906, 13.0645));
+    qm.process(&mut storage);
+    let _ = qm.take_updates();
+
+    qm.update(
+        &mut storage,
+        courier,
+        &[
+            Value::Text("Courier".into()),
+            Value::GeoPoint(point(52.5219, 13.4132)),
+        ],
+    )
+    .unwrap();
+    qm.process(&mut storage);
+    let updates = qm.take_updates();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].subscription_id, sub_id);
+    assert_eq!(updates[0].delta.added.len(), 1);
+    assert_eq!(updates[0].delta.added[0].id, courier);
+
+    qm.update(
+        &mut storage,
+        courier,
+        &[
+            Value::Text("Courier".into()),
+            Value::GeoPoint(point(48.8566, 2.3522)),
+        ],
+    )
+    .unwrap();
+    qm.process(&mut storage);
+    let updates = qm.take_updates();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].delta.removed.len(), 1);
+    assert_eq!(updates[0].delta.removed[0].id, courier);
+}
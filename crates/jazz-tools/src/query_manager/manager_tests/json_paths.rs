@@ -0,0 +1,155 @@
+use super::*;
+use crate::query_manager::types::SchemaBuilder;
+
+fn profiles_schema() -> Schema {
+    SchemaBuilder::new()
+        .table(
+            TableSchema::builder("profiles")
+                .column("name", ColumnType::Text)
+                .column("settings", ColumnType::Json { schema: None })
+                .index_json_path("settings->theme"),
+        )
+        .build()
+}
+
+fn insert_profile(
+    qm: &mut QueryManager,
+    storage: &mut MemoryStorage,
+    name: &str,
+    settings: &str,
+) -> ObjectId {
+    qm.insert(
+        storage,
+        "profiles",
+        &[Value::Text(name.into()), Value::Text(settings.into())],
+    )
+    .unwrap()
+    .row_id
+}
+
+fn names(results: &[(ObjectId, Vec<Value>)]) -> Vec<Value> {
+    results
+        .iter()
+        .map(|(_, values)| values[0].clone())
+        .collect()
+}
+
+#[test]
+fn json_path_conditions_filter_on_values_inside_the_document() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), profiles_schema());
+    insert_profile(
+        &mut qm,
+        &mut storage,
+        "Alice",
+        r#"{"theme":"dark","font":{"size":16},"tags":["admin"]}"#,
+    );
+    insert_profile(
+        &mut qm,
+        &mut storage,
+        "Bob",
+        r#"{"theme":"light","font":{"size":12},"tags":[]}"#,
+    );
+    insert_profile(&mut qm, &mut storage, "Carol", r#"{"font":{"size":14}}"#);
+
+    let query = qm
+        .query("profiles")
+        .filter_eq("settings->theme", Value::Text("dark".into()))
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(names(&results), vec![Value::Text("Alice".into())]);
+
+    let query = qm
+        .query("profiles")
+        .filter_ge("settings->font->size", Value::Integer(14))
+        .order_by("name")
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        names(&results),
+        vec![Value::Text("Alice".into()), Value::Text("Carol".into())]
+    );
+
+    let query = qm
+        .query("profiles")
+        .filter_has_key("settings->theme")
+        .order_by("name")
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        names(&results),
+        vec![Value::Text("Alice".into()), Value::Text("Bob".into())]
+    );
+
+    let query = qm
+        .query("profiles")
+        .filter_contains("settings->tags", Value::Text("admin".into()))
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(names(&results), vec![Value::Text("Alice".into())]);
+}
+
+#[test]
+fn order_by_json_path_sorts_by_extracted_number() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), profiles_schema());
+    insert_profile(&mut qm, &mut storage, "Alice", r#"{"font":{"size":16}}"#);
+    insert_profile(&mut qm, &mut storage, "Bob", r#"{"font":{"size":9}}"#);
+    insert_profile(&mut qm, &mut storage, "Carol", r#"{"font":{"size":12}}"#);
+    insert_profile(&mut qm, &mut storage, "Dave", "{}");
+
+    let query = qm
+        .query("profiles")
+        .order_by_desc("settings->font->size")
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        names(&results),
+        vec![
+            Value::Text("Alice".into()),
+            Value::Text("Carol".into()),
+            Value::Text("Bob".into()),
+            Value::Text("Dave".into()),
+        ],
+        "Numbers sort numerically and a missing path sorts like NULL"
+    );
+}
+
+#[test]
+fn json_path_index_follows_inserts_and_updates() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), profiles_schema());
+    let branch = get_branch(&qm);
+    let alice = insert_profile(&mut qm, &mut storage, "Alice", r#"{"theme":"dark"}"#);
+
+    let dark = Value::Text("dark".into());
+    let light = Value::Text("light".into());
+    assert_eq!(
+        storage.index_lookup("profiles", "settings->theme", &branch, &dark),
+        vec![alice]
+    );
+
+    qm.update(
+        &mut storage,
+        alice,
+        &[
+            Value::Text("Alice".into()),
+            Value::Text(r#"{"theme":"light"}"#.into()),
+        ],
+    )
+    .unwrap();
+
+    assert!(
+        storage
+            .index_lookup("profiles", "settings->theme", &branch, &dark)
+            .is_empty()
+    );
+    assert_eq!(
+        storage.index_lookup("profiles", "settings->theme", &branch, &light),
+        vec![alice]
+    );
+
+    let query = qm
+        .query("profiles")
+        .filter_eq("settings->theme", light)
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(names(&results), vec![Value::Text("Alice".into())]);
+}
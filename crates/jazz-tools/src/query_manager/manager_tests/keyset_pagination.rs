@@ -0,0 +1,100 @@
+use super::*;
+use crate::query_manager::query::PageCursor;
+
+fn insert_scored_users(qm: &mut QueryManager, storage: &mut MemoryStorage) -> Vec<ObjectId> {
+    [("A", 10), ("B", 20), ("C", 30), ("D", 40), ("E", 50)]
+        .into_iter()
+        .map(|(name, score)| {
+            qm.insert(
+                storage,
+                "users",
+                &[Value::Text(name.into()), Value::Integer(score)],
+            )
+            .unwrap()
+            .row_id
+        })
+        .collect()
+}
+
+fn ids(results: &[(ObjectId, Vec<Value>)]) -> Vec<ObjectId> {
+    results.iter().map(|(id, _)| *id).collect()
+}
+
+#[test]
+fn subscription_update_carries_cursors_for_page_edges() {
+    let sync_manager = SyncManager::new();
+    let (mut qm, mut storage) = create_query_manager(sync_manager, test_schema());
+    let users = insert_scored_users(&mut qm, &mut storage);
+
+    let query = qm.query("users").order_by("score").limit(2).build();
+    let sub_id = qm.subscribe(query).unwrap();
+    qm.process(&mut storage);
+
+    let updates = qm.take_updates();
+    let update = updates
+        .iter()
+        .find(|u| u.subscription_id == sub_id)
+        .expect("Initial settle should deliver the first page");
+    assert_eq!(
+        update.prev_cursor,
+        Some(PageCursor {
+            sort_values: vec![Value::Integer(10)],
+            row_id: users[0],
+        })
+    );
+    assert_eq!(
+        update.next_cursor,
+        Some(PageCursor {
+            sort_values: vec![Value::Integer(20)],
+            row_id: users[1],
+        })
+    );
+}
+
+#[test]
+fn after_and_before_cursors_walk_pages_in_sort_order() {
+    let sync_manager = SyncManager::new();
+    let (mut qm, mut storage) = create_query_manager(sync_manager, test_schema());
+    let users = insert_scored_users(&mut qm, &mut storage);
+
+    let after_b = PageCursor {
+        sort_values: vec![Value::Integer(20)],
+        row_id: users[1],
+    };
+    let query = qm
+        .query("users")
+        .order_by("score")
+        .after(after_b)
+        .limit(2)
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(ids(&results), vec![users[2], users[3]]);
+
+    let before_d = PageCursor {
+        sort_values: vec![Value::Integer(40)],
+        row_id: users[3],
+    };
+    let query = qm
+        .query("users")
+        .order_by("score")
+        .before(before_d)
+        .limit(2)
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        ids(&results),
+        vec![users[1], users[2]],
+        "Backward pages keep the rows just before the cursor, still in ascending order"
+    );
+}
+
+#[test]
+fn page_cursor_round_trips_through_opaque_token() {
+    let cursor = PageCursor {
+        sort_values: vec![Value::Text("Bob".into()), Value::Null],
+        row_id: ObjectId::new(),
+    };
+    let token = cursor.to_token();
+    assert_eq!(PageCursor::from_token(&token), Some(cursor));
+    assert_eq!(PageCursor::from_token("not a cursor"), None);
+}
@@ -0,0 +1,262 @@
+use super::*;
+use crate::batch_fate::BatchFate;
+use crate::query_manager::limits::{LIMIT_EXCEEDED_CODE, LimitViolation};
+use crate::query_manager::types::{ColumnName, SchemaBuilder};
+use crate::sync_manager::{ClientId, Destination, RowMetadata};
+
+fn limited_notes_schema() -> Schema {
+    SchemaBuilder::new()
+        .table(
+            TableSchema::builder("notes")
+                .column_descriptor(ColumnDescriptor::new("body", ColumnType::Text).max_bytes(16))
+                .max_rows_per_user(1),
+        )
+        .build()
+}
+
+fn note_batch_rejection(
+    outbox: &[crate::sync_manager::OutboxEntry],
+    client_id: ClientId,
+    batch_id: BatchId,
+) -> Option<(String, String)> {
+    outbox.iter().find_map(|entry| match &entry.payload {
+        SyncPayload::BatchFate {
+            fate:
+                BatchFate::Rejected {
+                    batch_id: rejected,
+                    code,
+                    reason,
+                },
+        } if entry.destination == Destination::Client(client_id) && *rejected == batch_id => {
+            Some((code.clone(), reason.clone()))
+        }
+        _ => None,
+    })
+}
+
+fn push_client_note(
+    qm: &mut QueryManager,
+    client_id: ClientId,
+    branch: &str,
+    body: &str,
+) -> BatchId {
+    push_client_notes(qm, client_id, branch, &[body])
+}
+
+/// Push one row per body, all in the same direct batch.
+fn push_client_notes(
+    qm: &mut QueryManager,
+    client_id: ClientId,
+    branch: &str,
+    bodies: &[&str],
+) -> BatchId {
+    let descriptor = qm.schema_context().current_schema[&TableName::new("notes")]
+        .columns
+        .clone();
+    let batch_id = BatchId::new();
+    for body in bodies {
+        let row_id = ObjectId::new();
+        let mut row = stored_row_commit(
+            smallvec![],
+            encode_row(&descriptor, &[Value::Text(body.to_string())]).unwrap(),
+            1_000,
+            "alice",
+        )
+        .to_row(row_id, branch, RowState::VisibleDirect);
+        row.batch_id = batch_id;
+        qm.sync_manager_mut().push_inbox(InboxEntry {
+            source: Source::Client(client_id),
+            payload: SyncPayload::RowBatchCreated {
+                metadata: Some(RowMetadata {
+                    id: row_id,
+                    metadata: HashMap::from([(
+                        MetadataKey::Table.to_string(),
+                        "notes".to_string(),
+                    )]),
+                }),
+                row,
+            },
+        });
+    }
+    batch_id
+}
+
+#[test]
+fn local_insert_rejects_oversized_value() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), limited_notes_schema());
+
+    let err = qm
+        .insert(
+            &mut storage,
+            "notes",
+            &[Value::Text("far more than sixteen bytes".into())],
+        )
+        .expect_err("oversized value must be rejected");
+
+    assert_eq!(
+        err,
+        QueryError::LimitExceeded {
+            table: TableName::new("notes"),
+            violation: LimitViolation::ValueTooLarge {
+                column: ColumnName::new("body"),
+                bytes: 27,
+                max_bytes: 16,
+            },
+        }
+    );
+}
+
+#[test]
+fn local_update_rejects_oversized_value() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), limited_notes_schema());
+    let inserted = qm
+        .insert(&mut storage, "notes", &[Value::Text("short".into())])
+        .expect("insert within limits");
+
+    let err = qm
+        .update(
+            &mut storage,
+            inserted.row_id,
+            &[Value::Text("far more than sixteen bytes".into())],
+        )
+        .expect_err("oversized update must be rejected");
+
+    assert!(
+        matches!(err, QueryError::LimitExceeded { .. }),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[test]
+fn local_insert_enforces_per_user_row_quota() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), limited_notes_schema());
+    let alice = PolicySession::new("alice");
+    let bob = PolicySession::new("bob");
+
+    qm.insert_with_session(
+        &mut storage,
+        "notes",
+        &[Value::Text("first".into())],
+        Some(&alice),
+    )
+    .expect("first row is within alice's quota");
+    let err = qm
+        .insert_with_session(
+            &mut storage,
+            "notes",
+            &[Value::Text("second".into())],
+            Some(&alice),
+        )
+        .expect_err("second row exceeds alice's quota");
+    assert_eq!(
+        err,
+        QueryError::LimitExceeded {
+            table: TableName::new("notes"),
+            violation: LimitViolation::RowQuotaExceeded {
+                user_id: "alice".to_string(),
+                rows: 1,
+                max_rows: 1,
+            },
+        }
+    );
+
+    qm.insert_with_session(
+        &mut storage,
+        "notes",
+        &[Value::Text("first".into())],
+        Some(&bob),
+    )
+    .expect("quotas are tracked per user");
+}
+
+#[test]
+fn server_rejects_client_rows_that_exceed_declared_limits() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), limited_notes_schema());
+    let branch = get_branch(&qm);
+    let client_id = ClientId::new();
+    connect_client(&mut qm, &storage, client_id);
+    qm.sync_manager_mut()
+        .set_client_session(client_id, PolicySession::new("alice"));
+
+    let oversized = push_client_note(&mut qm, client_id, &branch, "far more than sixteen bytes");
+    qm.process(&mut storage);
+    let outbox = qm.sync_manager_mut().take_outbox();
+    let (code, reason) =
+        note_batch_rejection(&outbox, client_id, oversized).expect("oversized row rejected");
+    assert_eq!(code, LIMIT_EXCEEDED_CODE);
+    assert!(
+        reason.contains("column body"),
+        "unexpected reason: {reason}"
+    );
+
+    let first = push_client_note(&mut qm, client_id, &branch, "first");
+    qm.process(&mut storage);
+    let outbox = qm.sync_manager_mut().take_outbox();
+    assert_eq!(note_batch_rejection(&outbox, client_id, first), None);
+
+    let second = push_client_note(&mut qm, client_id, &branch, "second");
+    qm.process(&mut storage);
+    let outbox = qm.sync_manager_mut().take_outbox();
+    let (code, reason) =
+        note_batch_rejection(&outbox, client_id, second).expect("row over quota rejected");
+    assert_eq!(code, LIMIT_EXCEEDED_CODE);
+    assert!(
+        reason.contains("already has 1 rows"),
+        "unexpected reason: {reason}"
+    );
+}
+
+#[test]
+fn deleting_a_row_frees_quota() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), limited_notes_schema());
+    let alice = PolicySession::new("alice");
+
+    let first = qm
+        .insert_with_session(
+            &mut storage,
+            "notes",
+            &[Value::Text("first".into())],
+            Some(&alice),
+        )
+        .expect("first row is within alice's quota");
+    qm.delete_with_session(&mut storage, first.row_id, Some(&alice))
+        .expect("alice can delete her row");
+
+    qm.insert_with_session(
+        &mut storage,
+        "notes",
+        &[Value::Text("second".into())],
+        Some(&alice),
+    )
+    .expect("deleted rows no longer count against the quota");
+}
+
+#[test]
+fn server_counts_rows_pending_in_the_same_batch() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), limited_notes_schema());
+    let branch = get_branch(&qm);
+    let client_id = ClientId::new();
+    connect_client(&mut qm, &storage, client_id);
+    qm.sync_manager_mut()
+        .set_client_session(client_id, PolicySession::new("alice"));
+
+    let batch = push_client_notes(&mut qm, client_id, &branch, &["first", "second"]);
+    qm.process(&mut storage);
+    let outbox = qm.sync_manager_mut().take_outbox();
+    let (code, reason) = note_batch_rejection(&outbox, client_id, batch)
+        .expect("a batch that goes over quota on its own is rejected");
+    assert_eq!(code, LIMIT_EXCEEDED_CODE);
+    assert!(
+        reason.contains("already has 1 rows"),
+        "unexpected reason: {reason}"
+    );
+
+    let retry = push_client_note(&mut qm, client_id, &branch, "retry");
+    qm.process(&mut storage);
+    let outbox = qm.sync_manager_mut().take_outbox();
+    assert_eq!(
+        note_batch_rejection(&outbox, client_id, retry),
+        None,
+        "the rejected batch no longer holds quota"
+    );
+}
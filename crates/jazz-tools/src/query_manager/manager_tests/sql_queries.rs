@@ -0,0 +1,218 @@
+use super::*;
+
+#[test]
+fn sql_query_filters_orders_and_paginates() {
+    let sync_manager = SyncManager::new();
+    let schema = test_schema();
+    let (mut qm, mut storage) = create_query_manager(sync_manager, schema);
+
+    for (name, score) in [("Alice", 50), ("Bob", 90), ("Carol", 70), ("Dave", 10)] {
+        qm.insert(
+            &mut storage,
+            "users",
+            &[Value::Text(name.into()), Value::Integer(score)],
+        )
+        .unwrap();
+    }
+
+    let query = qm
+        .sql_query(
+            "SELECT name, score FROM users WHERE score >= 20 AND name <> 'Carol' \
+             ORDER BY score DESC LIMIT 2",
+            None,
+        )
+        .unwrap();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    let names: Vec<Value> = results
+        .into_iter()
+        .map(|(_, values)| values[0].clone())
+        .collect();
+
+    assert_eq!(
+        names,
+        vec![Value::Text("Bob".into()), Value::Text("Alice".into())]
+    );
+}
+
+#[test]
+fn sql_query_joins_and_projects_joined_rows() {
+    let sync_manager = SyncManager::new();
+    let schema = join_schema_with_implicit_base_id();
+    let (mut qm, mut storage) = create_query_manager(sync_manager, schema);
+
+    let alice = qm
+        .insert(&mut storage, "users", &[Value::Text("Alice".into())])
+        .unwrap();
+    let bob = qm
+        .insert(&mut storage, "users", &[Value::Text("Bob".into())])
+        .unwrap();
+    let post = qm
+        .insert(
+            &mut storage,
+            "posts",
+            &[Value::Text("Hello".into()), Value::Uuid(alice.row_id)],
+        )
+        .unwrap();
+    qm.insert(
+        &mut storage,
+        "posts",
+        &[Value::Text("Other".into()), Value::Uuid(bob.row_id)],
+    )
+    .unwrap();
+
+    let query = qm
+        .sql_query(
+            "SELECT p.* FROM users u JOIN posts p ON p.author_id = u.id WHERE u.name = 'Alice'",
+            None,
+        )
+        .unwrap();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].0, post.row_id);
+    assert_eq!(results[0].1[0], Value::Text("Hello".into()));
+}
+
+#[test]
+fn sql_query_binds_session_parameters() {
+    let sync_manager = SyncManager::new();
+    let mut schema = Schema::new();
+    schema.insert(
+        TableName::new("todos"),
+        RowDescriptor::new(vec![
+            ColumnDescriptor::new("title", ColumnType::Text),
+            ColumnDescriptor::new("owner_id", ColumnType::Text),
+        ])
+        .into(),
+    );
+    let (mut qm, mut storage) = create_query_manager(sync_manager, schema);
+
+    qm.insert(
+        &mut storage,
+        "todos",
+        &[Value::Text("mine".into()), Value::Text("alice".into())],
+    )
+    .unwrap();
+    qm.insert(
+        &mut storage,
+        "todos",
+        &[Value::Text("theirs".into()), Value::Text("bob".into())],
+    )
+    .unwrap();
+
+    let sql = "SELECT title FROM todos WHERE owner_id = $session.user_id";
+    let error = qm.sql_query(sql, None).unwrap_err();
+    assert!(error.message.contains("needs a session"), "{error}");
+
+    let query = qm
+        .sql_query(sql, Some(&PolicySession::new("alice")))
+        .unwrap();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].1, vec![Value::Text("mine".into())]);
+}
+
+#[test]
+fn sql_recursive_query_expands_transitive_team_edges() {
+    let sync_manager = SyncManager::new();
+    let schema = recursive_team_schema();
+    let (mut qm, mut storage) = create_query_manager(sync_manager, schema);
+
+    // 1 -> 2 -> 3 -> 1 (cycle)
+    qm.insert(&mut storage, "teams", &[Value::Integer(1)])
+        .unwrap();
+    for (child, parent) in [(1, 2), (2, 3), (3, 1)] {
+        qm.insert(
+            &mut storage,
+            "team_edges",
+            &[Value::Integer(child), Value::Integer(parent)],
+        )
+        .unwrap();
+    }
+
+    let query = qm
+        .sql_query(
+            "WITH RECURSIVE reachable AS (
+                SELECT team_id FROM teams WHERE team_id = 1
+                UNION
+                SELECT parent_team AS team_id FROM team_edges
+                  JOIN reachable ON team_edges.child_team = reachable.team_id
+             )
+             SELECT team_id FROM reachable",
+            None,
+        )
+        .unwrap();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    let mut ids: Vec<i32> = results
+        .into_iter()
+        .filter_map(|(_, values)| match values.first() {
+            Some(Value::Integer(i)) => Some(*i),
+            _ => None,
+        })
+        .collect();
+    ids.sort_unstable();
+
+    assert_eq!(ids, vec![1, 2, 3]);
+}
+
+#[test]
+fn sql_recursive_query_with_hop_expands_transitive_closure() {
+    let sync_manager = SyncManager::new();
+    let schema = recursive_hop_team_schema();
+    let (mut qm, mut storage) = create_query_manager(sync_manager, schema);
+
+    let team1 = qm
+        .insert(&mut storage, "teams", &[Value::Text("team-1".into())])
+        .unwrap();
+    let team2 = qm
+        .insert(&mut storage, "teams", &[Value::Text("team-2".into())])
+        .unwrap();
+    let team3 = qm
+        .insert(&mut storage, "teams", &[Value::Text("team-3".into())])
+        .unwrap();
+    for (child, parent) in [(&team1, &team2), (&team2, &team3)] {
+        qm.insert(
+            &mut storage,
+            "team_edges",
+            &[Value::Uuid(child.row_id), Value::Uuid(parent.row_id)],
+        )
+        .unwrap();
+    }
+
+    let query = qm
+        .sql_query(
+            "WITH RECURSIVE reachable AS (
+                SELECT * FROM teams WHERE name = 'team-1'
+                UNION ALL
+                SELECT t.* FROM team_edges
+                  JOIN reachable ON team_edges.child_team = reachable.id
+                  JOIN teams t ON t.id = team_edges.parent_team
+             )
+             SELECT * FROM reachable",
+            None,
+        )
+        .unwrap();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    let mut names: Vec<String> = results
+        .into_iter()
+        .filter_map(|(_, values)| match values.first() {
+            Some(Value::Text(name)) => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+    names.sort();
+
+    assert_eq!(names, vec!["team-1", "team-2", "team-3"]);
+}
+
+#[test]
+fn sql_query_rejects_unknown_tables_with_position() {
+    let sync_manager = SyncManager::new();
+    let (qm, _storage) = create_query_manager(sync_manager, test_schema());
+
+    let error = qm.sql_query("SELECT * FROM accounts", None).unwrap_err();
+
+    assert_eq!(error.message, "unknown table 'accounts'");
+    assert_eq!(error.position, Some(14));
+}
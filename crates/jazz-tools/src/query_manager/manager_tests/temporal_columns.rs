@@ -0,0 +1,124 @@
+use super::*;
+use crate::query_manager::types::{Date, Interval, SchemaBuilder, Time};
+
+fn events_schema() -> Schema {
+    SchemaBuilder::new()
+        .table(
+            TableSchema::builder("events")
+                .column("title", ColumnType::Text)
+                .column("day", ColumnType::Date)
+                .column("starts", ColumnType::Time)
+                .column("length", ColumnType::Interval),
+        )
+        .build()
+}
+
+fn insert_event(
+    qm: &mut QueryManager,
+    storage: &mut MemoryStorage,
+    title: &str,
+    day: &str,
+    starts: &str,
+    length: &str,
+) -> ObjectId {
+    qm.insert(
+        storage,
+        "events",
+        &[
+            Value::Text(title.into()),
+            Value::Date(Date::parse(day).unwrap()),
+            Value::Time(Time::parse(starts).unwrap()),
+            Value::Interval(Interval::parse(length).unwrap()),
+        ],
+    )
+    .unwrap()
+    .row_id
+}
+
+fn titles(results: &[(ObjectId, Vec<Value>)]) -> Vec<Value> {
+    results
+        .iter()
+        .map(|(_, values)| values[0].clone())
+        .collect()
+}
+
+fn seed(qm: &mut QueryManager, storage: &mut MemoryStorage) {
+    insert_event(qm, storage, "Retro", "2024-03-01", "16:00", "PT45M");
+    insert_event(qm, storage, "Launch", "2024-02-29", "09:30", "PT2H");
+    insert_event(qm, storage, "Archive", "1969-12-31", "23:59:59", "P1D");
+    insert_event(qm, storage, "Planning", "2024-12-10", "09:00", "PT1H30M");
+}
+
+#[test]
+fn date_columns_range_query_and_sort_chronologically() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), events_schema());
+    seed(&mut qm, &mut storage);
+
+    let query = qm
+        .query("events")
+        .filter_between(
+            "day",
+            Value::Text("2024-02-01".into()),
+            Value::Date(Date::from_ymd(2024, 3, 31).unwrap()),
+        )
+        .order_by("day")
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        titles(&results),
+        vec![Value::Text("Launch".into()), Value::Text("Retro".into())],
+        "Text and Date bounds both compare as calendar dates"
+    );
+
+    let query = qm.query("events").order_by("day").build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        results.first().map(|(_, values)| values[0].clone()),
+        Some(Value::Text("Archive".into())),
+        "Dates before the epoch sort first"
+    );
+}
+
+#[test]
+fn time_and_interval_columns_compare_by_value() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), events_schema());
+    seed(&mut qm, &mut storage);
+
+    let query = qm
+        .query("events")
+        .filter_lt("starts", Value::Text("10:00".into()))
+        .order_by("starts")
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        titles(&results),
+        vec![Value::Text("Planning".into()), Value::Text("Launch".into())]
+    );
+
+    let query = qm
+        .query("events")
+        .filter_ge("length", Value::Text("PT1H".into()))
+        .order_by_desc("length")
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        titles(&results),
+        vec![
+            Value::Text("Archive".into()),
+            Value::Text("Launch".into()),
+            Value::Text("Planning".into()),
+        ]
+    );
+}
+
+#[test]
+fn unparseable_temporal_literals_are_rejected() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), events_schema());
+    seed(&mut qm, &mut storage);
+
+    let query = qm
+        .query("events")
+        .filter_eq("day", Value::Text("2024-02-30".into()))
+        .build();
+    assert!(execute_query(&mut qm, &mut storage, query).is_err());
+}
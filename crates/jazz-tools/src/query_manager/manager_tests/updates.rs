@@ -353,6 +353,79 @@ fn synced_update_is_visible_in_query() {
     assert_eq!(results.len(), 1, "Should find updated row by new score");
 }
 
+#[test]
+fn local_update_after_synced_update_from_fast_clock_wins_lww() {
+    use crate::query_manager::encoding::encode_row;
+    use crate::sync_manager::clock::HlcTimestamp;
+    use crate::sync_manager::{InboxEntry, ServerId, Source, SyncPayload};
+
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), test_schema());
+    let branch = get_branch(&qm);
+    let insert_handle = qm
+        .insert(
+            &mut storage,
+            "users",
+            &[Value::Text("Alice".into()), Value::Integer(100)],
+        )
+        .unwrap();
+    let row_id = insert_handle.row_id;
+    qm.process(&mut storage);
+
+    // Another writer whose clock runs a minute ahead of ours.
+    let remote_timestamp = load_visible_row(&storage, row_id, &branch).updated_at + 60 * 1_000_000;
+    let descriptor = RowDescriptor::new(vec![
+        ColumnDescriptor::new("name", ColumnType::Text),
+        ColumnDescriptor::new("score", ColumnType::Integer),
+    ]);
+    let remote_row = stored_row_commit(
+        smallvec![insert_handle.batch_id],
+        encode_row(
+            &descriptor,
+            &[Value::Text("Remote".into()), Value::Integer(200)],
+        )
+        .unwrap(),
+        remote_timestamp,
+        "bob",
+    )
+    .to_row(row_id, &branch, RowState::VisibleDirect);
+    qm.sync_manager_mut().push_inbox(InboxEntry {
+        source: Source::Server(ServerId::new()),
+        payload: SyncPayload::RowBatchCreated {
+            metadata: None,
+            row: remote_row,
+        },
+    });
+    qm.process(&mut storage);
+
+    qm.update(
+        &mut storage,
+        row_id,
+        &[Value::Text("Local".into()), Value::Integer(300)],
+    )
+    .unwrap();
+    qm.process(&mut storage);
+
+    let visible = load_visible_row(&storage, row_id, &branch);
+    let local = storage
+        .load_history_row_batch("users", &branch, row_id, visible.batch_id)
+        .unwrap()
+        .expect("local update should be in history");
+    assert!(
+        local.hlc() > HlcTimestamp::from_wall_clock(remote_timestamp),
+        "the causally later local write must carry the later HLC timestamp"
+    );
+    assert!(
+        local.updated_at < remote_timestamp,
+        "updated_at stays on the local wall clock"
+    );
+    let query = qm
+        .query("users")
+        .filter_eq("name", Value::Text("Local".into()))
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(results.len(), 1, "local update should win LWW");
+}
+
 #[test]
 fn update_reads_visible_region_after_legacy_commit_history_is_removed() {
     let schema = test_schema();
@@ -0,0 +1,205 @@
+use super::*;
+use crate::query_manager::query::QueryBuildError;
+use crate::query_manager::types::{SchemaBuilder, Vector};
+
+fn notes_schema() -> Schema {
+    SchemaBuilder::new()
+        .table(
+            TableSchema::builder("notes")
+                .column("title", ColumnType::Text)
+                .nullable_column("embedding", ColumnType::Vector { dims: 3 }),
+        )
+        .build()
+}
+
+fn vector(components: &[f32]) -> Vector {
+    Vector::new(components.to_vec()).unwrap()
+}
+
+fn insert_note(
+    qm: &mut QueryManager,
+    storage: &mut MemoryStorage,
+    title: &str,
+    embedding: Option<Vector>,
+) -> ObjectId {
+    qm.insert(
+        storage,
+        "notes",
+        &[
+            Value::Text(title.into()),
+            embedding.map(Value::Vector).unwrap_or(Value::Null),
+        ],
+    )
+    .unwrap()
+    .row_id
+}
+
+fn titles(results: &[(ObjectId, Vec<Value>)]) -> Vec<Value> {
+    results
+        .iter()
+        .map(|(_, values)| values[0].clone())
+        .collect()
+}
+
+fn seed(qm: &mut QueryManager, storage: &mut MemoryStorage) {
+    insert_note(qm, storage, "rust", Some(vector(&[1.0, 0.0, 0.0])));
+    insert_note(qm, storage, "rustc", Some(vector(&[0.9, 0.1, 0.0])));
+    insert_note(qm, storage, "cargo", Some(vector(&[0.7, 0.7, 0.0])));
+    insert_note(qm, storage, "gardening", Some(vector(&[0.0, 0.0, 1.0])));
+    insert_note(qm, storage, "draft", None);
+}
+
+#[test]
+fn nearest_returns_top_k_closest_first() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), notes_schema());
+    seed(&mut qm, &mut storage);
+
+    let query = qm
+        .query("notes")
+        .nearest("embedding", vector(&[1.0, 0.05, 0.0]), 3)
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        titles(&results),
+        vec![
+            Value::Text("rust".into()),
+            Value::Text("rustc".into()),
+            Value::Text("cargo".into()),
+        ]
+    );
+
+    // Filters narrow the candidates before ranking; NULL vectors never rank.
+    let query = qm
+        .query("notes")
+        .filter_ne("title", Value::Text("rust".into()))
+        .nearest("embedding", vector(&[0.1, 0.0, 1.0]), 10)
+        .build();
+    let results = execute_query(&mut qm, &mut storage, query).unwrap();
+    assert_eq!(
+        titles(&results),
+        vec![
+            Value::Text("gardening".into()),
+            Value::Text("rustc".into()),
+            Value::Text("cargo".into()),
+        ]
+    );
+}
+
+#[test]
+fn nearest_subscription_reranks_on_writes() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), notes_schema());
+    seed(&mut qm, &mut storage);
+
+    let query = qm
+        .query("notes")
+        .nearest("embedding", vector(&[0.0, 1.0, 0.0]), 1)
+        .build();
+    let sub_id = qm.subscribe(query).unwrap();
+    qm.process(&mut storage);
+    let updates = qm.take_updates();
+    assert_eq!(updates.len(), 1);
+    let added = decode_row(&updates[0].descriptor, &updates[0].delta.added[0].data).unwrap();
+    assert_eq!(added[0], Value::Text("cargo".into()));
+
+    let closer = insert_note(
+        &mut qm,
+        &mut storage,
+        "compost",
+        Some(vector(&[0.1, 1.0, 0.0])),
+    );
+    qm.process(&mut storage);
+    let updates = qm.take_updates();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].subscription_id, sub_id);
+    assert_eq!(updates[0].delta.added.len(), 1);
+    assert_eq!(updates[0].delta.added[0].id, closer);
+    assert_eq!(updates[0].delta.removed.len(), 1);
+
+    qm.delete(&mut storage, closer).unwrap();
+    qm.process(&mut storage);
+    let updates = qm.take_updates();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].delta.removed[0].id, closer);
+    let added = decode_row(&updates[0].descriptor, &updates[0].delta.added[0].data).unwrap();
+    assert_eq!(added[0], Value::Text("cargo".into()));
+}
+
+#[test]
+fn nearest_results_still_pass_select_policies() {
+    let mut schema = Schema::new();
+    schema.insert(
+        TableName::new("notes"),
+        TableSchema::with_policies(
+            RowDescriptor::new(vec![
+                ColumnDescriptor::new("owner_id", ColumnType::Text),
+                ColumnDescriptor::new("embedding", ColumnType::Vector { dims: 2 }),
+            ]),
+            TablePolicies::new()
+                .with_select(PolicyExpr::eq_session("owner_id", vec!["user_id".into()])),
+        ),
+    );
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), schema);
+    for (owner, embedding) in [
+        ("bob", [1.0, 0.0]),
+        ("alice", [0.8, 0.2]),
+        ("alice", [0.0, 1.0]),
+    ] {
+        qm.insert(
+            &mut storage,
+            "notes",
+            &[Value::Text(owner.into()), Value::Vector(vector(&embedding))],
+        )
+        .unwrap();
+    }
+
+    let query = qm
+        .query("notes")
+        .nearest("embedding", vector(&[1.0, 0.0]), 1)
+        .build();
+    let sub_id = qm
+        .subscribe_with_session(query, Some(PolicySession::new("alice")), None)
+        .unwrap();
+    qm.process(&mut storage);
+    let updates = qm.take_updates();
+    let update = updates
+        .iter()
+        .find(|update| update.subscription_id == sub_id)
+        .unwrap();
+    assert_eq!(update.delta.added.len(), 1);
+    let added = decode_row(&update.descriptor, &update.delta.added[0].data).unwrap();
+    assert_eq!(
+        added,
+        vec![
+            Value::Text("alice".into()),
+            Value::Vector(vector(&[0.8, 0.2])),
+        ]
+    );
+}
+
+#[test]
+fn nearest_rejects_ordering_and_mismatched_vectors() {
+    let (mut qm, mut storage) = create_query_manager(SyncManager::new(), notes_schema());
+    seed(&mut qm, &mut storage);
+
+    let built = qm
+        .query("notes")
+        .nearest("embedding", vector(&[1.0, 0.0, 0.0]), 2)
+        .order_by("title")
+        .try_build();
+    assert_eq!(built.unwrap_err(), QueryBuildError::NearestWithOrdering);
+
+    let query = qm
+        .query("notes")
+        .nearest("embedding", vector(&[1.0, 0.0]), 2)
+        .build();
+    assert!(execute_query(&mut qm, &mut storage, query).is_err());
+
+    let query = qm.query("notes").order_by("embedding").build();
+    assert!(execute_query(&mut qm, &mut storage, query).is_err());
+
+    let query = qm
+        .query("notes")
+        .filter_eq("embedding", Value::Vector(vector(&[1.0, 0.0, 0.0])))
+        .build();
+    assert!(execute_query(&mut qm, &mut storage, query).is_err());
+}
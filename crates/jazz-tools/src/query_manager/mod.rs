@@ -4,6 +4,8 @@ pub mod graph;
 pub mod graph_nodes;
 pub mod index;
 pub mod indices;
+pub mod json_path;
+pub mod limits;
 pub mod magic_columns;
 pub mod manager;
 pub mod policy;
@@ -15,11 +17,14 @@ mod query_to_relation_ir;
 pub mod query_wire;
 pub mod relation_ir;
 mod relation_ir_query_plan;
+pub mod scalar_expr;
 pub mod server_queries;
 pub mod session;
 pub mod settlement_eval_cache;
+pub mod sql;
 pub mod subscriptions;
 pub mod types;
+pub mod vector_index;
 pub mod writes;
 
 pub use graph_nodes::output::QuerySubscriptionId;
@@ -1044,9 +1044,13 @@ fn compare_values_for_ordering(left: &Value, right: &Value) -> Option<std::cmp::
         (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
         (Value::BigInt(a), Value::BigInt(b)) => Some(a.cmp(b)),
         (Value::Double(a), Value::Double(b)) => Some(a.total_cmp(b)),
+        (Value::Decimal(a), Value::Decimal(b)) => Some(a.cmp(b)),
         (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
         (Value::Text(a), Value::Text(b)) => Some(a.cmp(b)),
         (Value::Timestamp(a), Value::Timestamp(b)) => Some(a.cmp(b)),
+        (Value::Date(a), Value::Date(b)) => Some(a.cmp(b)),
+        (Value::Time(a), Value::Time(b)) => Some(a.cmp(b)),
+        (Value::Interval(a), Value::Interval(b)) => Some(a.cmp(b)),
         (Value::Uuid(a), Value::Uuid(b)) => Some(a.cmp(b)),
         (Value::Null, Value::Null) => Some(std::cmp::Ordering::Equal),
         (Value::Null, _) => Some(std::cmp::Ordering::Less),
@@ -1350,6 +1354,10 @@ pub fn bind_relation_refs(
             PredicateExpr::IsNotNull { column } => Some(PredicateExpr::IsNotNull {
                 column: column.clone(),
             }),
+            PredicateExpr::Within { column, region } => Some(PredicateExpr::Within {
+                column: column.clone(),
+                region: *region,
+            }),
             PredicateExpr::In { left, values } => Some(PredicateExpr::In {
                 left: left.clone(),
                 values: values
@@ -1365,6 +1373,8 @@ pub fn bind_relation_refs(
                     })
                     .collect::<Option<Vec<_>>>()?,
             }),
+            // Computed comparisons only carry literals, so there is nothing to bind.
+            PredicateExpr::Compare(comparison) => Some(PredicateExpr::Compare(comparison.clone())),
             PredicateExpr::And(exprs) => Some(PredicateExpr::And(
                 exprs
                     .iter()
@@ -1,19 +1,37 @@
+use base64::Engine as _;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use crate::object::ObjectId;
+
 use crate::query_manager::encoding::encode_value_with_type;
 use crate::query_manager::graph_nodes::filter::Predicate;
 use crate::query_manager::graph_nodes::sort::{SortDirection, SortKey, SortTarget};
+use crate::query_manager::json_path::{JSON_PATH_SEPARATOR, JsonPath, JsonPathTest};
 use crate::query_manager::magic_columns::is_magic_column_name;
-use crate::query_manager::types::{ColumnType, RowDescriptor, TableName, TupleDescriptor, Value};
+use crate::query_manager::types::{
+    ColumnType, GeoBox, GeoCircle, GeoRegion, RowDescriptor, TableName, TupleDescriptor, Value,
+    Vector,
+};
 
 use super::query_to_relation_ir::normalize_query_to_rel_expr;
-use super::relation_ir::ColumnRef;
+use super::relation_ir::{ColumnRef, PredicateCmpOp, ScalarComparison};
+use super::scalar_expr::BoundCondition;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum QueryBuildError {
     UnsupportedShape,
-    NullBetweenBound { column: String },
+    NullBetweenBound {
+        column: String,
+    },
+    /// A page cursor carries a different number of sort values than the
+    /// query orders by.
+    CursorArity {
+        expected: usize,
+        actual: usize,
+    },
+    /// `nearest()` fixes the ordering and the row count itself.
+    NearestWithOrdering,
 }
 
 impl fmt::Display for QueryBuildError {
@@ -31,6 +49,18 @@ impl fmt::Display for QueryBuildError {
                     "BETWEEN does not support NULL bounds for column '{column}'"
                 )
             }
+            QueryBuildError::CursorArity { expected, actual } => {
+                write!(
+                    f,
+                    "page cursor has {actual} sort values but the query orders by {expected} keys"
+                )
+            }
+            QueryBuildError::NearestWithOrdering => {
+                write!(
+                    f,
+                    "nearest() cannot be combined with order_by, limit, offset or page cursors"
+                )
+            }
         }
     }
 }
@@ -43,9 +73,11 @@ fn parse_condition_column(column: &str) -> Option<(Option<&str>, &str)> {
         return None;
     }
 
-    if let Some((scope, name)) = trimmed.rsplit_once('.') {
-        let scope = scope.trim();
-        let name = name.trim();
+    // Only the part before a JSON path may carry a scope; path keys can hold dots.
+    let head_len = trimmed.find(JSON_PATH_SEPARATOR).unwrap_or(trimmed.len());
+    if let Some(dot) = trimmed[..head_len].rfind('.') {
+        let scope = trimmed[..dot].trim();
+        let name = trimmed[dot + 1..].trim();
         if !scope.is_empty() && !name.is_empty() {
             return Some((Some(scope), name));
         }
@@ -159,6 +191,10 @@ pub enum Condition {
     IsNull { column: String },
     /// Column is not null.
     IsNotNull { column: String },
+    /// Geo point column lies inside a latitude/longitude box.
+    WithinBox { column: String, bounds: GeoBox },
+    /// Geo point column lies within `circle.radius_meters` of its centre.
+    WithinRadius { column: String, circle: GeoCircle },
 }
 
 impl Condition {
@@ -182,6 +218,52 @@ impl Condition {
         }
     }
 
+    /// JSON path this condition reads, when its column reaches into a `Json` column.
+    pub fn json_path(&self) -> Option<JsonPath> {
+        JsonPath::parse(self.column())
+    }
+
+    fn to_json_path_predicate(&self, col_index: usize, path: JsonPath) -> Predicate {
+        let test = |op: PredicateCmpOp, value: &Value| JsonPathTest::Compare {
+            op,
+            value: value.clone(),
+        };
+        let test = match self {
+            Condition::Eq { value, .. } => test(PredicateCmpOp::Eq, value),
+            Condition::Ne { value, .. } => test(PredicateCmpOp::Ne, value),
+            Condition::Lt { value, .. } => test(PredicateCmpOp::Lt, value),
+            Condition::Le { value, .. } => test(PredicateCmpOp::Le, value),
+            Condition::Gt { value, .. } => test(PredicateCmpOp::Gt, value),
+            Condition::Ge { value, .. } => test(PredicateCmpOp::Ge, value),
+            Condition::Between { min, max, .. } => {
+                return Predicate::And(vec![
+                    Predicate::JsonPath {
+                        col_index,
+                        path: path.clone(),
+                        test: test(PredicateCmpOp::Ge, min),
+                    },
+                    Predicate::JsonPath {
+                        col_index,
+                        path,
+                        test: test(PredicateCmpOp::Le, max),
+                    },
+                ]);
+            }
+            Condition::Contains { value, .. } => JsonPathTest::Contains(value.clone()),
+            Condition::IsNull { .. } => JsonPathTest::IsNull,
+            Condition::IsNotNull { .. } => JsonPathTest::IsNotNull,
+            // Compilation rejects geo filters on JSON paths.
+            Condition::WithinBox { .. } | Condition::WithinRadius { .. } => {
+                return Predicate::Or(vec![]);
+            }
+        };
+        Predicate::JsonPath {
+            col_index,
+            path,
+            test,
+        }
+    }
+
     fn to_row_id_predicate(&self, element_index: usize) -> Predicate {
         if let Some(predicate) = self.row_id_null_literal_predicate(element_index) {
             return predicate;
@@ -224,7 +306,9 @@ impl Condition {
                     value: encode(max),
                 },
             ]),
-            Condition::Contains { .. } => Predicate::Or(vec![]),
+            Condition::Contains { .. }
+            | Condition::WithinBox { .. }
+            | Condition::WithinRadius { .. } => Predicate::Or(vec![]),
             Condition::IsNull { .. } => Predicate::RowIdIsNull { element_index },
             Condition::IsNotNull { .. } => Predicate::RowIdIsNotNull { element_index },
         }
@@ -243,6 +327,17 @@ impl Condition {
             Condition::Contains { column, .. } => column,
             Condition::IsNull { column } => column,
             Condition::IsNotNull { column } => column,
+            Condition::WithinBox { column, .. } => column,
+            Condition::WithinRadius { column, .. } => column,
+        }
+    }
+
+    /// Area tested by a geo filter, or `None` for other conditions.
+    pub fn geo_region(&self) -> Option<GeoRegion> {
+        match self {
+            Condition::WithinBox { bounds, .. } => Some(GeoRegion::Box(*bounds)),
+            Condition::WithinRadius { circle, .. } => Some(GeoRegion::Circle(*circle)),
+            _ => None,
         }
     }
 
@@ -281,6 +376,16 @@ impl Condition {
 
     /// Convert to a Predicate for filter evaluation.
     pub fn to_predicate(&self, descriptor: &RowDescriptor) -> Option<Predicate> {
+        if let Some(path) = self.json_path()
+            && let Some(col_index) = descriptor.column_index(path.column())
+            && matches!(
+                descriptor.columns[col_index].column_type,
+                ColumnType::Json { .. }
+            )
+        {
+            return Some(self.to_json_path_predicate(col_index, path));
+        }
+
         if let Some(col_index) = descriptor.column_index(self.column()) {
             let col_type = &descriptor.columns[col_index].column_type;
             if let Some(predicate) = self.null_literal_predicate(col_index) {
@@ -328,6 +433,14 @@ impl Condition {
                 },
                 Condition::IsNull { .. } => Predicate::IsNull { col_index },
                 Condition::IsNotNull { .. } => Predicate::IsNotNull { col_index },
+                Condition::WithinBox { bounds, .. } => Predicate::Within {
+                    col_index,
+                    region: GeoRegion::Box(*bounds),
+                },
+                Condition::WithinRadius { circle, .. } => Predicate::Within {
+                    col_index,
+                    region: GeoRegion::Circle(*circle),
+                },
             });
         }
 
@@ -337,6 +450,22 @@ impl Condition {
 
     /// Convert to a Predicate using a TupleDescriptor so scoped join refs can resolve.
     pub fn to_tuple_predicate(&self, tuple_descriptor: &TupleDescriptor) -> Option<Predicate> {
+        if let Some(path) = self.json_path() {
+            let base_column = match self.column_scope() {
+                Some(scope) => format!("{scope}.{}", path.column()),
+                None => path.column().to_string(),
+            };
+            let col_index = tuple_condition_column_index(tuple_descriptor, &base_column)?;
+            let combined_descriptor = tuple_descriptor.combined_descriptor();
+            if !matches!(
+                combined_descriptor.columns[col_index].column_type,
+                ColumnType::Json { .. }
+            ) {
+                return None;
+            }
+            return Some(self.to_json_path_predicate(col_index, path));
+        }
+
         if let Some(col_index) = tuple_condition_column_index(tuple_descriptor, self.raw_column()) {
             let combined_descriptor = tuple_descriptor.combined_descriptor();
             let col_type = &combined_descriptor.columns[col_index].column_type;
@@ -385,6 +514,14 @@ impl Condition {
                 },
                 Condition::IsNull { .. } => Predicate::IsNull { col_index },
                 Condition::IsNotNull { .. } => Predicate::IsNotNull { col_index },
+                Condition::WithinBox { bounds, .. } => Predicate::Within {
+                    col_index,
+                    region: GeoRegion::Box(*bounds),
+                },
+                Condition::WithinRadius { circle, .. } => Predicate::Within {
+                    col_index,
+                    region: GeoRegion::Circle(*circle),
+                },
             });
         }
 
@@ -413,6 +550,10 @@ impl Condition {
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Conjunction {
     pub conditions: Vec<Condition>,
+    /// Comparisons between computed expressions. These never drive index scans
+    /// and are always checked by the residual filter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comparisons: Vec<ScalarComparison>,
 }
 
 impl Conjunction {
@@ -424,17 +565,27 @@ impl Conjunction {
         self.conditions.push(condition);
     }
 
+    /// True when the conjunction places no constraint on rows.
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty() && self.comparisons.is_empty()
+    }
+
     /// Convert to a Predicate.
     pub fn to_predicate(&self, descriptor: &RowDescriptor) -> Predicate {
-        if self.conditions.is_empty() {
+        if self.is_empty() {
             return Predicate::True;
         }
 
-        let predicates: Vec<_> = self
+        let mut predicates: Vec<_> = self
             .conditions
             .iter()
             .filter_map(|c| c.to_predicate(descriptor))
             .collect();
+        if !self.comparisons.is_empty() {
+            let tuple_descriptor =
+                TupleDescriptor::single_with_materialization("", descriptor.clone(), true);
+            predicates.extend(self.comparison_predicates(&tuple_descriptor));
+        }
 
         if predicates.len() == 1 {
             predicates.into_iter().next().unwrap()
@@ -445,15 +596,16 @@ impl Conjunction {
 
     /// Convert to a Predicate using a TupleDescriptor for scoped join refs.
     pub fn to_tuple_predicate(&self, tuple_descriptor: &TupleDescriptor) -> Predicate {
-        if self.conditions.is_empty() {
+        if self.is_empty() {
             return Predicate::True;
         }
 
-        let predicates: Vec<_> = self
+        let mut predicates: Vec<_> = self
             .conditions
             .iter()
             .filter_map(|c| c.to_tuple_predicate(tuple_descriptor))
             .collect();
+        predicates.extend(self.comparison_predicates(tuple_descriptor));
 
         if predicates.len() == 1 {
             predicates.into_iter().next().unwrap()
@@ -461,6 +613,21 @@ impl Conjunction {
             Predicate::And(predicates)
         }
     }
+
+    /// Bind expression comparisons. A comparison that cannot be bound matches
+    /// no rows; plans are validated before compilation, so this only guards
+    /// against descriptors that drift after validation.
+    fn comparison_predicates(&self, tuple_descriptor: &TupleDescriptor) -> Vec<Predicate> {
+        self.comparisons
+            .iter()
+            .map(
+                |comparison| match BoundCondition::bind_comparison(comparison, tuple_descriptor) {
+                    Ok(condition) => Predicate::Scalar(Box::new(condition)),
+                    Err(_) => Predicate::Or(vec![]),
+                },
+            )
+            .collect()
+    }
 }
 
 /// Specification for an array subquery (correlated subquery producing array column).
@@ -519,6 +686,72 @@ impl ArraySubquerySpec {
     }
 }
 
+/// Top-`k` rows by cosine distance between `column` and `vector`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NearestSpec {
+    pub column: String,
+    pub vector: Vector,
+    pub k: usize,
+}
+
+/// Keyset pagination position: the sort key values of one row plus its id.
+///
+/// `sort_values` line up with the query's `order_by` (or hold just the row id
+/// when the query has no explicit ordering). The row id breaks ties, matching
+/// the sort node's id tie-breaker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageCursor {
+    pub sort_values: Vec<Value>,
+    pub row_id: ObjectId,
+}
+
+impl PageCursor {
+    /// Encode as an opaque URL-safe token for clients to hand back later.
+    pub fn to_token(&self) -> String {
+        let json = serde_json::to_vec(self).expect("page cursor serializes");
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(json)
+    }
+
+    /// Decode a token produced by [`PageCursor::to_token`].
+    pub fn from_token(token: &str) -> Option<Self> {
+        let json = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(token)
+            .ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+}
+
+/// Correlated `EXISTS` / `NOT EXISTS` condition on the outer query.
+///
+/// Lowered to a semi join (or anti join when `negated`) against `table`; the
+/// outer row passes while some inner row with `inner_column = outer_column`
+/// satisfies `filters` (or while none does, for `NOT EXISTS`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExistsSubquerySpec {
+    /// Inner table to probe.
+    pub table: TableName,
+    /// Optional alias for the inner table.
+    #[serde(default)]
+    pub alias: Option<String>,
+    /// Column in inner table to correlate with outer.
+    pub inner_column: String,
+    /// Column in outer table (or alias.column) to use as correlation value.
+    pub outer_column: String,
+    /// Filters the inner row must satisfy to count as a match.
+    #[serde(default)]
+    pub filters: Vec<Condition>,
+    /// `true` for `NOT EXISTS`.
+    #[serde(default)]
+    pub negated: bool,
+}
+
+impl ExistsSubquerySpec {
+    /// Get the effective name (alias if set, otherwise table name).
+    pub fn effective_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(self.table.as_str())
+    }
+}
+
 /// Specification for a recursive relation expansion.
 ///
 /// The current query acts as the seed relation. Each recursive step evaluates
@@ -587,6 +820,16 @@ pub struct Query {
     /// Offset.
     #[serde(default)]
     pub offset: usize,
+    /// Keyset pagination: only rows strictly after this position in sort order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<PageCursor>,
+    /// Keyset pagination: only rows strictly before this position. Without
+    /// `after`, the limit window is taken from the end of the ordered rows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<PageCursor>,
+    /// Rank rows by vector distance and keep the closest `k`, closest first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nearest: Option<NearestSpec>,
     /// If true, also scan _id_deleted to include soft-deleted rows.
     #[serde(default)]
     pub include_deleted: bool,
@@ -596,6 +839,9 @@ pub struct Query {
     /// Array subqueries (correlated subqueries producing array columns).
     #[serde(default)]
     pub array_subqueries: Vec<ArraySubquerySpec>,
+    /// Correlated `EXISTS` / `NOT EXISTS` conditions, ANDed with `disjuncts`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exists_subqueries: Vec<ExistsSubquerySpec>,
     /// Optional recursive relation expansion.
     #[serde(default)]
     pub recursive: Option<RecursiveSpec>,
@@ -645,9 +891,30 @@ impl Query {
             Self::validate_conditions(&disjunct.conditions)?;
         }
         Self::validate_array_subqueries(&self.array_subqueries)?;
+        for spec in &self.exists_subqueries {
+            Self::validate_conditions(&spec.filters)?;
+        }
         if let Some(recursive) = &self.recursive {
             Self::validate_conditions(&recursive.filters)?;
         }
+        if self.nearest.is_some()
+            && (!self.order_by.is_empty()
+                || self.limit.is_some()
+                || self.offset > 0
+                || self.after.is_some()
+                || self.before.is_some())
+        {
+            return Err(QueryBuildError::NearestWithOrdering);
+        }
+        let expected = self.order_by.len().max(1);
+        for cursor in self.after.iter().chain(&self.before) {
+            if cursor.sort_values.len() != expected {
+                return Err(QueryBuildError::CursorArity {
+                    expected,
+                    actual: cursor.sort_values.len(),
+                });
+            }
+        }
         Ok(())
     }
 
@@ -663,9 +930,13 @@ impl Query {
             order_by: Vec::new(),
             limit: None,
             offset: 0,
+            after: None,
+            before: None,
+            nearest: None,
             include_deleted: false,
             select_columns: None,
             array_subqueries: Vec::new(),
+            exists_subqueries: Vec::new(),
             recursive: None,
             result_element_index: None,
             relation_ir: crate::query_manager::relation_ir::RelExpr::TableScan { table },
@@ -689,6 +960,11 @@ impl Query {
         !self.array_subqueries.is_empty()
     }
 
+    /// Whether the limit window counts back from a `before` cursor.
+    pub fn pages_backward(&self) -> bool {
+        self.before.is_some() && self.after.is_none()
+    }
+
     /// Check if this query has a recursive expansion.
     pub fn has_recursive(&self) -> bool {
         self.recursive.is_some()
@@ -719,11 +995,7 @@ impl Query {
         }
 
         // Filter out empty conjunctions
-        let non_empty: Vec<_> = self
-            .disjuncts
-            .iter()
-            .filter(|d| !d.conditions.is_empty())
-            .collect();
+        let non_empty: Vec<_> = self.disjuncts.iter().filter(|d| !d.is_empty()).collect();
 
         if non_empty.is_empty() {
             return Predicate::True;
@@ -880,6 +1152,26 @@ impl QueryBuilder {
         self
     }
 
+    /// Keep rows whose geo point column lies inside `bounds`.
+    pub fn filter_within_box(mut self, column: impl Into<String>, bounds: GeoBox) -> Self {
+        let current = self.query.disjuncts.last_mut().unwrap();
+        current.add(Condition::WithinBox {
+            column: column.into(),
+            bounds,
+        });
+        self
+    }
+
+    /// Keep rows whose geo point column lies inside `circle`.
+    pub fn filter_within_radius(mut self, column: impl Into<String>, circle: GeoCircle) -> Self {
+        let current = self.query.disjuncts.last_mut().unwrap();
+        current.add(Condition::WithinRadius {
+            column: column.into(),
+            circle,
+        });
+        self
+    }
+
     /// Add an is null filter condition.
     pub fn filter_is_null(mut self, column: impl Into<String>) -> Self {
         let current = self.query.disjuncts.last_mut().unwrap();
@@ -908,6 +1200,11 @@ impl QueryBuilder {
         self
     }
 
+    /// Require a JSON path such as `settings->theme` to hold a non-null value.
+    pub fn filter_has_key(self, path: impl Into<String>) -> Self {
+        self.filter_is_not_null(path)
+    }
+
     /// Start a new OR branch.
     pub fn or(mut self) -> Self {
         self.query.disjuncts.push(Conjunction::new());
@@ -942,6 +1239,38 @@ impl QueryBuilder {
         self
     }
 
+    /// Page forward: keep rows strictly after `cursor` in sort order.
+    ///
+    /// The cursor comes from a previous page's `next_cursor`. Unlike `offset`,
+    /// the page stays put when rows are inserted ahead of it.
+    pub fn after(mut self, cursor: PageCursor) -> Self {
+        self.query.after = Some(cursor);
+        self
+    }
+
+    /// Page backward: keep the rows just before `cursor` in sort order.
+    ///
+    /// Combined with `limit`, this yields the `limit` rows immediately
+    /// preceding the cursor, still in ascending sort order.
+    pub fn before(mut self, cursor: PageCursor) -> Self {
+        self.query.before = Some(cursor);
+        self
+    }
+
+    /// Keep the `k` rows whose `column` vector is closest to `vector` by
+    /// cosine distance, closest first. The result stays live: inserts, updates
+    /// and deletes re-rank it. Rows with a `NULL` vector are skipped.
+    ///
+    /// Replaces `order_by`/`limit`; combining them fails at build time.
+    pub fn nearest(mut self, column: impl Into<String>, vector: Vector, k: usize) -> Self {
+        self.query.nearest = Some(NearestSpec {
+            column: column.into(),
+            vector,
+            k,
+        });
+        self
+    }
+
     /// Include soft-deleted rows in query results.
     /// When true, the query will also scan the _id_deleted index.
     pub fn include_deleted(mut self) -> Self {
@@ -1036,6 +1365,50 @@ impl QueryBuilder {
         self
     }
 
+    /// Keep only rows for which a correlated inner row exists (semi join).
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Projects with at least one open todo
+    /// QueryBuilder::new("projects")
+    ///     .where_exists(|sub| {
+    ///         sub.from("todos")
+    ///            .correlate("project", "projects.id")
+    ///            .filter_eq("done", Value::Boolean(false))
+    ///     })
+    ///     .build()
+    /// ```
+    pub fn where_exists<F>(mut self, builder_fn: F) -> Self
+    where
+        F: FnOnce(ExistsSubqueryBuilder) -> ExistsSubqueryBuilder,
+    {
+        let configured = builder_fn(ExistsSubqueryBuilder::new(false));
+        self.query.exists_subqueries.push(configured.build());
+        self
+    }
+
+    /// Keep only rows for which no correlated inner row exists (anti join).
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Users who are not members of a team
+    /// QueryBuilder::new("users")
+    ///     .where_not_exists(|sub| {
+    ///         sub.from("team_members")
+    ///            .correlate("user", "users.id")
+    ///            .filter_eq("team", Value::Uuid(team_id))
+    ///     })
+    ///     .build()
+    /// ```
+    pub fn where_not_exists<F>(mut self, builder_fn: F) -> Self
+    where
+        F: FnOnce(ExistsSubqueryBuilder) -> ExistsSubqueryBuilder,
+    {
+        let configured = builder_fn(ExistsSubqueryBuilder::new(true));
+        self.query.exists_subqueries.push(configured.build());
+        self
+    }
+
     pub(crate) fn with_array_subqueries(
         mut self,
         array_subqueries: Vec<ArraySubquerySpec>,
@@ -1240,6 +1613,105 @@ impl ArraySubqueryBuilder {
     }
 }
 
+/// Builder for configuring `EXISTS` / `NOT EXISTS` subqueries.
+///
+/// Used with `QueryBuilder::where_exists()` and `QueryBuilder::where_not_exists()`.
+#[derive(Debug)]
+pub struct ExistsSubqueryBuilder {
+    table: Option<TableName>,
+    alias: Option<String>,
+    inner_column: String,
+    outer_column: String,
+    filters: Vec<Condition>,
+    negated: bool,
+}
+
+impl ExistsSubqueryBuilder {
+    fn new(negated: bool) -> Self {
+        Self {
+            table: None,
+            alias: None,
+            inner_column: String::new(),
+            outer_column: String::new(),
+            filters: Vec::new(),
+            negated,
+        }
+    }
+
+    /// Set the inner table to probe.
+    pub fn from(mut self, table: impl Into<TableName>) -> Self {
+        self.table = Some(table.into());
+        self
+    }
+
+    /// Alias the inner table (needed when it is the outer table too).
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.alias = Some(alias.into());
+        self
+    }
+
+    /// Set the correlation columns.
+    ///
+    /// # Arguments
+    /// * `inner_column` - Column in the inner table to match
+    /// * `outer_column` - Column in the outer table (e.g., "users.id")
+    pub fn correlate(
+        mut self,
+        inner_column: impl Into<String>,
+        outer_column: impl Into<String>,
+    ) -> Self {
+        self.inner_column = inner_column.into();
+        self.outer_column = outer_column.into();
+        self
+    }
+
+    /// Add an equality filter on the inner rows.
+    pub fn filter_eq(mut self, column: impl Into<String>, value: Value) -> Self {
+        self.filters.push(Condition::Eq {
+            column: column.into(),
+            value,
+        });
+        self
+    }
+
+    /// Add a not-equals filter on the inner rows.
+    pub fn filter_ne(mut self, column: impl Into<String>, value: Value) -> Self {
+        self.filters.push(Condition::Ne {
+            column: column.into(),
+            value,
+        });
+        self
+    }
+
+    /// Require an inner column to be NULL.
+    pub fn filter_is_null(mut self, column: impl Into<String>) -> Self {
+        self.filters.push(Condition::IsNull {
+            column: column.into(),
+        });
+        self
+    }
+
+    /// Require an inner column to be non-NULL.
+    pub fn filter_is_not_null(mut self, column: impl Into<String>) -> Self {
+        self.filters.push(Condition::IsNotNull {
+            column: column.into(),
+        });
+        self
+    }
+
+    /// Build the ExistsSubquerySpec.
+    pub fn build(self) -> ExistsSubquerySpec {
+        ExistsSubquerySpec {
+            table: self.table.unwrap_or_else(|| TableName::new("")),
+            alias: self.alias,
+            inner_column: self.inner_column,
+            outer_column: self.outer_column,
+            filters: self.filters,
+            negated: self.negated,
+        }
+    }
+}
+
 /// Builder for configuring recursive relation expansions.
 #[derive(Debug)]
 pub struct RecursiveBuilder {
@@ -1,9 +1,11 @@
 use super::graph_nodes::sort::SortDirection;
-use super::query::{Condition, Conjunction, Query, RecursiveSpec};
+use super::json_path::JsonPath;
+use super::query::{Condition, Conjunction, ExistsSubquerySpec, PageCursor, Query, RecursiveSpec};
 use super::relation_ir::{
     ColumnRef, JoinCondition, JoinKind, KeyRef, OrderByExpr, OrderDirection, PredicateCmpOp,
-    PredicateExpr, ProjectColumn, ProjectExpr, RelExpr, RowIdRef, ValueRef,
+    PredicateExpr, ProjectColumn, ProjectExpr, RelExpr, RowIdRef, ScalarExpr, ValueRef,
 };
+use super::types::Value;
 
 /// Convert query DSL fields into relation IR when shape-compatible.
 ///
@@ -12,8 +14,11 @@ pub(crate) fn normalize_query_to_rel_expr(query: &Query) -> Option<RelExpr> {
     let mut relation = RelExpr::TableScan { table: query.table };
     let mut current_scope = query.effective_name().to_string();
     let mut scope_order = vec![current_scope.clone()];
-    let predicate = normalize_disjuncts(&query.disjuncts)?;
+    let predicate = normalize_disjuncts(&disjuncts_with_page_bounds(query))?;
     if let Some(recursive) = query.recursive.as_ref() {
+        if !query.exists_subqueries.is_empty() {
+            return None;
+        }
         if query.joins.is_empty() {
             if !matches!(predicate, PredicateExpr::True) {
                 relation = RelExpr::Filter {
@@ -62,6 +67,9 @@ pub(crate) fn normalize_query_to_rel_expr(query: &Query) -> Option<RelExpr> {
             current_scope = right_scope;
             scope_order.push(current_scope.clone());
         }
+        for spec in &query.exists_subqueries {
+            relation = normalize_exists_subquery(spec, relation, query.effective_name())?;
+        }
         if !matches!(predicate, PredicateExpr::True) {
             relation = RelExpr::Filter {
                 input: Box::new(relation),
@@ -81,11 +89,33 @@ pub(crate) fn normalize_query_to_rel_expr(query: &Query) -> Option<RelExpr> {
         };
     }
 
-    if !query.order_by.is_empty() {
+    if let Some(nearest) = &query.nearest {
         relation = RelExpr::OrderBy {
             input: Box::new(relation),
-            terms: query
-                .order_by
+            terms: vec![OrderByExpr {
+                column: ColumnRef::unscoped("_distance"),
+                direction: OrderDirection::Asc,
+                expr: Some(ScalarExpr::VectorDistance {
+                    left: Box::new(ScalarExpr::Column(column_ref(&nearest.column))),
+                    right: Box::new(ScalarExpr::Literal(Value::Vector(nearest.vector.clone()))),
+                }),
+            }],
+        };
+        return Some(RelExpr::Limit {
+            input: Box::new(relation),
+            limit: nearest.k,
+        });
+    }
+
+    let order_by = if query.after.is_some() || query.before.is_some() {
+        cursor_order(query)
+    } else {
+        query.order_by.clone()
+    };
+    if !order_by.is_empty() {
+        relation = RelExpr::OrderBy {
+            input: Box::new(relation),
+            terms: order_by
                 .iter()
                 .map(|(column, direction)| OrderByExpr {
                     column: ColumnRef::unscoped(column.clone()),
@@ -93,6 +123,7 @@ pub(crate) fn normalize_query_to_rel_expr(query: &Query) -> Option<RelExpr> {
                         SortDirection::Ascending => OrderDirection::Asc,
                         SortDirection::Descending => OrderDirection::Desc,
                     },
+                    expr: json_path_sort_expr(column),
                 })
                 .collect(),
         };
@@ -115,6 +146,198 @@ pub(crate) fn normalize_query_to_rel_expr(query: &Query) -> Option<RelExpr> {
     Some(relation)
 }
 
+/// Order-by terms on a JSON path sort by the extracted scalar.
+fn json_path_sort_expr(column: &str) -> Option<ScalarExpr> {
+    let path = JsonPath::parse(column)?;
+    Some(ScalarExpr::JsonPath {
+        input: Box::new(ScalarExpr::Column(column_ref(path.column()))),
+        steps: path.steps().to_vec(),
+    })
+}
+
+/// `scope.column` or a bare column name.
+fn column_ref(column: &str) -> ColumnRef {
+    match column.rsplit_once('.') {
+        Some((scope, name)) => ColumnRef::scoped(scope, name),
+        None => ColumnRef::unscoped(column),
+    }
+}
+
+/// Sort keys that page cursors refer to; queries without an explicit
+/// ordering page by row id.
+fn cursor_order(query: &Query) -> Vec<(String, SortDirection)> {
+    if query.order_by.is_empty() {
+        vec![("id".to_string(), SortDirection::Ascending)]
+    } else {
+        query.order_by.clone()
+    }
+}
+
+/// AND the `after` / `before` keyset bounds into the WHERE disjuncts.
+fn disjuncts_with_page_bounds(query: &Query) -> Vec<Conjunction> {
+    let order = cursor_order(query);
+    let id_column = if query.joins.is_empty() {
+        "id".to_string()
+    } else {
+        format!("{}.id", query.effective_name())
+    };
+    // An empty disjunct list means "no WHERE"; keep it neutral under AND.
+    let mut disjuncts = if query.disjuncts.is_empty() {
+        vec![Conjunction::new()]
+    } else {
+        query.disjuncts.clone()
+    };
+    // Rows without a vector have no distance and are never ranked.
+    if let Some(nearest) = &query.nearest {
+        for conjunction in &mut disjuncts {
+            conjunction.conditions.push(Condition::IsNotNull {
+                column: nearest.column.clone(),
+            });
+        }
+    }
+    for (cursor, after) in [(&query.after, true), (&query.before, false)] {
+        if let Some(cursor) = cursor {
+            let mut bounds = keyset_disjuncts(&order, &id_column, cursor, after);
+            if bounds.is_empty() {
+                // Nothing lies past the cursor; row ids are never NULL.
+                bounds.push(Conjunction {
+                    conditions: vec![Condition::IsNull {
+                        column: id_column.clone(),
+                    }],
+                    comparisons: Vec::new(),
+                });
+            }
+            disjuncts = and_disjuncts(&disjuncts, &bounds);
+        }
+    }
+    disjuncts
+}
+
+/// Expand "strictly after (or before) the cursor" into DNF.
+///
+/// Conjunction `i` pins the first `i` sort keys to the cursor's values and
+/// moves past it on key `i`; the last one breaks full ties on the row id,
+/// which always sorts ascending. Every conjunction bounds a single sort column,
+/// so the planner can turn it into an index range seek.
+fn keyset_disjuncts(
+    order: &[(String, SortDirection)],
+    id_column: &str,
+    cursor: &PageCursor,
+    after: bool,
+) -> Vec<Conjunction> {
+    let mut disjuncts = Vec::new();
+    let mut prefix = Vec::new();
+    for ((column, direction), value) in order.iter().zip(&cursor.sort_values) {
+        let walks_up = matches!(direction, SortDirection::Ascending) == after;
+        for condition in conditions_past(column, value, walks_up) {
+            let mut conditions = prefix.clone();
+            conditions.push(condition);
+            disjuncts.push(Conjunction {
+                conditions,
+                comparisons: Vec::new(),
+            });
+        }
+        prefix.push(if value.is_null() {
+            Condition::IsNull {
+                column: column.clone(),
+            }
+        } else {
+            Condition::Eq {
+                column: column.clone(),
+                value: value.clone(),
+            }
+        });
+    }
+
+    let ordered_by_id = order
+        .last()
+        .is_some_and(|(column, _)| matches!(column.as_str(), "id" | "_id"));
+    if !ordered_by_id {
+        let column = id_column.to_string();
+        let value = Value::Uuid(cursor.row_id);
+        prefix.push(if after {
+            Condition::Gt { column, value }
+        } else {
+            Condition::Lt { column, value }
+        });
+        disjuncts.push(Conjunction {
+            conditions: prefix,
+            comparisons: Vec::new(),
+        });
+    }
+    disjuncts
+}
+
+/// Alternatives selecting values strictly past `value` when walking a column
+/// upwards (or downwards). NULLs sort before every other value.
+fn conditions_past(column: &str, value: &Value, walks_up: bool) -> Vec<Condition> {
+    let column = column.to_string();
+    match (value.is_null(), walks_up) {
+        (true, true) => vec![Condition::IsNotNull { column }],
+        (true, false) => Vec::new(),
+        (false, true) => vec![Condition::Gt {
+            column,
+            value: value.clone(),
+        }],
+        (false, false) => vec![
+            Condition::Lt {
+                column: column.clone(),
+                value: value.clone(),
+            },
+            Condition::IsNull { column },
+        ],
+    }
+}
+
+/// Conjunction of two DNF predicates, distributed back into DNF.
+fn and_disjuncts(left: &[Conjunction], right: &[Conjunction]) -> Vec<Conjunction> {
+    let mut combined = Vec::with_capacity(left.len() * right.len());
+    for l in left {
+        for r in right {
+            let mut conjunction = l.clone();
+            conjunction.conditions.extend(r.conditions.iter().cloned());
+            conjunction
+                .comparisons
+                .extend(r.comparisons.iter().cloned());
+            combined.push(conjunction);
+        }
+    }
+    combined
+}
+
+/// Lower an `EXISTS` subquery to a semi (or anti) join whose right side is the
+/// filtered inner table. Unqualified outer columns refer to the base table.
+fn normalize_exists_subquery(
+    spec: &ExistsSubquerySpec,
+    outer: RelExpr,
+    outer_scope: &str,
+) -> Option<RelExpr> {
+    let inner_scope = spec.effective_name();
+    let mut inner = RelExpr::TableScan { table: spec.table };
+    if !spec.filters.is_empty() {
+        inner = RelExpr::Filter {
+            input: Box::new(inner),
+            predicate: normalize_conjunction(&Conjunction {
+                conditions: spec.filters.clone(),
+                comparisons: Vec::new(),
+            })?,
+        };
+    }
+    Some(RelExpr::Join {
+        left: Box::new(outer),
+        right: Box::new(inner),
+        on: vec![JoinCondition {
+            left: parse_join_column(&spec.outer_column, outer_scope)?,
+            right: parse_join_column(&spec.inner_column, inner_scope)?,
+        }],
+        join_kind: if spec.negated {
+            JoinKind::Anti
+        } else {
+            JoinKind::Semi
+        },
+    })
+}
+
 fn normalize_recursive_spec(spec: &RecursiveSpec, seed: RelExpr) -> Option<RelExpr> {
     let outer_column = spec
         .outer_column
@@ -230,7 +453,7 @@ fn normalize_disjuncts(disjuncts: &[Conjunction]) -> Option<PredicateExpr> {
 
     let mut predicates = Vec::new();
     for conjunction in disjuncts {
-        if conjunction.conditions.is_empty() {
+        if conjunction.is_empty() {
             return Some(PredicateExpr::True);
         }
         predicates.push(normalize_conjunction(conjunction)?);
@@ -254,6 +477,13 @@ fn normalize_conjunction(conjunction: &Conjunction) -> Option<PredicateExpr> {
             other => terms.push(other),
         }
     }
+    terms.extend(
+        conjunction
+            .comparisons
+            .iter()
+            .cloned()
+            .map(PredicateExpr::Compare),
+    );
 
     if terms.is_empty() {
         Some(PredicateExpr::True)
@@ -315,6 +545,12 @@ fn normalize_condition(condition: &Condition) -> Option<PredicateExpr> {
             left: column_ref,
             right: ValueRef::Literal(value.clone()),
         }),
+        Condition::WithinBox { .. } | Condition::WithinRadius { .. } => {
+            condition.geo_region().map(|region| PredicateExpr::Within {
+                column: column_ref,
+                region,
+            })
+        }
     }
 }
 
@@ -377,6 +613,7 @@ mod tests {
                         terms: vec![OrderByExpr {
                             column: ColumnRef::unscoped("name"),
                             direction: OrderDirection::Desc,
+                            expr: None,
                         }],
                     }),
                     offset: 3,
@@ -775,4 +1012,76 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn normalize_query_lowers_where_not_exists_to_anti_join() {
+        let query = QueryBuilder::new("projects")
+            .where_not_exists(|sub| {
+                sub.from("todos")
+                    .correlate("project", "id")
+                    .filter_eq("done", Value::Boolean(false))
+            })
+            .build();
+
+        let relation =
+            normalize_query_to_rel_expr(&query).expect("query should normalize to relation");
+        assert_eq!(
+            relation,
+            RelExpr::Join {
+                left: Box::new(RelExpr::TableScan {
+                    table: TableName::new("projects"),
+                }),
+                right: Box::new(RelExpr::Filter {
+                    input: Box::new(RelExpr::TableScan {
+                        table: TableName::new("todos"),
+                    }),
+                    predicate: PredicateExpr::Cmp {
+                        left: ColumnRef::unscoped("done"),
+                        op: PredicateCmpOp::Eq,
+                        right: ValueRef::Literal(Value::Boolean(false)),
+                    },
+                }),
+                on: vec![JoinCondition {
+                    left: ColumnRef::scoped("projects", "id"),
+                    right: ColumnRef::scoped("todos", "project"),
+                }],
+                join_kind: JoinKind::Anti,
+            }
+        );
+    }
+
+    #[test]
+    fn keyset_after_cursor_on_descending_key_expands_to_dnf_with_id_tiebreak() {
+        let row_id = crate::object::ObjectId::new();
+        let cursor = PageCursor {
+            sort_values: vec![Value::Integer(20)],
+            row_id,
+        };
+        let order = vec![("score".to_string(), SortDirection::Descending)];
+
+        let disjuncts = keyset_disjuncts(&order, "id", &cursor, true);
+        let conditions: Vec<_> = disjuncts.into_iter().map(|c| c.conditions).collect();
+        assert_eq!(
+            conditions,
+            vec![
+                vec![Condition::Lt {
+                    column: "score".to_string(),
+                    value: Value::Integer(20),
+                }],
+                vec![Condition::IsNull {
+                    column: "score".to_string(),
+                }],
+                vec![
+                    Condition::Eq {
+                        column: "score".to_string(),
+                        value: Value::Integer(20),
+                    },
+                    Condition::Gt {
+                        column: "id".to_string(),
+                        value: Value::Uuid(row_id),
+                    },
+                ],
+            ]
+        );
+    }
 }
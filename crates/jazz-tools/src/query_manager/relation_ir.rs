@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::query_manager::types::{TableName, Value};
+use crate::query_manager::types::{GeoRegion, TableName, Value};
 
 pub const RELATION_GATHER_MAX_DEPTH_DEFAULT: usize = 10;
 pub const RELATION_GATHER_MAX_DEPTH_HARD_CAP: usize = 64;
@@ -77,10 +77,17 @@ pub enum PredicateExpr {
     IsNotNull {
         column: ColumnRef,
     },
+    /// Geo point column lies inside the region.
+    Within {
+        column: ColumnRef,
+        region: GeoRegion,
+    },
     In {
         left: ColumnRef,
         values: Vec<ValueRef>,
     },
+    /// Comparison between two computed scalar expressions.
+    Compare(ScalarComparison),
     And(Vec<PredicateExpr>),
     Or(Vec<PredicateExpr>),
     Not(Box<PredicateExpr>),
@@ -88,11 +95,268 @@ pub enum PredicateExpr {
     False,
 }
 
+/// Arithmetic operators available to scalar expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArithmeticOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+/// Calendar units accepted by [`ScalarExpr::DateTrunc`].
+///
+/// Truncation happens in UTC. Weeks start on Monday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DateTruncUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// Typed scalar expression computed per row.
+///
+/// Expressions are type-checked against the input tuple when a graph node is
+/// built (see `query_manager::scalar_expr`). A `NULL` operand makes the whole
+/// expression `NULL`, except inside `Coalesce` and `Case`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScalarExpr {
+    Column(ColumnRef),
+    Literal(Value),
+    Arithmetic {
+        op: ArithmeticOp,
+        left: Box<ScalarExpr>,
+        right: Box<ScalarExpr>,
+    },
+    /// String concatenation. Numbers, booleans and ids are rendered as text.
+    Concat(Vec<ScalarExpr>),
+    Lower(Box<ScalarExpr>),
+    Upper(Box<ScalarExpr>),
+    /// First non-null argument. All arguments must share one type.
+    Coalesce(Vec<ScalarExpr>),
+    /// `CASE WHEN ... THEN ... ELSE ... END`. A missing `otherwise` yields `NULL`.
+    Case {
+        branches: Vec<CaseBranch>,
+        otherwise: Option<Box<ScalarExpr>>,
+    },
+    /// Truncate a `Timestamp` down to the start of the given unit.
+    DateTrunc {
+        unit: DateTruncUnit,
+        input: Box<ScalarExpr>,
+    },
+    /// Scalar at a path inside a `Json` value; see `query_manager::json_path`.
+    JsonPath {
+        input: Box<ScalarExpr>,
+        steps: Vec<String>,
+    },
+    /// Cosine distance between two `Vector` values of equal dimension.
+    VectorDistance {
+        left: Box<ScalarExpr>,
+        right: Box<ScalarExpr>,
+    },
+}
+
+impl ScalarExpr {
+    /// Visit every column reference, including those in `Case` conditions.
+    pub fn visit_column_refs<'a>(&'a self, visit: &mut dyn FnMut(&'a ColumnRef)) {
+        match self {
+            ScalarExpr::Column(column) => visit(column),
+            ScalarExpr::Literal(_) => {}
+            ScalarExpr::Arithmetic { left, right, .. }
+            | ScalarExpr::VectorDistance { left, right } => {
+                left.visit_column_refs(visit);
+                right.visit_column_refs(visit);
+            }
+            ScalarExpr::Concat(args) | ScalarExpr::Coalesce(args) => {
+                for arg in args {
+                    arg.visit_column_refs(visit);
+                }
+            }
+            ScalarExpr::Lower(input)
+            | ScalarExpr::Upper(input)
+            | ScalarExpr::DateTrunc { input, .. }
+            | ScalarExpr::JsonPath { input, .. } => input.visit_column_refs(visit),
+            ScalarExpr::Case {
+                branches,
+                otherwise,
+            } => {
+                for branch in branches {
+                    branch.when.visit_column_refs(visit);
+                    branch.then.visit_column_refs(visit);
+                }
+                if let Some(otherwise) = otherwise {
+                    otherwise.visit_column_refs(visit);
+                }
+            }
+        }
+    }
+
+    /// Rebuild the expression with every column reference rewritten.
+    pub fn map_column_refs(&self, map: &mut dyn FnMut(&ColumnRef) -> ColumnRef) -> ScalarExpr {
+        match self {
+            ScalarExpr::Column(column) => ScalarExpr::Column(map(column)),
+            ScalarExpr::Literal(value) => ScalarExpr::Literal(value.clone()),
+            ScalarExpr::Arithmetic { op, left, right } => ScalarExpr::Arithmetic {
+                op: *op,
+                left: Box::new(left.map_column_refs(map)),
+                right: Box::new(right.map_column_refs(map)),
+            },
+            ScalarExpr::Concat(args) => {
+                ScalarExpr::Concat(args.iter().map(|arg| arg.map_column_refs(map)).collect())
+            }
+            ScalarExpr::Lower(input) => ScalarExpr::Lower(Box::new(input.map_column_refs(map))),
+            ScalarExpr::Upper(input) => ScalarExpr::Upper(Box::new(input.map_column_refs(map))),
+            ScalarExpr::Coalesce(args) => {
+                ScalarExpr::Coalesce(args.iter().map(|arg| arg.map_column_refs(map)).collect())
+            }
+            ScalarExpr::Case {
+                branches,
+                otherwise,
+            } => ScalarExpr::Case {
+                branches: branches
+                    .iter()
+                    .map(|branch| CaseBranch {
+                        when: branch.when.map_column_refs(map),
+                        then: branch.then.map_column_refs(map),
+                    })
+                    .collect(),
+                otherwise: otherwise
+                    .as_ref()
+                    .map(|otherwise| Box::new(otherwise.map_column_refs(map))),
+            },
+            ScalarExpr::DateTrunc { unit, input } => ScalarExpr::DateTrunc {
+                unit: *unit,
+                input: Box::new(input.map_column_refs(map)),
+            },
+            ScalarExpr::JsonPath { input, steps } => ScalarExpr::JsonPath {
+                input: Box::new(input.map_column_refs(map)),
+                steps: steps.clone(),
+            },
+            ScalarExpr::VectorDistance { left, right } => ScalarExpr::VectorDistance {
+                left: Box::new(left.map_column_refs(map)),
+                right: Box::new(right.map_column_refs(map)),
+            },
+        }
+    }
+}
+
+/// One `WHEN ... THEN ...` arm of a [`ScalarExpr::Case`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaseBranch {
+    pub when: PredicateExpr,
+    pub then: ScalarExpr,
+}
+
+/// Comparison whose operands are both computed expressions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScalarComparison {
+    pub left: ScalarExpr,
+    pub op: PredicateCmpOp,
+    pub right: ScalarExpr,
+}
+
+impl ScalarComparison {
+    pub fn visit_column_refs<'a>(&'a self, visit: &mut dyn FnMut(&'a ColumnRef)) {
+        self.left.visit_column_refs(visit);
+        self.right.visit_column_refs(visit);
+    }
+
+    pub fn map_column_refs(&self, map: &mut dyn FnMut(&ColumnRef) -> ColumnRef) -> Self {
+        Self {
+            left: self.left.map_column_refs(map),
+            op: self.op,
+            right: self.right.map_column_refs(map),
+        }
+    }
+}
+
+impl PredicateExpr {
+    /// Visit every row-side column reference. Outer and frontier references in
+    /// value positions belong to other relations and are not visited.
+    pub fn visit_column_refs<'a>(&'a self, visit: &mut dyn FnMut(&'a ColumnRef)) {
+        match self {
+            PredicateExpr::Cmp { left, .. }
+            | PredicateExpr::Contains { left, .. }
+            | PredicateExpr::In { left, .. } => visit(left),
+            PredicateExpr::IsNull { column }
+            | PredicateExpr::IsNotNull { column }
+            | PredicateExpr::Within { column, .. } => visit(column),
+            PredicateExpr::Compare(comparison) => comparison.visit_column_refs(visit),
+            PredicateExpr::And(exprs) | PredicateExpr::Or(exprs) => {
+                for expr in exprs {
+                    expr.visit_column_refs(visit);
+                }
+            }
+            PredicateExpr::Not(inner) => inner.visit_column_refs(visit),
+            PredicateExpr::True | PredicateExpr::False => {}
+        }
+    }
+
+    /// Rebuild the predicate with every row-side column reference rewritten.
+    pub fn map_column_refs(&self, map: &mut dyn FnMut(&ColumnRef) -> ColumnRef) -> Self {
+        match self {
+            PredicateExpr::Cmp { left, op, right } => PredicateExpr::Cmp {
+                left: map(left),
+                op: *op,
+                right: right.clone(),
+            },
+            PredicateExpr::Contains { left, right } => PredicateExpr::Contains {
+                left: map(left),
+                right: right.clone(),
+            },
+            PredicateExpr::IsNull { column } => PredicateExpr::IsNull {
+                column: map(column),
+            },
+            PredicateExpr::IsNotNull { column } => PredicateExpr::IsNotNull {
+                column: map(column),
+            },
+            PredicateExpr::Within { column, region } => PredicateExpr::Within {
+                column: map(column),
+                region: *region,
+            },
+            PredicateExpr::In { left, values } => PredicateExpr::In {
+                left: map(left),
+                values: values.clone(),
+            },
+            PredicateExpr::Compare(comparison) => {
+                PredicateExpr::Compare(comparison.map_column_refs(map))
+            }
+            PredicateExpr::And(exprs) => {
+                PredicateExpr::And(exprs.iter().map(|expr| expr.map_column_refs(map)).collect())
+            }
+            PredicateExpr::Or(exprs) => {
+                PredicateExpr::Or(exprs.iter().map(|expr| expr.map_column_refs(map)).collect())
+            }
+            PredicateExpr::Not(inner) => PredicateExpr::Not(Box::new(inner.map_column_refs(map))),
+            PredicateExpr::True => PredicateExpr::True,
+            PredicateExpr::False => PredicateExpr::False,
+        }
+    }
+}
+
 /// Join kind for relation composition.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum JoinKind {
     Inner,
     Left,
+    /// Keep left rows that have at least one matching right row (`EXISTS`).
+    /// Output carries only the left columns.
+    Semi,
+    /// Keep left rows that have no matching right row (`NOT EXISTS`).
+    /// Output carries only the left columns.
+    Anti,
+}
+
+impl JoinKind {
+    /// Semi and anti joins filter the left side instead of combining rows.
+    pub fn is_existential(self) -> bool {
+        matches!(self, JoinKind::Semi | JoinKind::Anti)
+    }
 }
 
 /// Join condition using explicit column references.
@@ -114,6 +378,8 @@ pub enum KeyRef {
 pub enum ProjectExpr {
     Column(ColumnRef),
     RowId(RowIdRef),
+    /// Computed value; the output column type is inferred from the expression.
+    Expr(ScalarExpr),
 }
 
 /// One projected output column.
@@ -135,6 +401,9 @@ pub enum OrderDirection {
 pub struct OrderByExpr {
     pub column: ColumnRef,
     pub direction: OrderDirection,
+    /// Computed sort key. When set, `column` only labels the term.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expr: Option<ScalarExpr>,
 }
 
 /// Unified relation IR shared by query and policy compilers.
@@ -1,17 +1,19 @@
 use super::graph_nodes::sort::SortDirection;
 use super::query::{
-    ArraySubquerySpec, Condition, Conjunction, JoinSpec, RecursiveHopSpec, RecursiveSpec,
+    ArraySubquerySpec, Condition, Conjunction, ExistsSubquerySpec, JoinSpec, RecursiveHopSpec,
+    RecursiveSpec,
 };
 use super::relation_ir::{
     ColumnRef, JoinKind, OrderDirection, PredicateCmpOp, PredicateExpr, ProjectColumn, ProjectExpr,
-    RelExpr, RowIdRef, ValueRef,
+    RelExpr, RowIdRef, ScalarExpr, ValueRef,
 };
-use super::types::TableName;
+use super::types::{GeoRegion, TableName};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct QueryEnvelope<'a> {
     core: &'a RelExpr,
     order_by: Vec<(String, SortDirection)>,
+    computed_order_by: Vec<(usize, ScalarExpr)>,
     offset: usize,
     limit: Option<usize>,
 }
@@ -23,6 +25,7 @@ struct LinearJoinInfo {
     scope_order: Vec<String>,
     disjuncts: Vec<Conjunction>,
     joins: Vec<JoinSpec>,
+    exists_joins: Vec<ExistsSubquerySpec>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -31,6 +34,7 @@ struct RuntimeCorePlan {
     base_scope: String,
     disjuncts: Vec<Conjunction>,
     joins: Vec<JoinSpec>,
+    exists_joins: Vec<ExistsSubquerySpec>,
     result_element_index: Option<usize>,
     recursive: Option<RecursiveSpec>,
     seed_relation: Option<RelExpr>,
@@ -44,12 +48,20 @@ pub(crate) struct ExecutionQueryPlan {
     pub branches: Vec<String>,
     pub disjuncts: Vec<Conjunction>,
     pub joins: Vec<JoinSpec>,
+    /// Semi/anti joins that filter the (joined) rows by a correlated inner table.
+    pub exists_joins: Vec<ExistsSubquerySpec>,
     pub recursive: Option<RecursiveSpec>,
     pub seed_relation: Option<RelExpr>,
     pub result_element_index: Option<usize>,
     pub order_by: Vec<(String, SortDirection)>,
+    /// Computed sort keys, keyed by their position in `order_by`. The matching
+    /// `order_by` entry only carries a label and the direction.
+    pub computed_order_by: Vec<(usize, ScalarExpr)>,
     pub offset: usize,
     pub limit: Option<usize>,
+    /// Take the offset/limit window from the end of the ordered rows
+    /// (backward keyset pages).
+    pub window_from_end: bool,
     pub include_deleted: bool,
     pub array_subqueries: Vec<ArraySubquerySpec>,
     pub project_columns: Option<Vec<ProjectColumn>>,
@@ -78,32 +90,12 @@ fn to_scoped_runtime_column(column_ref: &ColumnRef) -> String {
 }
 
 fn predicate_single_scope(predicate: &PredicateExpr) -> Option<String> {
-    fn collect(predicate: &PredicateExpr, scopes: &mut Vec<String>) {
-        match predicate {
-            PredicateExpr::Cmp { left, .. }
-            | PredicateExpr::Contains { left, .. }
-            | PredicateExpr::In { left, .. } => {
-                if let Some(scope) = &left.scope {
-                    scopes.push(scope.clone());
-                }
-            }
-            PredicateExpr::IsNull { column } | PredicateExpr::IsNotNull { column } => {
-                if let Some(scope) = &column.scope {
-                    scopes.push(scope.clone());
-                }
-            }
-            PredicateExpr::And(exprs) | PredicateExpr::Or(exprs) => {
-                for expr in exprs {
-                    collect(expr, scopes);
-                }
-            }
-            PredicateExpr::Not(inner) => collect(inner, scopes),
-            PredicateExpr::True | PredicateExpr::False => {}
-        }
-    }
-
     let mut scopes = Vec::new();
-    collect(predicate, &mut scopes);
+    predicate.visit_column_refs(&mut |column| {
+        if let Some(scope) = &column.scope {
+            scopes.push(scope.clone());
+        }
+    });
     let first = scopes.first()?.clone();
     scopes
         .into_iter()
@@ -170,6 +162,19 @@ fn predicate_term_to_condition(predicate: &PredicateExpr) -> Option<Condition> {
         PredicateExpr::IsNotNull { column } => Some(Condition::IsNotNull {
             column: to_scoped_runtime_column(column),
         }),
+        PredicateExpr::Within { column, region } => {
+            let column = to_scoped_runtime_column(column);
+            Some(match region {
+                GeoRegion::Box(bounds) => Condition::WithinBox {
+                    column,
+                    bounds: *bounds,
+                },
+                GeoRegion::Circle(circle) => Condition::WithinRadius {
+                    column,
+                    circle: *circle,
+                },
+            })
+        }
         PredicateExpr::True => None,
         _ => None,
     }
@@ -185,6 +190,7 @@ fn and_disjuncts(lhs: Vec<Conjunction>, rhs: Vec<Conjunction>) -> Vec<Conjunctio
         for right in &rhs {
             let mut merged = left.clone();
             merged.conditions.extend(right.conditions.clone());
+            merged.comparisons.extend(right.comparisons.clone());
             out.push(merged);
         }
     }
@@ -197,12 +203,18 @@ fn relation_predicate_to_disjuncts(predicate: &PredicateExpr) -> Option<Vec<Conj
         PredicateExpr::Cmp { .. }
         | PredicateExpr::Contains { .. }
         | PredicateExpr::IsNull { .. }
-        | PredicateExpr::IsNotNull { .. } => {
+        | PredicateExpr::IsNotNull { .. }
+        | PredicateExpr::Within { .. } => {
             let condition = predicate_term_to_condition(predicate)?;
             Some(vec![Conjunction {
                 conditions: vec![condition],
+                comparisons: Vec::new(),
             }])
         }
+        PredicateExpr::Compare(comparison) => Some(vec![Conjunction {
+            conditions: Vec::new(),
+            comparisons: vec![comparison.clone()],
+        }]),
         PredicateExpr::In { left, values } => {
             if values.is_empty() {
                 let column = to_scoped_runtime_column(left);
@@ -216,6 +228,7 @@ fn relation_predicate_to_disjuncts(predicate: &PredicateExpr) -> Option<Vec<Conj
                         },
                         Condition::IsNotNull { column },
                     ],
+                    comparisons: Vec::new(),
                 }]);
             }
             let mut out = Vec::with_capacity(values.len());
@@ -229,6 +242,7 @@ fn relation_predicate_to_disjuncts(predicate: &PredicateExpr) -> Option<Vec<Conj
                         column: to_scoped_runtime_column(left),
                         value: literal,
                     }],
+                    comparisons: Vec::new(),
                 });
             }
             Some(out)
@@ -266,6 +280,7 @@ fn extract_linear_join_info(expr: &RelExpr) -> Option<LinearJoinInfo> {
             scope_order: vec![table.as_str().to_string()],
             disjuncts: dnf_true(),
             joins: Vec::new(),
+            exists_joins: Vec::new(),
         }),
         RelExpr::Filter { input, predicate } => {
             let mut inner = extract_linear_join_info(input)?;
@@ -284,6 +299,38 @@ fn extract_linear_join_info(expr: &RelExpr) -> Option<LinearJoinInfo> {
             }
             Some(inner)
         }
+        RelExpr::Join {
+            left,
+            right,
+            on,
+            join_kind,
+        } if join_kind.is_existential() => {
+            let mut left_info = extract_linear_join_info(left)?;
+            let (inner_table, filters) = extract_exists_inner(right)?;
+            let [condition] = on.as_slice() else {
+                return None;
+            };
+            let outer_scope = condition
+                .left
+                .scope
+                .clone()
+                .unwrap_or_else(|| left_info.scope_order[0].clone());
+            let inner_scope = condition
+                .right
+                .scope
+                .clone()
+                .unwrap_or_else(|| inner_table.as_str().to_string());
+
+            left_info.exists_joins.push(ExistsSubquerySpec {
+                table: inner_table,
+                alias: (inner_scope != inner_table.as_str()).then_some(inner_scope.clone()),
+                inner_column: format!("{inner_scope}.{}", condition.right.column),
+                outer_column: format!("{outer_scope}.{}", condition.left.column),
+                filters,
+                negated: matches!(join_kind, JoinKind::Anti),
+            });
+            Some(left_info)
+        }
         RelExpr::Join {
             left,
             right,
@@ -331,6 +378,26 @@ fn extract_linear_join_info(expr: &RelExpr) -> Option<LinearJoinInfo> {
     }
 }
 
+/// Flatten the right side of a semi/anti join into its table and the
+/// conjunctive filters an inner row must satisfy.
+fn extract_exists_inner(expr: &RelExpr) -> Option<(TableName, Vec<Condition>)> {
+    match expr {
+        RelExpr::TableScan { table } => Some((*table, Vec::new())),
+        RelExpr::Filter { input, predicate } => {
+            let (table, mut filters) = extract_exists_inner(input)?;
+            let [conjunction]: [Conjunction; 1] = relation_predicate_to_disjuncts(predicate)?
+                .try_into()
+                .ok()?;
+            if !conjunction.comparisons.is_empty() {
+                return None;
+            }
+            filters.extend(conjunction.conditions);
+            Some((table, filters))
+        }
+        _ => None,
+    }
+}
+
 fn extract_step_scan(
     expr: &RelExpr,
     predicates: &mut Vec<PredicateExpr>,
@@ -424,6 +491,7 @@ fn normalize_project_columns(columns: &[ProjectColumn]) -> Vec<ProjectColumn> {
                     column: to_runtime_column(&column_ref.column),
                 }),
                 ProjectExpr::RowId(row_id_ref) => ProjectExpr::RowId(*row_id_ref),
+                ProjectExpr::Expr(expr) => ProjectExpr::Expr(expr.clone()),
             },
         })
         .collect()
@@ -487,10 +555,17 @@ fn bind_unscoped_project_filter_scope(predicate: &PredicateExpr, scope: &str) ->
         PredicateExpr::IsNotNull { column } => PredicateExpr::IsNotNull {
             column: bind_column_ref(column, scope),
         },
+        PredicateExpr::Within { column, region } => PredicateExpr::Within {
+            column: bind_column_ref(column, scope),
+            region: *region,
+        },
         PredicateExpr::In { left, values } => PredicateExpr::In {
             left: bind_column_ref(left, scope),
             values: values.clone(),
         },
+        PredicateExpr::Compare(comparison) => PredicateExpr::Compare(
+            comparison.map_column_refs(&mut |column| bind_column_ref(column, scope)),
+        ),
         PredicateExpr::And(exprs) => PredicateExpr::And(
             exprs
                 .iter()
@@ -541,6 +616,7 @@ fn parse_projected_result_element_plan(
             base_scope: linear.scope_order[0].clone(),
             disjuncts: linear.disjuncts,
             joins: linear.joins.clone(),
+            exists_joins: linear.exists_joins,
             result_element_index: Some(index),
             recursive: None,
             seed_relation: None,
@@ -551,7 +627,8 @@ fn parse_projected_result_element_plan(
 }
 
 fn parse_gather_core(seed: &RelExpr, step: &RelExpr, max_depth: usize) -> Option<RuntimeCorePlan> {
-    let simple_seed = extract_linear_join_info(seed).filter(|info| info.joins.is_empty());
+    let simple_seed = extract_linear_join_info(seed)
+        .filter(|info| info.joins.is_empty() && info.exists_joins.is_empty());
 
     let (step_core, step_projection) = match step {
         RelExpr::Project { input, columns } => (input.as_ref(), Some(columns.as_slice())),
@@ -595,6 +672,7 @@ fn parse_gather_core(seed: &RelExpr, step: &RelExpr, max_depth: usize) -> Option
                 base_scope: seed_info.scope_order[0].clone(),
                 disjuncts: seed_info.disjuncts.clone(),
                 joins: Vec::new(),
+                exists_joins: Vec::new(),
                 result_element_index: None,
                 recursive: Some(recursive),
                 seed_relation: None,
@@ -715,6 +793,7 @@ fn parse_gather_core(seed: &RelExpr, step: &RelExpr, max_depth: usize) -> Option
         base_scope,
         disjuncts,
         joins: Vec::new(),
+        exists_joins: Vec::new(),
         result_element_index: None,
         recursive: Some(recursive),
         seed_relation,
@@ -828,6 +907,7 @@ fn parse_runtime_core_plan(core: &RelExpr) -> Option<RuntimeCorePlan> {
                 base_scope: linear.scope_order[0].clone(),
                 disjuncts: linear.disjuncts,
                 joins: linear.joins,
+                exists_joins: linear.exists_joins,
                 result_element_index: None,
                 recursive: None,
                 seed_relation: None,
@@ -850,6 +930,7 @@ fn parse_runtime_core_plan(core: &RelExpr) -> Option<RuntimeCorePlan> {
                 base_scope: linear.scope_order[0].clone(),
                 disjuncts: linear.disjuncts,
                 joins: linear.joins.clone(),
+                exists_joins: linear.exists_joins,
                 result_element_index,
                 recursive: None,
                 seed_relation: None,
@@ -867,6 +948,7 @@ fn parse_runtime_core_plan(core: &RelExpr) -> Option<RuntimeCorePlan> {
                 base_scope: linear.scope_order[0].clone(),
                 disjuncts: linear.disjuncts,
                 joins: linear.joins,
+                exists_joins: linear.exists_joins,
                 result_element_index: None,
                 recursive: None,
                 seed_relation: None,
@@ -879,6 +961,7 @@ fn parse_runtime_core_plan(core: &RelExpr) -> Option<RuntimeCorePlan> {
 fn unwrap_query_envelope(expr: &RelExpr) -> QueryEnvelope<'_> {
     let mut current = expr;
     let mut order_by = Vec::new();
+    let mut computed_order_by = Vec::new();
     let mut offset = 0;
     let mut limit = None;
 
@@ -898,6 +981,11 @@ fn unwrap_query_envelope(expr: &RelExpr) -> QueryEnvelope<'_> {
                             )
                         })
                         .collect();
+                    computed_order_by = terms
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, term)| Some((index, term.expr.clone()?)))
+                        .collect();
                 }
                 current = input;
             }
@@ -913,6 +1001,7 @@ fn unwrap_query_envelope(expr: &RelExpr) -> QueryEnvelope<'_> {
                 return QueryEnvelope {
                     core: current,
                     order_by,
+                    computed_order_by,
                     offset,
                     limit,
                 };
@@ -944,12 +1033,15 @@ pub(crate) fn lower_relation_to_execution_plan(
         branches: branches.to_vec(),
         disjuncts: core_plan.disjuncts,
         joins: core_plan.joins,
+        exists_joins: core_plan.exists_joins,
         recursive: core_plan.recursive,
         seed_relation: core_plan.seed_relation,
         result_element_index: core_plan.result_element_index,
         order_by: envelope.order_by,
+        computed_order_by: envelope.computed_order_by,
         offset: envelope.offset,
         limit: envelope.limit,
+        window_from_end: false,
         include_deleted,
         array_subqueries,
         project_columns,
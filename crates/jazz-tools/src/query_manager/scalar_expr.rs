@@ -0,0 +1,1325 @@
+//! Binding and evaluation of relation-IR scalar expressions.
+//!
+//! A [`ScalarExpr`] is bound once against the [`TupleDescriptor`] of the graph
+//! node that evaluates it. Binding resolves column references to tuple
+//! positions and infers the result type, so type errors surface when a query
+//! graph is compiled instead of once per row.
+//!
+//! Evaluation follows SQL `NULL` semantics: any `NULL` operand produces `NULL`
+//! (or an unknown condition), except inside `Coalesce` and `Case`. Integer
+//! overflow and division by zero also produce `NULL` rather than failing the
+//! whole query.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::query_manager::encoding::decode_column;
+use crate::query_manager::json_path::JsonPath;
+use crate::query_manager::relation_ir::{
+    ArithmeticOp, ColumnRef, DateTruncUnit, PredicateCmpOp, PredicateExpr, ScalarComparison,
+    ScalarExpr, ValueRef,
+};
+use crate::query_manager::types::{
+    ColumnType, DECIMAL_MAX_PRECISION, Decimal, Tuple, TupleDescriptor, Value,
+};
+
+const MICROS_PER_SECOND: u64 = 1_000_000;
+const MICROS_PER_MINUTE: u64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: u64 = 60 * MICROS_PER_MINUTE;
+const MICROS_PER_DAY: u64 = 24 * MICROS_PER_HOUR;
+
+/// Reasons a scalar expression cannot be bound to a tuple shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScalarExprError {
+    UnknownColumn(String),
+    TypeMismatch {
+        context: &'static str,
+        left: ColumnType,
+        right: ColumnType,
+    },
+    UnsupportedType {
+        context: &'static str,
+        found: ColumnType,
+    },
+    UnsupportedOperand(&'static str),
+    EmptyArguments(&'static str),
+}
+
+impl fmt::Display for ScalarExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScalarExprError::UnknownColumn(column) => {
+                write!(f, "unknown column '{column}' in expression")
+            }
+            ScalarExprError::TypeMismatch {
+                context,
+                left,
+                right,
+            } => write!(f, "{context} cannot combine {left:?} with {right:?}"),
+            ScalarExprError::UnsupportedType { context, found } => {
+                write!(f, "{context} does not accept {found:?} operands")
+            }
+            ScalarExprError::UnsupportedOperand(context) => {
+                write!(
+                    f,
+                    "{context} only accepts literal values on the right-hand side"
+                )
+            }
+            ScalarExprError::EmptyArguments(context) => {
+                write!(f, "{context} requires at least one argument")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScalarExprError {}
+
+#[derive(Debug, Clone)]
+enum BoundNode {
+    Column {
+        global_index: usize,
+        element_index: usize,
+        local_index: usize,
+    },
+    RowId {
+        element_index: usize,
+    },
+    Literal(Value),
+    Arithmetic {
+        op: ArithmeticOp,
+        left: Box<BoundScalarExpr>,
+        right: Box<BoundScalarExpr>,
+    },
+    Concat(Vec<BoundScalarExpr>),
+    Lower(Box<BoundScalarExpr>),
+    Upper(Box<BoundScalarExpr>),
+    Coalesce(Vec<BoundScalarExpr>),
+    Case {
+        branches: Vec<(BoundCondition, BoundScalarExpr)>,
+        otherwise: Option<Box<BoundScalarExpr>>,
+    },
+    DateTrunc {
+        unit: DateTruncUnit,
+        input: Box<BoundScalarExpr>,
+    },
+    JsonPath {
+        input: Box<BoundScalarExpr>,
+        path: JsonPath,
+    },
+    VectorDistance {
+        left: Box<BoundScalarExpr>,
+        right: Box<BoundScalarExpr>,
+    },
+}
+
+/// A [`ScalarExpr`] resolved against one tuple shape.
+#[derive(Debug, Clone)]
+pub struct BoundScalarExpr {
+    node: BoundNode,
+    /// `None` for an untyped `NULL` literal (or an expression made of them) and
+    /// for JSON path extraction, whose scalar type varies per row.
+    output_type: Option<ColumnType>,
+}
+
+impl BoundScalarExpr {
+    /// Resolve and type-check `expr` against `descriptor`.
+    pub fn bind(expr: &ScalarExpr, descriptor: &TupleDescriptor) -> Result<Self, ScalarExprError> {
+        match expr {
+            ScalarExpr::Column(column) => bind_column(column, descriptor),
+            ScalarExpr::Literal(value) => Ok(Self {
+                output_type: value.column_type(),
+                node: BoundNode::Literal(value.clone()),
+            }),
+            ScalarExpr::Arithmetic { op, left, right } => {
+                let left = Self::bind(left, descriptor)?;
+                let right = Self::bind(right, descriptor)?;
+                let output_type =
+                    arithmetic_type(*op, left.output_type.clone(), right.output_type.clone())?;
+                Ok(Self {
+                    node: BoundNode::Arithmetic {
+                        op: *op,
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    },
+                    output_type,
+                })
+            }
+            ScalarExpr::Concat(args) => {
+                let args = bind_arguments(args, descriptor, "concat")?;
+                for arg in &args {
+                    if let Some(found) = &arg.output_type
+                        && !is_text_renderable(found)
+                    {
+                        return Err(ScalarExprError::UnsupportedType {
+                            context: "concat",
+                            found: found.clone(),
+                        });
+                    }
+                }
+                Ok(Self {
+                    node: BoundNode::Concat(args),
+                    output_type: Some(ColumnType::Text),
+                })
+            }
+            ScalarExpr::Lower(input) => {
+                let input = bind_text_input(input, descriptor, "lower")?;
+                Ok(Self {
+                    node: BoundNode::Lower(Box::new(input)),
+                    output_type: Some(ColumnType::Text),
+                })
+            }
+            ScalarExpr::Upper(input) => {
+                let input = bind_text_input(input, descriptor, "upper")?;
+                Ok(Self {
+                    node: BoundNode::Upper(Box::new(input)),
+                    output_type: Some(ColumnType::Text),
+                })
+            }
+            ScalarExpr::Coalesce(args) => {
+                let args = bind_arguments(args, descriptor, "coalesce")?;
+                let mut output_type = None;
+                for arg in &args {
+                    output_type = unify_types("coalesce", output_type, arg.output_type.clone())?;
+                }
+                Ok(Self {
+                    node: BoundNode::Coalesce(args),
+                    output_type,
+                })
+            }
+            ScalarExpr::Case {
+                branches,
+                otherwise,
+            } => {
+                if branches.is_empty() {
+                    return Err(ScalarExprError::EmptyArguments("case"));
+                }
+                let mut output_type = None;
+                let mut bound_branches = Vec::with_capacity(branches.len());
+                for branch in branches {
+                    let when = BoundCondition::bind(&branch.when, descriptor)?;
+                    let then = Self::bind(&branch.then, descriptor)?;
+                    output_type = unify_types("case", output_type, then.output_type.clone())?;
+                    bound_branches.push((when, then));
+                }
+                let otherwise = match otherwise {
+                    Some(otherwise) => {
+                        let otherwise = Self::bind(otherwise, descriptor)?;
+                        output_type =
+                            unify_types("case", output_type, otherwise.output_type.clone())?;
+                        Some(Box::new(otherwise))
+                    }
+                    None => None,
+                };
+                Ok(Self {
+                    node: BoundNode::Case {
+                        branches: bound_branches,
+                        otherwise,
+                    },
+                    output_type,
+                })
+            }
+            ScalarExpr::DateTrunc { unit, input } => {
+                let input = Self::bind(input, descriptor)?;
+                if let Some(found) = &input.output_type
+                    && *found != ColumnType::Timestamp
+                {
+                    return Err(ScalarExprError::UnsupportedType {
+                        context: "date_trunc",
+                        found: found.clone(),
+                    });
+                }
+                Ok(Self {
+                    node: BoundNode::DateTrunc {
+                        unit: *unit,
+                        input: Box::new(input),
+                    },
+                    output_type: Some(ColumnType::Timestamp),
+                })
+            }
+            ScalarExpr::JsonPath { input, steps } => {
+                // JSON columns bind as text; the path's scalar type is only
+                // known per row, so the result stays untyped.
+                let input = bind_text_input(input, descriptor, "json path")?;
+                Ok(Self {
+                    node: BoundNode::JsonPath {
+                        input: Box::new(input),
+                        path: JsonPath::new("", steps.clone()),
+                    },
+                    output_type: None,
+                })
+            }
+            ScalarExpr::VectorDistance { left, right } => {
+                let left = Self::bind(left, descriptor)?;
+                let right = Self::bind(right, descriptor)?;
+                for found in [&left.output_type, &right.output_type]
+                    .into_iter()
+                    .flatten()
+                {
+                    if !matches!(found, ColumnType::Vector { .. }) {
+                        return Err(ScalarExprError::UnsupportedType {
+                            context: "vector distance",
+                            found: found.clone(),
+                        });
+                    }
+                }
+                if let (Some(left_type), Some(right_type)) = (&left.output_type, &right.output_type)
+                    && left_type != right_type
+                {
+                    return Err(ScalarExprError::TypeMismatch {
+                        context: "vector distance",
+                        left: left_type.clone(),
+                        right: right_type.clone(),
+                    });
+                }
+                Ok(Self {
+                    node: BoundNode::VectorDistance {
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    },
+                    output_type: Some(ColumnType::Double),
+                })
+            }
+        }
+    }
+
+    /// Inferred result type, or `None` when the expression is an untyped `NULL`
+    /// or a JSON path.
+    pub fn output_type(&self) -> Option<&ColumnType> {
+        self.output_type.as_ref()
+    }
+
+    /// Global column indices read by this expression.
+    pub fn required_columns(&self) -> HashSet<usize> {
+        let mut columns = HashSet::new();
+        self.collect_columns(&mut columns);
+        columns
+    }
+
+    fn collect_columns(&self, columns: &mut HashSet<usize>) {
+        match &self.node {
+            BoundNode::Column { global_index, .. } => {
+                columns.insert(*global_index);
+            }
+            BoundNode::RowId { .. } | BoundNode::Literal(_) => {}
+            BoundNode::Arithmetic { left, right, .. }
+            | BoundNode::VectorDistance { left, right } => {
+                left.collect_columns(columns);
+                right.collect_columns(columns);
+            }
+            BoundNode::Concat(args) | BoundNode::Coalesce(args) => {
+                for arg in args {
+                    arg.collect_columns(columns);
+                }
+            }
+            BoundNode::Lower(input) | BoundNode::Upper(input) => input.collect_columns(columns),
+            BoundNode::Case {
+                branches,
+                otherwise,
+            } => {
+                for (when, then) in branches {
+                    when.collect_columns(columns);
+                    then.collect_columns(columns);
+                }
+                if let Some(otherwise) = otherwise {
+                    otherwise.collect_columns(columns);
+                }
+            }
+            BoundNode::DateTrunc { input, .. } | BoundNode::JsonPath { input, .. } => {
+                input.collect_columns(columns)
+            }
+        }
+    }
+
+    /// Evaluate against a tuple shaped like the descriptor used for binding.
+    pub fn evaluate(&self, tuple: &Tuple, descriptor: &TupleDescriptor) -> Value {
+        match &self.node {
+            BoundNode::Column {
+                element_index,
+                local_index,
+                ..
+            } => {
+                let Some(element) = tuple.get(*element_index) else {
+                    return Value::Null;
+                };
+                let (Some(content), Some(element_descriptor)) =
+                    (element.content(), descriptor.element(*element_index))
+                else {
+                    return Value::Null;
+                };
+                decode_column(&element_descriptor.descriptor, content, *local_index)
+                    .unwrap_or(Value::Null)
+            }
+            BoundNode::RowId { element_index } => tuple
+                .get(*element_index)
+                .map(|element| Value::Uuid(element.id()))
+                .unwrap_or(Value::Null),
+            BoundNode::Literal(value) => value.clone(),
+            BoundNode::Arithmetic { op, left, right } => {
+                let left = left.evaluate(tuple, descriptor);
+                let right = right.evaluate(tuple, descriptor);
+                if left.is_null() || right.is_null() {
+                    return Value::Null;
+                }
+                self.output_type
+                    .as_ref()
+                    .and_then(|output_type| evaluate_arithmetic(*op, left, right, output_type))
+                    .unwrap_or(Value::Null)
+            }
+            BoundNode::Concat(args) => {
+                let mut text = String::new();
+                for arg in args {
+                    let Some(part) = render_text(&arg.evaluate(tuple, descriptor)) else {
+                        return Value::Null;
+                    };
+                    text.push_str(&part);
+                }
+                Value::Text(text)
+            }
+            BoundNode::Lower(input) => match input.evaluate(tuple, descriptor) {
+                Value::Text(text) => Value::Text(text.to_lowercase()),
+                _ => Value::Null,
+            },
+            BoundNode::Upper(input) => match input.evaluate(tuple, descriptor) {
+                Value::Text(text) => Value::Text(text.to_uppercase()),
+                _ => Value::Null,
+            },
+            BoundNode::Coalesce(args) => args
+                .iter()
+                .map(|arg| arg.evaluate(tuple, descriptor))
+                .find(|value| !value.is_null())
+                .map(|value| self.coerce_output(value))
+                .unwrap_or(Value::Null),
+            BoundNode::Case {
+                branches,
+                otherwise,
+            } => {
+                for (when, then) in branches {
+                    if when.evaluate(tuple, descriptor) == Some(true) {
+                        return self.coerce_output(then.evaluate(tuple, descriptor));
+                    }
+                }
+                otherwise
+                    .as_ref()
+                    .map(|otherwise| self.coerce_output(otherwise.evaluate(tuple, descriptor)))
+                    .unwrap_or(Value::Null)
+            }
+            BoundNode::DateTrunc { unit, input } => match input.evaluate(tuple, descriptor) {
+                Value::Timestamp(micros) => truncate_timestamp(micros, *unit)
+                    .map(Value::Timestamp)
+                    .unwrap_or(Value::Null),
+                _ => Value::Null,
+            },
+            BoundNode::JsonPath { input, path } => path.extract(&input.evaluate(tuple, descriptor)),
+            BoundNode::VectorDistance { left, right } => match (
+                left.evaluate(tuple, descriptor),
+                right.evaluate(tuple, descriptor),
+            ) {
+                (Value::Vector(left), Value::Vector(right)) if left.dims() == right.dims() => {
+                    Value::Double(left.cosine_distance(&right))
+                }
+                _ => Value::Null,
+            },
+        }
+    }
+
+    fn coerce_output(&self, value: Value) -> Value {
+        match &self.output_type {
+            Some(output_type) => coerce_value(value, output_type),
+            None => value,
+        }
+    }
+}
+
+/// A boolean condition resolved against one tuple shape.
+///
+/// Evaluation is three-valued: `None` means unknown (a `NULL` was compared).
+#[derive(Debug, Clone)]
+pub enum BoundCondition {
+    Compare {
+        left: Box<BoundScalarExpr>,
+        op: PredicateCmpOp,
+        right: Box<BoundScalarExpr>,
+        operand_type: Option<ColumnType>,
+    },
+    Contains {
+        left: BoundScalarExpr,
+        value: Value,
+    },
+    IsNull(BoundScalarExpr),
+    IsNotNull(BoundScalarExpr),
+    And(Vec<BoundCondition>),
+    Or(Vec<BoundCondition>),
+    Not(Box<BoundCondition>),
+    Constant(bool),
+}
+
+impl BoundCondition {
+    /// Resolve a relation predicate. Only literal right-hand values are accepted;
+    /// session and correlation references must be bound before this point.
+    pub fn bind(
+        predicate: &PredicateExpr,
+        descriptor: &TupleDescriptor,
+    ) -> Result<Self, ScalarExprError> {
+        match predicate {
+            PredicateExpr::Cmp { left, op, right } => {
+                Self::compare(bind_column(left, descriptor)?, *op, bind_value_ref(right)?)
+            }
+            PredicateExpr::Contains { left, right } => {
+                let ValueRef::Literal(value) = right else {
+                    return Err(ScalarExprError::UnsupportedOperand("contains"));
+                };
+                Ok(Self::Contains {
+                    left: bind_column(left, descriptor)?,
+                    value: value.clone(),
+                })
+            }
+            PredicateExpr::IsNull { column } => Ok(Self::IsNull(bind_column(column, descriptor)?)),
+            PredicateExpr::IsNotNull { column } => {
+                Ok(Self::IsNotNull(bind_column(column, descriptor)?))
+            }
+            PredicateExpr::Within { .. } => Err(ScalarExprError::UnsupportedOperand("within")),
+            PredicateExpr::In { left, values } => {
+                let mut options = Vec::with_capacity(values.len());
+                for value in values {
+                    options.push(Self::compare(
+                        bind_column(left, descriptor)?,
+                        PredicateCmpOp::Eq,
+                        bind_value_ref(value)?,
+                    )?);
+                }
+                Ok(Self::Or(options))
+            }
+            PredicateExpr::Compare(comparison) => Self::bind_comparison(comparison, descriptor),
+            PredicateExpr::And(exprs) => Ok(Self::And(
+                exprs
+                    .iter()
+                    .map(|expr| Self::bind(expr, descriptor))
+                    .collect::<Result<_, _>>()?,
+            )),
+            PredicateExpr::Or(exprs) => Ok(Self::Or(
+                exprs
+                    .iter()
+                    .map(|expr| Self::bind(expr, descriptor))
+                    .collect::<Result<_, _>>()?,
+            )),
+            PredicateExpr::Not(inner) => Ok(Self::Not(Box::new(Self::bind(inner, descriptor)?))),
+            PredicateExpr::True => Ok(Self::Constant(true)),
+            PredicateExpr::False => Ok(Self::Constant(false)),
+        }
+    }
+
+    /// Resolve a comparison between two computed expressions.
+    pub fn bind_comparison(
+        comparison: &ScalarComparison,
+        descriptor: &TupleDescriptor,
+    ) -> Result<Self, ScalarExprError> {
+        Self::compare(
+            BoundScalarExpr::bind(&comparison.left, descriptor)?,
+            comparison.op,
+            BoundScalarExpr::bind(&comparison.right, descriptor)?,
+        )
+    }
+
+    fn compare(
+        left: BoundScalarExpr,
+        op: PredicateCmpOp,
+        right: BoundScalarExpr,
+    ) -> Result<Self, ScalarExprError> {
+        let operand_type = unify_types(
+            "comparison",
+            left.output_type.clone(),
+            right.output_type.clone(),
+        )?;
+        Ok(Self::Compare {
+            left: Box::new(left),
+            op,
+            right: Box::new(right),
+            operand_type,
+        })
+    }
+
+    /// Global column indices read by this condition.
+    pub fn required_columns(&self) -> HashSet<usize> {
+        let mut columns = HashSet::new();
+        self.collect_columns(&mut columns);
+        columns
+    }
+
+    fn collect_columns(&self, columns: &mut HashSet<usize>) {
+        match self {
+            BoundCondition::Compare { left, right, .. } => {
+                left.collect_columns(columns);
+                right.collect_columns(columns);
+            }
+            BoundCondition::Contains { left, .. }
+            | BoundCondition::IsNull(left)
+            | BoundCondition::IsNotNull(left) => left.collect_columns(columns),
+            BoundCondition::And(conditions) | BoundCondition::Or(conditions) => {
+                for condition in conditions {
+                    condition.collect_columns(columns);
+                }
+            }
+            BoundCondition::Not(inner) => inner.collect_columns(columns),
+            BoundCondition::Constant(_) => {}
+        }
+    }
+
+    /// Evaluate with SQL three-valued logic.
+    pub fn evaluate(&self, tuple: &Tuple, descriptor: &TupleDescriptor) -> Option<bool> {
+        match self {
+            BoundCondition::Compare {
+                left,
+                op,
+                right,
+                operand_type,
+            } => {
+                let left = left.evaluate(tuple, descriptor);
+                let right = right.evaluate(tuple, descriptor);
+                if left.is_null() || right.is_null() {
+                    return None;
+                }
+                let (left, right) = match operand_type {
+                    Some(operand_type) => (
+                        coerce_value(left, operand_type),
+                        coerce_value(right, operand_type),
+                    ),
+                    None => (left, right),
+                };
+                let ordering = compare_values(&left, &right);
+                Some(match op {
+                    PredicateCmpOp::Eq => ordering.map_or(left == right, Ordering::is_eq),
+                    PredicateCmpOp::Ne => ordering.map_or(left != right, Ordering::is_ne),
+                    PredicateCmpOp::Lt => ordering?.is_lt(),
+                    PredicateCmpOp::Le => ordering?.is_le(),
+                    PredicateCmpOp::Gt => ordering?.is_gt(),
+                    PredicateCmpOp::Ge => ordering?.is_ge(),
+                })
+            }
+            BoundCondition::Contains { left, value } => match left.evaluate(tuple, descriptor) {
+                Value::Null => None,
+                Value::Array(elements) => Some(elements.iter().any(|element| element == value)),
+                Value::Text(text) => Some(match value {
+                    Value::Text(needle) => text.contains(needle.as_str()),
+                    _ => false,
+                }),
+                _ => Some(false),
+            },
+            BoundCondition::IsNull(input) => Some(input.evaluate(tuple, descriptor).is_null()),
+            BoundCondition::IsNotNull(input) => Some(!input.evaluate(tuple, descriptor).is_null()),
+            BoundCondition::And(conditions) => {
+                let mut result = Some(true);
+                for condition in conditions {
+                    match condition.evaluate(tuple, descriptor) {
+                        Some(false) => return Some(false),
+                        None => result = None,
+                        Some(true) => {}
+                    }
+                }
+                result
+            }
+            BoundCondition::Or(conditions) => {
+                let mut result = Some(false);
+                for condition in conditions {
+                    match condition.evaluate(tuple, descriptor) {
+                        Some(true) => return Some(true),
+                        None => result = None,
+                        Some(false) => {}
+                    }
+                }
+                result
+            }
+            BoundCondition::Not(inner) => inner.evaluate(tuple, descriptor).map(|value| !value),
+            BoundCondition::Constant(value) => Some(*value),
+        }
+    }
+}
+
+fn bind_column(
+    column: &ColumnRef,
+    descriptor: &TupleDescriptor,
+) -> Result<BoundScalarExpr, ScalarExprError> {
+    let global_index = match column.scope.as_deref() {
+        Some(scope) => descriptor.qualified_column_index(scope, &column.column),
+        None => descriptor.column_index(&column.column),
+    };
+
+    if let Some(global_index) = global_index
+        && let Some((element_index, local_index)) = descriptor.resolve_column(global_index)
+        && let Some(element) = descriptor.element(element_index)
+    {
+        let column_type = &element.descriptor.columns[local_index].column_type;
+        return Ok(BoundScalarExpr {
+            node: BoundNode::Column {
+                global_index,
+                element_index,
+                local_index,
+            },
+            output_type: Some(expression_type(column_type)),
+        });
+    }
+
+    // Tables without a declared `id` column expose the row identity instead.
+    if column.column == "_id" || column.column == "id" {
+        let element_index = match column.scope.as_deref() {
+            Some(scope) => (0..descriptor.element_count()).find(|&index| {
+                descriptor
+                    .element(index)
+                    .is_some_and(|element| element.table == scope)
+            }),
+            None => (descriptor.element_count() == 1).then_some(0),
+        };
+        if let Some(element_index) = element_index {
+            return Ok(BoundScalarExpr {
+                node: BoundNode::RowId { element_index },
+                output_type: Some(ColumnType::Uuid),
+            });
+        }
+    }
+
+    Err(ScalarExprError::UnknownColumn(match &column.scope {
+        Some(scope) => format!("{scope}.{}", column.column),
+        None => column.column.clone(),
+    }))
+}
+
+fn bind_value_ref(value: &ValueRef) -> Result<BoundScalarExpr, ScalarExprError> {
+    let ValueRef::Literal(value) = value else {
+        return Err(ScalarExprError::UnsupportedOperand("comparison"));
+    };
+    Ok(BoundScalarExpr {
+        output_type: value.column_type(),
+        node: BoundNode::Literal(value.clone()),
+    })
+}
+
+fn bind_arguments(
+    args: &[ScalarExpr],
+    descriptor: &TupleDescriptor,
+    context: &'static str,
+) -> Result<Vec<BoundScalarExpr>, ScalarExprError> {
+    if args.is_empty() {
+        return Err(ScalarExprError::EmptyArguments(context));
+    }
+    args.iter()
+        .map(|arg| BoundScalarExpr::bind(arg, descriptor))
+        .collect()
+}
+
+fn bind_text_input(
+    input: &ScalarExpr,
+    descriptor: &TupleDescriptor,
+    context: &'static str,
+) -> Result<BoundScalarExpr, ScalarExprError> {
+    let input = BoundScalarExpr::bind(input, descriptor)?;
+    if let Some(found) = &input.output_type
+        && *found != ColumnType::Text
+    {
+        return Err(ScalarExprError::UnsupportedType {
+            context,
+            found: found.clone(),
+        });
+    }
+    Ok(input)
+}
+
+/// Enum and JSON columns decode to text values, so expressions treat them as text.
+fn expression_type(column_type: &ColumnType) -> ColumnType {
+    match column_type {
+        ColumnType::Enum { .. } | ColumnType::Json { .. } => ColumnType::Text,
+        other => other.clone(),
+    }
+}
+
+fn is_numeric(column_type: &ColumnType) -> bool {
+    matches!(
+        column_type,
+        ColumnType::Integer | ColumnType::BigInt | ColumnType::Double | ColumnType::Decimal { .. }
+    )
+}
+
+fn is_integral(column_type: &ColumnType) -> bool {
+    matches!(column_type, ColumnType::Integer | ColumnType::BigInt)
+}
+
+fn is_text_renderable(column_type: &ColumnType) -> bool {
+    is_numeric(column_type)
+        || matches!(
+            column_type,
+            ColumnType::Text
+                | ColumnType::Boolean
+                | ColumnType::Uuid
+                | ColumnType::Timestamp
+                | ColumnType::Date
+                | ColumnType::Time
+                | ColumnType::Interval
+        )
+}
+
+fn decimal_scale(column_type: &ColumnType) -> u8 {
+    match column_type {
+        ColumnType::Decimal { scale, .. } => *scale,
+        _ => 0,
+    }
+}
+
+/// Doubles absorb everything; otherwise decimals keep the larger scale so
+/// mixing them with integers stays exact.
+fn wider_numeric(left: &ColumnType, right: &ColumnType) -> ColumnType {
+    if *left == ColumnType::Double || *right == ColumnType::Double {
+        ColumnType::Double
+    } else if matches!(left, ColumnType::Decimal { .. })
+        || matches!(right, ColumnType::Decimal { .. })
+    {
+        ColumnType::Decimal {
+            precision: DECIMAL_MAX_PRECISION,
+            scale: decimal_scale(left).max(decimal_scale(right)),
+        }
+    } else if *left == ColumnType::BigInt || *right == ColumnType::BigInt {
+        ColumnType::BigInt
+    } else {
+        ColumnType::Integer
+    }
+}
+
+fn unify_types(
+    context: &'static str,
+    left: Option<ColumnType>,
+    right: Option<ColumnType>,
+) -> Result<Option<ColumnType>, ScalarExprError> {
+    match (left, right) {
+        (None, other) | (other, None) => Ok(other),
+        (Some(left), Some(right)) if left == right => Ok(Some(left)),
+        (Some(left), Some(right)) if is_numeric(&left) && is_numeric(&right) => {
+            Ok(Some(wider_numeric(&left, &right)))
+        }
+        (Some(left), Some(right)) => Err(ScalarExprError::TypeMismatch {
+            context,
+            left,
+            right,
+        }),
+    }
+}
+
+fn arithmetic_type(
+    op: ArithmeticOp,
+    left: Option<ColumnType>,
+    right: Option<ColumnType>,
+) -> Result<Option<ColumnType>, ScalarExprError> {
+    let shifts_timestamp = matches!(op, ArithmeticOp::Add | ArithmeticOp::Sub);
+    match (left, right) {
+        (None, None) => Ok(None),
+        (Some(found), None) | (None, Some(found)) => {
+            if is_numeric(&found) || (shifts_timestamp && found == ColumnType::Timestamp) {
+                Ok(Some(found))
+            } else {
+                Err(ScalarExprError::UnsupportedType {
+                    context: "arithmetic",
+                    found,
+                })
+            }
+        }
+        (Some(ColumnType::Timestamp), Some(ColumnType::Timestamp)) if op == ArithmeticOp::Sub => {
+            Ok(Some(ColumnType::BigInt))
+        }
+        (Some(ColumnType::Timestamp), Some(delta)) if shifts_timestamp && is_integral(&delta) => {
+            Ok(Some(ColumnType::Timestamp))
+        }
+        (Some(delta), Some(ColumnType::Timestamp))
+            if op == ArithmeticOp::Add && is_integral(&delta) =>
+        {
+            Ok(Some(ColumnType::Timestamp))
+        }
+        (Some(left), Some(right)) if is_numeric(&left) && is_numeric(&right) => {
+            Ok(Some(match wider_numeric(&left, &right) {
+                // Exact products carry the digits of both factors.
+                ColumnType::Decimal { precision, .. } if op == ArithmeticOp::Mul => {
+                    ColumnType::Decimal {
+                        precision,
+                        scale: (decimal_scale(&left) + decimal_scale(&right))
+                            .min(DECIMAL_MAX_PRECISION),
+                    }
+                }
+                wider => wider,
+            }))
+        }
+        (Some(left), Some(right)) => Err(ScalarExprError::TypeMismatch {
+            context: "arithmetic",
+            left,
+            right,
+        }),
+    }
+}
+
+fn as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(value) => Some(i64::from(*value)),
+        Value::BigInt(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(value) => Some(f64::from(*value)),
+        Value::BigInt(value) => Some(*value as f64),
+        Value::Double(value) => Some(*value),
+        Value::Decimal(value) => Some(value.to_f64()),
+        _ => None,
+    }
+}
+
+fn as_decimal(value: &Value) -> Option<Decimal> {
+    match value {
+        Value::Integer(value) => Some(Decimal::from(*value)),
+        Value::BigInt(value) => Some(Decimal::from(*value)),
+        Value::Decimal(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn evaluate_arithmetic(
+    op: ArithmeticOp,
+    left: Value,
+    right: Value,
+    output_type: &ColumnType,
+) -> Option<Value> {
+    match output_type {
+        ColumnType::Integer => {
+            let (Value::Integer(left), Value::Integer(right)) = (left, right) else {
+                return None;
+            };
+            match op {
+                ArithmeticOp::Add => left.checked_add(right),
+                ArithmeticOp::Sub => left.checked_sub(right),
+                ArithmeticOp::Mul => left.checked_mul(right),
+                ArithmeticOp::Div => left.checked_div(right),
+                ArithmeticOp::Mod => left.checked_rem(right),
+            }
+            .map(Value::Integer)
+        }
+        ColumnType::BigInt => {
+            if let (Value::Timestamp(left), Value::Timestamp(right)) = (&left, &right) {
+                let elapsed = i128::from(*left) - i128::from(*right);
+                return i64::try_from(elapsed).ok().map(Value::BigInt);
+            }
+            let (left, right) = (as_i64(&left)?, as_i64(&right)?);
+            match op {
+                ArithmeticOp::Add => left.checked_add(right),
+                ArithmeticOp::Sub => left.checked_sub(right),
+                ArithmeticOp::Mul => left.checked_mul(right),
+                ArithmeticOp::Div => left.checked_div(right),
+                ArithmeticOp::Mod => left.checked_rem(right),
+            }
+            .map(Value::BigInt)
+        }
+        ColumnType::Double => {
+            let (left, right) = (as_f64(&left)?, as_f64(&right)?);
+            if matches!(op, ArithmeticOp::Div | ArithmeticOp::Mod) && right == 0.0 {
+                return None;
+            }
+            Some(Value::Double(match op {
+                ArithmeticOp::Add => left + right,
+                ArithmeticOp::Sub => left - right,
+                ArithmeticOp::Mul => left * right,
+                ArithmeticOp::Div => left / right,
+                ArithmeticOp::Mod => left % right,
+            }))
+        }
+        ColumnType::Decimal { scale, .. } => {
+            let (left, right) = (as_decimal(&left)?, as_decimal(&right)?);
+            match op {
+                ArithmeticOp::Add => left.checked_add(&right),
+                ArithmeticOp::Sub => left.checked_sub(&right),
+                ArithmeticOp::Mul => left.checked_mul(&right),
+                ArithmeticOp::Div => left.checked_div(&right, *scale),
+                ArithmeticOp::Mod => left.checked_rem(&right),
+            }
+            .map(Value::Decimal)
+        }
+        ColumnType::Timestamp => {
+            let (timestamp, delta) = match (&left, &right) {
+                (Value::Timestamp(timestamp), delta) => (*timestamp, as_i64(delta)?),
+                (delta, Value::Timestamp(timestamp)) => (*timestamp, as_i64(delta)?),
+                _ => return None,
+            };
+            let delta = match op {
+                ArithmeticOp::Add => delta,
+                ArithmeticOp::Sub => delta.checked_neg()?,
+                _ => return None,
+            };
+            timestamp.checked_add_signed(delta).map(Value::Timestamp)
+        }
+        _ => None,
+    }
+}
+
+fn coerce_value(value: Value, target: &ColumnType) -> Value {
+    match (value, target) {
+        (Value::Integer(value), ColumnType::BigInt) => Value::BigInt(i64::from(value)),
+        (Value::Integer(value), ColumnType::Double) => Value::Double(f64::from(value)),
+        (Value::BigInt(value), ColumnType::Double) => Value::Double(value as f64),
+        (Value::Decimal(value), ColumnType::Double) => Value::Double(value.to_f64()),
+        (value @ (Value::Integer(_) | Value::BigInt(_)), ColumnType::Decimal { .. }) => {
+            as_decimal(&value).map(Value::Decimal).unwrap_or(value)
+        }
+        (value, _) => value,
+    }
+}
+
+pub(crate) fn render_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Text(text) => Some(text.clone()),
+        Value::Integer(value) => Some(value.to_string()),
+        Value::BigInt(value) => Some(value.to_string()),
+        Value::Double(value) => Some(value.to_string()),
+        Value::Decimal(value) => Some(value.to_string()),
+        Value::Boolean(value) => Some(value.to_string()),
+        Value::Timestamp(value) => Some(value.to_string()),
+        Value::Date(value) => Some(value.to_string()),
+        Value::Time(value) => Some(value.to_string()),
+        Value::Interval(value) => Some(value.to_string()),
+        Value::Uuid(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+/// Ordering between two non-null values of the same expression type.
+pub(crate) fn compare_values(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
+        (Value::BigInt(a), Value::BigInt(b)) => Some(a.cmp(b)),
+        (Value::Double(a), Value::Double(b)) => a.partial_cmp(b),
+        (Value::Decimal(a), Value::Decimal(b)) => Some(a.cmp(b)),
+        (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
+        (Value::Text(a), Value::Text(b)) => Some(a.cmp(b)),
+        (Value::Timestamp(a), Value::Timestamp(b)) => Some(a.cmp(b)),
+        (Value::Date(a), Value::Date(b)) => Some(a.cmp(b)),
+        (Value::Time(a), Value::Time(b)) => Some(a.cmp(b)),
+        (Value::Interval(a), Value::Interval(b)) => Some(a.cmp(b)),
+        (Value::Uuid(a), Value::Uuid(b)) => Some(a.cmp(b)),
+        (Value::BatchId(a), Value::BatchId(b)) => Some(a.cmp(b)),
+        (Value::Bytea(a), Value::Bytea(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn truncate_timestamp(micros: u64, unit: DateTruncUnit) -> Option<u64> {
+    let days = micros / MICROS_PER_DAY;
+    match unit {
+        DateTruncUnit::Second => Some(micros - micros % MICROS_PER_SECOND),
+        DateTruncUnit::Minute => Some(micros - micros % MICROS_PER_MINUTE),
+        DateTruncUnit::Hour => Some(micros - micros % MICROS_PER_HOUR),
+        DateTruncUnit::Day => Some(days * MICROS_PER_DAY),
+        DateTruncUnit::Week => {
+            // 1970-01-01 was a Thursday, three days after the preceding Monday.
+            let days_since_monday = (days + 3) % 7;
+            days.checked_sub(days_since_monday)
+                .map(|monday| monday * MICROS_PER_DAY)
+        }
+        DateTruncUnit::Month => {
+            let (year, month, _day) = civil_from_days(days);
+            Some(days_from_civil(year, month, 1) * MICROS_PER_DAY)
+        }
+        DateTruncUnit::Year => {
+            let (year, _month, _day) = civil_from_days(days);
+            Some(days_from_civil(year, 1, 1) * MICROS_PER_DAY)
+        }
+    }
+}
+
+/// Convert days since the Unix epoch to a proleptic Gregorian `(year, month, day)`.
+///
+/// Howard Hinnant's `civil_from_days`, restricted to non-negative day counts.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let shifted = days + 719_468;
+    let era = shifted / 146_097;
+    let day_of_era = shifted - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Inverse of [`civil_from_days`] for dates on or after 1970-01-01.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::ObjectId;
+    use crate::query_manager::encoding::encode_row;
+    use crate::query_manager::relation_ir::CaseBranch;
+    use crate::query_manager::types::{ColumnDescriptor, RowDescriptor, TupleElement};
+
+    fn people_descriptor() -> RowDescriptor {
+        RowDescriptor::new(vec![
+            ColumnDescriptor::new("first", ColumnType::Text),
+            ColumnDescriptor::new("last", ColumnType::Text).nullable(),
+            ColumnDescriptor::new("price", ColumnType::Integer),
+            ColumnDescriptor::new("qty", ColumnType::BigInt),
+            ColumnDescriptor::new("seen_at", ColumnType::Timestamp),
+        ])
+    }
+
+    fn people_tuple(values: &[Value]) -> (Tuple, TupleDescriptor) {
+        let descriptor = people_descriptor();
+        let content = encode_row(&descriptor, values).unwrap();
+        let tuple = Tuple::new(vec![TupleElement::Row {
+            id: ObjectId::new(),
+            content: content.into(),
+            batch_id: crate::row_histories::BatchId([0; 16]),
+            row_provenance: crate::metadata::RowProvenance::for_insert("jazz:test", 0),
+        }]);
+        let tuple_descriptor =
+            TupleDescriptor::single_with_materialization("people", descriptor, true);
+        (tuple, tuple_descriptor)
+    }
+
+    fn column(name: &str) -> ScalarExpr {
+        ScalarExpr::Column(ColumnRef::unscoped(name))
+    }
+
+    fn literal(value: Value) -> ScalarExpr {
+        ScalarExpr::Literal(value)
+    }
+
+    fn eval(expr: &ScalarExpr, tuple: &Tuple, descriptor: &TupleDescriptor) -> Value {
+        BoundScalarExpr::bind(expr, descriptor)
+            .expect("expression should bind")
+            .evaluate(tuple, descriptor)
+    }
+
+    // 2024-03-15T13:45:30.250Z
+    const SAMPLE_MICROS: u64 = 1_710_510_330_250_000;
+
+    fn sample() -> (Tuple, TupleDescriptor) {
+        people_tuple(&[
+            Value::Text("Ada".into()),
+            Value::Null,
+            Value::Integer(250),
+            Value::BigInt(4),
+            Value::Timestamp(SAMPLE_MICROS),
+        ])
+    }
+
+    #[test]
+    fn arithmetic_widens_integer_operands() {
+        let (tuple, descriptor) = sample();
+        let total = ScalarExpr::Arithmetic {
+            op: ArithmeticOp::Mul,
+            left: Box::new(column("price")),
+            right: Box::new(column("qty")),
+        };
+
+        let bound = BoundScalarExpr::bind(&total, &descriptor).unwrap();
+        assert_eq!(bound.output_type(), Some(&ColumnType::BigInt));
+        assert_eq!(bound.evaluate(&tuple, &descriptor), Value::BigInt(1000));
+    }
+
+    #[test]
+    fn decimal_arithmetic_is_exact_and_keeps_scale() {
+        let (tuple, descriptor) = sample();
+        let decimal = |raw: &str| literal(Value::Decimal(Decimal::parse(raw).unwrap()));
+        let discounted = ScalarExpr::Arithmetic {
+            op: ArithmeticOp::Mul,
+            left: Box::new(column("price")),
+            right: Box::new(decimal("0.15")),
+        };
+        let bound = BoundScalarExpr::bind(&discounted, &descriptor).unwrap();
+        assert_eq!(
+            bound.output_type(),
+            Some(&ColumnType::Decimal {
+                precision: DECIMAL_MAX_PRECISION,
+                scale: 2,
+            })
+        );
+        assert_eq!(
+            render_text(&bound.evaluate(&tuple, &descriptor)),
+            Some("37.50".to_string())
+        );
+
+        let sum = ScalarExpr::Arithmetic {
+            op: ArithmeticOp::Add,
+            left: Box::new(decimal("0.1")),
+            right: Box::new(decimal("0.2")),
+        };
+        assert_eq!(
+            eval(&sum, &tuple, &descriptor),
+            Value::Decimal(Decimal::parse("0.3").unwrap())
+        );
+    }
+
+    #[test]
+    fn arithmetic_overflow_and_division_by_zero_yield_null() {
+        let (tuple, descriptor) = sample();
+        let overflow = ScalarExpr::Arithmetic {
+            op: ArithmeticOp::Mul,
+            left: Box::new(column("price")),
+            right: Box::new(literal(Value::Integer(i32::MAX))),
+        };
+        let divide_by_zero = ScalarExpr::Arithmetic {
+            op: ArithmeticOp::Div,
+            left: Box::new(column("price")),
+            right: Box::new(literal(Value::Integer(0))),
+        };
+
+        assert_eq!(eval(&overflow, &tuple, &descriptor), Value::Null);
+        assert_eq!(eval(&divide_by_zero, &tuple, &descriptor), Value::Null);
+    }
+
+    #[test]
+    fn concat_and_case_functions_follow_null_rules() {
+        let (tuple, descriptor) = sample();
+        let full_name = ScalarExpr::Concat(vec![
+            column("first"),
+            literal(Value::Text(" ".into())),
+            column("last"),
+        ]);
+        let display = ScalarExpr::Upper(Box::new(ScalarExpr::Coalesce(vec![
+            full_name.clone(),
+            column("first"),
+        ])));
+        let tier = ScalarExpr::Case {
+            branches: vec![CaseBranch {
+                when: PredicateExpr::Cmp {
+                    left: ColumnRef::unscoped("price"),
+                    op: PredicateCmpOp::Gt,
+                    right: ValueRef::Literal(Value::Integer(100)),
+                },
+                then: literal(Value::Text("premium".into())),
+            }],
+            otherwise: Some(Box::new(literal(Value::Text("basic".into())))),
+        };
+
+        assert_eq!(eval(&full_name, &tuple, &descriptor), Value::Null);
+        assert_eq!(
+            eval(&display, &tuple, &descriptor),
+            Value::Text("ADA".into())
+        );
+        assert_eq!(
+            eval(&tier, &tuple, &descriptor),
+            Value::Text("premium".into())
+        );
+    }
+
+    #[test]
+    fn date_trunc_handles_calendar_units() {
+        let (tuple, descriptor) = sample();
+        let truncate = |unit| {
+            eval(
+                &ScalarExpr::DateTrunc {
+                    unit,
+                    input: Box::new(column("seen_at")),
+                },
+                &tuple,
+                &descriptor,
+            )
+        };
+
+        // 2024-03-15T13:45:30Z
+        assert_eq!(
+            truncate(DateTruncUnit::Second),
+            Value::Timestamp(1_710_510_330_000_000)
+        );
+        // 2024-03-15T00:00:00Z
+        assert_eq!(
+            truncate(DateTruncUnit::Day),
+            Value::Timestamp(1_710_460_800_000_000)
+        );
+        // Monday 2024-03-11T00:00:00Z
+        assert_eq!(
+            truncate(DateTruncUnit::Week),
+            Value::Timestamp(1_710_115_200_000_000)
+        );
+        // 2024-03-01T00:00:00Z
+        assert_eq!(
+            truncate(DateTruncUnit::Month),
+            Value::Timestamp(1_709_251_200_000_000)
+        );
+        // 2024-01-01T00:00:00Z
+        assert_eq!(
+            truncate(DateTruncUnit::Year),
+            Value::Timestamp(1_704_067_200_000_000)
+        );
+    }
+
+    #[test]
+    fn comparison_between_expressions_uses_unified_type() {
+        let (tuple, descriptor) = sample();
+        let condition = BoundCondition::bind_comparison(
+            &ScalarComparison {
+                left: ScalarExpr::Arithmetic {
+                    op: ArithmeticOp::Mul,
+                    left: Box::new(column("price")),
+                    right: Box::new(column("qty")),
+                },
+                op: PredicateCmpOp::Ge,
+                right: literal(Value::Integer(1000)),
+            },
+            &descriptor,
+        )
+        .unwrap();
+        let unknown = BoundCondition::bind_comparison(
+            &ScalarComparison {
+                left: column("last"),
+                op: PredicateCmpOp::Eq,
+                right: literal(Value::Text("Lovelace".into())),
+            },
+            &descriptor,
+        )
+        .unwrap();
+
+        assert_eq!(condition.evaluate(&tuple, &descriptor), Some(true));
+        assert_eq!(unknown.evaluate(&tuple, &descriptor), None);
+    }
+
+    #[test]
+    fn binding_rejects_unknown_columns_and_type_mismatches() {
+        let (_tuple, descriptor) = sample();
+
+        let unknown = BoundScalarExpr::bind(&column("missing"), &descriptor).unwrap_err();
+        assert_eq!(
+            unknown,
+            ScalarExprError::UnknownColumn("missing".to_string())
+        );
+
+        let mismatch = BoundScalarExpr::bind(
+            &ScalarExpr::Arithmetic {
+                op: ArithmeticOp::Add,
+                left: Box::new(column("first")),
+                right: Box::new(column("price")),
+            },
+            &descriptor,
+        )
+        .unwrap_err();
+        assert!(matches!(mismatch, ScalarExprError::TypeMismatch { .. }));
+
+        let lower_number =
+            BoundScalarExpr::bind(&ScalarExpr::Lower(Box::new(column("price"))), &descriptor)
+                .unwrap_err();
+        assert!(matches!(
+            lower_number,
+            ScalarExprError::UnsupportedType {
+                context: "lower",
+                ..
+            }
+        ));
+    }
+}
@@ -6,14 +6,17 @@ use std::time::{Duration, Instant};
 use crate::metadata::{MetadataKey, RowProvenance};
 use crate::object::{BranchName, ObjectId};
 use crate::query_manager::graph_nodes::policy_eval::PolicyContextEvaluator;
-use crate::row_histories::BatchId;
-use crate::schema_manager::{LensTransformer, transformer::translate_table_name_from_schema};
+use crate::row_histories::{BatchId, QueryRowBatch};
+use crate::schema_manager::{
+    LensTransformer, TransformError, transformer::translate_table_name_from_schema,
+};
 use crate::storage::Storage;
 use crate::sync_manager::{
     ClientId, ClientRole, DurabilityTier, PendingPermissionCheck, SyncPayload,
 };
 
-use super::manager::{QueryManager, SchemaWarningAccumulator, ServerQuerySubscription};
+use super::limits::{LIMIT_EXCEEDED_CODE, check_encoded_row_limits};
+use super::manager::{QueryError, QueryManager, SchemaWarningAccumulator, ServerQuerySubscription};
 use super::policy::{ComplexClause, Operation, PolicyExpr};
 use super::policy_graph::{PolicyGraph, PolicyGraphBuildOptions};
 use super::session::Session;
@@ -135,7 +138,7 @@ impl QueryManager {
                 .ok()?
                 .into_iter()
                 .filter(|row| row.state.is_visible() && row.delete_kind.is_none())
-                .max_by_key(|row| (row.updated_at, row.batch_id()))
+                .max_by_key(|row| row.lww_key())
                 .map(|row| row.row_provenance())
         })?;
         Some(row_provenance)
@@ -331,9 +334,11 @@ impl QueryManager {
         None
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn transform_content_to_authorization_schema(
         &self,
         table: &str,
+        object_id: ObjectId,
         content: &[u8],
         batch_id: BatchId,
         branch_name: BranchName,
@@ -355,7 +360,7 @@ impl QueryManager {
 
         let transformer = LensTransformer::new(auth_context, table);
         transformer
-            .transform(content, batch_id, source_hash)
+            .transform(object_id, content, batch_id, source_hash)
             .ok()
             .map(|result| result.data)
     }
@@ -436,6 +441,7 @@ impl QueryManager {
 
         let transformed = self.transform_content_to_authorization_schema(
             &table,
+            object_id,
             &tip_content,
             tip_batch_id,
             branch_name,
@@ -476,6 +482,7 @@ impl QueryManager {
         };
         let Some(transformed) = self.transform_content_to_authorization_schema(
             table_name.as_str(),
+            object_id,
             content,
             BatchId([0; 16]),
             branch_name,
@@ -758,20 +765,34 @@ impl QueryManager {
         normalized
     }
 
+    /// Lens `row`, the winning version of `id`, into the current schema; see
+    /// [`Self::transform_row_onto_newest`] for rows written under older
+    /// schemas.
     pub(super) fn transform_row_with_schema(
+        storage: &dyn Storage,
         id: ObjectId,
-        content: Vec<u8>,
-        batch_id: BatchId,
-        branch_name: BranchName,
+        row: &QueryRowBatch,
+        branches: &[String],
         context: &mut RowTransformContext<'_>,
     ) -> Option<ResolvedSchemaRow> {
-        let source_hash = context.branch_schema_map.get(branch_name.as_str()).copied();
+        let branch_name = BranchName::new(&row.branch);
+        let source_hash = context.branch_schema_map.get(row.branch.as_str()).copied();
 
         if let Some(source_hash) = source_hash
             && source_hash != context.schema_context.current_hash
         {
-            let transformer = LensTransformer::new(context.schema_context, context.table);
-            match transformer.transform(&content, batch_id, source_hash) {
+            match Self::transform_row_onto_newest(
+                storage,
+                id,
+                context.table,
+                Some(context.table),
+                row,
+                source_hash,
+                branches,
+                None,
+                context.schema_context,
+                context.branch_schema_map,
+            ) {
                 Ok(result) => {
                     return Some(ResolvedSchemaRow {
                         branch_name,
@@ -779,6 +800,7 @@ impl QueryManager {
                         content: result.data,
                     });
                 }
+                Err(TransformError::EmptyExtraction(_)) => return None,
                 Err(err) => {
                     context.schema_warnings.record(
                         context.table,
@@ -801,8 +823,8 @@ impl QueryManager {
 
         Some(ResolvedSchemaRow {
             branch_name,
-            batch_id,
-            content,
+            batch_id: row.batch_id,
+            content: row.data.to_vec(),
         })
     }
 
@@ -1602,6 +1624,49 @@ impl QueryManager {
             return;
         }
 
+        if matches!(check.operation, Operation::Insert | Operation::Update)
+            && let Some(new_content) = check.new_content.as_ref()
+        {
+            let limit_check = check_encoded_row_limits(&branch_table_schema, new_content)
+                .map_err(|violation| QueryError::LimitExceeded {
+                    table: write_table_name,
+                    violation,
+                })
+                .and_then(|()| match &check.payload {
+                    SyncPayload::RowBatchCreated { row, .. }
+                        if check.operation == Operation::Insert =>
+                    {
+                        self.check_and_reserve_row_quota(
+                            &*storage,
+                            write_table_name.as_str(),
+                            branch_name.as_str(),
+                            &branch_table_schema.limits,
+                            &check.session.user_id,
+                            row.row_id,
+                            row.batch_id,
+                        )
+                    }
+                    _ => Ok(()),
+                });
+            match limit_check {
+                Ok(()) => {}
+                Err(err @ QueryError::LimitExceeded { .. }) => {
+                    self.sync_manager.reject_permission_check_with_code(
+                        storage,
+                        check,
+                        LIMIT_EXCEEDED_CODE.to_string(),
+                        err.to_string(),
+                    );
+                    return;
+                }
+                Err(err) => {
+                    self.sync_manager
+                        .reject_permission_check(storage, check, err.to_string());
+                    return;
+                }
+            }
+        }
+
         let (auth_schema, auth_context) = match self.authorization_schema_for_branch(&branch_name) {
             Some(parts) => parts,
             None => {
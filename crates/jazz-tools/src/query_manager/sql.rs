@@ -0,0 +1,2228 @@
+//! SQL front-end for queries.
+//!
+//! Parses a documented subset of PostgreSQL `SELECT` syntax and lowers it into
+//! [`RelExpr`], so SQL strings run through the same reactive pipeline as
+//! builder queries (`Query::relation_ir` → `graph/compile.rs`). Supported:
+//!
+//! - `SELECT *`, `SELECT alias.*` and column lists (`col [AS name]`)
+//! - `FROM table [[AS] alias]` and `[INNER] JOIN table [[AS] alias] ON a.col = b.col`
+//! - `WHERE` with `AND` / `OR` / `NOT`, `= <> != < <= > >=`, `[NOT] IN (...)`,
+//!   `[NOT] BETWEEN`, `IS [NOT] NULL` and `value = ANY(array_column)`
+//! - `ORDER BY col [ASC | DESC], ...`, `LIMIT n`, `OFFSET n`
+//! - `WITH RECURSIVE name AS (seed UNION [ALL] step) SELECT ... FROM name`
+//! - session parameters: `$session.user_id`, `$session.claims.<path>`
+//!
+//! Literals are typed against the schema: strings become UUIDs for `id` and
+//! UUID columns, integers widen to BIGINT, DOUBLE and TIMESTAMP (microseconds).
+//! Anything outside the subset fails with an error that names an alternative.
+
+use std::fmt;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use uuid::Uuid;
+
+use crate::object::ObjectId;
+use crate::query_manager::policy::resolve_session_value;
+use crate::query_manager::query::Query;
+use crate::query_manager::relation_ir::{
+    ColumnRef, JoinCondition, JoinKind, KeyRef, OrderByExpr, OrderDirection, PredicateCmpOp,
+    PredicateExpr, ProjectColumn, ProjectExpr, RELATION_GATHER_MAX_DEPTH_DEFAULT, RelExpr,
+    RowIdRef, ValueRef,
+};
+use crate::query_manager::session::Session;
+use crate::query_manager::types::{
+    ColumnType, Decimal, GeoPoint, Schema, TableName, Value, Vector, parse_temporal_literal,
+};
+
+/// A SQL text that could not be parsed or lowered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlError {
+    pub message: String,
+    /// Byte offset into the SQL text of the offending token, when known.
+    pub position: Option<usize>,
+}
+
+impl SqlError {
+    fn at(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            position: Some(position),
+        }
+    }
+}
+
+impl fmt::Display for SqlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{} (at byte {position})", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for SqlError {}
+
+/// Lower a SQL `SELECT` into relation IR.
+///
+/// `$session.*` parameters stay as [`ValueRef::SessionRef`] so the IR can be
+/// bound per session later.
+pub fn sql_to_relation_ir(sql: &str, schema: &Schema) -> Result<RelExpr, SqlError> {
+    let statement = Parser::new(sql)?.parse_statement()?;
+    let lowerer = Lowerer {
+        schema,
+        session: None,
+        bind_session: false,
+    };
+    Ok(lowerer.lower(&statement)?.into_relation_ir())
+}
+
+/// Parse a SQL `SELECT` into a [`Query`] ready to subscribe to.
+///
+/// `$session.*` parameters are resolved from `session`; using one without a
+/// session is an error.
+pub fn parse_sql_query(
+    sql: &str,
+    schema: &Schema,
+    session: Option<&Session>,
+) -> Result<Query, SqlError> {
+    let statement = Parser::new(sql)?.parse_statement()?;
+    let lowerer = Lowerer {
+        schema,
+        session,
+        bind_session: true,
+    };
+    Ok(lowerer.lower(&statement)?.into_query())
+}
+
+// ============================================================================
+// Tokens
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// Identifier or keyword, as written.
+    Word(String),
+    QuotedIdent(String),
+    String(String),
+    Number(String),
+    /// `$session.<path>`, without the `session` prefix.
+    Session(Vec<String>),
+    Symbol(&'static str),
+    End,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    pos: usize,
+}
+
+const SYMBOLS: [&str; 14] = [
+    "<>", "!=", "<=", ">=", "=", "<", ">", "(", ")", ",", ".", "*", ";", "-",
+];
+
+const RESERVED: &[&str] = &[
+    "ALL",
+    "AND",
+    "ANY",
+    "AS",
+    "ASC",
+    "BETWEEN",
+    "BY",
+    "CROSS",
+    "DESC",
+    "DISTINCT",
+    "FALSE",
+    "FROM",
+    "FULL",
+    "GROUP",
+    "HAVING",
+    "ILIKE",
+    "IN",
+    "INNER",
+    "IS",
+    "JOIN",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "NATURAL",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "RECURSIVE",
+    "RIGHT",
+    "SELECT",
+    "TRUE",
+    "UNION",
+    "WHERE",
+    "WITH",
+];
+
+fn is_reserved(word: &str) -> bool {
+    RESERVED
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(word))
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token>, SqlError> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.char_indices().peekable();
+    while let Some(&(pos, ch)) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if sql[pos..].starts_with("--") {
+            for (_, c) in chars.by_ref() {
+                if c == '\n' {
+                    break;
+                }
+            }
+            continue;
+        }
+        if sql[pos..].starts_with("/*") {
+            let end = sql[pos + 2..]
+                .find("*/")
+                .ok_or_else(|| SqlError::at(pos, "unterminated comment"))?;
+            let resume = pos + 2 + end + 2;
+            while chars.peek().is_some_and(|&(next, _)| next < resume) {
+                chars.next();
+            }
+            continue;
+        }
+
+        let kind = if ch.is_ascii_alphabetic() || ch == '_' {
+            TokenKind::Word(take_while(&mut chars, is_identifier_char))
+        } else if ch.is_ascii_digit() {
+            TokenKind::Number(take_while(&mut chars, |c| c.is_ascii_digit() || c == '.'))
+        } else if ch == '\'' {
+            chars.next();
+            TokenKind::String(take_quoted(&mut chars, '\'', pos, "string literal")?)
+        } else if ch == '"' {
+            chars.next();
+            TokenKind::QuotedIdent(take_quoted(&mut chars, '"', pos, "quoted identifier")?)
+        } else if ch == '$' {
+            chars.next();
+            let mut path = vec![take_while(&mut chars, is_identifier_char)];
+            while chars.peek().is_some_and(|&(_, c)| c == '.') {
+                chars.next();
+                path.push(take_while(&mut chars, is_identifier_char));
+            }
+            if path.len() < 2 || path[0] != "session" || path.iter().any(String::is_empty) {
+                return Err(SqlError::at(
+                    pos,
+                    format!(
+                        "unsupported parameter ${}; only $session.user_id and $session.claims.<path> are supported",
+                        path.join(".")
+                    ),
+                ));
+            }
+            TokenKind::Session(path.split_off(1))
+        } else {
+            let symbol = SYMBOLS
+                .into_iter()
+                .find(|symbol| sql[pos..].starts_with(*symbol))
+                .ok_or_else(|| SqlError::at(pos, format!("unexpected character '{ch}'")))?;
+            for _ in 0..symbol.len() {
+                chars.next();
+            }
+            TokenKind::Symbol(symbol)
+        };
+        tokens.push(Token { kind, pos });
+    }
+    tokens.push(Token {
+        kind: TokenKind::End,
+        pos: sql.len(),
+    });
+    Ok(tokens)
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn take_while(chars: &mut Peekable<CharIndices<'_>>, keep: impl Fn(char) -> bool) -> String {
+    let mut out = String::new();
+    while let Some(&(_, c)) = chars.peek() {
+        if !keep(c) {
+            break;
+        }
+        out.push(c);
+        chars.next();
+    }
+    out
+}
+
+fn take_quoted(
+    chars: &mut Peekable<CharIndices<'_>>,
+    quote: char,
+    start: usize,
+    what: &str,
+) -> Result<String, SqlError> {
+    let mut out = String::new();
+    loop {
+        match chars.next() {
+            Some((_, c)) if c == quote => {
+                if chars.peek().is_some_and(|&(_, next)| next == quote) {
+                    chars.next();
+                    out.push(quote);
+                } else {
+                    return Ok(out);
+                }
+            }
+            Some((_, c)) => out.push(c),
+            None => return Err(SqlError::at(start, format!("unterminated {what}"))),
+        }
+    }
+}
+
+fn describe_token(kind: &TokenKind) -> String {
+    match kind {
+        TokenKind::Word(word) => format!("'{word}'"),
+        TokenKind::QuotedIdent(ident) => format!("\"{ident}\""),
+        TokenKind::String(_) => "a string literal".to_string(),
+        TokenKind::Number(number) => number.clone(),
+        TokenKind::Session(path) => format!("$session.{}", path.join(".")),
+        TokenKind::Symbol(symbol) => format!("'{symbol}'"),
+        TokenKind::End => "end of query".to_string(),
+    }
+}
+
+// ============================================================================
+// Syntax tree
+// ============================================================================
+
+#[derive(Debug)]
+struct Statement {
+    recursive: Option<RecursiveCte>,
+    body: SelectCore,
+    order_by: Vec<(ColumnName, OrderDirection)>,
+    limit: Option<usize>,
+    offset: usize,
+}
+
+#[derive(Debug)]
+struct RecursiveCte {
+    name: String,
+    seed: SelectCore,
+    step: SelectCore,
+}
+
+#[derive(Debug)]
+struct SelectCore {
+    pos: usize,
+    projection: Vec<SelectItem>,
+    from: TableRef,
+    joins: Vec<JoinClause>,
+    filter: Option<Expr>,
+}
+
+#[derive(Debug)]
+enum SelectItem {
+    Wildcard(usize),
+    ScopeWildcard { scope: String, pos: usize },
+    Column(ColumnName, Option<String>),
+}
+
+#[derive(Debug)]
+struct TableRef {
+    pos: usize,
+    name: String,
+    alias: Option<String>,
+}
+
+impl TableRef {
+    fn scope_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Debug)]
+struct JoinClause {
+    table: TableRef,
+    left: ColumnName,
+    right: ColumnName,
+}
+
+#[derive(Debug, Clone)]
+struct ColumnName {
+    pos: usize,
+    scope: Option<String>,
+    column: String,
+}
+
+impl ColumnName {
+    fn to_ref(&self) -> ColumnRef {
+        ColumnRef {
+            scope: self.scope.clone(),
+            column: self.column.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ColumnName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.scope {
+            Some(scope) => write!(f, "{scope}.{}", self.column),
+            None => write!(f, "{}", self.column),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Operand {
+    Column(ColumnName),
+    Literal { value: Value, pos: usize },
+    Null(usize),
+    Session { path: Vec<String>, pos: usize },
+}
+
+impl Operand {
+    fn pos(&self) -> usize {
+        match self {
+            Operand::Column(column) => column.pos,
+            Operand::Literal { pos, .. } | Operand::Null(pos) | Operand::Session { pos, .. } => {
+                *pos
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Expr {
+    Cmp {
+        left: Operand,
+        op: PredicateCmpOp,
+        right: Operand,
+    },
+    In {
+        column: ColumnName,
+        values: Vec<Operand>,
+        negated: bool,
+    },
+    Between {
+        column: ColumnName,
+        low: Operand,
+        high: Operand,
+        negated: bool,
+    },
+    IsNull {
+        column: ColumnName,
+        negated: bool,
+    },
+    AnyEq {
+        value: Operand,
+        column: ColumnName,
+    },
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl Parser {
+    fn new(sql: &str) -> Result<Self, SqlError> {
+        Ok(Self {
+            tokens: tokenize(sql)?,
+            index: 0,
+        })
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index]
+    }
+
+    fn peek_nth(&self, n: usize) -> &Token {
+        &self.tokens[(self.index + n).min(self.tokens.len() - 1)]
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.index].clone();
+        if self.index + 1 < self.tokens.len() {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(&self.peek().kind, TokenKind::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let matched = self.is_keyword(keyword);
+        if matched {
+            self.advance();
+        }
+        matched
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), SqlError> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("expected {keyword}")))
+        }
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        matches!(self.peek().kind, TokenKind::Symbol(found) if found == symbol)
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let matched = self.is_symbol(symbol);
+        if matched {
+            self.advance();
+        }
+        matched
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), SqlError> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("expected '{symbol}'")))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> SqlError {
+        SqlError::at(
+            self.peek().pos,
+            format!("{expected}, found {}", describe_token(&self.peek().kind)),
+        )
+    }
+
+    fn reject(&self, message: &str) -> SqlError {
+        SqlError::at(self.peek().pos, message)
+    }
+
+    fn at_subquery(&self) -> bool {
+        self.is_symbol("(")
+            && matches!(&self.peek_nth(1).kind, TokenKind::Word(word) if word.eq_ignore_ascii_case("SELECT"))
+    }
+
+    fn parse_statement(mut self) -> Result<Statement, SqlError> {
+        let recursive = if self.eat_keyword("WITH") {
+            if !self.eat_keyword("RECURSIVE") {
+                return Err(self.reject(
+                    "only WITH RECURSIVE is supported; inline non-recursive CTEs into the main query",
+                ));
+            }
+            Some(self.parse_recursive_cte()?)
+        } else {
+            None
+        };
+
+        let body = self.parse_select_core()?;
+        if self.is_keyword("UNION") {
+            return Err(self.reject(
+                "UNION is only supported inside WITH RECURSIVE; subscribe to each query separately",
+            ));
+        }
+
+        let mut order_by = Vec::new();
+        if self.eat_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let column = self.parse_column_name()?;
+                let direction = if self.eat_keyword("DESC") {
+                    OrderDirection::Desc
+                } else {
+                    self.eat_keyword("ASC");
+                    OrderDirection::Asc
+                };
+                if self.is_keyword("NULLS") {
+                    return Err(self.reject("NULLS FIRST / NULLS LAST is not supported"));
+                }
+                order_by.push((column, direction));
+                if !self.eat_symbol(",") {
+                    break;
+                }
+            }
+        }
+
+        let mut limit = None;
+        let mut offset = None;
+        loop {
+            if limit.is_none() && self.eat_keyword("LIMIT") {
+                limit = Some(self.parse_count("LIMIT")?);
+            } else if offset.is_none() && self.eat_keyword("OFFSET") {
+                offset = Some(self.parse_count("OFFSET")?);
+            } else {
+                break;
+            }
+        }
+
+        self.eat_symbol(";");
+        if self.peek().kind != TokenKind::End {
+            return Err(self.unexpected("expected end of query"));
+        }
+
+        Ok(Statement {
+            recursive,
+            body,
+            order_by,
+            limit,
+            offset: offset.unwrap_or(0),
+        })
+    }
+
+    fn parse_recursive_cte(&mut self) -> Result<RecursiveCte, SqlError> {
+        let name = self.parse_identifier("a CTE name")?;
+        if self.is_symbol("(") {
+            return Err(self.reject(
+                "column lists on recursive CTEs are not supported; name the columns in the seed SELECT",
+            ));
+        }
+        self.expect_keyword("AS")?;
+        self.expect_symbol("(")?;
+        let seed = self.parse_select_core()?;
+        self.expect_keyword("UNION")?;
+        self.eat_keyword("ALL");
+        let step = self.parse_select_core()?;
+        self.expect_symbol(")")?;
+        Ok(RecursiveCte { name, seed, step })
+    }
+
+    fn parse_select_core(&mut self) -> Result<SelectCore, SqlError> {
+        let pos = self.peek().pos;
+        self.expect_keyword("SELECT")?;
+        if self.is_keyword("DISTINCT") {
+            return Err(
+                self.reject("SELECT DISTINCT is not supported yet; rows are already unique by id")
+            );
+        }
+        let projection = self.parse_select_list()?;
+        self.expect_keyword("FROM")?;
+        let from = self.parse_table_ref()?;
+
+        let mut joins = Vec::new();
+        loop {
+            if ["LEFT", "RIGHT", "FULL", "CROSS", "NATURAL"]
+                .iter()
+                .any(|keyword| self.is_keyword(keyword))
+            {
+                return Err(self.reject(
+                    "only INNER JOIN is supported; use an array subquery in the query builder to include optional related rows",
+                ));
+            }
+            if self.is_symbol(",") {
+                return Err(
+                    self.reject("comma-separated FROM lists are not supported; use JOIN ... ON")
+                );
+            }
+            let inner = self.eat_keyword("INNER");
+            if !self.eat_keyword("JOIN") {
+                if inner {
+                    return Err(self.unexpected("expected JOIN"));
+                }
+                break;
+            }
+            let table = self.parse_table_ref()?;
+            self.expect_keyword("ON")?;
+            let left = self.parse_column_name()?;
+            if !self.eat_symbol("=") {
+                return Err(
+                    self.unexpected("JOIN ... ON supports a single column equality: expected '='")
+                );
+            }
+            let right = self.parse_column_name()?;
+            if self.is_keyword("AND") || self.is_keyword("OR") {
+                return Err(self.reject(
+                    "JOIN ... ON supports a single column equality; move extra conditions to WHERE",
+                ));
+            }
+            joins.push(JoinClause { table, left, right });
+        }
+
+        let filter = if self.eat_keyword("WHERE") {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        if self.is_keyword("GROUP") || self.is_keyword("HAVING") {
+            return Err(self.reject(
+                "GROUP BY and aggregates are not supported; subscribe to the rows and aggregate in the client",
+            ));
+        }
+
+        Ok(SelectCore {
+            pos,
+            projection,
+            from,
+            joins,
+            filter,
+        })
+    }
+
+    fn parse_select_list(&mut self) -> Result<Vec<SelectItem>, SqlError> {
+        let mut items = Vec::new();
+        loop {
+            let pos = self.peek().pos;
+            if self.eat_symbol("*") {
+                items.push(SelectItem::Wildcard(pos));
+            } else {
+                self.reject_function_call()?;
+                let first = self.parse_identifier("a column")?;
+                if self.eat_symbol(".") {
+                    if self.eat_symbol("*") {
+                        items.push(SelectItem::ScopeWildcard { scope: first, pos });
+                    } else {
+                        let column = self.parse_identifier("a column")?;
+                        let alias = self.parse_alias()?;
+                        items.push(SelectItem::Column(
+                            ColumnName {
+                                pos,
+                                scope: Some(first),
+                                column,
+                            },
+                            alias,
+                        ));
+                    }
+                } else {
+                    let alias = self.parse_alias()?;
+                    items.push(SelectItem::Column(
+                        ColumnName {
+                            pos,
+                            scope: None,
+                            column: first,
+                        },
+                        alias,
+                    ));
+                }
+            }
+            if !self.eat_symbol(",") {
+                return Ok(items);
+            }
+        }
+    }
+
+    fn parse_alias(&mut self) -> Result<Option<String>, SqlError> {
+        if self.eat_keyword("AS") {
+            return self.parse_identifier("an alias").map(Some);
+        }
+        match &self.peek().kind {
+            TokenKind::Word(word) if !is_reserved(word) => {
+                let alias = word.clone();
+                self.advance();
+                Ok(Some(alias))
+            }
+            TokenKind::QuotedIdent(ident) => {
+                let alias = ident.clone();
+                self.advance();
+                Ok(Some(alias))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn parse_table_ref(&mut self) -> Result<TableRef, SqlError> {
+        let pos = self.peek().pos;
+        if self.at_subquery() {
+            return Err(self.reject("subqueries are not supported; use JOIN or WITH RECURSIVE"));
+        }
+        let name = self.parse_identifier("a table name")?;
+        if self.is_symbol("(") {
+            return Err(self.reject("table functions are not supported"));
+        }
+        let alias = self.parse_alias()?;
+        Ok(TableRef { pos, name, alias })
+    }
+
+    fn parse_identifier(&mut self, what: &str) -> Result<String, SqlError> {
+        match &self.peek().kind {
+            TokenKind::Word(word) if !is_reserved(word) => {
+                let identifier = word.clone();
+                self.advance();
+                Ok(identifier)
+            }
+            TokenKind::QuotedIdent(ident) => {
+                let identifier = ident.clone();
+                self.advance();
+                Ok(identifier)
+            }
+            _ => Err(self.unexpected(&format!("expected {what}"))),
+        }
+    }
+
+    fn parse_column_name(&mut self) -> Result<ColumnName, SqlError> {
+        let pos = self.peek().pos;
+        self.reject_function_call()?;
+        let first = self.parse_identifier("a column")?;
+        if self.eat_symbol(".") {
+            let column = self.parse_identifier("a column")?;
+            Ok(ColumnName {
+                pos,
+                scope: Some(first),
+                column,
+            })
+        } else {
+            Ok(ColumnName {
+                pos,
+                scope: None,
+                column: first,
+            })
+        }
+    }
+
+    fn reject_function_call(&self) -> Result<(), SqlError> {
+        let TokenKind::Word(name) = &self.peek().kind else {
+            return Ok(());
+        };
+        if !matches!(self.peek_nth(1).kind, TokenKind::Symbol("(")) {
+            return Ok(());
+        }
+        let upper = name.to_ascii_uppercase();
+        let hint = match upper.as_str() {
+            "COUNT" | "SUM" | "AVG" | "MIN" | "MAX" => {
+                "aggregates are not supported; subscribe to the rows and aggregate in the client"
+            }
+            "LOWER" | "UPPER" | "SUBSTRING" | "TRIM" | "CONCAT" | "LENGTH" => {
+                "string functions are not supported; compare whole values or transform them in the client"
+            }
+            "NOW" | "CURRENT_TIMESTAMP" => {
+                "time functions are not supported; pass the current time as a literal"
+            }
+            _ => "functions are not supported in queries",
+        };
+        Err(self.reject(&format!("{upper}() is not supported: {hint}")))
+    }
+
+    fn parse_count(&mut self, clause: &str) -> Result<usize, SqlError> {
+        let token = self.advance();
+        match &token.kind {
+            TokenKind::Number(number) => number.parse::<usize>().map_err(|_| {
+                SqlError::at(
+                    token.pos,
+                    format!("{clause} expects a non-negative integer"),
+                )
+            }),
+            other => Err(SqlError::at(
+                token.pos,
+                format!(
+                    "{clause} expects a non-negative integer, found {}",
+                    describe_token(other)
+                ),
+            )),
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, SqlError> {
+        let mut left = self.parse_and()?;
+        while self.eat_keyword("OR") {
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, SqlError> {
+        let mut left = self.parse_not()?;
+        while self.eat_keyword("AND") {
+            let right = self.parse_not()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, SqlError> {
+        if self.eat_keyword("NOT") {
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_predicate()
+    }
+
+    fn parse_predicate(&mut self) -> Result<Expr, SqlError> {
+        if self.is_symbol("(") {
+            if self.at_subquery() {
+                return Err(self.reject("subqueries are not supported; use JOIN or WITH RECURSIVE"));
+            }
+            self.advance();
+            let inner = self.parse_expr()?;
+            self.expect_symbol(")")?;
+            return Ok(inner);
+        }
+
+        let left = self.parse_operand()?;
+        if self.eat_keyword("IS") {
+            let negated = self.eat_keyword("NOT");
+            self.expect_keyword("NULL")?;
+            let column = Self::operand_column(left, "IS NULL")?;
+            return Ok(Expr::IsNull { column, negated });
+        }
+
+        let negated = self.eat_keyword("NOT");
+        if self.eat_keyword("IN") {
+            if self.at_subquery() {
+                return Err(self.reject("IN (SELECT ...) is not supported; use JOIN instead"));
+            }
+            self.expect_symbol("(")?;
+            let mut values = vec![self.parse_operand()?];
+            while self.eat_symbol(",") {
+                values.push(self.parse_operand()?);
+            }
+            self.expect_symbol(")")?;
+            let column = Self::operand_column(left, "IN")?;
+            return Ok(Expr::In {
+                column,
+                values,
+                negated,
+            });
+        }
+        if self.eat_keyword("BETWEEN") {
+            let low = self.parse_operand()?;
+            self.expect_keyword("AND")?;
+            let high = self.parse_operand()?;
+            let column = Self::operand_column(left, "BETWEEN")?;
+            return Ok(Expr::Between {
+                column,
+                low,
+                high,
+                negated,
+            });
+        }
+        if self.is_keyword("LIKE") || self.is_keyword("ILIKE") {
+            return Err(self.reject(
+                "LIKE is not supported; use = for exact matches or filter text in the client",
+            ));
+        }
+        if negated {
+            return Err(self.unexpected("expected IN or BETWEEN after NOT"));
+        }
+
+        let op = match self.peek().kind {
+            TokenKind::Symbol("=") => PredicateCmpOp::Eq,
+            TokenKind::Symbol("<>" | "!=") => PredicateCmpOp::Ne,
+            TokenKind::Symbol("<") => PredicateCmpOp::Lt,
+            TokenKind::Symbol("<=") => PredicateCmpOp::Le,
+            TokenKind::Symbol(">") => PredicateCmpOp::Gt,
+            TokenKind::Symbol(">=") => PredicateCmpOp::Ge,
+            _ => return Err(self.unexpected("expected a comparison operator")),
+        };
+        self.advance();
+
+        if self.is_keyword("ANY") {
+            if op != PredicateCmpOp::Eq {
+                return Err(self.reject("only = ANY(array_column) is supported"));
+            }
+            self.advance();
+            self.expect_symbol("(")?;
+            let column = self.parse_column_name()?;
+            self.expect_symbol(")")?;
+            return Ok(Expr::AnyEq {
+                value: left,
+                column,
+            });
+        }
+        if self.is_keyword("ALL") {
+            return Err(self.reject("ALL(...) comparisons are not supported"));
+        }
+
+        let right = self.parse_operand()?;
+        Ok(Expr::Cmp { left, op, right })
+    }
+
+    fn operand_column(operand: Operand, construct: &str) -> Result<ColumnName, SqlError> {
+        match operand {
+            Operand::Column(column) => Ok(column),
+            other => Err(SqlError::at(
+                other.pos(),
+                format!("{construct} needs a column on its left-hand side"),
+            )),
+        }
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, SqlError> {
+        let token = self.peek().clone();
+        match &token.kind {
+            TokenKind::Number(number) => {
+                self.advance();
+                parse_number(number, false, token.pos)
+            }
+            TokenKind::Symbol("-") => {
+                self.advance();
+                match self.advance().kind {
+                    TokenKind::Number(number) => parse_number(&number, true, token.pos),
+                    _ => Err(SqlError::at(token.pos, "expected a number after '-'")),
+                }
+            }
+            TokenKind::String(text) => {
+                self.advance();
+                Ok(Operand::Literal {
+                    value: Value::Text(text.clone()),
+                    pos: token.pos,
+                })
+            }
+            TokenKind::Session(path) => {
+                self.advance();
+                Ok(Operand::Session {
+                    path: path.clone(),
+                    pos: token.pos,
+                })
+            }
+            TokenKind::Word(word) if word.eq_ignore_ascii_case("TRUE") => {
+                self.advance();
+                Ok(Operand::Literal {
+                    value: Value::Boolean(true),
+                    pos: token.pos,
+                })
+            }
+            TokenKind::Word(word) if word.eq_ignore_ascii_case("FALSE") => {
+                self.advance();
+                Ok(Operand::Literal {
+                    value: Value::Boolean(false),
+                    pos: token.pos,
+                })
+            }
+            TokenKind::Word(word) if word.eq_ignore_ascii_case("NULL") => {
+                self.advance();
+                Ok(Operand::Null(token.pos))
+            }
+            TokenKind::Word(_) | TokenKind::QuotedIdent(_) => {
+                self.parse_column_name().map(Operand::Column)
+            }
+            TokenKind::Symbol("(") if self.at_subquery() => {
+                Err(self.reject("subqueries are not supported; use JOIN or WITH RECURSIVE"))
+            }
+            _ => Err(self.unexpected("expected a column or value")),
+        }
+    }
+}
+
+fn parse_number(text: &str, negative: bool, pos: usize) -> Result<Operand, SqlError> {
+    let signed = if negative {
+        format!("-{text}")
+    } else {
+        text.to_string()
+    };
+    let value = if text.contains('.') {
+        signed
+            .parse::<f64>()
+            .map(Value::Double)
+            .map_err(|_| SqlError::at(pos, format!("invalid number {signed}")))?
+    } else {
+        let parsed = signed
+            .parse::<i64>()
+            .map_err(|_| SqlError::at(pos, format!("integer {signed} is out of range")))?;
+        match i32::try_from(parsed) {
+            Ok(small) => Value::Integer(small),
+            Err(_) => Value::BigInt(parsed),
+        }
+    };
+    Ok(Operand::Literal { value, pos })
+}
+
+// ============================================================================
+// Lowering
+// ============================================================================
+
+struct Scope {
+    name: String,
+    table: TableName,
+}
+
+struct Lowerer<'a> {
+    schema: &'a Schema,
+    session: Option<&'a Session>,
+    /// Resolve `$session.*` to literals instead of keeping `SessionRef`s.
+    bind_session: bool,
+}
+
+struct Lowered {
+    table: TableName,
+    alias: Option<String>,
+    core: RelExpr,
+    /// Output columns of a `WITH RECURSIVE` query. The runtime applies them as
+    /// a final selection, so they stay outside `core` until assembled.
+    recursive_columns: Option<Vec<String>>,
+    order_by: Vec<OrderByExpr>,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl Lowered {
+    fn into_relation_ir(self) -> RelExpr {
+        let Lowered {
+            core,
+            recursive_columns,
+            order_by,
+            offset,
+            limit,
+            ..
+        } = self;
+        let relation = match recursive_columns {
+            Some(columns) => RelExpr::Project {
+                input: Box::new(core),
+                columns: columns
+                    .into_iter()
+                    .map(|column| ProjectColumn {
+                        alias: column.clone(),
+                        expr: ProjectExpr::Column(ColumnRef::unscoped(column)),
+                    })
+                    .collect(),
+            },
+            None => core,
+        };
+        wrap_envelope(relation, order_by, offset, limit)
+    }
+
+    fn into_query(self) -> Query {
+        let mut query = Query::new(self.table);
+        query.alias = self.alias;
+        query.select_columns = self.recursive_columns;
+        query.relation_ir = wrap_envelope(self.core, self.order_by, self.offset, self.limit);
+        query
+    }
+}
+
+fn wrap_envelope(
+    mut relation: RelExpr,
+    order_by: Vec<OrderByExpr>,
+    offset: usize,
+    limit: Option<usize>,
+) -> RelExpr {
+    if !order_by.is_empty() {
+        relation = RelExpr::OrderBy {
+            input: Box::new(relation),
+            terms: order_by,
+        };
+    }
+    if offset > 0 {
+        relation = RelExpr::Offset {
+            input: Box::new(relation),
+            offset,
+        };
+    }
+    if let Some(limit) = limit {
+        relation = RelExpr::Limit {
+            input: Box::new(relation),
+            limit,
+        };
+    }
+    relation
+}
+
+/// Scope of the hop table inside a lowered recursive step, as the query
+/// builder names it.
+const RECURSIVE_HOP_SCOPE: &str = "__recursive_hop_0";
+
+fn is_row_id_column(column: &str) -> bool {
+    matches!(column, "id" | "_id")
+}
+
+fn flip_cmp(op: PredicateCmpOp) -> PredicateCmpOp {
+    match op {
+        PredicateCmpOp::Lt => PredicateCmpOp::Gt,
+        PredicateCmpOp::Le => PredicateCmpOp::Ge,
+        PredicateCmpOp::Gt => PredicateCmpOp::Lt,
+        PredicateCmpOp::Ge => PredicateCmpOp::Le,
+        PredicateCmpOp::Eq | PredicateCmpOp::Ne => op,
+    }
+}
+
+fn negate_cmp(op: PredicateCmpOp) -> PredicateCmpOp {
+    match op {
+        PredicateCmpOp::Eq => PredicateCmpOp::Ne,
+        PredicateCmpOp::Ne => PredicateCmpOp::Eq,
+        PredicateCmpOp::Lt => PredicateCmpOp::Ge,
+        PredicateCmpOp::Le => PredicateCmpOp::Gt,
+        PredicateCmpOp::Gt => PredicateCmpOp::Le,
+        PredicateCmpOp::Ge => PredicateCmpOp::Lt,
+    }
+}
+
+impl Lowerer<'_> {
+    fn lower(&self, statement: &Statement) -> Result<Lowered, SqlError> {
+        let (scopes, mut lowered) = match &statement.recursive {
+            Some(cte) => self.lower_recursive(cte, &statement.body)?,
+            None => {
+                let (scopes, core) = self.lower_select(&statement.body)?;
+                let from = &statement.body.from;
+                let lowered = Lowered {
+                    table: scopes[0].table,
+                    alias: from.alias.clone(),
+                    core,
+                    recursive_columns: None,
+                    order_by: Vec::new(),
+                    offset: 0,
+                    limit: None,
+                };
+                (scopes, lowered)
+            }
+        };
+
+        for (column, direction) in &statement.order_by {
+            self.column_type(&scopes, column)?;
+            lowered.order_by.push(OrderByExpr {
+                column: column.to_ref(),
+                direction: *direction,
+                expr: None,
+            });
+        }
+        lowered.offset = statement.offset;
+        lowered.limit = statement.limit;
+        Ok(lowered)
+    }
+
+    fn table(&self, table: &TableRef) -> Result<TableName, SqlError> {
+        let name = TableName::new(table.name.as_str());
+        if !self.schema.contains_key(&name) {
+            return Err(SqlError::at(
+                table.pos,
+                format!("unknown table '{}'", table.name),
+            ));
+        }
+        Ok(name)
+    }
+
+    fn lower_select(&self, select: &SelectCore) -> Result<(Vec<Scope>, RelExpr), SqlError> {
+        let base = self.table(&select.from)?;
+        let mut scopes = vec![Scope {
+            name: select.from.scope_name().to_string(),
+            table: base,
+        }];
+        let mut relation = RelExpr::TableScan { table: base };
+
+        for join in &select.joins {
+            let table = self.table(&join.table)?;
+            let scope = join.table.scope_name();
+            if scopes.iter().any(|existing| existing.name == scope) {
+                return Err(SqlError::at(
+                    join.table.pos,
+                    format!("'{scope}' appears twice in FROM; give one of them an alias"),
+                ));
+            }
+            let (earlier, joined) = if join.right.scope.as_deref() == Some(scope) {
+                (&join.left, &join.right)
+            } else {
+                (&join.right, &join.left)
+            };
+            let earlier_is_known = earlier
+                .scope
+                .as_deref()
+                .is_some_and(|earlier_scope| scopes.iter().any(|s| s.name == earlier_scope));
+            if joined.scope.as_deref() != Some(scope) || !earlier_is_known {
+                return Err(SqlError::at(
+                    join.left.pos,
+                    format!(
+                        "JOIN {scope} ON must compare a qualified column of {scope} with one of an earlier table"
+                    ),
+                ));
+            }
+            scopes.push(Scope {
+                name: scope.to_string(),
+                table,
+            });
+            self.column_type(&scopes, earlier)?;
+            self.column_type(&scopes, joined)?;
+            relation = RelExpr::Join {
+                left: Box::new(relation),
+                right: Box::new(RelExpr::TableScan { table }),
+                on: vec![JoinCondition {
+                    left: earlier.to_ref(),
+                    right: joined.to_ref(),
+                }],
+                join_kind: JoinKind::Inner,
+            };
+        }
+
+        if let Some(filter) = &select.filter {
+            relation = RelExpr::Filter {
+                input: Box::new(relation),
+                predicate: self.lower_predicate(filter, &scopes, false)?,
+            };
+        }
+
+        if let Some(columns) = self.lower_projection(&select.projection, &scopes)? {
+            relation = RelExpr::Project {
+                input: Box::new(relation),
+                columns,
+            };
+        }
+
+        Ok((scopes, relation))
+    }
+
+    fn lower_projection(
+        &self,
+        items: &[SelectItem],
+        scopes: &[Scope],
+    ) -> Result<Option<Vec<ProjectColumn>>, SqlError> {
+        match items {
+            [SelectItem::Wildcard(_)] => return Ok(None),
+            [SelectItem::ScopeWildcard { scope, pos }] => {
+                if !scopes.iter().any(|candidate| &candidate.name == scope) {
+                    return Err(SqlError::at(
+                        *pos,
+                        format!("unknown table or alias '{scope}'"),
+                    ));
+                }
+                if scopes.len() == 1 {
+                    return Ok(None);
+                }
+                return Ok(Some(vec![ProjectColumn {
+                    alias: "id".to_string(),
+                    expr: ProjectExpr::Column(ColumnRef::scoped(scope.clone(), "id")),
+                }]));
+            }
+            _ => {}
+        }
+
+        let mut columns: Vec<ProjectColumn> = Vec::with_capacity(items.len());
+        for item in items {
+            let (column, alias) = match item {
+                SelectItem::Column(column, alias) => (column, alias),
+                SelectItem::Wildcard(pos) | SelectItem::ScopeWildcard { pos, .. } => {
+                    return Err(SqlError::at(
+                        *pos,
+                        "mixing * with other select items is not supported",
+                    ));
+                }
+            };
+            self.column_type(scopes, column)?;
+            let alias = alias.clone().unwrap_or_else(|| column.column.clone());
+            if columns.iter().any(|existing| existing.alias == alias) {
+                return Err(SqlError::at(
+                    column.pos,
+                    format!("duplicate output column '{alias}'; add an alias with AS"),
+                ));
+            }
+            columns.push(ProjectColumn {
+                alias,
+                expr: ProjectExpr::Column(column.to_ref()),
+            });
+        }
+        Ok(Some(columns))
+    }
+
+    /// Resolve the type of a column reference against the tables in scope.
+    fn column_type(&self, scopes: &[Scope], column: &ColumnName) -> Result<ColumnType, SqlError> {
+        let candidates: Vec<&Scope> = match &column.scope {
+            Some(scope) => vec![
+                scopes
+                    .iter()
+                    .find(|candidate| &candidate.name == scope)
+                    .ok_or_else(|| {
+                        SqlError::at(column.pos, format!("unknown table or alias '{scope}'"))
+                    })?,
+            ],
+            None => scopes.iter().collect(),
+        };
+
+        let mut found = candidates.iter().filter_map(|scope| {
+            self.schema
+                .get(&scope.table)?
+                .columns
+                .column(&column.column)
+                .map(|descriptor| descriptor.column_type.clone())
+        });
+        match (found.next(), found.next()) {
+            (Some(column_type), None) => Ok(column_type),
+            (Some(_), Some(_)) => Err(SqlError::at(
+                column.pos,
+                format!("column '{column}' is ambiguous; qualify it with a table name or alias"),
+            )),
+            (None, _) if is_row_id_column(&column.column) && candidates.len() == 1 => {
+                Ok(ColumnType::Uuid)
+            }
+            (None, _) if is_row_id_column(&column.column) => Err(SqlError::at(
+                column.pos,
+                format!("column '{column}' is ambiguous; qualify it with a table name or alias"),
+            )),
+            (None, _) => Err(SqlError::at(
+                column.pos,
+                format!("unknown column '{column}'"),
+            )),
+        }
+    }
+
+    /// Lower a boolean expression, pushing `NOT` down to the comparisons so the
+    /// result stays in the forms the runtime plans.
+    fn lower_predicate(
+        &self,
+        expr: &Expr,
+        scopes: &[Scope],
+        negated: bool,
+    ) -> Result<PredicateExpr, SqlError> {
+        Ok(match expr {
+            Expr::And(left, right) => {
+                let terms = vec![
+                    self.lower_predicate(left, scopes, negated)?,
+                    self.lower_predicate(right, scopes, negated)?,
+                ];
+                if negated {
+                    PredicateExpr::Or(terms)
+                } else {
+                    PredicateExpr::And(terms)
+                }
+            }
+            Expr::Or(left, right) => {
+                let terms = vec![
+                    self.lower_predicate(left, scopes, negated)?,
+                    self.lower_predicate(right, scopes, negated)?,
+                ];
+                if negated {
+                    PredicateExpr::And(terms)
+                } else {
+                    PredicateExpr::Or(terms)
+                }
+            }
+            Expr::Not(inner) => self.lower_predicate(inner, scopes, !negated)?,
+            Expr::IsNull {
+                column,
+                negated: is_not,
+            } => {
+                self.column_type(scopes, column)?;
+                if *is_not != negated {
+                    PredicateExpr::IsNotNull {
+                        column: column.to_ref(),
+                    }
+                } else {
+                    PredicateExpr::IsNull {
+                        column: column.to_ref(),
+                    }
+                }
+            }
+            Expr::Cmp { left, op, right } => {
+                let (column, op, value) = match (left, right) {
+                    (Operand::Column(_), Operand::Column(_)) => {
+                        return Err(SqlError::at(
+                            left.pos(),
+                            "comparing two columns is only supported in JOIN ... ON",
+                        ));
+                    }
+                    (Operand::Column(column), value) => (column, *op, value),
+                    (value, Operand::Column(column)) => (column, flip_cmp(*op), value),
+                    _ => {
+                        return Err(SqlError::at(
+                            left.pos(),
+                            "a comparison needs a column on one side",
+                        ));
+                    }
+                };
+                let column_type = self.column_type(scopes, column)?;
+                PredicateExpr::Cmp {
+                    left: column.to_ref(),
+                    op: if negated { negate_cmp(op) } else { op },
+                    right: self.lower_value(value, &column_type, column)?,
+                }
+            }
+            Expr::In {
+                column,
+                values,
+                negated: not_in,
+            } => {
+                let column_type = self.column_type(scopes, column)?;
+                let values = values
+                    .iter()
+                    .map(|value| self.lower_value(value, &column_type, column))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if *not_in != negated {
+                    PredicateExpr::And(
+                        values
+                            .into_iter()
+                            .map(|value| PredicateExpr::Cmp {
+                                left: column.to_ref(),
+                                op: PredicateCmpOp::Ne,
+                                right: value,
+                            })
+                            .collect(),
+                    )
+                } else {
+                    PredicateExpr::In {
+                        left: column.to_ref(),
+                        values,
+                    }
+                }
+            }
+            Expr::Between {
+                column,
+                low,
+                high,
+                negated: not_between,
+            } => {
+                let column_type = self.column_type(scopes, column)?;
+                let low = self.lower_value(low, &column_type, column)?;
+                let high = self.lower_value(high, &column_type, column)?;
+                let cmp = |op: PredicateCmpOp, right: ValueRef| PredicateExpr::Cmp {
+                    left: column.to_ref(),
+                    op,
+                    right,
+                };
+                if *not_between != negated {
+                    PredicateExpr::Or(vec![
+                        cmp(PredicateCmpOp::Lt, low),
+                        cmp(PredicateCmpOp::Gt, high),
+                    ])
+                } else {
+                    PredicateExpr::And(vec![
+                        cmp(PredicateCmpOp::Ge, low),
+                        cmp(PredicateCmpOp::Le, high),
+                    ])
+                }
+            }
+            Expr::AnyEq { value, column } => {
+                if negated {
+                    return Err(SqlError::at(
+                        column.pos,
+                        "NOT (value = ANY(column)) is not supported",
+                    ));
+                }
+                let ColumnType::Array { element } = self.column_type(scopes, column)? else {
+                    return Err(SqlError::at(
+                        column.pos,
+                        format!("= ANY({column}) needs an array column"),
+                    ));
+                };
+                PredicateExpr::Contains {
+                    left: column.to_ref(),
+                    right: self.lower_value(value, &element, column)?,
+                }
+            }
+        })
+    }
+
+    fn lower_value(
+        &self,
+        operand: &Operand,
+        column_type: &ColumnType,
+        column: &ColumnName,
+    ) -> Result<ValueRef, SqlError> {
+        let coerce = |value: Value, pos: usize| {
+            coerce_value(value, column_type)
+                .map(ValueRef::Literal)
+                .map_err(|message| SqlError::at(pos, format!("{message} (column '{column}')")))
+        };
+        match operand {
+            Operand::Literal { value, pos } => coerce(value.clone(), *pos),
+            Operand::Null(pos) => Err(SqlError::at(
+                *pos,
+                "comparisons with NULL never match; use IS NULL or IS NOT NULL",
+            )),
+            Operand::Session { path, pos } => {
+                if !self.bind_session {
+                    return Ok(ValueRef::SessionRef(path.clone()));
+                }
+                let Some(session) = self.session else {
+                    return Err(SqlError::at(
+                        *pos,
+                        format!(
+                            "$session.{} needs a session; run the query with one",
+                            path.join(".")
+                        ),
+                    ));
+                };
+                let value = resolve_session_value(path, session).ok_or_else(|| {
+                    SqlError::at(
+                        *pos,
+                        format!("the session has no value at $session.{}", path.join(".")),
+                    )
+                })?;
+                coerce(value, *pos)
+            }
+            Operand::Column(other) => Err(SqlError::at(
+                other.pos,
+                "comparing two columns is only supported in JOIN ... ON",
+            )),
+        }
+    }
+
+    fn lower_recursive(
+        &self,
+        cte: &RecursiveCte,
+        outer: &SelectCore,
+    ) -> Result<(Vec<Scope>, Lowered), SqlError> {
+        // Seed: a filtered scan of one table.
+        let seed = &cte.seed;
+        if let Some(join) = seed.joins.first() {
+            return Err(SqlError::at(
+                join.table.pos,
+                "the seed of WITH RECURSIVE must select from a single table",
+            ));
+        }
+        let seed_table = self.table(&seed.from)?;
+        let seed_scopes = vec![Scope {
+            name: seed.from.scope_name().to_string(),
+            table: seed_table,
+        }];
+        let mut seed_relation = RelExpr::TableScan { table: seed_table };
+        if let Some(filter) = &seed.filter {
+            seed_relation = RelExpr::Filter {
+                input: Box::new(seed_relation),
+                predicate: self.lower_predicate(filter, &seed_scopes, false)?,
+            };
+        }
+        let seed_columns = self.recursive_columns(&seed.projection, &seed_scopes)?;
+        let cte_scopes = vec![Scope {
+            name: cte.name.clone(),
+            table: seed_table,
+        }];
+
+        // Step: one table correlated with the CTE, optionally hopping by id.
+        let step = &cte.step;
+        let step_table = self.table(&step.from)?;
+        let step_scope = step.from.scope_name().to_string();
+        let step_scopes = vec![Scope {
+            name: step_scope.clone(),
+            table: step_table,
+        }];
+        let mut frontier = None;
+        let mut hop = None;
+        for join in &step.joins {
+            let joined_scope = join.table.scope_name();
+            let (step_side, other_side) = if join.left.scope.as_deref() == Some(step_scope.as_str())
+            {
+                (&join.left, &join.right)
+            } else {
+                (&join.right, &join.left)
+            };
+            let correlated = step_side.scope.as_deref() == Some(step_scope.as_str())
+                && other_side.scope.as_deref() == Some(joined_scope);
+            if join.table.name == cte.name && frontier.is_none() && correlated {
+                self.column_type(&step_scopes, step_side)?;
+                self.column_type(&cte_scopes, &unscoped(other_side))?;
+                frontier = Some((step_side.column.clone(), other_side.column.clone()));
+            } else if join.table.name != cte.name
+                && hop.is_none()
+                && correlated
+                && is_row_id_column(&other_side.column)
+            {
+                self.column_type(&step_scopes, step_side)?;
+                hop = Some((
+                    self.table(&join.table)?,
+                    joined_scope.to_string(),
+                    step_side.column.clone(),
+                ));
+            } else {
+                return Err(SqlError::at(
+                    join.table.pos,
+                    format!(
+                        "a recursive step joins {name} once (ON {step_scope}.col = {name}.col) and may hop to one table by id (ON other.id = {step_scope}.col)",
+                        name = cte.name
+                    ),
+                ));
+            }
+        }
+        let Some((inner_column, outer_column)) = frontier else {
+            return Err(SqlError::at(
+                step.pos,
+                format!(
+                    "the recursive step must JOIN {} to correlate with it",
+                    cte.name
+                ),
+            ));
+        };
+
+        let seed_has_column = self
+            .schema
+            .get(&seed_table)
+            .is_some_and(|table| table.columns.column(&outer_column).is_some());
+        let (frontier_value, frontier_key) = if is_row_id_column(&outer_column) && !seed_has_column
+        {
+            (
+                ValueRef::RowId(RowIdRef::Frontier),
+                KeyRef::RowId(RowIdRef::Current),
+            )
+        } else {
+            (
+                ValueRef::FrontierColumn(ColumnRef::unscoped(outer_column.clone())),
+                KeyRef::Column(ColumnRef::unscoped(outer_column.clone())),
+            )
+        };
+
+        let mut step_terms = vec![PredicateExpr::Cmp {
+            left: ColumnRef::scoped(step_table.as_str(), inner_column),
+            op: PredicateCmpOp::Eq,
+            right: frontier_value,
+        }];
+        if let Some(filter) = &step.filter {
+            let predicate = self.lower_predicate(filter, &step_scopes, false)?;
+            step_terms.push(unscope_predicate(predicate));
+        }
+        let step_predicate = if step_terms.len() == 1 {
+            step_terms.remove(0)
+        } else {
+            PredicateExpr::And(step_terms)
+        };
+        let step_left = RelExpr::Filter {
+            input: Box::new(RelExpr::TableScan { table: step_table }),
+            predicate: step_predicate,
+        };
+
+        let step_relation = match hop {
+            Some((hop_table, selected_scope, via_column)) => {
+                let selects_hop = match step.projection.as_slice() {
+                    [SelectItem::ScopeWildcard { scope, .. }] => scope == &selected_scope,
+                    _ => false,
+                };
+                if !selects_hop {
+                    return Err(SqlError::at(
+                        step.pos,
+                        format!(
+                            "a recursive step that hops to {selected_scope} must SELECT {selected_scope}.*"
+                        ),
+                    ));
+                }
+                let hop_scope = RECURSIVE_HOP_SCOPE.to_string();
+                RelExpr::Project {
+                    input: Box::new(RelExpr::Join {
+                        left: Box::new(step_left),
+                        right: Box::new(RelExpr::TableScan { table: hop_table }),
+                        on: vec![JoinCondition {
+                            left: ColumnRef::scoped(step_table.as_str(), via_column),
+                            right: ColumnRef::scoped(hop_scope.clone(), "id"),
+                        }],
+                        join_kind: JoinKind::Inner,
+                    }),
+                    columns: vec![ProjectColumn {
+                        alias: "id".to_string(),
+                        expr: ProjectExpr::Column(ColumnRef::scoped(hop_scope, "id")),
+                    }],
+                }
+            }
+            None => match self.lower_projection(&step.projection, &step_scopes)? {
+                Some(columns) => RelExpr::Project {
+                    input: Box::new(step_left),
+                    columns: columns
+                        .into_iter()
+                        .map(|column| match column.expr {
+                            ProjectExpr::Column(column_ref) => ProjectColumn {
+                                alias: column.alias,
+                                expr: ProjectExpr::Column(ColumnRef::scoped(
+                                    step_table.as_str(),
+                                    column_ref.column,
+                                )),
+                            },
+                            expr => ProjectColumn {
+                                alias: column.alias,
+                                expr,
+                            },
+                        })
+                        .collect(),
+                },
+                None => step_left,
+            },
+        };
+
+        // Main query: reads the CTE as a whole.
+        if outer.from.name != cte.name {
+            return Err(SqlError::at(
+                outer.from.pos,
+                format!("a WITH RECURSIVE query must SELECT ... FROM {}", cte.name),
+            ));
+        }
+        if let Some(join) = outer.joins.first() {
+            return Err(SqlError::at(
+                join.table.pos,
+                "joining the result of WITH RECURSIVE is not supported yet",
+            ));
+        }
+        if outer.filter.is_some() {
+            return Err(SqlError::at(
+                outer.pos,
+                "WHERE on the result of WITH RECURSIVE is not supported; filter in the seed or step instead",
+            ));
+        }
+        let outer_scopes = vec![Scope {
+            name: outer.from.scope_name().to_string(),
+            table: seed_table,
+        }];
+        let outer_columns = self.recursive_columns(&outer.projection, &outer_scopes)?;
+
+        let lowered = Lowered {
+            table: seed_table,
+            alias: seed.from.alias.clone(),
+            core: RelExpr::Gather {
+                seed: Box::new(seed_relation),
+                step: Box::new(step_relation),
+                frontier_key: frontier_key.clone(),
+                max_depth: RELATION_GATHER_MAX_DEPTH_DEFAULT,
+                dedupe_key: vec![frontier_key],
+            },
+            recursive_columns: outer_columns.or(seed_columns),
+            order_by: Vec::new(),
+            offset: 0,
+            limit: None,
+        };
+        Ok((outer_scopes, lowered))
+    }
+
+    /// Plain column names selected by one part of a recursive query.
+    fn recursive_columns(
+        &self,
+        items: &[SelectItem],
+        scopes: &[Scope],
+    ) -> Result<Option<Vec<String>>, SqlError> {
+        let Some(columns) = self.lower_projection(items, scopes)? else {
+            return Ok(None);
+        };
+        columns
+            .into_iter()
+            .map(|column| match column.expr {
+                ProjectExpr::Column(column_ref) if column_ref.column == column.alias => {
+                    Ok(column_ref.column)
+                }
+                _ => Err(SqlError::at(
+                    items.first().map(select_item_pos).unwrap_or_default(),
+                    "WITH RECURSIVE selects plain columns or *; aliases are not supported",
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+}
+
+fn select_item_pos(item: &SelectItem) -> usize {
+    match item {
+        SelectItem::Wildcard(pos) | SelectItem::ScopeWildcard { pos, .. } => *pos,
+        SelectItem::Column(column, _) => column.pos,
+    }
+}
+
+fn unscoped(column: &ColumnName) -> ColumnName {
+    ColumnName {
+        pos: column.pos,
+        scope: None,
+        column: column.column.clone(),
+    }
+}
+
+/// Drop table qualifiers; recursive step filters are resolved against the step table.
+fn unscope_predicate(predicate: PredicateExpr) -> PredicateExpr {
+    predicate.map_column_refs(&mut |column| ColumnRef::unscoped(column.column.clone()))
+}
+
+/// Type a literal for comparison with a column of `column_type`.
+fn coerce_value(value: Value, column_type: &ColumnType) -> Result<Value, String> {
+    match (column_type, value) {
+        (ColumnType::Uuid, Value::Text(text)) => Uuid::parse_str(&text)
+            .map(|uuid| Value::Uuid(ObjectId::from_uuid(uuid)))
+            .map_err(|_| format!("'{text}' is not a valid UUID")),
+        (ColumnType::Uuid, value @ Value::Uuid(_)) => Ok(value),
+        (ColumnType::Integer, value @ Value::Integer(_)) => Ok(value),
+        (ColumnType::BigInt, Value::Integer(int)) => Ok(Value::BigInt(int.into())),
+        (ColumnType::BigInt, value @ Value::BigInt(_)) => Ok(value),
+        (ColumnType::Double, Value::Integer(int)) => Ok(Value::Double(int.into())),
+        (ColumnType::Double, Value::BigInt(int)) => Ok(Value::Double(int as f64)),
+        (ColumnType::Double, value @ Value::Double(_)) => Ok(value),
+        (ColumnType::Decimal { .. }, Value::Integer(int)) => Ok(Value::Decimal(int.into())),
+        (ColumnType::Decimal { .. }, Value::BigInt(int)) => Ok(Value::Decimal(int.into())),
+        // Fractional literals lex as doubles; their shortest rendering is the
+        // digits that were written.
+        (ColumnType::Decimal { .. }, Value::Double(number)) => Decimal::parse(&number.to_string())
+            .map(Value::Decimal)
+            .map_err(|error| error.to_string()),
+        (ColumnType::Decimal { .. }, Value::Text(text)) => Decimal::parse(&text)
+            .map(Value::Decimal)
+            .map_err(|error| error.to_string()),
+        (ColumnType::Decimal { .. }, value @ Value::Decimal(_)) => Ok(value),
+        (ColumnType::Timestamp, Value::Integer(int)) if int >= 0 => {
+            Ok(Value::Timestamp(int as u64))
+        }
+        (ColumnType::Timestamp, Value::BigInt(int)) if int >= 0 => Ok(Value::Timestamp(int as u64)),
+        (ColumnType::Date, value @ Value::Date(_))
+        | (ColumnType::Time, value @ Value::Time(_))
+        | (ColumnType::Interval, value @ Value::Interval(_)) => Ok(value),
+        (ColumnType::Date | ColumnType::Time | ColumnType::Interval, Value::Text(text)) => {
+            parse_temporal_literal(&text, column_type)
+                .expect("temporal column type")
+                .map_err(|error| error.to_string())
+        }
+        (ColumnType::GeoPoint, value @ Value::GeoPoint(_)) => Ok(value),
+        (ColumnType::GeoPoint, Value::Text(text)) => GeoPoint::parse(&text)
+            .map(Value::GeoPoint)
+            .map_err(|error| error.to_string()),
+        (ColumnType::Vector { dims }, Value::Text(text)) => {
+            let vector = Vector::parse(&text).map_err(|error| error.to_string())?;
+            if vector.dims() == *dims as usize {
+                Ok(Value::Vector(vector))
+            } else {
+                Err(format!(
+                    "vector has {} dimensions, column expects {dims}",
+                    vector.dims()
+                ))
+            }
+        }
+        (ColumnType::Boolean, value @ Value::Boolean(_)) => Ok(value),
+        (ColumnType::Text | ColumnType::Json { .. }, value @ Value::Text(_)) => Ok(value),
+        (ColumnType::Enum { variants }, Value::Text(text)) => {
+            if variants.contains(&text) {
+                Ok(Value::Text(text))
+            } else {
+                Err(format!("'{text}' is not one of {}", variants.join(", ")))
+            }
+        }
+        (column_type, value) => Err(format!(
+            "{} cannot be compared with a {column_type:?} column",
+            describe_value(&value)
+        )),
+    }
+}
+
+fn describe_value(value: &Value) -> &'static str {
+    match value {
+        Value::Text(_) => "a string",
+        Value::Integer(_) | Value::BigInt(_) => "an integer",
+        Value::Double(_) => "a decimal number",
+        Value::Boolean(_) => "a boolean",
+        Value::Null => "NULL",
+        _ => "this value",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_manager::types::{ColumnDescriptor, RowDescriptor};
+
+    fn schema() -> Schema {
+        let mut schema = Schema::new();
+        schema.insert(
+            TableName::new("users"),
+            RowDescriptor::new(vec![
+                ColumnDescriptor::new("name", ColumnType::Text),
+                ColumnDescriptor::new("score", ColumnType::Integer),
+                ColumnDescriptor::new("joined_at", ColumnType::Timestamp),
+            ])
+            .into(),
+        );
+        schema.insert(
+            TableName::new("posts"),
+            RowDescriptor::new(vec![
+                ColumnDescriptor::new("author_id", ColumnType::Uuid),
+                ColumnDescriptor::new("title", ColumnType::Text),
+                ColumnDescriptor::new(
+                    "tags",
+                    ColumnType::Array {
+                        element: Box::new(ColumnType::Text),
+                    },
+                ),
+            ])
+            .into(),
+        );
+        schema
+    }
+
+    fn scan(table: &str) -> Box<RelExpr> {
+        Box::new(RelExpr::TableScan {
+            table: TableName::new(table),
+        })
+    }
+
+    fn cmp(column: ColumnRef, op: PredicateCmpOp, value: Value) -> PredicateExpr {
+        PredicateExpr::Cmp {
+            left: column,
+            op,
+            right: ValueRef::Literal(value),
+        }
+    }
+
+    fn error(sql: &str) -> String {
+        sql_to_relation_ir(sql, &schema())
+            .expect_err("query should be rejected")
+            .message
+    }
+
+    #[test]
+    fn lowers_filter_order_and_pagination() {
+        let relation = sql_to_relation_ir(
+            "SELECT * FROM users WHERE score >= 10 AND name <> 'bob' ORDER BY score DESC LIMIT 5 OFFSET 10;",
+            &schema(),
+        )
+        .expect("lower select");
+
+        assert_eq!(
+            relation,
+            RelExpr::Limit {
+                input: Box::new(RelExpr::Offset {
+                    input: Box::new(RelExpr::OrderBy {
+                        input: Box::new(RelExpr::Filter {
+                            input: scan("users"),
+                            predicate: PredicateExpr::And(vec![
+                                cmp(
+                                    ColumnRef::unscoped("score"),
+                                    PredicateCmpOp::Ge,
+                                    Value::Integer(10)
+                                ),
+                                cmp(
+                                    ColumnRef::unscoped("name"),
+                                    PredicateCmpOp::Ne,
+                                    Value::Text("bob".to_string())
+                                ),
+                            ]),
+                        }),
+                        terms: vec![OrderByExpr {
+                            column: ColumnRef::unscoped("score"),
+                            direction: OrderDirection::Desc,
+                            expr: None,
+                        }],
+                    }),
+                    offset: 10,
+                }),
+                limit: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn pushes_not_down_and_types_literals() {
+        let relation = sql_to_relation_ir(
+            "select * from users where not (score between 1 and 5 or name in ('a', 'b')) and joined_at > 1000",
+            &schema(),
+        )
+        .expect("lower select");
+
+        let RelExpr::Filter { predicate, .. } = relation else {
+            panic!("expected filter, got {relation:?}");
+        };
+        assert_eq!(
+            predicate,
+            PredicateExpr::And(vec![
+                PredicateExpr::And(vec![
+                    PredicateExpr::Or(vec![
+                        cmp(
+                            ColumnRef::unscoped("score"),
+                            PredicateCmpOp::Lt,
+                            Value::Integer(1)
+                        ),
+                        cmp(
+                            ColumnRef::unscoped("score"),
+                            PredicateCmpOp::Gt,
+                            Value::Integer(5)
+                        ),
+                    ]),
+                    PredicateExpr::And(vec![
+                        cmp(
+                            ColumnRef::unscoped("name"),
+                            PredicateCmpOp::Ne,
+                            Value::Text("a".to_string())
+                        ),
+                        cmp(
+                            ColumnRef::unscoped("name"),
+                            PredicateCmpOp::Ne,
+                            Value::Text("b".to_string())
+                        ),
+                    ]),
+                ]),
+                cmp(
+                    ColumnRef::unscoped("joined_at"),
+                    PredicateCmpOp::Gt,
+                    Value::Timestamp(1000)
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn lowers_joins_with_scoped_projection_and_session_refs() {
+        let relation = sql_to_relation_ir(
+            "SELECT p.* FROM users u JOIN posts p ON p.author_id = u.id \
+             WHERE u.id = $session.user_id AND 'rust' = ANY(p.tags)",
+            &schema(),
+        )
+        .expect("lower join");
+
+        assert_eq!(
+            relation,
+            RelExpr::Project {
+                input: Box::new(RelExpr::Filter {
+                    input: Box::new(RelExpr::Join {
+                        left: scan("users"),
+                        right: scan("posts"),
+                        on: vec![JoinCondition {
+                            left: ColumnRef::scoped("u", "id"),
+                            right: ColumnRef::scoped("p", "author_id"),
+                        }],
+                        join_kind: JoinKind::Inner,
+                    }),
+                    predicate: PredicateExpr::And(vec![
+                        PredicateExpr::Cmp {
+                            left: ColumnRef::scoped("u", "id"),
+                            op: PredicateCmpOp::Eq,
+                            right: ValueRef::SessionRef(vec!["user_id".to_string()]),
+                        },
+                        PredicateExpr::Contains {
+                            left: ColumnRef::scoped("p", "tags"),
+                            right: ValueRef::Literal(Value::Text("rust".to_string())),
+                        },
+                    ]),
+                }),
+                columns: vec![ProjectColumn {
+                    alias: "id".to_string(),
+                    expr: ProjectExpr::Column(ColumnRef::scoped("p", "id")),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_sql_query_binds_session_values() {
+        let user_id = ObjectId::new();
+        let session = Session::new(user_id.to_string());
+        let query = parse_sql_query(
+            "SELECT title FROM posts WHERE author_id = $session.user_id",
+            &schema(),
+            Some(&session),
+        )
+        .expect("parse query");
+
+        assert_eq!(query.table, TableName::new("posts"));
+        assert_eq!(
+            query.relation_ir,
+            RelExpr::Project {
+                input: Box::new(RelExpr::Filter {
+                    input: scan("posts"),
+                    predicate: cmp(
+                        ColumnRef::unscoped("author_id"),
+                        PredicateCmpOp::Eq,
+                        Value::Uuid(user_id)
+                    ),
+                }),
+                columns: vec![ProjectColumn {
+                    alias: "title".to_string(),
+                    expr: ProjectExpr::Column(ColumnRef::unscoped("title")),
+                }],
+            }
+        );
+
+        let error = parse_sql_query(
+            "SELECT * FROM posts WHERE author_id = $session.user_id",
+            &schema(),
+            None,
+        )
+        .expect_err("session parameter without a session");
+        assert!(error.message.contains("needs a session"), "{error}");
+    }
+
+    #[test]
+    fn rejects_unsupported_sql_with_guidance() {
+        assert!(error("SELECT COUNT(*) FROM users").contains("aggregate"));
+        assert!(
+            error("SELECT * FROM users u LEFT JOIN posts p ON p.author_id = u.id")
+                .contains("INNER JOIN")
+        );
+        assert!(error("SELECT * FROM users WHERE name LIKE 'a%'").contains("LIKE"));
+        assert!(error("SELECT * FROM users WHERE score = NULL").contains("IS NULL"));
+        assert!(
+            error("SELECT * FROM users WHERE id IN (SELECT author_id FROM posts)").contains("JOIN")
+        );
+        assert!(error("SELECT * FROM users WHERE nickname = 'x'").contains("unknown column"));
+        assert!(error("SELECT * FROM users WHERE score = 'x'").contains("cannot be compared"));
+        assert!(error("SELECT * FROM posts WHERE author_id = 'nope'").contains("UUID"));
+        assert!(error("SELECT * FROM users WHERE name = $user").contains("$session"));
+
+        let located = sql_to_relation_ir("SELECT * FROM users GROUP BY name", &schema())
+            .expect_err("group by");
+        assert_eq!(located.position, Some(20));
+    }
+
+    #[test]
+    fn lowers_recursive_cte_into_gather() {
+        let mut schema = schema();
+        schema.insert(
+            TableName::new("teams"),
+            RowDescriptor::new(vec![ColumnDescriptor::new("name", ColumnType::Text)]).into(),
+        );
+        schema.insert(
+            TableName::new("team_edges"),
+            RowDescriptor::new(vec![
+                ColumnDescriptor::new("child_team", ColumnType::Uuid),
+                ColumnDescriptor::new("parent_team", ColumnType::Uuid),
+            ])
+            .into(),
+        );
+
+        let relation = sql_to_relation_ir(
+            "WITH RECURSIVE reachable AS (
+                SELECT * FROM teams WHERE name = 'core'
+                UNION
+                SELECT teams.* FROM team_edges
+                  JOIN reachable ON team_edges.child_team = reachable.id
+                  JOIN teams ON teams.id = team_edges.parent_team
+             )
+             SELECT name FROM reachable ORDER BY name",
+            &schema,
+        )
+        .expect("lower recursive query");
+
+        let expected_step = RelExpr::Project {
+            input: Box::new(RelExpr::Join {
+                left: Box::new(RelExpr::Filter {
+                    input: scan("team_edges"),
+                    predicate: PredicateExpr::Cmp {
+                        left: ColumnRef::scoped("team_edges", "child_team"),
+                        op: PredicateCmpOp::Eq,
+                        right: ValueRef::RowId(RowIdRef::Frontier),
+                    },
+                }),
+                right: scan("teams"),
+                on: vec![JoinCondition {
+                    left: ColumnRef::scoped("team_edges", "parent_team"),
+                    right: ColumnRef::scoped(RECURSIVE_HOP_SCOPE, "id"),
+                }],
+                join_kind: JoinKind::Inner,
+            }),
+            columns: vec![ProjectColumn {
+                alias: "id".to_string(),
+                expr: ProjectExpr::Column(ColumnRef::scoped(RECURSIVE_HOP_SCOPE, "id")),
+            }],
+        };
+        assert_eq!(
+            relation,
+            RelExpr::OrderBy {
+                input: Box::new(RelExpr::Project {
+                    input: Box::new(RelExpr::Gather {
+                        seed: Box::new(RelExpr::Filter {
+                            input: scan("teams"),
+                            predicate: cmp(
+                                ColumnRef::unscoped("name"),
+                                PredicateCmpOp::Eq,
+                                Value::Text("core".to_string())
+                            ),
+                        }),
+                        step: Box::new(expected_step),
+                        frontier_key: KeyRef::RowId(RowIdRef::Current),
+                        max_depth: RELATION_GATHER_MAX_DEPTH_DEFAULT,
+                        dedupe_key: vec![KeyRef::RowId(RowIdRef::Current)],
+                    }),
+                    columns: vec![ProjectColumn {
+                        alias: "name".to_string(),
+                        expr: ProjectExpr::Column(ColumnRef::unscoped("name")),
+                    }],
+                }),
+                terms: vec![OrderByExpr {
+                    column: ColumnRef::unscoped("name"),
+                    direction: OrderDirection::Asc,
+                    expr: None,
+                }],
+            }
+        );
+
+        let query = parse_sql_query(
+            "WITH RECURSIVE reachable AS (
+                SELECT * FROM teams WHERE name = 'core'
+                UNION ALL
+                SELECT teams.* FROM team_edges
+                  JOIN reachable ON team_edges.child_team = reachable.id
+                  JOIN teams ON teams.id = team_edges.parent_team
+             )
+             SELECT name FROM reachable",
+            &schema,
+            None,
+        )
+        .expect("parse recursive query");
+        assert_eq!(query.select_columns, Some(vec!["name".to_string()]));
+        assert!(matches!(query.relation_ir, RelExpr::Gather { .. }));
+    }
+}
@@ -11,16 +11,20 @@ use crate::sync_manager::{OutgoingQuerySubscription, QueryPropagation};
 
 #[cfg(test)]
 use super::encoding::decode_row;
+use super::graph::QueryGraph;
+use super::graph::explain::QueryExplain;
 use super::graph_nodes::output::QuerySubscriptionId;
 use super::manager::{
     CatalogueUpdate, LocalUpdates, QueryError, QueryManager, QuerySubscription,
-    QuerySubscriptionFailure, QueryUpdate,
+    QuerySubscriptionFailure, QueryUpdate, SchemaWarningAccumulator,
 };
 use super::query::{Query, QueryBuilder};
+use super::relation_ir::canonicalize_rel_expr;
 use super::session::Session;
+use super::sql::{SqlError, parse_sql_query};
 #[cfg(test)]
 use super::types::Value;
-use super::types::{ComposedBranchName, Schema, SchemaHash};
+use super::types::{ComposedBranchName, LoadedRow, RowPolicyMode, Schema, SchemaHash, TableName};
 
 type ReplayableQuerySubscription = (
     QueryId,
@@ -58,6 +62,105 @@ impl QueryManager {
         QueryBuilder::new(table)
     }
 
+    /// Parse a SQL `SELECT` against the current schema.
+    ///
+    /// `$session.*` parameters are bound from `session`. See [`super::sql`] for
+    /// the supported dialect.
+    pub fn sql_query(&self, sql: &str, session: Option<&Session>) -> Result<Query, SqlError> {
+        parse_sql_query(sql, &self.schema_context().current_schema, session)
+    }
+
+    /// Describe how a query compiles for `session`: the canonical relation IR,
+    /// the graph nodes, and the index and condition behind each scan.
+    ///
+    /// With `analyze`, the graph is also settled once against committed rows
+    /// and every node reports the rows it scanned and emitted.
+    pub fn explain<H: Storage>(
+        &self,
+        storage: &H,
+        query: &Query,
+        session: Option<Session>,
+        analyze: bool,
+    ) -> Result<QueryExplain, QueryError> {
+        let branches = self.local_query_branches(query)?;
+        let mut graph = self.compile_local_query_graph(query, session)?;
+        let storage_ref: &dyn Storage = storage;
+
+        if analyze {
+            let table = graph.table.as_str().to_string();
+            let mut schema_warnings = SchemaWarningAccumulator::default();
+            let row_loader = |id: ObjectId, table_hint: Option<TableName>| -> Option<LoadedRow> {
+                Self::load_visible_row_for_query(
+                    storage_ref,
+                    id,
+                    table_hint.as_ref().map(TableName::as_str),
+                    &branches,
+                    None,
+                    None,
+                    false,
+                    false,
+                    query.include_deleted,
+                    &self.schema_context,
+                    &self.branch_schema_map,
+                    &table,
+                    QuerySubscriptionId(self.next_subscription_id),
+                    &mut schema_warnings,
+                )
+            };
+            let _delta = graph.settle(storage_ref, row_loader);
+        }
+
+        let relation_ir = canonicalize_rel_expr(query.relation_ir.clone())
+            .unwrap_or_else(|_| query.relation_ir.clone());
+        Ok(QueryExplain::new(
+            relation_ir,
+            branches,
+            &graph,
+            storage_ref,
+            analyze,
+        ))
+    }
+
+    /// Branches a local query reads: its own, or every live schema branch.
+    fn local_query_branches(&self, query: &Query) -> Result<Vec<String>, QueryError> {
+        if !query.branches.is_empty() {
+            Ok(query.branches.clone())
+        } else if self.schema_context.is_initialized() {
+            Ok(self
+                .schema_context
+                .all_branch_names()
+                .into_iter()
+                .map(|b| b.as_str().to_string())
+                .collect())
+        } else {
+            Err(QueryError::QueryCompilationError(
+                "schema context not initialized - call set_current_schema() first".into(),
+            ))
+        }
+    }
+
+    fn compile_local_query_graph(
+        &self,
+        query: &Query,
+        session: Option<Session>,
+    ) -> Result<QueryGraph, QueryError> {
+        let compile_schema = self.local_subscription_compile_schema(session.as_ref());
+        let compile_row_policy_mode =
+            if self.local_subscription_uses_explicit_authorization(session.as_ref()) {
+                RowPolicyMode::PermissiveLocal
+            } else {
+                self.row_policy_mode
+            };
+        Self::compile_graph(
+            query,
+            &compile_schema,
+            session,
+            &self.schema_context,
+            compile_row_policy_mode,
+        )
+        .map_err(|err| QueryError::QueryCompilationError(err.to_string()))
+    }
+
     /// Subscribe to query results (delta mode).
     pub fn subscribe(&mut self, query: Query) -> Result<QuerySubscriptionId, QueryError> {
         self.subscribe_with_session(query, None, None)
@@ -122,37 +225,10 @@ impl QueryManager {
         } = options;
         let _span =
             tracing::debug_span!("QM::subscribe", table = %query.table, ?durability_tier).entered();
-        // Determine branches
-        let branches: Vec<String> = if !query.branches.is_empty() {
-            query.branches.clone()
-        } else if self.schema_context.is_initialized() {
-            self.schema_context
-                .all_branch_names()
-                .into_iter()
-                .map(|b| b.as_str().to_string())
-                .collect()
-        } else {
-            return Err(QueryError::QueryCompilationError(
-                "schema context not initialized - call set_current_schema() first".into(),
-            ));
-        };
-
+        let branches = self.local_query_branches(&query)?;
         let uses_explicit_authorization_filtering =
             self.local_subscription_uses_explicit_authorization(session.as_ref());
-        let compile_schema = self.local_subscription_compile_schema(session.as_ref());
-        let compile_row_policy_mode = if uses_explicit_authorization_filtering {
-            crate::query_manager::types::RowPolicyMode::PermissiveLocal
-        } else {
-            self.row_policy_mode
-        };
-        let graph = Self::compile_graph(
-            &query,
-            &compile_schema,
-            session.clone(),
-            &self.schema_context,
-            compile_row_policy_mode,
-        )
-        .map_err(|err| QueryError::QueryCompilationError(err.to_string()))?;
+        let graph = self.compile_local_query_graph(&query, session.clone())?;
         let policy_context_tables = Self::policy_context_tables_for_graph(&graph);
 
         let id = QuerySubscriptionId(self.next_subscription_id);
@@ -101,6 +101,26 @@ impl SchemaHash {
                     hasher.update(&[0]);
                 }
             }
+
+            if !table_schema.limits.is_unbounded() {
+                // Like indexed columns, unbounded tables hash exactly like
+                // schemas that predate declared limits.
+                hasher.update(&[2]);
+                hash_optional_u64(
+                    &mut hasher,
+                    table_schema.limits.max_row_bytes.map(u64::from),
+                );
+                hash_optional_u64(&mut hasher, table_schema.limits.max_rows_per_user);
+            }
+
+            if table_schema.visibility.is_internal() {
+                hasher.update(&[3]);
+            }
+
+            if table_schema.annotations.has_hashed() {
+                hasher.update(&[4]);
+                hash_annotations(&mut hasher, &table_schema.annotations);
+            }
         }
 
         Self(*hasher.finalize().as_bytes())
@@ -180,9 +200,48 @@ fn hash_column_descriptor(hasher: &mut blake3::Hasher, col: &ColumnDescriptor) {
     } else {
         hasher.update(&[0]);
     }
+
+    if !col.limits.is_unbounded() {
+        hasher.update(&[1]);
+        hash_optional_u64(hasher, col.limits.max_bytes.map(u64::from));
+        hash_optional_u64(hasher, col.limits.max_items.map(u64::from));
+    }
+
+    if col.visibility.is_internal() {
+        // Public columns hash like schemas that predate visibility.
+        hasher.update(&[2]);
+    }
+
+    // Unhashed annotations can change without minting a new schema version.
+    if col.annotations.has_hashed() {
+        hasher.update(&[3]);
+        hash_annotations(hasher, &col.annotations);
+    }
     hasher.update(&[0]); // delimiter
 }
 
+fn hash_annotations(hasher: &mut blake3::Hasher, annotations: &Annotations) {
+    for (key, value) in annotations.hashed() {
+        hasher.update(&(key.len() as u32).to_le_bytes());
+        hasher.update(key.as_bytes());
+        hasher.update(&(value.len() as u32).to_le_bytes());
+        hasher.update(value.as_bytes());
+    }
+    hasher.update(&u32::MAX.to_le_bytes());
+}
+
+fn hash_optional_u64(hasher: &mut blake3::Hasher, value: Option<u64>) {
+    match value {
+        Some(value) => {
+            hasher.update(&[1]);
+            hasher.update(&value.to_le_bytes());
+        }
+        None => {
+            hasher.update(&[0]);
+        }
+    }
+}
+
 fn hash_value(hasher: &mut blake3::Hasher, value: &Value) {
     match value {
         Value::Integer(v) => {
@@ -197,6 +256,12 @@ fn hash_value(hasher: &mut blake3::Hasher, value: &Value) {
             hasher.update(&[10]);
             hasher.update(&v.to_le_bytes());
         }
+        Value::Decimal(v) => {
+            let normalized = v.normalized();
+            hasher.update(&[13]);
+            hasher.update(&normalized.units().to_le_bytes());
+            hasher.update(&[normalized.scale()]);
+        }
         Value::Boolean(v) => {
             hasher.update(&[3, *v as u8]);
         }
@@ -209,6 +274,32 @@ fn hash_value(hasher: &mut blake3::Hasher, value: &Value) {
             hasher.update(&[5]);
             hasher.update(&v.to_le_bytes());
         }
+        Value::Date(v) => {
+            hasher.update(&[14]);
+            hasher.update(&v.days_since_epoch().to_le_bytes());
+        }
+        Value::Time(v) => {
+            hasher.update(&[15]);
+            hasher.update(&v.micros_since_midnight().to_le_bytes());
+        }
+        Value::Interval(v) => {
+            hasher.update(&[16]);
+            hasher.update(&v.months().to_le_bytes());
+            hasher.update(&v.days().to_le_bytes());
+            hasher.update(&v.micros().to_le_bytes());
+        }
+        Value::GeoPoint(v) => {
+            hasher.update(&[17]);
+            hasher.update(&v.lat().to_le_bytes());
+            hasher.update(&v.lon().to_le_bytes());
+        }
+        Value::Vector(v) => {
+            hasher.update(&[18]);
+            hasher.update(&(v.dims() as u32).to_le_bytes());
+            for component in v.as_slice() {
+                hasher.update(&component.to_le_bytes());
+            }
+        }
         Value::Uuid(v) => {
             hasher.update(&[6]);
             hasher.update(v.uuid().as_bytes());
@@ -254,6 +345,9 @@ fn hash_column_type(hasher: &mut blake3::Hasher, col_type: &ColumnType) {
         ColumnType::Double => {
             hasher.update(&[10]);
         }
+        ColumnType::Decimal { precision, scale } => {
+            hasher.update(&[13, *precision, *scale]);
+        }
         ColumnType::Boolean => {
             hasher.update(&[3]);
         }
@@ -275,6 +369,22 @@ fn hash_column_type(hasher: &mut blake3::Hasher, col_type: &ColumnType) {
         ColumnType::Timestamp => {
             hasher.update(&[5]);
         }
+        ColumnType::Date => {
+            hasher.update(&[14]);
+        }
+        ColumnType::Time => {
+            hasher.update(&[15]);
+        }
+        ColumnType::Interval => {
+            hasher.update(&[16]);
+        }
+        ColumnType::GeoPoint => {
+            hasher.update(&[17]);
+        }
+        ColumnType::Vector { dims } => {
+            hasher.update(&[18]);
+            hasher.update(&dims.to_le_bytes());
+        }
         ColumnType::Uuid => {
             hasher.update(&[6]);
         }
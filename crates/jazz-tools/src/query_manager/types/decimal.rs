@@ -0,0 +1,465 @@
+//! Exact fixed-point numbers for `DECIMAL(precision, scale)` columns.
+//!
+//! A [`Decimal`] is an `i128` count of `10^-scale` units, so `12.30` is 1230
+//! units at scale 2. Values carry at most [`DECIMAL_MAX_PRECISION`] digits.
+//! Addition, subtraction, multiplication and remainder are exact and fail
+//! instead of rounding; division truncates toward zero at a chosen scale,
+//! like integer division.
+//!
+//! Equality, ordering and hashing are numeric: `1.5`, `1.50` and `1.500` are
+//! the same value. Display keeps the scale the value was written with.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Most significant digits a decimal can hold (the width of `i128`).
+pub const DECIMAL_MAX_PRECISION: u8 = 38;
+
+const MAX_UNITS: i128 = 10i128.pow(DECIMAL_MAX_PRECISION as u32) - 1;
+
+fn pow10(exponent: u32) -> Option<i128> {
+    10i128.checked_pow(exponent)
+}
+
+fn within_precision(units: i128) -> Option<i128> {
+    (units.abs() <= MAX_UNITS).then_some(units)
+}
+
+/// Why a decimal literal could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecimalError {
+    /// Not a decimal literal, e.g. `"1.2.3"` or `"abc"`.
+    Invalid(String),
+    /// More than [`DECIMAL_MAX_PRECISION`] significant or fractional digits.
+    Overflow(String),
+}
+
+impl fmt::Display for DecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecimalError::Invalid(raw) => write!(f, "invalid decimal literal '{raw}'"),
+            DecimalError::Overflow(raw) => write!(
+                f,
+                "decimal literal '{raw}' has more than {DECIMAL_MAX_PRECISION} digits"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecimalError {}
+
+/// An exact decimal number: `units * 10^-scale`.
+#[derive(Debug, Clone, Copy)]
+pub struct Decimal {
+    units: i128,
+    scale: u8,
+}
+
+impl Decimal {
+    pub const ZERO: Decimal = Decimal { units: 0, scale: 0 };
+
+    /// `units * 10^-scale`, or `None` past [`DECIMAL_MAX_PRECISION`].
+    pub fn new(units: i128, scale: u8) -> Option<Self> {
+        if scale > DECIMAL_MAX_PRECISION {
+            return None;
+        }
+        within_precision(units).map(|units| Self { units, scale })
+    }
+
+    /// Parse plain notation such as `-12.30`, `+7` or `.5` (no exponents).
+    pub fn parse(raw: &str) -> Result<Self, DecimalError> {
+        let invalid = || DecimalError::Invalid(raw.to_string());
+        let overflow = || DecimalError::Overflow(raw.to_string());
+
+        let trimmed = raw.trim();
+        let (negative, digits) = match trimmed.as_bytes().first() {
+            Some(b'-') => (true, &trimmed[1..]),
+            Some(b'+') => (false, &trimmed[1..]),
+            _ => (false, trimmed),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return Err(invalid());
+        }
+        if !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let scale = u8::try_from(fraction.len())
+            .ok()
+            .filter(|scale| *scale <= DECIMAL_MAX_PRECISION)
+            .ok_or_else(overflow)?;
+        let mut units: i128 = 0;
+        for digit in whole.bytes().chain(fraction.bytes()) {
+            units = units
+                .checked_mul(10)
+                .and_then(|units| units.checked_add(i128::from(digit - b'0')))
+                .and_then(within_precision)
+                .ok_or_else(overflow)?;
+        }
+        if negative {
+            units = -units;
+        }
+        Ok(Self { units, scale })
+    }
+
+    /// Unscaled integer value.
+    pub fn units(&self) -> i128 {
+        self.units
+    }
+
+    /// Digits after the decimal point.
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.units == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.units < 0
+    }
+
+    /// Significant digits in the unscaled value (at least 1).
+    pub fn digits(&self) -> u8 {
+        let mut remaining = self.units.unsigned_abs();
+        let mut digits = 1;
+        while remaining >= 10 {
+            remaining /= 10;
+            digits += 1;
+        }
+        digits
+    }
+
+    /// The same value at `scale`, or `None` if that would drop non-zero
+    /// digits or exceed the precision limit.
+    pub fn rescale(&self, scale: u8) -> Option<Self> {
+        match scale.cmp(&self.scale) {
+            Ordering::Equal => Some(*self),
+            Ordering::Greater => {
+                let factor = pow10(u32::from(scale - self.scale))?;
+                Self::new(self.units.checked_mul(factor)?, scale)
+            }
+            Ordering::Less => {
+                let factor = pow10(u32::from(self.scale - scale))?;
+                (self.units % factor == 0).then(|| Self {
+                    units: self.units / factor,
+                    scale,
+                })
+            }
+        }
+    }
+
+    /// Whether the value is representable in a `DECIMAL(precision, scale)` column.
+    pub fn fits(&self, precision: u8, scale: u8) -> bool {
+        self.rescale(scale)
+            .is_some_and(|rescaled| rescaled.digits() <= precision)
+    }
+
+    /// Drop trailing fractional zeros: `1.500` becomes `1.5`.
+    pub fn normalized(&self) -> Self {
+        let mut normalized = *self;
+        while normalized.scale > 0 && normalized.units % 10 == 0 {
+            normalized.units /= 10;
+            normalized.scale -= 1;
+        }
+        normalized
+    }
+
+    /// Both operands' units at their common (larger) scale.
+    fn aligned(&self, other: &Self) -> Option<(i128, i128, u8)> {
+        let scale = self.scale.max(other.scale);
+        Some((
+            self.rescale(scale)?.units,
+            other.rescale(scale)?.units,
+            scale,
+        ))
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let (left, right, scale) = self.aligned(other)?;
+        Self::new(left.checked_add(right)?, scale)
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let (left, right, scale) = self.aligned(other)?;
+        Self::new(left.checked_sub(right)?, scale)
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        Self::new(self.units.checked_neg()?, self.scale)
+    }
+
+    /// Exact product; its scale is the sum of the operands' scales.
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let units = self.units.checked_mul(other.units)?;
+        let scale = self.scale.checked_add(other.scale)?;
+        if scale <= DECIMAL_MAX_PRECISION {
+            return Self::new(units, scale);
+        }
+        // Only trailing zeros may be dropped to get back within the scale limit.
+        let factor = pow10(u32::from(scale - DECIMAL_MAX_PRECISION))?;
+        (units % factor == 0)
+            .then(|| units / factor)
+            .and_then(|units| Self::new(units, DECIMAL_MAX_PRECISION))
+    }
+
+    /// Quotient at `scale`, truncated toward zero. `None` on division by zero.
+    pub fn checked_div(&self, other: &Self, scale: u8) -> Option<Self> {
+        if other.units == 0 || scale > DECIMAL_MAX_PRECISION {
+            return None;
+        }
+        // units = self.units * 10^(other.scale + scale - self.scale) / other.units
+        let exponent = i32::from(other.scale) + i32::from(scale) - i32::from(self.scale);
+        let (numerator, denominator) = if exponent >= 0 {
+            (
+                self.units.checked_mul(pow10(exponent as u32)?)?,
+                other.units,
+            )
+        } else {
+            (
+                self.units,
+                other.units.checked_mul(pow10(exponent.unsigned_abs())?)?,
+            )
+        };
+        Self::new(numerator / denominator, scale)
+    }
+
+    /// Remainder with the sign of the dividend, as for integers.
+    pub fn checked_rem(&self, other: &Self) -> Option<Self> {
+        let (left, right, scale) = self.aligned(other)?;
+        Self::new(left.checked_rem(right)?, scale)
+    }
+
+    /// Nearest `f64`; may lose precision.
+    pub fn to_f64(&self) -> f64 {
+        self.units as f64 / 10f64.powi(i32::from(self.scale))
+    }
+
+    /// Integral part and fractional part scaled to 38 digits. Comparing these
+    /// pairs lexicographically orders decimals without overflow.
+    fn ordering_key(&self) -> (i128, i128) {
+        let factor = 10i128.pow(u32::from(self.scale));
+        let fraction = self.units % factor;
+        let fraction_scale = 10i128.pow(u32::from(DECIMAL_MAX_PRECISION - self.scale));
+        (self.units / factor, fraction * fraction_scale)
+    }
+}
+
+impl From<i32> for Decimal {
+    fn from(value: i32) -> Self {
+        Self {
+            units: i128::from(value),
+            scale: 0,
+        }
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Self {
+            units: i128::from(value),
+            scale: 0,
+        }
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = DecimalError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::parse(raw)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ordering_key().cmp(&other.ordering_key())
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalized();
+        normalized.units.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.units < 0 { "-" } else { "" };
+        let digits = self.units.unsigned_abs().to_string();
+        let scale = usize::from(self.scale);
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+        let padded = format!("{digits:0>width$}", width = scale + 1);
+        let (whole, fraction) = padded.split_at(padded.len() - scale);
+        write!(f, "{sign}{whole}.{fraction}")
+    }
+}
+
+/// JSON and other human-readable formats carry decimals as strings so no
+/// digits are lost in transit; binary formats carry `(units, scale)`.
+impl Serialize for Decimal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (self.units, self.scale).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            let (units, scale) = <(i128, u8)>::deserialize(deserializer)?;
+            return Decimal::new(units, scale)
+                .ok_or_else(|| de::Error::custom("decimal out of range"));
+        }
+
+        // JS callers may hand over plain numbers; accept those too.
+        struct DecimalVisitor;
+
+        impl Visitor<'_> for DecimalVisitor {
+            type Value = Decimal;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a decimal string or number")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Decimal::parse(value).map_err(E::custom)
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(Decimal::from(value))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Decimal::new(i128::from(value), 0).ok_or_else(|| E::custom("decimal out of range"))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                if !value.is_finite() {
+                    return Err(E::custom("decimal must be finite"));
+                }
+                // `f64` Display prints the shortest round-tripping digits.
+                Decimal::parse(&value.to_string()).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(raw: &str) -> Decimal {
+        Decimal::parse(raw).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_keep_the_written_scale() {
+        assert_eq!(dec("12.30").to_string(), "12.30");
+        assert_eq!(dec("-0.05").to_string(), "-0.05");
+        assert_eq!(dec("+7").to_string(), "7");
+        assert_eq!(dec(".5").to_string(), "0.5");
+        assert_eq!(dec("12.30").units(), 1230);
+        assert!(Decimal::parse("1.2.3").is_err());
+        assert!(Decimal::parse("-").is_err());
+        assert!(matches!(
+            Decimal::parse(&"9".repeat(39)),
+            Err(DecimalError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn equality_and_ordering_are_numeric() {
+        assert_eq!(dec("1.5"), dec("1.500"));
+        assert!(dec("-1.5") < dec("-1.2"));
+        assert!(dec("-0.5") < dec("0.2"));
+        assert!(dec("0.1") < dec("0.10000000000000000000000000000000000001"));
+        assert!(dec("99999999999999999999999999999999999999") > dec("0.5"));
+    }
+
+    #[test]
+    fn arithmetic_is_exact() {
+        assert_eq!(dec("0.1").checked_add(&dec("0.2")).unwrap(), dec("0.3"));
+        assert_eq!(dec("1.25").checked_sub(&dec("2")).unwrap(), dec("-0.75"));
+        assert_eq!(
+            dec("1.5").checked_mul(&dec("1.5")).unwrap().to_string(),
+            "2.25"
+        );
+        assert_eq!(
+            dec("10").checked_div(&dec("3"), 2).unwrap().to_string(),
+            "3.33"
+        );
+        assert_eq!(dec("7.5").checked_rem(&dec("2")).unwrap(), dec("1.5"));
+        assert_eq!(dec("1").checked_div(&dec("0"), 2), None);
+        let max = dec(&"9".repeat(38));
+        assert_eq!(max.checked_add(&dec("1")), None);
+    }
+
+    #[test]
+    fn fits_checks_scale_and_precision() {
+        assert!(dec("123.4").fits(5, 2));
+        assert!(!dec("1234.5").fits(5, 2));
+        assert!(!dec("1.005").fits(10, 2));
+        assert_eq!(dec("1.5").rescale(3).unwrap().to_string(), "1.500");
+    }
+
+    #[test]
+    fn json_uses_strings_and_accepts_numbers() {
+        assert_eq!(serde_json::to_string(&dec("12.30")).unwrap(), "\"12.30\"");
+        let parsed: Decimal = serde_json::from_str("\"-4.20\"").unwrap();
+        assert_eq!(parsed.to_string(), "-4.20");
+        let parsed: Decimal = serde_json::from_str("0.1").unwrap();
+        assert_eq!(parsed.to_string(), "0.1");
+        let parsed: Decimal = serde_json::from_str("42").unwrap();
+        assert_eq!(parsed, dec("42"));
+    }
+}
@@ -0,0 +1,574 @@
+//! Geographic points for `GEOPOINT` columns and the index cells that cover them.
+//!
+//! A [`GeoPoint`] is a WGS 84 latitude/longitude pair in degrees. Index keys
+//! use a 64-bit Z-order (Morton) cell: latitude and longitude are each
+//! quantized to 32 bits and their bits interleaved, so nearby points usually
+//! share a long key prefix. Box and radius queries scan the cell ranges from
+//! [`GeoRegion::covering_ranges`] and then check every candidate exactly with
+//! [`GeoRegion::contains`].
+//!
+//! Distances use the haversine formula on a sphere of radius
+//! [`EARTH_RADIUS_METERS`], which stays within about 0.5% of the ellipsoid.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Mean Earth radius (IUGG) used for distance calculations.
+pub const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+/// Upper bound on the cells used to cover one box before merging ranges.
+const MAX_COVERING_CELLS: usize = 16;
+
+/// Deepest cell level: 32 bits per axis.
+const MAX_CELL_LEVEL: u32 = 32;
+
+/// Slack (in degrees) added around query boxes so points on an edge are never
+/// lost to floating-point rounding during quantization.
+const COVERING_EPSILON_DEGREES: f64 = 1e-9;
+
+const AXIS_STEPS: f64 = 4_294_967_296.0; // 2^32
+
+/// Why a geographic literal was rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoError {
+    /// Latitude outside `[-90, 90]` or not finite.
+    InvalidLatitude(f64),
+    /// Longitude outside `[-180, 180]` or not finite.
+    InvalidLongitude(f64),
+    /// Radius negative or not finite.
+    InvalidRadius(f64),
+    /// Box whose southern edge lies north of its northern edge.
+    InvertedBox { south: f64, north: f64 },
+    /// Text that is not a `lat,lon` pair.
+    Invalid(String),
+}
+
+impl fmt::Display for GeoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeoError::InvalidLatitude(lat) => {
+                write!(f, "latitude {lat} is outside [-90, 90]")
+            }
+            GeoError::InvalidLongitude(lon) => {
+                write!(f, "longitude {lon} is outside [-180, 180]")
+            }
+            GeoError::InvalidRadius(radius) => {
+                write!(f, "radius {radius} must be a non-negative number of meters")
+            }
+            GeoError::InvertedBox { south, north } => {
+                write!(
+                    f,
+                    "box south edge {south} lies north of its north edge {north}"
+                )
+            }
+            GeoError::Invalid(raw) => write!(f, "invalid geo point '{raw}', expected 'lat,lon'"),
+        }
+    }
+}
+
+impl std::error::Error for GeoError {}
+
+/// A latitude/longitude pair in degrees.
+///
+/// Equality is bitwise on the coordinates; `-0.0` is stored as `0.0`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "RawGeoPoint")]
+pub struct GeoPoint {
+    lat: f64,
+    lon: f64,
+}
+
+#[derive(Deserialize)]
+struct RawGeoPoint {
+    lat: f64,
+    lon: f64,
+}
+
+impl TryFrom<RawGeoPoint> for GeoPoint {
+    type Error = GeoError;
+
+    fn try_from(raw: RawGeoPoint) -> Result<Self, Self::Error> {
+        GeoPoint::new(raw.lat, raw.lon)
+    }
+}
+
+impl GeoPoint {
+    /// Build a point, rejecting coordinates outside the valid ranges.
+    pub fn new(lat: f64, lon: f64) -> Result<Self, GeoError> {
+        if !lat.is_finite() || !(-90.0..=90.0).contains(&lat) {
+            return Err(GeoError::InvalidLatitude(lat));
+        }
+        if !lon.is_finite() || !(-180.0..=180.0).contains(&lon) {
+            return Err(GeoError::InvalidLongitude(lon));
+        }
+        // Adding 0.0 turns -0.0 into 0.0 so equal points compare equal.
+        Ok(Self {
+            lat: lat + 0.0,
+            lon: lon + 0.0,
+        })
+    }
+
+    pub fn lat(&self) -> f64 {
+        self.lat
+    }
+
+    pub fn lon(&self) -> f64 {
+        self.lon
+    }
+
+    /// Parse `"lat,lon"`, e.g. `"52.52,13.405"`.
+    pub fn parse(raw: &str) -> Result<Self, GeoError> {
+        let invalid = || GeoError::Invalid(raw.to_string());
+        let (lat, lon) = raw.split_once(',').ok_or_else(invalid)?;
+        let lat = lat.trim().parse::<f64>().map_err(|_| invalid())?;
+        let lon = lon.trim().parse::<f64>().map_err(|_| invalid())?;
+        Self::new(lat, lon)
+    }
+
+    /// Z-order cell holding this point at the finest level.
+    pub fn cell(&self) -> u64 {
+        interleave(
+            quantize(self.lat, -90.0, 180.0),
+            quantize(self.lon, -180.0, 360.0),
+        )
+    }
+
+    /// A point inside `cell` (its centre), so `from_cell(c).cell() == c`.
+    pub fn from_cell(cell: u64) -> Self {
+        let (lat_steps, lon_steps) = deinterleave(cell);
+        Self {
+            lat: -90.0 + (f64::from(lat_steps) + 0.5) * (180.0 / AXIS_STEPS),
+            lon: -180.0 + (f64::from(lon_steps) + 0.5) * (360.0 / AXIS_STEPS),
+        }
+    }
+
+    /// Great-circle distance in meters.
+    pub fn distance_meters(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let dlat = lat2 - lat1;
+        let dlon = (other.lon - self.lon).to_radians();
+        let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_METERS * h.sqrt().min(1.0).asin()
+    }
+}
+
+impl PartialEq for GeoPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.lat.to_bits() == other.lat.to_bits() && self.lon.to_bits() == other.lon.to_bits()
+    }
+}
+
+impl Eq for GeoPoint {}
+
+impl fmt::Display for GeoPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.lat, self.lon)
+    }
+}
+
+impl FromStr for GeoPoint {
+    type Err = GeoError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::parse(raw)
+    }
+}
+
+/// A latitude/longitude box. When `south_west.lon() > north_east.lon()` the
+/// box crosses the antimeridian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeoBox {
+    pub south_west: GeoPoint,
+    pub north_east: GeoPoint,
+}
+
+impl GeoBox {
+    pub fn new(south_west: GeoPoint, north_east: GeoPoint) -> Result<Self, GeoError> {
+        if south_west.lat > north_east.lat {
+            return Err(GeoError::InvertedBox {
+                south: south_west.lat,
+                north: north_east.lat,
+            });
+        }
+        Ok(Self {
+            south_west,
+            north_east,
+        })
+    }
+
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        let (west, east) = (self.south_west.lon, self.north_east.lon);
+        let lon_inside = if west <= east {
+            (west..=east).contains(&point.lon)
+        } else {
+            point.lon >= west || point.lon <= east
+        };
+        lon_inside && (self.south_west.lat..=self.north_east.lat).contains(&point.lat)
+    }
+
+    /// Split into boxes that do not cross the antimeridian.
+    fn pieces(&self) -> Vec<LatLonRect> {
+        let (south, north) = (self.south_west.lat, self.north_east.lat);
+        let (west, east) = (self.south_west.lon, self.north_east.lon);
+        if south > north {
+            return Vec::new();
+        }
+        if west <= east {
+            vec![LatLonRect::new(south, north, west, east)]
+        } else {
+            vec![
+                LatLonRect::new(south, north, west, 180.0),
+                LatLonRect::new(south, north, -180.0, east),
+            ]
+        }
+    }
+}
+
+/// All points within `radius_meters` of `center`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GeoCircle {
+    pub center: GeoPoint,
+    pub radius_meters: f64,
+}
+
+impl GeoCircle {
+    pub fn new(center: GeoPoint, radius_meters: f64) -> Result<Self, GeoError> {
+        if !radius_meters.is_finite() || radius_meters < 0.0 {
+            return Err(GeoError::InvalidRadius(radius_meters));
+        }
+        Ok(Self {
+            center,
+            radius_meters,
+        })
+    }
+
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        self.center.distance_meters(point) <= self.radius_meters
+    }
+
+    /// Latitude/longitude rectangles enclosing the circle.
+    fn pieces(&self) -> Vec<LatLonRect> {
+        let angular = self.radius_meters / EARTH_RADIUS_METERS;
+        let dlat = angular.to_degrees();
+        let south = self.center.lat - dlat;
+        let north = self.center.lat + dlat;
+        if south <= -90.0 || north >= 90.0 {
+            // The circle reaches a pole, so it spans every longitude.
+            return vec![LatLonRect::new(
+                south.max(-90.0),
+                north.min(90.0),
+                -180.0,
+                180.0,
+            )];
+        }
+        let spread = angular.sin() / self.center.lat.to_radians().cos();
+        if spread >= 1.0 {
+            return vec![LatLonRect::new(south, north, -180.0, 180.0)];
+        }
+        let dlon = spread.asin().to_degrees();
+        let (west, east) = (self.center.lon - dlon, self.center.lon + dlon);
+        if west < -180.0 {
+            vec![
+                LatLonRect::new(south, north, west + 360.0, 180.0),
+                LatLonRect::new(south, north, -180.0, east),
+            ]
+        } else if east > 180.0 {
+            vec![
+                LatLonRect::new(south, north, west, 180.0),
+                LatLonRect::new(south, north, -180.0, east - 360.0),
+            ]
+        } else {
+            vec![LatLonRect::new(south, north, west, east)]
+        }
+    }
+}
+
+impl PartialEq for GeoCircle {
+    fn eq(&self, other: &Self) -> bool {
+        self.center == other.center && self.radius_meters.to_bits() == other.radius_meters.to_bits()
+    }
+}
+
+impl Eq for GeoCircle {}
+
+/// Area tested by a geographic query condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GeoRegion {
+    Box(GeoBox),
+    Circle(GeoCircle),
+}
+
+impl GeoRegion {
+    /// Exact containment test applied after the index scan.
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        match self {
+            GeoRegion::Box(bounds) => bounds.contains(point),
+            GeoRegion::Circle(circle) => circle.contains(point),
+        }
+    }
+
+    /// Inclusive, sorted and non-overlapping ranges of [`GeoPoint::cell`]
+    /// values that together hold every point of the region (and usually a few
+    /// more).
+    pub fn covering_ranges(&self) -> Vec<(u64, u64)> {
+        let pieces = match self {
+            GeoRegion::Box(bounds) => bounds.pieces(),
+            GeoRegion::Circle(circle) => circle.pieces(),
+        };
+        let mut ranges: Vec<(u64, u64)> = pieces
+            .iter()
+            .flat_map(|piece| piece.expanded(COVERING_EPSILON_DEGREES).covering_cells())
+            .map(|cell| cell.code_range())
+            .collect();
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+        for (lo, hi) in ranges {
+            match merged.last_mut() {
+                Some(last) if lo <= last.1.saturating_add(1) => last.1 = last.1.max(hi),
+                _ => merged.push((lo, hi)),
+            }
+        }
+        merged
+    }
+}
+
+/// A non-wrapping latitude/longitude rectangle in degrees.
+#[derive(Debug, Clone, Copy)]
+struct LatLonRect {
+    south: f64,
+    north: f64,
+    west: f64,
+    east: f64,
+}
+
+impl LatLonRect {
+    fn new(south: f64, north: f64, west: f64, east: f64) -> Self {
+        Self {
+            south,
+            north,
+            west,
+            east,
+        }
+    }
+
+    fn expanded(self, by: f64) -> Self {
+        Self {
+            south: (self.south - by).max(-90.0),
+            north: (self.north + by).min(90.0),
+            west: (self.west - by).max(-180.0),
+            east: (self.east + by).min(180.0),
+        }
+    }
+
+    /// Refine a quadtree breadth-first until the next level would need more
+    /// than [`MAX_COVERING_CELLS`] cells.
+    fn covering_cells(&self) -> Vec<Cell> {
+        let mut inside = Vec::new();
+        let mut partial = vec![Cell::ROOT];
+        for _ in 0..MAX_CELL_LEVEL {
+            let mut next_inside = Vec::new();
+            let mut next_partial = Vec::new();
+            for cell in &partial {
+                for child in cell.children() {
+                    let (south, north, west, east) = child.extent();
+                    if north < self.south
+                        || south > self.north
+                        || east < self.west
+                        || west > self.east
+                    {
+                        continue;
+                    }
+                    if south >= self.south
+                        && north <= self.north
+                        && west >= self.west
+                        && east <= self.east
+                    {
+                        next_inside.push(child);
+                    } else {
+                        next_partial.push(child);
+                    }
+                }
+            }
+            if inside.len() + next_inside.len() + next_partial.len() > MAX_COVERING_CELLS {
+                break;
+            }
+            inside.extend(next_inside);
+            partial = next_partial;
+            if partial.is_empty() {
+                break;
+            }
+        }
+        inside.extend(partial);
+        inside
+    }
+}
+
+/// A quadtree cell: the top `level` bits of each axis.
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    level: u32,
+    lat_bits: u32,
+    lon_bits: u32,
+}
+
+impl Cell {
+    const ROOT: Cell = Cell {
+        level: 0,
+        lat_bits: 0,
+        lon_bits: 0,
+    };
+
+    fn children(&self) -> [Cell; 4] {
+        let child = |lat: u32, lon: u32| Cell {
+            level: self.level + 1,
+            lat_bits: (self.lat_bits << 1) | lat,
+            lon_bits: (self.lon_bits << 1) | lon,
+        };
+        [child(0, 0), child(0, 1), child(1, 0), child(1, 1)]
+    }
+
+    /// `(south, north, west, east)` in degrees.
+    fn extent(&self) -> (f64, f64, f64, f64) {
+        let divisions = 2f64.powi(self.level as i32);
+        let lat_step = 180.0 / divisions;
+        let lon_step = 360.0 / divisions;
+        let south = -90.0 + f64::from(self.lat_bits) * lat_step;
+        let west = -180.0 + f64::from(self.lon_bits) * lon_step;
+        (south, south + lat_step, west, west + lon_step)
+    }
+
+    /// Inclusive range of finest-level cell codes inside this cell.
+    fn code_range(&self) -> (u64, u64) {
+        let free_bits = 2 * (MAX_CELL_LEVEL - self.level);
+        let prefix = interleave(self.lat_bits, self.lon_bits);
+        let lo = prefix.checked_shl(free_bits).unwrap_or(0);
+        let span = 1u64
+            .checked_shl(free_bits)
+            .map_or(u64::MAX, |span| span - 1);
+        (lo, lo | span)
+    }
+}
+
+fn quantize(value: f64, min: f64, span: f64) -> u32 {
+    ((value - min) / span * AXIS_STEPS)
+        .floor()
+        .clamp(0.0, f64::from(u32::MAX)) as u32
+}
+
+/// Spread the bits of `value` into the even bit positions of a `u64`.
+fn spread_bits(value: u32) -> u64 {
+    let mut x = u64::from(value);
+    x = (x | (x << 16)) & 0x0000_FFFF_0000_FFFF;
+    x = (x | (x << 8)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    x = (x | (x << 1)) & 0x5555_5555_5555_5555;
+    x
+}
+
+fn compact_bits(value: u64) -> u32 {
+    let mut x = value & 0x5555_5555_5555_5555;
+    x = (x | (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x | (x >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x >> 4)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x >> 8)) & 0x0000_FFFF_0000_FFFF;
+    x = (x | (x >> 16)) & 0x0000_0000_FFFF_FFFF;
+    x as u32
+}
+
+/// Latitude bits take the odd positions, longitude bits the even ones.
+fn interleave(lat_steps: u32, lon_steps: u32) -> u64 {
+    (spread_bits(lat_steps) << 1) | spread_bits(lon_steps)
+}
+
+fn deinterleave(cell: u64) -> (u32, u32) {
+    (compact_bits(cell >> 1), compact_bits(cell))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(lat: f64, lon: f64) -> GeoPoint {
+        GeoPoint::new(lat, lon).unwrap()
+    }
+
+    fn covered(region: &GeoRegion, point: &GeoPoint) -> bool {
+        let cell = point.cell();
+        region
+            .covering_ranges()
+            .iter()
+            .any(|(lo, hi)| (*lo..=*hi).contains(&cell))
+    }
+
+    #[test]
+    fn points_validate_and_roundtrip_through_text() {
+        assert_eq!(GeoPoint::parse("52.52, 13.405"), Ok(point(52.52, 13.405)));
+        assert_eq!(point(-0.0, 0.0), point(0.0, 0.0));
+        assert!(matches!(
+            GeoPoint::new(91.0, 0.0),
+            Err(GeoError::InvalidLatitude(_))
+        ));
+        assert!(matches!(
+            GeoPoint::new(0.0, f64::NAN),
+            Err(GeoError::InvalidLongitude(_))
+        ));
+        let berlin = point(52.52, 13.405);
+        assert_eq!(berlin.to_string().parse::<GeoPoint>(), Ok(berlin));
+    }
+
+    #[test]
+    fn cell_centres_map_back_to_their_cell() {
+        for p in [
+            point(-90.0, -180.0),
+            point(0.0, 0.0),
+            point(48.8566, 2.3522),
+        ] {
+            let cell = p.cell();
+            assert_eq!(GeoPoint::from_cell(cell).cell(), cell);
+        }
+        assert_eq!(point(90.0, 180.0).cell(), u64::MAX);
+    }
+
+    #[test]
+    fn haversine_matches_known_distance() {
+        let paris = point(48.8566, 2.3522);
+        let london = point(51.5074, -0.1278);
+        let km = paris.distance_meters(&london) / 1000.0;
+        assert!((km - 343.5).abs() < 1.0, "got {km}");
+    }
+
+    #[test]
+    fn covering_ranges_include_every_point_of_the_region() {
+        let radius = GeoRegion::Circle(GeoCircle::new(point(52.52, 13.405), 5_000.0).unwrap());
+        let across_antimeridian =
+            GeoRegion::Box(GeoBox::new(point(-20.0, 170.0), point(-10.0, -170.0)).unwrap());
+        let on_equator = GeoRegion::Box(GeoBox::new(point(-1.0, -1.0), point(1.0, 1.0)).unwrap());
+
+        for region in [radius, across_antimeridian, on_equator] {
+            let ranges = region.covering_ranges();
+            assert!(ranges.len() <= 2 * MAX_COVERING_CELLS);
+            assert!(ranges.windows(2).all(|pair| pair[0].1 < pair[1].0));
+            for lat_step in 0..=40 {
+                for lon_step in 0..=80 {
+                    let p = point(
+                        -90.0 + 180.0 * f64::from(lat_step) / 40.0,
+                        -180.0 + 360.0 * f64::from(lon_step) / 80.0,
+                    );
+                    if region.contains(&p) {
+                        assert!(covered(&region, &p), "{p} missing from {region:?}");
+                    }
+                }
+            }
+        }
+
+        let circle = GeoCircle::new(point(52.52, 13.405), 5_000.0).unwrap();
+        let edge = point(52.52 + 4_999.0 / 111_195.0, 13.405);
+        assert!(circle.contains(&edge));
+        assert!(covered(&GeoRegion::Circle(circle), &edge));
+        assert!(!circle.contains(&point(52.6, 13.405)));
+    }
+}
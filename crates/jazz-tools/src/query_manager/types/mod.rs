@@ -3,21 +3,29 @@ pub(crate) use super::encoding;
 
 // Sub-modules
 pub mod branch;
+pub mod decimal;
 pub mod descriptor;
+pub mod geo;
 pub mod policy;
 pub mod row;
 pub mod schema;
+pub mod temporal;
 pub mod tuple;
 pub mod value;
+pub mod vector;
 
 // Re-export all public items from sub-modules
 pub use branch::*;
+pub use decimal::*;
 pub use descriptor::*;
+pub use geo::*;
 pub use policy::*;
 pub use row::*;
 pub use schema::*;
+pub use temporal::*;
 pub use tuple::*;
 pub use value::*;
+pub use vector::*;
 
 // Import PolicyExpr for use by schema module
 pub(crate) use crate::query_manager::policy::PolicyExpr;
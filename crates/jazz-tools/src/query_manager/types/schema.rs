@@ -1,10 +1,11 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::OnceLock;
 
 use internment::Intern;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use super::*;
+use crate::query_manager::json_path::JsonPath;
 
 /// Interned name identifying a table in the schema.
 /// Pointer-sized (8 bytes), Copy, fast equality via pointer comparison.
@@ -105,6 +106,20 @@ pub enum ColumnType {
     /// Heterogeneous row/tuple of values with a known schema.
     /// Used for nested rows (e.g., array of rows from subquery).
     Row { columns: Box<RowDescriptor> },
+    /// 4-byte calendar date (days since 1970-01-01).
+    Date,
+    /// 8-byte time of day (microseconds since midnight).
+    Time,
+    /// 16-byte span of months, days and microseconds, like PostgreSQL INTERVAL.
+    Interval,
+    /// 16-byte latitude/longitude pair in degrees, indexed by Z-order cell.
+    GeoPoint,
+    /// `dims` little-endian `f32`s. Not stored in ordered indices; nearest
+    /// neighbour queries use an in-memory HNSW graph instead.
+    Vector { dims: u32 },
+    /// 16-byte exact fixed-point number with up to `precision` significant
+    /// digits, `scale` of them after the decimal point, like PostgreSQL NUMERIC.
+    Decimal { precision: u8, scale: u8 },
 }
 
 impl ColumnType {
@@ -114,8 +129,14 @@ impl ColumnType {
             ColumnType::Integer => Some(4),
             ColumnType::BigInt => Some(8),
             ColumnType::Double => Some(8),
+            ColumnType::Decimal { .. } => Some(16),
             ColumnType::Boolean => Some(1),
             ColumnType::Timestamp => Some(8),
+            ColumnType::Date => Some(4),
+            ColumnType::Time => Some(8),
+            ColumnType::Interval => Some(16),
+            ColumnType::GeoPoint => Some(16),
+            ColumnType::Vector { dims } => Some(*dims as usize * 4),
             ColumnType::Uuid => Some(16),
             ColumnType::BatchId => Some(16),
             ColumnType::Text => None,
@@ -236,6 +257,131 @@ pub struct ColumnDescriptor {
     /// Optional per-column merge strategy. Absence means MRCA-relative LWW.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub merge_strategy: Option<ColumnMergeStrategy>,
+    /// Optional size bounds enforced on every write to this column.
+    #[serde(default, skip_serializing_if = "ColumnLimits::is_unbounded")]
+    pub limits: ColumnLimits,
+    /// Whether user-role clients see this column in their catalogue.
+    #[serde(default, skip_serializing_if = "Visibility::is_public")]
+    pub visibility: Visibility,
+    /// Free-form metadata for tooling, e.g. UI generated from the schema.
+    #[serde(default, skip_serializing_if = "Annotations::is_empty")]
+    pub annotations: Annotations,
+}
+
+/// Who sees a table or column in the schema catalogue.
+///
+/// Internal tables and columns are stripped from the schemas, lenses and rows
+/// the server sends to user-role clients. Admin, backend and peer connections
+/// always see the full schema.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Visibility {
+    #[default]
+    Public,
+    Internal,
+}
+
+impl Visibility {
+    /// Return true for the default, client-visible setting.
+    pub fn is_public(&self) -> bool {
+        matches!(self, Visibility::Public)
+    }
+
+    /// Return true when user-role clients must not see the item.
+    pub fn is_internal(&self) -> bool {
+        matches!(self, Visibility::Internal)
+    }
+}
+
+/// Free-form key/value metadata on a table or column: labels, UI widget
+/// hints, PII classification, descriptions.
+///
+/// Annotations never affect storage or queries. Each one is either hashed,
+/// so changing it makes a new schema version, or unhashed, so it can be
+/// edited in place: republishing a schema that only changed unhashed
+/// annotations updates its catalogue entry under the same hash.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Annotations(BTreeMap<String, Annotation>);
+
+/// A single annotation value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub value: String,
+    /// Whether the annotation is part of the schema hash.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hashed: bool,
+}
+
+impl Annotations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return true when nothing is annotated.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Set an annotation left out of the schema hash.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.insert_annotation(key, value.into(), false);
+    }
+
+    /// Set an annotation that is part of the schema hash.
+    pub fn insert_hashed(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.insert_annotation(key, value.into(), true);
+    }
+
+    pub fn insert_annotation(&mut self, key: impl Into<String>, value: String, hashed: bool) {
+        self.0.insert(key.into(), Annotation { value, hashed });
+    }
+
+    /// Get an annotation value.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(|annotation| annotation.value.as_str())
+    }
+
+    /// All annotations, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Annotation)> {
+        self.0
+            .iter()
+            .map(|(key, annotation)| (key.as_str(), annotation))
+    }
+
+    /// Hashed annotations, ordered by key.
+    pub fn hashed(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.iter()
+            .filter(|(_, annotation)| annotation.hashed)
+            .map(|(key, annotation)| (key, annotation.value.as_str()))
+    }
+
+    /// Return true when any annotation is part of the schema hash.
+    pub fn has_hashed(&self) -> bool {
+        self.0.values().any(|annotation| annotation.hashed)
+    }
+}
+
+/// Size bounds declared on a single column.
+///
+/// Limits are checked against the binary row encoding, so `max_bytes` counts
+/// UTF-8 bytes for TEXT/JSON, raw bytes for BYTEA and the full encoded array
+/// (including its element count and offset table) for ARRAY columns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnLimits {
+    /// Maximum encoded size of a non-null value, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u32>,
+    /// Maximum number of elements in an ARRAY value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<u32>,
+}
+
+impl ColumnLimits {
+    /// Return true when no bound is declared.
+    pub fn is_unbounded(&self) -> bool {
+        self.max_bytes.is_none() && self.max_items.is_none()
+    }
 }
 
 impl ColumnDescriptor {
@@ -247,6 +393,9 @@ impl ColumnDescriptor {
             references: None,
             default: None,
             merge_strategy: None,
+            limits: ColumnLimits::default(),
+            visibility: Visibility::Public,
+            annotations: Annotations::new(),
         }
     }
 
@@ -275,13 +424,47 @@ impl ColumnDescriptor {
         self
     }
 
+    /// Bound the encoded size of values written to this column.
+    pub fn max_bytes(mut self, max_bytes: u32) -> Self {
+        self.limits.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Bound the number of elements in values written to this ARRAY column.
+    pub fn max_items(mut self, max_items: u32) -> Self {
+        self.limits.max_items = Some(max_items);
+        self
+    }
+
+    /// Hide this column from user-role clients.
+    pub fn internal(mut self) -> Self {
+        self.visibility = Visibility::Internal;
+        self
+    }
+
+    /// Annotate this column, leaving the annotation out of the schema hash.
+    pub fn annotate(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.annotations.insert(key, value);
+        self
+    }
+
+    /// Annotate this column, making the annotation part of the schema hash.
+    pub fn annotate_hashed(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.annotations.insert_hashed(key, value);
+        self
+    }
+
     pub fn validate_merge_strategy(&self) -> Result<(), String> {
         match self.merge_strategy {
             None => Ok(()),
             Some(ColumnMergeStrategy::Counter) => {
-                if self.nullable || self.column_type != ColumnType::Integer {
+                let numeric = matches!(
+                    self.column_type,
+                    ColumnType::Integer | ColumnType::Decimal { .. }
+                );
+                if self.nullable || !numeric {
                     Err(format!(
-                        "counter merge strategy is only supported on non-nullable INTEGER columns, got {} ({:?}, nullable={})",
+                        "counter merge strategy is only supported on non-nullable INTEGER or DECIMAL columns, got {} ({:?}, nullable={})",
                         self.name_str(),
                         self.column_type,
                         self.nullable
@@ -400,11 +583,41 @@ pub struct TableSchema {
     /// Internal `_id` and `_id_deleted` indexes are always maintained.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub indexed_columns: Option<Vec<ColumnName>>,
+    /// Row size and per-user row quotas enforced on writes.
+    #[serde(default, skip_serializing_if = "TableLimits::is_unbounded")]
+    pub limits: TableLimits,
+    /// Whether user-role clients see this table in their catalogue.
+    #[serde(default, skip_serializing_if = "Visibility::is_public")]
+    pub visibility: Visibility,
+    /// Free-form metadata for tooling, e.g. UI generated from the schema.
+    #[serde(default, skip_serializing_if = "Annotations::is_empty")]
+    pub annotations: Annotations,
     /// Access control policies.
     #[serde(default, skip_serializing_if = "table_policies_are_default")]
     pub policies: TablePolicies,
 }
 
+/// Storage bounds declared on a whole table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableLimits {
+    /// Maximum size of the encoded row, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_row_bytes: Option<u32>,
+    /// Maximum number of live rows a single user may have created.
+    ///
+    /// Rows are attributed through `$createdBy`; writes authored by the
+    /// system principal are not counted against any quota.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_rows_per_user: Option<u64>,
+}
+
+impl TableLimits {
+    /// Return true when no bound is declared.
+    pub fn is_unbounded(&self) -> bool {
+        self.max_row_bytes.is_none() && self.max_rows_per_user.is_none()
+    }
+}
+
 fn table_policies_are_default(policies: &TablePolicies) -> bool {
     *policies == TablePolicies::default()
 }
@@ -417,6 +630,9 @@ impl TableSchema {
         Self {
             columns,
             indexed_columns: None,
+            limits: TableLimits::default(),
+            visibility: Visibility::Public,
+            annotations: Annotations::new(),
             policies: TablePolicies::default(),
         }
     }
@@ -426,6 +642,9 @@ impl TableSchema {
         Self {
             columns,
             indexed_columns: None,
+            limits: TableLimits::default(),
+            visibility: Visibility::Public,
+            annotations: Annotations::new(),
             policies,
         }
     }
@@ -438,15 +657,59 @@ impl TableSchema {
     /// Return true when the given user column has a maintained secondary index.
     ///
     /// The implicit object-id indexes are always available and are handled here
-    /// too so query planning can use one predicate path.
+    /// too so query planning can use one predicate path. JSON path selectors
+    /// (`settings->theme`) are only indexed when listed explicitly. Vector
+    /// columns never are.
     pub fn is_indexed_column(&self, column: &str) -> bool {
         if column == "_id" || column == "_id_deleted" {
             return true;
         }
+        if self
+            .columns
+            .column(column)
+            .is_some_and(|descriptor| matches!(descriptor.column_type, ColumnType::Vector { .. }))
+        {
+            return false;
+        }
+        if JsonPath::is_selector(column) {
+            return self
+                .indexed_columns
+                .as_ref()
+                .is_some_and(|columns| columns.iter().any(|name| name.as_str() == column));
+        }
         self.indexed_columns
             .as_ref()
             .is_none_or(|columns| columns.iter().any(|name| name.as_str() == column))
     }
+
+    /// JSON path selectors listed in `indexed_columns`.
+    pub fn json_path_indices(&self) -> Vec<JsonPath> {
+        self.indexed_columns
+            .iter()
+            .flatten()
+            .filter_map(|name| JsonPath::parse(name.as_str()))
+            .collect()
+    }
+
+    /// Return true when any table- or column-level limit is declared.
+    pub fn has_limits(&self) -> bool {
+        !self.limits.is_unbounded()
+            || self
+                .columns
+                .columns
+                .iter()
+                .any(|column| !column.limits.is_unbounded())
+    }
+
+    /// Return true when the table or any of its columns is internal.
+    pub fn has_internal_items(&self) -> bool {
+        self.visibility.is_internal()
+            || self
+                .columns
+                .columns
+                .iter()
+                .any(|column| column.visibility.is_internal())
+    }
 }
 
 impl From<RowDescriptor> for TableSchema {
@@ -461,6 +724,10 @@ pub struct TableSchemaBuilder {
     name: String,
     columns: Vec<ColumnDescriptor>,
     indexed_columns: Option<Vec<ColumnName>>,
+    json_path_indices: Vec<ColumnName>,
+    limits: TableLimits,
+    visibility: Visibility,
+    annotations: Annotations,
     policies: TablePolicies,
 }
 
@@ -471,6 +738,10 @@ impl TableSchemaBuilder {
             name: name.to_string(),
             columns: Vec::new(),
             indexed_columns: None,
+            json_path_indices: Vec::new(),
+            limits: TableLimits::default(),
+            visibility: Visibility::Public,
+            annotations: Annotations::new(),
             policies: TablePolicies::default(),
         }
     }
@@ -481,6 +752,12 @@ impl TableSchemaBuilder {
         self
     }
 
+    /// Add a fully configured column descriptor to the table.
+    pub fn column_descriptor(mut self, column: ColumnDescriptor) -> Self {
+        self.columns.push(column);
+        self
+    }
+
     /// Add a column with a schema-level default to the table.
     pub fn column_with_default(
         mut self,
@@ -493,6 +770,13 @@ impl TableSchemaBuilder {
         self
     }
 
+    /// Add a column hidden from user-role clients.
+    pub fn internal_column(mut self, name: &str, column_type: ColumnType) -> Self {
+        self.columns
+            .push(ColumnDescriptor::new(name, column_type).internal());
+        self
+    }
+
     /// Add a nullable column to the table.
     pub fn nullable_column(mut self, name: &str, column_type: ColumnType) -> Self {
         self.columns
@@ -537,6 +821,51 @@ impl TableSchemaBuilder {
         self
     }
 
+    /// Hide the whole table from user-role clients.
+    pub fn internal(mut self) -> Self {
+        self.visibility = Visibility::Internal;
+        self
+    }
+
+    /// Annotate the table, leaving the annotation out of the schema hash.
+    pub fn annotate(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.annotations.insert(key, value);
+        self
+    }
+
+    /// Annotate the table, making the annotation part of the schema hash.
+    pub fn annotate_hashed(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.annotations.insert_hashed(key, value);
+        self
+    }
+
+    /// Annotate the most recently added column, leaving the annotation out of
+    /// the schema hash.
+    ///
+    /// # Panics
+    ///
+    /// Panics when no column has been added yet.
+    pub fn annotate_column(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.columns
+            .last_mut()
+            .expect("annotate_column needs a preceding column")
+            .annotations
+            .insert(key, value);
+        self
+    }
+
+    /// Bound the encoded size of every row in the table.
+    pub fn max_row_bytes(mut self, max_row_bytes: u32) -> Self {
+        self.limits.max_row_bytes = Some(max_row_bytes);
+        self
+    }
+
+    /// Bound how many live rows each user may create in the table.
+    pub fn max_rows_per_user(mut self, max_rows: u64) -> Self {
+        self.limits.max_rows_per_user = Some(max_rows);
+        self
+    }
+
     /// Index only this explicit user-column subset.
     ///
     /// Internal `_id` and `_id_deleted` indexes are always maintained.
@@ -549,6 +878,27 @@ impl TableSchemaBuilder {
         self
     }
 
+    /// Maintain an expression index on a JSON path such as `settings->theme`.
+    ///
+    /// Path indices live in the explicit index list; if no list was given,
+    /// every plain column stays indexed alongside the path.
+    pub fn index_json_path(mut self, path: &str) -> Self {
+        self.json_path_indices.push(ColumnName::new(path));
+        self
+    }
+
+    fn resolved_indexed_columns(&self) -> Option<Vec<ColumnName>> {
+        if self.json_path_indices.is_empty() {
+            return self.indexed_columns.clone();
+        }
+        let mut indexed = self
+            .indexed_columns
+            .clone()
+            .unwrap_or_else(|| self.columns.iter().map(|column| column.name).collect());
+        indexed.extend(self.json_path_indices.iter().copied());
+        Some(indexed)
+    }
+
     /// Get the table name.
     pub fn name(&self) -> &str {
         &self.name
@@ -557,8 +907,11 @@ impl TableSchemaBuilder {
     /// Build the TableSchema (returns just the schema, not the name).
     pub fn build(self) -> TableSchema {
         TableSchema {
+            indexed_columns: self.resolved_indexed_columns(),
             columns: RowDescriptor::new(self.columns),
-            indexed_columns: self.indexed_columns,
+            limits: self.limits,
+            visibility: self.visibility,
+            annotations: self.annotations,
             policies: self.policies,
         }
     }
@@ -567,8 +920,11 @@ impl TableSchemaBuilder {
     pub fn build_named(self) -> (TableName, TableSchema) {
         let name = TableName::new(&self.name);
         let schema = TableSchema {
+            indexed_columns: self.resolved_indexed_columns(),
             columns: RowDescriptor::new(self.columns),
-            indexed_columns: self.indexed_columns,
+            limits: self.limits,
+            visibility: self.visibility,
+            annotations: self.annotations,
             policies: self.policies,
         };
         (name, schema)
@@ -0,0 +1,913 @@
+//! Calendar dates, times of day and intervals for `DATE`, `TIME` and
+//! `INTERVAL` columns.
+//!
+//! A [`Date`] counts days since 1970-01-01 in the proleptic Gregorian
+//! calendar and a [`Time`] counts microseconds since midnight. An
+//! [`Interval`] keeps months, days and microseconds apart, like PostgreSQL,
+//! because months and (across offset changes) days have no fixed length:
+//! `P1M` added to January 31st lands on the last day of February.
+//!
+//! Text forms are ISO 8601: `2024-02-29`, `13:45:00.25` and `P1Y2M3DT4H5M6S`.
+//!
+//! Time-zone helpers take a fixed [`UtcOffset`]. Named zones are resolved to
+//! the offset in force at the instant in question by the caller (e.g. `Intl`
+//! in JS), since the core carries no time-zone database.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{ColumnType, Value};
+
+pub const MICROS_PER_SECOND: i64 = 1_000_000;
+pub const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+pub const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+pub const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+
+/// Days per month used when ordering intervals, as in PostgreSQL.
+const INTERVAL_DAYS_PER_MONTH: i128 = 30;
+
+/// Why a date, time, interval or offset literal could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemporalError {
+    /// Not a literal of the expected shape, e.g. `"2024-13-01"` for a date.
+    Invalid { kind: &'static str, raw: String },
+    /// Well-formed, but outside the representable range.
+    OutOfRange { kind: &'static str, raw: String },
+}
+
+impl TemporalError {
+    fn invalid(kind: &'static str, raw: &str) -> Self {
+        TemporalError::Invalid {
+            kind,
+            raw: raw.to_string(),
+        }
+    }
+
+    fn out_of_range(kind: &'static str, raw: &str) -> Self {
+        TemporalError::OutOfRange {
+            kind,
+            raw: raw.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TemporalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemporalError::Invalid { kind, raw } => write!(f, "invalid {kind} literal '{raw}'"),
+            TemporalError::OutOfRange { kind, raw } => {
+                write!(f, "{kind} literal '{raw}' is out of range")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemporalError {}
+
+// Howard Hinnant's `days_from_civil` / `civil_from_days`.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = (if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    }) as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn parse_digits(raw: &str) -> Option<u64> {
+    if raw.is_empty() || !raw.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    raw.parse().ok()
+}
+
+/// Microseconds from up to six fractional digits (`"25"` is 250 000).
+fn parse_fraction_micros(raw: &str) -> Option<i64> {
+    if raw.len() > 6 {
+        return None;
+    }
+    let digits = parse_digits(raw)? as i64;
+    Some(digits * 10i64.pow(6 - raw.len() as u32))
+}
+
+fn write_fraction(f: &mut fmt::Formatter<'_>, micros: u64) -> fmt::Result {
+    if micros == 0 {
+        return Ok(());
+    }
+    let digits = format!("{micros:06}");
+    write!(f, ".{}", digits.trim_end_matches('0'))
+}
+
+/// A fixed offset from UTC, in seconds east of Greenwich.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct UtcOffset(i32);
+
+impl UtcOffset {
+    pub const UTC: UtcOffset = UtcOffset(0);
+
+    /// `None` unless the offset is less than a day either way.
+    pub fn from_seconds(seconds: i32) -> Option<Self> {
+        (i64::from(seconds).abs() * MICROS_PER_SECOND < MICROS_PER_DAY).then_some(Self(seconds))
+    }
+
+    pub fn from_hours_minutes(hours: i32, minutes: i32) -> Option<Self> {
+        Self::from_seconds(
+            hours
+                .checked_mul(3600)?
+                .checked_add(minutes.checked_mul(60)?)?,
+        )
+    }
+
+    pub fn seconds(&self) -> i32 {
+        self.0
+    }
+
+    fn micros(&self) -> i64 {
+        i64::from(self.0) * MICROS_PER_SECOND
+    }
+
+    /// Parse `Z`, `+HH`, `+HH:MM` or `+HHMM` (and their `-` forms).
+    pub fn parse(raw: &str) -> Result<Self, TemporalError> {
+        let invalid = || TemporalError::invalid("UTC offset", raw);
+        let trimmed = raw.trim();
+        if trimmed.eq_ignore_ascii_case("z") {
+            return Ok(Self::UTC);
+        }
+        let (sign, rest) = match trimmed.as_bytes().first() {
+            Some(b'+') => (1, &trimmed[1..]),
+            Some(b'-') => (-1, &trimmed[1..]),
+            _ => return Err(invalid()),
+        };
+        let (hours, minutes) = match rest.split_once(':') {
+            Some((hours, minutes)) => (hours, minutes),
+            None if rest.len() == 4 => rest.split_at(2),
+            None => (rest, "00"),
+        };
+        if hours.len() != 2 || minutes.len() != 2 {
+            return Err(invalid());
+        }
+        let hours = parse_digits(hours).ok_or_else(invalid)? as i32;
+        let minutes = parse_digits(minutes).ok_or_else(invalid)? as i32;
+        if minutes >= 60 {
+            return Err(invalid());
+        }
+        Self::from_hours_minutes(sign * hours, sign * minutes)
+            .ok_or_else(|| TemporalError::out_of_range("UTC offset", raw))
+    }
+}
+
+impl fmt::Display for UtcOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return f.write_str("Z");
+        }
+        let sign = if self.0 < 0 { '-' } else { '+' };
+        let minutes = self.0.unsigned_abs() / 60;
+        write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+impl FromStr for UtcOffset {
+    type Err = TemporalError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::parse(raw)
+    }
+}
+
+/// Split a timestamp (microseconds since the Unix epoch) into the local
+/// calendar day and wall-clock time at `offset`.
+fn local_parts(timestamp: u64, offset: UtcOffset) -> (i64, i64) {
+    let local = i128::from(timestamp) + i128::from(offset.micros());
+    let micros_per_day = i128::from(MICROS_PER_DAY);
+    (
+        local.div_euclid(micros_per_day) as i64,
+        local.rem_euclid(micros_per_day) as i64,
+    )
+}
+
+/// A calendar date, stored as days since 1970-01-01.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Date(i32);
+
+impl Date {
+    pub const UNIX_EPOCH: Date = Date(0);
+
+    pub fn from_days_since_epoch(days: i32) -> Self {
+        Self(days)
+    }
+
+    pub fn days_since_epoch(&self) -> i32 {
+        self.0
+    }
+
+    /// `None` for impossible dates such as February 30th.
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year.into(), month) {
+            return None;
+        }
+        i32::try_from(days_from_civil(year.into(), month, day))
+            .ok()
+            .map(Self)
+    }
+
+    /// `(year, month, day)` with months and days counted from 1.
+    pub fn ymd(&self) -> (i32, u32, u32) {
+        let (year, month, day) = civil_from_days(self.0.into());
+        (year as i32, month, day)
+    }
+
+    /// ISO weekday: Monday is 1, Sunday is 7.
+    pub fn iso_weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday.
+        (i64::from(self.0) + 3).rem_euclid(7) as u32 + 1
+    }
+
+    /// Parse `YYYY-MM-DD`; years outside 0000–9999 carry a sign (`-0044-03-15`).
+    pub fn parse(raw: &str) -> Result<Self, TemporalError> {
+        let invalid = || TemporalError::invalid("date", raw);
+        let trimmed = raw.trim();
+        let (sign, rest) = match trimmed.as_bytes().first() {
+            Some(b'+') => (1, &trimmed[1..]),
+            Some(b'-') => (-1, &trimmed[1..]),
+            _ => (1, trimmed),
+        };
+        let mut parts = rest.splitn(3, '-');
+        let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        if year.len() < 4 || month.len() != 2 || day.len() != 2 {
+            return Err(invalid());
+        }
+        let year = i32::try_from(parse_digits(year).ok_or_else(invalid)?)
+            .map_err(|_| TemporalError::out_of_range("date", raw))?;
+        let month = parse_digits(month).ok_or_else(invalid)? as u32;
+        let day = parse_digits(day).ok_or_else(invalid)? as u32;
+        Self::from_ymd(sign * year, month, day).ok_or_else(invalid)
+    }
+
+    pub fn checked_add_days(&self, days: i64) -> Option<Self> {
+        i32::try_from(i64::from(self.0).checked_add(days)?)
+            .ok()
+            .map(Self)
+    }
+
+    /// Add calendar months, clamping to the end of shorter months
+    /// (January 31st plus one month is February 28th or 29th).
+    pub fn checked_add_months(&self, months: i64) -> Option<Self> {
+        let (year, month, day) = civil_from_days(self.0.into());
+        let total = year
+            .checked_mul(12)?
+            .checked_add(i64::from(month) - 1 + months)?;
+        let year = total.div_euclid(12);
+        let month = total.rem_euclid(12) as u32 + 1;
+        let day = day.min(days_in_month(year, month));
+        i32::try_from(days_from_civil(year, month, day))
+            .ok()
+            .map(Self)
+    }
+
+    /// Local calendar date of a timestamp at `offset`.
+    pub fn of_timestamp(timestamp: u64, offset: UtcOffset) -> Self {
+        Self(local_parts(timestamp, offset).0 as i32)
+    }
+
+    /// Timestamp of this date at `time` on the wall clock at `offset`;
+    /// `None` before the Unix epoch.
+    pub fn at(&self, time: Time, offset: UtcOffset) -> Option<u64> {
+        let micros = i128::from(self.0) * i128::from(MICROS_PER_DAY) + i128::from(time.0)
+            - i128::from(offset.micros());
+        u64::try_from(micros).ok()
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        if (0..=9999).contains(&year) {
+            write!(f, "{year:04}-{month:02}-{day:02}")
+        } else {
+            write!(f, "{year:+05}-{month:02}-{day:02}")
+        }
+    }
+}
+
+impl FromStr for Date {
+    type Err = TemporalError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::parse(raw)
+    }
+}
+
+/// A wall-clock time of day, stored as microseconds since midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Time(u64);
+
+impl Time {
+    pub const MIDNIGHT: Time = Time(0);
+
+    /// `None` unless `micros` falls within one day.
+    pub fn from_micros(micros: u64) -> Option<Self> {
+        (micros < MICROS_PER_DAY as u64).then_some(Self(micros))
+    }
+
+    pub fn from_hms_micro(hour: u32, minute: u32, second: u32, micro: u32) -> Option<Self> {
+        if hour >= 24 || minute >= 60 || second >= 60 || i64::from(micro) >= MICROS_PER_SECOND {
+            return None;
+        }
+        Some(Self(
+            (u64::from(hour) * MICROS_PER_HOUR as u64)
+                + u64::from(minute) * MICROS_PER_MINUTE as u64
+                + u64::from(second) * MICROS_PER_SECOND as u64
+                + u64::from(micro),
+        ))
+    }
+
+    pub fn micros_since_midnight(&self) -> u64 {
+        self.0
+    }
+
+    /// Parse `HH:MM`, `HH:MM:SS` or `HH:MM:SS.ffffff`.
+    pub fn parse(raw: &str) -> Result<Self, TemporalError> {
+        let invalid = || TemporalError::invalid("time", raw);
+        let trimmed = raw.trim();
+        let (clock, fraction) = match trimmed.split_once('.') {
+            Some((clock, fraction)) => (clock, Some(fraction)),
+            None => (trimmed, None),
+        };
+        let mut parts = clock.split(':');
+        let hour = parts.next().ok_or_else(invalid)?;
+        let minute = parts.next().ok_or_else(invalid)?;
+        let second = parts.next();
+        if parts.next().is_some()
+            || hour.len() != 2
+            || minute.len() != 2
+            || second.is_some_and(|second| second.len() != 2)
+            || (second.is_none() && fraction.is_some())
+        {
+            return Err(invalid());
+        }
+        let micro = match fraction {
+            Some(fraction) => parse_fraction_micros(fraction).ok_or_else(invalid)?,
+            None => 0,
+        };
+        Self::from_hms_micro(
+            parse_digits(hour).ok_or_else(invalid)? as u32,
+            parse_digits(minute).ok_or_else(invalid)? as u32,
+            second.map_or(Some(0), parse_digits).ok_or_else(invalid)? as u32,
+            micro as u32,
+        )
+        .ok_or_else(invalid)
+    }
+
+    /// Local wall-clock time of a timestamp at `offset`.
+    pub fn of_timestamp(timestamp: u64, offset: UtcOffset) -> Self {
+        Self(local_parts(timestamp, offset).1 as u64)
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.0 / MICROS_PER_SECOND as u64;
+        write!(
+            f,
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )?;
+        write_fraction(f, self.0 % MICROS_PER_SECOND as u64)
+    }
+}
+
+impl FromStr for Time {
+    type Err = TemporalError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::parse(raw)
+    }
+}
+
+/// A span of calendar time: months, days and microseconds, each signed.
+///
+/// The parts are kept apart because their lengths vary. For ordering only, a
+/// month counts as 30 days and a day as 24 hours (so `P1M` sorts just after
+/// `P29D`); parts that tie on that total order by months, then days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Interval {
+    months: i32,
+    days: i32,
+    micros: i64,
+}
+
+impl Interval {
+    pub const ZERO: Interval = Interval {
+        months: 0,
+        days: 0,
+        micros: 0,
+    };
+
+    pub fn new(months: i32, days: i32, micros: i64) -> Self {
+        Self {
+            months,
+            days,
+            micros,
+        }
+    }
+
+    pub fn from_micros(micros: i64) -> Self {
+        Self::new(0, 0, micros)
+    }
+
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    pub fn micros(&self) -> i64 {
+        self.micros
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    /// Length in microseconds with 30-day months, as used for ordering.
+    pub fn approximate_micros(&self) -> i128 {
+        (i128::from(self.months) * INTERVAL_DAYS_PER_MONTH + i128::from(self.days))
+            * i128::from(MICROS_PER_DAY)
+            + i128::from(self.micros)
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(Self::new(
+            self.months.checked_add(other.months)?,
+            self.days.checked_add(other.days)?,
+            self.micros.checked_add(other.micros)?,
+        ))
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Self::new(
+            self.months.checked_neg()?,
+            self.days.checked_neg()?,
+            self.micros.checked_neg()?,
+        ))
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    /// Add this interval to a timestamp on the wall clock at `offset`: months
+    /// and days move the local date (keeping the local time), microseconds
+    /// are exact elapsed time. `None` if the result precedes the epoch.
+    pub fn add_to_timestamp(&self, timestamp: u64, offset: UtcOffset) -> Option<u64> {
+        let date = Date::of_timestamp(timestamp, offset)
+            .checked_add_months(self.months.into())?
+            .checked_add_days(self.days.into())?;
+        let moved = date.at(Time::of_timestamp(timestamp, offset), offset)?;
+        u64::try_from(i128::from(moved) + i128::from(self.micros)).ok()
+    }
+
+    /// Parse an ISO 8601 duration such as `P1Y2M10DT2H30M`, `PT0.5S` or
+    /// `-P3W`. Components may carry their own sign (`P1M-1D`).
+    pub fn parse(raw: &str) -> Result<Self, TemporalError> {
+        let invalid = || TemporalError::invalid("interval", raw);
+        let out_of_range = || TemporalError::out_of_range("interval", raw);
+        let trimmed = raw.trim();
+        let (negate, rest) = match trimmed.as_bytes().first() {
+            Some(b'-') => (true, &trimmed[1..]),
+            Some(b'+') => (false, &trimmed[1..]),
+            _ => (false, trimmed),
+        };
+        let rest = rest
+            .strip_prefix('P')
+            .or_else(|| rest.strip_prefix('p'))
+            .ok_or_else(invalid)?;
+
+        let mut months: i128 = 0;
+        let mut days: i128 = 0;
+        let mut micros: i128 = 0;
+        let mut in_time = false;
+        let mut any_component = false;
+        let mut remaining = rest;
+        while !remaining.is_empty() {
+            if let Some(after) = remaining
+                .strip_prefix('T')
+                .or_else(|| remaining.strip_prefix('t'))
+            {
+                if in_time {
+                    return Err(invalid());
+                }
+                in_time = true;
+                remaining = after;
+                continue;
+            }
+            let designator_at = remaining
+                .find(|c: char| c.is_ascii_alphabetic())
+                .ok_or_else(invalid)?;
+            let (number, after) = remaining.split_at(designator_at);
+            let designator = after.as_bytes()[0].to_ascii_uppercase();
+            remaining = &after[1..];
+
+            let (negative, number) = match number.strip_prefix('-') {
+                Some(number) => (true, number),
+                None => (false, number),
+            };
+            let (whole, fraction) = match number.split_once('.') {
+                Some((whole, fraction)) => (whole, Some(fraction)),
+                None => (number, None),
+            };
+            let whole = i128::from(parse_digits(whole).ok_or_else(invalid)?);
+            let fraction = match fraction {
+                Some(fraction) if in_time && designator == b'S' => {
+                    i128::from(parse_fraction_micros(fraction).ok_or_else(invalid)?)
+                }
+                Some(_) => return Err(invalid()),
+                None => 0,
+            };
+            let sign = if negative { -1 } else { 1 };
+            match (in_time, designator) {
+                (false, b'Y') => months += sign * whole * 12,
+                (false, b'M') => months += sign * whole,
+                (false, b'W') => days += sign * whole * 7,
+                (false, b'D') => days += sign * whole,
+                (true, b'H') => micros += sign * whole * i128::from(MICROS_PER_HOUR),
+                (true, b'M') => micros += sign * whole * i128::from(MICROS_PER_MINUTE),
+                (true, b'S') => micros += sign * (whole * i128::from(MICROS_PER_SECOND) + fraction),
+                _ => return Err(invalid()),
+            }
+            any_component = true;
+        }
+        if !any_component {
+            return Err(invalid());
+        }
+
+        let interval = Self::new(
+            i32::try_from(months).map_err(|_| out_of_range())?,
+            i32::try_from(days).map_err(|_| out_of_range())?,
+            i64::try_from(micros).map_err(|_| out_of_range())?,
+        );
+        if negate {
+            interval.checked_neg().ok_or_else(out_of_range)
+        } else {
+            Ok(interval)
+        }
+    }
+}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.approximate_micros()
+            .cmp(&other.approximate_micros())
+            .then(self.months.cmp(&other.months))
+            .then(self.days.cmp(&other.days))
+            .then(self.micros.cmp(&other.micros))
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.write_str("PT0S");
+        }
+        // Write `-P1D` rather than `P-1D` when every part points backwards.
+        let all_negative = self.months <= 0 && self.days <= 0 && self.micros <= 0;
+        let sign: i128 = if all_negative { -1 } else { 1 };
+        if all_negative {
+            f.write_str("-")?;
+        }
+        f.write_str("P")?;
+
+        let months = sign * i128::from(self.months);
+        let (years, months) = (months / 12, months % 12);
+        if years != 0 {
+            write!(f, "{years}Y")?;
+        }
+        if months != 0 {
+            write!(f, "{months}M")?;
+        }
+        let days = sign * i128::from(self.days);
+        if days != 0 {
+            write!(f, "{days}D")?;
+        }
+
+        let micros = sign * i128::from(self.micros);
+        if micros != 0 {
+            f.write_str("T")?;
+            let magnitude = micros.unsigned_abs();
+            let hours = magnitude / MICROS_PER_HOUR as u128;
+            let minutes = magnitude / MICROS_PER_MINUTE as u128 % 60;
+            let seconds = magnitude / MICROS_PER_SECOND as u128 % 60;
+            let fraction = (magnitude % MICROS_PER_SECOND as u128) as u64;
+            let sign = if micros < 0 { "-" } else { "" };
+            if hours != 0 {
+                write!(f, "{sign}{hours}H")?;
+            }
+            if minutes != 0 {
+                write!(f, "{sign}{minutes}M")?;
+            }
+            if seconds != 0 || fraction != 0 {
+                write!(f, "{sign}{seconds}")?;
+                write_fraction(f, fraction)?;
+                f.write_str("S")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Interval {
+    type Err = TemporalError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::parse(raw)
+    }
+}
+
+/// Read a text literal as the value of a `Date`, `Time` or `Interval` column,
+/// so filters can be written as `"2024-03-01"`. `None` for other column types.
+pub fn parse_temporal_literal(
+    raw: &str,
+    column_type: &ColumnType,
+) -> Option<Result<Value, TemporalError>> {
+    match column_type {
+        ColumnType::Date => Some(Date::parse(raw).map(Value::Date)),
+        ColumnType::Time => Some(Time::parse(raw).map(Value::Time)),
+        ColumnType::Interval => Some(Interval::parse(raw).map(Value::Interval)),
+        _ => None,
+    }
+}
+
+// Human-readable formats (JSON) use the ISO 8601 text forms; binary formats
+// store the raw counts.
+
+impl Serialize for Date {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl Serialize for Time {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl Serialize for Interval {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (self.months, self.days, self.micros).serialize(serializer)
+        }
+    }
+}
+
+/// Visitor for the text form of a temporal value.
+struct TemporalTextVisitor<T> {
+    expecting: &'static str,
+    parse: fn(&str) -> Result<T, TemporalError>,
+}
+
+impl<T> Visitor<'_> for TemporalTextVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        (self.parse)(value).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return i32::deserialize(deserializer).map(Date);
+        }
+        deserializer.deserialize_str(TemporalTextVisitor {
+            expecting: "an ISO 8601 date string",
+            parse: Date::parse,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Time {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            let micros = u64::deserialize(deserializer)?;
+            return Time::from_micros(micros)
+                .ok_or_else(|| de::Error::custom("time of day out of range"));
+        }
+        deserializer.deserialize_str(TemporalTextVisitor {
+            expecting: "an ISO 8601 time string",
+            parse: Time::parse,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Interval {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            let (months, days, micros) = <(i32, i32, i64)>::deserialize(deserializer)?;
+            return Ok(Interval::new(months, days, micros));
+        }
+        deserializer.deserialize_str(TemporalTextVisitor {
+            expecting: "an ISO 8601 duration string",
+            parse: Interval::parse,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_round_trip_through_iso_text_and_calendar_parts() {
+        let leap_day = Date::parse("2024-02-29").unwrap();
+        assert_eq!(leap_day.ymd(), (2024, 2, 29));
+        assert_eq!(leap_day.to_string(), "2024-02-29");
+        assert_eq!(leap_day.iso_weekday(), 4);
+        assert_eq!(Date::parse("1970-01-01").unwrap(), Date::UNIX_EPOCH);
+        assert_eq!(Date::parse("1969-12-31").unwrap().days_since_epoch(), -1);
+        assert_eq!(
+            Date::parse("-0044-03-15").unwrap().to_string(),
+            "-0044-03-15"
+        );
+        assert!(Date::parse("2023-02-29").is_err());
+        assert!(Date::parse("2024-1-05").is_err());
+    }
+
+    #[test]
+    fn adding_months_clamps_to_the_end_of_shorter_months() {
+        let jan_31 = Date::parse("2024-01-31").unwrap();
+        assert_eq!(
+            jan_31.checked_add_months(1).unwrap().to_string(),
+            "2024-02-29"
+        );
+        assert_eq!(
+            jan_31.checked_add_months(13).unwrap().to_string(),
+            "2025-02-28"
+        );
+        assert_eq!(
+            jan_31.checked_add_months(-2).unwrap().to_string(),
+            "2023-11-30"
+        );
+    }
+
+    #[test]
+    fn times_parse_optional_seconds_and_fractions() {
+        assert_eq!(Time::parse("13:45").unwrap().to_string(), "13:45:00");
+        assert_eq!(
+            Time::parse("13:45:07.25").unwrap().to_string(),
+            "13:45:07.25"
+        );
+        assert_eq!(
+            Time::parse("00:00:00.000001")
+                .unwrap()
+                .micros_since_midnight(),
+            1
+        );
+        assert!(Time::parse("24:00").is_err());
+        assert!(Time::parse("12:00.5").is_err());
+    }
+
+    #[test]
+    fn intervals_parse_iso_durations_and_order_by_approximate_length() {
+        let interval = Interval::parse("P1Y2M10DT2H30M0.5S").unwrap();
+        assert_eq!(interval.months(), 14);
+        assert_eq!(interval.days(), 10);
+        assert_eq!(
+            interval.micros(),
+            2 * MICROS_PER_HOUR + 30 * MICROS_PER_MINUTE + 500_000
+        );
+        assert_eq!(interval.to_string(), "P1Y2M10DT2H30M0.5S");
+        assert_eq!(Interval::parse("-P3W").unwrap().to_string(), "-P21D");
+        assert_eq!(Interval::parse("P1M-1D").unwrap().to_string(), "P1M-1D");
+        assert_eq!(Interval::ZERO.to_string(), "PT0S");
+
+        let month = Interval::parse("P1M").unwrap();
+        assert!(Interval::parse("P29D").unwrap() < month);
+        assert!(Interval::parse("P30D").unwrap() < month);
+        assert!(month < Interval::parse("P30DT1S").unwrap());
+        assert!(Interval::parse("P").is_err());
+        assert!(Interval::parse("P1.5D").is_err());
+    }
+
+    #[test]
+    fn offsets_split_timestamps_into_local_dates_and_times() {
+        // 2024-03-10T23:30:00Z
+        let timestamp = Date::parse("2024-03-10")
+            .unwrap()
+            .at(Time::parse("23:30").unwrap(), UtcOffset::UTC)
+            .unwrap();
+        let berlin = UtcOffset::parse("+01:00").unwrap();
+        assert_eq!(
+            Date::of_timestamp(timestamp, berlin).to_string(),
+            "2024-03-11"
+        );
+        assert_eq!(
+            Time::of_timestamp(timestamp, berlin).to_string(),
+            "00:30:00"
+        );
+        let new_york = UtcOffset::parse("-0500").unwrap();
+        assert_eq!(
+            Date::of_timestamp(timestamp, new_york).to_string(),
+            "2024-03-10"
+        );
+        assert_eq!(new_york.to_string(), "-05:00");
+
+        // One month after local Jan 31st 00:30 in Berlin is local Feb 29th 00:30.
+        let local_jan_31 = Date::parse("2024-01-31")
+            .unwrap()
+            .at(Time::parse("00:30").unwrap(), berlin)
+            .unwrap();
+        let next = Interval::parse("P1M")
+            .unwrap()
+            .add_to_timestamp(local_jan_31, berlin)
+            .unwrap();
+        assert_eq!(Date::of_timestamp(next, berlin).to_string(), "2024-02-29");
+        assert_eq!(Time::of_timestamp(next, berlin).to_string(), "00:30:00");
+    }
+}
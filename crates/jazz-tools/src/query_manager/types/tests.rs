@@ -210,6 +210,148 @@ fn row_descriptor_hash_changes_when_merge_strategy_changes() {
     );
 }
 
+#[test]
+fn column_descriptor_deserializes_payload_with_limits() {
+    let col: ColumnDescriptor = serde_json::from_str(
+        r#"{
+            "name":"body",
+            "column_type":{"type":"Text"},
+            "nullable":false,
+            "limits":{"max_bytes":4096}
+        }"#,
+    )
+    .expect("deserialize column descriptor with limits");
+
+    assert_eq!(col.limits.max_bytes, Some(4096));
+    assert_eq!(col.limits.max_items, None);
+}
+
+#[test]
+fn schema_hash_changes_when_tables_or_columns_are_internal() {
+    let plain = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("name", ColumnType::Text)
+                .column("notes", ColumnType::Text),
+        )
+        .build();
+    let internal_column = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("name", ColumnType::Text)
+                .internal_column("notes", ColumnType::Text),
+        )
+        .build();
+    let internal_table = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("name", ColumnType::Text)
+                .column("notes", ColumnType::Text)
+                .internal(),
+        )
+        .build();
+
+    assert_ne!(
+        SchemaHash::compute(&plain),
+        SchemaHash::compute(&internal_column)
+    );
+    assert_ne!(
+        SchemaHash::compute(&plain),
+        SchemaHash::compute(&internal_table)
+    );
+    assert_ne!(
+        SchemaHash::compute(&internal_column),
+        SchemaHash::compute(&internal_table)
+    );
+}
+
+#[test]
+fn schema_hash_only_includes_hashed_annotations() {
+    let users = |column: ColumnDescriptor| {
+        SchemaBuilder::new()
+            .table(TableSchema::builder("users").column_descriptor(column))
+            .build()
+    };
+    let plain = users(ColumnDescriptor::new("email", ColumnType::Text));
+    let labelled =
+        users(ColumnDescriptor::new("email", ColumnType::Text).annotate("label", "Email"));
+    let classified =
+        users(ColumnDescriptor::new("email", ColumnType::Text).annotate_hashed("pii", "contact"));
+    let reclassified =
+        users(ColumnDescriptor::new("email", ColumnType::Text).annotate_hashed("pii", "none"));
+    let labelled_table = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("email", ColumnType::Text)
+                .annotate("label", "Users"),
+        )
+        .build();
+    let classified_table = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("email", ColumnType::Text)
+                .annotate_hashed("retention", "30d"),
+        )
+        .build();
+
+    assert_eq!(SchemaHash::compute(&plain), SchemaHash::compute(&labelled));
+    assert_eq!(
+        SchemaHash::compute(&plain),
+        SchemaHash::compute(&labelled_table)
+    );
+    assert_ne!(
+        SchemaHash::compute(&plain),
+        SchemaHash::compute(&classified)
+    );
+    assert_ne!(
+        SchemaHash::compute(&classified),
+        SchemaHash::compute(&reclassified)
+    );
+    assert_ne!(
+        SchemaHash::compute(&plain),
+        SchemaHash::compute(&classified_table)
+    );
+}
+
+#[test]
+fn schema_hash_only_changes_when_limits_are_declared() {
+    let plain = SchemaBuilder::new()
+        .table(TableSchema::builder("notes").column("body", ColumnType::Text))
+        .build();
+    let mut explicit_unbounded = plain.clone();
+    explicit_unbounded
+        .get_mut(&TableName::new("notes"))
+        .unwrap()
+        .limits = TableLimits::default();
+    let quota = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("notes")
+                .column("body", ColumnType::Text)
+                .max_rows_per_user(100),
+        )
+        .build();
+    let value_limit = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("notes")
+                .column_descriptor(ColumnDescriptor::new("body", ColumnType::Text).max_bytes(4096)),
+        )
+        .build();
+
+    assert_eq!(
+        SchemaHash::compute(&plain),
+        SchemaHash::compute(&explicit_unbounded)
+    );
+    assert_ne!(SchemaHash::compute(&plain), SchemaHash::compute(&quota));
+    assert_ne!(
+        SchemaHash::compute(&plain),
+        SchemaHash::compute(&value_limit)
+    );
+    assert_ne!(
+        SchemaHash::compute(&quota),
+        SchemaHash::compute(&value_limit)
+    );
+}
+
 #[test]
 fn tuple_element_row() {
     let id = crate::object::ObjectId::from_uuid(Uuid::from_u128(42));
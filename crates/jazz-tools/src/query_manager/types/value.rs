@@ -17,9 +17,21 @@ pub enum Value {
     BigInt(i64),
     /// 8-byte IEEE 754 double-precision float.
     Double(f64),
+    /// Exact fixed-point number.
+    Decimal(Decimal),
     Boolean(bool),
     Text(String),
     Timestamp(u64),
+    /// Calendar date.
+    Date(Date),
+    /// Time of day.
+    Time(Time),
+    /// Span of months, days and microseconds.
+    Interval(Interval),
+    /// Latitude/longitude pair.
+    GeoPoint(GeoPoint),
+    /// Fixed-dimension embedding.
+    Vector(Vector),
     Uuid(ObjectId),
     BatchId([u8; 16]),
     Bytea(Vec<u8>),
@@ -43,9 +55,15 @@ enum ValueHuman {
     Integer(i32),
     BigInt(i64),
     Double(f64),
+    Decimal(Decimal),
     Boolean(bool),
     Text(String),
     Timestamp(#[serde(deserialize_with = "deserialize_timestamp_value")] u64),
+    Date(Date),
+    Time(Time),
+    Interval(Interval),
+    GeoPoint(GeoPoint),
+    Vector(Vector),
     Uuid(ObjectId),
     BatchId([u8; 16]),
     Bytea(Vec<u8>),
@@ -62,6 +80,7 @@ struct RowHuman {
 }
 
 /// Use externally-tagged enum for binary serialization (postcard does not support internally-tagged enums).
+/// Postcard encodes variants by index, so new variants must be appended.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 enum ValueBinary {
     Integer(i32),
@@ -76,6 +95,12 @@ enum ValueBinary {
     Array(Vec<ValueBinary>),
     Row(Vec<ValueBinary>),
     Null,
+    Date(Date),
+    Time(Time),
+    Interval(Interval),
+    GeoPoint(GeoPoint),
+    Vector(Vector),
+    Decimal(Decimal),
 }
 
 fn deserialize_timestamp_value<'de, D>(deserializer: D) -> Result<u64, D::Error>
@@ -133,9 +158,15 @@ impl From<&Value> for ValueHuman {
             Value::Integer(v) => ValueHuman::Integer(*v),
             Value::BigInt(v) => ValueHuman::BigInt(*v),
             Value::Double(v) => ValueHuman::Double(*v),
+            Value::Decimal(v) => ValueHuman::Decimal(*v),
             Value::Boolean(v) => ValueHuman::Boolean(*v),
             Value::Text(v) => ValueHuman::Text(v.clone()),
             Value::Timestamp(v) => ValueHuman::Timestamp(*v),
+            Value::Date(v) => ValueHuman::Date(*v),
+            Value::Time(v) => ValueHuman::Time(*v),
+            Value::Interval(v) => ValueHuman::Interval(*v),
+            Value::GeoPoint(v) => ValueHuman::GeoPoint(*v),
+            Value::Vector(v) => ValueHuman::Vector(v.clone()),
             Value::Uuid(v) => ValueHuman::Uuid(*v),
             Value::BatchId(v) => ValueHuman::BatchId(*v),
             Value::Bytea(v) => ValueHuman::Bytea(v.clone()),
@@ -155,9 +186,15 @@ impl From<ValueHuman> for Value {
             ValueHuman::Integer(v) => Value::Integer(v),
             ValueHuman::BigInt(v) => Value::BigInt(v),
             ValueHuman::Double(v) => Value::Double(v),
+            ValueHuman::Decimal(v) => Value::Decimal(v),
             ValueHuman::Boolean(v) => Value::Boolean(v),
             ValueHuman::Text(v) => Value::Text(v),
             ValueHuman::Timestamp(v) => Value::Timestamp(v),
+            ValueHuman::Date(v) => Value::Date(v),
+            ValueHuman::Time(v) => Value::Time(v),
+            ValueHuman::Interval(v) => Value::Interval(v),
+            ValueHuman::GeoPoint(v) => Value::GeoPoint(v),
+            ValueHuman::Vector(v) => Value::Vector(v),
             ValueHuman::Uuid(v) => Value::Uuid(v),
             ValueHuman::BatchId(v) => Value::BatchId(v),
             ValueHuman::Bytea(v) => Value::Bytea(v),
@@ -177,9 +214,15 @@ impl From<&Value> for ValueBinary {
             Value::Integer(v) => ValueBinary::Integer(*v),
             Value::BigInt(v) => ValueBinary::BigInt(*v),
             Value::Double(v) => ValueBinary::Double(*v),
+            Value::Decimal(v) => ValueBinary::Decimal(*v),
             Value::Boolean(v) => ValueBinary::Boolean(*v),
             Value::Text(v) => ValueBinary::Text(v.clone()),
             Value::Timestamp(v) => ValueBinary::Timestamp(*v),
+            Value::Date(v) => ValueBinary::Date(*v),
+            Value::Time(v) => ValueBinary::Time(*v),
+            Value::Interval(v) => ValueBinary::Interval(*v),
+            Value::GeoPoint(v) => ValueBinary::GeoPoint(*v),
+            Value::Vector(v) => ValueBinary::Vector(v.clone()),
             Value::Uuid(v) => ValueBinary::Uuid(*v),
             Value::BatchId(v) => ValueBinary::BatchId(*v),
             Value::Bytea(v) => ValueBinary::Bytea(v.clone()),
@@ -198,9 +241,15 @@ impl From<ValueBinary> for Value {
             ValueBinary::Integer(v) => Value::Integer(v),
             ValueBinary::BigInt(v) => Value::BigInt(v),
             ValueBinary::Double(v) => Value::Double(v),
+            ValueBinary::Decimal(v) => Value::Decimal(v),
             ValueBinary::Boolean(v) => Value::Boolean(v),
             ValueBinary::Text(v) => Value::Text(v),
             ValueBinary::Timestamp(v) => Value::Timestamp(v),
+            ValueBinary::Date(v) => Value::Date(v),
+            ValueBinary::Time(v) => Value::Time(v),
+            ValueBinary::Interval(v) => Value::Interval(v),
+            ValueBinary::GeoPoint(v) => Value::GeoPoint(v),
+            ValueBinary::Vector(v) => Value::Vector(v),
             ValueBinary::Uuid(v) => Value::Uuid(v),
             ValueBinary::BatchId(v) => Value::BatchId(v),
             ValueBinary::Bytea(v) => Value::Bytea(v),
@@ -246,9 +295,15 @@ impl PartialEq for Value {
             (Value::Integer(a), Value::Integer(b)) => a == b,
             (Value::BigInt(a), Value::BigInt(b)) => a == b,
             (Value::Double(a), Value::Double(b)) => a.to_bits() == b.to_bits(),
+            (Value::Decimal(a), Value::Decimal(b)) => a == b,
             (Value::Boolean(a), Value::Boolean(b)) => a == b,
             (Value::Text(a), Value::Text(b)) => a == b,
             (Value::Timestamp(a), Value::Timestamp(b)) => a == b,
+            (Value::Date(a), Value::Date(b)) => a == b,
+            (Value::Time(a), Value::Time(b)) => a == b,
+            (Value::Interval(a), Value::Interval(b)) => a == b,
+            (Value::GeoPoint(a), Value::GeoPoint(b)) => a == b,
+            (Value::Vector(a), Value::Vector(b)) => a == b,
             (Value::Uuid(a), Value::Uuid(b)) => a == b,
             (Value::BatchId(a), Value::BatchId(b)) => a == b,
             (Value::Bytea(a), Value::Bytea(b)) => a == b,
@@ -279,9 +334,20 @@ impl Value {
             Value::Integer(_) => Some(ColumnType::Integer),
             Value::BigInt(_) => Some(ColumnType::BigInt),
             Value::Double(_) => Some(ColumnType::Double),
+            Value::Decimal(v) => Some(ColumnType::Decimal {
+                precision: DECIMAL_MAX_PRECISION,
+                scale: v.scale(),
+            }),
             Value::Boolean(_) => Some(ColumnType::Boolean),
             Value::Text(_) => Some(ColumnType::Text),
             Value::Timestamp(_) => Some(ColumnType::Timestamp),
+            Value::Date(_) => Some(ColumnType::Date),
+            Value::Time(_) => Some(ColumnType::Time),
+            Value::Interval(_) => Some(ColumnType::Interval),
+            Value::GeoPoint(_) => Some(ColumnType::GeoPoint),
+            Value::Vector(v) => Some(ColumnType::Vector {
+                dims: v.dims() as u32,
+            }),
             Value::Uuid(_) => Some(ColumnType::Uuid),
             Value::BatchId(_) => Some(ColumnType::BatchId),
             Value::Bytea(_) => Some(ColumnType::Bytea),
@@ -368,6 +434,42 @@ impl From<f64> for Value {
     }
 }
 
+impl From<Decimal> for Value {
+    fn from(v: Decimal) -> Self {
+        Value::Decimal(v)
+    }
+}
+
+impl From<Date> for Value {
+    fn from(v: Date) -> Self {
+        Value::Date(v)
+    }
+}
+
+impl From<Time> for Value {
+    fn from(v: Time) -> Self {
+        Value::Time(v)
+    }
+}
+
+impl From<Interval> for Value {
+    fn from(v: Interval) -> Self {
+        Value::Interval(v)
+    }
+}
+
+impl From<GeoPoint> for Value {
+    fn from(v: GeoPoint) -> Self {
+        Value::GeoPoint(v)
+    }
+}
+
+impl From<Vector> for Value {
+    fn from(v: Vector) -> Self {
+        Value::Vector(v)
+    }
+}
+
 impl From<&str> for Value {
     fn from(v: &str) -> Self {
         Value::Text(v.to_string())
@@ -559,6 +661,45 @@ mod tests {
         assert_eq!(v, Value::Text("hey".to_string()));
     }
 
+    // ── Binary encoding ─────────────────────────────────────────────
+
+    #[test]
+    fn binary_discriminants_are_stable() {
+        let cases = [
+            (Value::Integer(7), 0),
+            (Value::BigInt(7), 1),
+            (Value::Double(1.5), 2),
+            (Value::Boolean(true), 3),
+            (Value::Text("hi".to_string()), 4),
+            (Value::Timestamp(1_700_000_000_000_000), 5),
+            (Value::Uuid(ObjectId::new()), 6),
+            (Value::BatchId([9; 16]), 7),
+            (Value::Bytea(vec![1, 2]), 8),
+            (Value::Array(vec![Value::Integer(1)]), 9),
+            (
+                Value::Row {
+                    id: None,
+                    values: vec![Value::Null],
+                },
+                10,
+            ),
+            (Value::Null, 11),
+            (Value::Date(Date::from_days_since_epoch(19_782)), 12),
+            (Value::Time(Time::from_micros(49_500_250_000).unwrap()), 13),
+            (Value::Interval(Interval::new(14, 3, 14_706_000_000)), 14),
+            (Value::GeoPoint(GeoPoint::new(52.52, 13.405).unwrap()), 15),
+            (Value::Vector(Vector::new(vec![0.5, -1.0]).unwrap()), 16),
+            (Value::Decimal(Decimal::parse("-12.30").unwrap()), 17),
+        ];
+
+        for (value, discriminant) in cases {
+            let bytes = postcard::to_allocvec(&value).unwrap();
+            assert_eq!(bytes[0], discriminant, "{value:?}");
+            let decoded: Value = postcard::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded, value);
+        }
+    }
+
     // ── row_input! ──────────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,183 @@
+//! Fixed-dimension `f32` embeddings for `VECTOR(n)` columns.
+//!
+//! Rows store a vector as `dims` little-endian `f32`s. Nearest-neighbour
+//! queries rank rows by [`Vector::cosine_distance`], which lies in `[0, 2]`:
+//! `0` for vectors pointing the same way, `1` for orthogonal ones.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Why a vector literal was rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VectorError {
+    /// A vector needs at least one component.
+    Empty,
+    /// Component at `index` is NaN or infinite.
+    NonFinite { index: usize },
+    /// Text that is not a `[x, y, ...]` list of numbers.
+    Invalid(String),
+}
+
+impl fmt::Display for VectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VectorError::Empty => write!(f, "vector must have at least one component"),
+            VectorError::NonFinite { index } => {
+                write!(f, "vector component {index} is not a finite number")
+            }
+            VectorError::Invalid(raw) => {
+                write!(f, "invalid vector '{raw}', expected '[x, y, ...]'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VectorError {}
+
+/// An embedding with finite `f32` components.
+///
+/// Equality is bitwise on the components; `-0.0` is stored as `0.0`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "Vec<f32>", into = "Vec<f32>")]
+pub struct Vector(Vec<f32>);
+
+impl Vector {
+    pub fn new(components: Vec<f32>) -> Result<Self, VectorError> {
+        if components.is_empty() {
+            return Err(VectorError::Empty);
+        }
+        if let Some(index) = components.iter().position(|c| !c.is_finite()) {
+            return Err(VectorError::NonFinite { index });
+        }
+        Ok(Self(components.into_iter().map(|c| c + 0.0).collect()))
+    }
+
+    /// Parse `"[0.1, 0.2, 0.3]"`; the brackets are optional.
+    pub fn parse(raw: &str) -> Result<Self, VectorError> {
+        let trimmed = raw.trim();
+        let inner = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .unwrap_or(trimmed);
+        let components = inner
+            .split(',')
+            .map(|part| part.trim().parse::<f32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| VectorError::Invalid(raw.to_string()))?;
+        Self::new(components)
+    }
+
+    pub fn dims(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        &self.0
+    }
+
+    /// `1 - cos(angle)`. A zero vector is treated as orthogonal to everything.
+    /// Callers compare vectors of the same dimension; extra components of the
+    /// longer vector are ignored.
+    pub fn cosine_distance(&self, other: &Vector) -> f64 {
+        let mut dot = 0.0f64;
+        let mut norm_a = 0.0f64;
+        let mut norm_b = 0.0f64;
+        for (a, b) in self.0.iter().zip(&other.0) {
+            let (a, b) = (f64::from(*a), f64::from(*b));
+            dot += a * b;
+            norm_a += a * a;
+            norm_b += b * b;
+        }
+        if norm_a == 0.0 || norm_b == 0.0 {
+            return 1.0;
+        }
+        (1.0 - dot / (norm_a.sqrt() * norm_b.sqrt())).clamp(0.0, 2.0)
+    }
+}
+
+impl TryFrom<Vec<f32>> for Vector {
+    type Error = VectorError;
+
+    fn try_from(components: Vec<f32>) -> Result<Self, Self::Error> {
+        Vector::new(components)
+    }
+}
+
+impl From<Vector> for Vec<f32> {
+    fn from(vector: Vector) -> Self {
+        vector.0
+    }
+}
+
+impl PartialEq for Vector {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(&other.0)
+                .all(|(a, b)| a.to_bits() == b.to_bits())
+    }
+}
+
+impl Eq for Vector {}
+
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (index, component) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{component}")?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl FromStr for Vector {
+    type Err = VectorError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::parse(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(components: &[f32]) -> Vector {
+        Vector::new(components.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn vectors_validate_and_roundtrip_through_text() {
+        assert_eq!(Vector::parse("[1, 2.5, -3]"), Ok(vector(&[1.0, 2.5, -3.0])));
+        assert_eq!(Vector::parse("0.5,0.25"), Ok(vector(&[0.5, 0.25])));
+        assert_eq!(Vector::new(Vec::new()), Err(VectorError::Empty));
+        assert_eq!(
+            Vector::new(vec![1.0, f32::NAN]),
+            Err(VectorError::NonFinite { index: 1 })
+        );
+        assert!(matches!(
+            Vector::parse("[1, x]"),
+            Err(VectorError::Invalid(_))
+        ));
+        assert_eq!(vector(&[-0.0]), vector(&[0.0]));
+
+        let embedding = vector(&[0.125, -2.0, 3.5]);
+        assert_eq!(embedding.to_string().parse::<Vector>(), Ok(embedding));
+    }
+
+    #[test]
+    fn cosine_distance_ranks_by_angle() {
+        let x = vector(&[1.0, 0.0]);
+        assert_eq!(x.cosine_distance(&vector(&[3.0, 0.0])), 0.0);
+        assert!((x.cosine_distance(&vector(&[0.0, 2.0])) - 1.0).abs() < 1e-12);
+        assert!((x.cosine_distance(&vector(&[-1.0, 0.0])) - 2.0).abs() < 1e-12);
+        assert_eq!(x.cosine_distance(&vector(&[0.0, 0.0])), 1.0);
+    }
+}
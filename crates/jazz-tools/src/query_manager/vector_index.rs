@@ -0,0 +1,493 @@
+//! In-memory HNSW graph for nearest-neighbour queries over `Vector` columns.
+//!
+//! Embeddings have no useful total order, so vector columns are left out of
+//! the ordered storage indices (see `indices.rs`). Queries built with
+//! `QueryBuilder::nearest` rank rows through this graph instead; it is kept in
+//! step with the rows reaching the nearest node, one insert or remove per row
+//! change.
+//!
+//! Layers are derived from a hash of the row id rather than a random draw, so
+//! every replica that indexes the same rows builds the same graph and returns
+//! the same top-k.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::object::ObjectId;
+
+use super::types::Vector;
+
+/// Neighbours kept per node on the upper layers.
+const MAX_NEIGHBOURS: usize = 16;
+/// Neighbours kept per node on layer 0, which holds every row.
+const MAX_NEIGHBOURS_LAYER0: usize = 2 * MAX_NEIGHBOURS;
+/// Candidate list size while linking a new node.
+const EF_CONSTRUCTION: usize = 64;
+const MAX_LEVEL: usize = 16;
+
+/// Candidate list size used by nearest queries. Searches never use fewer
+/// candidates than the requested `k`.
+pub const DEFAULT_EF_SEARCH: usize = 64;
+
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    distance: f64,
+    id: ObjectId,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    /// Closer first; ties break on row id so results are deterministic.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+#[derive(Debug)]
+struct HnswNode {
+    vector: Vector,
+    /// Neighbour lists for layers `0..=level`.
+    neighbours: Vec<Vec<ObjectId>>,
+}
+
+impl HnswNode {
+    fn level(&self) -> usize {
+        self.neighbours.len() - 1
+    }
+}
+
+/// Hierarchical navigable small-world graph keyed by row id, ranking by
+/// [`Vector::cosine_distance`].
+#[derive(Debug, Default)]
+pub struct HnswIndex {
+    nodes: HashMap<ObjectId, HnswNode>,
+    entry_point: Option<ObjectId>,
+}
+
+impl HnswIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, id: ObjectId) -> bool {
+        self.nodes.contains_key(&id)
+    }
+
+    /// Add a row, replacing its previous vector if it was already indexed.
+    pub fn insert(&mut self, id: ObjectId, vector: Vector) {
+        self.remove(id);
+
+        let level = level_for(id);
+        let Some(entry) = self.entry_point else {
+            self.nodes.insert(
+                id,
+                HnswNode {
+                    vector,
+                    neighbours: vec![Vec::new(); level + 1],
+                },
+            );
+            self.entry_point = Some(id);
+            return;
+        };
+
+        let entry_level = self.nodes[&entry].level();
+        let mut closest = entry;
+        for layer in (level + 1..=entry_level).rev() {
+            closest = self.greedy_closest(&vector, closest, layer);
+        }
+
+        let mut neighbours = vec![Vec::new(); level + 1];
+        let mut entry_points = vec![closest];
+        for layer in (0..=level.min(entry_level)).rev() {
+            let found = self.search_layer(&vector, &entry_points, EF_CONSTRUCTION, layer);
+            neighbours[layer] = found
+                .iter()
+                .take(max_neighbours(layer))
+                .map(|candidate| candidate.id)
+                .collect();
+            entry_points = found.into_iter().map(|candidate| candidate.id).collect();
+        }
+
+        for (layer, layer_neighbours) in neighbours.iter().enumerate() {
+            for &neighbour in layer_neighbours {
+                self.link(neighbour, id, layer, &vector);
+            }
+        }
+        self.nodes.insert(id, HnswNode { vector, neighbours });
+        if level > entry_level {
+            self.entry_point = Some(id);
+        }
+    }
+
+    /// Drop a row. Its neighbours are relinked to each other so the graph
+    /// stays navigable; one-way links other nodes still hold to it are
+    /// skipped by searches.
+    pub fn remove(&mut self, id: ObjectId) -> bool {
+        let Some(removed) = self.nodes.remove(&id) else {
+            return false;
+        };
+
+        for (layer, layer_neighbours) in removed.neighbours.iter().enumerate() {
+            for &neighbour in layer_neighbours {
+                let Some(node) = self.nodes.get(&neighbour) else {
+                    continue;
+                };
+                let Some(current) = node.neighbours.get(layer) else {
+                    continue;
+                };
+                let mut candidates: Vec<ObjectId> = current
+                    .iter()
+                    .copied()
+                    .filter(|candidate| *candidate != id)
+                    .collect();
+                for &candidate in layer_neighbours {
+                    if candidate != neighbour && !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+                let relinked = self.closest_to(&node.vector, &candidates, max_neighbours(layer));
+                if let Some(node) = self.nodes.get_mut(&neighbour) {
+                    node.neighbours[layer] = relinked;
+                }
+            }
+        }
+
+        if self.entry_point == Some(id) {
+            self.entry_point = self
+                .nodes
+                .iter()
+                .max_by(|(a_id, a), (b_id, b)| a.level().cmp(&b.level()).then(b_id.cmp(a_id)))
+                .map(|(id, _)| *id);
+        }
+        true
+    }
+
+    /// Approximate `k` nearest rows, closest first, with their distances.
+    pub fn search(&self, query: &Vector, k: usize, ef: usize) -> Vec<(ObjectId, f64)> {
+        let Some(entry) = self.entry_point else {
+            return Vec::new();
+        };
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let mut closest = entry;
+        for layer in (1..=self.nodes[&entry].level()).rev() {
+            closest = self.greedy_closest(query, closest, layer);
+        }
+        self.search_layer(query, &[closest], ef.max(k), 0)
+            .into_iter()
+            .take(k)
+            .map(|candidate| (candidate.id, candidate.distance))
+            .collect()
+    }
+
+    fn distance(&self, query: &Vector, id: ObjectId) -> Option<f64> {
+        self.nodes
+            .get(&id)
+            .map(|node| query.cosine_distance(&node.vector))
+    }
+
+    fn neighbours(&self, id: ObjectId, layer: usize) -> &[ObjectId] {
+        self.nodes
+            .get(&id)
+            .and_then(|node| node.neighbours.get(layer))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Add `to` to `from`'s neighbour list on `layer`, pruning to the closest
+    /// `max_neighbours(layer)` when the list overflows.
+    fn link(&mut self, from: ObjectId, to: ObjectId, layer: usize, to_vector: &Vector) {
+        let Some(node) = self.nodes.get(&from) else {
+            return;
+        };
+        let Some(current) = node.neighbours.get(layer) else {
+            return;
+        };
+        if current.contains(&to) {
+            return;
+        }
+
+        let cap = max_neighbours(layer);
+        let linked = if current.len() < cap {
+            let mut linked = current.clone();
+            linked.push(to);
+            linked
+        } else {
+            let mut scored: Vec<Candidate> = current
+                .iter()
+                .filter_map(|&id| {
+                    self.distance(&node.vector, id)
+                        .map(|distance| Candidate { distance, id })
+                })
+                .collect();
+            scored.push(Candidate {
+                distance: node.vector.cosine_distance(to_vector),
+                id: to,
+            });
+            scored.sort();
+            scored.truncate(cap);
+            scored.into_iter().map(|candidate| candidate.id).collect()
+        };
+        if let Some(node) = self.nodes.get_mut(&from) {
+            node.neighbours[layer] = linked;
+        }
+    }
+
+    fn closest_to(&self, base: &Vector, ids: &[ObjectId], limit: usize) -> Vec<ObjectId> {
+        let mut scored: Vec<Candidate> = ids
+            .iter()
+            .filter_map(|&id| {
+                self.distance(base, id)
+                    .map(|distance| Candidate { distance, id })
+            })
+            .collect();
+        scored.sort();
+        scored.truncate(limit);
+        scored.into_iter().map(|candidate| candidate.id).collect()
+    }
+
+    /// Walk `layer` towards `query` until no neighbour is closer.
+    fn greedy_closest(&self, query: &Vector, start: ObjectId, layer: usize) -> ObjectId {
+        let mut best = Candidate {
+            distance: self.distance(query, start).unwrap_or(f64::INFINITY),
+            id: start,
+        };
+        loop {
+            let mut improved = false;
+            for &neighbour in self.neighbours(best.id, layer) {
+                if let Some(distance) = self.distance(query, neighbour) {
+                    let candidate = Candidate {
+                        distance,
+                        id: neighbour,
+                    };
+                    if candidate < best {
+                        best = candidate;
+                        improved = true;
+                    }
+                }
+            }
+            if !improved {
+                return best.id;
+            }
+        }
+    }
+
+    /// Best-first search of one layer keeping `ef` candidates, closest first.
+    fn search_layer(
+        &self,
+        query: &Vector,
+        entry_points: &[ObjectId],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited: HashSet<ObjectId> = entry_points.iter().copied().collect();
+        let mut frontier = BinaryHeap::new();
+        let mut results: BinaryHeap<Candidate> = BinaryHeap::new();
+        for &id in entry_points {
+            if let Some(distance) = self.distance(query, id) {
+                let candidate = Candidate { distance, id };
+                frontier.push(Reverse(candidate));
+                results.push(candidate);
+            }
+        }
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(Reverse(closest)) = frontier.pop() {
+            if results.len() >= ef
+                && let Some(furthest) = results.peek()
+                && closest > *furthest
+            {
+                break;
+            }
+            for &neighbour in self.neighbours(closest.id, layer) {
+                if !visited.insert(neighbour) {
+                    continue;
+                }
+                let Some(distance) = self.distance(query, neighbour) else {
+                    continue;
+                };
+                let candidate = Candidate {
+                    distance,
+                    id: neighbour,
+                };
+                if results.len() < ef
+                    || results.peek().is_some_and(|furthest| candidate < *furthest)
+                {
+                    frontier.push(Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        results.into_sorted_vec()
+    }
+}
+
+fn max_neighbours(layer: usize) -> usize {
+    if layer == 0 {
+        MAX_NEIGHBOURS_LAYER0
+    } else {
+        MAX_NEIGHBOURS
+    }
+}
+
+/// Geometric layer draw with `p = 1 / MAX_NEIGHBOURS`, seeded by the row id.
+fn level_for(id: ObjectId) -> usize {
+    let bits = id.uuid().as_u128();
+    let mut x = (bits as u64) ^ ((bits >> 64) as u64);
+    // splitmix64 finaliser
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+    let uniform = ((x >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+    let level = -uniform.ln() / (MAX_NEIGHBOURS as f64).ln();
+    (level as usize).min(MAX_LEVEL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(n: u128) -> ObjectId {
+        ObjectId::from_uuid(uuid::Uuid::from_u128(n))
+    }
+
+    /// Deterministic pseudo-random unit-ish vectors.
+    fn vectors(count: usize, dims: usize) -> Vec<Vector> {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        (0..count)
+            .map(|_| {
+                let components = (0..dims)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        (state % 2000) as f32 / 1000.0 - 1.0
+                    })
+                    .collect();
+                Vector::new(components).unwrap()
+            })
+            .collect()
+    }
+
+    fn brute_force(items: &[(ObjectId, Vector)], query: &Vector, k: usize) -> Vec<ObjectId> {
+        let mut scored: Vec<Candidate> = items
+            .iter()
+            .map(|(id, vector)| Candidate {
+                distance: query.cosine_distance(vector),
+                id: *id,
+            })
+            .collect();
+        scored.sort();
+        scored.into_iter().take(k).map(|c| c.id).collect()
+    }
+
+    #[test]
+    fn search_matches_brute_force_on_small_sets() {
+        let items: Vec<(ObjectId, Vector)> = vectors(40, 4)
+            .into_iter()
+            .enumerate()
+            .map(|(n, vector)| (id(n as u128 + 1), vector))
+            .collect();
+        let mut index = HnswIndex::new();
+        for (row, vector) in &items {
+            index.insert(*row, vector.clone());
+        }
+
+        for query in vectors(5, 4) {
+            let found: Vec<ObjectId> = index
+                .search(&query, 5, DEFAULT_EF_SEARCH)
+                .into_iter()
+                .map(|(row, _)| row)
+                .collect();
+            assert_eq!(found, brute_force(&items, &query, 5));
+        }
+    }
+
+    #[test]
+    fn recall_stays_high_on_larger_sets() {
+        let items: Vec<(ObjectId, Vector)> = vectors(1000, 8)
+            .into_iter()
+            .enumerate()
+            .map(|(n, vector)| (id(n as u128 + 1), vector))
+            .collect();
+        let mut index = HnswIndex::new();
+        for (row, vector) in &items {
+            index.insert(*row, vector.clone());
+        }
+
+        let mut hits = 0;
+        let queries = vectors(1020, 8).split_off(1000);
+        for query in &queries {
+            let expected = brute_force(&items, query, 10);
+            hits += index
+                .search(query, 10, DEFAULT_EF_SEARCH)
+                .iter()
+                .filter(|(row, _)| expected.contains(row))
+                .count();
+        }
+        assert!(hits >= queries.len() * 10 * 9 / 10, "recall {hits}/200");
+    }
+
+    #[test]
+    fn removed_rows_leave_the_graph_searchable() {
+        let items: Vec<(ObjectId, Vector)> = vectors(60, 3)
+            .into_iter()
+            .enumerate()
+            .map(|(n, vector)| (id(n as u128 + 1), vector))
+            .collect();
+        let mut index = HnswIndex::new();
+        for (row, vector) in &items {
+            index.insert(*row, vector.clone());
+        }
+
+        let (kept, dropped): (Vec<_>, Vec<_>) =
+            items.into_iter().enumerate().partition(|(n, _)| n % 2 == 0);
+        for (_, (row, _)) in &dropped {
+            assert!(index.remove(*row));
+        }
+        assert!(!index.remove(dropped[0].1.0));
+        assert_eq!(index.len(), kept.len());
+
+        let kept: Vec<(ObjectId, Vector)> = kept.into_iter().map(|(_, item)| item).collect();
+        for (_, query) in &kept[..5] {
+            let found: Vec<ObjectId> = index
+                .search(query, 3, DEFAULT_EF_SEARCH)
+                .into_iter()
+                .map(|(row, _)| row)
+                .collect();
+            assert_eq!(found, brute_force(&kept, query, 3));
+        }
+    }
+}
@@ -18,6 +18,7 @@ use crate::storage::{
 use crate::sync_manager::RowBatchKey;
 
 use super::encoding::{decode_column, decode_row, encode_row};
+use super::limits::check_encoded_row_limits_with_descriptor;
 use super::manager::{
     DeleteHandle, InsertResult, QueryError, QueryManager, SchemaWarningAccumulator,
     WriteTableCacheEntry,
@@ -59,6 +60,9 @@ struct RowBatchAuthoring<'a> {
     delete_kind: Option<DeleteKind>,
     row_state: RowState,
     batch_id: Option<BatchId>,
+    /// False when the write context pins `updated_at`; the batch then orders
+    /// by that timestamp, as batches without an HLC do.
+    stamp_hlc: bool,
 }
 
 struct PreparedLocalRowHistoryWrite<'a> {
@@ -109,6 +113,7 @@ impl QueryManager {
         let entry = Arc::new(WriteTableCacheEntry {
             descriptor: Arc::new(table_schema.columns.clone()),
             indexed_columns: table_schema.indexed_columns.clone().map(Arc::new),
+            limits: table_schema.limits,
             row_layout: compiled_row_layout(&table_schema.columns),
             row_locator: RowLocator {
                 table: table_name.as_str().to_string().into(),
@@ -183,6 +188,29 @@ impl QueryManager {
             .unwrap_or_else(|| self.reserve_write_timestamp())
     }
 
+    /// Timestamp for a write that supersedes the current version of `row_id`
+    /// on `branch`. The clock observes the HLCs of the superseded branch tips
+    /// first, so the new batch wins LWW against them even when they were
+    /// stamped by a node whose clock runs ahead of ours.
+    fn resolve_superseding_timestamp(
+        &mut self,
+        storage: &dyn Storage,
+        table: &str,
+        row_id: ObjectId,
+        branch: &str,
+        write_context: Option<&WriteContext>,
+    ) -> u64 {
+        let history_table = crate::storage::history_table_for_row(storage, row_id, table);
+        for batch_id in self.load_branch_tip_ids(storage, table, row_id, branch) {
+            if let Ok(Some(tip)) =
+                storage.load_history_row_batch(&history_table, branch, row_id, batch_id)
+            {
+                self.sync_manager.observe_hlc(tip.hlc());
+            }
+        }
+        self.resolve_update_timestamp(write_context)
+    }
+
     fn row_provenance_for_insert(
         &self,
         write_context: Option<&WriteContext>,
@@ -237,11 +265,12 @@ impl QueryManager {
             delete_kind,
             row_state: Self::resolve_write_row_state(write_context),
             batch_id: write_context.and_then(WriteContext::batch_id),
+            stamp_hlc: write_context.and_then(WriteContext::updated_at).is_none(),
         }
     }
 
     fn authored_row_batch(
-        &self,
+        &mut self,
         row_id: ObjectId,
         branch_name: &str,
         parents: impl IntoIterator<Item = BatchId>,
@@ -252,7 +281,7 @@ impl QueryManager {
             .into_iter()
             .collect();
 
-        if let Some(batch_id) = authoring.batch_id {
+        let mut row = if let Some(batch_id) = authoring.batch_id {
             StoredRowBatch::new_with_batch_id(
                 batch_id,
                 row_id,
@@ -275,7 +304,11 @@ impl QueryManager {
                 authoring.row_state,
                 None,
             )
+        };
+        if authoring.stamp_hlc {
+            row.hlc = Some(self.sync_manager.reserve_hlc());
         }
+        row
     }
 
     #[cfg(test)]
@@ -737,6 +770,11 @@ impl QueryManager {
 
         let new_data =
             encode_row(descriptor, values).map_err(|e| QueryError::EncodingError(e.to_string()))?;
+        check_encoded_row_limits_with_descriptor(descriptor, &table_write.limits, &new_data)
+            .map_err(|violation| QueryError::LimitExceeded {
+                table: table_name,
+                violation,
+            })?;
 
         if let Some(session) = write_context.and_then(WriteContext::session) {
             if let Some((auth_schema, auth_context)) =
@@ -973,28 +1011,47 @@ impl QueryManager {
             return None;
         }
 
-        Self::transform_row_with_schema(
-            id,
-            row.data.to_vec(),
-            row.batch_id(),
-            BranchName::new(&row.branch),
-            &mut transform_context,
+        let resolved =
+            Self::transform_row_with_schema(storage, id, &row, branches, &mut transform_context)?;
+        let resolved_table = resolve_current_table_name(
+            schema_context,
+            &table,
+            branch_schema_map.get(resolved.branch_name.as_str()),
         )
-        .map(|resolved| {
-            let resolved_table = resolve_current_table_name(
-                schema_context,
-                &table,
-                branch_schema_map.get(resolved.branch_name.as_str()),
-            )
-            .unwrap_or_else(|| table.clone());
-            (
-                resolved_table,
-                resolved.branch_name.as_str().to_string(),
-                resolved.content,
-                resolved.batch_id,
-                row.row_provenance(),
-            )
-        })
+        .unwrap_or_else(|| table.clone());
+        Some((
+            resolved_table,
+            resolved.branch_name.as_str().to_string(),
+            resolved.content,
+            resolved.batch_id,
+            row.row_provenance(),
+        ))
+    }
+
+    /// Branch holding a row's latest visible version, when that version has
+    /// content and isn't deleted there.
+    ///
+    /// Schema GC uses this to tell a row it couldn't carry onto the target
+    /// schema apart from one with nothing to carry.
+    pub fn visible_row_branch_in_context<H: Storage>(
+        &self,
+        storage: &H,
+        id: ObjectId,
+        branches: &[String],
+        schema_context: &SchemaContext,
+    ) -> Option<String> {
+        let branch_schema_map = Self::branch_schema_map_for_context(schema_context);
+        let (table, row) = self.load_best_visible_row_batch(
+            storage,
+            id,
+            branches,
+            None,
+            schema_context,
+            &branch_schema_map,
+        )?;
+        let branch = row.branch.as_str().to_string();
+        (!row.data.is_empty() && !self.row_is_deleted_on_branch(storage, &table, &branch, id))
+            .then_some(branch)
     }
 
     /// Insert a new row into a table.
@@ -1117,6 +1174,20 @@ impl QueryManager {
             });
         }
 
+        check_encoded_row_limits_with_descriptor(descriptor, &table_write.limits, &data).map_err(
+            |violation| QueryError::LimitExceeded {
+                table: table_name,
+                violation,
+            },
+        )?;
+        self.check_row_quota(
+            &*storage,
+            table,
+            branch,
+            &table_write.limits,
+            &provenance.created_by,
+        )?;
+
         if let Some(session) = write_context.and_then(WriteContext::session) {
             if let Some((auth_schema, auth_context)) =
                 self.local_write_authorization_context(branch, Some(session))
@@ -1876,7 +1947,8 @@ impl QueryManager {
             });
         }
 
-        let timestamp = self.resolve_update_timestamp(write_context);
+        let timestamp =
+            self.resolve_superseding_timestamp(&*storage, table, id, branch, write_context);
         let new_provenance =
             self.row_provenance_for_update(old_provenance_for_policy, write_context, timestamp);
         let prepared = self.prepare_update_write_for_schema(
@@ -2141,7 +2213,8 @@ impl QueryManager {
         }
 
         let parents = self.parent_ids_for_write(storage, table, id, branch, write_context);
-        let timestamp = self.resolve_update_timestamp(write_context);
+        let timestamp =
+            self.resolve_superseding_timestamp(&*storage, table, id, branch, write_context);
         let delete_provenance =
             self.row_provenance_for_update(old_provenance_for_policy, write_context, timestamp);
 
@@ -2274,13 +2347,19 @@ impl QueryManager {
 
         let new_data =
             encode_row(descriptor, values).map_err(|e| QueryError::EncodingError(e.to_string()))?;
+        check_encoded_row_limits_with_descriptor(descriptor, &table_write.limits, &new_data)
+            .map_err(|violation| QueryError::LimitExceeded {
+                table: table_name,
+                violation,
+            })?;
         let parents = self.load_branch_tip_ids(storage, table, id, branch);
         let old_provenance = self
             .load_row_provenance_on_branch(storage, id, branch)
             .ok_or_else(|| {
                 QueryError::EncodingError("missing row provenance on current tip".to_string())
             })?;
-        let timestamp = self.resolve_update_timestamp(write_context);
+        let timestamp =
+            self.resolve_superseding_timestamp(&*storage, table, id, branch, write_context);
         let row_provenance =
             self.row_provenance_for_update(&old_provenance, write_context, timestamp);
 
@@ -2385,7 +2464,8 @@ impl QueryManager {
             .ok_or_else(|| {
                 QueryError::EncodingError("missing row provenance on current tip".to_string())
             })?;
-        let timestamp = self.reserve_write_timestamp();
+        let timestamp =
+            self.resolve_superseding_timestamp(&*storage, &table, id, branch.as_str(), None);
         let delete_provenance = self.row_provenance_for_update(&old_provenance, None, timestamp);
 
         // Add commit with empty content + delete: hard metadata
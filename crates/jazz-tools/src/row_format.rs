@@ -3,7 +3,10 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex, OnceLock};
 
 use crate::object::ObjectId;
-use crate::query_manager::types::{ColumnDescriptor, ColumnType, RowDescriptor, Value};
+use crate::query_manager::types::{
+    ColumnDescriptor, ColumnType, Date, Decimal, GeoPoint, Interval, RowDescriptor, Time, Value,
+    Vector, parse_temporal_literal,
+};
 use uuid::Uuid;
 
 /// Maximum payload size allowed for a single BYTEA value (1 MiB).
@@ -543,8 +546,18 @@ fn value_matches_column_type(value: &Value, column_type: &ColumnType) -> bool {
         ColumnType::Integer => matches!(value, Value::Integer(_)),
         ColumnType::BigInt => matches!(value, Value::BigInt(_)),
         ColumnType::Double => matches!(value, Value::Double(_)),
+        ColumnType::Decimal { precision, scale } => {
+            matches!(value, Value::Decimal(decimal) if decimal.fits(*precision, *scale))
+        }
         ColumnType::Boolean => matches!(value, Value::Boolean(_)),
         ColumnType::Timestamp => matches!(value, Value::Timestamp(_)),
+        ColumnType::Date => matches!(value, Value::Date(_)),
+        ColumnType::Time => matches!(value, Value::Time(_)),
+        ColumnType::Interval => matches!(value, Value::Interval(_)),
+        ColumnType::GeoPoint => matches!(value, Value::GeoPoint(_)),
+        ColumnType::Vector { dims } => {
+            matches!(value, Value::Vector(v) if v.dims() == *dims as usize)
+        }
         ColumnType::Uuid => matches!(value, Value::Uuid(_)),
         ColumnType::BatchId => {
             matches!(value, Value::BatchId(_))
@@ -646,6 +659,73 @@ fn encode_enum_variant_index(variants: &[String], value: &str) -> Result<u8, Enc
         })
 }
 
+/// Units of a decimal at a `Decimal` column's scale. Values are validated
+/// against the column before encoding, so the rescale is exact.
+fn decimal_column_units(value: &Decimal, column_type: &ColumnType) -> i128 {
+    let ColumnType::Decimal { scale, .. } = column_type else {
+        return value.units();
+    };
+    value
+        .rescale(*scale)
+        .map(|rescaled| rescaled.units())
+        .unwrap_or_else(|| unreachable!("decimal {value} was validated against scale {scale}"))
+}
+
+/// Encode a decimal filter literal for a `Decimal` column: 16 bytes of units
+/// at the column scale, or 17 bytes (units, then their own scale) when the
+/// literal has more fractional digits than the column can store.
+fn encode_decimal_literal(value: &Decimal, column_scale: u8) -> Vec<u8> {
+    match value.rescale(column_scale) {
+        Some(rescaled) => rescaled.units().to_le_bytes().to_vec(),
+        None => {
+            let mut bytes = value.units().to_le_bytes().to_vec();
+            bytes.push(value.scale());
+            bytes
+        }
+    }
+}
+
+fn decode_decimal_literal(bytes: &[u8], column_scale: u8) -> Option<Decimal> {
+    let units = i128::from_le_bytes(bytes.get(..16)?.try_into().ok()?);
+    let scale = bytes.get(16).copied().unwrap_or(column_scale);
+    Decimal::new(units, scale)
+}
+
+/// Append the little-endian column bytes of a `Date`, `Time` or `Interval`.
+fn encode_temporal_into(buf: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Date(date) => buf.extend_from_slice(&date.days_since_epoch().to_le_bytes()),
+        Value::Time(time) => buf.extend_from_slice(&time.micros_since_midnight().to_le_bytes()),
+        Value::Interval(interval) => {
+            buf.extend_from_slice(&interval.months().to_le_bytes());
+            buf.extend_from_slice(&interval.days().to_le_bytes());
+            buf.extend_from_slice(&interval.micros().to_le_bytes());
+        }
+        other => unreachable!("not a temporal value: {other:?}"),
+    }
+}
+
+/// Append a `GeoPoint` as little-endian latitude then longitude.
+fn encode_geo_point_into(buf: &mut Vec<u8>, point: &GeoPoint) {
+    buf.extend_from_slice(&point.lat().to_le_bytes());
+    buf.extend_from_slice(&point.lon().to_le_bytes());
+}
+
+/// Append a `Vector` as little-endian `f32` components.
+fn encode_vector_into(buf: &mut Vec<u8>, vector: &Vector) {
+    for component in vector.as_slice() {
+        buf.extend_from_slice(&component.to_le_bytes());
+    }
+}
+
+fn decode_interval(bytes: &[u8]) -> Interval {
+    Interval::new(
+        i32::from_le_bytes(bytes[..4].try_into().unwrap()),
+        i32::from_le_bytes(bytes[4..8].try_into().unwrap()),
+        i64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+    )
+}
+
 /// Encode a fixed-size value to the buffer.
 fn encode_fixed_value(buf: &mut Vec<u8>, col: &ColumnDescriptor, val: &Value) {
     if col.nullable {
@@ -676,8 +756,14 @@ fn encode_fixed_value(buf: &mut Vec<u8>, col: &ColumnDescriptor, val: &Value) {
         Value::Integer(n) => buf.extend_from_slice(&n.to_le_bytes()),
         Value::BigInt(n) => buf.extend_from_slice(&n.to_le_bytes()),
         Value::Double(f) => buf.extend_from_slice(&f.to_le_bytes()),
+        Value::Decimal(d) => {
+            buf.extend_from_slice(&decimal_column_units(d, &col.column_type).to_le_bytes())
+        }
         Value::Boolean(b) => buf.push(if *b { 1 } else { 0 }),
         Value::Timestamp(t) => buf.extend_from_slice(&t.to_le_bytes()),
+        Value::Date(_) | Value::Time(_) | Value::Interval(_) => encode_temporal_into(buf, val),
+        Value::GeoPoint(point) => encode_geo_point_into(buf, point),
+        Value::Vector(vector) => encode_vector_into(buf, vector),
         Value::Uuid(id) => buf.extend_from_slice(id.uuid().as_bytes()),
         Value::BatchId(bytes) => buf.extend_from_slice(bytes),
         Value::Bytea(bytes) if matches!(col.column_type, ColumnType::BatchId) => {
@@ -829,6 +915,19 @@ fn decode_non_null_value(
                 data[..8].try_into().unwrap(),
             )))
         }
+        ColumnType::Decimal { scale, .. } => {
+            if data.len() < 16 {
+                return Err(EncodingError::MalformedData {
+                    message: context.too_short_message("decimal"),
+                });
+            }
+            let units = i128::from_le_bytes(data[..16].try_into().unwrap());
+            Decimal::new(units, *scale)
+                .map(Value::Decimal)
+                .ok_or_else(|| EncodingError::MalformedData {
+                    message: format!("decimal out of range: {units} at scale {scale}"),
+                })
+        }
         ColumnType::Boolean => {
             if data.is_empty() {
                 return Err(EncodingError::MalformedData {
@@ -847,6 +946,68 @@ fn decode_non_null_value(
                 data[..8].try_into().unwrap(),
             )))
         }
+        ColumnType::Date => {
+            if data.len() < 4 {
+                return Err(EncodingError::MalformedData {
+                    message: context.too_short_message("date"),
+                });
+            }
+            Ok(Value::Date(Date::from_days_since_epoch(
+                i32::from_le_bytes(data[..4].try_into().unwrap()),
+            )))
+        }
+        ColumnType::Time => {
+            if data.len() < 8 {
+                return Err(EncodingError::MalformedData {
+                    message: context.too_short_message("time"),
+                });
+            }
+            let micros = u64::from_le_bytes(data[..8].try_into().unwrap());
+            Time::from_micros(micros)
+                .map(Value::Time)
+                .ok_or_else(|| EncodingError::MalformedData {
+                    message: format!("time of day out of range: {micros} microseconds"),
+                })
+        }
+        ColumnType::Interval => {
+            if data.len() < 16 {
+                return Err(EncodingError::MalformedData {
+                    message: context.too_short_message("interval"),
+                });
+            }
+            Ok(Value::Interval(decode_interval(&data[..16])))
+        }
+        ColumnType::GeoPoint => {
+            if data.len() < 16 {
+                return Err(EncodingError::MalformedData {
+                    message: context.too_short_message("geo point"),
+                });
+            }
+            let lat = f64::from_le_bytes(data[..8].try_into().unwrap());
+            let lon = f64::from_le_bytes(data[8..16].try_into().unwrap());
+            GeoPoint::new(lat, lon)
+                .map(Value::GeoPoint)
+                .map_err(|error| EncodingError::MalformedData {
+                    message: error.to_string(),
+                })
+        }
+        ColumnType::Vector { dims } => {
+            let len = *dims as usize * 4;
+            if data.len() < len {
+                return Err(EncodingError::MalformedData {
+                    message: context.too_short_message("vector"),
+                });
+            }
+            let components = data[..len]
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+                .collect();
+            Vector::new(components).map(Value::Vector).map_err(|error| {
+                EncodingError::MalformedData {
+                    message: error.to_string(),
+                }
+            })
+        }
         ColumnType::Uuid => {
             if data.len() < 16 {
                 return Err(EncodingError::MalformedData {
@@ -1189,26 +1350,40 @@ pub fn compare_column(
             let f2 = f64::from_le_bytes(bytes2[..8].try_into().unwrap());
             Ok(f1.total_cmp(&f2))
         }
+        ColumnType::Decimal { .. } => {
+            // Both sides are stored at the column scale.
+            let d1 = i128::from_le_bytes(bytes1[..16].try_into().unwrap());
+            let d2 = i128::from_le_bytes(bytes2[..16].try_into().unwrap());
+            Ok(d1.cmp(&d2))
+        }
         ColumnType::Boolean => {
             let b1 = bytes1[0] != 0;
             let b2 = bytes2[0] != 0;
             Ok(b1.cmp(&b2))
         }
-        ColumnType::Timestamp => {
+        ColumnType::Timestamp | ColumnType::Time => {
             let t1 = u64::from_le_bytes(bytes1[..8].try_into().unwrap());
             let t2 = u64::from_le_bytes(bytes2[..8].try_into().unwrap());
             Ok(t1.cmp(&t2))
         }
+        ColumnType::Date => {
+            let d1 = i32::from_le_bytes(bytes1[..4].try_into().unwrap());
+            let d2 = i32::from_le_bytes(bytes2[..4].try_into().unwrap());
+            Ok(d1.cmp(&d2))
+        }
+        ColumnType::Interval => Ok(decode_interval(bytes1).cmp(&decode_interval(bytes2))),
         ColumnType::Uuid => {
             // Compare as bytes (UUIDs have natural byte ordering)
             Ok(bytes1.cmp(bytes2))
         }
         ColumnType::BatchId => Ok(bytes1.cmp(bytes2)),
-        ColumnType::Bytea => Err(EncodingError::UnsupportedComparison {
-            column: col.name_str().to_string(),
-            column_type: col.column_type.clone(),
-            operation: "ordering".to_string(),
-        }),
+        ColumnType::Bytea | ColumnType::GeoPoint | ColumnType::Vector { .. } => {
+            Err(EncodingError::UnsupportedComparison {
+                column: col.name_str().to_string(),
+                column_type: col.column_type.clone(),
+                operation: "ordering".to_string(),
+            })
+        }
         ColumnType::Text
         | ColumnType::Json { schema: _ }
         | ColumnType::Enum { variants: _ }
@@ -1252,22 +1427,40 @@ pub fn compare_column_to_value(
             let f2 = f64::from_le_bytes(value[..8].try_into().unwrap());
             Ok(f1.total_cmp(&f2))
         }
+        ColumnType::Decimal { scale, .. } => {
+            let stored = decode_decimal_literal(bytes, *scale);
+            let literal = decode_decimal_literal(value, *scale);
+            match (stored, literal) {
+                (Some(stored), Some(literal)) => Ok(stored.cmp(&literal)),
+                _ => Err(EncodingError::MalformedData {
+                    message: "decimal comparison operand out of range".to_string(),
+                }),
+            }
+        }
         ColumnType::Boolean => {
             let b1 = bytes[0] != 0;
             let b2 = value[0] != 0;
             Ok(b1.cmp(&b2))
         }
-        ColumnType::Timestamp => {
+        ColumnType::Timestamp | ColumnType::Time => {
             let t1 = u64::from_le_bytes(bytes[..8].try_into().unwrap());
             let t2 = u64::from_le_bytes(value[..8].try_into().unwrap());
             Ok(t1.cmp(&t2))
         }
+        ColumnType::Date => {
+            let d1 = i32::from_le_bytes(bytes[..4].try_into().unwrap());
+            let d2 = i32::from_le_bytes(value[..4].try_into().unwrap());
+            Ok(d1.cmp(&d2))
+        }
+        ColumnType::Interval => Ok(decode_interval(bytes).cmp(&decode_interval(value))),
         ColumnType::BatchId => Ok(bytes.cmp(value)),
-        ColumnType::Bytea => Err(EncodingError::UnsupportedComparison {
-            column: col.name_str().to_string(),
-            column_type: col.column_type.clone(),
-            operation: "ordering".to_string(),
-        }),
+        ColumnType::Bytea | ColumnType::GeoPoint | ColumnType::Vector { .. } => {
+            Err(EncodingError::UnsupportedComparison {
+                column: col.name_str().to_string(),
+                column_type: col.column_type.clone(),
+                operation: "ordering".to_string(),
+            })
+        }
         ColumnType::Uuid
         | ColumnType::Text
         | ColumnType::Json { schema: _ }
@@ -1310,8 +1503,24 @@ pub fn encode_value(value: &Value) -> Vec<u8> {
         Value::Integer(n) => n.to_le_bytes().to_vec(),
         Value::BigInt(n) => n.to_le_bytes().to_vec(),
         Value::Double(f) => f.to_le_bytes().to_vec(),
+        Value::Decimal(d) => d.units().to_le_bytes().to_vec(),
         Value::Boolean(b) => vec![if *b { 1 } else { 0 }],
         Value::Timestamp(t) => t.to_le_bytes().to_vec(),
+        Value::Date(_) | Value::Time(_) | Value::Interval(_) => {
+            let mut buf = Vec::with_capacity(16);
+            encode_temporal_into(&mut buf, value);
+            buf
+        }
+        Value::GeoPoint(point) => {
+            let mut buf = Vec::with_capacity(16);
+            encode_geo_point_into(&mut buf, point);
+            buf
+        }
+        Value::Vector(vector) => {
+            let mut buf = Vec::with_capacity(vector.dims() * 4);
+            encode_vector_into(&mut buf, vector);
+            buf
+        }
         Value::Uuid(id) => id.uuid().as_bytes().to_vec(),
         Value::BatchId(bytes) => bytes.to_vec(),
         Value::Text(s) => s.as_bytes().to_vec(),
@@ -1328,9 +1537,32 @@ pub fn encode_value_with_type(value: &Value, col_type: &ColumnType) -> Vec<u8> {
         (Value::Text(raw), ColumnType::Uuid) => Uuid::parse_str(raw)
             .map(|uuid| ObjectId::from_uuid(uuid).uuid().as_bytes().to_vec())
             .unwrap_or_else(|_| INVALID_UUID_TEXT_SENTINEL.to_vec()),
+        (Value::Decimal(d), ColumnType::Decimal { scale, .. }) => encode_decimal_literal(d, *scale),
+        (Value::Integer(n), ColumnType::Decimal { scale, .. }) => {
+            encode_decimal_literal(&Decimal::from(*n), *scale)
+        }
+        (Value::BigInt(n), ColumnType::Decimal { scale, .. }) => {
+            encode_decimal_literal(&Decimal::from(*n), *scale)
+        }
         (Value::Text(raw), ColumnType::Enum { variants }) if col_type.fixed_size().is_some() => {
             vec![encode_enum_variant_index(variants, raw).unwrap_or_else(|_| unreachable!())]
         }
+        (Value::Text(raw), ColumnType::Date | ColumnType::Time | ColumnType::Interval) => {
+            match parse_temporal_literal(raw, col_type) {
+                Some(Ok(parsed)) => encode_value(&parsed),
+                // Query compilation rejects unparsable literals; keep the
+                // width right so comparisons cannot read past the literal.
+                _ => vec![0; col_type.fixed_size().unwrap_or_default()],
+            }
+        }
+        (Value::Text(raw), ColumnType::GeoPoint) => match GeoPoint::parse(raw) {
+            Ok(point) => encode_value(&Value::GeoPoint(point)),
+            Err(_) => vec![0; 16],
+        },
+        (Value::Text(raw), ColumnType::Vector { dims }) => match Vector::parse(raw) {
+            Ok(vector) if vector.dims() == *dims as usize => encode_value(&Value::Vector(vector)),
+            _ => vec![0; *dims as usize * 4],
+        },
         (Value::Row { id, values }, ColumnType::Row { columns: desc }) => {
             let mut buf = Vec::new();
             // Encode optional row id: 1-byte flag + 16-byte UUID if present
@@ -1454,11 +1686,19 @@ fn encode_value_with_type_into(buf: &mut Vec<u8>, value: &Value, col_type: &Colu
         (Value::Text(raw), ColumnType::Enum { variants }) if col_type.fixed_size().is_some() => {
             buf.push(encode_enum_variant_index(variants, raw).unwrap_or_else(|_| unreachable!()));
         }
+        (Value::Decimal(d), ColumnType::Decimal { .. }) => {
+            buf.extend_from_slice(&decimal_column_units(d, col_type).to_le_bytes())
+        }
         (Value::Integer(n), _) => buf.extend_from_slice(&n.to_le_bytes()),
         (Value::BigInt(n), _) => buf.extend_from_slice(&n.to_le_bytes()),
         (Value::Double(f), _) => buf.extend_from_slice(&f.to_le_bytes()),
         (Value::Boolean(b), _) => buf.push(if *b { 1 } else { 0 }),
         (Value::Timestamp(t), _) => buf.extend_from_slice(&t.to_le_bytes()),
+        (Value::Date(_) | Value::Time(_) | Value::Interval(_), _) => {
+            encode_temporal_into(buf, value)
+        }
+        (Value::GeoPoint(point), _) => encode_geo_point_into(buf, point),
+        (Value::Vector(vector), _) => encode_vector_into(buf, vector),
         (Value::Uuid(id), _) => buf.extend_from_slice(id.uuid().as_bytes()),
         (Value::BatchId(bytes), _) => buf.extend_from_slice(bytes),
         _ => buf.extend(encode_value_with_type(value, col_type)),
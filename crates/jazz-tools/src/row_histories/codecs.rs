@@ -17,7 +17,10 @@ use crate::row_format::{
 };
 use crate::sync_manager::DurabilityTier;
 
-use super::types::{BatchId, RowMetadata, RowState, StoredRowBatch, VisibleRowEntry};
+use super::types::{
+    BatchId, RowMetadata, RowState, StoredRowBatch, VisibleRowEntry, metadata_with_hlc,
+    split_hlc_metadata,
+};
 
 pub(super) fn tier_satisfies(
     confirmed_tier: Option<DurabilityTier>,
@@ -160,7 +163,7 @@ fn history_row_system_values(row: &StoredRowBatch) -> Vec<Value> {
             .map(delete_kind_to_value)
             .unwrap_or(Value::Null),
         Value::Boolean(row.is_deleted),
-        metadata_to_value(&row.metadata),
+        metadata_to_value(&metadata_with_hlc(&row.metadata, row.hlc)),
     ]
 }
 
@@ -778,6 +781,10 @@ pub(crate) fn decode_flat_history_row_with_codecs(
         Some(bytes) => SmallVec::from_vec(decode_batch_ids_array_bytes(bytes, "parents")?),
     };
 
+    let (metadata, hlc) = split_hlc_metadata(decode_metadata_bytes(column_bytes_with_layout(
+        descriptor, layout, data, 9,
+    )?)?);
+
     Ok(StoredRowBatch {
         row_id,
         batch_id,
@@ -787,6 +794,7 @@ pub(crate) fn decode_flat_history_row_with_codecs(
             decode_required_column_bytes(descriptor, layout, data, 1, "updated_at")?,
             "updated_at",
         )?,
+        hlc,
         created_by: decode_text_bytes(
             decode_required_column_bytes(descriptor, layout, data, 2, "created_by")?,
             "created_by",
@@ -810,7 +818,7 @@ pub(crate) fn decode_flat_history_row_with_codecs(
         delete_kind,
         is_deleted,
         data: user_data.into(),
-        metadata: decode_metadata_bytes(column_bytes_with_layout(descriptor, layout, data, 9)?)?,
+        metadata,
     })
 }
 
@@ -838,6 +846,7 @@ pub(crate) fn decode_flat_visible_row_entry_with_codecs(
             decode_required_column_bytes(descriptor, layout, data, 1, "updated_at")?,
             "updated_at",
         )?,
+        hlc: None,
         created_by: decode_text_bytes(
             decode_required_column_bytes(descriptor, layout, data, 2, "created_by")?,
             "created_by",
@@ -30,7 +30,9 @@ pub use codecs::{
 };
 pub(crate) use mutations::{ApplyRowBatchWithContext, apply_row_batch_with_context};
 pub use mutations::{apply_row_batch, patch_row_batch_state};
-pub(crate) use resolution::visible_row_preview_from_history_rows;
+pub(crate) use resolution::{
+    merge_lensed_write, visible_row_preview_from_history_rows, writer_parent_version,
+};
 pub use types::{
     ApplyRowBatchResult, BatchId, HistoryScan, QueryRowBatch, RowHistoryError, RowMetadata,
     RowState, RowVisibilityChange, StoredRowBatch, VisibleRowEntry,
@@ -50,6 +52,7 @@ mod tests {
     };
     use crate::row_format::{decode_row, encode_row};
     use crate::sync_manager::DurabilityTier;
+    use crate::sync_manager::clock::HlcTimestamp;
 
     fn visible_row(updated_at: u64, confirmed_tier: Option<DurabilityTier>) -> StoredRowBatch {
         StoredRowBatch::new(
@@ -1051,6 +1054,86 @@ mod tests {
         assert_eq!(decoded.metadata, row.metadata);
     }
 
+    #[test]
+    fn flat_history_row_binary_roundtrips_hlc() {
+        let user_descriptor = user_descriptor();
+        let mut row = StoredRowBatch::new(
+            ObjectId::from_uuid(Uuid::from_u128(45)),
+            "main",
+            Vec::new(),
+            encode_row(
+                &user_descriptor,
+                &[Value::Text("Ship".into()), Value::Boolean(true)],
+            )
+            .expect("encode user row"),
+            RowProvenance::for_insert("alice".to_string(), 100),
+            HashMap::from([("source".to_string(), "local".to_string())]),
+            RowState::VisibleDirect,
+            Some(DurabilityTier::Local),
+        );
+        row.hlc = Some(HlcTimestamp {
+            physical: 250,
+            logical: 2,
+        });
+
+        let encoded =
+            encode_flat_history_row(&user_descriptor, &row).expect("encode flat history row");
+        let decoded = decode_flat_history_row(
+            &user_descriptor,
+            row.row_id,
+            row.branch.as_str(),
+            row.batch_id(),
+            &encoded,
+        )
+        .expect("decode flat history row");
+
+        assert_eq!(decoded.hlc, row.hlc);
+        assert_eq!(decoded.metadata, row.metadata);
+        assert_eq!(decoded.content_digest(), row.content_digest());
+    }
+
+    #[test]
+    fn hlc_travels_in_the_wire_layout_of_rows_without_one() {
+        let mut row = visible_row(100, None);
+        row.hlc = Some(HlcTimestamp {
+            physical: 250,
+            logical: 2,
+        });
+
+        let bytes = postcard::to_allocvec(&row).expect("serialize row");
+        let decoded: StoredRowBatch = postcard::from_bytes(&bytes).expect("deserialize row");
+        assert_eq!(decoded, row);
+
+        // A peer that predates the field sees the HLC as a metadata entry
+        // and digests the same content.
+        let mut legacy = row.clone();
+        legacy.hlc = None;
+        legacy.metadata.insert("hlc", "250.2");
+        assert_eq!(
+            postcard::to_allocvec(&legacy).expect("serialize legacy row"),
+            bytes
+        );
+        assert_eq!(legacy.content_digest(), row.content_digest());
+    }
+
+    #[test]
+    fn rows_without_an_hlc_order_by_updated_at() {
+        let older = visible_row(100, None);
+        let mut newer = visible_row(200, None);
+
+        assert_eq!(newer.hlc(), HlcTimestamp::from_wall_clock(200));
+        assert!(newer.lww_key() > older.lww_key());
+
+        newer.hlc = Some(HlcTimestamp {
+            physical: 50,
+            logical: 0,
+        });
+        assert!(
+            newer.lww_key() < older.lww_key(),
+            "a stamped HLC takes precedence over updated_at"
+        );
+    }
+
     #[test]
     fn flat_history_row_hard_delete_uses_null_user_columns() {
         let user_descriptor = user_descriptor();
@@ -1168,4 +1251,62 @@ mod tests {
             "direct visible rows should publish under their batch identity"
         );
     }
+
+    #[test]
+    fn writer_parent_is_the_latest_earlier_version_on_any_branch() {
+        let on_branch = |branch: &str, updated_at: u64| {
+            let mut row = visible_row(updated_at, None);
+            row.branch = branch.into();
+            row
+        };
+        let v2_insert = on_branch("dev-v2-main", 100);
+        let v1_older = on_branch("dev-v1-main", 50);
+        let v1_write = on_branch("dev-v1-main", 200);
+        // Written after the v1 write, so its author can't have seen it.
+        let v2_later = on_branch("dev-v2-main", 300);
+        let mut v2_deleted = on_branch("dev-v2-main", 150);
+        v2_deleted.is_deleted = true;
+        let mut v2_rejected = on_branch("dev-v2-main", 180);
+        v2_rejected.state = RowState::Rejected;
+
+        let history = vec![
+            v1_older,
+            v2_insert.clone(),
+            v2_deleted,
+            v2_rejected,
+            v1_write.clone(),
+            v2_later,
+        ];
+
+        assert_eq!(
+            writer_parent_version(&history, &v1_write).map(StoredRowBatch::batch_id),
+            Some(v2_insert.batch_id())
+        );
+        assert_eq!(writer_parent_version(&history[..1], &history[0]), None);
+    }
+
+    #[test]
+    fn lensed_write_only_overrides_columns_the_writer_changed() {
+        let newest = vec![
+            Value::Text("Alice".into()),
+            Value::Text("alice@example.com".into()),
+            Value::Integer(3),
+        ];
+        // An older client renamed the row; its schema has no `email`, so the
+        // lens filled in a default there.
+        let lensed_write = vec![
+            Value::Text("Alice Updated".into()),
+            Value::Text(String::new()),
+            Value::Integer(3),
+        ];
+
+        assert_eq!(
+            merge_lensed_write(&newest, &lensed_write, &[true, false, false]),
+            vec![
+                Value::Text("Alice Updated".into()),
+                Value::Text("alice@example.com".into()),
+                Value::Integer(3),
+            ]
+        );
+    }
 }
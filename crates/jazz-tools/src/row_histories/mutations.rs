@@ -138,7 +138,7 @@ pub(super) fn supersede_older_staging_rows_for_batch<H: Storage>(
         return Ok(());
     }
 
-    pending_rows.sort_by_key(|row| (row.updated_at, row.batch_id()));
+    pending_rows.sort_by_key(|row| row.lww_key());
     pending_rows.pop();
 
     for row in pending_rows {
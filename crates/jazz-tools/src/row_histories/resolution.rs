@@ -17,6 +17,9 @@
 //! - [`visible_entry_from_history_rows`] — preview wrapped in `VisibleRowEntry`
 //! - [`branch_frontier`], [`latest_visible_version_for_tier`] — frontier/version
 //!   queries used by `VisibleRowEntry::rebuild_*`
+//! - [`writer_parent_version`], [`merge_lensed_write`] — pick the version an
+//!   older-schema write was made against and overlay the write onto the
+//!   newest row
 //! - [`merge_column_with_strategy`], [`assign_winner_ordinals`],
 //!   [`preview_override_sidecar`] — building blocks reused by `types.rs` when
 //!   encoding the visible-row sidecar.
@@ -25,7 +28,7 @@ use std::collections::{BTreeMap, HashMap};
 
 use crate::metadata::DeleteKind;
 use crate::query_manager::types::{
-    ColumnDescriptor, ColumnMergeStrategy, ColumnType, RowDescriptor, Value,
+    ColumnDescriptor, ColumnMergeStrategy, ColumnType, Decimal, RowDescriptor, Value,
 };
 use crate::row_format::{EncodingError, encode_row, encode_value_with_type};
 use crate::sync_manager::DurabilityTier;
@@ -78,7 +81,7 @@ pub(super) fn latest_common_ancestor<'a>(
     common_ancestors?
         .into_iter()
         .filter_map(|batch_id| row_by_batch_id.get(&batch_id).copied())
-        .max_by_key(|row| (row.updated_at, row.batch_id()))
+        .max_by_key(|row| row.lww_key())
 }
 
 pub(super) fn delete_winner<'a>(frontier: &[&'a StoredRowBatch]) -> Option<&'a StoredRowBatch> {
@@ -97,11 +100,7 @@ pub(super) fn delete_winner<'a>(frontier: &[&'a StoredRowBatch]) -> Option<&'a S
                 Some(DeleteKind::Soft) => 1u8,
                 None => 0u8,
             };
-            (left_rank, left.updated_at, left.batch_id()).cmp(&(
-                right_rank,
-                right.updated_at,
-                right.batch_id(),
-            ))
+            (left_rank, left.lww_key()).cmp(&(right_rank, right.lww_key()))
         })
 }
 
@@ -127,6 +126,63 @@ pub(super) struct ColumnContender<'a> {
     value: &'a Value,
 }
 
+/// Counter merge over a DECIMAL column: the ancestor plus each contender's
+/// exact delta from it, kept within the column's precision.
+fn merge_decimal_counter<'a>(
+    column: &ColumnDescriptor,
+    precision: u8,
+    scale: u8,
+    ancestor_value: &Value,
+    contenders: &[ColumnContender<'a>],
+) -> Result<(Value, Option<&'a StoredRowBatch>), EncodingError> {
+    let overflow = || {
+        malformed(format!(
+            "counter merge overflow for column '{}'",
+            column.name_str()
+        ))
+    };
+    let ancestor = match ancestor_value {
+        Value::Decimal(value) => *value,
+        Value::Null => Decimal::ZERO,
+        other => {
+            return Err(malformed(format!(
+                "counter merge expected DECIMAL ancestor for column '{}', got {:?}",
+                column.name_str(),
+                other
+            )));
+        }
+    };
+
+    let mut merged = ancestor;
+    let mut latest_contributor: Option<&'a StoredRowBatch> = None;
+    for contender in contenders {
+        let Value::Decimal(contender_value) = contender.value else {
+            return Err(malformed(format!(
+                "counter merge expected DECIMAL contender for column '{}', got {:?}",
+                column.name_str(),
+                contender.value
+            )));
+        };
+        let delta = contender_value
+            .checked_sub(&ancestor)
+            .ok_or_else(overflow)?;
+        merged = merged.checked_add(&delta).ok_or_else(overflow)?;
+        if !delta.is_zero()
+            && latest_contributor
+                .map(|current| contender.row.lww_key() > current.lww_key())
+                .unwrap_or(true)
+        {
+            latest_contributor = Some(contender.row);
+        }
+    }
+
+    let merged = merged
+        .rescale(scale)
+        .filter(|merged| merged.digits() <= precision)
+        .ok_or_else(overflow)?;
+    Ok((Value::Decimal(merged), latest_contributor))
+}
+
 pub(super) fn merge_column_with_strategy<'a>(
     column: &ColumnDescriptor,
     ancestor_value: &Value,
@@ -134,6 +190,9 @@ pub(super) fn merge_column_with_strategy<'a>(
 ) -> Result<(Value, Option<&'a StoredRowBatch>), EncodingError> {
     match column.merge_strategy {
         Some(ColumnMergeStrategy::Counter) => {
+            if let ColumnType::Decimal { precision, scale } = column.column_type {
+                return merge_decimal_counter(column, precision, scale, ancestor_value, contenders);
+            }
             let ancestor = match ancestor_value {
                 Value::Integer(value) => *value,
                 Value::Null => 0,
@@ -173,10 +232,7 @@ pub(super) fn merge_column_with_strategy<'a>(
                 })?;
                 if delta != 0
                     && latest_contributor
-                        .map(|current| {
-                            (contender.row.updated_at, contender.row.batch_id())
-                                > (current.updated_at, current.batch_id())
-                        })
+                        .map(|current| contender.row.lww_key() > current.lww_key())
                         .unwrap_or(true)
                 {
                     latest_contributor = Some(contender.row);
@@ -210,7 +266,7 @@ pub(super) fn merge_column_with_strategy<'a>(
 
             let latest_contributor = contenders
                 .iter()
-                .max_by_key(|contender| (contender.row.updated_at, contender.row.batch_id()))
+                .max_by_key(|contender| contender.row.lww_key())
                 .map(|contender| contender.row);
 
             Ok((Value::Array(merged), latest_contributor))
@@ -221,10 +277,7 @@ pub(super) fn merge_column_with_strategy<'a>(
 
             for contender in contenders {
                 if latest_changed
-                    .map(|current| {
-                        (contender.row.updated_at, contender.row.batch_id())
-                            > (current.updated_at, current.batch_id())
-                    })
+                    .map(|current| contender.row.lww_key() > current.lww_key())
                     .unwrap_or(true)
                 {
                     latest_changed = Some(contender.row);
@@ -330,7 +383,7 @@ pub(super) fn build_computed_visible_preview(
         .copied()
         .filter(|row| !non_tips.contains(&row.batch_id()))
         .collect();
-    frontier.sort_by_key(|row| (row.updated_at, row.batch_id()));
+    frontier.sort_by_key(|row| row.lww_key());
     frontier.dedup_by_key(|row| row.batch_id());
     let Some(latest_tip) = frontier.last().copied() else {
         return Ok(None);
@@ -406,7 +459,7 @@ pub(super) fn build_computed_visible_preview(
         contributing_rows
             .iter()
             .copied()
-            .max_by_key(|row| (row.updated_at, row.batch_id()))
+            .max_by_key(|row| row.lww_key())
             .unwrap_or(latest_tip)
     });
 
@@ -438,6 +491,7 @@ pub(super) fn build_computed_visible_preview(
         branch: metadata_row.branch.clone(),
         parents: metadata_row.parents.clone(),
         updated_at: metadata_row.updated_at,
+        hlc: metadata_row.hlc,
         created_by: metadata_row.created_by.clone(),
         created_at: metadata_row.created_at,
         updated_by: metadata_row.updated_by.clone(),
@@ -479,6 +533,50 @@ pub(crate) fn visible_row_preview_from_history_rows(
     )
 }
 
+/// The version the author of `write` edited: the latest visible, undeleted
+/// version of the row in `history`, on any branch, that
+/// [`StoredRowBatch::lww_key`] orders before the write.
+///
+/// Parents only name versions on the write's own branch, but a client on an
+/// older schema reads versions written under newer schemas through a lens,
+/// so the version it edited can live on another branch.
+pub(crate) fn writer_parent_version<'a>(
+    history: &'a [StoredRowBatch],
+    write: &StoredRowBatch,
+) -> Option<&'a StoredRowBatch> {
+    let write_key = write.lww_key();
+    history
+        .iter()
+        .filter(|row| row.state.is_visible() && !row.is_deleted && row.lww_key() < write_key)
+        .max_by_key(|row| row.lww_key())
+}
+
+/// Overlay a write made under an older schema onto the row's newest version.
+///
+/// `lensed_write` is the write lensed into the newest schema, and `written[i]`
+/// says whether the writer changed column `i` as its own schema saw it.
+/// Columns the writer left alone — including ones its schema doesn't have,
+/// which the lens can only fill with defaults — keep `newest`'s values, so an
+/// old client's update doesn't wipe fields it can't see.
+pub(crate) fn merge_lensed_write(
+    newest: &[Value],
+    lensed_write: &[Value],
+    written: &[bool],
+) -> Vec<Value> {
+    newest
+        .iter()
+        .zip(lensed_write)
+        .zip(written)
+        .map(|((newest, write), &written)| {
+            if written {
+                write.clone()
+            } else {
+                newest.clone()
+            }
+        })
+        .collect()
+}
+
 pub(super) fn visible_entry_from_history_rows(
     user_descriptor: &RowDescriptor,
     history_rows: &[StoredRowBatch],
@@ -493,7 +591,7 @@ pub(super) fn latest_visible_version_for_tier(
     history_rows
         .iter()
         .filter(|row| row.state.is_visible() && tier_satisfies(row.confirmed_tier, required_tier))
-        .max_by_key(|row| (row.updated_at, row.batch_id()))
+        .max_by_key(|row| row.lww_key())
         .map(StoredRowBatch::batch_id)
 }
 
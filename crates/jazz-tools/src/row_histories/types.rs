@@ -1,5 +1,6 @@
 //! Data types: BatchId, RowState, QueryRowBatch, StoredRowBatch, VisibleRowEntry, etc.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
@@ -15,6 +16,7 @@ use crate::query_manager::types::{RowBytes, RowDescriptor, SharedString, Value};
 use crate::row_format::{EncodingError, encode_row};
 use crate::storage::{RowLocator, Storage, StorageError};
 use crate::sync_manager::DurabilityTier;
+use crate::sync_manager::clock::HlcTimestamp;
 
 use super::codecs::{compute_row_digest, flat_user_values, malformed, tier_satisfies};
 use super::resolution::{
@@ -206,12 +208,16 @@ impl From<&StoredRowBatch> for QueryRowBatch {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "StoredRowBatchRepr", into = "StoredRowBatchRepr")]
 pub struct StoredRowBatch {
     pub row_id: ObjectId,
     pub batch_id: BatchId,
     pub branch: SharedString,
     pub parents: SmallVec<[BatchId; 2]>,
     pub updated_at: u64,
+    /// Hybrid logical clock timestamp. `None` on batches written before rows
+    /// carried one; see [`StoredRowBatch::hlc`].
+    pub hlc: Option<HlcTimestamp>,
     pub created_by: SharedString,
     pub created_at: u64,
     pub updated_by: SharedString,
@@ -243,6 +249,24 @@ impl RowMetadata {
             .find_map(|(entry_key, value)| (entry_key == key).then_some(value))
     }
 
+    /// Set `key` to `value`, keeping entries sorted by key.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let key = key.into();
+        let value = value.into();
+        match self
+            .0
+            .binary_search_by(|(entry_key, _)| entry_key.as_str().cmp(key.as_str()))
+        {
+            Ok(index) => self.0[index].1 = value,
+            Err(index) => self.0.insert(index, (key, value)),
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let index = self.0.iter().position(|(entry_key, _)| entry_key == key)?;
+        Some(self.0.remove(index).1)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
@@ -258,6 +282,100 @@ impl RowMetadata {
     }
 }
 
+/// Wire and storage layout of [`StoredRowBatch`].
+///
+/// The HLC travels as a [`MetadataKey::Hlc`] metadata entry, so the layout is
+/// the one peers and stores used before rows carried an HLC, and the content
+/// digest matches what they compute.
+#[derive(Serialize, Deserialize)]
+struct StoredRowBatchRepr {
+    row_id: ObjectId,
+    batch_id: BatchId,
+    branch: SharedString,
+    parents: SmallVec<[BatchId; 2]>,
+    updated_at: u64,
+    created_by: SharedString,
+    created_at: u64,
+    updated_by: SharedString,
+    state: RowState,
+    confirmed_tier: Option<DurabilityTier>,
+    delete_kind: Option<DeleteKind>,
+    is_deleted: bool,
+    data: RowBytes,
+    metadata: RowMetadata,
+}
+
+impl From<StoredRowBatch> for StoredRowBatchRepr {
+    fn from(row: StoredRowBatch) -> Self {
+        Self {
+            metadata: metadata_with_hlc(&row.metadata, row.hlc).into_owned(),
+            row_id: row.row_id,
+            batch_id: row.batch_id,
+            branch: row.branch,
+            parents: row.parents,
+            updated_at: row.updated_at,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            updated_by: row.updated_by,
+            state: row.state,
+            confirmed_tier: row.confirmed_tier,
+            delete_kind: row.delete_kind,
+            is_deleted: row.is_deleted,
+            data: row.data,
+        }
+    }
+}
+
+impl From<StoredRowBatchRepr> for StoredRowBatch {
+    fn from(repr: StoredRowBatchRepr) -> Self {
+        let (metadata, hlc) = split_hlc_metadata(repr.metadata);
+        Self {
+            row_id: repr.row_id,
+            batch_id: repr.batch_id,
+            branch: repr.branch,
+            parents: repr.parents,
+            updated_at: repr.updated_at,
+            hlc,
+            created_by: repr.created_by,
+            created_at: repr.created_at,
+            updated_by: repr.updated_by,
+            state: repr.state,
+            confirmed_tier: repr.confirmed_tier,
+            delete_kind: repr.delete_kind,
+            is_deleted: repr.is_deleted,
+            data: repr.data,
+            metadata,
+        }
+    }
+}
+
+/// `metadata` with `hlc` stored as a [`MetadataKey::Hlc`] entry.
+pub(crate) fn metadata_with_hlc(
+    metadata: &RowMetadata,
+    hlc: Option<HlcTimestamp>,
+) -> Cow<'_, RowMetadata> {
+    match hlc {
+        Some(hlc) => {
+            let mut metadata = metadata.clone();
+            metadata.insert(MetadataKey::Hlc.as_str(), hlc.to_string());
+            Cow::Owned(metadata)
+        }
+        None => Cow::Borrowed(metadata),
+    }
+}
+
+/// Move a stored [`MetadataKey::Hlc`] entry out of `metadata`. An entry that
+/// does not parse stays in place, so the row's digest is unchanged.
+pub(crate) fn split_hlc_metadata(mut metadata: RowMetadata) -> (RowMetadata, Option<HlcTimestamp>) {
+    let hlc = metadata
+        .get(MetadataKey::Hlc.as_str())
+        .and_then(|raw| raw.parse::<HlcTimestamp>().ok());
+    if hlc.is_some() {
+        metadata.remove(MetadataKey::Hlc.as_str());
+    }
+    (metadata, hlc)
+}
+
 fn delete_kind_from_metadata(metadata: &HashMap<String, String>) -> Option<DeleteKind> {
     match metadata
         .get(MetadataKey::Delete.as_str())
@@ -308,12 +426,12 @@ impl StoredRowBatch {
     ) -> Self {
         let delete_kind = delete_kind_from_metadata(&metadata);
         let is_deleted = delete_kind.is_some();
-        let metadata = RowMetadata::from_hash_map(
+        let (metadata, hlc) = split_hlc_metadata(RowMetadata::from_hash_map(
             metadata
                 .into_iter()
                 .filter(|(key, _)| key != MetadataKey::Delete.as_str())
                 .collect(),
-        );
+        ));
         let branch = SharedString::from(branch.into());
         let parents = parents.into_iter().collect::<SmallVec<[BatchId; 2]>>();
 
@@ -323,6 +441,7 @@ impl StoredRowBatch {
             branch,
             parents,
             updated_at: provenance.updated_at,
+            hlc,
             created_by: provenance.created_by.into(),
             created_at: provenance.created_at,
             updated_by: provenance.updated_by.into(),
@@ -348,14 +467,63 @@ impl StoredRowBatch {
         self.batch_id
     }
 
+    /// The batch's hybrid logical clock timestamp. Batches written before
+    /// rows carried an HLC read as `(updated_at, 0)`.
+    pub fn hlc(&self) -> HlcTimestamp {
+        self.hlc
+            .unwrap_or_else(|| HlcTimestamp::from_wall_clock(self.updated_at))
+    }
+
+    /// Last-writer-wins order: the batch's HLC, tie-broken by batch id.
+    pub fn lww_key(&self) -> (HlcTimestamp, BatchId) {
+        (self.hlc(), self.batch_id)
+    }
+
     pub fn content_digest(&self) -> Digest32 {
+        let metadata = metadata_with_hlc(&self.metadata, self.hlc);
         compute_row_digest(
             &self.branch,
             &self.parents,
             &self.data,
             self.updated_at,
             &self.updated_by,
-            (!self.metadata.is_empty()).then_some(&self.metadata),
+            (!metadata.is_empty()).then_some(&*metadata),
+        )
+    }
+
+    /// Digest of the row as its author sealed it.
+    ///
+    /// A server may re-time an out-of-range client row after the client
+    /// sealed its batch. The adjusted row keeps the client's `updated_at` and
+    /// HLC in metadata, so undo the adjustment before comparing against the
+    /// seal.
+    pub fn sealed_digest(&self) -> Digest32 {
+        if self
+            .metadata
+            .get(MetadataKey::TimestampAdjustment.as_str())
+            .is_none()
+        {
+            return self.content_digest();
+        }
+        let mut metadata = self.metadata.clone();
+        metadata.remove(MetadataKey::TimestampAdjustment.as_str());
+        metadata.remove(MetadataKey::ClientCreatedAt.as_str());
+        let updated_at = metadata
+            .remove(MetadataKey::ClientUpdatedAt.as_str())
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(self.updated_at);
+        let hlc = metadata
+            .remove(MetadataKey::ClientHlc.as_str())
+            .and_then(|value| value.parse::<HlcTimestamp>().ok())
+            .or(self.hlc);
+        let metadata = metadata_with_hlc(&metadata, hlc);
+        compute_row_digest(
+            &self.branch,
+            &self.parents,
+            &self.data,
+            updated_at,
+            &self.updated_by,
+            (!metadata.is_empty()).then_some(&*metadata),
         )
     }
 
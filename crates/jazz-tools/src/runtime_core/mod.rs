@@ -47,7 +47,7 @@ use crate::query_manager::types::{
 };
 use crate::row_format::decode_row;
 use crate::row_histories::BatchId;
-use crate::schema_manager::{Lens, SchemaManager};
+use crate::schema_manager::{Lens, SchemaGcReport, SchemaManager};
 use crate::storage::{Storage, StorageError};
 use crate::sync_manager::{ClientId, DurabilityTier, InboxEntry, OutboxEntry, ServerId};
 
@@ -172,6 +172,10 @@ pub enum RuntimeError {
         table: crate::query_manager::types::TableName,
         operation: crate::query_manager::policy::Operation,
     },
+    LimitExceeded {
+        table: crate::query_manager::types::TableName,
+        violation: crate::query_manager::limits::LimitViolation,
+    },
 }
 
 impl std::fmt::Display for RuntimeError {
@@ -187,6 +191,9 @@ impl std::fmt::Display for RuntimeError {
                     operation, table
                 )
             }
+            RuntimeError::LimitExceeded { table, violation } => {
+                write!(f, "limit exceeded on table {table}: {violation}")
+            }
         }
     }
 }
@@ -199,18 +206,25 @@ impl From<QueryError> for RuntimeError {
             QueryError::AnonymousWriteDenied { table, operation } => {
                 RuntimeError::AnonymousWriteDenied { table, operation }
             }
+            QueryError::LimitExceeded { table, violation } => {
+                RuntimeError::LimitExceeded { table, violation }
+            }
             other => RuntimeError::QueryError(other.to_string()),
         }
     }
 }
 
-/// Convert a `QueryError` from a write path, preserving the
-/// `AnonymousWriteDenied` variant and mapping anything else to `WriteError`.
+/// Convert a `QueryError` from a write path, preserving the structured
+/// `AnonymousWriteDenied` and `LimitExceeded` variants and mapping anything
+/// else to `WriteError`.
 pub(crate) fn write_error_from_query(e: QueryError) -> RuntimeError {
     match e {
         QueryError::AnonymousWriteDenied { table, operation } => {
             RuntimeError::AnonymousWriteDenied { table, operation }
         }
+        QueryError::LimitExceeded { table, violation } => {
+            RuntimeError::LimitExceeded { table, violation }
+        }
         other => RuntimeError::WriteError(other.to_string()),
     }
 }
@@ -278,6 +292,10 @@ pub struct SubscriptionDelta {
     /// Output descriptor for decoding the binary row data.
     /// Use with `decode_row(&descriptor, &row.data)` to get `Vec<Value>`.
     pub descriptor: crate::query_manager::types::RowDescriptor,
+    /// Keyset cursor of the first row on the page (sorted, paginated queries).
+    pub prev_cursor: Option<crate::query_manager::query::PageCursor>,
+    /// Keyset cursor of the last row on the page (sorted, paginated queries).
+    pub next_cursor: Option<crate::query_manager::query::PageCursor>,
 }
 
 /// Callback type for subscriptions.
@@ -732,6 +750,82 @@ impl<S: Storage, Sch: Scheduler> RuntimeCore<S, Sch> {
         self.immediate_tick();
         Ok(id)
     }
+
+    /// Publish a corrected revision of the lens already published for the
+    /// same schema pair.
+    pub fn publish_lens_revision(
+        &mut self,
+        lens: &Lens,
+        reason: &str,
+    ) -> Result<ObjectId, RuntimeError> {
+        let id = self
+            .schema_manager
+            .publish_lens_revision(&mut self.storage, lens, reason)
+            .map_err(|error| RuntimeError::WriteError(error.to_string()))?;
+        self.mark_storage_write_pending_flush();
+        self.refresh_transport_catalogue_state_hash();
+        self.immediate_tick();
+        Ok(id)
+    }
+
+    /// Publish a schema, its lenses and its permissions as one release. See
+    /// [`SchemaManager::publish_release`].
+    pub fn publish_release(
+        &mut self,
+        schema: Schema,
+        lenses: Vec<Lens>,
+        permissions: HashMap<TableName, TablePolicies>,
+        expected_parent_release_object_id: Option<ObjectId>,
+    ) -> Result<ObjectId, RuntimeError> {
+        let id = self
+            .schema_manager
+            .publish_release(
+                &mut self.storage,
+                schema,
+                lenses,
+                permissions,
+                expected_parent_release_object_id,
+            )
+            .map_err(|error| RuntimeError::WriteError(error.to_string()))?;
+        self.mark_storage_write_pending_flush();
+        self.refresh_transport_catalogue_state_hash();
+        self.immediate_tick();
+        info!(object_id = %id, "published release to catalogue");
+        Ok(id)
+    }
+
+    /// Roll back to the previous release. See
+    /// [`SchemaManager::rollback_release`].
+    pub fn rollback_release(&mut self) -> Result<ObjectId, RuntimeError> {
+        let id = self
+            .schema_manager
+            .rollback_release(&mut self.storage)
+            .map_err(|error| RuntimeError::WriteError(error.to_string()))?;
+        self.mark_storage_write_pending_flush();
+        self.refresh_transport_catalogue_state_hash();
+        self.immediate_tick();
+        info!(object_id = %id, "rolled back to previous release");
+        Ok(id)
+    }
+
+    /// Retire schema versions published before `retain_published_since`,
+    /// rewriting their rows onto `target_hash` first. See
+    /// [`SchemaManager::collect_schema_garbage`].
+    pub fn collect_schema_garbage(
+        &mut self,
+        target_hash: SchemaHash,
+        retain_published_since: u64,
+    ) -> Result<SchemaGcReport, RuntimeError> {
+        let report = self
+            .schema_manager
+            .collect_schema_garbage(&mut self.storage, target_hash, retain_published_since)
+            .map_err(|error| RuntimeError::WriteError(error.to_string()))?;
+        self.mark_storage_write_pending_flush();
+        self.refresh_transport_catalogue_state_hash();
+        self.immediate_tick();
+        Ok(report)
+    }
+
     // =========================================================================
     // Schema/State Access
     // =========================================================================
@@ -1,4 +1,5 @@
 use super::*;
+use crate::query_manager::graph::explain::QueryExplain;
 use crate::query_manager::manager::LocalUpdates;
 use crate::sync_manager::QueryPropagation;
 
@@ -281,6 +282,29 @@ impl<S: Storage, Sch: Scheduler> RuntimeCore<S, Sch> {
     // Queries
     // =========================================================================
 
+    /// Describe how a query compiles, optionally with per-node row counters.
+    pub fn explain(
+        &self,
+        query: &Query,
+        session: Option<Session>,
+        analyze: bool,
+    ) -> Result<QueryExplain, RuntimeError> {
+        self.schema_manager
+            .query_manager()
+            .explain(&self.storage, query, session, analyze)
+            .map_err(|e| RuntimeError::QueryError(e.to_string()))
+    }
+
+    /// Parse a SQL `SELECT` against the current schema into a query.
+    ///
+    /// `$session.*` parameters are bound from `session`.
+    pub fn sql_query(&self, sql: &str, session: Option<&Session>) -> Result<Query, RuntimeError> {
+        self.schema_manager
+            .query_manager()
+            .sql_query(sql, session)
+            .map_err(|e| RuntimeError::QueryError(e.to_string()))
+    }
+
     /// Execute a one-shot query.
     pub fn query(&mut self, query: Query, session: Option<Session>) -> QueryFuture {
         self.query_with_propagation(
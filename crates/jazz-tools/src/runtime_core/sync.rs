@@ -316,8 +316,7 @@ impl<S: Storage, Sch: Scheduler> RuntimeCore<S, Sch> {
         if sm.get_client(client_id).is_some() {
             sm.set_client_role(client_id, ClientRole::Admin);
         } else {
-            sm.add_client_with_storage(&self.storage, client_id);
-            sm.set_client_role(client_id, ClientRole::Admin);
+            sm.add_client_with_role_and_storage(&self.storage, client_id, ClientRole::Admin);
             self.immediate_tick();
         }
     }
@@ -355,8 +354,7 @@ impl<S: Storage, Sch: Scheduler> RuntimeCore<S, Sch> {
         if sm.get_client(client_id).is_some() {
             sm.set_client_role(client_id, ClientRole::Backend);
         } else {
-            sm.add_client_with_storage(&self.storage, client_id);
-            sm.set_client_role(client_id, ClientRole::Backend);
+            sm.add_client_with_role_and_storage(&self.storage, client_id, ClientRole::Backend);
             self.immediate_tick();
         }
     }
@@ -181,16 +181,15 @@ fn rc_delete_old_schema_row_after_evolution_hides_row_from_queries() {
     );
 }
 
-/// FIXME: this is an undesired behavior. See `/specs/todo/ideas/1_mvp/lens-hardening.md`
 #[test]
-fn rc_old_client_update_removes_unseen_newer_fields() {
+fn rc_old_client_update_preserves_unseen_newer_fields() {
     let v1 = schema_evolution_v1();
     let v2 = schema_evolution_v2();
 
     // Flow:
     // v2 client writes row with email on the v2 branch
     // v1 client reads that row through v2 -> v1 lens and updates only name
-    // v2 client does not see the original email after the v1-originated update
+    // v2 client still sees the original email after the v1-originated update
     let mut new_runtime =
         create_runtime_with_schema(v2.clone(), "schema-evolution-backward-update-test");
     let user_id = ObjectId::new();
@@ -273,8 +272,76 @@ fn rc_old_client_update_removes_unseen_newer_fields() {
     );
     assert_eq!(
         queried_values[email_idx],
-        Value::Text("".to_string()),
-        "Old-client updates remove unseen new-schema fields",
+        Value::Text("alice@example.com".to_string()),
+        "Old-client updates should preserve unseen new-schema fields",
+    );
+}
+
+#[test]
+fn rc_new_client_update_after_old_client_update_keeps_unseen_fields() {
+    let v1 = schema_evolution_v1();
+    let v2 = schema_evolution_v2();
+
+    // Flow:
+    // v2 client writes row with email on the v2 branch
+    // v1 client updates only name on the v1 branch
+    // v2 client loads the row for an update (the v1 write wins) and updates name again
+    // the email the v1 client never saw survives both updates
+    let app = "schema-evolution-update-after-backward-update-test";
+    let mut new_runtime = create_runtime_with_schema(v2.clone(), app);
+    let ((row_id, _), _) = new_runtime
+        .insert(
+            "users",
+            HashMap::from([
+                ("id".to_string(), Value::Uuid(ObjectId::new())),
+                ("name".to_string(), Value::Text("Alice".to_string())),
+                (
+                    "email".to_string(),
+                    Value::Text("alice@example.com".to_string()),
+                ),
+            ]),
+            None,
+        )
+        .unwrap();
+
+    let mut old_runtime = create_runtime_with_storage(v1.clone(), app, new_runtime.into_storage());
+    old_runtime
+        .add_live_schema_and_persist_catalogue(v2.clone())
+        .expect("v2 should be attachable as a live schema for v1");
+    old_runtime.immediate_tick();
+    old_runtime
+        .update(
+            row_id,
+            vec![("name".to_string(), Value::Text("Alice from v1".to_string()))],
+            None,
+        )
+        .expect("old client update");
+
+    let mut reloaded_v2 = create_runtime_with_storage(v2.clone(), app, old_runtime.into_storage());
+    reloaded_v2
+        .add_live_schema_and_persist_catalogue(v1.clone())
+        .expect("v1 should be attachable as a live schema for v2");
+    reloaded_v2.immediate_tick();
+    reloaded_v2
+        .update(
+            row_id,
+            vec![("name".to_string(), Value::Text("Alice from v2".to_string()))],
+            None,
+        )
+        .expect("new client update over an old-schema write");
+
+    let results = execute_runtime_query(&mut reloaded_v2, Query::new("users"), None);
+    assert_eq!(results.len(), 1);
+    let (_, values) = &results[0];
+    let current_schema = reloaded_v2.current_schema();
+    assert_eq!(
+        values[column_index(current_schema, "users", "name")],
+        Value::Text("Alice from v2".to_string()),
+    );
+    assert_eq!(
+        values[column_index(current_schema, "users", "email")],
+        Value::Text("alice@example.com".to_string()),
+        "loading an old-schema write for an update should keep unseen fields",
     );
 }
 
@@ -32,7 +32,7 @@ impl<S: Storage, Sch: Scheduler> RuntimeCore<S, Sch> {
             )
             .ok()
             .flatten()?;
-        (row.content_digest() == member.row_digest).then_some((row_locator, row))
+        (row.sealed_digest() == member.row_digest).then_some((row_locator, row))
     }
 
     fn sealed_submission_batch_members(&self, batch_id: BatchId) -> Vec<LocalBatchMember> {
@@ -138,7 +138,7 @@ impl<S: Storage, Sch: Scheduler> RuntimeCore<S, Sch> {
                     table_name: row_locator.table.to_string(),
                     branch_name,
                     schema_hash,
-                    row_digest: row.content_digest(),
+                    row_digest: row.sealed_digest(),
                 };
                 rows.push((member, row_locator.clone(), row));
             }
@@ -654,6 +654,8 @@ impl<S: Storage, Sch: Scheduler> RuntimeCore<S, Sch> {
                         handle,
                         ordered_delta: update.ordered_delta.clone(),
                         descriptor: update.descriptor.clone(),
+                        prev_cursor: update.prev_cursor.clone(),
+                        next_cursor: update.next_cursor.clone(),
                     };
                     (state.callback)(delta);
                     callbacks_fired += 1;
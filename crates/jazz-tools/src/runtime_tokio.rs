@@ -21,6 +21,7 @@ use futures::channel::oneshot;
 
 use crate::batch_fate::BatchMode;
 use crate::object::ObjectId;
+use crate::query_manager::graph::explain::QueryExplain;
 use crate::query_manager::query::Query;
 use crate::query_manager::session::{Session, WriteContext};
 use crate::query_manager::types::{Schema, SchemaHash, Value};
@@ -31,7 +32,7 @@ use crate::runtime_core::{
     SubscriptionDelta, SyncSender,
 };
 use crate::schema_manager::manager::{CurrentPermissionsSummary, PermissionsHeadSummary};
-use crate::schema_manager::{Lens, SchemaManager};
+use crate::schema_manager::{Lens, SchemaGcReport, SchemaManager};
 use crate::storage::Storage;
 use crate::sync_manager::{
     ClientId, DurabilityTier, InboxEntry, OutboxEntry, QueryPropagation, ServerId,
@@ -209,6 +210,9 @@ impl From<CoreRuntimeError> for RuntimeError {
                     operation, table
                 ))
             }
+            CoreRuntimeError::LimitExceeded { table, violation } => {
+                RuntimeError::WriteError(format!("limit exceeded on table {table}: {violation}"))
+            }
         }
     }
 }
@@ -331,6 +335,67 @@ impl<S: Storage + Send + 'static> TokioRuntime<S> {
         Ok(core.publish_lens(lens)?)
     }
 
+    /// Publish a corrected revision of the lens already published for the
+    /// same schema pair.
+    pub fn publish_lens_revision(
+        &self,
+        lens: &Lens,
+        reason: &str,
+    ) -> Result<ObjectId, RuntimeError> {
+        let mut core = self.core.lock().map_err(|_| RuntimeError::LockError)?;
+        Ok(core.publish_lens_revision(lens, reason)?)
+    }
+
+    /// Publish a schema, its lenses and its permissions as one release.
+    pub fn publish_release(
+        &self,
+        schema: Schema,
+        lenses: Vec<Lens>,
+        permissions: HashMap<
+            crate::query_manager::types::TableName,
+            crate::query_manager::types::TablePolicies,
+        >,
+        expected_parent_release_object_id: Option<ObjectId>,
+    ) -> Result<ObjectId, RuntimeError> {
+        let mut core = self.core.lock().map_err(|_| RuntimeError::LockError)?;
+        Ok(core.publish_release(
+            schema,
+            lenses,
+            permissions,
+            expected_parent_release_object_id,
+        )?)
+    }
+
+    /// Roll back to the previous release.
+    pub fn rollback_release(&self) -> Result<ObjectId, RuntimeError> {
+        let mut core = self.core.lock().map_err(|_| RuntimeError::LockError)?;
+        Ok(core.rollback_release()?)
+    }
+
+    /// Retire schema versions published before `retain_published_since`.
+    pub fn collect_schema_garbage(
+        &self,
+        target_hash: SchemaHash,
+        retain_published_since: u64,
+    ) -> Result<SchemaGcReport, RuntimeError> {
+        let mut core = self.core.lock().map_err(|_| RuntimeError::LockError)?;
+        Ok(core.collect_schema_garbage(target_hash, retain_published_since)?)
+    }
+
+    /// Record a client connection declaring `hash`, so schema GC keeps it.
+    pub fn client_schema_connected(&self, hash: SchemaHash) -> Result<(), RuntimeError> {
+        let mut core = self.core.lock().map_err(|_| RuntimeError::LockError)?;
+        core.schema_manager_mut().client_schema_connected(hash);
+        Ok(())
+    }
+
+    /// Record that a client connection declaring `hash` closed.
+    pub fn client_schema_disconnected(&self, hash: SchemaHash) -> Result<(), RuntimeError> {
+        let mut core = self.core.lock().map_err(|_| RuntimeError::LockError)?;
+        core.schema_manager_mut().client_schema_disconnected(hash);
+        Ok(())
+    }
+
     // =========================================================================
     // CRUD Operations
     // =========================================================================
@@ -515,6 +580,23 @@ impl<S: Storage + Send + 'static> TokioRuntime<S> {
         )?)
     }
 
+    /// Describe how a query compiles, optionally with per-node row counters.
+    pub fn explain(
+        &self,
+        query: &Query,
+        session: Option<Session>,
+        analyze: bool,
+    ) -> Result<QueryExplain, RuntimeError> {
+        let core = self.core.lock().map_err(|_| RuntimeError::LockError)?;
+        core.explain(query, session, analyze)
+    }
+
+    /// Parse a SQL `SELECT` against the current schema into a query.
+    pub fn sql_query(&self, sql: &str, session: Option<&Session>) -> Result<Query, RuntimeError> {
+        let core = self.core.lock().map_err(|_| RuntimeError::LockError)?;
+        core.sql_query(sql, session)
+    }
+
     // =========================================================================
     // Subscriptions
     // =========================================================================
@@ -798,6 +880,12 @@ impl<S: Storage + Send + 'static> TokioRuntime<S> {
         Ok(f(core.storage()))
     }
 
+    /// Mutate the underlying storage while holding the core lock.
+    pub fn with_storage_mut<R>(&self, f: impl FnOnce(&mut S) -> R) -> Result<R, RuntimeError> {
+        let mut core = self.core.lock().map_err(|_| RuntimeError::LockError)?;
+        Ok(f(core.storage_mut()))
+    }
+
     /// Run a closure with read access to the SyncManager (for testing/inspection).
     #[cfg(test)]
     pub(crate) fn with_sync_manager<R>(
@@ -4,10 +4,12 @@
 //! - Added/removed tables
 //! - Added/removed columns
 //! - Potential renames (marked as drafts for review)
+//! - Columns extracted into a new table (marked as drafts for review)
+//! - Column splits/merges, once a reviewer confirms them (marked as drafts)
 
 use crate::query_manager::types::{Schema, SchemaHash};
 
-use super::diff::diff_schemas;
+use super::diff::{Ambiguity, diff_schemas, diff_schemas_confirming};
 use super::lens::Lens;
 
 /// Generate a lens that transforms from old schema to new schema.
@@ -22,6 +24,15 @@ pub fn generate_lens(old: &Schema, new: &Schema) -> Lens {
     Lens::new(old_hash, new_hash, diff.transform)
 }
 
+/// [`generate_lens`], also emitting the splits and merges in `confirmed`;
+/// see [`diff_schemas_confirming`].
+pub fn generate_lens_confirming(old: &Schema, new: &Schema, confirmed: &[Ambiguity]) -> Lens {
+    let old_hash = SchemaHash::compute(old);
+    let new_hash = SchemaHash::compute(new);
+    let diff = diff_schemas_confirming(old, new, confirmed);
+    Lens::new(old_hash, new_hash, diff.transform)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -448,4 +459,51 @@ mod tests {
             assert_eq!(fwd_op.invert(), *bwd_op);
         }
     }
+
+    #[test]
+    fn auto_lens_splits_only_once_confirmed_and_inverts_to_merge() {
+        let old = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("id", ColumnType::Uuid)
+                    .column("name", ColumnType::Text),
+            )
+            .build();
+
+        let new = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("id", ColumnType::Uuid)
+                    .column("first_name", ColumnType::Text)
+                    .nullable_column("last_name", ColumnType::Text),
+            )
+            .build();
+
+        let lens = generate_lens(&old, &new);
+        assert!(
+            !lens
+                .forward
+                .ops
+                .iter()
+                .any(|op| matches!(op, LensOp::SplitColumn { .. }))
+        );
+
+        let split = Ambiguity::PossibleSplit {
+            table: "users".to_string(),
+            column: "name".to_string(),
+            into: vec!["first_name".to_string(), "last_name".to_string()],
+        };
+        let lens = generate_lens_confirming(&old, &new, &[split]);
+
+        assert!(lens.is_draft());
+        assert!(matches!(
+            lens.forward.ops.as_slice(),
+            [LensOp::SplitColumn { column, into, .. }]
+                if column == "name" && into == &["first_name", "last_name"]
+        ));
+        assert!(matches!(
+            lens.backward.ops.as_slice(),
+            [LensOp::MergeColumns { into, .. }] if into == "name"
+        ));
+    }
 }
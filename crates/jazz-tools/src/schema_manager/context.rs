@@ -70,11 +70,27 @@ pub enum SchemaError {
         source: SchemaHash,
         target: SchemaHash,
     },
+    /// A lens for the schema pair is already published at `current` revision
+    /// or later; replacing it takes a newer revision with a reason.
+    StaleLensRevision {
+        source: SchemaHash,
+        target: SchemaHash,
+        current: u32,
+    },
     /// Publishing permissions used an outdated parent bundle.
     StalePermissionsParent {
         expected: Option<ObjectId>,
         current: Option<ObjectId>,
     },
+    /// Publishing a release used an outdated parent release.
+    StaleReleaseParent {
+        expected: Option<ObjectId>,
+        current: Option<ObjectId>,
+    },
+    /// A release can't be published or applied as a whole.
+    InvalidRelease(String),
+    /// There is no earlier release to roll back to.
+    NoPreviousRelease,
 }
 
 impl std::fmt::Display for SchemaError {
@@ -107,6 +123,19 @@ impl std::fmt::Display for SchemaError {
                     target.short()
                 )
             }
+            SchemaError::StaleLensRevision {
+                source,
+                target,
+                current,
+            } => {
+                write!(
+                    f,
+                    "Lens {} -> {} is already at revision {}; publish a revision to replace it",
+                    source.short(),
+                    target.short(),
+                    current
+                )
+            }
             SchemaError::StalePermissionsParent { expected, current } => {
                 write!(
                     f,
@@ -114,6 +143,15 @@ impl std::fmt::Display for SchemaError {
                     expected, current
                 )
             }
+            SchemaError::StaleReleaseParent { expected, current } => {
+                write!(
+                    f,
+                    "stale release parent: expected {:?}, current {:?}",
+                    expected, current
+                )
+            }
+            SchemaError::InvalidRelease(message) => write!(f, "invalid release: {message}"),
+            SchemaError::NoPreviousRelease => write!(f, "no previous release to roll back to"),
         }
     }
 }
@@ -223,14 +261,56 @@ impl SchemaContext {
     pub fn add_live_schema(&mut self, schema: Schema, lens: Lens) {
         let hash = SchemaHash::compute(&schema);
         self.live_schemas.insert(hash, schema);
-        self.lenses
-            .insert((lens.source_hash, lens.target_hash), lens);
+        self.register_lens(lens);
     }
 
     /// Register a lens between two schemas.
-    pub fn register_lens(&mut self, lens: Lens) {
-        self.lenses
-            .insert((lens.source_hash, lens.target_hash), lens);
+    ///
+    /// A lens only replaces the one registered for the same pair if its
+    /// revision is at least as high, so a superseded revision arriving late
+    /// (e.g. via catalogue sync) never wins. Returns whether it was kept.
+    pub fn register_lens(&mut self, lens: Lens) -> bool {
+        let key = (lens.source_hash, lens.target_hash);
+        if self
+            .lenses
+            .get(&key)
+            .is_some_and(|existing| existing.revision > lens.revision)
+        {
+            return false;
+        }
+        self.lenses.insert(key, lens);
+        true
+    }
+
+    /// Forget a lens, returning it if it was registered.
+    pub fn remove_lens(&mut self, source: &SchemaHash, target: &SchemaHash) -> Option<Lens> {
+        self.lenses.remove(&(*source, *target))
+    }
+
+    /// Forget a retired schema and every lens touching it.
+    ///
+    /// Returns the `(source, target)` pairs of the removed lenses. The current
+    /// schema can't be retired.
+    pub fn retire_schema(&mut self, hash: &SchemaHash) -> Vec<(SchemaHash, SchemaHash)> {
+        if hash == &self.current_hash {
+            return Vec::new();
+        }
+        self.live_schemas.remove(hash);
+        self.pending_schemas.remove(hash);
+
+        let mut removed: Vec<_> = self
+            .lenses
+            .keys()
+            .filter(|(source, target)| source == hash || target == hash)
+            .copied()
+            .collect();
+        for key in &removed {
+            self.lenses.remove(key);
+        }
+        removed.sort_by(|left, right| {
+            (left.0.as_bytes(), left.1.as_bytes()).cmp(&(right.0.as_bytes(), right.1.as_bytes()))
+        });
+        removed
     }
 
     /// Get lens between two schemas if it exists.
@@ -246,6 +326,9 @@ impl SchemaContext {
     /// - Backward: lens(A→B) also allows B to reach A (use lens.backward transform)
     ///
     /// The returned path contains lenses paired with the direction to apply.
+    /// When several shortest paths exist, the one whose lenses sort first by
+    /// `(source_hash, target_hash)` wins, so every node holding the same
+    /// lenses picks the same path (see [`Self::ambiguous_lens_paths`]).
     pub fn lens_path(
         &self,
         from: &SchemaHash,
@@ -281,12 +364,14 @@ impl SchemaContext {
         queue.push_back(*from);
         visited.insert(*from);
 
+        let lenses = self.sorted_lenses();
         while let Some(current) = queue.pop_front() {
             // Check all lenses - both forward and backward directions
-            for ((source, target), lens) in &self.lenses {
+            for &lens in &lenses {
                 if skip_drafts && lens.is_draft() {
                     continue;
                 }
+                let (source, target) = (&lens.source_hash, &lens.target_hash);
                 // Forward direction: source -> target
                 if source == &current && !visited.contains(target) {
                     visited.insert(*target);
@@ -316,6 +401,71 @@ impl SchemaContext {
         })
     }
 
+    /// Registered lenses in `(source_hash, target_hash)` order, so path search
+    /// doesn't depend on hash map iteration order.
+    fn sorted_lenses(&self) -> Vec<&Lens> {
+        let mut lenses: Vec<_> = self.lenses.values().collect();
+        lenses.sort_by(|left, right| {
+            left.source_hash
+                .as_bytes()
+                .cmp(right.source_hash.as_bytes())
+                .then_with(|| {
+                    left.target_hash
+                        .as_bytes()
+                        .cmp(right.target_hash.as_bytes())
+                })
+        });
+        lenses
+    }
+
+    /// Live schemas with more than one shortest lens path to the current schema.
+    ///
+    /// `lens_path` breaks such ties deterministically, but equal-length paths
+    /// can still mean different things (e.g. the two sides of a diamond
+    /// filling in different defaults), so these deserve a review.
+    pub fn ambiguous_lens_paths(&self) -> Vec<SchemaHash> {
+        let mut distance = HashMap::from([(self.current_hash, 0usize)]);
+        let mut path_count = HashMap::from([(self.current_hash, 1usize)]);
+        let mut queue = std::collections::VecDeque::from([self.current_hash]);
+        let lenses = self.sorted_lenses();
+
+        // Every predecessor of a node is dequeued before the node itself, so
+        // its path count is final by the time it's propagated.
+        while let Some(current) = queue.pop_front() {
+            let next_distance = distance[&current] + 1;
+            let count = path_count[&current];
+            for lens in &lenses {
+                let next = if lens.source_hash == current {
+                    lens.target_hash
+                } else if lens.target_hash == current {
+                    lens.source_hash
+                } else {
+                    continue;
+                };
+                match distance.get(&next) {
+                    None => {
+                        distance.insert(next, next_distance);
+                        path_count.insert(next, count);
+                        queue.push_back(next);
+                    }
+                    Some(&known) if known == next_distance => {
+                        *path_count.entry(next).or_default() += count;
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        let mut ambiguous: Vec<_> = self
+            .live_schemas
+            .keys()
+            .filter(|hash| path_count.get(hash).is_some_and(|&count| count > 1))
+            .copied()
+            .collect();
+        ambiguous.sort_by(|left, right| left.as_bytes().cmp(right.as_bytes()));
+        ambiguous
+    }
+
     /// Reconstruct the lens path from BFS parent map.
     fn reconstruct_path<'a>(
         &'a self,
@@ -769,4 +919,80 @@ mod tests {
         assert!(path.is_ok());
         assert_eq!(path.unwrap().len(), 2);
     }
+
+    fn make_schema_v2_with_role() -> Schema {
+        SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("id", ColumnType::Uuid)
+                    .column("name", ColumnType::Text)
+                    .nullable_column("role", ColumnType::Text),
+            )
+            .build()
+    }
+
+    fn diamond_context(register_role_side_first: bool) -> (SchemaContext, SchemaHash) {
+        // v1 reaches v3 (current) through either v2 (email first) or v2b (role first).
+        let v1 = make_schema_v1();
+        let v2 = make_schema_v2();
+        let v2b = make_schema_v2_with_role();
+        let v3 = make_schema_v3();
+
+        let email_side = [generate_lens(&v1, &v2), generate_lens(&v2, &v3)];
+        let role_side = [generate_lens(&v1, &v2b), generate_lens(&v2b, &v3)];
+        let (first, second) = if register_role_side_first {
+            (role_side, email_side)
+        } else {
+            (email_side, role_side)
+        };
+
+        let mut ctx = SchemaContext::new(v3, "dev", "main");
+        for lens in first.into_iter().chain(second) {
+            ctx.register_lens(lens);
+        }
+        let v1_hash = SchemaHash::compute(&v1);
+        ctx.live_schemas.insert(v1_hash, v1);
+        ctx.live_schemas.insert(SchemaHash::compute(&v2), v2);
+        ctx.live_schemas.insert(SchemaHash::compute(&v2b), v2b);
+        (ctx, v1_hash)
+    }
+
+    #[test]
+    fn context_lens_path_breaks_diamond_ties_deterministically() {
+        let hops = |ctx: &SchemaContext, from: &SchemaHash| -> Vec<(SchemaHash, SchemaHash)> {
+            ctx.lens_path(from)
+                .unwrap()
+                .into_iter()
+                .map(|(lens, _)| (lens.source_hash, lens.target_hash))
+                .collect()
+        };
+
+        let (email_first, v1_hash) = diamond_context(false);
+        let (role_first, _) = diamond_context(true);
+
+        assert_eq!(hops(&email_first, &v1_hash).len(), 2);
+        assert_eq!(hops(&email_first, &v1_hash), hops(&role_first, &v1_hash));
+        assert_eq!(email_first.ambiguous_lens_paths(), vec![v1_hash]);
+    }
+
+    #[test]
+    fn context_register_lens_keeps_latest_revision() {
+        let v1 = make_schema_v1();
+        let v2 = make_schema_v2();
+        let v1_hash = SchemaHash::compute(&v1);
+        let v2_hash = SchemaHash::compute(&v2);
+
+        let original = generate_lens(&v1, &v2);
+        let revised = generate_lens(&v1, &v2).superseding(&original, "better default");
+
+        let mut ctx = SchemaContext::new(v2, "dev", "main");
+        assert!(ctx.register_lens(revised));
+        // The superseded revision arrives late, e.g. via catalogue sync.
+        assert!(!ctx.register_lens(original));
+
+        let lens = ctx.get_lens(&v1_hash, &v2_hash).unwrap();
+        assert_eq!(lens.revision, 1);
+        assert_eq!(lens.revision_reason.as_deref(), Some("better default"));
+        assert!(ctx.ambiguous_lens_paths().is_empty());
+    }
 }
@@ -9,13 +9,31 @@
 //! - Missing column in new schema → `RemoveColumn` with schema default when present (otherwise `NULL`)
 //! - Table added → `AddTable`
 //! - Table removed → `RemoveTable`
-//! - Column type change → Marked as ambiguity (requires manual review)
+//! - Column type change the `Cast` converter handles (text ↔ temporal, integer
+//!   widening/narrowing, timestamp ↔ bigint, JSON/enum ↔ text) → `ChangeColumnType`
+//!   (marked as draft when some values could become `NULL`)
+//! - Other column type changes → Marked as ambiguity (requires manual review,
+//!   e.g. choosing a fallback or enum renames)
 //! - Possible column rename (same type, one added + one removed) → `RenameColumn` marked as draft
 //! - Possible table rename (same structure) → `RenameTable` marked as draft
-
-use crate::query_manager::types::{ColumnType, Schema, TableSchema, Value};
-
-use super::lens::{LensOp, LensTransform};
+//! - Text column replaced by text columns named after it (`name` →
+//!   `first_name`, `last_name`) → possible split, and the reverse → possible
+//!   merge. Both are only ambiguities; once confirmed through
+//!   [`diff_schemas_confirming`] they become `SplitColumn` / `MergeColumns`
+//!   marked as draft
+//! - New table holding columns removed from an existing table that gains a
+//!   reference to it → `ExtractColumns` marked as draft
+//! - Annotation changes → no op; added and removed tables keep their
+//!   annotations, and rename detection ignores them
+
+use crate::query_manager::types::{
+    Annotations, ColumnDescriptor, ColumnType, Date, Decimal, Interval, RowDescriptor, Schema,
+    TableSchema, Time, Value,
+};
+
+use super::lens::{
+    LensOp, LensTransform, TypeConverter, is_lossy_type_change, is_supported_type_change,
+};
 
 /// Result of a schema diff operation.
 #[derive(Debug, Clone)]
@@ -40,6 +58,24 @@ pub enum Ambiguity {
         old_col: String,
         new_col: String,
     },
+    /// A column might have been split into several.
+    PossibleSplit {
+        table: String,
+        column: String,
+        into: Vec<String>,
+    },
+    /// Several columns might have been merged into one.
+    PossibleMerge {
+        table: String,
+        columns: Vec<String>,
+        into: String,
+    },
+    /// Columns might have moved into a new table.
+    PossibleExtract {
+        table: String,
+        columns: Vec<String>,
+        to_table: String,
+    },
     /// A column's type changed (requires manual migration).
     TypeChange {
         table: String,
@@ -73,6 +109,45 @@ impl std::fmt::Display for Ambiguity {
                     table, old_col, new_col
                 )
             }
+            Ambiguity::PossibleSplit {
+                table,
+                column,
+                into,
+            } => {
+                write!(
+                    f,
+                    "Possible split in {}: {} -> {}",
+                    table,
+                    column,
+                    into.join(", ")
+                )
+            }
+            Ambiguity::PossibleMerge {
+                table,
+                columns,
+                into,
+            } => {
+                write!(
+                    f,
+                    "Possible merge in {}: {} -> {}",
+                    table,
+                    columns.join(", "),
+                    into
+                )
+            }
+            Ambiguity::PossibleExtract {
+                table,
+                columns,
+                to_table,
+            } => {
+                write!(
+                    f,
+                    "Possible extraction from {}: {} -> {}",
+                    table,
+                    columns.join(", "),
+                    to_table
+                )
+            }
             Ambiguity::TypeChange {
                 table,
                 column,
@@ -94,6 +169,16 @@ impl std::fmt::Display for Ambiguity {
 /// Returns a LensTransform that transforms `old` into `new`,
 /// along with any ambiguities that require manual review.
 pub fn diff_schemas(old: &Schema, new: &Schema) -> DiffResult {
+    diff_schemas_confirming(old, new, &[])
+}
+
+/// [`diff_schemas`], turning the `confirmed` split and merge ambiguities into
+/// lens ops.
+///
+/// Column splits and merges are guessed from column names alone, so they are
+/// only reported as ambiguities, with the columns added and removed, until a
+/// reviewer confirms them. Confirmed ones are still emitted as drafts.
+pub fn diff_schemas_confirming(old: &Schema, new: &Schema, confirmed: &[Ambiguity]) -> DiffResult {
     let mut transform = LensTransform::new();
     let mut ambiguities = Vec::new();
 
@@ -144,8 +229,19 @@ pub fn diff_schemas(old: &Schema, new: &Schema) -> DiffResult {
             false,
         );
     }
+    let extractions: Vec<Extraction> = added_tables
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !matched_added.contains(idx))
+        .filter_map(|(_, table_name)| detect_extraction(old, new, table_name))
+        .collect();
+
     for (idx, table_name) in added_tables.iter().enumerate() {
-        if matched_added.contains(&idx) {
+        if matched_added.contains(&idx)
+            || extractions
+                .iter()
+                .any(|extraction| extraction.to_table == table_name.as_str())
+        {
             continue;
         }
         transform.push(
@@ -160,15 +256,45 @@ pub fn diff_schemas(old: &Schema, new: &Schema) -> DiffResult {
     for table_name in old_tables.intersection(&new_tables) {
         let old_table = &old[*table_name];
         let new_table = &new[*table_name];
+        let moved: Vec<&str> = extractions
+            .iter()
+            .filter(|extraction| extraction.table == table_name.as_str())
+            .flat_map(|extraction| {
+                extraction
+                    .columns
+                    .iter()
+                    .chain(std::iter::once(&extraction.fk_column))
+                    .map(String::as_str)
+            })
+            .collect();
         diff_table(
             table_name.as_str(),
             old_table,
             new_table,
+            &moved,
+            confirmed,
             &mut transform,
             &mut ambiguities,
         );
     }
 
+    for extraction in extractions {
+        ambiguities.push(Ambiguity::PossibleExtract {
+            table: extraction.table.clone(),
+            columns: extraction.columns.clone(),
+            to_table: extraction.to_table.clone(),
+        });
+        transform.push(
+            LensOp::ExtractColumns {
+                table: extraction.table,
+                columns: extraction.columns,
+                to_table: extraction.to_table,
+                fk_column: extraction.fk_column,
+            },
+            true,
+        );
+    }
+
     DiffResult {
         transform,
         ambiguities,
@@ -188,7 +314,8 @@ fn detect_possible_table_renames(
                 .iter()
                 .enumerate()
                 .filter_map(|(added_idx, new_table_name)| {
-                    (old[*old_table_name] == new[*new_table_name]).then_some(added_idx)
+                    same_table_shape(&old[*old_table_name], &new[*new_table_name])
+                        .then_some(added_idx)
                 })
                 .collect()
         })
@@ -200,7 +327,8 @@ fn detect_possible_table_renames(
                 .iter()
                 .enumerate()
                 .filter_map(|(removed_idx, old_table_name)| {
-                    (new[*new_table_name] == old[*old_table_name]).then_some(removed_idx)
+                    same_table_shape(&new[*new_table_name], &old[*old_table_name])
+                        .then_some(removed_idx)
                 })
                 .collect()
         })
@@ -221,11 +349,95 @@ fn detect_possible_table_renames(
         .collect()
 }
 
+/// Whether two tables are equal apart from annotations, which describe a
+/// table rather than shape it and so must not block rename detection.
+fn same_table_shape(a: &TableSchema, b: &TableSchema) -> bool {
+    fn without_annotations(table: &TableSchema) -> TableSchema {
+        let columns = table
+            .columns
+            .columns
+            .iter()
+            .map(|column| ColumnDescriptor {
+                annotations: Annotations::new(),
+                ..column.clone()
+            })
+            .collect();
+        TableSchema {
+            columns: RowDescriptor::new(columns),
+            annotations: Annotations::new(),
+            ..table.clone()
+        }
+    }
+    without_annotations(a) == without_annotations(b)
+}
+
+/// Columns moved from `table` into the new table `to_table`.
+struct Extraction {
+    table: String,
+    columns: Vec<String>,
+    to_table: String,
+    fk_column: String,
+}
+
+/// Check whether the new table `to_table` holds only columns removed from a
+/// surviving table that now references it.
+fn detect_extraction(
+    old: &Schema,
+    new: &Schema,
+    to_table: &crate::query_manager::types::TableName,
+) -> Option<Extraction> {
+    let extracted = &new[to_table].columns.columns;
+    if extracted.is_empty() {
+        return None;
+    }
+    let mut candidates = old.iter().filter_map(|(table_name, old_table)| {
+        let new_table = new.get(table_name)?;
+        let removed = |name: &str| {
+            new_table.columns.column(name).is_none()
+                && old_table.columns.column(name).is_some_and(|old_col| {
+                    extracted.iter().any(|col| {
+                        col.name.as_str() == name && col.column_type == old_col.column_type
+                    })
+                })
+        };
+        if !extracted.iter().all(|col| removed(col.name.as_str())) {
+            return None;
+        }
+        let fk = new_table.columns.columns.iter().find(|col| {
+            col.column_type == ColumnType::Uuid
+                && col.references.as_ref() == Some(to_table)
+                && old_table.columns.column(col.name.as_str()).is_none()
+        })?;
+        Some(Extraction {
+            table: table_name.as_str().to_string(),
+            columns: extracted
+                .iter()
+                .map(|col| col.name.as_str().to_string())
+                .collect(),
+            to_table: to_table.as_str().to_string(),
+            fk_column: fk.name.as_str().to_string(),
+        })
+    });
+    let extraction = candidates.next()?;
+    // Only suggest when exactly one table fits.
+    candidates.next().is_none().then_some(extraction)
+}
+
+/// Whether `part` is one of the `_`-separated words of `name`.
+fn has_name_component(name: &str, part: &str) -> bool {
+    name != part && name.split('_').any(|word| word == part)
+}
+
 /// Diff two table schemas and add operations to the transform.
+///
+/// Columns in `moved` are handled by a table-level op and skipped here.
+/// Splits and merges become ops only when listed in `confirmed`.
 fn diff_table(
     table_name: &str,
     old: &TableSchema,
     new: &TableSchema,
+    moved: &[&str],
+    confirmed: &[Ambiguity],
     transform: &mut LensTransform,
     ambiguities: &mut Vec<Ambiguity>,
 ) {
@@ -247,10 +459,18 @@ fn diff_table(
     let new_names: std::collections::HashSet<_> = new_cols.keys().copied().collect();
 
     // Columns only in old (removed or renamed)
-    let removed: Vec<_> = old_names.difference(&new_names).copied().collect();
+    let removed: Vec<_> = old_names
+        .difference(&new_names)
+        .copied()
+        .filter(|name| !moved.contains(name))
+        .collect();
 
     // Columns only in new (added or renamed)
-    let added: Vec<_> = new_names.difference(&old_names).copied().collect();
+    let added: Vec<_> = new_names
+        .difference(&old_names)
+        .copied()
+        .filter(|name| !moved.contains(name))
+        .collect();
 
     // Columns in both (check for type changes)
     for col_name in old_names.intersection(&new_names) {
@@ -258,6 +478,25 @@ fn diff_table(
         let new_col = new_cols[*col_name];
 
         if old_col.column_type != new_col.column_type {
+            if is_supported_type_change(&old_col.column_type, &new_col.column_type) {
+                // Only lossless casts (e.g. temporal → text) are safe to
+                // apply unreviewed.
+                transform.push(
+                    LensOp::ChangeColumnType {
+                        table: table_name.to_string(),
+                        column: col_name.to_string(),
+                        old_type: old_col.column_type.clone(),
+                        new_type: new_col.column_type.clone(),
+                        converter: TypeConverter::Cast,
+                    },
+                    is_lossy_type_change(
+                        &old_col.column_type,
+                        &new_col.column_type,
+                        &TypeConverter::Cast,
+                    ),
+                );
+                continue;
+            }
             // Type changed - this is an ambiguity
             ambiguities.push(Ambiguity::TypeChange {
                 table: table_name.to_string(),
@@ -270,16 +509,109 @@ fn diff_table(
         // (they're constraints, not structural)
     }
 
-    // Try to detect renames: same type, one added + one removed
     let mut handled_removed: std::collections::HashSet<&str> = std::collections::HashSet::new();
     let mut handled_added: std::collections::HashSet<&str> = std::collections::HashSet::new();
 
+    // Try to detect splits and merges: text columns named after the column
+    // they replace. Walk the schemas in column order so suggestions are stable.
+    // Unconfirmed suggestions keep their columns out of rename detection.
+    let mut suggested: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    let is_text =
+        |col: &crate::query_manager::types::ColumnDescriptor| col.column_type == ColumnType::Text;
+    let removed_text: Vec<&str> = old
+        .columns
+        .columns
+        .iter()
+        .filter(|col| is_text(col) && removed.contains(&col.name.as_str()))
+        .map(|col| col.name.as_str())
+        .collect();
+    let added_text: Vec<&str> = new
+        .columns
+        .columns
+        .iter()
+        .filter(|col| is_text(col) && added.contains(&col.name.as_str()))
+        .map(|col| col.name.as_str())
+        .collect();
+
+    for &old_col_name in &removed_text {
+        let into: Vec<&str> = added_text
+            .iter()
+            .copied()
+            .filter(|name| has_name_component(name, old_col_name))
+            .collect();
+        if into.len() < 2 || into.iter().any(|name| suggested.contains(name)) {
+            continue;
+        }
+        suggested.insert(old_col_name);
+        suggested.extend(into.iter().copied());
+        let into: Vec<String> = into.into_iter().map(str::to_string).collect();
+        let ambiguity = Ambiguity::PossibleSplit {
+            table: table_name.to_string(),
+            column: old_col_name.to_string(),
+            into: into.clone(),
+        };
+        if confirmed.contains(&ambiguity) {
+            handled_removed.insert(old_col_name);
+            handled_added.extend(into.iter().map(String::as_str));
+            transform.push(
+                LensOp::SplitColumn {
+                    table: table_name.to_string(),
+                    column: old_col_name.to_string(),
+                    into,
+                    separator: " ".to_string(),
+                },
+                true,
+            );
+        }
+        ambiguities.push(ambiguity);
+    }
+
+    for &new_col_name in &added_text {
+        if suggested.contains(new_col_name) {
+            continue;
+        }
+        let columns: Vec<&str> = removed_text
+            .iter()
+            .copied()
+            .filter(|name| !suggested.contains(name) && has_name_component(name, new_col_name))
+            .collect();
+        if columns.len() < 2 {
+            continue;
+        }
+        suggested.insert(new_col_name);
+        suggested.extend(columns.iter().copied());
+        let columns: Vec<String> = columns.into_iter().map(str::to_string).collect();
+        let ambiguity = Ambiguity::PossibleMerge {
+            table: table_name.to_string(),
+            columns: columns.clone(),
+            into: new_col_name.to_string(),
+        };
+        if confirmed.contains(&ambiguity) {
+            handled_added.insert(new_col_name);
+            handled_removed.extend(columns.iter().map(String::as_str));
+            transform.push(
+                LensOp::MergeColumns {
+                    table: table_name.to_string(),
+                    columns,
+                    into: new_col_name.to_string(),
+                    separator: " ".to_string(),
+                },
+                true,
+            );
+        }
+        ambiguities.push(ambiguity);
+    }
+
+    // Try to detect renames: same type, one added + one removed
     for old_col_name in &removed {
+        if handled_removed.contains(*old_col_name) || suggested.contains(*old_col_name) {
+            continue;
+        }
         let old_col = old_cols[*old_col_name];
 
         // Find an added column with the same type
         for new_col_name in &added {
-            if handled_added.contains(*new_col_name) {
+            if handled_added.contains(*new_col_name) || suggested.contains(*new_col_name) {
                 continue;
             }
 
@@ -348,7 +680,9 @@ fn diff_table(
 }
 
 /// Prefer an explicit schema default, then fall back to a heuristic.
-fn lens_default_for_column(col: &crate::query_manager::types::ColumnDescriptor) -> Value {
+pub(crate) fn lens_default_for_column(
+    col: &crate::query_manager::types::ColumnDescriptor,
+) -> Value {
     col.default
         .clone()
         .unwrap_or_else(|| heuristic_default_for_type(&col.column_type, col.nullable))
@@ -364,6 +698,7 @@ fn heuristic_default_for_type(ct: &ColumnType, nullable: bool) -> Value {
         ColumnType::Integer => Value::Integer(0),
         ColumnType::BigInt => Value::BigInt(0),
         ColumnType::Double => Value::Double(0.0),
+        ColumnType::Decimal { .. } => Value::Decimal(Decimal::ZERO),
         ColumnType::Boolean => Value::Boolean(false),
         ColumnType::Text => Value::Text(String::new()),
         ColumnType::Enum { variants } => variants
@@ -372,7 +707,12 @@ fn heuristic_default_for_type(ct: &ColumnType, nullable: bool) -> Value {
             .map(Value::Text)
             .unwrap_or(Value::Null),
         ColumnType::Timestamp => Value::Timestamp(0),
-        ColumnType::Uuid => Value::Null, // Can't generate a sensible default
+        ColumnType::Date => Value::Date(Date::UNIX_EPOCH),
+        ColumnType::Time => Value::Time(Time::MIDNIGHT),
+        ColumnType::Interval => Value::Interval(Interval::ZERO),
+        ColumnType::GeoPoint => Value::Null, // No meaningful default location
+        ColumnType::Vector { .. } => Value::Null, // A zero vector has no direction
+        ColumnType::Uuid => Value::Null,     // Can't generate a sensible default
         ColumnType::BatchId => Value::BatchId([0; 16]),
         ColumnType::Bytea => Value::Bytea(vec![]),
         ColumnType::Json { schema: _ } => Value::Null,
@@ -541,6 +881,180 @@ mod tests {
         }
     }
 
+    #[test]
+    fn diff_text_to_date_emits_draft_type_change() {
+        let old = make_schema(vec![("todos", vec![("due", ColumnType::Text)])]);
+        let new = make_schema(vec![("todos", vec![("due", ColumnType::Date)])]);
+
+        let result = diff_schemas(&old, &new);
+
+        assert!(result.ambiguities.is_empty());
+        assert_eq!(
+            result.transform.ops,
+            vec![LensOp::ChangeColumnType {
+                table: "todos".to_string(),
+                column: "due".to_string(),
+                old_type: ColumnType::Text,
+                new_type: ColumnType::Date,
+                converter: TypeConverter::Cast,
+            }]
+        );
+        assert_eq!(result.transform.draft_ops, vec![0]);
+
+        let back = diff_schemas(&new, &old);
+        assert!(back.ambiguities.is_empty());
+        assert_eq!(back.transform.ops.len(), 1);
+        assert!(back.transform.draft_ops.is_empty());
+    }
+
+    #[test]
+    fn diff_integer_widening_is_not_draft_but_narrowing_is() {
+        let old = make_schema(vec![("events", vec![("count", ColumnType::Integer)])]);
+        let new = make_schema(vec![("events", vec![("count", ColumnType::BigInt)])]);
+
+        let result = diff_schemas(&old, &new);
+        assert!(result.ambiguities.is_empty());
+        assert_eq!(result.transform.ops.len(), 1);
+        assert!(result.transform.draft_ops.is_empty());
+
+        let back = diff_schemas(&new, &old);
+        assert!(back.ambiguities.is_empty());
+        assert_eq!(back.transform.draft_ops, vec![0]);
+    }
+
+    #[test]
+    fn diff_reports_split_and_merge_until_confirmed() {
+        let old = make_schema(vec![(
+            "users",
+            vec![("id", ColumnType::Uuid), ("name", ColumnType::Text)],
+        )]);
+        let new = make_schema(vec![(
+            "users",
+            vec![
+                ("id", ColumnType::Uuid),
+                ("first_name", ColumnType::Text),
+                ("last_name", ColumnType::Text),
+            ],
+        )]);
+        let split = Ambiguity::PossibleSplit {
+            table: "users".to_string(),
+            column: "name".to_string(),
+            into: vec!["first_name".to_string(), "last_name".to_string()],
+        };
+
+        // Unconfirmed: the columns are removed and added, not renamed.
+        let result = diff_schemas(&old, &new);
+        assert_eq!(result.ambiguities, vec![split.clone()]);
+        let mut changed: Vec<String> = result
+            .transform
+            .ops
+            .iter()
+            .map(|op| match op {
+                LensOp::RemoveColumn { column, .. } => format!("-{column}"),
+                LensOp::AddColumn { column, .. } => format!("+{column}"),
+                other => panic!("unexpected op {other:?}"),
+            })
+            .collect();
+        changed.sort();
+        assert_eq!(changed, vec!["+first_name", "+last_name", "-name"]);
+
+        let result = diff_schemas_confirming(&old, &new, std::slice::from_ref(&split));
+        assert_eq!(
+            result.transform.ops,
+            vec![LensOp::SplitColumn {
+                table: "users".to_string(),
+                column: "name".to_string(),
+                into: vec!["first_name".to_string(), "last_name".to_string()],
+                separator: " ".to_string(),
+            }]
+        );
+        assert_eq!(result.transform.draft_ops, vec![0]);
+        assert_eq!(result.ambiguities, vec![split.clone()]);
+
+        let merge = Ambiguity::PossibleMerge {
+            table: "users".to_string(),
+            columns: vec!["first_name".to_string(), "last_name".to_string()],
+            into: "name".to_string(),
+        };
+        let back = diff_schemas(&new, &old);
+        assert_eq!(back.ambiguities, vec![merge.clone()]);
+        assert!(
+            !back
+                .transform
+                .ops
+                .iter()
+                .any(|op| matches!(op, LensOp::MergeColumns { .. }))
+        );
+
+        // Confirming a different suggestion doesn't confirm this one.
+        let back = diff_schemas_confirming(&new, &old, &[split]);
+        assert!(
+            !back
+                .transform
+                .ops
+                .iter()
+                .any(|op| matches!(op, LensOp::MergeColumns { .. }))
+        );
+
+        let back = diff_schemas_confirming(&new, &old, &[merge]);
+        assert_eq!(
+            back.transform.ops,
+            vec![LensOp::MergeColumns {
+                table: "users".to_string(),
+                columns: vec!["first_name".to_string(), "last_name".to_string()],
+                into: "name".to_string(),
+                separator: " ".to_string(),
+            }]
+        );
+        assert_eq!(back.transform.draft_ops, vec![0]);
+    }
+
+    #[test]
+    fn diff_suggests_extract_for_columns_moved_to_referenced_table() {
+        let old = make_schema(vec![(
+            "users",
+            vec![
+                ("name", ColumnType::Text),
+                ("street", ColumnType::Text),
+                ("city", ColumnType::Text),
+            ],
+        )]);
+        let mut new = make_schema(vec![
+            ("users", vec![("name", ColumnType::Text)]),
+            (
+                "addresses",
+                vec![("street", ColumnType::Text), ("city", ColumnType::Text)],
+            ),
+        ]);
+        new.insert(
+            TableName::new("users"),
+            TableSchema::new(RowDescriptor::new(vec![
+                ColumnDescriptor::new("name", ColumnType::Text),
+                ColumnDescriptor::new("address_id", ColumnType::Uuid).references("addresses"),
+            ])),
+        );
+
+        let result = diff_schemas(&old, &new);
+        assert_eq!(
+            result.transform.ops,
+            vec![LensOp::ExtractColumns {
+                table: "users".to_string(),
+                columns: vec!["street".to_string(), "city".to_string()],
+                to_table: "addresses".to_string(),
+                fk_column: "address_id".to_string(),
+            }]
+        );
+        assert_eq!(result.transform.draft_ops, vec![0]);
+        assert_eq!(
+            result.ambiguities,
+            vec![Ambiguity::PossibleExtract {
+                table: "users".to_string(),
+                columns: vec!["street".to_string(), "city".to_string()],
+                to_table: "addresses".to_string(),
+            }]
+        );
+    }
+
     #[test]
     fn diff_possible_rename() {
         let old = make_schema(vec![("users", vec![("email", ColumnType::Text)])]);
@@ -604,6 +1118,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn diff_ignores_annotations_but_keeps_them_on_added_tables() {
+        let old = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("email", ColumnType::Text)
+                    .annotate_column("label", "Email"),
+            )
+            .build();
+        let new = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("people")
+                    .column("email", ColumnType::Text)
+                    .annotate_column("label", "Email address"),
+            )
+            .table(
+                TableSchema::builder("notes")
+                    .column("body", ColumnType::Text)
+                    .annotate("label", "Notes"),
+            )
+            .build();
+
+        let result = diff_schemas(&old, &new);
+
+        assert_eq!(
+            result.ambiguities,
+            vec![Ambiguity::PossibleTableRename {
+                old_table: "users".to_string(),
+                new_table: "people".to_string(),
+            }]
+        );
+        let added = result.transform.ops.iter().find_map(|op| match op {
+            LensOp::AddTable { table, schema } if table == "notes" => Some(schema),
+            _ => None,
+        });
+        assert_eq!(
+            added.and_then(|schema| schema.annotations.get("label")),
+            Some("Notes")
+        );
+
+        let relabelled = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("email", ColumnType::Text)
+                    .annotate_column("label", "E-mail"),
+            )
+            .build();
+        assert!(diff_schemas(&old, &relabelled).transform.ops.is_empty());
+    }
+
     #[test]
     fn diff_pairs_multiple_unique_table_renames() {
         let old = make_schema(vec![
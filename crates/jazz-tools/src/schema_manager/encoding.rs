@@ -10,18 +10,20 @@ use std::collections::HashMap;
 use crate::object::ObjectId;
 use crate::query_manager::policy::{CmpOp, Operation, PolicyExpr, PolicyValue};
 use crate::query_manager::types::{
-    ColumnDescriptor, ColumnMergeStrategy, ColumnName, ColumnType, RowDescriptor, Schema,
-    SchemaHash, TableName, TablePolicies, TableSchema, Value,
+    Annotations, ColumnDescriptor, ColumnLimits, ColumnMergeStrategy, ColumnName, ColumnType, Date,
+    Decimal, GeoPoint, Interval, RowDescriptor, Schema, SchemaHash, TableLimits, TableName,
+    TablePolicies, TableSchema, Time, Value, Vector, Visibility,
 };
 
-use super::lens::{LensOp, LensTransform};
+use super::lens::{Lens, LensOp, LensTransform, TypeConverter};
 
 /// Current encoding version.
-const SCHEMA_VERSION: u8 = SchemaEncodingVersion::V6 as u8;
+const SCHEMA_VERSION: u8 = SchemaEncodingVersion::V9 as u8;
 const LENS_VERSION: u8 = 2;
 const PERMISSIONS_VERSION: u8 = 1;
 const PERMISSIONS_BUNDLE_VERSION: u8 = 2;
 const PERMISSIONS_HEAD_VERSION: u8 = 2;
+const RELEASE_VERSION: u8 = 1;
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 #[repr(u8)]
@@ -38,6 +40,12 @@ enum SchemaEncodingVersion {
     V5 = 5,
     // v6 schemas include per-table indexed-column overrides.
     V6 = 6,
+    // v7 schemas include column and table size limits.
+    V7 = 7,
+    // v8 schemas include table and column visibility.
+    V8 = 8,
+    // v9 schemas include table and column annotations.
+    V9 = 9,
 }
 
 impl SchemaEncodingVersion {
@@ -49,6 +57,9 @@ impl SchemaEncodingVersion {
             4 => Some(Self::V4),
             5 => Some(Self::V5),
             6 => Some(Self::V6),
+            7 => Some(Self::V7),
+            8 => Some(Self::V8),
+            9 => Some(Self::V9),
             _ => None,
         }
     }
@@ -62,15 +73,30 @@ impl SchemaEncodingVersion {
     }
 
     fn has_column_defaults(self) -> bool {
-        matches!(self, Self::V4 | Self::V5 | Self::V6)
+        matches!(
+            self,
+            Self::V4 | Self::V5 | Self::V6 | Self::V7 | Self::V8 | Self::V9
+        )
     }
 
     fn has_column_merge_strategies(self) -> bool {
-        matches!(self, Self::V5 | Self::V6)
+        matches!(self, Self::V5 | Self::V6 | Self::V7 | Self::V8 | Self::V9)
     }
 
     fn has_indexed_columns(self) -> bool {
-        matches!(self, Self::V6)
+        matches!(self, Self::V6 | Self::V7 | Self::V8 | Self::V9)
+    }
+
+    fn has_limits(self) -> bool {
+        matches!(self, Self::V7 | Self::V8 | Self::V9)
+    }
+
+    fn has_visibility(self) -> bool {
+        matches!(self, Self::V8 | Self::V9)
+    }
+
+    fn has_annotations(self) -> bool {
+        matches!(self, Self::V9)
     }
 }
 
@@ -128,7 +154,7 @@ impl std::error::Error for CatalogueEncodingError {}
 /// table is preserved exactly as declared.
 pub fn encode_schema(schema: &Schema) -> Vec<u8> {
     let mut buf = Vec::new();
-    let version = SchemaEncodingVersion::V6;
+    let version = SchemaEncodingVersion::V9;
     buf.push(version as u8);
 
     // Sort tables by name for deterministic ordering
@@ -198,6 +224,15 @@ pub fn decode_table_descriptor_from_schema(
         if version.has_indexed_columns() {
             skip_indexed_columns(data, &mut offset)?;
         }
+        if version.has_limits() {
+            decode_table_limits(data, &mut offset)?;
+        }
+        if version.has_visibility() {
+            decode_visibility(data, &mut offset)?;
+        }
+        if version.has_annotations() {
+            decode_annotations(data, &mut offset)?;
+        }
         if version.has_table_policies() {
             decode_table_policies(data, &mut offset)?;
         }
@@ -217,6 +252,15 @@ fn encode_table_entry_with_version(
     if version.has_indexed_columns() {
         encode_indexed_columns(buf, schema.indexed_columns.as_deref());
     }
+    if version.has_limits() {
+        encode_table_limits(buf, &schema.limits);
+    }
+    if version.has_visibility() {
+        encode_visibility(buf, schema.visibility);
+    }
+    if version.has_annotations() {
+        encode_annotations(buf, &schema.annotations);
+    }
     if version.has_table_policies() {
         encode_table_policies(buf, &schema.policies);
     }
@@ -234,6 +278,21 @@ fn decode_table_entry_with_version(
     } else {
         None
     };
+    let limits = if version.has_limits() {
+        decode_table_limits(data, offset)?
+    } else {
+        TableLimits::default()
+    };
+    let visibility = if version.has_visibility() {
+        decode_visibility(data, offset)?
+    } else {
+        Visibility::Public
+    };
+    let annotations = if version.has_annotations() {
+        decode_annotations(data, offset)?
+    } else {
+        Annotations::new()
+    };
     if version.has_table_policies() {
         // Legacy schema versions encoded policies inline, but structural schema
         // decode intentionally drops them now that permissions are catalogued
@@ -246,6 +305,9 @@ fn decode_table_entry_with_version(
         TableSchema {
             columns: descriptor,
             indexed_columns,
+            limits,
+            visibility,
+            annotations,
             policies: TablePolicies::default(),
         },
     ))
@@ -285,6 +347,69 @@ fn decode_indexed_columns(
     Ok(Some(columns))
 }
 
+fn encode_table_limits(buf: &mut Vec<u8>, limits: &TableLimits) {
+    encode_optional_u32(buf, limits.max_row_bytes);
+    match limits.max_rows_per_user {
+        Some(max_rows) => {
+            buf.push(1);
+            write_u64(buf, max_rows);
+        }
+        None => buf.push(0),
+    }
+}
+
+fn decode_table_limits(
+    data: &[u8],
+    offset: &mut usize,
+) -> Result<TableLimits, CatalogueEncodingError> {
+    let max_row_bytes = decode_optional_u32(data, offset)?;
+    let max_rows_per_user = if read_u8(data, offset)? != 0 {
+        Some(read_u64(data, offset)?)
+    } else {
+        None
+    };
+    Ok(TableLimits {
+        max_row_bytes,
+        max_rows_per_user,
+    })
+}
+
+fn encode_column_limits(buf: &mut Vec<u8>, limits: &ColumnLimits) {
+    encode_optional_u32(buf, limits.max_bytes);
+    encode_optional_u32(buf, limits.max_items);
+}
+
+fn decode_column_limits(
+    data: &[u8],
+    offset: &mut usize,
+) -> Result<ColumnLimits, CatalogueEncodingError> {
+    Ok(ColumnLimits {
+        max_bytes: decode_optional_u32(data, offset)?,
+        max_items: decode_optional_u32(data, offset)?,
+    })
+}
+
+fn encode_optional_u32(buf: &mut Vec<u8>, value: Option<u32>) {
+    match value {
+        Some(value) => {
+            buf.push(1);
+            write_u32(buf, value);
+        }
+        None => buf.push(0),
+    }
+}
+
+fn decode_optional_u32(
+    data: &[u8],
+    offset: &mut usize,
+) -> Result<Option<u32>, CatalogueEncodingError> {
+    if read_u8(data, offset)? != 0 {
+        Ok(Some(read_u32(data, offset)?))
+    } else {
+        Ok(None)
+    }
+}
+
 fn skip_indexed_columns(data: &[u8], offset: &mut usize) -> Result<(), CatalogueEncodingError> {
     let count = read_u32(data, offset)?;
     if count == u32::MAX {
@@ -399,6 +524,15 @@ fn encode_column_descriptor_with_version(
             None => buf.push(0),
         }
     }
+    if version.has_limits() {
+        encode_column_limits(buf, &col.limits);
+    }
+    if version.has_visibility() {
+        encode_visibility(buf, col.visibility);
+    }
+    if version.has_annotations() {
+        encode_annotations(buf, &col.annotations);
+    }
 }
 
 fn decode_column_descriptor_with_version(
@@ -447,6 +581,21 @@ fn decode_column_descriptor_with_version(
     } else {
         None
     };
+    let limits = if version.has_limits() {
+        decode_column_limits(data, offset)?
+    } else {
+        ColumnLimits::default()
+    };
+    let visibility = if version.has_visibility() {
+        decode_visibility(data, offset)?
+    } else {
+        Visibility::Public
+    };
+    let annotations = if version.has_annotations() {
+        decode_annotations(data, offset)?
+    } else {
+        Annotations::new()
+    };
 
     Ok(ColumnDescriptor {
         name: ColumnName::new(name),
@@ -455,6 +604,9 @@ fn decode_column_descriptor_with_version(
         references,
         default,
         merge_strategy,
+        limits,
+        visibility,
+        annotations,
     })
 }
 
@@ -491,9 +643,73 @@ fn skip_column_descriptor_with_version(
             }
         }
     }
+    if version.has_limits() {
+        decode_column_limits(data, offset)?;
+    }
+    if version.has_visibility() {
+        decode_visibility(data, offset)?;
+    }
+    if version.has_annotations() {
+        decode_annotations(data, offset)?;
+    }
     Ok(())
 }
 
+fn encode_annotations(buf: &mut Vec<u8>, annotations: &Annotations) {
+    let entries: Vec<_> = annotations.iter().collect();
+    write_u32(buf, entries.len() as u32);
+    for (key, annotation) in entries {
+        write_string(buf, key);
+        write_string(buf, &annotation.value);
+        buf.push(annotation.hashed as u8);
+    }
+}
+
+fn decode_annotations(
+    data: &[u8],
+    offset: &mut usize,
+) -> Result<Annotations, CatalogueEncodingError> {
+    let count = read_u32(data, offset)?;
+    let mut annotations = Annotations::new();
+    for _ in 0..count {
+        let key = read_string(data, offset, "annotation_key")?;
+        let value = read_string(data, offset, "annotation_value")?;
+        let hashed = match read_u8(data, offset)? {
+            0 => false,
+            1 => true,
+            tag => {
+                return Err(CatalogueEncodingError::InvalidTypeTag {
+                    tag,
+                    context: "annotation_hashed",
+                });
+            }
+        };
+        annotations.insert_annotation(key, value, hashed);
+    }
+    Ok(annotations)
+}
+
+fn encode_visibility(buf: &mut Vec<u8>, visibility: Visibility) {
+    buf.push(match visibility {
+        Visibility::Public => 0,
+        Visibility::Internal => 1,
+    });
+}
+
+fn decode_visibility(
+    data: &[u8],
+    offset: &mut usize,
+) -> Result<Visibility, CatalogueEncodingError> {
+    match read_u8(data, offset)? {
+        0 => Ok(Visibility::Public),
+        1 => Ok(Visibility::Internal),
+        tag => Err(CatalogueEncodingError::InvalidTypeTag {
+            tag,
+            context: "visibility",
+        }),
+    }
+}
+
 /// Column type tags.
 const TYPE_INTEGER: u8 = 1;
 const TYPE_BIGINT: u8 = 2;
@@ -508,6 +724,12 @@ const TYPE_DOUBLE: u8 = 10;
 const TYPE_BYTEA: u8 = 11;
 const TYPE_JSON: u8 = 12;
 const TYPE_BATCH_ID: u8 = 13;
+const TYPE_DECIMAL: u8 = 14;
+const TYPE_DATE: u8 = 15;
+const TYPE_TIME: u8 = 16;
+const TYPE_INTERVAL: u8 = 17;
+const TYPE_GEO_POINT: u8 = 18;
+const TYPE_VECTOR: u8 = 19;
 
 fn encode_column_type_with_version(
     buf: &mut Vec<u8>,
@@ -518,9 +740,22 @@ fn encode_column_type_with_version(
         ColumnType::Integer => buf.push(TYPE_INTEGER),
         ColumnType::BigInt => buf.push(TYPE_BIGINT),
         ColumnType::Double => buf.push(TYPE_DOUBLE),
+        ColumnType::Decimal { precision, scale } => {
+            buf.push(TYPE_DECIMAL);
+            buf.push(*precision);
+            buf.push(*scale);
+        }
         ColumnType::Boolean => buf.push(TYPE_BOOLEAN),
         ColumnType::Text => buf.push(TYPE_TEXT),
         ColumnType::Timestamp => buf.push(TYPE_TIMESTAMP),
+        ColumnType::Date => buf.push(TYPE_DATE),
+        ColumnType::Time => buf.push(TYPE_TIME),
+        ColumnType::Interval => buf.push(TYPE_INTERVAL),
+        ColumnType::GeoPoint => buf.push(TYPE_GEO_POINT),
+        ColumnType::Vector { dims } => {
+            buf.push(TYPE_VECTOR);
+            write_u32(buf, *dims);
+        }
         ColumnType::Uuid => buf.push(TYPE_UUID),
         ColumnType::BatchId => buf.push(TYPE_BATCH_ID),
         ColumnType::Bytea => buf.push(TYPE_BYTEA),
@@ -567,9 +802,21 @@ fn decode_column_type_with_version(
         TYPE_INTEGER => Ok(ColumnType::Integer),
         TYPE_BIGINT => Ok(ColumnType::BigInt),
         TYPE_DOUBLE => Ok(ColumnType::Double),
+        TYPE_DECIMAL => {
+            let precision = read_u8(data, offset)?;
+            let scale = read_u8(data, offset)?;
+            Ok(ColumnType::Decimal { precision, scale })
+        }
         TYPE_BOOLEAN => Ok(ColumnType::Boolean),
         TYPE_TEXT => Ok(ColumnType::Text),
         TYPE_TIMESTAMP => Ok(ColumnType::Timestamp),
+        TYPE_DATE => Ok(ColumnType::Date),
+        TYPE_TIME => Ok(ColumnType::Time),
+        TYPE_INTERVAL => Ok(ColumnType::Interval),
+        TYPE_GEO_POINT => Ok(ColumnType::GeoPoint),
+        TYPE_VECTOR => Ok(ColumnType::Vector {
+            dims: read_u32(data, offset)?,
+        }),
         TYPE_UUID => Ok(ColumnType::Uuid),
         TYPE_BATCH_ID => Ok(ColumnType::BatchId),
         TYPE_BYTEA => Ok(ColumnType::Bytea),
@@ -625,7 +872,10 @@ fn skip_column_type_with_version(
     let tag = read_u8(data, offset)?;
     match tag {
         TYPE_INTEGER | TYPE_BIGINT | TYPE_DOUBLE | TYPE_BOOLEAN | TYPE_TEXT | TYPE_TIMESTAMP
-        | TYPE_UUID | TYPE_BATCH_ID | TYPE_BYTEA => Ok(()),
+        | TYPE_UUID | TYPE_BATCH_ID | TYPE_BYTEA | TYPE_DATE | TYPE_TIME | TYPE_INTERVAL
+        | TYPE_GEO_POINT => Ok(()),
+        TYPE_DECIMAL => read_bytes(data, offset, 2).map(|_| ()),
+        TYPE_VECTOR => read_u32(data, offset).map(|_| ()),
         TYPE_JSON => {
             let has_schema = read_u8(data, offset)? != 0;
             if has_schema {
@@ -748,6 +998,62 @@ const OP_RENAME_COLUMN: u8 = 3;
 const OP_ADD_TABLE: u8 = 4;
 const OP_REMOVE_TABLE: u8 = 5;
 const OP_RENAME_TABLE: u8 = 6;
+const OP_CHANGE_COLUMN_TYPE: u8 = 7;
+const OP_SPLIT_COLUMN: u8 = 8;
+const OP_MERGE_COLUMNS: u8 = 9;
+const OP_EXTRACT_COLUMNS: u8 = 10;
+const OP_INLINE_COLUMNS: u8 = 11;
+
+/// TypeConverter tags.
+const CONVERTER_CAST: u8 = 0;
+const CONVERTER_PARSE_INTEGER: u8 = 1;
+const CONVERTER_RENAME_VARIANTS: u8 = 2;
+
+fn encode_type_converter(buf: &mut Vec<u8>, converter: &TypeConverter) {
+    match converter {
+        TypeConverter::Cast => buf.push(CONVERTER_CAST),
+        TypeConverter::ParseInteger { fallback } => {
+            buf.push(CONVERTER_PARSE_INTEGER);
+            buf.extend_from_slice(&fallback.to_le_bytes());
+        }
+        TypeConverter::RenameVariants { renames } => {
+            buf.push(CONVERTER_RENAME_VARIANTS);
+            write_u32(buf, renames.len() as u32);
+            for (from, to) in renames {
+                write_string(buf, from);
+                write_string(buf, to);
+            }
+        }
+    }
+}
+
+fn decode_type_converter(
+    data: &[u8],
+    offset: &mut usize,
+) -> Result<TypeConverter, CatalogueEncodingError> {
+    let tag = read_u8(data, offset)?;
+    match tag {
+        CONVERTER_CAST => Ok(TypeConverter::Cast),
+        CONVERTER_PARSE_INTEGER => {
+            let fallback = read_u32(data, offset)? as i32;
+            Ok(TypeConverter::ParseInteger { fallback })
+        }
+        CONVERTER_RENAME_VARIANTS => {
+            let count = read_u32(data, offset)?;
+            let mut renames = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let from = read_string(data, offset, "rename_from")?;
+                let to = read_string(data, offset, "rename_to")?;
+                renames.push((from, to));
+            }
+            Ok(TypeConverter::RenameVariants { renames })
+        }
+        _ => Err(CatalogueEncodingError::InvalidTypeTag {
+            tag,
+            context: "type_converter",
+        }),
+    }
+}
 
 fn encode_lens_op(buf: &mut Vec<u8>, op: &LensOp) {
     match op {
@@ -800,6 +1106,68 @@ fn encode_lens_op(buf: &mut Vec<u8>, op: &LensOp) {
             write_string(buf, table);
             encode_table_schema(buf, schema);
         }
+        LensOp::ChangeColumnType {
+            table,
+            column,
+            old_type,
+            new_type,
+            converter,
+        } => {
+            buf.push(OP_CHANGE_COLUMN_TYPE);
+            write_string(buf, table);
+            write_string(buf, column);
+            encode_column_type(buf, old_type);
+            encode_column_type(buf, new_type);
+            encode_type_converter(buf, converter);
+        }
+        LensOp::SplitColumn {
+            table,
+            column,
+            into,
+            separator,
+        } => {
+            buf.push(OP_SPLIT_COLUMN);
+            write_string(buf, table);
+            write_string(buf, column);
+            write_strings(buf, into);
+            write_string(buf, separator);
+        }
+        LensOp::MergeColumns {
+            table,
+            columns,
+            into,
+            separator,
+        } => {
+            buf.push(OP_MERGE_COLUMNS);
+            write_string(buf, table);
+            write_strings(buf, columns);
+            write_string(buf, into);
+            write_string(buf, separator);
+        }
+        LensOp::ExtractColumns {
+            table,
+            columns,
+            to_table,
+            fk_column,
+        } => {
+            buf.push(OP_EXTRACT_COLUMNS);
+            write_string(buf, table);
+            write_strings(buf, columns);
+            write_string(buf, to_table);
+            write_string(buf, fk_column);
+        }
+        LensOp::InlineColumns {
+            table,
+            columns,
+            from_table,
+            fk_column,
+        } => {
+            buf.push(OP_INLINE_COLUMNS);
+            write_string(buf, table);
+            write_strings(buf, columns);
+            write_string(buf, from_table);
+            write_string(buf, fk_column);
+        }
     }
 }
 
@@ -855,6 +1223,68 @@ fn decode_lens_op(data: &[u8], offset: &mut usize) -> Result<LensOp, CatalogueEn
             let schema = decode_table_schema(data, offset)?;
             Ok(LensOp::RemoveTable { table, schema })
         }
+        OP_CHANGE_COLUMN_TYPE => {
+            let table = read_string(data, offset, "table")?;
+            let column = read_string(data, offset, "column")?;
+            let old_type = decode_column_type(data, offset)?;
+            let new_type = decode_column_type(data, offset)?;
+            let converter = decode_type_converter(data, offset)?;
+            Ok(LensOp::ChangeColumnType {
+                table,
+                column,
+                old_type,
+                new_type,
+                converter,
+            })
+        }
+        OP_SPLIT_COLUMN => {
+            let table = read_string(data, offset, "table")?;
+            let column = read_string(data, offset, "column")?;
+            let into = read_strings(data, offset, "into")?;
+            let separator = read_string(data, offset, "separator")?;
+            Ok(LensOp::SplitColumn {
+                table,
+                column,
+                into,
+                separator,
+            })
+        }
+        OP_MERGE_COLUMNS => {
+            let table = read_string(data, offset, "table")?;
+            let columns = read_strings(data, offset, "columns")?;
+            let into = read_string(data, offset, "into")?;
+            let separator = read_string(data, offset, "separator")?;
+            Ok(LensOp::MergeColumns {
+                table,
+                columns,
+                into,
+                separator,
+            })
+        }
+        OP_EXTRACT_COLUMNS => {
+            let table = read_string(data, offset, "table")?;
+            let columns = read_strings(data, offset, "columns")?;
+            let to_table = read_string(data, offset, "to_table")?;
+            let fk_column = read_string(data, offset, "fk_column")?;
+            Ok(LensOp::ExtractColumns {
+                table,
+                columns,
+                to_table,
+                fk_column,
+            })
+        }
+        OP_INLINE_COLUMNS => {
+            let table = read_string(data, offset, "table")?;
+            let columns = read_strings(data, offset, "columns")?;
+            let from_table = read_string(data, offset, "from_table")?;
+            let fk_column = read_string(data, offset, "fk_column")?;
+            Ok(LensOp::InlineColumns {
+                table,
+                columns,
+                from_table,
+                fk_column,
+            })
+        }
         _ => Err(CatalogueEncodingError::InvalidTypeTag {
             tag,
             context: "lens_op",
@@ -964,6 +1394,9 @@ fn decode_table_schema(
     Ok(TableSchema {
         columns: descriptor,
         indexed_columns: None,
+        limits: TableLimits::default(),
+        visibility: Visibility::Public,
+        annotations: Annotations::new(),
         policies: TablePolicies::default(),
     })
 }
@@ -977,6 +1410,9 @@ fn decode_table_schema_v1(
     Ok(TableSchema {
         columns: descriptor,
         indexed_columns: None,
+        limits: TableLimits::default(),
+        visibility: Visibility::Public,
+        annotations: Annotations::new(),
         policies: TablePolicies::default(),
     })
 }
@@ -1268,6 +1704,146 @@ fn decode_permissions_head_v2(
     ))
 }
 
+// ============================================================================
+// Release Encoding
+// ============================================================================
+
+/// Encode a catalogue release: a schema, the lenses that go with it and an
+/// encoded permissions bundle (see [`encode_permissions_bundle`]).
+///
+/// Format:
+/// ```text
+/// [version: u8][release_version: u64][has_parent: u8][parent_release_id: 16]?
+/// [schema_len: u32][schema][lens_count: u32][lens_1]...[lens_n]
+/// [bundle_len: u32][permissions_bundle]
+///
+/// lens: [source_hash: 32][target_hash: 32][revision: u32]
+///       [has_reason: u8][reason: string]?[transform_len: u32][transform]
+/// ```
+pub fn encode_release(
+    version: u64,
+    parent_release_object_id: Option<ObjectId>,
+    schema: &Schema,
+    lenses: &[Lens],
+    permissions_bundle: &[u8],
+) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.push(RELEASE_VERSION);
+    write_u64(&mut buf, version);
+    match parent_release_object_id {
+        Some(parent_release_object_id) => {
+            buf.push(1);
+            buf.extend_from_slice(parent_release_object_id.uuid().as_bytes());
+        }
+        None => buf.push(0),
+    }
+
+    let encoded_schema = encode_schema(schema);
+    write_u32(&mut buf, encoded_schema.len() as u32);
+    buf.extend_from_slice(&encoded_schema);
+
+    write_u32(&mut buf, lenses.len() as u32);
+    for lens in lenses {
+        buf.extend_from_slice(lens.source_hash.as_bytes());
+        buf.extend_from_slice(lens.target_hash.as_bytes());
+        write_u32(&mut buf, lens.revision);
+        match &lens.revision_reason {
+            Some(reason) => {
+                buf.push(1);
+                write_string(&mut buf, reason);
+            }
+            None => buf.push(0),
+        }
+        let encoded_transform = encode_lens_transform(&lens.forward);
+        write_u32(&mut buf, encoded_transform.len() as u32);
+        buf.extend_from_slice(&encoded_transform);
+    }
+
+    write_u32(&mut buf, permissions_bundle.len() as u32);
+    buf.extend_from_slice(permissions_bundle);
+    buf
+}
+
+type DecodedRelease = (
+    u64,
+    Option<ObjectId>,
+    Schema,
+    Vec<Lens>,
+    DecodedPermissionsBundle,
+);
+
+/// Decode a catalogue release. Fails as a whole if any part is malformed.
+pub fn decode_release(data: &[u8]) -> Result<DecodedRelease, CatalogueEncodingError> {
+    if data.is_empty() {
+        return Err(CatalogueEncodingError::TruncatedData {
+            expected: 1,
+            actual: 0,
+        });
+    }
+    if data[0] != RELEASE_VERSION {
+        return Err(CatalogueEncodingError::UnsupportedVersion {
+            found: data[0],
+            expected: RELEASE_VERSION,
+        });
+    }
+
+    let mut offset = 1;
+    let version = read_u64(data, &mut offset)?;
+    let has_parent = read_u8(data, &mut offset)? != 0;
+    let parent_release_object_id = if has_parent {
+        let parent_uuid =
+            uuid::Uuid::from_slice(read_bytes(data, &mut offset, 16)?).map_err(|err| {
+                CatalogueEncodingError::DecodeError {
+                    message: format!("invalid parent release object id: {err}"),
+                }
+            })?;
+        Some(ObjectId::from_uuid(parent_uuid))
+    } else {
+        None
+    };
+
+    let schema_len = read_u32(data, &mut offset)? as usize;
+    let schema = decode_schema(read_bytes(data, &mut offset, schema_len)?)?;
+
+    let lens_count = read_u32(data, &mut offset)?;
+    let mut lenses = Vec::new();
+    for _ in 0..lens_count {
+        let source_hash = SchemaHash::from_bytes(
+            read_bytes(data, &mut offset, 32)?
+                .try_into()
+                .expect("schema hash length should be exact"),
+        );
+        let target_hash = SchemaHash::from_bytes(
+            read_bytes(data, &mut offset, 32)?
+                .try_into()
+                .expect("schema hash length should be exact"),
+        );
+        let revision = read_u32(data, &mut offset)?;
+        let revision_reason = if read_u8(data, &mut offset)? != 0 {
+            Some(read_string(data, &mut offset, "lens_revision_reason")?)
+        } else {
+            None
+        };
+        let transform_len = read_u32(data, &mut offset)? as usize;
+        let transform = decode_lens_transform(read_bytes(data, &mut offset, transform_len)?)?;
+        let mut lens = Lens::new(source_hash, target_hash, transform);
+        lens.revision = revision;
+        lens.revision_reason = revision_reason;
+        lenses.push(lens);
+    }
+
+    let bundle_len = read_u32(data, &mut offset)? as usize;
+    let permissions_bundle = decode_permissions_bundle(read_bytes(data, &mut offset, bundle_len)?)?;
+
+    Ok((
+        version,
+        parent_release_object_id,
+        schema,
+        lenses,
+        permissions_bundle,
+    ))
+}
+
 fn encode_operation_policy(
     buf: &mut Vec<u8>,
     policy: &crate::query_manager::types::OperationPolicy,
@@ -1751,6 +2327,12 @@ const VALUE_ROW: u8 = 8;
 const VALUE_DOUBLE: u8 = 10;
 const VALUE_BYTEA: u8 = 11;
 const VALUE_BATCH_ID: u8 = 12;
+const VALUE_DECIMAL: u8 = 13;
+const VALUE_DATE: u8 = 14;
+const VALUE_TIME: u8 = 15;
+const VALUE_INTERVAL: u8 = 16;
+const VALUE_GEO_POINT: u8 = 17;
+const VALUE_VECTOR: u8 = 18;
 
 fn encode_value(buf: &mut Vec<u8>, value: &Value) {
     match value {
@@ -1767,6 +2349,11 @@ fn encode_value(buf: &mut Vec<u8>, value: &Value) {
             buf.push(VALUE_DOUBLE);
             buf.extend_from_slice(&f.to_le_bytes());
         }
+        Value::Decimal(d) => {
+            buf.push(VALUE_DECIMAL);
+            buf.extend_from_slice(&d.units().to_le_bytes());
+            buf.push(d.scale());
+        }
         Value::Boolean(b) => {
             buf.push(VALUE_BOOLEAN);
             buf.push(if *b { 1 } else { 0 });
@@ -1779,6 +2366,32 @@ fn encode_value(buf: &mut Vec<u8>, value: &Value) {
             buf.push(VALUE_TIMESTAMP);
             buf.extend_from_slice(&t.to_le_bytes());
         }
+        Value::Date(date) => {
+            buf.push(VALUE_DATE);
+            buf.extend_from_slice(&date.days_since_epoch().to_le_bytes());
+        }
+        Value::Time(time) => {
+            buf.push(VALUE_TIME);
+            buf.extend_from_slice(&time.micros_since_midnight().to_le_bytes());
+        }
+        Value::Interval(interval) => {
+            buf.push(VALUE_INTERVAL);
+            buf.extend_from_slice(&interval.months().to_le_bytes());
+            buf.extend_from_slice(&interval.days().to_le_bytes());
+            buf.extend_from_slice(&interval.micros().to_le_bytes());
+        }
+        Value::GeoPoint(point) => {
+            buf.push(VALUE_GEO_POINT);
+            buf.extend_from_slice(&point.lat().to_le_bytes());
+            buf.extend_from_slice(&point.lon().to_le_bytes());
+        }
+        Value::Vector(vector) => {
+            buf.push(VALUE_VECTOR);
+            write_u32(buf, vector.dims() as u32);
+            for component in vector.as_slice() {
+                buf.extend_from_slice(&component.to_le_bytes());
+            }
+        }
         Value::Uuid(id) => {
             buf.push(VALUE_UUID);
             buf.extend_from_slice(id.uuid().as_bytes());
@@ -1827,6 +2440,16 @@ fn decode_value(data: &[u8], offset: &mut usize) -> Result<Value, CatalogueEncod
             let bytes = read_bytes(data, offset, 8)?;
             Ok(Value::Double(f64::from_le_bytes(bytes.try_into().unwrap())))
         }
+        VALUE_DECIMAL => {
+            let bytes = read_bytes(data, offset, 16)?;
+            let units = i128::from_le_bytes(bytes.try_into().unwrap());
+            let scale = read_u8(data, offset)?;
+            Decimal::new(units, scale)
+                .map(Value::Decimal)
+                .ok_or_else(|| CatalogueEncodingError::DecodeError {
+                    message: format!("decimal out of range: {units} at scale {scale}"),
+                })
+        }
         VALUE_BOOLEAN => {
             let b = read_u8(data, offset)?;
             Ok(Value::Boolean(b != 0))
@@ -1841,6 +2464,53 @@ fn decode_value(data: &[u8], offset: &mut usize) -> Result<Value, CatalogueEncod
                 bytes.try_into().unwrap(),
             )))
         }
+        VALUE_DATE => {
+            let bytes = read_bytes(data, offset, 4)?;
+            Ok(Value::Date(Date::from_days_since_epoch(
+                i32::from_le_bytes(bytes.try_into().unwrap()),
+            )))
+        }
+        VALUE_TIME => {
+            let bytes = read_bytes(data, offset, 8)?;
+            let micros = u64::from_le_bytes(bytes.try_into().unwrap());
+            Time::from_micros(micros).map(Value::Time).ok_or_else(|| {
+                CatalogueEncodingError::DecodeError {
+                    message: format!("time of day out of range: {micros} microseconds"),
+                }
+            })
+        }
+        VALUE_INTERVAL => {
+            let bytes = read_bytes(data, offset, 16)?;
+            Ok(Value::Interval(Interval::new(
+                i32::from_le_bytes(bytes[..4].try_into().unwrap()),
+                i32::from_le_bytes(bytes[4..8].try_into().unwrap()),
+                i64::from_le_bytes(bytes[8..].try_into().unwrap()),
+            )))
+        }
+        VALUE_GEO_POINT => {
+            let bytes = read_bytes(data, offset, 16)?;
+            GeoPoint::new(
+                f64::from_le_bytes(bytes[..8].try_into().unwrap()),
+                f64::from_le_bytes(bytes[8..].try_into().unwrap()),
+            )
+            .map(Value::GeoPoint)
+            .map_err(|error| CatalogueEncodingError::DecodeError {
+                message: error.to_string(),
+            })
+        }
+        VALUE_VECTOR => {
+            let dims = read_u32(data, offset)? as usize;
+            let bytes = read_bytes(data, offset, dims * 4)?;
+            let components = bytes
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+                .collect();
+            Vector::new(components).map(Value::Vector).map_err(|error| {
+                CatalogueEncodingError::DecodeError {
+                    message: error.to_string(),
+                }
+            })
+        }
         VALUE_UUID => {
             let bytes = read_bytes(data, offset, 16)?;
             let uuid =
@@ -1885,11 +2555,20 @@ fn skip_value(data: &[u8], offset: &mut usize) -> Result<(), CatalogueEncodingEr
     let tag = read_u8(data, offset)?;
     match tag {
         VALUE_NULL => Ok(()),
-        VALUE_INTEGER => read_bytes(data, offset, 4).map(|_| ()),
-        VALUE_BIGINT | VALUE_DOUBLE | VALUE_TIMESTAMP => read_bytes(data, offset, 8).map(|_| ()),
+        VALUE_INTEGER | VALUE_DATE => read_bytes(data, offset, 4).map(|_| ()),
+        VALUE_BIGINT | VALUE_DOUBLE | VALUE_TIMESTAMP | VALUE_TIME => {
+            read_bytes(data, offset, 8).map(|_| ())
+        }
         VALUE_BOOLEAN => read_u8(data, offset).map(|_| ()),
         VALUE_TEXT => skip_string(data, offset),
-        VALUE_UUID | VALUE_BATCH_ID => read_bytes(data, offset, 16).map(|_| ()),
+        VALUE_UUID | VALUE_BATCH_ID | VALUE_INTERVAL | VALUE_GEO_POINT => {
+            read_bytes(data, offset, 16).map(|_| ())
+        }
+        VALUE_DECIMAL => read_bytes(data, offset, 17).map(|_| ()),
+        VALUE_VECTOR => {
+            let dims = read_u32(data, offset)? as usize;
+            read_bytes(data, offset, dims * 4).map(|_| ())
+        }
         VALUE_BYTEA => {
             let len = read_u32(data, offset)? as usize;
             read_bytes(data, offset, len).map(|_| ())
@@ -1974,6 +2653,24 @@ fn read_string(
     String::from_utf8(bytes.to_vec()).map_err(|_| CatalogueEncodingError::InvalidUtf8 { context })
 }
 
+fn write_strings(buf: &mut Vec<u8>, strings: &[String]) {
+    write_u32(buf, strings.len() as u32);
+    for s in strings {
+        write_string(buf, s);
+    }
+}
+
+fn read_strings(
+    data: &[u8],
+    offset: &mut usize,
+    context: &'static str,
+) -> Result<Vec<String>, CatalogueEncodingError> {
+    let count = read_u32(data, offset)?;
+    (0..count)
+        .map(|_| read_string(data, offset, context))
+        .collect()
+}
+
 fn skip_string(data: &[u8], offset: &mut usize) -> Result<(), CatalogueEncodingError> {
     let len = read_u32(data, offset)? as usize;
     read_bytes(data, offset, len).map(|_| ())
@@ -2165,6 +2862,123 @@ mod tests {
         assert_eq!(column.merge_strategy, Some(ColumnMergeStrategy::Counter));
     }
 
+    #[test]
+    fn schema_roundtrip_preserves_decimal_columns_and_defaults() {
+        let price_type = ColumnType::Decimal {
+            precision: 10,
+            scale: 2,
+        };
+        let mut schema = Schema::new();
+        schema.insert(
+            TableName::new("products"),
+            TableSchema::new(RowDescriptor::new(vec![
+                ColumnDescriptor::new("price", price_type.clone())
+                    .default(Value::Decimal(Decimal::parse("9.99").unwrap())),
+            ])),
+        );
+
+        let decoded = decode_schema(&encode_schema(&schema)).unwrap();
+        let column = decoded
+            .get(&TableName::new("products"))
+            .and_then(|table| table.columns.column("price").cloned())
+            .expect("decoded price column");
+
+        assert_eq!(column.column_type, price_type);
+        assert_eq!(
+            column.default,
+            Some(Value::Decimal(Decimal::parse("9.99").unwrap()))
+        );
+    }
+
+    #[test]
+    fn schema_roundtrip_preserves_limits() {
+        let schema = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("notes")
+                    .column_descriptor(
+                        ColumnDescriptor::new("body", ColumnType::Text).max_bytes(4096),
+                    )
+                    .column_descriptor(
+                        ColumnDescriptor::new(
+                            "tags",
+                            ColumnType::Array {
+                                element: Box::new(ColumnType::Text),
+                            },
+                        )
+                        .max_items(16),
+                    )
+                    .max_row_bytes(8192)
+                    .max_rows_per_user(500),
+            )
+            .table(TableSchema::builder("plain").column("name", ColumnType::Text))
+            .build();
+
+        let encoded = encode_schema(&schema);
+        assert_eq!(encoded[0], SCHEMA_VERSION);
+
+        let decoded = decode_schema(&encoded).unwrap();
+        assert_eq!(decoded, schema);
+
+        let descriptor = decode_table_descriptor_from_schema(&encoded, "plain")
+            .unwrap()
+            .expect("descriptor for plain");
+        assert_eq!(descriptor.columns.len(), 1);
+    }
+
+    #[test]
+    fn schema_roundtrip_preserves_visibility() {
+        let schema = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("name", ColumnType::Text)
+                    .internal_column("risk_score", ColumnType::Integer),
+            )
+            .table(
+                TableSchema::builder("audit_log")
+                    .column("entry", ColumnType::Text)
+                    .internal(),
+            )
+            .build();
+
+        let encoded = encode_schema(&schema);
+        let decoded = decode_schema(&encoded).unwrap();
+        assert_eq!(decoded, schema);
+
+        let descriptor = decode_table_descriptor_from_schema(&encoded, "users")
+            .unwrap()
+            .expect("descriptor for users");
+        assert!(descriptor.columns[1].visibility.is_internal());
+    }
+
+    #[test]
+    fn schema_roundtrip_preserves_annotations() {
+        let schema = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("tasks")
+                    .column("description", ColumnType::Text)
+                    .annotate_column("component", "textarea")
+                    .column_descriptor(
+                        ColumnDescriptor::new("email", ColumnType::Text)
+                            .annotate_hashed("pii", "contact"),
+                    )
+                    .annotate("label", "Tasks"),
+            )
+            .build();
+
+        let encoded = encode_schema(&schema);
+        let decoded = decode_schema(&encoded).unwrap();
+        assert_eq!(decoded, schema);
+
+        let descriptor = decode_table_descriptor_from_schema(&encoded, "tasks")
+            .unwrap()
+            .expect("descriptor for tasks");
+        assert_eq!(
+            descriptor.columns[0].annotations.get("component"),
+            Some("textarea")
+        );
+        assert!(descriptor.columns[1].annotations.has_hashed());
+    }
+
     #[test]
     fn schema_roundtrip_preserves_indexed_columns() {
         let schema = SchemaBuilder::new()
@@ -2564,6 +3378,68 @@ mod tests {
         assert_eq!(decoded_bundle_object_id, bundle_object_id);
     }
 
+    #[test]
+    fn release_roundtrip_preserves_schema_lenses_and_permissions() {
+        let old_schema = SchemaBuilder::new()
+            .table(TableSchema::builder("todos").column("title", ColumnType::Text))
+            .build();
+        let schema = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("todos")
+                    .column("title", ColumnType::Text)
+                    .nullable_column("owner", ColumnType::Text),
+            )
+            .build();
+        let schema_hash = SchemaHash::compute(&schema);
+        let mut transform = LensTransform::new();
+        transform.push(
+            LensOp::AddColumn {
+                table: "todos".to_string(),
+                column: "owner".to_string(),
+                column_type: ColumnType::Text,
+                default: Value::Null,
+            },
+            false,
+        );
+        let mut lens = Lens::new(SchemaHash::compute(&old_schema), schema_hash, transform);
+        lens.revision = 2;
+        lens.revision_reason = Some("keep owners".to_string());
+        let parent_release_object_id = Some(ObjectId::new());
+        let permissions = HashMap::from([(
+            TableName::new("todos"),
+            TablePolicies::new().with_select(PolicyExpr::True),
+        )]);
+        let bundle = encode_permissions_bundle(schema_hash, 3, None, &permissions);
+
+        let encoded = encode_release(
+            4,
+            parent_release_object_id,
+            &schema,
+            std::slice::from_ref(&lens),
+            &bundle,
+        );
+        let (version, decoded_parent, decoded_schema, decoded_lenses, decoded_bundle) =
+            decode_release(&encoded).expect("release should decode");
+
+        assert_eq!(version, 4);
+        assert_eq!(decoded_parent, parent_release_object_id);
+        assert_eq!(decoded_schema, schema);
+        assert_eq!(decoded_lenses.len(), 1);
+        assert_eq!(decoded_lenses[0].source_hash, lens.source_hash);
+        assert_eq!(decoded_lenses[0].target_hash, lens.target_hash);
+        assert_eq!(decoded_lenses[0].revision, 2);
+        assert_eq!(
+            decoded_lenses[0].revision_reason.as_deref(),
+            Some("keep owners")
+        );
+        assert_eq!(
+            encode_lens_transform(&decoded_lenses[0].forward),
+            encode_lens_transform(&lens.forward)
+        );
+        assert_eq!(decoded_bundle, (schema_hash, 3, None, permissions));
+        assert!(decode_release(&encoded[..encoded.len() - 1]).is_err());
+    }
+
     #[test]
     fn decode_v2_schema_discards_policies() {
         fn encode_schema_v2_with_policies(schema: &Schema) -> Vec<u8> {
@@ -2812,10 +3688,86 @@ mod tests {
             false,
         );
 
+        // ChangeColumnType
+        transform.push(
+            LensOp::ChangeColumnType {
+                table: "t".to_string(),
+                column: "due".to_string(),
+                old_type: ColumnType::Text,
+                new_type: ColumnType::Date,
+                converter: TypeConverter::Cast,
+            },
+            true,
+        );
+        transform.push(
+            LensOp::ChangeColumnType {
+                table: "t".to_string(),
+                column: "age".to_string(),
+                old_type: ColumnType::Text,
+                new_type: ColumnType::Integer,
+                converter: TypeConverter::ParseInteger { fallback: -1 },
+            },
+            false,
+        );
+        transform.push(
+            LensOp::ChangeColumnType {
+                table: "t".to_string(),
+                column: "status".to_string(),
+                old_type: ColumnType::Enum {
+                    variants: vec!["todo".to_string(), "done".to_string()],
+                },
+                new_type: ColumnType::Enum {
+                    variants: vec!["open".to_string(), "done".to_string()],
+                },
+                converter: TypeConverter::RenameVariants {
+                    renames: vec![("todo".to_string(), "open".to_string())],
+                },
+            },
+            false,
+        );
+
+        transform.push(
+            LensOp::SplitColumn {
+                table: "t".to_string(),
+                column: "name".to_string(),
+                into: vec!["first_name".to_string(), "last_name".to_string()],
+                separator: " ".to_string(),
+            },
+            true,
+        );
+        transform.push(
+            LensOp::SplitColumn {
+                table: "t".to_string(),
+                column: "name".to_string(),
+                into: vec!["first_name".to_string(), "last_name".to_string()],
+                separator: " ".to_string(),
+            }
+            .invert(),
+            true,
+        );
+        transform.push(
+            LensOp::ExtractColumns {
+                table: "t".to_string(),
+                columns: vec!["street".to_string(), "city".to_string()],
+                to_table: "addresses".to_string(),
+                fk_column: "address_id".to_string(),
+            },
+            true,
+        );
+        transform.push(
+            LensOp::InlineColumns {
+                table: "t".to_string(),
+                columns: vec!["street".to_string()],
+                from_table: "addresses".to_string(),
+                fk_column: "address_id".to_string(),
+            },
+            false,
+        );
+
         let encoded = encode_lens_transform(&transform);
         let decoded = decode_lens_transform(&encoded).unwrap();
 
-        assert_eq!(decoded.ops.len(), 6);
+        assert_eq!(decoded.ops.len(), 13);
         assert_eq!(decoded.ops, transform.ops);
     }
 
@@ -2828,6 +3780,11 @@ mod tests {
             Value::Boolean(true),
             Value::Text("hello world".to_string()),
             Value::Timestamp(1234567890123456),
+            Value::Date(Date::parse("2024-02-29").unwrap()),
+            Value::Time(Time::parse("13:45:07.25").unwrap()),
+            Value::Interval(Interval::parse("P1M-2DT3H").unwrap()),
+            Value::GeoPoint(GeoPoint::new(52.52, 13.405).unwrap()),
+            Value::Vector(Vector::new(vec![0.25, -1.5, 3.0]).unwrap()),
             Value::Uuid(ObjectId::from_uuid(uuid::Uuid::from_u128(0xDEADBEEF))),
             Value::Bytea(vec![0, 1, 2, 3, 0, 255]),
             Value::Array(vec![Value::Integer(1), Value::Integer(2)]),
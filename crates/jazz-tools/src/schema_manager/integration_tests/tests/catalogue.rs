@@ -248,6 +248,75 @@ fn catalogue_lens_activates_pending_schema() {
     assert_eq!(manager_b.all_branches().len(), 2);
 }
 
+#[test]
+fn catalogue_lens_revision_supersedes_earlier_revision_in_any_order() {
+    let v1 = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("id", ColumnType::Uuid)
+                .column("name", ColumnType::Text),
+        )
+        .build();
+    let v2 = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("id", ColumnType::Uuid)
+                .column("name", ColumnType::Text)
+                .nullable_column("email", ColumnType::Text),
+        )
+        .build();
+
+    let v1_hash = SchemaHash::compute(&v1);
+    let v2_hash = SchemaHash::compute(&v2);
+
+    let original = generate_lens(&v1, &v2);
+    let revised = generate_lens(&v1, &v2).superseding(&original, "backfill email");
+    assert_ne!(original.object_id(), revised.object_id());
+
+    let lens_metadata = |lens: &Lens| {
+        let mut metadata = HashMap::from([
+            (
+                MetadataKey::Type.to_string(),
+                ObjectType::CatalogueLens.to_string(),
+            ),
+            (
+                MetadataKey::AppId.to_string(),
+                test_app_id().uuid().to_string(),
+            ),
+            (MetadataKey::SourceHash.to_string(), v1_hash.to_string()),
+            (MetadataKey::TargetHash.to_string(), v2_hash.to_string()),
+        ]);
+        if let Some(reason) = &lens.revision_reason {
+            metadata.insert(
+                MetadataKey::LensRevision.to_string(),
+                lens.revision.to_string(),
+            );
+            metadata.insert(MetadataKey::LensRevisionReason.to_string(), reason.clone());
+        }
+        metadata
+    };
+
+    // Revision 1 syncs before revision 0 on one node and after it on the other.
+    for order in [[&revised, &original], [&original, &revised]] {
+        let mut manager =
+            SchemaManager::new(SyncManager::new(), v2.clone(), test_app_id(), "dev", "main")
+                .unwrap();
+        for lens in order {
+            manager
+                .process_catalogue_update(
+                    lens.object_id(),
+                    &lens_metadata(lens),
+                    &encode_lens_transform(&lens.forward),
+                )
+                .unwrap();
+        }
+
+        let lens = manager.get_lens(&v1_hash, &v2_hash).unwrap();
+        assert_eq!(lens.revision, 1);
+        assert_eq!(lens.revision_reason.as_deref(), Some("backfill email"));
+    }
+}
+
 #[test]
 fn catalogue_draft_lens_does_not_activate_pending_schema() {
     let v1 = SchemaBuilder::new()
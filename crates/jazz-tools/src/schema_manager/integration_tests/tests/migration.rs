@@ -42,7 +42,7 @@ fn full_migration_add_column() {
     let v1_hash = SchemaHash::compute(&v1);
     let transformer = manager.transformer("users");
     let result = transformer
-        .transform(&v1_data, make_commit_id(1), v1_hash)
+        .transform(id, &v1_data, make_commit_id(1), v1_hash)
         .unwrap();
 
     assert!(result.was_transformed);
@@ -115,7 +115,7 @@ fn multi_table_migration() {
 
     let user_transformer = manager.transformer("users");
     let user_result = user_transformer
-        .transform(&v1_user_data, make_commit_id(1), v1_hash)
+        .transform(user_id, &v1_user_data, make_commit_id(1), v1_hash)
         .unwrap();
     assert!(user_result.was_transformed);
 
@@ -131,7 +131,7 @@ fn multi_table_migration() {
 
     let post_transformer = manager.transformer("posts");
     let post_result = post_transformer
-        .transform(&v1_post_data, make_commit_id(2), v1_hash)
+        .transform(post_id, &v1_post_data, make_commit_id(2), v1_hash)
         .unwrap();
     assert!(post_result.was_transformed);
 
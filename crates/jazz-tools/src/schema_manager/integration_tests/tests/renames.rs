@@ -54,7 +54,7 @@ fn column_rename_lens() {
 
     let transformer = manager.transformer("users");
     let result = transformer
-        .transform(&v1_data, make_commit_id(1), v1_hash)
+        .transform(id, &v1_data, make_commit_id(1), v1_hash)
         .unwrap();
 
     assert!(result.was_transformed);
@@ -84,6 +84,437 @@ fn schema_manager_update_uses_visible_row_after_legacy_commit_history_is_removed
     );
 }
 
+#[test]
+fn schema_gc_rewrites_rows_and_tombstones_retired_schema() {
+    let v1 = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("id", ColumnType::Uuid)
+                .column("name", ColumnType::Text),
+        )
+        .build();
+    let v2 = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("id", ColumnType::Uuid)
+                .column("name", ColumnType::Text)
+                .nullable_column("email", ColumnType::Text),
+        )
+        .build();
+
+    let v1_hash = SchemaHash::compute(&v1);
+    let v2_hash = SchemaHash::compute(&v2);
+    let v1_branch = format!("dev-{}-main", v1_hash.short());
+
+    let mut storage = MemoryStorage::new();
+    let mut admin =
+        SchemaManager::new(SyncManager::new(), v2.clone(), test_app_id(), "dev", "main").unwrap();
+    let lens = admin.add_live_schema(v1.clone()).unwrap().clone();
+    admin.persist_schema(&mut storage);
+    admin.persist_schema_object(&mut storage, &v1);
+    admin.persist_lens(&mut storage, &lens);
+
+    let row_id = ObjectId::new();
+    let v1_table = v1.get(&TableName::new("users")).unwrap();
+    let content = encode_row(
+        &v1_table.columns,
+        &[Value::Uuid(row_id), Value::Text("Alice".to_string())],
+    )
+    .unwrap();
+    ingest_remote_row(
+        admin.query_manager_mut(),
+        &mut storage,
+        "users",
+        v1_hash,
+        row_id,
+        &v1_branch,
+        content,
+        1_000,
+    );
+    admin.process(&mut storage);
+
+    let report = admin
+        .collect_schema_garbage(&mut storage, v2_hash, u64::MAX)
+        .unwrap();
+    assert_eq!(report.retained, vec![v2_hash]);
+    assert_eq!(report.retired, vec![v1_hash]);
+    assert_eq!(report.retired_lenses, vec![(v1_hash, v2_hash)]);
+    assert_eq!(report.rewritten_rows, 1);
+    assert!(admin.is_schema_retired(&v1_hash));
+    assert!(admin.get_lens(&v1_hash, &v2_hash).is_none());
+
+    // The row now lives on the v2 branch, so it survives losing the v1 branch.
+    let query = admin
+        .query("users")
+        .select(&["id", "name", "email"])
+        .build();
+    let results = execute_query(&mut admin, &mut storage, query);
+    assert_eq!(
+        results,
+        vec![(
+            row_id,
+            vec![
+                Value::Uuid(row_id),
+                Value::Text("Alice".to_string()),
+                Value::Null,
+            ],
+        )]
+    );
+
+    // Another node picks the tombstones up through the catalogue.
+    let mut follower =
+        SchemaManager::new(SyncManager::new(), v2, test_app_id(), "dev", "main").unwrap();
+    follower.add_live_schema(v1).unwrap();
+    crate::schema_manager::rehydrate_schema_manager_from_catalogue(
+        &mut follower,
+        &storage,
+        test_app_id(),
+    )
+    .unwrap();
+    assert!(follower.is_schema_retired(&v1_hash));
+    assert!(follower.get_lens(&v1_hash, &v2_hash).is_none());
+    assert!(!follower.all_live_hashes().contains(&v1_hash));
+}
+
+fn users_v1_v2() -> (Schema, Schema) {
+    let v1 = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("id", ColumnType::Uuid)
+                .column("name", ColumnType::Text),
+        )
+        .build();
+    let v2 = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("id", ColumnType::Uuid)
+                .column("name", ColumnType::Text)
+                .nullable_column("email", ColumnType::Text),
+        )
+        .build();
+    (v1, v2)
+}
+
+/// An admin on v2 that published v1 and the lens between them.
+fn admin_publishing_v1_and_v2(
+    storage: &mut MemoryStorage,
+    v1: &Schema,
+    v2: &Schema,
+) -> SchemaManager {
+    let mut admin =
+        SchemaManager::new(SyncManager::new(), v2.clone(), test_app_id(), "dev", "main").unwrap();
+    let lens = admin.add_live_schema(v1.clone()).unwrap().clone();
+    admin.persist_schema(storage);
+    admin.persist_schema_object(storage, v1);
+    admin.persist_lens(storage, &lens);
+    admin
+}
+
+fn ingest_user_row(
+    manager: &mut SchemaManager,
+    storage: &mut MemoryStorage,
+    v1: &Schema,
+    name: &str,
+    timestamp: u64,
+) -> ObjectId {
+    let v1_hash = SchemaHash::compute(v1);
+    let row_id = ObjectId::new();
+    let content = encode_row(
+        &v1.get(&TableName::new("users")).unwrap().columns,
+        &[Value::Uuid(row_id), Value::Text(name.to_string())],
+    )
+    .unwrap();
+    ingest_remote_row(
+        manager.query_manager_mut(),
+        storage,
+        "users",
+        v1_hash,
+        row_id,
+        &format!("dev-{}-main", v1_hash.short()),
+        content,
+        timestamp,
+    );
+    manager.process(storage);
+    row_id
+}
+
+/// Feed catalogue entries to `manager` the way catalogue sync delivers them.
+fn sync_catalogue_entries(
+    manager: &mut SchemaManager,
+    storage: &mut MemoryStorage,
+    entries: Vec<crate::catalogue::CatalogueEntry>,
+) {
+    for entry in entries {
+        ingest_remote_catalogue_object(
+            manager.query_manager_mut(),
+            storage,
+            entry.object_id,
+            entry.metadata,
+            entry.content,
+            0,
+        );
+    }
+    manager.process(storage);
+}
+
+/// A v2 node that knew v1 and then received an admin's retirement of v1
+/// through catalogue sync.
+fn follower_after_v1_retired(v1: &Schema, v2: &Schema) -> (SchemaManager, MemoryStorage) {
+    let v2_hash = SchemaHash::compute(v2);
+    let mut admin_storage = MemoryStorage::new();
+    let mut admin = admin_publishing_v1_and_v2(&mut admin_storage, v1, v2);
+    admin
+        .collect_schema_garbage(&mut admin_storage, v2_hash, u64::MAX)
+        .unwrap();
+
+    let mut storage = MemoryStorage::new();
+    let mut follower =
+        SchemaManager::new(SyncManager::new(), v2.clone(), test_app_id(), "dev", "main").unwrap();
+    follower.add_live_schema(v1.clone()).unwrap();
+    sync_catalogue_entries(
+        &mut follower,
+        &mut storage,
+        admin_storage.scan_catalogue_entries().unwrap(),
+    );
+    (follower, storage)
+}
+
+fn live_lens_catalogue_entry(lens: &Lens) -> crate::catalogue::CatalogueEntry {
+    let mut metadata = HashMap::from([
+        (
+            MetadataKey::Type.to_string(),
+            ObjectType::CatalogueLens.to_string(),
+        ),
+        (
+            MetadataKey::AppId.to_string(),
+            test_app_id().uuid().to_string(),
+        ),
+        (
+            MetadataKey::SourceHash.to_string(),
+            lens.source_hash.to_string(),
+        ),
+        (
+            MetadataKey::TargetHash.to_string(),
+            lens.target_hash.to_string(),
+        ),
+    ]);
+    if let Some(reason) = &lens.revision_reason {
+        metadata.insert(
+            MetadataKey::LensRevision.to_string(),
+            lens.revision.to_string(),
+        );
+        metadata.insert(MetadataKey::LensRevisionReason.to_string(), reason.clone());
+    }
+    crate::catalogue::CatalogueEntry {
+        object_id: lens.object_id(),
+        metadata,
+        content: encode_lens_transform(&lens.forward),
+    }
+}
+
+#[test]
+fn schema_gc_retains_schemas_of_connected_and_recently_seen_clients() {
+    let (v1, v2) = users_v1_v2();
+    let v1_hash = SchemaHash::compute(&v1);
+    let v2_hash = SchemaHash::compute(&v2);
+
+    let mut storage = MemoryStorage::new();
+    let mut admin = admin_publishing_v1_and_v2(&mut storage, &v1, &v2);
+    let cutoff = admin.schema_published_at(&v1_hash).unwrap() + 1;
+
+    // A v1 client is connected, so v1 stays however old its publish is.
+    admin.client_schema_connected(v1_hash);
+    let report = admin
+        .collect_schema_garbage(&mut storage, v2_hash, u64::MAX)
+        .unwrap();
+    assert!(report.retained.contains(&v1_hash));
+    assert!(report.retired.is_empty());
+
+    // Once it disconnects, v1 stays while the client was seen after the cutoff.
+    admin.client_schema_disconnected(v1_hash);
+    let report = admin
+        .collect_schema_garbage(&mut storage, v2_hash, cutoff)
+        .unwrap();
+    assert!(report.retained.contains(&v1_hash));
+    assert!(!admin.is_schema_retired(&v1_hash));
+
+    let report = admin
+        .collect_schema_garbage(&mut storage, v2_hash, u64::MAX)
+        .unwrap();
+    assert_eq!(report.retired, vec![v1_hash]);
+}
+
+#[test]
+fn schema_gc_retains_schemas_clients_still_write_with() {
+    let (v1, v2) = users_v1_v2();
+    let v1_hash = SchemaHash::compute(&v1);
+    let v2_hash = SchemaHash::compute(&v2);
+
+    let mut storage = MemoryStorage::new();
+    let mut admin = admin_publishing_v1_and_v2(&mut storage, &v1, &v2);
+    let published_at = admin.schema_published_at(&v1_hash).unwrap();
+    ingest_user_row(&mut admin, &mut storage, &v1, "Alice", published_at + 10);
+
+    // The v1 branch took a write after the cutoff: a client still uses v1.
+    let report = admin
+        .collect_schema_garbage(&mut storage, v2_hash, published_at + 1)
+        .unwrap();
+    assert!(report.retained.contains(&v1_hash));
+    assert_eq!(report.rewritten_rows, 0);
+    assert!(!admin.is_schema_retired(&v1_hash));
+
+    let report = admin
+        .collect_schema_garbage(&mut storage, v2_hash, published_at + 11)
+        .unwrap();
+    assert_eq!(report.retired, vec![v1_hash]);
+    assert_eq!(report.rewritten_rows, 1);
+}
+
+#[test]
+fn schema_gc_retains_schema_whose_rows_fail_to_rewrite() {
+    use crate::schema_manager::lens::TypeConverter;
+
+    let v1 = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("events")
+                .column("id", ColumnType::Uuid)
+                .column("value", ColumnType::BigInt),
+        )
+        .build();
+    let v2 = SchemaBuilder::new()
+        .table(
+            TableSchema::builder("events")
+                .column("id", ColumnType::Uuid)
+                .column("value", ColumnType::Integer),
+        )
+        .build();
+    let v1_hash = SchemaHash::compute(&v1);
+    let v2_hash = SchemaHash::compute(&v2);
+    let v1_branch = format!("dev-{}-main", v1_hash.short());
+    let lens = Lens::new(
+        v1_hash,
+        v2_hash,
+        LensTransform::with_ops(vec![LensOp::ChangeColumnType {
+            table: "events".to_string(),
+            column: "value".to_string(),
+            old_type: ColumnType::BigInt,
+            new_type: ColumnType::Integer,
+            converter: TypeConverter::Cast,
+        }]),
+    );
+
+    let mut storage = MemoryStorage::new();
+    let mut admin =
+        SchemaManager::new(SyncManager::new(), v2.clone(), test_app_id(), "dev", "main").unwrap();
+    admin
+        .add_live_schema_with_lens(v1.clone(), lens.clone())
+        .unwrap();
+    admin.persist_schema(&mut storage);
+    admin.persist_schema_object(&mut storage, &v1);
+    admin.persist_lens(&mut storage, &lens);
+
+    let v1_columns = &v1.get(&TableName::new("events")).unwrap().columns;
+    for value in [7, 1 << 40] {
+        let row_id = ObjectId::new();
+        let content = encode_row(v1_columns, &[Value::Uuid(row_id), Value::BigInt(value)]).unwrap();
+        ingest_remote_row(
+            admin.query_manager_mut(),
+            &mut storage,
+            "events",
+            v1_hash,
+            row_id,
+            &v1_branch,
+            content,
+            1_000,
+        );
+    }
+    admin.process(&mut storage);
+
+    // The overflowing value has no Integer form and no default to fall back
+    // on, so its row can't move and v1 must stay.
+    let report = admin
+        .collect_schema_garbage(&mut storage, v2_hash, u64::MAX)
+        .unwrap();
+    assert_eq!(report.rewritten_rows, 1);
+    assert!(report.retained.contains(&v1_hash));
+    assert!(report.retired.is_empty());
+    assert!(!admin.is_schema_retired(&v1_hash));
+    assert!(admin.get_lens(&v1_hash, &v2_hash).is_some());
+}
+
+#[test]
+fn schema_gc_tombstones_reach_a_node_through_catalogue_sync() {
+    let (v1, v2) = users_v1_v2();
+    let v1_hash = SchemaHash::compute(&v1);
+    let v2_hash = SchemaHash::compute(&v2);
+
+    let (follower, _storage) = follower_after_v1_retired(&v1, &v2);
+
+    assert!(follower.is_schema_retired(&v1_hash));
+    assert!(follower.get_lens(&v1_hash, &v2_hash).is_none());
+    assert!(!follower.all_live_hashes().contains(&v1_hash));
+}
+
+#[test]
+fn schema_gc_ignores_a_late_lens_for_a_retired_schema() {
+    let (v1, v2) = users_v1_v2();
+    let v1_hash = SchemaHash::compute(&v1);
+    let v2_hash = SchemaHash::compute(&v2);
+
+    let (mut follower, mut storage) = follower_after_v1_retired(&v1, &v2);
+
+    // A lens revision published before the retirement arrives after it.
+    let original = generate_lens(&v1, &v2);
+    let revised = generate_lens(&v1, &v2).superseding(&original, "late fix");
+    sync_catalogue_entries(
+        &mut follower,
+        &mut storage,
+        vec![live_lens_catalogue_entry(&revised)],
+    );
+
+    assert!(follower.is_schema_retired(&v1_hash));
+    assert!(follower.get_lens(&v1_hash, &v2_hash).is_none());
+    assert!(!follower.all_live_hashes().contains(&v1_hash));
+}
+
+#[test]
+fn schema_gc_republished_schema_comes_back() {
+    let (v1, v2) = users_v1_v2();
+    let v1_hash = SchemaHash::compute(&v1);
+    let v2_hash = SchemaHash::compute(&v2);
+
+    let (mut follower, mut storage) = follower_after_v1_retired(&v1, &v2);
+
+    // Republishing writes a live schema entry, then its lens.
+    let schema_entry = crate::catalogue::CatalogueEntry {
+        object_id: v1_hash.to_object_id(),
+        metadata: HashMap::from([
+            (
+                MetadataKey::Type.to_string(),
+                ObjectType::CatalogueSchema.to_string(),
+            ),
+            (
+                MetadataKey::AppId.to_string(),
+                test_app_id().uuid().to_string(),
+            ),
+            (MetadataKey::SchemaHash.to_string(), v1_hash.to_string()),
+        ]),
+        content: encode_schema(&v1),
+    };
+    sync_catalogue_entries(
+        &mut follower,
+        &mut storage,
+        vec![
+            schema_entry,
+            live_lens_catalogue_entry(&generate_lens(&v1, &v2)),
+        ],
+    );
+
+    assert!(!follower.is_schema_retired(&v1_hash));
+    assert!(follower.get_lens(&v1_hash, &v2_hash).is_some());
+    assert!(follower.all_live_hashes().contains(&v1_hash));
+}
+
 #[test]
 fn removed_table_then_readded_does_not_resurface_old_rows() {
     // Branch story:
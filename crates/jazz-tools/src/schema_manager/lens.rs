@@ -3,12 +3,15 @@
 //! Lenses define bidirectional row transformations between schema versions.
 //! They are declarative (auto-invertible) and support row-level operations.
 
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::object::ObjectId;
 #[cfg(test)]
 use crate::query_manager::types::ColumnDescriptor;
-use crate::query_manager::types::{ColumnType, RowDescriptor, SchemaHash, TableSchema, Value};
+use crate::query_manager::types::{
+    ColumnType, RowDescriptor, SchemaHash, TableSchema, Value, parse_temporal_literal,
+};
 
 /// Direction for lens application.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -55,6 +58,50 @@ pub enum LensOp {
         old_name: String,
         new_name: String,
     },
+    /// Change a column's type, converting stored values with
+    /// [`convert_column_value`].
+    ChangeColumnType {
+        table: String,
+        column: String,
+        old_type: ColumnType,
+        new_type: ColumnType,
+        converter: TypeConverter,
+    },
+    /// Split a text column into several, cutting at `separator`. The last
+    /// column takes the remainder; missing parts are `NULL`.
+    SplitColumn {
+        table: String,
+        column: String,
+        into: Vec<String>,
+        separator: String,
+    },
+    /// Join text columns into one with `separator`, skipping `NULL`s.
+    MergeColumns {
+        table: String,
+        columns: Vec<String>,
+        into: String,
+        separator: String,
+    },
+    /// Move `columns` of `table` into rows of `to_table` that share the
+    /// source row's id, and point `fk_column` at them.
+    ExtractColumns {
+        table: String,
+        columns: Vec<String>,
+        to_table: String,
+        fk_column: String,
+    },
+    /// Fold `columns` of `from_table` back into `table`, dropping `fk_column`.
+    ///
+    /// Lossy: a lens sees one row at a time, so rows of `table` read the
+    /// inlined columns as `NULL` rather than the referenced row's values.
+    /// Backward, inlined values move into a `from_table` row keyed by the
+    /// `table` row's own id, so the original reference is not restored.
+    InlineColumns {
+        table: String,
+        columns: Vec<String>,
+        from_table: String,
+        fk_column: String,
+    },
     /// Add a new table.
     AddTable { table: String, schema: TableSchema },
     /// Remove a table.
@@ -112,10 +159,300 @@ impl LensOp {
                 table: table.clone(),
                 schema: schema.clone(),
             },
+            LensOp::ChangeColumnType {
+                table,
+                column,
+                old_type,
+                new_type,
+                converter,
+            } => LensOp::ChangeColumnType {
+                table: table.clone(),
+                column: column.clone(),
+                old_type: new_type.clone(),
+                new_type: old_type.clone(),
+                converter: converter.invert(),
+            },
+            LensOp::SplitColumn {
+                table,
+                column,
+                into,
+                separator,
+            } => LensOp::MergeColumns {
+                table: table.clone(),
+                columns: into.clone(),
+                into: column.clone(),
+                separator: separator.clone(),
+            },
+            LensOp::MergeColumns {
+                table,
+                columns,
+                into,
+                separator,
+            } => LensOp::SplitColumn {
+                table: table.clone(),
+                column: into.clone(),
+                into: columns.clone(),
+                separator: separator.clone(),
+            },
+            LensOp::ExtractColumns {
+                table,
+                columns,
+                to_table,
+                fk_column,
+            } => LensOp::InlineColumns {
+                table: table.clone(),
+                columns: columns.clone(),
+                from_table: to_table.clone(),
+                fk_column: fk_column.clone(),
+            },
+            LensOp::InlineColumns {
+                table,
+                columns,
+                from_table,
+                fk_column,
+            } => LensOp::ExtractColumns {
+                table: table.clone(),
+                columns: columns.clone(),
+                to_table: from_table.clone(),
+                fk_column: fk_column.clone(),
+            },
+        }
+    }
+
+    /// The table this operation applies to.
+    pub fn table(&self) -> &str {
+        match self {
+            LensOp::RenameTable { old_name, .. } => old_name,
+            LensOp::AddColumn { table, .. }
+            | LensOp::RemoveColumn { table, .. }
+            | LensOp::RenameColumn { table, .. }
+            | LensOp::ChangeColumnType { table, .. }
+            | LensOp::SplitColumn { table, .. }
+            | LensOp::MergeColumns { table, .. }
+            | LensOp::ExtractColumns { table, .. }
+            | LensOp::InlineColumns { table, .. }
+            | LensOp::AddTable { table, .. }
+            | LensOp::RemoveTable { table, .. } => table,
+        }
+    }
+}
+
+/// How a [`LensOp::ChangeColumnType`] converts stored values.
+///
+/// Converters work in both directions: the backward transform of a type
+/// change swaps the two types and applies [`TypeConverter::invert`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TypeConverter {
+    /// The conversion implied by the two column types; see
+    /// [`is_supported_type_change`].
+    #[default]
+    Cast,
+    /// Text ↔ Integer. Text that is not a 32-bit integer becomes `fallback`.
+    ParseInteger { fallback: i32 },
+    /// Enum → Enum, renaming variants as `(from, to)` pairs. Several pairs may
+    /// share a `to` to merge variants; the inverse maps a merged variant back
+    /// to the first `from`. Unlisted variants keep their name.
+    RenameVariants { renames: Vec<(String, String)> },
+}
+
+impl TypeConverter {
+    /// The converter for the reverse type change.
+    pub fn invert(&self) -> TypeConverter {
+        match self {
+            TypeConverter::Cast | TypeConverter::ParseInteger { .. } => self.clone(),
+            TypeConverter::RenameVariants { renames } => TypeConverter::RenameVariants {
+                renames: renames
+                    .iter()
+                    .map(|(from, to)| (to.clone(), from.clone()))
+                    .collect(),
+            },
+        }
+    }
+
+    /// Whether this converter can convert between the two column types.
+    pub fn supports(&self, old_type: &ColumnType, new_type: &ColumnType) -> bool {
+        match self {
+            TypeConverter::Cast => is_supported_type_change(old_type, new_type),
+            TypeConverter::ParseInteger { .. } => matches!(
+                (old_type, new_type),
+                (ColumnType::Text, ColumnType::Integer) | (ColumnType::Integer, ColumnType::Text)
+            ),
+            TypeConverter::RenameVariants { .. } => matches!(
+                (old_type, new_type),
+                (ColumnType::Enum { .. }, ColumnType::Enum { .. })
+            ),
+        }
+    }
+}
+
+/// Whether [`TypeConverter::Cast`] knows how to convert between two column types.
+pub fn is_supported_type_change(old_type: &ColumnType, new_type: &ColumnType) -> bool {
+    let is_temporal = |ty: &ColumnType| {
+        matches!(
+            ty,
+            ColumnType::Date | ColumnType::Time | ColumnType::Interval
+        )
+    };
+    let is_textual = |ty: &ColumnType| {
+        matches!(
+            ty,
+            ColumnType::Text | ColumnType::Json { .. } | ColumnType::Enum { .. }
+        )
+    };
+    old_type == new_type
+        || (*old_type == ColumnType::Text && is_temporal(new_type))
+        || (is_temporal(old_type) && *new_type == ColumnType::Text)
+        || (is_textual(old_type) && is_textual(new_type))
+        || matches!(
+            (old_type, new_type),
+            (ColumnType::Integer, ColumnType::BigInt)
+                | (ColumnType::BigInt, ColumnType::Integer)
+                | (ColumnType::Timestamp, ColumnType::BigInt)
+                | (ColumnType::BigInt, ColumnType::Timestamp)
+        )
+}
+
+/// Whether converting from `old_type` to `new_type` with `converter` can lose
+/// information: some stored values become `NULL`, a fallback, or a merged
+/// enum variant.
+pub fn is_lossy_type_change(
+    old_type: &ColumnType,
+    new_type: &ColumnType,
+    converter: &TypeConverter,
+) -> bool {
+    match (old_type, new_type) {
+        _ if old_type == new_type => false,
+        (ColumnType::Date | ColumnType::Time | ColumnType::Interval, ColumnType::Text)
+        | (ColumnType::Integer, ColumnType::BigInt)
+        | (ColumnType::Timestamp, ColumnType::BigInt)
+        | (ColumnType::Integer, ColumnType::Text)
+        | (ColumnType::Json { .. } | ColumnType::Enum { .. }, ColumnType::Text) => false,
+        (ColumnType::Enum { variants: old }, ColumnType::Enum { variants: new }) => {
+            let renames = match converter {
+                TypeConverter::RenameVariants { renames } => renames.as_slice(),
+                _ => &[],
+            };
+            let mut targets = std::collections::HashSet::new();
+            !old.iter().all(|variant| {
+                let target = rename_variant(variant, renames);
+                new.contains(&target) && targets.insert(target)
+            })
         }
+        _ => true,
+    }
+}
+
+/// A stored value that a lens could not convert into a non-nullable column
+/// without a default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionError {
+    pub table: String,
+    pub column: String,
+    pub column_type: ColumnType,
+}
+
+impl std::fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cannot convert stored value of {}.{} to {:?}",
+            self.table, self.column, self.column_type
+        )
     }
 }
 
+impl std::error::Error for ConversionError {}
+
+fn rename_variant(variant: &str, renames: &[(String, String)]) -> String {
+    renames
+        .iter()
+        .find(|(from, _)| from == variant)
+        .map_or(variant, |(_, to)| to.as_str())
+        .to_string()
+}
+
+/// Convert a stored value from `old_type` to `new_type`.
+///
+/// Text parses into temporal types and temporal values render back to their
+/// ISO 8601 text form. Integers widen and narrow, and timestamps map to their
+/// microsecond count. JSON and enum values are stored as text, so they pass
+/// through when the target accepts them. Returns `None` for values that
+/// cannot be converted, unless the converter supplies a fallback; what the
+/// column holds then is up to [`Lens::apply`].
+pub fn convert_column_value(
+    value: &Value,
+    old_type: &ColumnType,
+    new_type: &ColumnType,
+    converter: &TypeConverter,
+) -> Option<Value> {
+    if old_type == new_type {
+        return Some(value.clone());
+    }
+    match (converter, value, new_type) {
+        (_, Value::Null, _) => Some(Value::Null),
+        (TypeConverter::ParseInteger { fallback }, Value::Text(raw), ColumnType::Integer) => {
+            Some(Value::Integer(raw.trim().parse().unwrap_or(*fallback)))
+        }
+        (TypeConverter::ParseInteger { .. }, Value::Integer(n), ColumnType::Text) => {
+            Some(Value::Text(n.to_string()))
+        }
+        (
+            TypeConverter::RenameVariants { renames },
+            Value::Text(variant),
+            ColumnType::Enum { .. },
+        ) => text_into(rename_variant(variant, renames), new_type),
+        (TypeConverter::Cast, Value::Text(raw), _) => match parse_temporal_literal(raw, new_type) {
+            Some(parsed) => parsed.ok(),
+            None => text_into(raw.clone(), new_type),
+        },
+        (TypeConverter::Cast, Value::Date(date), ColumnType::Text) => {
+            Some(Value::Text(date.to_string()))
+        }
+        (TypeConverter::Cast, Value::Time(time), ColumnType::Text) => {
+            Some(Value::Text(time.to_string()))
+        }
+        (TypeConverter::Cast, Value::Interval(interval), ColumnType::Text) => {
+            Some(Value::Text(interval.to_string()))
+        }
+        (TypeConverter::Cast, Value::Integer(n), ColumnType::BigInt) => {
+            Some(Value::BigInt(i64::from(*n)))
+        }
+        (TypeConverter::Cast, Value::BigInt(n), ColumnType::Integer) => {
+            i32::try_from(*n).ok().map(Value::Integer)
+        }
+        (TypeConverter::Cast, Value::Timestamp(micros), ColumnType::BigInt) => {
+            i64::try_from(*micros).ok().map(Value::BigInt)
+        }
+        (TypeConverter::Cast, Value::BigInt(micros), ColumnType::Timestamp) => {
+            u64::try_from(*micros).ok().map(Value::Timestamp)
+        }
+        _ => None,
+    }
+}
+
+/// Store text in a text-backed column, or `None` if the column rejects it.
+fn text_into(text: String, column_type: &ColumnType) -> Option<Value> {
+    let accepted = match column_type {
+        ColumnType::Text => true,
+        ColumnType::Json { .. } => serde_json::from_str::<serde_json::Value>(&text).is_ok(),
+        ColumnType::Enum { variants } => variants.contains(&text),
+        _ => false,
+    };
+    accepted.then_some(Value::Text(text))
+}
+
+/// Cut `text` at `separator` into exactly `parts` values; the last part keeps
+/// the remainder and missing parts are `NULL`.
+fn split_text(text: &str, separator: &str, parts: usize) -> Vec<Value> {
+    let mut values: Vec<Value> = text
+        .splitn(parts, separator)
+        .map(|part| Value::Text(part.to_string()))
+        .collect();
+    values.resize(parts, Value::Null);
+    values
+}
+
 /// A lens transform containing a sequence of operations.
 #[derive(Debug, Clone, Default)]
 pub struct LensTransform {
@@ -178,6 +515,13 @@ pub struct Lens {
     /// Backward transformation: target → source.
     /// Auto-computed from forward by default.
     pub backward: LensTransform,
+    /// Revision of the lens for this schema pair, starting at 0.
+    ///
+    /// A higher revision supersedes every lower one, so a corrected migration
+    /// can be published for a pair that already has a lens.
+    pub revision: u32,
+    /// Why this revision replaced the previous one (`None` for revision 0).
+    pub revision_reason: Option<String>,
 }
 
 impl Lens {
@@ -190,6 +534,8 @@ impl Lens {
             target_hash,
             forward,
             backward,
+            revision: 0,
+            revision_reason: None,
         }
     }
 
@@ -205,16 +551,32 @@ impl Lens {
             target_hash,
             forward,
             backward,
+            revision: 0,
+            revision_reason: None,
         }
     }
 
+    /// Mark this lens as the revision that supersedes `previous`, the lens
+    /// currently published for the same schema pair.
+    pub fn superseding(mut self, previous: &Lens, reason: impl Into<String>) -> Self {
+        self.revision = previous.revision + 1;
+        self.revision_reason = Some(reason.into());
+        self
+    }
+
     /// Compute an ObjectId for storage: UUIDv5(source_hash, target_hash).
+    ///
+    /// Revisions after the first also hash in the revision number, so every
+    /// revision is its own catalogue object and they all sync.
     pub fn object_id(&self) -> ObjectId {
         // Use DNS namespace as a base, then hash our content
         let namespace = Uuid::NAMESPACE_DNS;
-        let mut content = Vec::with_capacity(64);
+        let mut content = Vec::with_capacity(68);
         content.extend_from_slice(self.source_hash.as_bytes());
         content.extend_from_slice(self.target_hash.as_bytes());
+        if self.revision > 0 {
+            content.extend_from_slice(&self.revision.to_le_bytes());
+        }
         ObjectId::from_uuid(Uuid::new_v5(&namespace, &content))
     }
 
@@ -231,6 +593,35 @@ impl Lens {
         }
     }
 
+    /// Whether a `table` row, stored as described by `desc`, has no row in
+    /// `to_table` because every column extracted into it is `NULL`.
+    pub fn extracts_nothing(
+        &self,
+        table: &str,
+        to_table: &str,
+        values: &[Value],
+        desc: &RowDescriptor,
+        direction: Direction,
+    ) -> bool {
+        self.transform(direction).ops.iter().any(|op| match op {
+            LensOp::ExtractColumns {
+                table: op_table,
+                columns,
+                to_table: op_to_table,
+                ..
+            } => {
+                op_table == table
+                    && op_to_table == to_table
+                    && columns.iter().all(|column| {
+                        desc.column_index(column)
+                            .and_then(|idx| values.get(idx))
+                            .is_none_or(Value::is_null)
+                    })
+            }
+            _ => false,
+        })
+    }
+
     /// Translate a table name through the lens for a given direction.
     pub fn translate_table(&self, table: &str, direction: Direction) -> Option<String> {
         let transform = self.transform(direction);
@@ -248,9 +639,22 @@ impl Lens {
                         return None;
                     }
                 }
+                LensOp::InlineColumns {
+                    table, from_table, ..
+                } => {
+                    // Extracted rows share their id with the row they came
+                    // from, so they read as that row.
+                    if current_table == *from_table {
+                        current_table = table.clone();
+                    }
+                }
                 LensOp::AddColumn { .. }
                 | LensOp::RemoveColumn { .. }
-                | LensOp::RenameColumn { .. } => {}
+                | LensOp::RenameColumn { .. }
+                | LensOp::ChangeColumnType { .. }
+                | LensOp::SplitColumn { .. }
+                | LensOp::MergeColumns { .. }
+                | LensOp::ExtractColumns { .. } => {}
             }
         }
 
@@ -288,8 +692,33 @@ impl Lens {
                         return None;
                     }
                 }
-                LensOp::AddColumn { .. } => {
-                    // New columns don't affect existing column references.
+                LensOp::SplitColumn {
+                    table: op_table,
+                    column: split,
+                    ..
+                } => {
+                    if current_table == *op_table && current_column == *split {
+                        return None;
+                    }
+                }
+                LensOp::MergeColumns {
+                    table: op_table,
+                    columns,
+                    ..
+                }
+                | LensOp::ExtractColumns {
+                    table: op_table,
+                    columns,
+                    ..
+                } => {
+                    if current_table == *op_table && columns.contains(&current_column) {
+                        return None;
+                    }
+                }
+                LensOp::AddColumn { .. }
+                | LensOp::ChangeColumnType { .. }
+                | LensOp::InlineColumns { .. } => {
+                    // New columns and type changes don't affect existing column references.
                 }
                 LensOp::RenameTable { .. }
                 | LensOp::AddTable { .. }
@@ -300,17 +729,26 @@ impl Lens {
         Some((current_table, current_column))
     }
 
-    /// Apply the lens transform to a row.
+    /// Apply the lens transform to row `row_id` of `table`, named as in the
+    /// schema the row is stored in.
     /// Returns the transformed row values.
+    ///
+    /// A value that [`convert_column_value`] can't convert becomes `Null` in a
+    /// nullable column and the column default otherwise; a non-nullable
+    /// column without a default fails the row with a [`ConversionError`].
     pub fn apply(
         &self,
+        table: &str,
+        row_id: ObjectId,
         values: &[Value],
         source_desc: &RowDescriptor,
         target_desc: &RowDescriptor,
         direction: Direction,
-    ) -> Vec<Value> {
+    ) -> Result<Vec<Value>, ConversionError> {
         let transform = self.transform(direction);
+        // `None` marks a value whose type conversion failed.
         let mut result: Vec<Option<Value>> = values.iter().cloned().map(Some).collect();
+        let mut current_table = table.to_string();
 
         // Track column name mappings
         let mut column_names: Vec<String> = source_desc
@@ -320,10 +758,17 @@ impl Lens {
             .collect();
 
         for op in &transform.ops {
-            match op {
-                LensOp::RenameTable { .. } => {
-                    // Table renames do not change row values directly.
+            if let LensOp::RenameTable { old_name, new_name } = op {
+                if current_table == *old_name {
+                    current_table = new_name.clone();
                 }
+                continue;
+            }
+            if op.table() != current_table {
+                continue;
+            }
+            match op {
+                LensOp::RenameTable { .. } => {}
                 LensOp::AddColumn {
                     column, default, ..
                 } => {
@@ -346,6 +791,98 @@ impl Lens {
                         column_names[idx] = new_name.clone();
                     }
                 }
+                LensOp::ChangeColumnType {
+                    column,
+                    old_type,
+                    new_type,
+                    converter,
+                    ..
+                } => {
+                    if let Some(idx) = column_names.iter().position(|n| n == column)
+                        && let Some(value) = result[idx].as_ref()
+                    {
+                        result[idx] = convert_column_value(value, old_type, new_type, converter);
+                    }
+                }
+                LensOp::SplitColumn {
+                    column,
+                    into,
+                    separator,
+                    ..
+                } => {
+                    let Some(idx) = column_names.iter().position(|n| n == column) else {
+                        continue;
+                    };
+                    column_names.remove(idx);
+                    let parts = match result.remove(idx) {
+                        Some(Value::Text(text)) => split_text(&text, separator, into.len()),
+                        _ => vec![Value::Null; into.len()],
+                    };
+                    for (name, part) in into.iter().zip(parts) {
+                        result.push(Some(part));
+                        column_names.push(name.clone());
+                    }
+                }
+                LensOp::MergeColumns {
+                    columns,
+                    into,
+                    separator,
+                    ..
+                } => {
+                    let mut parts = Vec::new();
+                    for column in columns {
+                        if let Some(idx) = column_names.iter().position(|n| n == column) {
+                            column_names.remove(idx);
+                            if let Some(Value::Text(text)) = result.remove(idx) {
+                                parts.push(text);
+                            }
+                        }
+                    }
+                    let merged = if parts.is_empty() {
+                        Value::Null
+                    } else {
+                        Value::Text(parts.join(separator))
+                    };
+                    result.push(Some(merged));
+                    column_names.push(into.clone());
+                }
+                LensOp::ExtractColumns {
+                    columns, fk_column, ..
+                } => {
+                    // The extracted columns stay in place: they are projected
+                    // away for `table` and kept when the row is read as a
+                    // `to_table` row. With all of them `NULL` there is no
+                    // `to_table` row to point at.
+                    if !column_names.contains(fk_column) {
+                        let extracted_nothing = columns.iter().all(|column| {
+                            column_names
+                                .iter()
+                                .position(|n| n == column)
+                                .is_none_or(|idx| result[idx] == Some(Value::Null))
+                        });
+                        let fk = if extracted_nothing {
+                            Value::Null
+                        } else {
+                            Value::Uuid(row_id)
+                        };
+                        result.push(Some(fk));
+                        column_names.push(fk_column.clone());
+                    }
+                }
+                LensOp::InlineColumns {
+                    columns, fk_column, ..
+                } => {
+                    if let Some(idx) = column_names.iter().position(|n| n == fk_column) {
+                        result.remove(idx);
+                        column_names.remove(idx);
+                    }
+                    for column in columns {
+                        if !column_names.contains(column) {
+                            result.push(Some(Value::Null));
+                            column_names.push(column.clone());
+                        }
+                    }
+                }
                 LensOp::AddTable { .. } | LensOp::RemoveTable { .. } => {
                     // Table-level ops don't affect row transformation
                 }
@@ -357,14 +894,23 @@ impl Lens {
         for target_col in &target_desc.columns {
             let name = target_col.name.as_str();
             if let Some(idx) = column_names.iter().position(|n| n == name) {
-                final_result.push(result[idx].clone().unwrap_or(Value::Null));
+                let value = match &result[idx] {
+                    Some(value) => value.clone(),
+                    None if target_col.nullable => Value::Null,
+                    None => target_col.default.clone().ok_or_else(|| ConversionError {
+                        table: current_table.clone(),
+                        column: name.to_string(),
+                        column_type: target_col.column_type.clone(),
+                    })?,
+                };
+                final_result.push(value);
             } else {
                 // Column not found - use Null (shouldn't happen with correct lens)
                 final_result.push(Value::Null);
             }
         }
 
-        final_result
+        Ok(final_result)
     }
 }
 
@@ -476,6 +1022,24 @@ mod tests {
         assert_eq!(lens1.object_id(), lens2.object_id());
     }
 
+    #[test]
+    fn lens_revisions_get_their_own_object_ids() {
+        let source = make_hash(1);
+        let target = make_hash(2);
+
+        let original = Lens::new(source, target, LensTransform::new());
+        let revised =
+            Lens::new(source, target, LensTransform::new()).superseding(&original, "fix default");
+        let revised_again =
+            Lens::new(source, target, LensTransform::new()).superseding(&revised, "fix it again");
+
+        assert_eq!(revised.revision, 1);
+        assert_eq!(revised.revision_reason.as_deref(), Some("fix default"));
+        assert_eq!(revised_again.revision, 2);
+        assert_ne!(original.object_id(), revised.object_id());
+        assert_ne!(revised.object_id(), revised_again.object_id());
+    }
+
     #[test]
     fn lens_object_id_different_for_different_hashes() {
         let source = make_hash(1);
@@ -638,7 +1202,16 @@ mod tests {
         let lens = Lens::new(source, target, transform);
 
         let input = vec![Value::Uuid(ObjectId::new())];
-        let output = lens.apply(&input, &source_desc, &target_desc, Direction::Forward);
+        let output = lens
+            .apply(
+                "users",
+                ObjectId::new(),
+                &input,
+                &source_desc,
+                &target_desc,
+                Direction::Forward,
+            )
+            .unwrap();
 
         assert_eq!(output.len(), 2);
         assert_eq!(output[1], Value::Text("unknown".to_string()));
@@ -673,17 +1246,520 @@ mod tests {
             Value::Uuid(ObjectId::new()),
             Value::Text("value".to_string()),
         ];
-        let output = lens.apply(&input, &source_desc, &target_desc, Direction::Forward);
+        let output = lens
+            .apply(
+                "users",
+                ObjectId::new(),
+                &input,
+                &source_desc,
+                &target_desc,
+                Direction::Forward,
+            )
+            .unwrap();
 
         assert_eq!(output.len(), 1);
         // Only id remains
 
         // Now test backward
-        let output_backward = lens.apply(&output, &target_desc, &source_desc, Direction::Backward);
+        let output_backward = lens
+            .apply(
+                "users",
+                ObjectId::new(),
+                &output,
+                &target_desc,
+                &source_desc,
+                Direction::Backward,
+            )
+            .unwrap();
         assert_eq!(output_backward.len(), 2);
         assert_eq!(output_backward[1], Value::Text("default".to_string()));
     }
 
+    #[test]
+    fn lens_apply_change_column_type_text_to_date() {
+        let source = make_hash(1);
+        let target = make_hash(2);
+
+        let source_desc = RowDescriptor::new(vec![ColumnDescriptor::new("due", ColumnType::Text)]);
+        let target_desc = RowDescriptor::new(vec![ColumnDescriptor::new("due", ColumnType::Date)]);
+
+        let mut transform = LensTransform::new();
+        transform.push(
+            LensOp::ChangeColumnType {
+                table: "todos".to_string(),
+                column: "due".to_string(),
+                old_type: ColumnType::Text,
+                new_type: ColumnType::Date,
+                converter: TypeConverter::Cast,
+            },
+            true,
+        );
+
+        let lens = Lens::new(source, target, transform);
+
+        let input = vec![Value::Text("2024-02-29".to_string())];
+        let output = lens
+            .apply(
+                "todos",
+                ObjectId::new(),
+                &input,
+                &source_desc,
+                &target_desc,
+                Direction::Forward,
+            )
+            .unwrap();
+        assert_eq!(
+            output,
+            vec![Value::Date("2024-02-29".parse().expect("valid date"))]
+        );
+
+        let output_backward = lens
+            .apply(
+                "todos",
+                ObjectId::new(),
+                &output,
+                &target_desc,
+                &source_desc,
+                Direction::Backward,
+            )
+            .unwrap();
+        assert_eq!(output_backward, input);
+
+        // Unparseable text cannot be represented as a date.
+        let garbage = vec![Value::Text("next tuesday".to_string())];
+        let output = lens
+            .apply(
+                "todos",
+                ObjectId::new(),
+                &garbage,
+                &source_desc,
+                &target_desc,
+                Direction::Forward,
+            )
+            .unwrap();
+        assert_eq!(output, vec![Value::Null]);
+    }
+
+    #[test]
+    fn lens_apply_parse_integer_uses_fallback_and_round_trips() {
+        let source_desc = RowDescriptor::new(vec![ColumnDescriptor::new("age", ColumnType::Text)]);
+        let target_desc =
+            RowDescriptor::new(vec![ColumnDescriptor::new("age", ColumnType::Integer)]);
+
+        let lens = Lens::new(
+            make_hash(1),
+            make_hash(2),
+            LensTransform::with_ops(vec![LensOp::ChangeColumnType {
+                table: "users".to_string(),
+                column: "age".to_string(),
+                old_type: ColumnType::Text,
+                new_type: ColumnType::Integer,
+                converter: TypeConverter::ParseInteger { fallback: -1 },
+            }]),
+        );
+
+        let forward = |raw: &str| {
+            lens.apply(
+                "users",
+                ObjectId::new(),
+                &[Value::Text(raw.to_string())],
+                &source_desc,
+                &target_desc,
+                Direction::Forward,
+            )
+            .unwrap()
+        };
+        assert_eq!(forward(" 42 "), vec![Value::Integer(42)]);
+        assert_eq!(forward("forty-two"), vec![Value::Integer(-1)]);
+
+        let backward = lens
+            .apply(
+                "users",
+                ObjectId::new(),
+                &[Value::Integer(42)],
+                &target_desc,
+                &source_desc,
+                Direction::Backward,
+            )
+            .unwrap();
+        assert_eq!(backward, vec![Value::Text("42".to_string())]);
+    }
+
+    #[test]
+    fn lens_apply_rename_variants_merges_and_inverts() {
+        let old_status = ColumnType::Enum {
+            variants: vec!["todo".into(), "doing".into(), "done".into()],
+        };
+        let new_status = ColumnType::Enum {
+            variants: vec!["open".into(), "closed".into()],
+        };
+        let source_desc =
+            RowDescriptor::new(vec![ColumnDescriptor::new("status", old_status.clone())]);
+        let target_desc =
+            RowDescriptor::new(vec![ColumnDescriptor::new("status", new_status.clone())]);
+        let converter = TypeConverter::RenameVariants {
+            renames: vec![
+                ("todo".into(), "open".into()),
+                ("doing".into(), "open".into()),
+                ("done".into(), "closed".into()),
+            ],
+        };
+        assert!(is_lossy_type_change(&old_status, &new_status, &converter));
+
+        let lens = Lens::new(
+            make_hash(1),
+            make_hash(2),
+            LensTransform::with_ops(vec![LensOp::ChangeColumnType {
+                table: "tasks".to_string(),
+                column: "status".to_string(),
+                old_type: old_status,
+                new_type: new_status,
+                converter,
+            }]),
+        );
+
+        let text = |s: &str| vec![Value::Text(s.to_string())];
+        assert_eq!(
+            lens.apply(
+                "tasks",
+                ObjectId::new(),
+                &text("doing"),
+                &source_desc,
+                &target_desc,
+                Direction::Forward
+            )
+            .unwrap(),
+            text("open")
+        );
+        assert_eq!(
+            lens.apply(
+                "tasks",
+                ObjectId::new(),
+                &text("done"),
+                &source_desc,
+                &target_desc,
+                Direction::Forward
+            )
+            .unwrap(),
+            text("closed")
+        );
+        // A merged variant maps back to the first variant that produced it.
+        assert_eq!(
+            lens.apply(
+                "tasks",
+                ObjectId::new(),
+                &text("open"),
+                &target_desc,
+                &source_desc,
+                Direction::Backward
+            )
+            .unwrap(),
+            text("todo")
+        );
+    }
+
+    #[test]
+    fn cast_catalogue_converts_numbers_timestamps_and_json() {
+        let cast = |value: Value, old: ColumnType, new: ColumnType| {
+            assert!(TypeConverter::Cast.supports(&old, &new));
+            convert_column_value(&value, &old, &new, &TypeConverter::Cast)
+        };
+        let json = || ColumnType::Json { schema: None };
+
+        assert_eq!(
+            cast(Value::Integer(7), ColumnType::Integer, ColumnType::BigInt),
+            Some(Value::BigInt(7))
+        );
+        assert_eq!(
+            cast(
+                Value::BigInt(1 << 40),
+                ColumnType::BigInt,
+                ColumnType::Integer
+            ),
+            None
+        );
+        assert_eq!(
+            cast(
+                Value::Timestamp(1_700_000_000_000_000),
+                ColumnType::Timestamp,
+                ColumnType::BigInt
+            ),
+            Some(Value::BigInt(1_700_000_000_000_000))
+        );
+        assert_eq!(
+            cast(Value::BigInt(-1), ColumnType::BigInt, ColumnType::Timestamp),
+            None
+        );
+        assert_eq!(
+            cast(Value::Text("{\"a\":1}".into()), json(), ColumnType::Text),
+            Some(Value::Text("{\"a\":1}".into()))
+        );
+        assert_eq!(
+            cast(Value::Text("not json".into()), ColumnType::Text, json()),
+            None
+        );
+
+        assert!(!TypeConverter::Cast.supports(&ColumnType::Text, &ColumnType::Integer));
+        assert!(!is_lossy_type_change(
+            &ColumnType::Integer,
+            &ColumnType::BigInt,
+            &TypeConverter::Cast
+        ));
+        assert!(is_lossy_type_change(
+            &ColumnType::BigInt,
+            &ColumnType::Integer,
+            &TypeConverter::Cast
+        ));
+    }
+
+    #[test]
+    fn lens_apply_split_column_round_trips_through_merge() {
+        let source_desc = RowDescriptor::new(vec![ColumnDescriptor::new("name", ColumnType::Text)]);
+        let target_desc = RowDescriptor::new(vec![
+            ColumnDescriptor::new("first_name", ColumnType::Text),
+            ColumnDescriptor::new("last_name", ColumnType::Text),
+        ]);
+        let lens = Lens::new(
+            make_hash(1),
+            make_hash(2),
+            LensTransform::with_ops(vec![LensOp::SplitColumn {
+                table: "users".to_string(),
+                column: "name".to_string(),
+                into: vec!["first_name".to_string(), "last_name".to_string()],
+                separator: " ".to_string(),
+            }]),
+        );
+        let id = ObjectId::new();
+        let text = |s: &str| Value::Text(s.to_string());
+
+        let split = lens
+            .apply(
+                "users",
+                id,
+                &[text("Ada King Lovelace")],
+                &source_desc,
+                &target_desc,
+                Direction::Forward,
+            )
+            .unwrap();
+        assert_eq!(split, vec![text("Ada"), text("King Lovelace")]);
+        let merged = lens
+            .apply(
+                "users",
+                id,
+                &split,
+                &target_desc,
+                &source_desc,
+                Direction::Backward,
+            )
+            .unwrap();
+        assert_eq!(merged, vec![text("Ada King Lovelace")]);
+
+        let single = lens
+            .apply(
+                "users",
+                id,
+                &[text("Plato")],
+                &source_desc,
+                &target_desc,
+                Direction::Forward,
+            )
+            .unwrap();
+        assert_eq!(single, vec![text("Plato"), Value::Null]);
+        let merged = lens
+            .apply(
+                "users",
+                id,
+                &single,
+                &target_desc,
+                &source_desc,
+                Direction::Backward,
+            )
+            .unwrap();
+        assert_eq!(merged, vec![text("Plato")]);
+
+        // Ops only touch rows of their own table.
+        let untouched = lens
+            .apply(
+                "pets",
+                id,
+                &[text("Rex the Dog")],
+                &source_desc,
+                &source_desc,
+                Direction::Forward,
+            )
+            .unwrap();
+        assert_eq!(untouched, vec![text("Rex the Dog")]);
+    }
+
+    #[test]
+    fn lens_extract_columns_projects_rows_into_both_tables() {
+        let users_v1 = RowDescriptor::new(vec![
+            ColumnDescriptor::new("name", ColumnType::Text),
+            ColumnDescriptor::new("street", ColumnType::Text),
+        ]);
+        let users_v2 = RowDescriptor::new(vec![
+            ColumnDescriptor::new("name", ColumnType::Text),
+            ColumnDescriptor::new("address_id", ColumnType::Uuid).references("addresses"),
+        ]);
+        let addresses_v2 =
+            RowDescriptor::new(vec![ColumnDescriptor::new("street", ColumnType::Text)]);
+        let lens = Lens::new(
+            make_hash(1),
+            make_hash(2),
+            LensTransform::with_ops(vec![LensOp::ExtractColumns {
+                table: "users".to_string(),
+                columns: vec!["street".to_string()],
+                to_table: "addresses".to_string(),
+                fk_column: "address_id".to_string(),
+            }]),
+        );
+        let id = ObjectId::new();
+        let row = vec![
+            Value::Text("Ada".to_string()),
+            Value::Text("1 Main St".to_string()),
+        ];
+
+        assert_eq!(
+            lens.apply("users", id, &row, &users_v1, &users_v2, Direction::Forward)
+                .unwrap(),
+            vec![Value::Text("Ada".to_string()), Value::Uuid(id)]
+        );
+        assert_eq!(
+            lens.apply(
+                "users",
+                id,
+                &row,
+                &users_v1,
+                &addresses_v2,
+                Direction::Forward
+            )
+            .unwrap(),
+            vec![Value::Text("1 Main St".to_string())]
+        );
+
+        // Extracted rows are stored in the old table; the moved column is gone.
+        assert_eq!(
+            lens.translate_table("addresses", Direction::Backward),
+            Some("users".to_string())
+        );
+        assert_eq!(
+            lens.translate_table_and_column("users", "street", Direction::Forward),
+            None
+        );
+
+        // Backward, the row no longer carries the moved column.
+        let v2_row = vec![Value::Text("Ada".to_string()), Value::Uuid(id)];
+        assert_eq!(
+            lens.apply(
+                "users",
+                id,
+                &v2_row,
+                &users_v2,
+                &users_v1,
+                Direction::Backward
+            )
+            .unwrap(),
+            vec![Value::Text("Ada".to_string()), Value::Null]
+        );
+    }
+
+    #[test]
+    fn lens_inline_columns_loses_referenced_values_and_reference() {
+        let users_v1 = RowDescriptor::new(vec![
+            ColumnDescriptor::new("name", ColumnType::Text),
+            ColumnDescriptor::new("address_id", ColumnType::Uuid)
+                .nullable()
+                .references("addresses"),
+        ]);
+        let users_v2 = RowDescriptor::new(vec![
+            ColumnDescriptor::new("name", ColumnType::Text),
+            ColumnDescriptor::new("street", ColumnType::Text).nullable(),
+        ]);
+        let addresses_v1 =
+            RowDescriptor::new(vec![ColumnDescriptor::new("street", ColumnType::Text)]);
+        let lens = Lens::new(
+            make_hash(1),
+            make_hash(2),
+            LensTransform::with_ops(vec![LensOp::InlineColumns {
+                table: "users".to_string(),
+                columns: vec!["street".to_string()],
+                from_table: "addresses".to_string(),
+                fk_column: "address_id".to_string(),
+            }]),
+        );
+        let id = ObjectId::new();
+        let address_id = ObjectId::new();
+
+        // Forward, the referenced street is not reachable from the user row.
+        let v1_row = vec![Value::Text("Ada".to_string()), Value::Uuid(address_id)];
+        assert_eq!(
+            lens.apply(
+                "users",
+                id,
+                &v1_row,
+                &users_v1,
+                &users_v2,
+                Direction::Forward
+            )
+            .unwrap(),
+            vec![Value::Text("Ada".to_string()), Value::Null]
+        );
+
+        // Backward, the street moves to an address keyed by the user's id,
+        // not the address the user referenced.
+        let v2_row = vec![
+            Value::Text("Ada".to_string()),
+            Value::Text("1 Main St".to_string()),
+        ];
+        assert_eq!(
+            lens.apply(
+                "users",
+                id,
+                &v2_row,
+                &users_v2,
+                &users_v1,
+                Direction::Backward
+            )
+            .unwrap(),
+            vec![Value::Text("Ada".to_string()), Value::Uuid(id)]
+        );
+        assert_eq!(
+            lens.apply(
+                "users",
+                id,
+                &v2_row,
+                &users_v2,
+                &addresses_v1,
+                Direction::Backward
+            )
+            .unwrap(),
+            vec![Value::Text("1 Main St".to_string())]
+        );
+
+        // A user without a street has no address at all.
+        let no_street = vec![Value::Text("Bob".to_string()), Value::Null];
+        assert!(lens.extracts_nothing(
+            "users",
+            "addresses",
+            &no_street,
+            &users_v2,
+            Direction::Backward
+        ));
+        assert_eq!(
+            lens.apply(
+                "users",
+                id,
+                &no_street,
+                &users_v2,
+                &users_v1,
+                Direction::Backward
+            )
+            .unwrap(),
+            vec![Value::Text("Bob".to_string()), Value::Null]
+        );
+    }
+
     #[test]
     fn lens_is_draft() {
         let source = make_hash(1);
@@ -34,11 +34,12 @@ use super::auto_lens::generate_lens;
 use super::context::{SchemaContext, SchemaError};
 use super::encoding::{
     decode_lens_transform, decode_permissions, decode_permissions_bundle, decode_permissions_head,
-    decode_schema, encode_lens_transform, encode_permissions, encode_permissions_bundle,
-    encode_permissions_head, encode_schema,
+    decode_release, decode_schema, encode_lens_transform, encode_permissions,
+    encode_permissions_bundle, encode_permissions_head, encode_release, encode_schema,
 };
 use super::lens::Lens;
 use super::types::AppId;
+use super::visibility::SchemaProjection;
 
 #[derive(Clone, Debug, PartialEq)]
 struct PermissionsBundleState {
@@ -70,6 +71,26 @@ pub struct CurrentPermissionsSummary {
     pub permissions: HashMap<TableName, TablePolicies>,
 }
 
+/// A schema, the lenses published with it and its permissions, applied
+/// together on every node.
+#[derive(Clone, Debug)]
+struct ReleaseState {
+    version: u64,
+    parent_release_object_id: Option<ObjectId>,
+    schema: Schema,
+    lenses: Vec<Lens>,
+    permissions: PermissionsBundleState,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReleaseSummary {
+    pub object_id: ObjectId,
+    pub version: u64,
+    pub parent_release_object_id: Option<ObjectId>,
+    pub schema_hash: SchemaHash,
+    pub permissions_version: u64,
+}
+
 #[derive(Clone, Debug)]
 struct InsertAlignmentColumn {
     name: String,
@@ -83,6 +104,21 @@ struct InsertAlignmentPlan {
     positions_by_name: HashMap<String, usize>,
 }
 
+/// Outcome of [`SchemaManager::collect_schema_garbage`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaGcReport {
+    /// Schemas kept because they are the target, were published recently, are
+    /// used by connected or recently seen clients, sit on a retained schema's
+    /// lens path, or still hold rows that couldn't be rewritten.
+    pub retained: Vec<SchemaHash>,
+    /// Schemas tombstoned in the catalogue.
+    pub retired: Vec<SchemaHash>,
+    /// `(source, target)` pairs of the lenses tombstoned with them.
+    pub retired_lenses: Vec<(SchemaHash, SchemaHash)>,
+    /// Rows copied from a retired schema's branch onto the target branch.
+    pub rewritten_rows: usize,
+}
+
 /// SchemaManager coordinates schema evolution with query execution.
 ///
 /// It manages:
@@ -138,6 +174,9 @@ pub struct SchemaManager {
     current_permissions_head: Option<PermissionsHeadState>,
     known_permissions_bundles: HashMap<ObjectId, PermissionsBundleState>,
     pending_permissions_head: Option<PermissionsHeadState>,
+    known_releases: HashMap<ObjectId, ReleaseState>,
+    /// The release with the highest version seen so far.
+    current_release: Option<ObjectId>,
     /// Schemas known to this manager (for server mode).
     /// Server adds schemas here when received via catalogue sync.
     /// These are stored without requiring a lens path to current.
@@ -145,6 +184,13 @@ pub struct SchemaManager {
     known_schemas_dirty: bool,
     persisted_current_schema_in_storage: HashSet<(usize, SchemaHash)>,
     insert_alignment_cache: HashMap<(SchemaHash, TableName), Arc<InsertAlignmentPlan>>,
+    /// Schemas tombstoned by schema GC, so late catalogue entries for them
+    /// (or lenses touching them) don't bring them back.
+    retired_schemas: HashSet<SchemaHash>,
+    /// Open client connections per declared schema; schema GC keeps these.
+    connected_client_schemas: HashMap<SchemaHash, usize>,
+    /// When a client declaring each schema last connected or disconnected.
+    client_schemas_seen_at: HashMap<SchemaHash, u64>,
 }
 
 impl SchemaManager {
@@ -204,9 +250,9 @@ impl SchemaManager {
 
         // Initialize known_schemas with current schema
         let mut known_schemas = HashMap::new();
-        known_schemas.insert(current_hash, structural_schema);
+        known_schemas.insert(current_hash, structural_schema.clone());
 
-        Ok(Self {
+        let mut manager = Self {
             context,
             query_manager,
             app_id,
@@ -214,11 +260,18 @@ impl SchemaManager {
             current_permissions_head: None,
             known_permissions_bundles: HashMap::new(),
             pending_permissions_head: None,
+            known_releases: HashMap::new(),
+            current_release: None,
             known_schemas: Arc::new(known_schemas),
             known_schemas_dirty: true,
             persisted_current_schema_in_storage: HashSet::new(),
             insert_alignment_cache: HashMap::new(),
-        })
+            retired_schemas: HashSet::new(),
+            connected_client_schemas: HashMap::new(),
+            client_schemas_seen_at: HashMap::new(),
+        };
+        manager.register_client_projection(&structural_schema);
+        Ok(manager)
     }
 
     /// Create with default environment ("dev").
@@ -250,10 +303,15 @@ impl SchemaManager {
             current_permissions_head: None,
             known_permissions_bundles: HashMap::new(),
             pending_permissions_head: None,
+            known_releases: HashMap::new(),
+            current_release: None,
             known_schemas: Arc::new(HashMap::new()),
             known_schemas_dirty: false,
             persisted_current_schema_in_storage: HashSet::new(),
             insert_alignment_cache: HashMap::new(),
+            retired_schemas: HashSet::new(),
+            connected_client_schemas: HashMap::new(),
+            client_schemas_seen_at: HashMap::new(),
         }
     }
 
@@ -281,16 +339,55 @@ impl SchemaManager {
 
         Arc::make_mut(&mut self.known_schemas).insert(hash, schema.clone());
         self.known_schemas_dirty = true;
+        self.retired_schemas.remove(&hash);
 
         // If we have a current schema context, also try the lens-path activation
         if self.context.is_initialized() {
             self.context.add_pending_schema(schema.clone());
             self.activate_pending_and_sync_to_query_manager();
         }
+        self.register_client_projection(&schema);
 
         self.try_apply_pending_permissions_head();
     }
 
+    /// Make the client projection of a schema with internal tables or
+    /// columns known, lensed from the full schema.
+    ///
+    /// User-role clients only ever see the projection (see
+    /// [`super::visibility`]), so rows they write land on its branch and must
+    /// stay reachable from the full schema.
+    fn register_client_projection(&mut self, schema: &Schema) {
+        let Some(projection) = SchemaProjection::of(schema) else {
+            return;
+        };
+        let (full_hash, projected_hash) = (projection.full_hash, projection.projected_hash);
+        if self.retired_schemas.contains(&full_hash) {
+            return;
+        }
+
+        if !self.known_schemas.contains_key(&projected_hash) {
+            Arc::make_mut(&mut self.known_schemas)
+                .insert(projected_hash, projection.projected.clone());
+            self.known_schemas_dirty = true;
+        }
+
+        // A published lens between the two (a projection that equals an older
+        // schema version) takes precedence over the derived one.
+        if self.context.get_lens(&full_hash, &projected_hash).is_none()
+            && self.context.get_lens(&projected_hash, &full_hash).is_none()
+        {
+            let lens = projection.lens(schema);
+            self.context.register_lens(lens.clone());
+            self.query_manager.register_lens(lens);
+        }
+
+        if self.context.is_initialized() && !self.context.is_live(&projected_hash) {
+            self.context.add_pending_schema(projection.projected);
+        }
+        self.activate_pending_and_sync_to_query_manager();
+    }
+
     /// Get a known schema by hash.
     pub fn get_known_schema(&self, hash: &SchemaHash) -> Option<&Schema> {
         self.known_schemas.get(hash)
@@ -575,6 +672,10 @@ impl SchemaManager {
     /// Register a lens between two schemas.
     ///
     /// Also registers the lens in QueryManager and tries to activate pending schemas.
+    ///
+    /// A lens for a pair that already has one must be a later revision (see
+    /// [`Lens::superseding`]); re-registering the same revision unchanged is
+    /// a no-op.
     pub fn register_lens(&mut self, lens: Lens) -> Result<(), SchemaError> {
         if lens.is_draft() {
             return Err(SchemaError::DraftLensInPath {
@@ -583,12 +684,35 @@ impl SchemaManager {
             });
         }
 
+        if let Some(existing) = self.context.get_lens(&lens.source_hash, &lens.target_hash)
+            && existing.revision >= lens.revision
+        {
+            let unchanged = existing.revision == lens.revision
+                && encode_lens_transform(&existing.forward) == encode_lens_transform(&lens.forward);
+            if unchanged {
+                return Ok(());
+            }
+            return Err(SchemaError::StaleLensRevision {
+                source: lens.source_hash,
+                target: lens.target_hash,
+                current: existing.revision,
+            });
+        }
+
         // Update context
         self.context.register_lens(lens.clone());
 
         // Update QueryManager
         self.query_manager.register_lens(lens);
 
+        for hash in self.context.ambiguous_lens_paths() {
+            tracing::warn!(
+                schema = %hash.short(),
+                current = %self.context.current_hash.short(),
+                "several equally short lens paths reach the current schema; the first by lens hash order is used"
+            );
+        }
+
         Ok(())
     }
 
@@ -677,6 +801,7 @@ impl SchemaManager {
             hasher.update(b"lens");
             hasher.update(lens.source_hash.as_bytes());
             hasher.update(lens.target_hash.as_bytes());
+            hasher.update(&lens.revision.to_le_bytes());
             let encoded = encode_lens_transform(&lens.forward);
             hash_len_prefixed(&mut hasher, &encoded);
         }
@@ -694,6 +819,11 @@ impl SchemaManager {
             hash_len_prefixed(&mut hasher, &encoded);
         }
 
+        if let Some(release_object_id) = self.current_release {
+            hasher.update(b"release");
+            hasher.update(release_object_id.uuid().as_bytes());
+        }
+
         hasher.finalize().to_hex().to_string()
     }
 
@@ -736,6 +866,18 @@ impl SchemaManager {
         })
     }
 
+    pub fn current_release(&self) -> Option<ReleaseSummary> {
+        let object_id = self.current_release?;
+        let release = self.known_releases.get(&object_id)?;
+        Some(ReleaseSummary {
+            object_id,
+            version: release.version,
+            parent_release_object_id: release.parent_release_object_id,
+            schema_hash: SchemaHash::compute(&release.schema),
+            permissions_version: release.permissions.version,
+        })
+    }
+
     pub fn connection_schema_diagnostics(
         &self,
         client_schema_hash: SchemaHash,
@@ -1122,6 +1264,100 @@ impl SchemaManager {
         Ok(self.persist_lens(storage, lens))
     }
 
+    /// Publish `lens` as a corrected revision of the lens already published
+    /// for its schema pair, recording why it replaces it.
+    ///
+    /// The previous revision stays in the catalogue, but every node that
+    /// syncs both keeps the later one.
+    pub fn publish_lens_revision<H: Storage>(
+        &mut self,
+        storage: &mut H,
+        lens: &Lens,
+        reason: impl Into<String>,
+    ) -> Result<ObjectId, SchemaError> {
+        let previous = self
+            .context
+            .get_lens(&lens.source_hash, &lens.target_hash)
+            .ok_or(SchemaError::LensNotFound {
+                source: lens.source_hash,
+                target: lens.target_hash,
+            })?;
+        let revised = lens.clone().superseding(previous, reason);
+        self.publish_lens(storage, &revised)
+    }
+
+    /// Publish a schema together with the lenses that reach it and its
+    /// permissions as one release.
+    ///
+    /// Every node applies a release as a whole when it arrives, so the schema
+    /// never becomes usable without its lenses and permissions. The parts are
+    /// also catalogued as entries of their own, tagged with the release, for
+    /// nodes that predate releases.
+    pub fn publish_release<H: Storage>(
+        &mut self,
+        storage: &mut H,
+        schema: Schema,
+        lenses: Vec<Lens>,
+        permissions: HashMap<TableName, TablePolicies>,
+        expected_parent_release_object_id: Option<ObjectId>,
+    ) -> Result<ObjectId, SchemaError> {
+        if self.current_release != expected_parent_release_object_id {
+            return Err(SchemaError::StaleReleaseParent {
+                expected: expected_parent_release_object_id,
+                current: self.current_release,
+            });
+        }
+
+        let schema = strip_schema_policies(&schema);
+        if schema.is_empty() {
+            return Err(SchemaError::InvalidRelease(
+                "release schema has no tables".to_string(),
+            ));
+        }
+        let schema_hash = SchemaHash::compute(&schema);
+        for lens in &lenses {
+            self.check_release_lens(lens, schema_hash)?;
+        }
+
+        let release = self.next_release(
+            self.current_release,
+            schema,
+            lenses,
+            schema_hash,
+            permissions,
+        );
+        Ok(self.publish_release_state(storage, release))
+    }
+
+    /// Roll back to the release before the current one by publishing its
+    /// schema, lenses and permissions again as a new release.
+    ///
+    /// The new release takes the place of the one it restores, so rolling
+    /// back again steps further back. Schemas and lenses of the rolled-back
+    /// release stay known, so rows written against them remain readable; lens
+    /// revisions only ever move forward.
+    pub fn rollback_release<H: Storage>(
+        &mut self,
+        storage: &mut H,
+    ) -> Result<ObjectId, SchemaError> {
+        let previous = self
+            .current_release
+            .and_then(|object_id| self.known_releases.get(&object_id))
+            .and_then(|current| current.parent_release_object_id)
+            .and_then(|object_id| self.known_releases.get(&object_id))
+            .cloned()
+            .ok_or(SchemaError::NoPreviousRelease)?;
+
+        let release = self.next_release(
+            previous.parent_release_object_id,
+            previous.schema,
+            previous.lenses,
+            previous.permissions.schema_hash,
+            previous.permissions.permissions,
+        );
+        Ok(self.publish_release_state(storage, release))
+    }
+
     /// Build metadata for a schema catalogue object.
     fn schema_metadata(&self, schema_hash: &SchemaHash) -> HashMap<String, String> {
         let mut metadata = HashMap::new();
@@ -1189,6 +1425,223 @@ impl SchemaManager {
         metadata
     }
 
+    /// Reject lenses a release can't be applied with: drafts, lenses between
+    /// schemas this node doesn't know, and stale revisions.
+    fn check_release_lens(&self, lens: &Lens, schema_hash: SchemaHash) -> Result<(), SchemaError> {
+        if lens.is_draft() {
+            return Err(SchemaError::DraftLensInPath {
+                source: lens.source_hash,
+                target: lens.target_hash,
+            });
+        }
+        for hash in [lens.source_hash, lens.target_hash] {
+            if hash != schema_hash && !self.is_schema_known(&hash) {
+                return Err(SchemaError::InvalidRelease(format!(
+                    "lens {} -> {} references unknown schema {}",
+                    lens.source_hash.short(),
+                    lens.target_hash.short(),
+                    hash.short()
+                )));
+            }
+        }
+        if let Some(existing) = self.context.get_lens(&lens.source_hash, &lens.target_hash)
+            && existing.revision >= lens.revision
+            && (existing.revision != lens.revision
+                || encode_lens_transform(&existing.forward) != encode_lens_transform(&lens.forward))
+        {
+            return Err(SchemaError::StaleLensRevision {
+                source: lens.source_hash,
+                target: lens.target_hash,
+                current: existing.revision,
+            });
+        }
+        Ok(())
+    }
+
+    fn next_release(
+        &self,
+        parent_release_object_id: Option<ObjectId>,
+        schema: Schema,
+        lenses: Vec<Lens>,
+        permissions_schema_hash: SchemaHash,
+        permissions: HashMap<TableName, TablePolicies>,
+    ) -> ReleaseState {
+        let version = self
+            .current_release
+            .and_then(|object_id| self.known_releases.get(&object_id))
+            .map(|current| current.version + 1)
+            .unwrap_or(1);
+        ReleaseState {
+            version,
+            parent_release_object_id,
+            schema,
+            lenses,
+            permissions: PermissionsBundleState {
+                schema_hash: permissions_schema_hash,
+                version: self
+                    .current_permissions_head
+                    .map(|head| head.version + 1)
+                    .unwrap_or(1),
+                parent_bundle_object_id: self
+                    .current_permissions_head
+                    .map(|head| head.bundle_object_id),
+                permissions,
+            },
+        }
+    }
+
+    fn publish_release_state<H: Storage>(
+        &mut self,
+        storage: &mut H,
+        release: ReleaseState,
+    ) -> ObjectId {
+        let content = encode_release_state(&release);
+        let object_id = self.release_object_id(&content);
+        self.apply_release(object_id, release.clone());
+
+        // The release goes first, so nodes that understand releases have it
+        // before the tagged parts.
+        let release_metadata = self.release_metadata();
+        self.persist_catalogue_object_if_changed(storage, object_id, release_metadata, content);
+
+        let tag = |mut metadata: HashMap<String, String>| {
+            metadata.insert(
+                crate::metadata::MetadataKey::Release.to_string(),
+                object_id.to_string(),
+            );
+            metadata
+        };
+        let schema_hash = SchemaHash::compute(&release.schema);
+        self.persist_catalogue_object_if_changed(
+            storage,
+            schema_hash.to_object_id(),
+            tag(self.schema_metadata(&schema_hash)),
+            encode_schema(&release.schema),
+        );
+        for lens in &release.lenses {
+            self.persist_catalogue_object_if_changed(
+                storage,
+                lens.object_id(),
+                tag(self.lens_metadata(lens)),
+                encode_lens_transform(&lens.forward),
+            );
+        }
+        let bundle = &release.permissions;
+        let bundle_object_id = self.permissions_bundle_object_id(bundle);
+        self.persist_catalogue_object_if_changed(
+            storage,
+            bundle_object_id,
+            tag(self.permissions_bundle_metadata()),
+            encode_permissions_bundle(
+                bundle.schema_hash,
+                bundle.version,
+                bundle.parent_bundle_object_id,
+                &bundle.permissions,
+            ),
+        );
+        self.persist_catalogue_object_if_changed(
+            storage,
+            self.permissions_head_object_id(),
+            tag(self.permissions_head_metadata()),
+            encode_permissions_head(
+                bundle.schema_hash,
+                bundle.version,
+                bundle.parent_bundle_object_id,
+                bundle_object_id,
+            ),
+        );
+
+        object_id
+    }
+
+    /// Apply a release as a whole: its schema, then its lenses, then its
+    /// permissions.
+    ///
+    /// Releases older than the current one may still arrive (catalogue replay
+    /// order isn't fixed); they fill in schemas and lenses this node hasn't
+    /// seen, but don't bring back retired schemas or override newer
+    /// permissions.
+    ///
+    /// Two releases published concurrently can share a version; the one with
+    /// the greater object id wins on every node, whatever order they arrive in.
+    fn apply_release(&mut self, object_id: ObjectId, release: ReleaseState) {
+        let newest = self
+            .current_release
+            .and_then(|current| Some((current, self.known_releases.get(&current)?)))
+            .is_none_or(|(current_id, current)| {
+                (current.version, current_id) < (release.version, object_id)
+            });
+
+        let schema_hash = SchemaHash::compute(&release.schema);
+        if newest || !self.retired_schemas.contains(&schema_hash) {
+            self.learn_catalogue_schema(release.schema.clone());
+        }
+
+        for lens in &release.lenses {
+            if self.retired_schemas.contains(&lens.source_hash)
+                || self.retired_schemas.contains(&lens.target_hash)
+            {
+                continue;
+            }
+            if self
+                .context
+                .get_lens(&lens.source_hash, &lens.target_hash)
+                .is_some_and(|existing| existing.revision >= lens.revision)
+            {
+                continue;
+            }
+            self.context.register_lens(lens.clone());
+            self.query_manager.register_lens(lens.clone());
+        }
+        self.activate_pending_and_sync_to_query_manager();
+
+        let bundle_object_id = self.permissions_bundle_object_id(&release.permissions);
+        let head = PermissionsHeadState {
+            schema_hash: release.permissions.schema_hash,
+            version: release.permissions.version,
+            parent_bundle_object_id: release.permissions.parent_bundle_object_id,
+            bundle_object_id,
+        };
+        self.known_permissions_bundles
+            .insert(bundle_object_id, release.permissions.clone());
+        if self.current_permissions_head.is_some_and(|current| {
+            current.version > head.version || (current.version == head.version && !newest)
+        }) {
+            self.try_apply_pending_permissions_head();
+        } else {
+            self.current_permissions_head = Some(head);
+            if self.apply_permissions_head(head) {
+                self.pending_permissions_head = None;
+            } else {
+                self.pending_permissions_head = Some(head);
+            }
+        }
+
+        self.known_releases.insert(object_id, release);
+        if newest {
+            self.current_release = Some(object_id);
+        }
+    }
+
+    fn release_object_id(&self, content: &[u8]) -> ObjectId {
+        let mut identity = format!("jazz-catalogue-release:{}:", self.app_id.uuid()).into_bytes();
+        identity.extend_from_slice(content);
+        ObjectId::from_uuid(Uuid::new_v5(&Uuid::NAMESPACE_DNS, &identity))
+    }
+
+    fn release_metadata(&self) -> HashMap<String, String> {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            crate::metadata::MetadataKey::Type.to_string(),
+            crate::metadata::ObjectType::CatalogueRelease.to_string(),
+        );
+        metadata.insert(
+            crate::metadata::MetadataKey::AppId.to_string(),
+            self.app_id.uuid().to_string(),
+        );
+        metadata
+    }
+
     /// Build metadata for a lens catalogue object.
     fn lens_metadata(&self, lens: &Lens) -> HashMap<String, String> {
         let mut metadata = HashMap::new();
@@ -1208,6 +1661,18 @@ impl SchemaManager {
             crate::metadata::MetadataKey::TargetHash.to_string(),
             lens.target_hash.to_string(),
         );
+        if lens.revision > 0 {
+            metadata.insert(
+                crate::metadata::MetadataKey::LensRevision.to_string(),
+                lens.revision.to_string(),
+            );
+        }
+        if let Some(reason) = &lens.revision_reason {
+            metadata.insert(
+                crate::metadata::MetadataKey::LensRevisionReason.to_string(),
+                reason.clone(),
+            );
+        }
         metadata
     }
 
@@ -1228,6 +1693,19 @@ impl SchemaManager {
             return Ok(()); // Not a catalogue object
         };
 
+        if metadata
+            .get(crate::metadata::MetadataKey::Retired.as_str())
+            .is_some_and(|value| value == "true")
+        {
+            return self.process_catalogue_tombstone(type_str, metadata);
+        }
+
+        // Parts of a release are applied with the release itself; they are
+        // only catalogued on their own for nodes that predate releases.
+        if metadata.contains_key(crate::metadata::MetadataKey::Release.as_str()) {
+            return Ok(());
+        }
+
         match type_str.as_str() {
             t if t == crate::metadata::ObjectType::CatalogueSchema.as_str() => {
                 self.process_catalogue_schema(metadata, content)
@@ -1244,6 +1722,9 @@ impl SchemaManager {
             t if t == crate::metadata::ObjectType::CatalogueLens.as_str() => {
                 self.process_catalogue_lens(metadata, content)
             }
+            t if t == crate::metadata::ObjectType::CatalogueRelease.as_str() => {
+                self.process_catalogue_release(object_id, metadata, content)
+            }
             _ => Ok(()), // Unknown type, ignore
         }
     }
@@ -1274,18 +1755,31 @@ impl SchemaManager {
             return Ok(());
         }
 
+        self.learn_catalogue_schema(schema);
+        Ok(())
+    }
+
+    /// Make a catalogued schema known, and live once a lens path reaches it.
+    fn learn_catalogue_schema(&mut self, schema: Schema) {
         let hash = SchemaHash::compute(&schema);
+        // A live catalogue entry for a retired schema means it was republished.
+        self.retired_schemas.remove(&hash);
 
         // Always add to known_schemas (server or client)
-        // This allows server-mode query execution even without lens paths
-        if !self.known_schemas.contains_key(&hash) {
+        // This allows server-mode query execution even without lens paths.
+        // Unhashed annotations can change under the same hash, so a known
+        // schema takes the latest entry, except this node's own current one.
+        let is_current = self.context.is_initialized() && self.context.current_hash == hash;
+        if !is_current && self.known_schemas.get(&hash) != Some(&schema) {
             Arc::make_mut(&mut self.known_schemas).insert(hash, schema.clone());
             self.known_schemas_dirty = true;
         }
 
+        self.register_client_projection(&schema);
+
         // Skip if already live or is current
         if self.context.is_live(&hash) {
-            return Ok(());
+            return;
         }
 
         // If we have a current schema, also try lens-path activation
@@ -1298,7 +1792,62 @@ impl SchemaManager {
         }
 
         self.try_apply_pending_permissions_head();
+    }
+
+    /// Apply a release received via catalogue sync. A release that doesn't
+    /// decode, or carries a draft lens, is rejected as a whole.
+    fn process_catalogue_release(
+        &mut self,
+        object_id: ObjectId,
+        metadata: &HashMap<String, String>,
+        content: &[u8],
+    ) -> Result<(), SchemaError> {
+        let app_id_str = metadata
+            .get(crate::metadata::MetadataKey::AppId.as_str())
+            .map(|s| s.as_str())
+            .unwrap_or("");
+        if app_id_str != self.app_id.uuid().to_string() {
+            return Ok(());
+        }
+        if self.known_releases.contains_key(&object_id) {
+            return Ok(());
+        }
+
+        let (
+            version,
+            parent_release_object_id,
+            schema,
+            lenses,
+            (schema_hash, bundle_version, parent_bundle_object_id, permissions),
+        ) = decode_release(content)
+            .map_err(|error| SchemaError::InvalidRelease(error.to_string()))?;
+        if schema.is_empty() {
+            return Err(SchemaError::InvalidRelease(
+                "release schema has no tables".to_string(),
+            ));
+        }
+        if let Some(lens) = lenses.iter().find(|lens| lens.is_draft()) {
+            return Err(SchemaError::DraftLensInPath {
+                source: lens.source_hash,
+                target: lens.target_hash,
+            });
+        }
 
+        self.apply_release(
+            object_id,
+            ReleaseState {
+                version,
+                parent_release_object_id,
+                schema,
+                lenses,
+                permissions: PermissionsBundleState {
+                    schema_hash,
+                    version: bundle_version,
+                    parent_bundle_object_id,
+                    permissions,
+                },
+            },
+        );
         Ok(())
     }
 
@@ -1451,11 +2000,31 @@ impl SchemaManager {
 
         let source_hash = parse_schema_hash(source_hex)?;
         let target_hash = parse_schema_hash(target_hex)?;
-
-        // Skip if we already have this lens (handles duplicate syncs)
-        // Since ObjectId is deterministic from hashes and encoding is deterministic,
-        // the same source/target should always produce identical content.
-        if self.context.get_lens(&source_hash, &target_hash).is_some() {
+        if self.retired_schemas.contains(&source_hash)
+            || self.retired_schemas.contains(&target_hash)
+        {
+            return Ok(());
+        }
+        // Lenses published before revisions existed carry no revision key.
+        let revision = metadata
+            .get(crate::metadata::MetadataKey::LensRevision.as_str())
+            .map(|raw| raw.parse::<u32>())
+            .transpose()
+            .map_err(|_| SchemaError::LensNotFound {
+                source: source_hash,
+                target: target_hash,
+            })?
+            .unwrap_or(0);
+
+        // Skip if we already have this revision or a later one (handles
+        // duplicate syncs and superseded revisions arriving late). ObjectId is
+        // deterministic from hashes and revision, and encoding is
+        // deterministic, so one revision always has identical content.
+        if self
+            .context
+            .get_lens(&source_hash, &target_hash)
+            .is_some_and(|existing| existing.revision >= revision)
+        {
             return Ok(());
         }
 
@@ -1466,7 +2035,11 @@ impl SchemaManager {
         })?;
 
         // Reconstruct lens (backward is computed from forward)
-        let lens = Lens::new(source_hash, target_hash, transform);
+        let mut lens = Lens::new(source_hash, target_hash, transform);
+        lens.revision = revision;
+        lens.revision_reason = metadata
+            .get(crate::metadata::MetadataKey::LensRevisionReason.as_str())
+            .cloned();
 
         // Log warning if draft, but still store it
         // Note: Draft lenses can still be registered but won't be used for activation
@@ -1515,47 +2088,493 @@ impl SchemaManager {
             return false;
         };
 
-        let authorization_schema = merge_permissions_into_schema(&schema, &bundle.permissions);
+        let authorization_schema = merge_permissions_into_schema(&schema, &bundle.permissions);
+        self.query_manager
+            .set_authorization_schema(authorization_schema);
+        true
+    }
+
+    fn try_apply_pending_permissions_head(&mut self) {
+        let Some(head) = self.pending_permissions_head else {
+            return;
+        };
+
+        if self.apply_permissions_head(head) {
+            self.pending_permissions_head = None;
+        }
+    }
+
+    /// Try to activate pending schemas that now have lens paths.
+    ///
+    /// Called after registering new lenses. Returns hashes of newly activated schemas.
+    pub fn try_activate_pending_schemas(&mut self) -> Vec<SchemaHash> {
+        self.context.try_activate_pending()
+    }
+
+    /// Activate pending schemas and sync them to QueryManager.
+    ///
+    /// This is the incremental replacement for sync_context().
+    fn activate_pending_and_sync_to_query_manager(&mut self) {
+        let activated = self.context.try_activate_pending();
+        if activated.is_empty() {
+            return;
+        }
+
+        // For each newly activated schema, add it to QueryManager
+        for hash in &activated {
+            if let Some(schema) = self.context.live_schemas.get(hash).cloned() {
+                self.query_manager.add_live_schema(schema);
+            }
+        }
+
+        // Pending row updates will be retried in the next process() call,
+        // which has access to Storage needed for index updates.
+    }
+
+    // =========================================================================
+    // Schema GC
+    // =========================================================================
+
+    /// Retire schema versions nobody should need any more.
+    ///
+    /// Keeps `target_hash`, every schema published at or after
+    /// `retain_published_since` (or whose publish time this node never saw),
+    /// every schema a connected client declared, every schema a client
+    /// declared or wrote rows on its branches with at or after that cutoff,
+    /// and every schema on a kept schema's lens path to the target. For each
+    /// other schema, rows whose latest visible version still lives on its
+    /// branch are rewritten through lenses onto the matching target branch.
+    /// The schema and the lenses touching it are then tombstoned in the
+    /// catalogue, so the retirement reaches every node through catalogue sync.
+    ///
+    /// A schema whose rows can't be rewritten (no lens path to the target, a
+    /// value the lenses can't convert, or a failed write) is retained.
+    /// Tombstones keep the schema content, so older history batches stay
+    /// decodable.
+    pub fn collect_schema_garbage<H: Storage>(
+        &mut self,
+        storage: &mut H,
+        target_hash: SchemaHash,
+        retain_published_since: u64,
+    ) -> Result<SchemaGcReport, QueryError> {
+        let target_schema = self
+            .schema_for_gc(&target_hash)
+            .ok_or(QueryError::UnknownSchema(target_hash))?;
+
+        let mut known: HashMap<SchemaHash, Schema> = self
+            .known_schemas
+            .iter()
+            .map(|(hash, schema)| (*hash, schema.clone()))
+            .collect();
+        for (hash, schema) in self
+            .context
+            .live_schemas
+            .iter()
+            .chain(self.context.pending_schemas.iter())
+        {
+            known.entry(*hash).or_insert_with(|| schema.clone());
+        }
+        if self.context.is_initialized() {
+            known
+                .entry(self.context.current_hash)
+                .or_insert_with(|| strip_schema_policies(&self.context.current_schema));
+        }
+
+        let mut gc_context =
+            SchemaContext::new(target_schema, &self.context.env, &self.context.user_branch);
+        for lens in self.context.lenses.values() {
+            gc_context.register_lens(lens.clone());
+        }
+        for (hash, schema) in &known {
+            if *hash != target_hash {
+                gc_context.add_pending_schema_with_hash(*hash, schema.clone());
+            }
+        }
+        gc_context.try_activate_pending();
+
+        let mut retained = HashSet::from([target_hash]);
+        if self.context.is_initialized() {
+            retained.insert(self.context.current_hash);
+        }
+        for hash in known.keys() {
+            let published_recently = self
+                .schema_published_at(hash)
+                .is_none_or(|published_at| published_at >= retain_published_since);
+            let client_seen_recently = self.connected_client_schemas.contains_key(hash)
+                || self
+                    .client_schemas_seen_at
+                    .get(hash)
+                    .is_some_and(|seen_at| *seen_at >= retain_published_since);
+            if published_recently || client_seen_recently {
+                retained.insert(*hash);
+            }
+        }
+        let unretained_by_short: HashMap<String, SchemaHash> = known
+            .keys()
+            .filter(|hash| !retained.contains(hash))
+            .map(|hash| (hash.short(), *hash))
+            .collect();
+        retained.extend(Self::schemas_written_since(
+            storage,
+            &unretained_by_short,
+            retain_published_since,
+        )?);
+        for hash in retained.clone() {
+            if let Ok(path) = gc_context.lens_path(&hash) {
+                for (lens, _) in path {
+                    retained.insert(lens.source_hash);
+                    retained.insert(lens.target_hash);
+                }
+            }
+        }
+
+        let candidates_by_short: HashMap<String, SchemaHash> = known
+            .keys()
+            .filter(|hash| !retained.contains(hash))
+            .map(|hash| (hash.short(), *hash))
+            .collect();
+        let mut stuck = HashSet::new();
+        let mut rewritten_rows = 0;
+
+        if !candidates_by_short.is_empty() {
+            let locators = storage
+                .scan_row_locators()
+                .map_err(|err| QueryError::EncodingError(format!("{err:?}")))?;
+            for (row_id, locator) in locators {
+                let history = storage
+                    .scan_history_row_batches(locator.table.as_str(), row_id)
+                    .map_err(|err| QueryError::EncodingError(format!("{err:?}")))?;
+
+                // Branch families (env, user branch) with history on a candidate.
+                let mut families = HashSet::new();
+                for batch in &history {
+                    let Some(branch) =
+                        ComposedBranchName::parse(&BranchName::new(batch.branch.as_str()))
+                    else {
+                        continue;
+                    };
+                    let Some(candidate) = candidates_by_short.get(&branch.schema_hash.short())
+                    else {
+                        continue;
+                    };
+                    if !gc_context.is_live(candidate) {
+                        stuck.insert(*candidate);
+                        continue;
+                    }
+                    families.insert((branch.env, branch.user_branch));
+                }
+
+                for (env, user_branch) in families {
+                    let mut family_context = gc_context.clone();
+                    family_context.env = env;
+                    family_context.user_branch = user_branch;
+                    match self.rewrite_row_onto_target(storage, row_id, &family_context) {
+                        Ok(true) => rewritten_rows += 1,
+                        Ok(false) => {}
+                        Err((source_branch, err)) => {
+                            tracing::warn!(
+                                %row_id,
+                                branch = %source_branch,
+                                error = ?err,
+                                "schema GC could not rewrite row; retaining its schema"
+                            );
+                            if let Some(branch) =
+                                ComposedBranchName::parse(&BranchName::new(&source_branch))
+                                && let Some(candidate) =
+                                    candidates_by_short.get(&branch.schema_hash.short())
+                            {
+                                stuck.insert(*candidate);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut report = SchemaGcReport {
+            rewritten_rows,
+            ..SchemaGcReport::default()
+        };
+        for hash in known.keys() {
+            if retained.contains(hash) || stuck.contains(hash) {
+                report.retained.push(*hash);
+                continue;
+            }
+            report
+                .retired_lenses
+                .extend(self.tombstone_schema(storage, *hash, &known[hash]));
+            report.retired.push(*hash);
+        }
+
+        report
+            .retained
+            .sort_by(|left, right| left.as_bytes().cmp(right.as_bytes()));
+        report
+            .retired
+            .sort_by(|left, right| left.as_bytes().cmp(right.as_bytes()));
+        report.retired_lenses.sort_by(|left, right| {
+            (left.0.as_bytes(), left.1.as_bytes()).cmp(&(right.0.as_bytes(), right.1.as_bytes()))
+        });
+        Ok(report)
+    }
+
+    /// Schemas among `candidates_by_short` whose branches hold a row version
+    /// written at or after `since`, i.e. ones clients still write with.
+    fn schemas_written_since<H: Storage>(
+        storage: &H,
+        candidates_by_short: &HashMap<String, SchemaHash>,
+        since: u64,
+    ) -> Result<HashSet<SchemaHash>, QueryError> {
+        let mut written = HashSet::new();
+        if candidates_by_short.is_empty() {
+            return Ok(written);
+        }
+        let locators = storage
+            .scan_row_locators()
+            .map_err(|err| QueryError::EncodingError(format!("{err:?}")))?;
+        for (row_id, locator) in locators {
+            let history = storage
+                .scan_history_row_batches(locator.table.as_str(), row_id)
+                .map_err(|err| QueryError::EncodingError(format!("{err:?}")))?;
+            for batch in history.iter().filter(|batch| batch.updated_at >= since) {
+                if let Some(branch) =
+                    ComposedBranchName::parse(&BranchName::new(batch.branch.as_str()))
+                    && let Some(candidate) = candidates_by_short.get(&branch.schema_hash.short())
+                {
+                    written.insert(*candidate);
+                }
+            }
+        }
+        Ok(written)
+    }
+
+    /// Record a client connection declaring `hash`; schema GC retains the
+    /// schema while the connection is open.
+    pub fn client_schema_connected(&mut self, hash: SchemaHash) {
+        *self.connected_client_schemas.entry(hash).or_default() += 1;
+        let seen_at = self.query_manager.sync_manager_mut().reserve_timestamp();
+        self.client_schemas_seen_at.insert(hash, seen_at);
+    }
+
+    /// Record that a client connection declaring `hash` closed. Schema GC
+    /// keeps the schema while it was seen at or after its retention cutoff.
+    pub fn client_schema_disconnected(&mut self, hash: SchemaHash) {
+        if let Some(count) = self.connected_client_schemas.get_mut(&hash) {
+            *count -= 1;
+            if *count == 0 {
+                self.connected_client_schemas.remove(&hash);
+            }
+        }
+        let seen_at = self.query_manager.sync_manager_mut().reserve_timestamp();
+        self.client_schemas_seen_at.insert(hash, seen_at);
+    }
+
+    /// Whether schema GC has retired `hash`.
+    pub fn is_schema_retired(&self, hash: &SchemaHash) -> bool {
+        self.retired_schemas.contains(hash)
+    }
+
+    fn schema_for_gc(&self, hash: &SchemaHash) -> Option<Schema> {
+        if self.context.is_initialized() && self.context.current_hash == *hash {
+            return Some(self.context.current_schema.clone());
+        }
+        self.context
+            .live_schemas
+            .get(hash)
+            .or_else(|| self.context.pending_schemas.get(hash))
+            .or_else(|| self.known_schemas.get(hash))
+            .cloned()
+    }
+
+    /// Copy a row's latest visible version onto the target branch of
+    /// `family_context` when it still lives on an older schema's branch.
+    ///
+    /// Returns whether a row was written. Errors carry the source branch.
+    fn rewrite_row_onto_target<H: Storage>(
+        &mut self,
+        storage: &mut H,
+        row_id: ObjectId,
+        family_context: &SchemaContext,
+    ) -> Result<bool, (String, QueryError)> {
+        let target_hash = family_context.current_hash;
+        let branches = family_context
+            .all_branch_names()
+            .into_iter()
+            .map(|branch_name| branch_name.as_str().to_string())
+            .collect::<Vec<_>>();
+        let target_branch = ComposedBranchName::new(
+            &family_context.env,
+            target_hash,
+            &family_context.user_branch,
+        )
+        .to_branch_name()
+        .as_str()
+        .to_string();
+        let Some((table, source_branch, data, _batch_id, provenance)) = self
+            .query_manager
+            .load_row_for_schema_update_in_context(storage, row_id, &branches, family_context)
+        else {
+            // A visible version the lenses couldn't carry onto the target
+            // still needs its schema.
+            return match self.query_manager.visible_row_branch_in_context(
+                storage,
+                row_id,
+                &branches,
+                family_context,
+            ) {
+                Some(source_branch) if source_branch != target_branch => Err((
+                    source_branch,
+                    QueryError::EncodingError(format!(
+                        "row {row_id} could not be transformed onto schema {}",
+                        target_hash.short()
+                    )),
+                )),
+                _ => Ok(false),
+            };
+        };
+
+        if source_branch == target_branch
+            || self
+                .query_manager
+                .row_is_deleted_on_branch(storage, &table, &source_branch, row_id)
+        {
+            return Ok(false);
+        }
+
+        let table_name = TableName::new(&table);
+        let Some(table_schema) = family_context.current_schema.get(&table_name) else {
+            return Err((source_branch, QueryError::TableNotFound(table_name)));
+        };
+        let values = match decode_row(&table_schema.columns, &data) {
+            Ok(values) => values,
+            Err(err) => {
+                return Err((source_branch, QueryError::EncodingError(format!("{err:?}"))));
+            }
+        };
+
         self.query_manager
-            .set_authorization_schema(authorization_schema);
-        true
+            .write_existing_row_on_branch_with_schema_and_write_context(
+                storage,
+                RowBranchWrite {
+                    table: &table,
+                    branch: &target_branch,
+                    id: row_id,
+                    values: &values,
+                    old_data_for_policy: &data,
+                    old_provenance_for_policy: &provenance,
+                },
+                &family_context.current_schema,
+                None,
+                false,
+            )
+            .map(|_| true)
+            .map_err(|err| (source_branch, err))
     }
 
-    fn try_apply_pending_permissions_head(&mut self) {
-        let Some(head) = self.pending_permissions_head else {
-            return;
-        };
+    /// Publish catalogue tombstones for `hash` and every lens touching it,
+    /// then forget them locally. Returns the retired lens pairs.
+    fn tombstone_schema<H: Storage>(
+        &mut self,
+        storage: &mut H,
+        hash: SchemaHash,
+        schema: &Schema,
+    ) -> Vec<(SchemaHash, SchemaHash)> {
+        let retired_key = crate::metadata::MetadataKey::Retired.to_string();
 
-        if self.apply_permissions_head(head) {
-            self.pending_permissions_head = None;
+        let mut metadata = self.schema_metadata(&hash);
+        metadata.insert(retired_key.clone(), "true".to_string());
+        self.query_manager
+            .sync_manager_mut()
+            .upsert_catalogue_entry(
+                storage,
+                CatalogueEntry {
+                    object_id: hash.to_object_id(),
+                    metadata,
+                    content: encode_schema(schema),
+                },
+            );
+        self.catalogue_publish_timestamps
+            .remove(&hash.to_object_id());
+
+        let lenses: Vec<Lens> = self
+            .context
+            .lenses
+            .values()
+            .filter(|lens| lens.source_hash == hash || lens.target_hash == hash)
+            .cloned()
+            .collect();
+        for lens in &lenses {
+            let mut metadata = self.lens_metadata(lens);
+            metadata.insert(retired_key.clone(), "true".to_string());
+            self.query_manager
+                .sync_manager_mut()
+                .upsert_catalogue_entry(
+                    storage,
+                    CatalogueEntry {
+                        object_id: lens.object_id(),
+                        metadata,
+                        content: Vec::new(),
+                    },
+                );
+            self.catalogue_publish_timestamps.remove(&lens.object_id());
         }
-    }
 
-    /// Try to activate pending schemas that now have lens paths.
-    ///
-    /// Called after registering new lenses. Returns hashes of newly activated schemas.
-    pub fn try_activate_pending_schemas(&mut self) -> Vec<SchemaHash> {
-        self.context.try_activate_pending()
+        self.retire_schema_locally(hash);
+        lenses
+            .iter()
+            .map(|lens| (lens.source_hash, lens.target_hash))
+            .collect()
     }
 
-    /// Activate pending schemas and sync them to QueryManager.
-    ///
-    /// This is the incremental replacement for sync_context().
-    fn activate_pending_and_sync_to_query_manager(&mut self) {
-        let activated = self.context.try_activate_pending();
-        if activated.is_empty() {
+    fn retire_schema_locally(&mut self, hash: SchemaHash) {
+        if self.context.is_initialized() && self.context.current_hash == hash {
             return;
         }
+        self.retired_schemas.insert(hash);
+        self.context.retire_schema(&hash);
+        self.query_manager.retire_schema(hash);
+        if self.known_schemas.contains_key(&hash) {
+            Arc::make_mut(&mut self.known_schemas).remove(&hash);
+            self.known_schemas_dirty = true;
+        }
+        self.insert_alignment_cache
+            .retain(|(schema_hash, _), _| *schema_hash != hash);
+    }
 
-        // For each newly activated schema, add it to QueryManager
-        for hash in &activated {
-            if let Some(schema) = self.context.live_schemas.get(hash).cloned() {
-                self.query_manager.add_live_schema(schema);
-            }
+    /// Apply a schema or lens tombstone received via catalogue sync.
+    fn process_catalogue_tombstone(
+        &mut self,
+        type_str: &str,
+        metadata: &HashMap<String, String>,
+    ) -> Result<(), SchemaError> {
+        let app_id_str = metadata
+            .get(crate::metadata::MetadataKey::AppId.as_str())
+            .map(|s| s.as_str())
+            .unwrap_or("");
+        if app_id_str != self.app_id.uuid().to_string() {
+            return Ok(());
         }
 
-        // Pending row updates will be retried in the next process() call,
-        // which has access to Storage needed for index updates.
+        if type_str == crate::metadata::ObjectType::CatalogueSchema.as_str() {
+            let hash = metadata
+                .get(crate::metadata::MetadataKey::SchemaHash.as_str())
+                .ok_or_else(|| SchemaError::SchemaNotFound(SchemaHash::from_bytes([0; 32])))
+                .and_then(|value| parse_schema_hash(value))?;
+            self.retire_schema_locally(hash);
+        } else if type_str == crate::metadata::ObjectType::CatalogueLens.as_str() {
+            let (Some(source_hex), Some(target_hex)) = (
+                metadata.get(crate::metadata::MetadataKey::SourceHash.as_str()),
+                metadata.get(crate::metadata::MetadataKey::TargetHash.as_str()),
+            ) else {
+                return Ok(());
+            };
+            let source_hash = parse_schema_hash(source_hex)?;
+            let target_hash = parse_schema_hash(target_hex)?;
+            self.context.remove_lens(&source_hash, &target_hash);
+            self.query_manager.remove_lens(&source_hash, &target_hash);
+        }
+
+        Ok(())
     }
 
     // =========================================================================
@@ -1987,6 +3006,22 @@ fn merge_permissions_into_schema(
         .collect()
 }
 
+fn encode_release_state(release: &ReleaseState) -> Vec<u8> {
+    let permissions = &release.permissions;
+    encode_release(
+        release.version,
+        release.parent_release_object_id,
+        &release.schema,
+        &release.lenses,
+        &encode_permissions_bundle(
+            permissions.schema_hash,
+            permissions.version,
+            permissions.parent_bundle_object_id,
+            &permissions.permissions,
+        ),
+    )
+}
+
 fn strip_schema_policies(schema: &Schema) -> Schema {
     schema
         .iter()
@@ -2074,6 +3109,36 @@ mod tests {
         assert_eq!(manager.app_id(), test_app_id());
     }
 
+    #[test]
+    fn schema_manager_registers_client_projection_of_internal_items() {
+        let schema = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("name", ColumnType::Text)
+                    .internal_column("risk_score", ColumnType::Integer),
+            )
+            .table(
+                TableSchema::builder("audit_log")
+                    .column("entry", ColumnType::Text)
+                    .internal(),
+            )
+            .build();
+        let manager =
+            SchemaManager::new(SyncManager::new(), schema, test_app_id(), "dev", "main").unwrap();
+
+        let projected = SchemaBuilder::new()
+            .table(TableSchema::builder("users").column("name", ColumnType::Text))
+            .build();
+        let projected_hash = SchemaHash::compute(&projected);
+
+        assert_eq!(manager.get_known_schema(&projected_hash), Some(&projected));
+        assert!(manager.is_live(&projected_hash));
+        let lens = manager
+            .get_lens(&manager.current_hash(), &projected_hash)
+            .expect("lens from the full schema to its projection");
+        assert!(!lens.is_draft());
+    }
+
     #[test]
     fn schema_manager_new_preserves_declared_table_column_order() {
         let schema = SchemaBuilder::new()
@@ -2401,6 +3466,61 @@ mod tests {
         assert!(known.contains(&real_hash));
     }
 
+    #[test]
+    fn process_catalogue_schema_takes_annotation_updates_under_the_same_hash() {
+        let current = make_schema_v1();
+        let mut manager = SchemaManager::new(
+            SyncManager::new(),
+            current.clone(),
+            test_app_id(),
+            "dev",
+            "main",
+        )
+        .unwrap();
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            crate::metadata::MetadataKey::Type.to_string(),
+            crate::metadata::ObjectType::CatalogueSchema.to_string(),
+        );
+        metadata.insert(
+            crate::metadata::MetadataKey::AppId.to_string(),
+            test_app_id().uuid().to_string(),
+        );
+        let mut process = |manager: &mut SchemaManager, schema: &Schema| {
+            let hash = SchemaHash::compute(schema);
+            metadata.insert(
+                crate::metadata::MetadataKey::SchemaHash.to_string(),
+                hash.to_string(),
+            );
+            let content = crate::schema_manager::encoding::encode_schema(schema);
+            manager
+                .process_catalogue_update(hash.to_object_id(), &metadata, &content)
+                .unwrap();
+            hash
+        };
+
+        let v2 = make_schema_v2();
+        let v2_hash = process(&mut manager, &v2);
+        let mut annotated_v2 = v2.clone();
+        annotated_v2
+            .get_mut(&TableName::new("users"))
+            .unwrap()
+            .annotations
+            .insert("label", "People");
+        assert_eq!(process(&mut manager, &annotated_v2), v2_hash);
+        assert_eq!(manager.get_known_schema(&v2_hash), Some(&annotated_v2));
+
+        // This node's own schema is what it declared, whatever others publish.
+        let mut annotated_current = current.clone();
+        annotated_current
+            .get_mut(&TableName::new("users"))
+            .unwrap()
+            .annotations
+            .insert("label", "People");
+        let current_hash = process(&mut manager, &annotated_current);
+        assert_eq!(manager.get_known_schema(&current_hash), Some(&current));
+    }
+
     #[test]
     fn connection_schema_diagnostics_treat_unknown_client_schema_as_disconnected() {
         let schema = make_schema_v2();
@@ -3246,4 +4366,331 @@ mod tests {
             "expected unknown column error, got {err:?}"
         );
     }
+
+    fn select_policy(expr: PolicyExpr) -> HashMap<TableName, TablePolicies> {
+        HashMap::from([(
+            TableName::new("users"),
+            TablePolicies::new().with_select(expr),
+        )])
+    }
+
+    #[test]
+    fn release_parts_only_take_effect_with_the_release() {
+        let v1 = make_schema_v1();
+        let v2 = make_schema_v2();
+        let v2_hash = SchemaHash::compute(&v2);
+        let mut storage = crate::storage::MemoryStorage::new();
+        let mut publisher =
+            SchemaManager::new(SyncManager::new(), v1.clone(), test_app_id(), "dev", "main")
+                .unwrap();
+        let permissions = select_policy(PolicyExpr::True);
+        let release_id = publisher
+            .publish_release(
+                &mut storage,
+                v2.clone(),
+                vec![generate_lens(&v1, &v2)],
+                permissions.clone(),
+                None,
+            )
+            .unwrap();
+
+        let (releases, parts): (Vec<_>, Vec<_>) = storage
+            .scan_catalogue_entries()
+            .unwrap()
+            .into_iter()
+            .partition(|entry| {
+                entry.object_type() == Some(crate::metadata::ObjectType::CatalogueRelease.as_str())
+            });
+        assert_eq!(releases.len(), 1);
+        assert_eq!(parts.len(), 4, "schema, lens, permissions bundle and head");
+
+        let mut receiver =
+            SchemaManager::new(SyncManager::new(), v1, test_app_id(), "dev", "main").unwrap();
+        for entry in &parts {
+            receiver
+                .process_catalogue_update(entry.object_id, &entry.metadata, &entry.content)
+                .unwrap();
+        }
+        assert!(!receiver.is_schema_known(&v2_hash));
+        assert!(receiver.current_permissions().is_none());
+
+        receiver
+            .process_catalogue_update(
+                releases[0].object_id,
+                &releases[0].metadata,
+                &releases[0].content,
+            )
+            .unwrap();
+        assert!(receiver.context().is_live(&v2_hash));
+        assert_eq!(
+            receiver
+                .current_permissions()
+                .map(|current| current.permissions),
+            Some(permissions)
+        );
+        assert_eq!(
+            receiver.current_release().map(|release| release.object_id),
+            Some(release_id)
+        );
+    }
+
+    #[test]
+    fn release_with_a_draft_lens_is_rejected_as_a_whole() {
+        use crate::schema_manager::lens::{LensOp, LensTransform};
+
+        let v1 = make_schema_v1();
+        let v2 = make_schema_v2();
+        let v2_hash = SchemaHash::compute(&v2);
+        let mut draft = LensTransform::new();
+        draft.push(
+            LensOp::AddColumn {
+                table: "users".to_string(),
+                column: "email".to_string(),
+                column_type: ColumnType::Text,
+                default: Value::Null,
+            },
+            true,
+        );
+        let lens = Lens::new(SchemaHash::compute(&v1), v2_hash, draft);
+        let content = encode_release(
+            1,
+            None,
+            &v2,
+            &[lens],
+            &encode_permissions_bundle(v2_hash, 1, None, &select_policy(PolicyExpr::True)),
+        );
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            crate::metadata::MetadataKey::Type.to_string(),
+            crate::metadata::ObjectType::CatalogueRelease.to_string(),
+        );
+        metadata.insert(
+            crate::metadata::MetadataKey::AppId.to_string(),
+            test_app_id().uuid().to_string(),
+        );
+
+        let mut receiver =
+            SchemaManager::new(SyncManager::new(), v1, test_app_id(), "dev", "main").unwrap();
+        let result = receiver.process_catalogue_update(ObjectId::new(), &metadata, &content);
+
+        assert!(matches!(result, Err(SchemaError::DraftLensInPath { .. })));
+        assert!(!receiver.is_schema_known(&v2_hash));
+        assert!(receiver.current_permissions().is_none());
+        assert!(receiver.current_release().is_none());
+    }
+
+    #[test]
+    fn rollback_release_republishes_the_previous_release() {
+        let v1 = make_schema_v1();
+        let v2 = make_schema_v2();
+        let mut storage = crate::storage::MemoryStorage::new();
+        let mut manager =
+            SchemaManager::new(SyncManager::new(), v1.clone(), test_app_id(), "dev", "main")
+                .unwrap();
+        let open = select_policy(PolicyExpr::True);
+        let first = manager
+            .publish_release(&mut storage, v1.clone(), Vec::new(), open.clone(), None)
+            .unwrap();
+        manager
+            .publish_release(
+                &mut storage,
+                v2.clone(),
+                vec![generate_lens(&v1, &v2)],
+                select_policy(PolicyExpr::False),
+                Some(first),
+            )
+            .unwrap();
+        assert!(matches!(
+            manager.publish_release(
+                &mut storage,
+                v2.clone(),
+                Vec::new(),
+                open.clone(),
+                Some(first)
+            ),
+            Err(SchemaError::StaleReleaseParent { .. })
+        ));
+
+        let rollback = manager.rollback_release(&mut storage).unwrap();
+
+        let current = manager.current_release().unwrap();
+        assert_eq!(current.object_id, rollback);
+        assert_eq!(current.version, 3);
+        assert_eq!(current.parent_release_object_id, None);
+        assert_eq!(current.schema_hash, SchemaHash::compute(&v1));
+        assert_eq!(current.permissions_version, 3);
+        assert_eq!(
+            manager
+                .current_permissions()
+                .map(|current| current.permissions),
+            Some(open)
+        );
+        assert!(manager.is_schema_known(&SchemaHash::compute(&v2)));
+        assert!(matches!(
+            manager.rollback_release(&mut storage),
+            Err(SchemaError::NoPreviousRelease)
+        ));
+    }
+
+    fn release_entries(storage: &crate::storage::MemoryStorage) -> Vec<CatalogueEntry> {
+        storage
+            .scan_catalogue_entries()
+            .unwrap()
+            .into_iter()
+            .filter(|entry| {
+                entry.object_type() == Some(crate::metadata::ObjectType::CatalogueRelease.as_str())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn concurrent_releases_of_the_same_version_resolve_alike_in_any_order() {
+        let v1 = make_schema_v1();
+        let v2 = make_schema_v2();
+        let open = select_policy(PolicyExpr::True);
+        let closed = select_policy(PolicyExpr::False);
+
+        // Two admins publish version 1 without seeing each other's release.
+        let publish = |schema: Schema, lenses, permissions: &HashMap<TableName, TablePolicies>| {
+            let mut storage = crate::storage::MemoryStorage::new();
+            let mut publisher =
+                SchemaManager::new(SyncManager::new(), v1.clone(), test_app_id(), "dev", "main")
+                    .unwrap();
+            publisher
+                .publish_release(&mut storage, schema, lenses, permissions.clone(), None)
+                .unwrap();
+            release_entries(&storage).remove(0)
+        };
+        let first = publish(v1.clone(), Vec::new(), &open);
+        let second = publish(v2.clone(), vec![generate_lens(&v1, &v2)], &closed);
+        let (winner, winner_permissions) = if first.object_id > second.object_id {
+            (&first, &open)
+        } else {
+            (&second, &closed)
+        };
+
+        for order in [[&first, &second], [&second, &first]] {
+            let mut receiver =
+                SchemaManager::new(SyncManager::new(), v1.clone(), test_app_id(), "dev", "main")
+                    .unwrap();
+            for entry in order {
+                receiver
+                    .process_catalogue_update(entry.object_id, &entry.metadata, &entry.content)
+                    .unwrap();
+            }
+            let current = receiver.current_release().unwrap();
+            assert_eq!(current.object_id, winner.object_id);
+            assert_eq!(current.version, 1);
+            assert_eq!(
+                receiver
+                    .current_permissions()
+                    .map(|current| current.permissions),
+                Some(winner_permissions.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn releases_replayed_out_of_order_settle_on_the_newest() {
+        let v1 = make_schema_v1();
+        let v2 = make_schema_v2();
+        let v2_hash = SchemaHash::compute(&v2);
+        let mut storage = crate::storage::MemoryStorage::new();
+        let mut publisher =
+            SchemaManager::new(SyncManager::new(), v1.clone(), test_app_id(), "dev", "main")
+                .unwrap();
+        let open = select_policy(PolicyExpr::True);
+        let first = publisher
+            .publish_release(&mut storage, v1.clone(), Vec::new(), open.clone(), None)
+            .unwrap();
+        let second = publisher
+            .publish_release(
+                &mut storage,
+                v2.clone(),
+                vec![generate_lens(&v1, &v2)],
+                select_policy(PolicyExpr::False),
+                Some(first),
+            )
+            .unwrap();
+        let rollback = publisher.rollback_release(&mut storage).unwrap();
+
+        let entries = release_entries(&storage);
+        assert_eq!(entries.len(), 3);
+        let entry_for = |object_id| {
+            entries
+                .iter()
+                .find(|entry| entry.object_id == object_id)
+                .unwrap()
+        };
+        // Newest first, then the oldest, then the middle one.
+        let replay = [entry_for(rollback), entry_for(first), entry_for(second)];
+
+        let mut receiver =
+            SchemaManager::new(SyncManager::new(), v1, test_app_id(), "dev", "main").unwrap();
+        for entry in replay {
+            receiver
+                .process_catalogue_update(entry.object_id, &entry.metadata, &entry.content)
+                .unwrap();
+        }
+
+        let current = receiver.current_release().unwrap();
+        assert_eq!(current.object_id, rollback);
+        assert_eq!(current.version, 3);
+        assert_eq!(
+            receiver
+                .current_permissions()
+                .map(|current| current.permissions),
+            Some(open)
+        );
+        // The rolled-back release still taught the receiver its schema.
+        assert!(receiver.is_schema_known(&v2_hash));
+    }
+
+    #[test]
+    fn release_arrives_through_catalogue_sync() {
+        let v1 = make_schema_v1();
+        let v2 = make_schema_v2();
+        let v2_hash = SchemaHash::compute(&v2);
+        let mut publisher_storage = crate::storage::MemoryStorage::new();
+        let mut publisher =
+            SchemaManager::new(SyncManager::new(), v1.clone(), test_app_id(), "dev", "main")
+                .unwrap();
+        let permissions = select_policy(PolicyExpr::True);
+        let release_id = publisher
+            .publish_release(
+                &mut publisher_storage,
+                v2.clone(),
+                vec![generate_lens(&v1, &v2)],
+                permissions.clone(),
+                None,
+            )
+            .unwrap();
+
+        let mut storage = crate::storage::MemoryStorage::new();
+        let mut receiver =
+            SchemaManager::new(SyncManager::new(), v1, test_app_id(), "dev", "main").unwrap();
+        for entry in publisher_storage.scan_catalogue_entries().unwrap() {
+            receiver.query_manager_mut().sync_manager_mut().push_inbox(
+                crate::sync_manager::InboxEntry {
+                    source: crate::sync_manager::Source::Server(
+                        crate::sync_manager::ServerId::new(),
+                    ),
+                    payload: crate::sync_manager::SyncPayload::CatalogueEntryUpdated { entry },
+                },
+            );
+        }
+        receiver.process(&mut storage);
+
+        assert!(receiver.context().is_live(&v2_hash));
+        assert_eq!(
+            receiver.current_release().map(|release| release.object_id),
+            Some(release_id)
+        );
+        assert_eq!(
+            receiver
+                .current_permissions()
+                .map(|current| current.permissions),
+            Some(permissions)
+        );
+    }
 }
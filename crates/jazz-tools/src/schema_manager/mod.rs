@@ -45,26 +45,35 @@ pub mod diff;
 pub mod encoding;
 pub mod lens;
 pub mod manager;
+pub mod rehearsal;
 pub mod rehydrate;
+pub mod reviewed_migration;
 pub mod transformer;
 pub mod types;
+pub mod visibility;
 
 #[cfg(test)]
 mod integration_tests;
 
 // Re-exports
-pub use auto_lens::generate_lens;
+pub use auto_lens::{generate_lens, generate_lens_confirming};
 pub use context::{QuerySchemaContext, SchemaContext, SchemaError};
-pub use diff::{Ambiguity, DiffResult, diff_schemas};
+pub use diff::{Ambiguity, DiffResult, diff_schemas, diff_schemas_confirming};
 pub use encoding::{
     CatalogueEncodingError, decode_lens_transform, decode_permissions, decode_schema,
     encode_lens_transform, encode_permissions, encode_schema,
 };
-pub use lens::{Direction, Lens, LensOp, LensTransform};
-pub use manager::SchemaManager;
+pub use lens::{ConversionError, Direction, Lens, LensOp, LensTransform, TypeConverter};
+pub use manager::{SchemaGcReport, SchemaManager};
+pub use rehearsal::{
+    MigrationRehearsal, TableRehearsal, rehearse_migration, rehearse_reviewed_migration,
+};
 pub use rehydrate::rehydrate_schema_manager_from_catalogue;
+pub use reviewed_migration::{ReviewedLensOp, ReviewedMigration, ReviewedTableLens};
 pub use transformer::{
-    LensTransformer, TransformError, TransformResult, origin_schema_hash_from_metadata,
-    resolve_current_table_name, translate_column_for_index, translate_table_name_to_schema,
+    LensTransformer, RowRehearsal, TransformError, TransformResult,
+    origin_schema_hash_from_metadata, resolve_current_table_name, translate_column_for_index,
+    translate_table_name_to_schema,
 };
 pub use types::AppId;
+pub use visibility::{RowProjection, SchemaProjection, project_lens, project_schema};
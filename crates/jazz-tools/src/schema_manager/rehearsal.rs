@@ -0,0 +1,341 @@
+//! Migration rehearsal - dry-runs a candidate schema and lens against stored rows.
+//!
+//! Used by `jazz-tools migrate rehearse` to show what a migration would do to
+//! production data before it is published. Nothing is written to the store.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::object::BranchName;
+use crate::query_manager::types::{ComposedBranchName, Schema, SchemaHash};
+use crate::row_histories::StoredRowBatch;
+use crate::storage::Storage;
+use crate::sync_manager::SyncManager;
+
+use super::encoding::decode_schema;
+use super::lens::{Lens, LensOp};
+use super::rehydrate::rehydrate_schema_manager_from_catalogue;
+use super::reviewed_migration::ReviewedMigration;
+use super::transformer::{LensTransformer, TransformError, resolve_current_table_name};
+use super::{AppId, SchemaManager};
+
+/// Per-table outcome of a migration rehearsal.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableRehearsal {
+    /// Table name in the candidate schema (or the stored name when the
+    /// candidate drops the table).
+    pub table: String,
+    /// Latest visible row versions examined, one per row and branch.
+    pub rows: usize,
+    /// Rows that would be lensed from an older schema.
+    pub transformed: usize,
+    /// Values the lens path would fill with defaults.
+    pub defaulted_values: usize,
+    /// Stored values that wouldn't survive lensing there and back.
+    pub lossy_values: usize,
+    /// Rows the candidate schema couldn't read, the condition a runtime
+    /// `SchemaWarning` reports.
+    pub unreachable: usize,
+}
+
+/// Outcome of [`rehearse_migration`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationRehearsal {
+    pub source_hash: SchemaHash,
+    pub target_hash: SchemaHash,
+    /// Tables touched by the lens, sorted by name.
+    pub tables: Vec<TableRehearsal>,
+}
+
+/// Apply `candidate` and `lens` to every row of the tables the lens touches.
+///
+/// Schemas and lenses already in the store's catalogue are loaded first, so
+/// rows on any older schema reach the candidate the way they would once the
+/// migration is published.
+pub fn rehearse_migration<S: Storage + ?Sized>(
+    storage: &S,
+    app_id: AppId,
+    candidate: &Schema,
+    lens: &Lens,
+) -> Result<MigrationRehearsal, String> {
+    let target_hash = SchemaHash::compute(candidate);
+    if lens.target_hash != target_hash {
+        return Err(format!(
+            "lens targets schema {} but the candidate schema is {}",
+            lens.target_hash, target_hash
+        ));
+    }
+
+    let mut manager = SchemaManager::new(
+        SyncManager::new(),
+        candidate.clone(),
+        app_id,
+        "rehearsal",
+        "main",
+    )
+    .map_err(|err| format!("invalid candidate schema: {err}"))?;
+    rehydrate_schema_manager_from_catalogue(&mut manager, storage, app_id)?;
+    if manager.get_known_schema(&lens.source_hash).is_none() {
+        return Err(format!(
+            "source schema {} is not in the store's catalogue",
+            lens.source_hash
+        ));
+    }
+    // Rehearsing a correction of an already published lens replaces it.
+    let lens = match manager.get_lens(&lens.source_hash, &lens.target_hash) {
+        Some(published) => lens.clone().superseding(published, "rehearsal"),
+        None => lens.clone(),
+    };
+    manager
+        .register_lens(lens.clone())
+        .map_err(|err| format!("invalid lens: {err}"))?;
+    manager.try_activate_pending_schemas();
+    let context = manager.context();
+
+    let affected = affected_tables(&lens);
+    let hashes_by_short: HashMap<String, SchemaHash> = manager
+        .known_schema_hashes()
+        .into_iter()
+        .chain(context.all_live_hashes())
+        .map(|hash| (hash.short(), hash))
+        .collect();
+
+    let mut tables: BTreeMap<String, TableRehearsal> = BTreeMap::new();
+    let locators = storage
+        .scan_row_locators()
+        .map_err(|err| format!("failed to scan rows: {err:?}"))?;
+    for (row_id, locator) in locators {
+        let stored_table = locator.table.as_str();
+        let table =
+            resolve_current_table_name(context, stored_table, locator.origin_schema_hash.as_ref());
+        if !affected.contains(stored_table)
+            && !table.as_ref().is_some_and(|table| affected.contains(table))
+        {
+            continue;
+        }
+
+        let history = storage
+            .scan_history_row_batches(stored_table, row_id)
+            .map_err(|err| format!("failed to scan history of row {row_id}: {err:?}"))?;
+        let report_name = table.clone().unwrap_or_else(|| stored_table.to_string());
+        let report = tables
+            .entry(report_name.clone())
+            .or_insert_with(|| TableRehearsal {
+                table: report_name,
+                ..TableRehearsal::default()
+            });
+
+        for batch in latest_visible_per_branch(&history) {
+            report.rows += 1;
+            let Some(table) = &table else {
+                report.unreachable += 1;
+                continue;
+            };
+            let source_hash = ComposedBranchName::parse(&BranchName::new(batch.branch.as_str()))
+                .and_then(|branch| hashes_by_short.get(&branch.schema_hash.short()).copied());
+            let Some(source_hash) = source_hash else {
+                report.unreachable += 1;
+                continue;
+            };
+            if source_hash == target_hash {
+                continue;
+            }
+
+            match LensTransformer::new(context, table).rehearse(row_id, &batch.data, source_hash) {
+                Ok(row) => {
+                    report.transformed += 1;
+                    report.defaulted_values += row.defaulted;
+                    report.lossy_values += row.lossy;
+                }
+                // Not a row of `table` at all.
+                Err(TransformError::EmptyExtraction(_)) => report.rows -= 1,
+                Err(_) => report.unreachable += 1,
+            }
+        }
+    }
+
+    Ok(MigrationRehearsal {
+        source_hash: lens.source_hash,
+        target_hash,
+        tables: tables.into_values().collect(),
+    })
+}
+
+/// [`rehearse_migration`] for a reviewed migration, reading its source schema
+/// from the store's catalogue.
+pub fn rehearse_reviewed_migration<S: Storage + ?Sized>(
+    storage: &S,
+    app_id: AppId,
+    candidate: &Schema,
+    migration: ReviewedMigration,
+) -> Result<MigrationRehearsal, String> {
+    let source_hash = SchemaHash::from_hex(&migration.from_hash)
+        .ok_or_else(|| format!("invalid fromHash {}", migration.from_hash))?;
+    let target_hash = SchemaHash::from_hex(&migration.to_hash)
+        .ok_or_else(|| format!("invalid toHash {}", migration.to_hash))?;
+    let source_schema = storage
+        .load_catalogue_entry(source_hash.to_object_id())
+        .map_err(|err| format!("failed to load source schema {source_hash}: {err:?}"))?
+        .ok_or_else(|| format!("source schema {source_hash} is not in the store's catalogue"))
+        .and_then(|entry| {
+            decode_schema(&entry.content)
+                .map_err(|err| format!("failed to decode source schema {source_hash}: {err:?}"))
+        })?;
+
+    let forward =
+        ReviewedMigration::forward_transform(migration.forward, &source_schema, candidate)?;
+    let lens = Lens::new(source_hash, target_hash, forward);
+    rehearse_migration(storage, app_id, candidate, &lens)
+}
+
+/// Every table name the lens mentions, on either side.
+fn affected_tables(lens: &Lens) -> HashSet<String> {
+    let mut tables = HashSet::new();
+    for op in &lens.forward.ops {
+        tables.insert(op.table().to_string());
+        match op {
+            LensOp::RenameTable { new_name, .. } => {
+                tables.insert(new_name.clone());
+            }
+            LensOp::ExtractColumns { to_table, .. } => {
+                tables.insert(to_table.clone());
+            }
+            LensOp::InlineColumns { from_table, .. } => {
+                tables.insert(from_table.clone());
+            }
+            _ => {}
+        }
+    }
+    tables
+}
+
+/// The latest visible, non-deleted version of a row on each branch.
+fn latest_visible_per_branch(history: &[StoredRowBatch]) -> Vec<&StoredRowBatch> {
+    let mut latest: HashMap<&str, &StoredRowBatch> = HashMap::new();
+    for batch in history.iter().filter(|batch| batch.state.is_visible()) {
+        let entry = latest.entry(batch.branch.as_str()).or_insert(batch);
+        if batch.lww_key() > entry.lww_key() {
+            *entry = batch;
+        }
+    }
+    let mut latest: Vec<_> = latest
+        .into_values()
+        .filter(|batch| !batch.is_soft_deleted() && !batch.is_hard_deleted())
+        .collect();
+    latest.sort_by(|left, right| left.branch.as_str().cmp(right.branch.as_str()));
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::ObjectId;
+    use crate::query_manager::types::{ColumnType, SchemaBuilder, TableSchema, Value};
+    use crate::schema_manager::{LensTransform, TypeConverter};
+    use crate::storage::MemoryStorage;
+
+    fn test_app_id() -> AppId {
+        AppId::from_name("rehearsal-test-app")
+    }
+
+    #[test]
+    fn rehearsal_counts_defaulted_lossy_and_unreachable_rows() {
+        let v1 = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("id", ColumnType::Uuid)
+                    .column("name", ColumnType::Text)
+                    .column("age", ColumnType::Text),
+            )
+            .build();
+        let v2 = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("id", ColumnType::Uuid)
+                    .column("name", ColumnType::Text)
+                    .column("age", ColumnType::Integer)
+                    .column_with_default("active", ColumnType::Boolean, Value::Boolean(true)),
+            )
+            .build();
+        let v1_hash = SchemaHash::compute(&v1);
+
+        let mut storage = MemoryStorage::new();
+        let mut writer =
+            SchemaManager::new(SyncManager::new(), v1.clone(), test_app_id(), "dev", "main")
+                .unwrap();
+        writer.persist_schema(&mut storage);
+
+        for (name, age) in [("Alice", "42"), ("Bob", "forty")] {
+            let row_id = ObjectId::new();
+            writer
+                .insert(
+                    &mut storage,
+                    "users",
+                    HashMap::from([
+                        ("id".to_string(), Value::Uuid(row_id)),
+                        ("name".to_string(), Value::Text(name.to_string())),
+                        ("age".to_string(), Value::Text(age.to_string())),
+                    ]),
+                    Some(row_id),
+                    None,
+                )
+                .unwrap();
+        }
+
+        let lens = Lens::new(
+            v1_hash,
+            SchemaHash::compute(&v2),
+            LensTransform::with_ops(vec![
+                LensOp::ChangeColumnType {
+                    table: "users".to_string(),
+                    column: "age".to_string(),
+                    old_type: ColumnType::Text,
+                    new_type: ColumnType::Integer,
+                    converter: TypeConverter::ParseInteger { fallback: 0 },
+                },
+                LensOp::AddColumn {
+                    table: "users".to_string(),
+                    column: "active".to_string(),
+                    column_type: ColumnType::Boolean,
+                    default: Value::Boolean(true),
+                },
+            ]),
+        );
+
+        let rehearsal = rehearse_migration(&storage, test_app_id(), &v2, &lens).unwrap();
+        assert_eq!(rehearsal.source_hash, v1_hash);
+        assert_eq!(
+            rehearsal.tables,
+            vec![TableRehearsal {
+                table: "users".to_string(),
+                rows: 2,
+                transformed: 2,
+                defaulted_values: 2,
+                lossy_values: 1,
+                unreachable: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn rehearsal_rejects_lens_for_another_schema() {
+        let v1 = SchemaBuilder::new()
+            .table(TableSchema::builder("users").column("id", ColumnType::Uuid))
+            .build();
+        let v2 = SchemaBuilder::new()
+            .table(TableSchema::builder("users").column("name", ColumnType::Text))
+            .build();
+        let lens = Lens::new(
+            SchemaHash::compute(&v2),
+            SchemaHash::compute(&v1),
+            LensTransform::new(),
+        );
+
+        let error =
+            rehearse_migration(&MemoryStorage::new(), test_app_id(), &v2, &lens).unwrap_err();
+        assert!(error.contains("lens targets schema"));
+    }
+}
@@ -4,7 +4,7 @@ use crate::metadata::{MetadataKey, ObjectType};
 use crate::object::ObjectId;
 use crate::storage::Storage;
 
-use super::encoding::decode_permissions_head;
+use super::encoding::{decode_permissions_head, decode_release};
 use super::{AppId, SchemaManager};
 
 fn entry_matches_app(entry: &crate::catalogue::CatalogueEntry, app_id: AppId) -> bool {
@@ -39,6 +39,7 @@ pub fn rehydrate_schema_manager_from_catalogue<S: Storage + ?Sized>(
     let mut schema_count = 0usize;
     let mut permissions_count = 0usize;
     let mut lens_count = 0usize;
+    let mut release_count = 0usize;
 
     for entry in entries
         .iter()
@@ -155,11 +156,42 @@ pub fn rehydrate_schema_manager_from_catalogue<S: Storage + ?Sized>(
         }
     }
 
+    // Newest release first, so older ones only fill in what it doesn't
+    // cover and never revive schemas retired since.
+    let mut releases: Vec<_> = entries
+        .iter()
+        .filter(|entry| entry.object_type() == Some(ObjectType::CatalogueRelease.as_str()))
+        .map(|entry| {
+            let version = decode_release(&entry.content)
+                .map(|(version, ..)| version)
+                .unwrap_or(0);
+            (version, entry)
+        })
+        .collect();
+    releases.sort_by(|(left, _), (right, _)| right.cmp(left));
+    for (_, entry) in releases {
+        if let Err(error) = schema_manager.process_catalogue_update(
+            entry.object_id,
+            &entry.metadata,
+            &entry.content,
+        ) {
+            warn!(
+                app_id = %app_id,
+                object_id = %entry.object_id,
+                ?error,
+                "failed to process release catalogue entry from storage"
+            );
+        } else {
+            release_count += 1;
+        }
+    }
+
     info!(
         app_id = %app_id,
         schema_count,
         permissions_count,
         lens_count,
+        release_count,
         "rehydrated schema manager from catalogue storage"
     );
 
@@ -0,0 +1,449 @@
+//! Reviewed migrations - the JSON form of a lens that `migrations push` sends.
+//!
+//! `POST /admin/migrations` publishes one of these, and
+//! `jazz-tools migrate rehearse` reads one from disk to dry-run it against a
+//! store. Both turn it into a [`LensTransform`] with
+//! [`ReviewedMigration::forward_transform`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::query_manager::types::{ColumnType, Schema, TableName, Value};
+
+use super::lens::{LensOp, LensTransform, TypeConverter};
+
+/// A reviewed migration edge between two schema versions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewedMigration {
+    pub from_hash: String,
+    pub to_hash: String,
+    pub forward: Vec<ReviewedTableLens>,
+}
+
+/// The changes a reviewed migration makes to one table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewedTableLens {
+    pub table: String,
+    #[serde(default)]
+    pub added: bool,
+    #[serde(default)]
+    pub removed: bool,
+    pub renamed_from: Option<String>,
+    pub operations: Vec<ReviewedLensOp>,
+}
+
+/// A column operation of a reviewed migration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ReviewedLensOp {
+    Introduce {
+        column: String,
+        column_type: ColumnType,
+        value: Value,
+    },
+    Drop {
+        column: String,
+        column_type: ColumnType,
+        value: Value,
+    },
+    Rename {
+        column: String,
+        value: String,
+    },
+    ChangeType {
+        column: String,
+        from: ColumnType,
+        to: ColumnType,
+        #[serde(default)]
+        converter: TypeConverter,
+    },
+    Split {
+        column: String,
+        into: Vec<String>,
+        separator: String,
+    },
+    Merge {
+        columns: Vec<String>,
+        into: String,
+        separator: String,
+    },
+    Extract {
+        columns: Vec<String>,
+        to_table: String,
+        fk_column: String,
+    },
+}
+
+impl ReviewedMigration {
+    /// Build the forward transform, taking added and removed table schemas
+    /// from `target_schema` and `source_schema`.
+    pub fn forward_transform(
+        tables: Vec<ReviewedTableLens>,
+        source_schema: &Schema,
+        target_schema: &Schema,
+    ) -> Result<LensTransform, String> {
+        let mut forward = LensTransform::new();
+        for table_lens in tables {
+            let table_name = table_lens.table;
+            if table_lens.added && table_lens.removed {
+                return Err(format!(
+                    "table {} cannot be both added and removed",
+                    table_name
+                ));
+            }
+            if (table_lens.added || table_lens.removed) && table_lens.renamed_from.is_some() {
+                return Err(format!(
+                    "table {} cannot combine added/removed markers with renamedFrom",
+                    table_name
+                ));
+            }
+            if (table_lens.added || table_lens.removed) && !table_lens.operations.is_empty() {
+                return Err(format!(
+                    "table {} cannot combine added/removed markers with column operations",
+                    table_name
+                ));
+            }
+            if table_lens.added {
+                let schema = target_schema
+                    .get(&TableName::from(table_name.clone()))
+                    .cloned()
+                    .ok_or_else(|| {
+                        format!(
+                            "createTables references unknown target table {}",
+                            table_name
+                        )
+                    })?;
+                forward.push(
+                    LensOp::AddTable {
+                        table: table_name.clone(),
+                        schema,
+                    },
+                    false,
+                );
+            }
+            if table_lens.removed {
+                let schema = source_schema
+                    .get(&TableName::from(table_name.clone()))
+                    .cloned()
+                    .ok_or_else(|| {
+                        format!("dropTables references unknown source table {}", table_name)
+                    })?;
+                forward.push(
+                    LensOp::RemoveTable {
+                        table: table_name.clone(),
+                        schema,
+                    },
+                    false,
+                );
+            }
+            if let Some(renamed_from) = table_lens.renamed_from {
+                forward.push(
+                    LensOp::RenameTable {
+                        old_name: renamed_from,
+                        new_name: table_name.clone(),
+                    },
+                    false,
+                );
+            }
+            for operation in table_lens.operations {
+                let op = match operation {
+                    ReviewedLensOp::Introduce {
+                        column,
+                        column_type,
+                        value,
+                    } => LensOp::AddColumn {
+                        table: table_name.clone(),
+                        column,
+                        column_type,
+                        default: value,
+                    },
+                    ReviewedLensOp::Drop {
+                        column,
+                        column_type,
+                        value,
+                    } => LensOp::RemoveColumn {
+                        table: table_name.clone(),
+                        column,
+                        column_type,
+                        default: value,
+                    },
+                    ReviewedLensOp::Rename { column, value } => LensOp::RenameColumn {
+                        table: table_name.clone(),
+                        old_name: column,
+                        new_name: value,
+                    },
+                    ReviewedLensOp::ChangeType {
+                        column,
+                        from,
+                        to,
+                        converter,
+                    } => {
+                        if !converter.supports(&from, &to) {
+                            return Err(format!(
+                                "changeType for {table_name}.{column} cannot convert {from:?} to {to:?} with the given converter"
+                            ));
+                        }
+                        LensOp::ChangeColumnType {
+                            table: table_name.clone(),
+                            column,
+                            old_type: from,
+                            new_type: to,
+                            converter,
+                        }
+                    }
+                    ReviewedLensOp::Split {
+                        column,
+                        into,
+                        separator,
+                    } => LensOp::SplitColumn {
+                        table: table_name.clone(),
+                        column,
+                        into,
+                        separator,
+                    },
+                    ReviewedLensOp::Merge {
+                        columns,
+                        into,
+                        separator,
+                    } => LensOp::MergeColumns {
+                        table: table_name.clone(),
+                        columns,
+                        into,
+                        separator,
+                    },
+                    ReviewedLensOp::Extract {
+                        columns,
+                        to_table,
+                        fk_column,
+                    } => LensOp::ExtractColumns {
+                        table: table_name.clone(),
+                        columns,
+                        to_table,
+                        fk_column,
+                    },
+                };
+                forward.push(op, false);
+            }
+        }
+        Ok(forward)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_manager::types::{SchemaBuilder, TableSchema};
+
+    fn users_schema() -> Schema {
+        SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("id", ColumnType::Uuid)
+                    .column("name", ColumnType::Text),
+            )
+            .build()
+    }
+
+    fn parse_tables(json: serde_json::Value) -> Vec<ReviewedTableLens> {
+        serde_json::from_value(json).expect("reviewed table lenses parse")
+    }
+
+    #[test]
+    fn parses_column_operations_into_lens_ops() {
+        let tables = parse_tables(serde_json::json!([{
+            "table": "users",
+            "operations": [
+                {
+                    "type": "introduce",
+                    "column": "email",
+                    "columnType": { "type": "Text" },
+                    "value": { "type": "Text", "value": "" }
+                },
+                { "type": "rename", "column": "name", "value": "full_name" },
+                {
+                    "type": "changeType",
+                    "column": "age",
+                    "from": { "type": "Text" },
+                    "to": { "type": "Integer" },
+                    "converter": { "type": "parseInteger", "fallback": 0 }
+                },
+                {
+                    "type": "split",
+                    "column": "full_name",
+                    "into": ["first", "last"],
+                    "separator": " "
+                }
+            ]
+        }]));
+
+        let forward =
+            ReviewedMigration::forward_transform(tables, &users_schema(), &users_schema()).unwrap();
+        assert!(forward.draft_ops.is_empty());
+        assert_eq!(
+            forward.ops,
+            vec![
+                LensOp::AddColumn {
+                    table: "users".to_string(),
+                    column: "email".to_string(),
+                    column_type: ColumnType::Text,
+                    default: Value::Text(String::new()),
+                },
+                LensOp::RenameColumn {
+                    table: "users".to_string(),
+                    old_name: "name".to_string(),
+                    new_name: "full_name".to_string(),
+                },
+                LensOp::ChangeColumnType {
+                    table: "users".to_string(),
+                    column: "age".to_string(),
+                    old_type: ColumnType::Text,
+                    new_type: ColumnType::Integer,
+                    converter: TypeConverter::ParseInteger { fallback: 0 },
+                },
+                LensOp::SplitColumn {
+                    table: "users".to_string(),
+                    column: "full_name".to_string(),
+                    into: vec!["first".to_string(), "last".to_string()],
+                    separator: " ".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn change_type_defaults_to_cast_and_rejects_unsupported_conversions() {
+        let change_type = |to: &str| {
+            parse_tables(serde_json::json!([{
+                "table": "users",
+                "operations": [{
+                    "type": "changeType",
+                    "column": "age",
+                    "from": { "type": "Integer" },
+                    "to": { "type": to }
+                }]
+            }]))
+        };
+
+        let forward = ReviewedMigration::forward_transform(
+            change_type("BigInt"),
+            &users_schema(),
+            &users_schema(),
+        )
+        .unwrap();
+        assert!(matches!(
+            &forward.ops[..],
+            [LensOp::ChangeColumnType {
+                converter: TypeConverter::Cast,
+                ..
+            }]
+        ));
+
+        let err = ReviewedMigration::forward_transform(
+            change_type("Uuid"),
+            &users_schema(),
+            &users_schema(),
+        )
+        .unwrap_err();
+        assert!(err.contains("users.age"), "{err}");
+    }
+
+    #[test]
+    fn added_and_removed_tables_take_their_schema_from_the_right_side() {
+        let empty = SchemaBuilder::new().build();
+        let users = users_schema();
+        let users_table = users.get(&TableName::new("users")).unwrap().clone();
+
+        let added = ReviewedMigration::forward_transform(
+            parse_tables(
+                serde_json::json!([{ "table": "users", "added": true, "operations": [] }]),
+            ),
+            &empty,
+            &users,
+        )
+        .unwrap();
+        assert_eq!(
+            added.ops,
+            vec![LensOp::AddTable {
+                table: "users".to_string(),
+                schema: users_table.clone(),
+            }]
+        );
+
+        let removed = ReviewedMigration::forward_transform(
+            parse_tables(
+                serde_json::json!([{ "table": "users", "removed": true, "operations": [] }]),
+            ),
+            &users,
+            &empty,
+        )
+        .unwrap();
+        assert_eq!(
+            removed.ops,
+            vec![LensOp::RemoveTable {
+                table: "users".to_string(),
+                schema: users_table,
+            }]
+        );
+
+        // The table has to exist on the side it is taken from.
+        assert!(
+            ReviewedMigration::forward_transform(
+                parse_tables(
+                    serde_json::json!([{ "table": "users", "added": true, "operations": [] }])
+                ),
+                &users,
+                &empty,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_contradictory_table_markers() {
+        let users = users_schema();
+        for table in [
+            serde_json::json!({ "table": "users", "added": true, "removed": true, "operations": [] }),
+            serde_json::json!({
+                "table": "users",
+                "added": true,
+                "renamedFrom": "people",
+                "operations": []
+            }),
+            serde_json::json!({
+                "table": "users",
+                "removed": true,
+                "operations": [{ "type": "rename", "column": "name", "value": "full_name" }]
+            }),
+        ] {
+            let tables = parse_tables(serde_json::Value::Array(vec![table]));
+            assert!(ReviewedMigration::forward_transform(tables, &users, &users).is_err());
+        }
+    }
+
+    #[test]
+    fn renamed_table_becomes_a_rename_before_its_column_ops() {
+        let tables = parse_tables(serde_json::json!([{
+            "table": "users",
+            "renamedFrom": "people",
+            "operations": [{ "type": "rename", "column": "name", "value": "full_name" }]
+        }]));
+
+        let forward =
+            ReviewedMigration::forward_transform(tables, &users_schema(), &users_schema()).unwrap();
+        assert_eq!(
+            forward.ops,
+            vec![
+                LensOp::RenameTable {
+                    old_name: "people".to_string(),
+                    new_name: "users".to_string(),
+                },
+                LensOp::RenameColumn {
+                    table: "users".to_string(),
+                    old_name: "name".to_string(),
+                    new_name: "full_name".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -6,12 +6,13 @@
 use std::collections::HashMap;
 
 use crate::metadata::MetadataKey;
+use crate::object::ObjectId;
 use crate::query_manager::encoding::{decode_row, encode_row};
-use crate::query_manager::types::{SchemaHash, TableName};
-use crate::row_histories::BatchId;
+use crate::query_manager::types::{RowDescriptor, SchemaHash, TableName, Value};
+use crate::row_histories::{BatchId, merge_lensed_write};
 
 use super::context::SchemaContext;
-use super::lens::Direction;
+use super::lens::{ConversionError, Direction, Lens, LensOp};
 
 /// Result of a lens transform operation.
 #[derive(Debug, Clone)]
@@ -24,6 +25,17 @@ pub struct TransformResult {
     pub was_transformed: bool,
 }
 
+/// What lensing one row to the current schema would do to its values; see
+/// [`LensTransformer::rehearse`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RowRehearsal {
+    /// Values the lens path fills in instead of carrying stored data.
+    pub defaulted: usize,
+    /// Stored values that don't survive lensing to the current schema and
+    /// back (dropped columns, lossy type conversions).
+    pub lossy: usize,
+}
+
 /// Error during lens transformation.
 #[derive(Debug, Clone, PartialEq)]
 pub enum TransformError {
@@ -38,6 +50,11 @@ pub enum TransformError {
     },
     /// Table not found in schema.
     TableNotFound(String),
+    /// A stored value can't be converted into its non-nullable column.
+    ConversionError(ConversionError),
+    /// The row's columns extracted into this table are all `NULL`, so it has
+    /// no row here. Not a failure: the row is simply skipped.
+    EmptyExtraction(String),
 }
 
 impl std::fmt::Display for TransformError {
@@ -54,12 +71,22 @@ impl std::fmt::Display for TransformError {
                 )
             }
             TransformError::TableNotFound(name) => write!(f, "table not found: {}", name),
+            TransformError::ConversionError(err) => write!(f, "{}", err),
+            TransformError::EmptyExtraction(table) => {
+                write!(f, "no row extracted into {}", table)
+            }
         }
     }
 }
 
 impl std::error::Error for TransformError {}
 
+impl From<ConversionError> for TransformError {
+    fn from(err: ConversionError) -> Self {
+        TransformError::ConversionError(err)
+    }
+}
+
 /// Transforms rows from old schema versions to the current schema.
 ///
 /// Used during materialization to convert rows loaded from old schema branches
@@ -81,6 +108,7 @@ impl<'a> LensTransformer<'a> {
     /// Transform a row from a source schema to the current schema.
     ///
     /// # Arguments
+    /// * `row_id` - Id of the row (extracted tables reference it)
     /// * `data` - Raw row data encoded with source schema
     /// * `batch_id` - Batch ID of the row
     /// * `source_hash` - Schema hash of the source (where row was stored)
@@ -89,6 +117,7 @@ impl<'a> LensTransformer<'a> {
     /// Transformed row data and metadata, or error if transform fails.
     pub fn transform(
         &self,
+        row_id: ObjectId,
         data: &[u8],
         batch_id: BatchId,
         source_hash: SchemaHash,
@@ -102,35 +131,151 @@ impl<'a> LensTransformer<'a> {
             });
         }
 
-        // Get source and target descriptors
-        let source_schema =
-            self.context
-                .get_schema(&source_hash)
-                .ok_or(TransformError::NoLensPath {
-                    source: source_hash,
-                    target: self.context.current_hash,
-                })?;
+        let hops = self.hops(source_hash)?;
+        let source_table = hops.first().map_or(&self.table, |hop| &hop.from_table);
+        let source_desc = self.columns(source_hash, source_table)?;
+        let target_desc = self.columns(self.context.current_hash, &self.table)?;
 
-        let source_table_name =
-            translate_table_name_to_schema(self.context, &self.table, &source_hash)
-                .ok_or_else(|| TransformError::TableNotFound(self.table.clone()))?;
+        // Decode row with source schema
+        let values = decode_row(source_desc, data)
+            .map_err(|e| TransformError::DecodeError(format!("{:?}", e)))?;
+        let values = self.lens_forward(row_id, values, &hops)?;
 
-        let source_table = source_schema
-            .get(&crate::query_manager::types::TableName::new(
-                &source_table_name,
-            ))
-            .ok_or_else(|| TransformError::TableNotFound(source_table_name.clone()))?;
+        // Encode with target schema
+        let transformed_data = encode_row(target_desc, &values)
+            .map_err(|e| TransformError::EncodeError(format!("{:?}", e)))?;
 
-        let target_table = self
-            .context
-            .current_schema
-            .get(&crate::query_manager::types::TableName::new(&self.table))
-            .ok_or_else(|| TransformError::TableNotFound(self.table.clone()))?;
+        Ok(TransformResult {
+            data: transformed_data,
+            batch_id,
+            was_transformed: true,
+        })
+    }
+
+    /// Transform a write from a source schema to the current schema as a
+    /// column-level diff against `parent`, the version its author edited,
+    /// applied onto `newest`, the row's latest version under another schema.
+    /// Both are given as stored data and the hash of the schema it's stored
+    /// under.
+    ///
+    /// Whole-row lensing would let the defaults a lens fills in for columns
+    /// the writer's schema lacks overwrite real values. Instead, `parent` is
+    /// lensed into the writer's schema, only the columns the write changed
+    /// relative to it are taken from the write, and the rest keep `newest`'s
+    /// values. Without a parent, every column the writer's schema has counts
+    /// as written.
+    pub fn transform_onto(
+        &self,
+        row_id: ObjectId,
+        data: &[u8],
+        batch_id: BatchId,
+        source_hash: SchemaHash,
+        parent: Option<(&[u8], SchemaHash)>,
+        newest: (&[u8], SchemaHash),
+    ) -> Result<TransformResult, TransformError> {
+        let (newest, newest_hash) = newest;
+        if source_hash == self.context.current_hash || source_hash == newest_hash {
+            return self.transform(row_id, data, batch_id, source_hash);
+        }
+
+        let hops = self.hops(source_hash)?;
+        let source_table = hops.first().map_or(&self.table, |hop| &hop.from_table);
+        let source_desc = self.columns(source_hash, source_table)?;
+        let target_desc = self.columns(self.context.current_hash, &self.table)?;
+
+        let newest = self.transform(row_id, newest, batch_id, newest_hash)?;
+        let newest = decode_row(target_desc, &newest.data)
+            .map_err(|e| TransformError::DecodeError(format!("{:?}", e)))?;
+        let seen = match parent {
+            Some((parent, parent_hash)) if parent_hash == source_hash => Some(
+                decode_row(source_desc, parent)
+                    .map_err(|e| TransformError::DecodeError(format!("{:?}", e)))?,
+            ),
+            Some((parent, parent_hash)) => {
+                let parent = self.transform(row_id, parent, batch_id, parent_hash)?;
+                let parent = decode_row(target_desc, &parent.data)
+                    .map_err(|e| TransformError::DecodeError(format!("{:?}", e)))?;
+                Some(self.lens_backward(row_id, parent, &hops)?)
+            }
+            None => None,
+        };
+
+        let written = decode_row(source_desc, data)
+            .map_err(|e| TransformError::DecodeError(format!("{:?}", e)))?;
+        let changed: Vec<bool> = target_desc
+            .columns
+            .iter()
+            .map(|column| {
+                translate_table_and_column_for_schema(
+                    self.context,
+                    &self.table,
+                    column.name.as_str(),
+                    &source_hash,
+                )
+                .and_then(|(_, source_column)| source_desc.column_index(&source_column))
+                .is_some_and(|index| {
+                    seen.as_ref()
+                        .is_none_or(|seen| written[index] != seen[index])
+                })
+            })
+            .collect();
+        let lensed = self.lens_forward(row_id, written, &hops)?;
+        let values = merge_lensed_write(&newest, &lensed, &changed);
+
+        let transformed_data = encode_row(target_desc, &values)
+            .map_err(|e| TransformError::EncodeError(format!("{:?}", e)))?;
+
+        Ok(TransformResult {
+            data: transformed_data,
+            batch_id,
+            was_transformed: true,
+        })
+    }
 
-        let source_desc = &source_table.columns;
-        let target_desc = &target_table.columns;
+    /// Dry-run [`Self::transform`] for one row and count the values it would
+    /// default or lose.
+    ///
+    /// Fails exactly when `transform` would, i.e. when the row would be
+    /// reported in a schema warning at runtime.
+    pub fn rehearse(
+        &self,
+        row_id: ObjectId,
+        data: &[u8],
+        source_hash: SchemaHash,
+    ) -> Result<RowRehearsal, TransformError> {
+        if source_hash == self.context.current_hash {
+            return Ok(RowRehearsal::default());
+        }
+
+        let hops = self.hops(source_hash)?;
+        let source_table = hops.first().map_or(&self.table, |hop| &hop.from_table);
+        let source_desc = self.columns(source_hash, source_table)?;
+        let target_desc = self.columns(self.context.current_hash, &self.table)?;
 
-        // Get lens path and apply transforms
+        let values = decode_row(source_desc, data)
+            .map_err(|e| TransformError::DecodeError(format!("{:?}", e)))?;
+        let lensed = self.lens_forward(row_id, values.clone(), &hops)?;
+        encode_row(target_desc, &lensed)
+            .map_err(|e| TransformError::EncodeError(format!("{:?}", e)))?;
+        // A row that can't be lensed back at all loses every stored value.
+        let lossy = match self.lens_backward(row_id, lensed, &hops) {
+            Ok(round_trip) => values
+                .iter()
+                .zip(&round_trip)
+                .filter(|(stored, restored)| stored != restored)
+                .count(),
+            Err(_) => values.len(),
+        };
+
+        Ok(RowRehearsal {
+            defaulted: hops.iter().map(Hop::defaulted_columns).sum(),
+            lossy,
+        })
+    }
+
+    /// The lens path from `source_hash` to the current schema, with this
+    /// table's name on both sides of every hop.
+    fn hops(&self, source_hash: SchemaHash) -> Result<Vec<Hop<'a>>, TransformError> {
         let lens_path =
             self.context
                 .lens_path(&source_hash)
@@ -139,52 +284,141 @@ impl<'a> LensTransformer<'a> {
                     target: self.context.current_hash,
                 })?;
 
-        // Decode row with source schema
-        let mut values = decode_row(source_desc, data)
-            .map_err(|e| TransformError::DecodeError(format!("{:?}", e)))?;
+        // Name the table at every hop, walking back from the current schema:
+        // several tables can read from one stored table (e.g. extracted
+        // columns), so names can't be recovered walking forward.
+        let mut table_names = vec![self.table.clone()];
+        for (lens, direction) in lens_path.iter().rev() {
+            let previous = table_names.last().expect("starts non-empty");
+            let previous = lens
+                .translate_table(previous, direction.reverse())
+                .ok_or_else(|| TransformError::TableNotFound(previous.clone()))?;
+            table_names.push(previous);
+        }
+        table_names.reverse();
+
+        Ok(lens_path
+            .into_iter()
+            .zip(table_names.windows(2))
+            .map(|((lens, direction), names)| Hop {
+                lens,
+                direction,
+                from_table: names[0].clone(),
+                to_table: names[1].clone(),
+            })
+            .collect())
+    }
 
-        // Apply each lens in the path with the appropriate direction
-        let mut current_desc = source_desc.clone();
-        let mut current_table_name = source_table_name;
-        for (lens, direction) in lens_path {
-            // Get the next schema based on direction
-            // Forward: source -> target, Backward: target -> source
-            let next_hash = match direction {
-                Direction::Forward => lens.target_hash,
-                Direction::Backward => lens.source_hash,
-            };
-
-            let next_schema = self.context.get_schema(&next_hash).ok_or({
-                TransformError::NoLensPath {
-                    source: lens.source_hash,
-                    target: lens.target_hash,
-                }
+    /// Columns of `table` in the schema identified by `hash`.
+    fn columns(&self, hash: SchemaHash, table: &str) -> Result<&'a RowDescriptor, TransformError> {
+        let schema = self
+            .context
+            .get_schema(&hash)
+            .ok_or(TransformError::NoLensPath {
+                source: hash,
+                target: self.context.current_hash,
             })?;
-            let next_table_name = lens
-                .translate_table(&current_table_name, direction)
-                .ok_or_else(|| TransformError::TableNotFound(current_table_name.clone()))?;
-            let next_table = next_schema
-                .get(&crate::query_manager::types::TableName::new(
-                    &next_table_name,
-                ))
-                .ok_or_else(|| TransformError::TableNotFound(next_table_name.clone()))?;
-            let next_desc = &next_table.columns;
-
-            // Apply lens with the appropriate direction
-            values = lens.apply(&values, &current_desc, next_desc, direction);
-            current_desc = next_desc.clone();
-            current_table_name = next_table_name;
+        schema
+            .get(&TableName::new(table))
+            .map(|table| &table.columns)
+            .ok_or_else(|| TransformError::TableNotFound(table.to_string()))
+    }
+
+    /// Apply each hop in order, from the source schema to the current one.
+    fn lens_forward(
+        &self,
+        row_id: ObjectId,
+        mut values: Vec<Value>,
+        hops: &[Hop<'a>],
+    ) -> Result<Vec<Value>, TransformError> {
+        for hop in hops {
+            // Forward: source -> target, Backward: target -> source
+            let (from_hash, to_hash) = hop.hashes();
+            let from_desc = self.columns(from_hash, &hop.from_table)?;
+            if hop.lens.extracts_nothing(
+                &hop.from_table,
+                &hop.to_table,
+                &values,
+                from_desc,
+                hop.direction,
+            ) {
+                return Err(TransformError::EmptyExtraction(hop.to_table.clone()));
+            }
+            values = hop.lens.apply(
+                &hop.from_table,
+                row_id,
+                &values,
+                from_desc,
+                self.columns(to_hash, &hop.to_table)?,
+                hop.direction,
+            )?;
         }
+        Ok(values)
+    }
 
-        // Encode with target schema
-        let transformed_data = encode_row(target_desc, &values)
-            .map_err(|e| TransformError::EncodeError(format!("{:?}", e)))?;
+    /// Undo each hop in reverse order, from the current schema back to the
+    /// source one.
+    fn lens_backward(
+        &self,
+        row_id: ObjectId,
+        mut values: Vec<Value>,
+        hops: &[Hop<'a>],
+    ) -> Result<Vec<Value>, TransformError> {
+        for hop in hops.iter().rev() {
+            let (from_hash, to_hash) = hop.hashes();
+            let to_desc = self.columns(to_hash, &hop.to_table)?;
+            if hop.lens.extracts_nothing(
+                &hop.to_table,
+                &hop.from_table,
+                &values,
+                to_desc,
+                hop.direction.reverse(),
+            ) {
+                return Err(TransformError::EmptyExtraction(hop.from_table.clone()));
+            }
+            values = hop.lens.apply(
+                &hop.to_table,
+                row_id,
+                &values,
+                to_desc,
+                self.columns(from_hash, &hop.from_table)?,
+                hop.direction.reverse(),
+            )?;
+        }
+        Ok(values)
+    }
+}
 
-        Ok(TransformResult {
-            data: transformed_data,
-            batch_id,
-            was_transformed: true,
-        })
+/// One step of a lens path, with the table's name on either side.
+struct Hop<'a> {
+    lens: &'a Lens,
+    direction: Direction,
+    from_table: String,
+    to_table: String,
+}
+
+impl Hop<'_> {
+    /// Schemas this hop reads from and writes to.
+    fn hashes(&self) -> (SchemaHash, SchemaHash) {
+        match self.direction {
+            Direction::Forward => (self.lens.source_hash, self.lens.target_hash),
+            Direction::Backward => (self.lens.target_hash, self.lens.source_hash),
+        }
+    }
+
+    /// Columns this hop fills with a default rather than a stored value.
+    fn defaulted_columns(&self) -> usize {
+        self.lens
+            .transform(self.direction)
+            .ops
+            .iter()
+            .filter(|op| op.table() == self.from_table || op.table() == self.to_table)
+            .map(|op| match op {
+                LensOp::AddColumn { .. } => 1,
+                LensOp::InlineColumns { columns, .. } => columns.len(),
+                _ => 0,
+            })
+            .sum()
     }
 }
 
@@ -314,7 +548,9 @@ pub fn translate_column_for_index(
 mod tests {
     use super::*;
     use crate::object::ObjectId;
-    use crate::query_manager::types::{ColumnType, SchemaBuilder, TableSchema, Value};
+    use crate::query_manager::types::{
+        ColumnDescriptor, ColumnType, Date, SchemaBuilder, TableSchema, Value,
+    };
     use crate::schema_manager::auto_lens::generate_lens;
     use crate::schema_manager::lens::{Lens, LensOp, LensTransform};
 
@@ -362,7 +598,7 @@ mod tests {
         let data = encode_row(&table.columns, &values).unwrap();
 
         let result = transformer
-            .transform(&data, make_commit_id(1), v2_hash)
+            .transform(ObjectId::new(), &data, make_commit_id(1), v2_hash)
             .unwrap();
 
         assert!(!result.was_transformed);
@@ -391,7 +627,7 @@ mod tests {
 
         // Transform to v2
         let result = transformer
-            .transform(&v1_data, make_commit_id(1), v1_hash)
+            .transform(id, &v1_data, make_commit_id(1), v1_hash)
             .unwrap();
 
         assert!(result.was_transformed);
@@ -408,6 +644,162 @@ mod tests {
         assert_eq!(v2_values[2], Value::Null); // Added column
     }
 
+    #[test]
+    fn transform_onto_keeps_columns_the_writer_cannot_see() {
+        let v1 = make_schema_v1();
+        let v2 = make_schema_v2();
+        let v1_hash = SchemaHash::compute(&v1);
+        let v2_hash = SchemaHash::compute(&v2);
+        let lens = generate_lens(&v1, &v2);
+
+        let mut ctx = SchemaContext::new(v2.clone(), "dev", "main");
+        ctx.add_live_schema(v1.clone(), lens);
+
+        let transformer = LensTransformer::new(&ctx, "users");
+        let v1_table = v1.get(&TableName::new("users")).unwrap();
+        let v2_table = v2.get(&TableName::new("users")).unwrap();
+
+        let id = ObjectId::new();
+        let newest = encode_row(
+            &v2_table.columns,
+            &[
+                Value::Uuid(id),
+                Value::Text("Alice".to_string()),
+                Value::Text("alice@example.com".to_string()),
+            ],
+        )
+        .unwrap();
+        let v1_write = encode_row(
+            &v1_table.columns,
+            &[Value::Uuid(id), Value::Text("Alice Updated".to_string())],
+        )
+        .unwrap();
+
+        let result = transformer
+            .transform_onto(
+                id,
+                &v1_write,
+                make_commit_id(2),
+                v1_hash,
+                Some((newest.as_slice(), v2_hash)),
+                (newest.as_slice(), v2_hash),
+            )
+            .unwrap();
+
+        assert!(result.was_transformed);
+        assert_eq!(result.batch_id, make_commit_id(2));
+        assert_eq!(
+            decode_row(&v2_table.columns, &result.data).unwrap(),
+            vec![
+                Value::Uuid(id),
+                Value::Text("Alice Updated".to_string()),
+                Value::Text("alice@example.com".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn transform_onto_diffs_against_the_version_the_writer_edited() {
+        let v1 = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("name", ColumnType::Text)
+                    .column("age", ColumnType::Integer),
+            )
+            .build();
+        let v2 = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("name", ColumnType::Text)
+                    .column("age", ColumnType::Integer)
+                    .nullable_column("email", ColumnType::Text),
+            )
+            .build();
+        let v1_hash = SchemaHash::compute(&v1);
+        let v2_hash = SchemaHash::compute(&v2);
+        let mut ctx = SchemaContext::new(v2.clone(), "dev", "main");
+        ctx.add_live_schema(v1.clone(), generate_lens(&v1, &v2));
+        let transformer = LensTransformer::new(&ctx, "users");
+        let v1_desc = &v1.get(&TableName::new("users")).unwrap().columns;
+        let v2_desc = &v2.get(&TableName::new("users")).unwrap().columns;
+        let text = |s: &str| Value::Text(s.to_string());
+
+        let id = ObjectId::new();
+        // The v1 client edited this version and changed only `age`...
+        let parent = encode_row(
+            v2_desc,
+            &[text("Alice"), Value::Integer(30), text("alice@example.com")],
+        )
+        .unwrap();
+        let v1_write = encode_row(v1_desc, &[text("Alice"), Value::Integer(31)]).unwrap();
+        // ...while a v2 client renamed the row.
+        let newest = encode_row(
+            v2_desc,
+            &[
+                text("Alicia"),
+                Value::Integer(30),
+                text("alice@example.com"),
+            ],
+        )
+        .unwrap();
+
+        let result = transformer
+            .transform_onto(
+                id,
+                &v1_write,
+                make_commit_id(3),
+                v1_hash,
+                Some((parent.as_slice(), v2_hash)),
+                (newest.as_slice(), v2_hash),
+            )
+            .unwrap();
+        assert_eq!(
+            decode_row(v2_desc, &result.data).unwrap(),
+            vec![
+                text("Alicia"),
+                Value::Integer(31),
+                text("alice@example.com")
+            ]
+        );
+
+        // A parent stored under the writer's own schema needs no lensing.
+        let v1_parent = encode_row(v1_desc, &[text("Alice"), Value::Integer(30)]).unwrap();
+        let result = transformer
+            .transform_onto(
+                id,
+                &v1_write,
+                make_commit_id(3),
+                v1_hash,
+                Some((v1_parent.as_slice(), v1_hash)),
+                (newest.as_slice(), v2_hash),
+            )
+            .unwrap();
+        assert_eq!(
+            decode_row(v2_desc, &result.data).unwrap(),
+            vec![
+                text("Alicia"),
+                Value::Integer(31),
+                text("alice@example.com")
+            ]
+        );
+
+        // Without a parent, every column the writer sees counts as written.
+        let result = transformer
+            .transform_onto(
+                id,
+                &v1_write,
+                make_commit_id(3),
+                v1_hash,
+                None,
+                (newest.as_slice(), v2_hash),
+            )
+            .unwrap();
+        assert_eq!(
+            decode_row(v2_desc, &result.data).unwrap(),
+            vec![text("Alice"), Value::Integer(31), text("alice@example.com")]
+        );
+    }
+
     #[test]
     fn transform_renamed_table() {
         use crate::schema_manager::lens::{Lens, LensOp, LensTransform};
@@ -452,7 +844,7 @@ mod tests {
         let v1_data = encode_row(&v1_table.columns, &v1_values).unwrap();
 
         let result = transformer
-            .transform(&v1_data, make_commit_id(1), v1_hash)
+            .transform(id, &v1_data, make_commit_id(1), v1_hash)
             .unwrap();
 
         assert!(result.was_transformed);
@@ -464,6 +856,313 @@ mod tests {
         assert_eq!(v2_values, v1_values);
     }
 
+    #[test]
+    fn transform_changes_column_type_in_both_directions() {
+        use crate::query_manager::types::TableName;
+        use crate::schema_manager::lens::TypeConverter;
+
+        let schema_with_age = |age_type| {
+            SchemaBuilder::new()
+                .table(
+                    TableSchema::builder("users")
+                        .column("id", ColumnType::Uuid)
+                        .column("age", age_type),
+                )
+                .build()
+        };
+        let v1 = schema_with_age(ColumnType::Text);
+        let v2 = schema_with_age(ColumnType::Integer);
+        let v1_hash = SchemaHash::compute(&v1);
+        let v2_hash = SchemaHash::compute(&v2);
+        let lens = Lens::new(
+            v1_hash,
+            v2_hash,
+            LensTransform::with_ops(vec![LensOp::ChangeColumnType {
+                table: "users".to_string(),
+                column: "age".to_string(),
+                old_type: ColumnType::Text,
+                new_type: ColumnType::Integer,
+                converter: TypeConverter::ParseInteger { fallback: 0 },
+            }]),
+        );
+        let v1_desc = &v1.get(&TableName::new("users")).unwrap().columns;
+        let v2_desc = &v2.get(&TableName::new("users")).unwrap().columns;
+        let id = ObjectId::new();
+
+        // Forward: old text rows read as integers, falling back to 0.
+        let mut ctx = SchemaContext::new(v2.clone(), "dev", "main");
+        ctx.add_live_schema(v1.clone(), lens.clone());
+        let transformer = LensTransformer::new(&ctx, "users");
+        for (raw, expected) in [("42", 42), ("n/a", 0)] {
+            let data =
+                encode_row(v1_desc, &[Value::Uuid(id), Value::Text(raw.to_string())]).unwrap();
+            let result = transformer
+                .transform(id, &data, make_commit_id(1), v1_hash)
+                .unwrap();
+            assert_eq!(
+                decode_row(v2_desc, &result.data).unwrap(),
+                vec![Value::Uuid(id), Value::Integer(expected)]
+            );
+        }
+
+        // Backward: a client still on v1 reads newer integer rows as text.
+        let mut ctx = SchemaContext::new(v1.clone(), "dev", "main");
+        ctx.add_live_schema(v2.clone(), lens);
+        let transformer = LensTransformer::new(&ctx, "users");
+        let data = encode_row(v2_desc, &[Value::Uuid(id), Value::Integer(7)]).unwrap();
+        let result = transformer
+            .transform(id, &data, make_commit_id(2), v2_hash)
+            .unwrap();
+        assert_eq!(
+            decode_row(v1_desc, &result.data).unwrap(),
+            vec![Value::Uuid(id), Value::Text("7".to_string())]
+        );
+    }
+
+    /// Lenses a row whose `value` column changes from `old` to `new` forward
+    /// to `new`, and the resulting row back to `old`.
+    fn lens_value_column(
+        stored: Value,
+        old: ColumnDescriptor,
+        new: ColumnDescriptor,
+    ) -> Result<(Value, Value), TransformError> {
+        use crate::query_manager::types::TableName;
+        use crate::schema_manager::lens::TypeConverter;
+
+        let schema_with = |column: &ColumnDescriptor| {
+            SchemaBuilder::new()
+                .table(
+                    TableSchema::builder("events")
+                        .column("id", ColumnType::Uuid)
+                        .column_descriptor(column.clone()),
+                )
+                .build()
+        };
+        let v1 = schema_with(&old);
+        let v2 = schema_with(&new);
+        let lens = Lens::new(
+            SchemaHash::compute(&v1),
+            SchemaHash::compute(&v2),
+            LensTransform::with_ops(vec![LensOp::ChangeColumnType {
+                table: "events".to_string(),
+                column: "value".to_string(),
+                old_type: old.column_type.clone(),
+                new_type: new.column_type.clone(),
+                converter: TypeConverter::Cast,
+            }]),
+        );
+        let v1_desc = &v1.get(&TableName::new("events")).unwrap().columns;
+        let v2_desc = &v2.get(&TableName::new("events")).unwrap().columns;
+        let id = ObjectId::new();
+
+        let mut ctx = SchemaContext::new(v2.clone(), "dev", "main");
+        ctx.add_live_schema(v1.clone(), lens.clone());
+        let data = encode_row(v1_desc, &[Value::Uuid(id), stored]).unwrap();
+        let forward = LensTransformer::new(&ctx, "events").transform(
+            id,
+            &data,
+            make_commit_id(1),
+            SchemaHash::compute(&v1),
+        )?;
+        let forward = decode_row(v2_desc, &forward.data).unwrap().remove(1);
+
+        let mut ctx = SchemaContext::new(v1.clone(), "dev", "main");
+        ctx.add_live_schema(v2.clone(), lens);
+        let data = encode_row(v2_desc, &[Value::Uuid(id), forward.clone()]).unwrap();
+        let backward = LensTransformer::new(&ctx, "events").transform(
+            id,
+            &data,
+            make_commit_id(2),
+            SchemaHash::compute(&v2),
+        )?;
+        let backward = decode_row(v1_desc, &backward.data).unwrap().remove(1);
+
+        Ok((forward, backward))
+    }
+
+    #[test]
+    fn bigint_overflowing_integer_is_nulled_defaulted_or_rejected() {
+        let bigint = || ColumnDescriptor::new("value", ColumnType::BigInt).nullable();
+        let integer = || ColumnDescriptor::new("value", ColumnType::Integer);
+
+        assert_eq!(
+            lens_value_column(Value::BigInt(7), bigint(), integer()),
+            Ok((Value::Integer(7), Value::BigInt(7)))
+        );
+        assert_eq!(
+            lens_value_column(Value::BigInt(1 << 40), bigint(), integer().nullable()),
+            Ok((Value::Null, Value::Null))
+        );
+        assert_eq!(
+            lens_value_column(
+                Value::BigInt(1 << 40),
+                bigint(),
+                integer().default(Value::Integer(-1))
+            ),
+            Ok((Value::Integer(-1), Value::BigInt(-1)))
+        );
+        assert_eq!(
+            lens_value_column(Value::BigInt(1 << 40), bigint(), integer()),
+            Err(TransformError::ConversionError(ConversionError {
+                table: "events".to_string(),
+                column: "value".to_string(),
+                column_type: ColumnType::Integer,
+            }))
+        );
+    }
+
+    #[test]
+    fn unparsable_date_text_takes_the_column_default() {
+        let text = || ColumnDescriptor::new("value", ColumnType::Text);
+        let date = || ColumnDescriptor::new("value", ColumnType::Date);
+        let epoch = Date::parse("1970-01-01").unwrap();
+
+        assert_eq!(
+            lens_value_column(Value::Text("2024-02-29".to_string()), text(), date()),
+            Ok((
+                Value::Date(Date::parse("2024-02-29").unwrap()),
+                Value::Text("2024-02-29".to_string())
+            ))
+        );
+        assert_eq!(
+            lens_value_column(
+                Value::Text("next tuesday".to_string()),
+                text(),
+                date().default(Value::Date(epoch))
+            ),
+            Ok((Value::Date(epoch), Value::Text("1970-01-01".to_string())))
+        );
+        assert!(matches!(
+            lens_value_column(Value::Text("next tuesday".to_string()), text(), date()),
+            Err(TransformError::ConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn out_of_range_timestamps_are_rejected_or_nulled() {
+        let bigint = || ColumnDescriptor::new("value", ColumnType::BigInt);
+        let timestamp = || ColumnDescriptor::new("value", ColumnType::Timestamp);
+
+        // Negative microsecond counts are before the epoch.
+        assert!(matches!(
+            lens_value_column(Value::BigInt(-1), bigint(), timestamp()),
+            Err(TransformError::ConversionError(_))
+        ));
+        assert_eq!(
+            lens_value_column(
+                Value::BigInt(-1),
+                bigint().nullable(),
+                timestamp().nullable()
+            ),
+            Ok((Value::Null, Value::Null))
+        );
+        assert_eq!(
+            lens_value_column(Value::BigInt(1_700_000_000_000_000), bigint(), timestamp()),
+            Ok((
+                Value::Timestamp(1_700_000_000_000_000),
+                Value::BigInt(1_700_000_000_000_000)
+            ))
+        );
+        // Timestamps past `i64::MAX` don't fit into a bigint.
+        assert_eq!(
+            lens_value_column(
+                Value::Timestamp(u64::MAX),
+                timestamp().nullable(),
+                bigint().nullable()
+            ),
+            Ok((Value::Null, Value::Null))
+        );
+    }
+
+    #[test]
+    fn transform_reads_extracted_table_from_old_rows() {
+        use crate::query_manager::types::TableName;
+
+        let v1 = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("name", ColumnType::Text)
+                    .nullable_column("street", ColumnType::Text),
+            )
+            .build();
+        let v2 = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("name", ColumnType::Text)
+                    .nullable_fk_column("address_id", "addresses"),
+            )
+            .table(TableSchema::builder("addresses").nullable_column("street", ColumnType::Text))
+            .build();
+        let v1_hash = SchemaHash::compute(&v1);
+        let lens = generate_lens(&v1, &v2);
+        assert!(matches!(
+            lens.forward.ops.as_slice(),
+            [LensOp::ExtractColumns { .. }]
+        ));
+
+        let mut ctx = SchemaContext::new(v2.clone(), "dev", "main");
+        ctx.add_live_schema(v1.clone(), lens);
+        assert_eq!(
+            translate_table_name_to_schema(&ctx, "addresses", &v1_hash),
+            Some("users".to_string())
+        );
+
+        let id = ObjectId::new();
+        let v1_desc = &v1.get(&TableName::new("users")).unwrap().columns;
+        let data = encode_row(
+            v1_desc,
+            &[
+                Value::Text("Ada".to_string()),
+                Value::Text("1 Main St".to_string()),
+            ],
+        )
+        .unwrap();
+
+        let users = LensTransformer::new(&ctx, "users")
+            .transform(id, &data, make_commit_id(1), v1_hash)
+            .unwrap();
+        assert_eq!(
+            decode_row(
+                &v2.get(&TableName::new("users")).unwrap().columns,
+                &users.data
+            )
+            .unwrap(),
+            vec![Value::Text("Ada".to_string()), Value::Uuid(id)]
+        );
+
+        let addresses = LensTransformer::new(&ctx, "addresses")
+            .transform(id, &data, make_commit_id(1), v1_hash)
+            .unwrap();
+        assert_eq!(
+            decode_row(
+                &v2.get(&TableName::new("addresses")).unwrap().columns,
+                &addresses.data
+            )
+            .unwrap(),
+            vec![Value::Text("1 Main St".to_string())]
+        );
+
+        // A user without a street has no address row and no reference.
+        let data = encode_row(v1_desc, &[Value::Text("Bob".to_string()), Value::Null]).unwrap();
+        let users = LensTransformer::new(&ctx, "users")
+            .transform(id, &data, make_commit_id(2), v1_hash)
+            .unwrap();
+        assert_eq!(
+            decode_row(
+                &v2.get(&TableName::new("users")).unwrap().columns,
+                &users.data
+            )
+            .unwrap(),
+            vec![Value::Text("Bob".to_string()), Value::Null]
+        );
+        assert_eq!(
+            LensTransformer::new(&ctx, "addresses")
+                .transform(id, &data, make_commit_id(2), v1_hash)
+                .unwrap_err(),
+            TransformError::EmptyExtraction("addresses".to_string())
+        );
+    }
+
     #[test]
     fn translate_column_no_change() {
         let v2 = make_schema_v2();
@@ -563,7 +1262,7 @@ mod tests {
 
         // Transform from v1 to v3 (2 hops)
         let result = transformer
-            .transform(&v1_data, make_commit_id(1), v1_hash)
+            .transform(id, &v1_data, make_commit_id(1), v1_hash)
             .unwrap();
 
         assert!(result.was_transformed);
@@ -613,7 +1312,7 @@ mod tests {
 
         // Transform from v2 to v3 (1 hop)
         let result = transformer
-            .transform(&v2_data, make_commit_id(1), v2_hash)
+            .transform(id, &v2_data, make_commit_id(1), v2_hash)
             .unwrap();
 
         assert!(result.was_transformed);
@@ -830,7 +1529,7 @@ mod tests {
 
         // Transform v1 -> v3
         let result = transformer
-            .transform(&v1_data, make_commit_id(1), v1_hash)
+            .transform(id, &v1_data, make_commit_id(1), v1_hash)
             .unwrap();
 
         assert!(result.was_transformed);
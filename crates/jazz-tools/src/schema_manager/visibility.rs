@@ -0,0 +1,530 @@
+//! Partial schema visibility - the view user-role clients get of schemas with
+//! internal tables or columns.
+//!
+//! A schema's client projection drops its internal tables and columns. Every
+//! node that knows the full schema derives the same projection and registers
+//! it behind a lens from the full schema, so rows written against the
+//! projected hash resolve like rows of any other live schema version. The
+//! server rewrites catalogue entries and row batches into the projection
+//! before sending them to user-role clients.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::query_manager::encoding::{EncodingError, decode_row, encode_row};
+use crate::query_manager::types::{
+    ColumnName, RowDescriptor, Schema, SchemaHash, TableName, TableSchema,
+};
+
+use super::diff::lens_default_for_column;
+use super::lens::{Lens, LensOp, LensTransform};
+
+/// The client projection of one schema with internal items.
+#[derive(Debug, Clone)]
+pub struct SchemaProjection {
+    pub full_hash: SchemaHash,
+    pub projected_hash: SchemaHash,
+    /// The schema user-role clients see.
+    pub projected: Schema,
+    hidden_tables: HashSet<TableName>,
+    /// Public tables that lose columns: their full descriptor and the indices
+    /// of the columns clients keep.
+    hidden_columns: HashMap<TableName, (RowDescriptor, Vec<usize>)>,
+}
+
+/// What [`SchemaProjection::project_row`] did to a stored row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RowProjection {
+    /// The row belongs to an internal table and must not be sent.
+    Hidden,
+    /// The table has no internal columns; the row bytes are already valid
+    /// for the projected schema.
+    Unchanged,
+    /// The row re-encoded without its internal columns.
+    Rewritten(Vec<u8>),
+}
+
+impl SchemaProjection {
+    /// Project `schema`, or `None` when nothing in it is internal.
+    pub fn of(schema: &Schema) -> Option<Self> {
+        if !schema.values().any(TableSchema::has_internal_items) {
+            return None;
+        }
+
+        let projected = project_schema(schema);
+        let hidden_tables = schema
+            .iter()
+            .filter(|(_, table)| table.visibility.is_internal())
+            .map(|(name, _)| *name)
+            .collect();
+        let hidden_columns = schema
+            .iter()
+            .filter(|(_, table)| {
+                !table.visibility.is_internal()
+                    && table
+                        .columns
+                        .columns
+                        .iter()
+                        .any(|column| column.visibility.is_internal())
+            })
+            .map(|(name, table)| {
+                let kept = table
+                    .columns
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, column)| !column.visibility.is_internal())
+                    .map(|(index, _)| index)
+                    .collect();
+                (*name, (table.columns.clone(), kept))
+            })
+            .collect();
+
+        Some(Self {
+            full_hash: SchemaHash::compute(schema),
+            projected_hash: SchemaHash::compute(&projected),
+            projected,
+            hidden_tables,
+            hidden_columns,
+        })
+    }
+
+    /// Whether the whole table is internal.
+    pub fn hides_table(&self, table: &str) -> bool {
+        self.hidden_tables.contains(&TableName::new(table))
+    }
+
+    /// Whether the table or this column of it is internal.
+    pub fn hides_column(&self, table: &str, column: &str) -> bool {
+        if self.hides_table(table) {
+            return true;
+        }
+        self.hidden_columns
+            .get(&TableName::new(table))
+            .is_some_and(|(descriptor, kept)| {
+                descriptor
+                    .column_index(column)
+                    .is_some_and(|index| !kept.contains(&index))
+            })
+    }
+
+    /// The lens from the full schema to its projection.
+    ///
+    /// Going backward, rows written against the projection get the hidden
+    /// columns back with their schema defaults.
+    pub fn lens(&self, full: &Schema) -> Lens {
+        let mut tables: Vec<_> = full.iter().collect();
+        tables.sort_by_key(|(name, _)| name.as_str());
+
+        let mut ops = Vec::new();
+        for (name, table) in tables {
+            if table.visibility.is_internal() {
+                ops.push(LensOp::RemoveTable {
+                    table: name.as_str().to_string(),
+                    schema: table.clone(),
+                });
+                continue;
+            }
+            for column in &table.columns.columns {
+                if column.visibility.is_internal() {
+                    ops.push(LensOp::RemoveColumn {
+                        table: name.as_str().to_string(),
+                        column: column.name_str().to_string(),
+                        column_type: column.column_type.clone(),
+                        default: lens_default_for_column(column),
+                    });
+                }
+            }
+        }
+
+        Lens::new(
+            self.full_hash,
+            self.projected_hash,
+            LensTransform::with_ops(ops),
+        )
+    }
+
+    /// Rewrite a row of the full schema for the projection.
+    pub fn project_row(&self, table: &str, data: &[u8]) -> Result<RowProjection, EncodingError> {
+        if self.hides_table(table) {
+            return Ok(RowProjection::Hidden);
+        }
+        let table_name = TableName::new(table);
+        let Some((descriptor, kept)) = self.hidden_columns.get(&table_name) else {
+            return Ok(RowProjection::Unchanged);
+        };
+        // Deletion tombstones carry no row bytes.
+        if data.is_empty() {
+            return Ok(RowProjection::Unchanged);
+        }
+        let Some(projected_table) = self.projected.get(&table_name) else {
+            return Ok(RowProjection::Hidden);
+        };
+
+        let values = decode_row(descriptor, data)?;
+        let kept_values: Vec<_> = kept.iter().map(|&index| values[index].clone()).collect();
+        encode_row(&projected_table.columns, &kept_values).map(RowProjection::Rewritten)
+    }
+}
+
+/// `schema` without its internal tables and columns.
+///
+/// Foreign keys into internal tables are kept as plain UUID columns, and
+/// index overrides only name the columns that remain.
+pub fn project_schema(schema: &Schema) -> Schema {
+    let hidden_tables: HashSet<TableName> = schema
+        .iter()
+        .filter(|(_, table)| table.visibility.is_internal())
+        .map(|(name, _)| *name)
+        .collect();
+
+    schema
+        .iter()
+        .filter(|(name, _)| !hidden_tables.contains(*name))
+        .map(|(name, table)| {
+            let columns: Vec<_> = table
+                .columns
+                .columns
+                .iter()
+                .filter(|column| !column.visibility.is_internal())
+                .cloned()
+                .map(|mut column| {
+                    if column
+                        .references
+                        .is_some_and(|target| hidden_tables.contains(&target))
+                    {
+                        column.references = None;
+                    }
+                    column
+                })
+                .collect();
+            let kept: HashSet<ColumnName> = columns.iter().map(|column| column.name).collect();
+            let indexed_columns = table.indexed_columns.as_ref().map(|indexed| {
+                indexed
+                    .iter()
+                    .filter(|name| {
+                        let column = name.as_str().split("->").next().unwrap_or_default();
+                        kept.contains(&ColumnName::new(column))
+                    })
+                    .copied()
+                    .collect()
+            });
+
+            let projected = TableSchema {
+                columns: RowDescriptor::new(columns),
+                indexed_columns,
+                limits: table.limits,
+                visibility: table.visibility,
+                annotations: table.annotations.clone(),
+                policies: table.policies.clone(),
+            };
+            (*name, projected)
+        })
+        .collect()
+}
+
+/// The lens clients of the projections see in place of `lens`.
+///
+/// Ops that touch an internal table or column on either side are dropped,
+/// and table schemas carried by the remaining ops lose their internal
+/// columns. Returns `None` when both ends project to the same schema, i.e.
+/// the lens only changed things clients can't see.
+pub fn project_lens(
+    lens: &Lens,
+    source: Option<&SchemaProjection>,
+    target: Option<&SchemaProjection>,
+) -> Option<Lens> {
+    let source_hash = source.map_or(lens.source_hash, |source| source.projected_hash);
+    let target_hash = target.map_or(lens.target_hash, |target| target.projected_hash);
+    if source_hash == target_hash {
+        return None;
+    }
+
+    let hides_table = |table: &str| {
+        source.is_some_and(|source| source.hides_table(table))
+            || target.is_some_and(|target| target.hides_table(table))
+    };
+    let hides_column = |table: &str, column: &str| {
+        source.is_some_and(|source| source.hides_column(table, column))
+            || target.is_some_and(|target| target.hides_column(table, column))
+    };
+    let hides_any =
+        |table: &str, columns: &[String]| columns.iter().any(|column| hides_column(table, column));
+
+    let mut forward = LensTransform::new();
+    for (index, op) in lens.forward.ops.iter().enumerate() {
+        let op = match op {
+            LensOp::RenameTable { old_name, new_name } => {
+                if hides_table(old_name) || hides_table(new_name) {
+                    continue;
+                }
+                op.clone()
+            }
+            LensOp::AddColumn { table, column, .. }
+            | LensOp::RemoveColumn { table, column, .. }
+            | LensOp::ChangeColumnType { table, column, .. } => {
+                if hides_column(table, column) {
+                    continue;
+                }
+                op.clone()
+            }
+            LensOp::RenameColumn {
+                table,
+                old_name,
+                new_name,
+            } => {
+                if hides_column(table, old_name) || hides_column(table, new_name) {
+                    continue;
+                }
+                op.clone()
+            }
+            LensOp::SplitColumn {
+                table,
+                column,
+                into,
+                ..
+            } => {
+                if hides_column(table, column) || hides_any(table, into) {
+                    continue;
+                }
+                op.clone()
+            }
+            LensOp::MergeColumns {
+                table,
+                columns,
+                into,
+                ..
+            } => {
+                if hides_any(table, columns) || hides_column(table, into) {
+                    continue;
+                }
+                op.clone()
+            }
+            LensOp::ExtractColumns {
+                table,
+                columns,
+                to_table,
+                fk_column,
+            }
+            | LensOp::InlineColumns {
+                table,
+                columns,
+                from_table: to_table,
+                fk_column,
+            } => {
+                if hides_table(to_table)
+                    || hides_any(table, columns)
+                    || hides_any(to_table, columns)
+                    || hides_column(table, fk_column)
+                {
+                    continue;
+                }
+                op.clone()
+            }
+            LensOp::AddTable { table, schema } => {
+                if hides_table(table) {
+                    continue;
+                }
+                LensOp::AddTable {
+                    table: table.clone(),
+                    schema: project_op_table(table, schema, target),
+                }
+            }
+            LensOp::RemoveTable { table, schema } => {
+                if hides_table(table) {
+                    continue;
+                }
+                LensOp::RemoveTable {
+                    table: table.clone(),
+                    schema: project_op_table(table, schema, source),
+                }
+            }
+        };
+        forward.push(op, lens.forward.draft_ops.contains(&index));
+    }
+
+    let mut projected = Lens::new(source_hash, target_hash, forward);
+    projected.revision = lens.revision;
+    projected.revision_reason = lens.revision_reason.clone();
+    Some(projected)
+}
+
+/// Drop the internal columns of a table schema carried by a lens op.
+///
+/// Lens ops encode bare row descriptors, so visibility comes from the
+/// projection of the schema the table belongs to.
+fn project_op_table(
+    table: &str,
+    schema: &TableSchema,
+    projection: Option<&SchemaProjection>,
+) -> TableSchema {
+    let Some(projection) = projection else {
+        return schema.clone();
+    };
+    let columns = schema
+        .columns
+        .columns
+        .iter()
+        .filter(|column| !projection.hides_column(table, column.name_str()))
+        .cloned()
+        .collect();
+    TableSchema {
+        columns: RowDescriptor::new(columns),
+        ..schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_manager::types::{ColumnType, SchemaBuilder, Value};
+
+    fn full_schema() -> Schema {
+        SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("name", ColumnType::Text)
+                    .internal_column("risk_score", ColumnType::Integer)
+                    .nullable_fk_column("audit_id", "audit_log"),
+            )
+            .table(
+                TableSchema::builder("audit_log")
+                    .column("entry", ColumnType::Text)
+                    .internal(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn projection_drops_internal_tables_and_columns() {
+        let schema = full_schema();
+        let projection = SchemaProjection::of(&schema).expect("schema has internal items");
+
+        let users = projection
+            .projected
+            .get(&TableName::new("users"))
+            .expect("users stays visible");
+        let names: Vec<_> = users
+            .columns
+            .columns
+            .iter()
+            .map(|column| column.name_str())
+            .collect();
+        assert_eq!(names, vec!["name", "audit_id"]);
+        assert_eq!(users.columns.columns[1].references, None);
+        assert!(
+            !projection
+                .projected
+                .contains_key(&TableName::new("audit_log"))
+        );
+        assert_eq!(
+            projection.projected_hash,
+            SchemaHash::compute(&projection.projected)
+        );
+        assert!(SchemaProjection::of(&projection.projected).is_none());
+    }
+
+    #[test]
+    fn projection_rewrites_rows_without_internal_columns() {
+        let schema = full_schema();
+        let projection = SchemaProjection::of(&schema).unwrap();
+        let users = &schema[&TableName::new("users")];
+        let data = encode_row(
+            &users.columns,
+            &[
+                Value::Text("Alice".to_string()),
+                Value::Integer(97),
+                Value::Null,
+            ],
+        )
+        .unwrap();
+
+        let RowProjection::Rewritten(projected) = projection.project_row("users", &data).unwrap()
+        else {
+            panic!("users rows lose their internal column");
+        };
+        let projected_users = &projection.projected[&TableName::new("users")];
+        assert_eq!(
+            decode_row(&projected_users.columns, &projected).unwrap(),
+            vec![Value::Text("Alice".to_string()), Value::Null]
+        );
+        assert_eq!(
+            projection.project_row("audit_log", &data).unwrap(),
+            RowProjection::Hidden
+        );
+    }
+
+    #[test]
+    fn projected_lens_keeps_only_visible_ops() {
+        let v1 = full_schema();
+        let v2 = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("name", ColumnType::Text)
+                    .internal_column("risk_score", ColumnType::Integer)
+                    .internal_column("risk_reason", ColumnType::Text)
+                    .nullable_fk_column("audit_id", "audit_log")
+                    .nullable_column("email", ColumnType::Text),
+            )
+            .table(
+                TableSchema::builder("audit_log")
+                    .column("entry", ColumnType::Text)
+                    .internal(),
+            )
+            .build();
+        let v1_projection = SchemaProjection::of(&v1).unwrap();
+        let v2_projection = SchemaProjection::of(&v2).unwrap();
+        let lens = Lens::new(
+            SchemaHash::compute(&v1),
+            SchemaHash::compute(&v2),
+            LensTransform::with_ops(vec![
+                LensOp::AddColumn {
+                    table: "users".to_string(),
+                    column: "risk_reason".to_string(),
+                    column_type: ColumnType::Text,
+                    default: Value::Text(String::new()),
+                },
+                LensOp::AddColumn {
+                    table: "users".to_string(),
+                    column: "email".to_string(),
+                    column_type: ColumnType::Text,
+                    default: Value::Null,
+                },
+            ]),
+        );
+
+        let projected = project_lens(&lens, Some(&v1_projection), Some(&v2_projection))
+            .expect("email is visible");
+        assert_eq!(projected.source_hash, v1_projection.projected_hash);
+        assert_eq!(projected.target_hash, v2_projection.projected_hash);
+        assert_eq!(projected.forward.ops.len(), 1);
+        assert!(matches!(
+            &projected.forward.ops[0],
+            LensOp::AddColumn { column, .. } if column == "email"
+        ));
+
+        // A version that only adds an internal column projects to v1's
+        // client schema, so clients get no lens for it.
+        let v3 = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("name", ColumnType::Text)
+                    .internal_column("risk_score", ColumnType::Integer)
+                    .internal_column("risk_reason", ColumnType::Text)
+                    .nullable_fk_column("audit_id", "audit_log"),
+            )
+            .table(
+                TableSchema::builder("audit_log")
+                    .column("entry", ColumnType::Text)
+                    .internal(),
+            )
+            .build();
+        let v3_projection = SchemaProjection::of(&v3).unwrap();
+        let hidden_only = Lens::new(
+            SchemaHash::compute(&v1),
+            SchemaHash::compute(&v3),
+            LensTransform::with_ops(vec![lens.forward.ops[0].clone()]),
+        );
+        assert_eq!(v3_projection.projected_hash, v1_projection.projected_hash);
+        assert!(project_lens(&hidden_only, Some(&v1_projection), Some(&v3_projection)).is_none());
+    }
+}
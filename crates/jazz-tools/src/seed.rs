@@ -0,0 +1,429 @@
+//! Seed fixtures - rows for populating a local development server.
+//!
+//! Used by `jazz-tools seed`. Fixtures are validated against a schema and get
+//! deterministic object ids, so seeding the same fixtures again upserts the
+//! same rows instead of duplicating them.
+//!
+//! A JSON fixture maps table names to arrays of rows:
+//!
+//! ```json
+//! {
+//!   "users": [{ "$id": "alice", "name": "Alice" }],
+//!   "todos": [{ "$id": "milk", "title": "Buy milk", "owner": { "$ref": "alice" } }]
+//! }
+//! ```
+//!
+//! An NDJSON fixture holds one row per line and names its table in `$table`.
+//!
+//! Every row needs an `$id`: a symbolic key, unique within its table, that its
+//! object id is derived from. Keys don't depend on where a row sits in a file,
+//! so adding, removing or reordering rows doesn't change which row re-seeding
+//! updates.
+//!
+//! `{"$ref": "alice"}` stands for the object id of the row keyed `alice` in the
+//! table the column references; for UUID columns without a declared reference,
+//! write `{"$ref": "users/alice"}`.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value as JsonValue};
+use uuid::Uuid;
+
+use crate::object::ObjectId;
+use crate::query_manager::types::{
+    ColumnDescriptor, ColumnType, Date, Decimal, GeoPoint, Interval, Schema, TableName, Time,
+    Value, Vector,
+};
+use crate::schema_manager::AppId;
+
+const ID_KEY: &str = "$id";
+const TABLE_KEY: &str = "$table";
+const REF_KEY: &str = "$ref";
+
+/// A fixture row as read from a file, before validation.
+#[derive(Debug, Clone)]
+struct FixtureRow {
+    /// Where the row came from, for error messages.
+    source: String,
+    table: String,
+    key: String,
+    values: Map<String, JsonValue>,
+}
+
+/// Fixture rows from one or more files, in load order.
+#[derive(Debug, Default)]
+pub struct Fixtures {
+    rows: Vec<FixtureRow>,
+}
+
+/// A validated fixture row, ready to upsert.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeedRow {
+    pub table: String,
+    pub object_id: ObjectId,
+    pub values: HashMap<String, Value>,
+}
+
+impl Fixtures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of rows loaded so far.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Add the rows of a JSON fixture keyed by table.
+    pub fn add_json(&mut self, source: &str, text: &str) -> Result<(), String> {
+        let tables: Map<String, JsonValue> =
+            serde_json::from_str(text).map_err(|err| format!("{source}: invalid JSON: {err}"))?;
+        for (table, rows) in tables {
+            let JsonValue::Array(rows) = rows else {
+                return Err(format!(
+                    "{source}: rows of table '{table}' must be an array"
+                ));
+            };
+            for (index, row) in rows.into_iter().enumerate() {
+                let source = format!("{source}: {table}[{index}]");
+                let JsonValue::Object(mut values) = row else {
+                    return Err(format!("{source}: row must be an object"));
+                };
+                if let Some(row_table) = values.remove(TABLE_KEY)
+                    && row_table.as_str() != Some(table.as_str())
+                {
+                    return Err(format!("{source}: {TABLE_KEY} does not match '{table}'"));
+                }
+                let key = take_key(&source, &mut values)?;
+                self.rows.push(FixtureRow {
+                    source,
+                    table: table.clone(),
+                    key,
+                    values,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the rows of an NDJSON fixture, one row per line.
+    pub fn add_ndjson(&mut self, source: &str, text: &str) -> Result<(), String> {
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let source = format!("{source}:{}", index + 1);
+            let mut values: Map<String, JsonValue> = serde_json::from_str(line)
+                .map_err(|err| format!("{source}: invalid JSON row: {err}"))?;
+            let table = match values.remove(TABLE_KEY) {
+                Some(JsonValue::String(table)) => table,
+                _ => return Err(format!("{source}: row needs a string {TABLE_KEY}")),
+            };
+            let key = take_key(&source, &mut values)?;
+            self.rows.push(FixtureRow {
+                source,
+                table,
+                key,
+                values,
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate every row against `schema` and resolve its references.
+    ///
+    /// Rows come back in load order, with object ids derived from the app,
+    /// the table and the row key.
+    pub fn plan(&self, app_id: AppId, schema: &Schema) -> Result<Vec<SeedRow>, String> {
+        let mut ids = HashMap::new();
+        let mut keyed = Vec::with_capacity(self.rows.len());
+        for row in &self.rows {
+            if !schema.contains_key(&TableName::new(row.table.as_str())) {
+                return Err(format!("{}: unknown table '{}'", row.source, row.table));
+            }
+            let object_id = seed_object_id(app_id, &row.table, &row.key);
+            if ids
+                .insert((row.table.clone(), row.key.clone()), object_id)
+                .is_some()
+            {
+                return Err(format!(
+                    "{}: duplicate key '{}' in table '{}'",
+                    row.source, row.key, row.table
+                ));
+            }
+            keyed.push(object_id);
+        }
+
+        self.rows
+            .iter()
+            .zip(keyed)
+            .map(|(row, object_id)| {
+                let table = &schema[&TableName::new(row.table.as_str())];
+                let values = row_values(row, &table.columns.columns, &ids)?;
+                Ok(SeedRow {
+                    table: row.table.clone(),
+                    object_id,
+                    values,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Object id of the fixture row keyed `key` in `table`.
+pub fn seed_object_id(app_id: AppId, table: &str, key: &str) -> ObjectId {
+    ObjectId::from_uuid(Uuid::new_v5(
+        &Uuid::NAMESPACE_DNS,
+        format!("jazz-seed:{}:{table}:{key}", app_id.uuid()).as_bytes(),
+    ))
+}
+
+fn take_key(source: &str, values: &mut Map<String, JsonValue>) -> Result<String, String> {
+    match values.remove(ID_KEY) {
+        Some(JsonValue::String(key)) if !key.is_empty() => Ok(key),
+        Some(_) => Err(format!("{source}: {ID_KEY} must be a non-empty string")),
+        None => Err(format!(
+            "{source}: row needs an {ID_KEY} so seeding it again updates it instead of adding a copy"
+        )),
+    }
+}
+
+type RowIds = HashMap<(String, String), ObjectId>;
+
+fn row_values(
+    row: &FixtureRow,
+    columns: &[ColumnDescriptor],
+    ids: &RowIds,
+) -> Result<HashMap<String, Value>, String> {
+    if let Some(name) = row
+        .values
+        .keys()
+        .find(|name| !columns.iter().any(|column| column.name.as_str() == *name))
+    {
+        return Err(format!(
+            "{}: unknown column '{name}' in table '{}'",
+            row.source, row.table
+        ));
+    }
+
+    let mut values = HashMap::with_capacity(row.values.len());
+    for column in columns {
+        let name = column.name.as_str();
+        let value = match row.values.get(name) {
+            None if column.nullable || column.default.is_some() => continue,
+            None => return Err(format!("{}: missing column '{name}'", row.source)),
+            Some(JsonValue::Null) if column.nullable => Value::Null,
+            Some(JsonValue::Null) => {
+                return Err(format!("{}: column '{name}' is not nullable", row.source));
+            }
+            Some(json) => fixture_value(json, &column.column_type, column.references, ids)
+                .map_err(|message| format!("{}: column '{name}': {message}", row.source))?,
+        };
+        values.insert(name.to_string(), value);
+    }
+    Ok(values)
+}
+
+fn fixture_value(
+    json: &JsonValue,
+    column_type: &ColumnType,
+    references: Option<TableName>,
+    ids: &RowIds,
+) -> Result<Value, String> {
+    if *column_type == ColumnType::Uuid
+        && let Some(reference) = json.get(REF_KEY)
+    {
+        let reference = reference
+            .as_str()
+            .ok_or_else(|| format!("{REF_KEY} must be a string"))?;
+        let (table, key) = match references {
+            Some(table) => (table.as_str().to_string(), reference),
+            None => reference
+                .split_once('/')
+                .map(|(table, key)| (table.to_string(), key))
+                .ok_or_else(|| {
+                    format!("column has no declared reference; write {REF_KEY} as 'table/key'")
+                })?,
+        };
+        return ids
+            .get(&(table.clone(), key.to_string()))
+            .map(|object_id| Value::Uuid(*object_id))
+            .ok_or_else(|| format!("no fixture row keyed '{key}' in table '{table}'"));
+    }
+
+    let mismatch = || format!("expected a {column_type:?} value, got {json}");
+    let value = match column_type {
+        ColumnType::Integer => json
+            .as_i64()
+            .and_then(|n| i32::try_from(n).ok())
+            .map(Value::Integer),
+        ColumnType::BigInt => json.as_i64().map(Value::BigInt),
+        ColumnType::Double => json.as_f64().map(Value::Double),
+        ColumnType::Boolean => json.as_bool().map(Value::Boolean),
+        ColumnType::Text => json.as_str().map(|text| Value::Text(text.to_string())),
+        ColumnType::Enum { variants } => json
+            .as_str()
+            .filter(|variant| variants.iter().any(|known| known == variant))
+            .map(|variant| Value::Text(variant.to_string())),
+        // JSON columns store their payload as text.
+        ColumnType::Json { .. } => Some(Value::Text(json.to_string())),
+        ColumnType::Timestamp => json.as_u64().map(Value::Timestamp),
+        ColumnType::Date => parse_json::<Date>(json).map(Value::Date),
+        ColumnType::Time => parse_json::<Time>(json).map(Value::Time),
+        ColumnType::Interval => parse_json::<Interval>(json).map(Value::Interval),
+        ColumnType::Decimal { .. } => parse_json::<Decimal>(json).map(Value::Decimal),
+        ColumnType::GeoPoint => parse_json::<GeoPoint>(json).map(Value::GeoPoint),
+        ColumnType::Vector { .. } => parse_json::<Vector>(json).map(Value::Vector),
+        ColumnType::Uuid => json
+            .as_str()
+            .and_then(|text| Uuid::parse_str(text).ok())
+            .map(|uuid| Value::Uuid(ObjectId::from_uuid(uuid))),
+        ColumnType::Bytea => parse_json::<Vec<u8>>(json).map(Value::Bytea),
+        ColumnType::Array { element } => {
+            let JsonValue::Array(items) = json else {
+                return Err(mismatch());
+            };
+            let items = items
+                .iter()
+                .map(|item| match item {
+                    JsonValue::Null => Ok(Value::Null),
+                    item => fixture_value(item, element, references, ids),
+                })
+                .collect::<Result<_, _>>()?;
+            Some(Value::Array(items))
+        }
+        ColumnType::BatchId | ColumnType::Row { .. } => {
+            return Err(format!("{column_type:?} columns can't be seeded"));
+        }
+    };
+    value.ok_or_else(mismatch)
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(json: &JsonValue) -> Option<T> {
+    serde_json::from_value(json.clone()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_manager::types::{SchemaBuilder, TableSchema};
+
+    fn todo_schema() -> Schema {
+        SchemaBuilder::new()
+            .table(TableSchema::builder("users").column("name", ColumnType::Text))
+            .table(
+                TableSchema::builder("todos")
+                    .column("title", ColumnType::Text)
+                    .nullable_column("done", ColumnType::Boolean)
+                    .fk_column("owner", "users"),
+            )
+            .build()
+    }
+
+    #[test]
+    fn json_and_ndjson_fixtures_resolve_references_to_seeded_ids() {
+        let app_id = AppId::from_name("seed-test");
+        let mut fixtures = Fixtures::new();
+        fixtures
+            .add_json(
+                "users.json",
+                r#"{"users": [{"$id": "alice", "name": "Alice"}, {"$id": "bob", "name": "Bob"}]}"#,
+            )
+            .unwrap();
+        fixtures
+            .add_ndjson(
+                "todos.ndjson",
+                "{\"$table\": \"todos\", \"$id\": \"milk\", \"title\": \"Buy milk\", \"owner\": {\"$ref\": \"alice\"}}\n\n\
+                 {\"$table\": \"todos\", \"$id\": \"walk\", \"title\": \"Walk\", \"done\": true, \"owner\": {\"$ref\": \"bob\"}}\n",
+            )
+            .unwrap();
+
+        let rows = fixtures.plan(app_id, &todo_schema()).unwrap();
+
+        let alice = seed_object_id(app_id, "users", "alice");
+        let bob = seed_object_id(app_id, "users", "bob");
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0].object_id, alice);
+        assert_eq!(rows[1].object_id, bob);
+        assert_eq!(rows[2].object_id, seed_object_id(app_id, "todos", "milk"));
+        assert_eq!(rows[2].values["owner"], Value::Uuid(alice));
+        assert!(!rows[2].values.contains_key("done"));
+        assert_eq!(rows[3].values["owner"], Value::Uuid(bob));
+        assert_eq!(rows[3].values["done"], Value::Boolean(true));
+
+        // Planning the same fixtures again yields the same ids.
+        assert_eq!(fixtures.plan(app_id, &todo_schema()).unwrap(), rows);
+    }
+
+    #[test]
+    fn fixtures_that_do_not_match_the_schema_are_rejected() {
+        let app_id = AppId::from_name("seed-test");
+        let cases = [
+            (
+                r#"{"projects": [{"$id": "x", "name": "x"}]}"#,
+                "unknown table 'projects'",
+            ),
+            (
+                r#"{"users": [{"$id": "alice", "name": "Alice", "email": "a@example.com"}]}"#,
+                "unknown column 'email'",
+            ),
+            (
+                r#"{"users": [{"$id": "seven", "name": 7}]}"#,
+                "column 'name'",
+            ),
+            (
+                r#"{"todos": [{"$id": "walk", "title": "Walk", "owner": {"$ref": "nobody"}}]}"#,
+                "no fixture row keyed 'nobody' in table 'users'",
+            ),
+            (
+                r#"{"todos": [{"$id": "walk", "title": "Walk"}]}"#,
+                "missing column 'owner'",
+            ),
+            (
+                r#"{"users": [{"$id": "a", "name": "A"}, {"$id": "a", "name": "B"}]}"#,
+                "duplicate key 'a'",
+            ),
+        ];
+
+        for (fixture, expected) in cases {
+            let mut fixtures = Fixtures::new();
+            fixtures.add_json("fixture.json", fixture).unwrap();
+            let error = fixtures
+                .plan(app_id, &todo_schema())
+                .expect_err("fixture should be rejected");
+            assert!(
+                error.contains(expected),
+                "expected '{expected}' in '{error}'"
+            );
+        }
+    }
+
+    #[test]
+    fn rows_without_an_id_are_refused() {
+        let mut fixtures = Fixtures::new();
+        let error = fixtures
+            .add_json("users.json", r#"{"users": [{"name": "Alice"}]}"#)
+            .expect_err("keyless JSON row should be refused");
+        assert!(
+            error.contains("users.json: users[0]: row needs an $id"),
+            "{error}"
+        );
+
+        let error = fixtures
+            .add_ndjson(
+                "todos.ndjson",
+                "{\"$table\": \"users\", \"$id\": 3, \"name\": \"Bob\"}",
+            )
+            .expect_err("non-string key should be refused");
+        assert!(
+            error.contains("todos.ndjson:1: $id must be a non-empty string"),
+            "{error}"
+        );
+        assert!(fixtures.is_empty());
+    }
+}
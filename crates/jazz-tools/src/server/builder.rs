@@ -15,12 +15,16 @@ use crate::query_manager::types::Schema;
 use crate::routes;
 use crate::runtime_tokio::TokioRuntime;
 use crate::schema_manager::{AppId, SchemaManager, rehydrate_schema_manager_from_catalogue};
-use crate::server::{ConnectionEventHub, DynStorage, ServerState, ServerTopology};
+use crate::server::{
+    ConnectionEventHub, DynStorage, RateLimitConfig, RateLimiter, ServerState, ServerTopology,
+    WebhookConfig, WebhookDispatcher,
+};
 #[cfg(feature = "rocksdb")]
 use crate::storage::RocksDBStorage;
 #[cfg(feature = "sqlite")]
 use crate::storage::SqliteStorage;
 use crate::storage::{MemoryStorage, Storage};
+use crate::sync_manager::timestamp_skew::TimestampSkewPolicy;
 use crate::sync_manager::{Destination, DurabilityTier, SyncManager};
 use crate::transport_manager::TransportRetryConfig;
 
@@ -71,6 +75,9 @@ pub struct ServerBuilder {
     sync_tracer: Option<crate::sync_tracer::SyncTracer>,
     upstream_url: Option<String>,
     shutdown_timeout: Duration,
+    rate_limits: RateLimitConfig,
+    timestamp_skew_policy: Option<TimestampSkewPolicy>,
+    webhooks: WebhookConfig,
 }
 
 impl ServerBuilder {
@@ -88,6 +95,9 @@ impl ServerBuilder {
             sync_tracer: None,
             upstream_url: None,
             shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            rate_limits: RateLimitConfig::default(),
+            timestamp_skew_policy: None,
+            webhooks: WebhookConfig::default(),
         }
     }
 
@@ -116,6 +126,23 @@ impl ServerBuilder {
         self
     }
 
+    pub fn with_rate_limits(mut self, rate_limits: RateLimitConfig) -> Self {
+        self.rate_limits = rate_limits;
+        self
+    }
+
+    /// Bound how far client row timestamps may drift from the server clock.
+    pub fn with_timestamp_skew_policy(mut self, policy: TimestampSkewPolicy) -> Self {
+        self.timestamp_skew_policy = Some(policy);
+        self
+    }
+
+    /// Deliver batches settled on this (core) server to HTTP webhooks.
+    pub fn with_webhooks(mut self, webhooks: WebhookConfig) -> Self {
+        self.webhooks = webhooks;
+        self
+    }
+
     pub fn with_storage(mut self, backend: StorageBackend) -> Self {
         self.storage_backend = backend;
         self
@@ -143,6 +170,17 @@ impl ServerBuilder {
             None => None,
         };
         validate_server_config(&auth_config, topology)?;
+        if topology.is_edge() && !self.webhooks.is_empty() {
+            return Err(
+                "webhooks are delivered by the core server; remove --webhooks / JAZZ_WEBHOOKS when --upstream-url / JAZZ_UPSTREAM_URL is set"
+                    .to_string(),
+            );
+        }
+        let webhooks = if self.webhooks.is_empty() {
+            None
+        } else {
+            Some(Arc::new(WebhookDispatcher::new(self.webhooks.clone())?))
+        };
         let jwt_verifier = build_jwt_verifier(&auth_config).await?;
         log_auth_config(&auth_config, topology);
 
@@ -169,7 +207,10 @@ impl ServerBuilder {
             client_ttl: RwLock::new(Duration::from_secs(300)),
             sync_tracer: self.sync_tracer.clone(),
             shutdown: crate::server::ShutdownController::new(self.shutdown_timeout),
+            rate_limiter: RateLimiter::new(self.rate_limits),
+            webhooks,
         });
+        super::webhooks::start_webhook_delivery(&state)?;
 
         // Spawn periodic client state sweep (uses Weak so the task exits
         // when all strong refs to ServerState are dropped, e.g. in tests).
@@ -215,7 +256,11 @@ impl ServerBuilder {
     }
 
     fn build_schema_manager(&self, storage: &dyn Storage) -> Result<SchemaManager, String> {
-        let sync_manager = server_sync_manager(self.local_durability_tier());
+        let sync_manager = server_sync_manager(
+            self.local_durability_tier(),
+            self.timestamp_skew_policy,
+            !self.webhooks.is_empty(),
+        );
 
         match &self.schema_mode {
             ServerSchemaMode::Dynamic => {
@@ -299,8 +344,18 @@ impl ServerBuilder {
     }
 }
 
-fn server_sync_manager(local_tier: DurabilityTier) -> SyncManager {
-    let sync_manager = SyncManager::new().with_durability_tier(local_tier);
+fn server_sync_manager(
+    local_tier: DurabilityTier,
+    timestamp_skew_policy: Option<TimestampSkewPolicy>,
+    change_capture: bool,
+) -> SyncManager {
+    let mut sync_manager = SyncManager::new().with_durability_tier(local_tier);
+    if let Some(policy) = timestamp_skew_policy {
+        sync_manager = sync_manager.with_timestamp_skew_policy(policy);
+    }
+    if change_capture {
+        sync_manager = sync_manager.with_change_capture();
+    }
 
     if should_allow_unprivileged_schema_catalogue_writes() {
         sync_manager.with_unprivileged_schema_catalogue_writes()
@@ -12,21 +12,32 @@ use tokio::time::Instant;
 
 use crate::middleware::AuthConfig;
 use crate::middleware::auth::JwtVerifier;
+use crate::query_manager::types::SchemaHash;
 use crate::runtime_tokio::TokioRuntime;
 use crate::schema_manager::AppId;
 use crate::storage::Storage;
 use crate::sync_manager::{ClientId, InboxEntry, Source, SyncPayload};
 
 mod builder;
+mod rate_limit;
 pub mod routes;
 mod shutdown;
 #[cfg(feature = "test-utils")]
 mod testing;
+mod webhooks;
 
 pub use builder::{BuiltServer, ServerBuilder, StorageBackend};
+pub use rate_limit::{
+    RATE_LIMITED_CODE, RateLimitConfig, RateLimiter, ScopeLimits, TokenBucketConfig,
+};
 pub use shutdown::{ShutdownController, ShutdownPhase};
 #[cfg(feature = "test-utils")]
 pub use testing::{JazzServer, JazzServerBuilder, ServerDataDir, TestJwtIssuer, TestJwtOptions};
+pub use webhooks::{
+    WEBHOOK_DELIVERY_HEADER, WEBHOOK_NAME_HEADER, WEBHOOK_SIGNATURE_HEADER, WebhookAdminError,
+    WebhookConfig, WebhookDeliveryStatus, WebhookDispatcher, WebhookEndpoint, WebhookStatusView,
+    webhook_signature,
+};
 
 pub type DynStorage = Box<dyn Storage + Send>;
 
@@ -427,11 +438,17 @@ pub struct ServerState {
     /// Optional sync message tracer for test observability.
     pub sync_tracer: Option<crate::sync_tracer::SyncTracer>,
     pub shutdown: ShutdownController,
+    /// Token-bucket limits on inbound client sync payloads.
+    pub rate_limiter: RateLimiter,
+    /// Delivery of the change log to configured webhooks (core servers only).
+    pub webhooks: Option<Arc<WebhookDispatcher>>,
 }
 
 /// State for a single SSE connection.
 pub struct ConnectionState {
     pub client_id: ClientId,
+    /// Schema the client declared in its handshake, if any.
+    pub declared_schema_hash: Option<SchemaHash>,
 }
 
 impl ServerState {
@@ -592,6 +609,7 @@ impl ServerState {
             }
             match self.runtime.remove_client(client_id) {
                 Ok(true) => {
+                    self.rate_limiter.forget_client(client_id);
                     reaped.push(client_id);
                     tracing::debug!(%client_id, "reaped disconnected client");
                 }
@@ -605,6 +623,8 @@ impl ServerState {
             }
         }
 
+        self.rate_limiter.prune_idle_principals();
+
         // Re-insert clients that couldn't be reaped yet
         if !requeued.is_empty() {
             let mut candidates = self.disconnect_candidates.write().await;
@@ -628,7 +648,8 @@ impl ServerState {
     ///
     /// Frames are expected to be post-handshake postcard payloads: either an
     /// `OutboxEntry` for a single message or a `SyncBatchRequest` for batched
-    /// messages.
+    /// messages. Payloads are metered by [`RateLimiter`] first; replies for
+    /// throttled payloads go straight back to the sending client.
     pub async fn process_ws_client_frame(
         &self,
         client_id: ClientId,
@@ -638,32 +659,40 @@ impl ServerState {
             return Err("server is shutting down".to_string());
         }
 
-        if let Ok(payload) = crate::transport_protocol::decode_outbox_entry_payload(payload) {
-            let inbox = InboxEntry {
-                source: Source::Client(client_id),
-                payload,
+        let (payloads, batched) =
+            match crate::transport_protocol::decode_outbox_entry_payload(payload) {
+                Ok(payload) => (vec![payload], false),
+                Err(_) => {
+                    match crate::transport_protocol::SyncBatchRequest::decode_payload(payload) {
+                        Ok(batch) => (batch.payloads, true),
+                        Err(e) => return Err(format!("invalid ws payload: {e}")),
+                    }
+                }
             };
-            return self
-                .runtime
-                .push_sync_inbox(inbox)
-                .map_err(|e| e.to_string());
+
+        let admission = self.rate_limiter.admit(client_id, payloads);
+        for reply in admission.replies {
+            self.connection_event_hub.dispatch_payload(client_id, reply);
         }
 
-        match crate::transport_protocol::SyncBatchRequest::decode_payload(payload) {
-            Ok(batch) => {
-                let entries = batch
-                    .payloads
-                    .into_iter()
-                    .map(|payload| InboxEntry {
-                        source: Source::Client(client_id),
-                        payload,
-                    })
-                    .collect();
-                self.runtime
-                    .push_sync_inbox_batch(entries)
-                    .map_err(|e| e.to_string())
-            }
-            Err(e) => Err(format!("invalid ws payload: {e}")),
+        let mut entries = admission
+            .admitted
+            .into_iter()
+            .map(|payload| InboxEntry {
+                source: Source::Client(client_id),
+                payload,
+            })
+            .collect::<Vec<_>>();
+        match (batched, entries.len()) {
+            (_, 0) => Ok(()),
+            (false, 1) => self
+                .runtime
+                .push_sync_inbox(entries.pop().expect("single entry is present"))
+                .map_err(|e| e.to_string()),
+            _ => self
+                .runtime
+                .push_sync_inbox_batch(entries)
+                .map_err(|e| e.to_string()),
         }
     }
 }
@@ -746,6 +775,8 @@ mod tests {
             client_ttl: RwLock::new(Duration::from_secs(300)),
             sync_tracer: None,
             shutdown: ShutdownController::new(timeout),
+            rate_limiter: RateLimiter::default(),
+            webhooks: None,
         })
     }
 
@@ -754,11 +785,13 @@ mod tests {
         let connection_id = state
             .next_connection_id
             .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        state
-            .connections
-            .write()
-            .await
-            .insert(connection_id, ConnectionState { client_id });
+        state.connections.write().await.insert(
+            connection_id,
+            ConnectionState {
+                client_id,
+                declared_schema_hash: None,
+            },
+        );
         connection_id
     }
 
@@ -842,6 +875,63 @@ mod tests {
         assert_eq!(error, "server is shutting down");
     }
 
+    #[tokio::test]
+    async fn websocket_frames_over_rate_limit_are_rejected_back_to_the_client() {
+        let state = ServerBuilder::new(AppId::from_name("rate-limit-test"))
+            .with_storage(StorageBackend::InMemory)
+            .with_rate_limits(RateLimitConfig {
+                per_client: ScopeLimits {
+                    rows: Some(TokenBucketConfig::new(0, 0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .build()
+            .await
+            .expect("build rate limited server")
+            .state;
+        let client_id = ClientId::new();
+        state.rate_limiter.register_client(client_id, "alice");
+        let mut registration = state.connection_event_hub.register_connection(1, client_id);
+        let row = crate::row_histories::StoredRowBatch::new(
+            crate::object::ObjectId::new(),
+            "main",
+            Vec::<crate::row_histories::BatchId>::new(),
+            b"alice".to_vec(),
+            crate::metadata::RowProvenance::for_insert("alice".to_string(), 1_000),
+            Default::default(),
+            crate::row_histories::RowState::StagingPending,
+            None,
+        );
+        let batch_id = row.batch_id;
+        let frame = crate::transport_protocol::SyncBatchRequest {
+            payloads: vec![SyncPayload::RowBatchCreated {
+                metadata: None,
+                row,
+            }],
+            client_id,
+        }
+        .encode_payload()
+        .expect("encode frame");
+
+        state
+            .process_ws_client_frame(client_id, &frame)
+            .await
+            .expect("throttled frame is not a transport error");
+
+        let update = registration.receiver.try_recv().expect("rejection reply");
+        assert_eq!(
+            update.payload,
+            SyncPayload::BatchFate {
+                fate: crate::batch_fate::BatchFate::Rejected {
+                    batch_id,
+                    code: RATE_LIMITED_CODE.to_string(),
+                    reason: "client rate limit exceeded for rows".to_string(),
+                },
+            }
+        );
+    }
+
     #[tokio::test]
     async fn disconnect_does_not_add_candidate_when_other_connections_exist() {
         let state = build_test_state().await;
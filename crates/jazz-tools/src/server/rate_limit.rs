@@ -0,0 +1,840 @@
+//! Token-bucket rate limits on inbound client sync traffic.
+//!
+//! Limits are checked in [`ServerState::process_ws_client_frame`] before any
+//! payload reaches the runtime inbox. Every metered payload is charged against
+//! three scopes — the sending client, the session principal (`user_id`) behind
+//! it, and the whole app — and is admitted only when all three can afford it.
+//!
+//! What happens to a throttled payload follows the durability contract from
+//! `specs/todo/ideas/1_mvp/durability-guarantees-and-rate-limits.md`:
+//!
+//! - Throttled row batches, direct and transactional alike, are answered with
+//!   a `BatchFate::Rejected` carrying [`RATE_LIMITED_CODE`]. The server can't
+//!   tell whether a direct write is awaited with `.wait({tier})`, and a waiter
+//!   must not hang on a batch that was never admitted. Writers that don't
+//!   wait only see the rejection in their local batch record.
+//! - Query subscriptions are answered with `QuerySubscriptionRejected`.
+//!
+//! The rows of a batch that arrive in one frame are charged together, so they
+//! are admitted or throttled as a whole. Once a batch is throttled, every
+//! later payload for the same batch (sibling rows, the `SealBatch`) is dropped
+//! too, so the authority never sees a partial batch. The batch is forgotten when its seal arrives, since every
+//! batch ends with one. Backend and admin connections are trusted and never
+//! metered.
+//!
+//! [`ServerState::process_ws_client_frame`]: super::ServerState::process_ws_client_frame
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+use crate::batch_fate::BatchFate;
+use crate::row_histories::BatchId;
+use crate::sync_manager::{ClientId, SyncError, SyncPayload};
+
+/// `BatchFate::Rejected` / `QuerySubscriptionRejected` code for throttled payloads.
+pub const RATE_LIMITED_CODE: &str = "rate_limited";
+
+/// Throttled batches remembered per client while waiting for their seals.
+/// Seals that never arrive (a client that crashed mid-batch but kept its
+/// connection) must not grow the set without bound.
+const MAX_THROTTLED_BATCHES: usize = 1024;
+
+/// One token bucket: `capacity` tokens, refilled continuously at `refill_per_sec`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenBucketConfig {
+    pub capacity: u64,
+    pub refill_per_sec: u64,
+}
+
+impl TokenBucketConfig {
+    pub fn new(capacity: u64, refill_per_sec: u64) -> Self {
+        Self {
+            capacity,
+            refill_per_sec,
+        }
+    }
+}
+
+/// Buckets applied to one scope. `None` leaves that resource unmetered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScopeLimits {
+    /// Inbound `RowBatchCreated` row payload bytes.
+    pub row_bytes: Option<TokenBucketConfig>,
+    /// Inbound `RowBatchCreated` payloads.
+    pub rows: Option<TokenBucketConfig>,
+    /// Inbound `QuerySubscription` payloads.
+    pub query_subscriptions: Option<TokenBucketConfig>,
+}
+
+impl ScopeLimits {
+    fn is_unlimited(&self) -> bool {
+        self.row_bytes.is_none() && self.rows.is_none() && self.query_subscriptions.is_none()
+    }
+}
+
+/// Server-wide rate limit configuration. The default limits nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    pub per_client: ScopeLimits,
+    pub per_principal: ScopeLimits,
+    pub per_app: ScopeLimits,
+}
+
+impl RateLimitConfig {
+    pub fn is_unlimited(&self) -> bool {
+        self.per_client.is_unlimited()
+            && self.per_principal.is_unlimited()
+            && self.per_app.is_unlimited()
+    }
+}
+
+/// Which scope ran out of tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RateLimitScope {
+    Client,
+    Principal,
+    App,
+}
+
+impl RateLimitScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Client => "client",
+            Self::Principal => "principal",
+            Self::App => "app",
+        }
+    }
+}
+
+/// Which metered resource ran out of tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RateLimitResource {
+    RowBytes,
+    Rows,
+    QuerySubscriptions,
+}
+
+impl RateLimitResource {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::RowBytes => "row bytes",
+            Self::Rows => "rows",
+            Self::QuerySubscriptions => "query subscriptions",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RateLimitExceeded {
+    scope: RateLimitScope,
+    resource: RateLimitResource,
+}
+
+impl RateLimitExceeded {
+    fn reason(&self) -> String {
+        format!(
+            "{} rate limit exceeded for {}",
+            self.scope.as_str(),
+            self.resource.as_str()
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    config: TokenBucketConfig,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(config: TokenBucketConfig, now: Instant) -> Self {
+        Self {
+            config,
+            tokens: config.capacity as f64,
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.config.refill_per_sec as f64)
+            .min(self.config.capacity as f64);
+        self.updated_at = now;
+    }
+
+    fn can_afford(&mut self, cost: u64, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= cost as f64
+    }
+
+    fn debit(&mut self, cost: u64) {
+        self.tokens -= cost as f64;
+    }
+
+    fn is_full(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= self.config.capacity as f64
+    }
+}
+
+#[derive(Debug, Default)]
+struct ScopeBuckets {
+    row_bytes: Option<TokenBucket>,
+    rows: Option<TokenBucket>,
+    query_subscriptions: Option<TokenBucket>,
+}
+
+impl ScopeBuckets {
+    fn new(limits: &ScopeLimits, now: Instant) -> Self {
+        Self {
+            row_bytes: limits.row_bytes.map(|config| TokenBucket::new(config, now)),
+            rows: limits.rows.map(|config| TokenBucket::new(config, now)),
+            query_subscriptions: limits
+                .query_subscriptions
+                .map(|config| TokenBucket::new(config, now)),
+        }
+    }
+
+    fn charges<'a>(
+        &'a mut self,
+        cost: &Cost,
+    ) -> impl Iterator<Item = (RateLimitResource, &'a mut TokenBucket, u64)> {
+        [
+            (
+                RateLimitResource::RowBytes,
+                self.row_bytes.as_mut(),
+                cost.row_bytes,
+            ),
+            (RateLimitResource::Rows, self.rows.as_mut(), cost.rows),
+            (
+                RateLimitResource::QuerySubscriptions,
+                self.query_subscriptions.as_mut(),
+                cost.query_subscriptions,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(resource, bucket, amount)| match bucket {
+            Some(bucket) if amount > 0 => Some((resource, bucket, amount)),
+            _ => None,
+        })
+    }
+
+    fn check(&mut self, cost: &Cost, now: Instant) -> Result<(), RateLimitResource> {
+        for (resource, bucket, amount) in self.charges(cost) {
+            if !bucket.can_afford(amount, now) {
+                return Err(resource);
+            }
+        }
+        Ok(())
+    }
+
+    fn debit(&mut self, cost: &Cost) {
+        for (_, bucket, amount) in self.charges(cost) {
+            bucket.debit(amount);
+        }
+    }
+
+    fn is_idle(&mut self, now: Instant) -> bool {
+        [
+            self.row_bytes.as_mut(),
+            self.rows.as_mut(),
+            self.query_subscriptions.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        .all(|bucket| bucket.is_full(now))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Cost {
+    row_bytes: u64,
+    rows: u64,
+    query_subscriptions: u64,
+}
+
+impl Cost {
+    fn of(payload: &SyncPayload) -> Self {
+        match payload {
+            SyncPayload::RowBatchCreated { row, .. } => Self {
+                row_bytes: row.data.len() as u64,
+                rows: 1,
+                query_subscriptions: 0,
+            },
+            SyncPayload::QuerySubscription { .. } => Self {
+                query_subscriptions: 1,
+                ..Self::default()
+            },
+            _ => Self::default(),
+        }
+    }
+
+    fn add(&mut self, other: &Cost) {
+        self.row_bytes += other.row_bytes;
+        self.rows += other.rows;
+        self.query_subscriptions += other.query_subscriptions;
+    }
+
+    fn is_free(&self) -> bool {
+        self.row_bytes == 0 && self.rows == 0 && self.query_subscriptions == 0
+    }
+}
+
+#[derive(Debug)]
+struct MeteredClient {
+    principal: String,
+    buckets: ScopeBuckets,
+    /// Batches already throttled for this client; later members and seals
+    /// for them are dropped so the authority never sees a partial batch.
+    throttled_batches: ThrottledBatches,
+}
+
+/// Throttled batch ids in throttling order, capped at [`MAX_THROTTLED_BATCHES`].
+#[derive(Debug, Default)]
+struct ThrottledBatches {
+    ids: HashSet<BatchId>,
+    order: VecDeque<BatchId>,
+}
+
+impl ThrottledBatches {
+    fn contains(&self, batch_id: &BatchId) -> bool {
+        self.ids.contains(batch_id)
+    }
+
+    fn insert(&mut self, batch_id: BatchId) {
+        if !self.ids.insert(batch_id) {
+            return;
+        }
+        self.order.push_back(batch_id);
+        while self.order.len() > MAX_THROTTLED_BATCHES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+    }
+
+    fn remove(&mut self, batch_id: &BatchId) {
+        if self.ids.remove(batch_id) {
+            self.order.retain(|id| id != batch_id);
+        }
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.ids.len()
+    }
+}
+
+#[derive(Debug)]
+struct RateLimiterState {
+    clients: HashMap<ClientId, MeteredClient>,
+    principals: HashMap<String, ScopeBuckets>,
+    app: ScopeBuckets,
+}
+
+/// Payloads that passed admission, plus replies owed to the sender for the
+/// ones that did not.
+#[derive(Debug, Default)]
+pub(crate) struct Admission {
+    pub admitted: Vec<SyncPayload>,
+    pub replies: Vec<SyncPayload>,
+}
+
+/// Per-server limiter shared by all connections.
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    state: Mutex<RateLimiterState>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(RateLimitConfig::default())
+    }
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            config,
+            state: Mutex::new(RateLimiterState {
+                clients: HashMap::new(),
+                principals: HashMap::new(),
+                app: ScopeBuckets::new(&config.per_app, now),
+            }),
+        }
+    }
+
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Start metering `client_id` on behalf of `principal`. Connections that
+    /// are never registered (backend, admin) bypass the limiter entirely.
+    pub fn register_client(&self, client_id: ClientId, principal: &str) {
+        if self.config.is_unlimited() {
+            return;
+        }
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        match state.clients.get_mut(&client_id) {
+            Some(client) => client.principal = principal.to_string(),
+            None => {
+                state.clients.insert(
+                    client_id,
+                    MeteredClient {
+                        principal: principal.to_string(),
+                        buckets: ScopeBuckets::new(&self.config.per_client, now),
+                        throttled_batches: ThrottledBatches::default(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Drop all state for a reaped client.
+    pub fn forget_client(&self, client_id: ClientId) {
+        self.state.lock().unwrap().clients.remove(&client_id);
+    }
+
+    /// Drop principal buckets that have refilled completely. A full bucket is
+    /// indistinguishable from a fresh one, so this only bounds memory.
+    pub fn prune_idle_principals(&self) {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        state.principals.retain(|_, buckets| !buckets.is_idle(now));
+    }
+
+    pub(crate) fn admit(&self, client_id: ClientId, payloads: Vec<SyncPayload>) -> Admission {
+        if self.config.is_unlimited() {
+            return Admission {
+                admitted: payloads,
+                replies: Vec::new(),
+            };
+        }
+
+        let now = Instant::now();
+        let mut guard = self.state.lock().unwrap();
+        let RateLimiterState {
+            clients,
+            principals,
+            app,
+        } = &mut *guard;
+        let Some(client) = clients.get_mut(&client_id) else {
+            return Admission {
+                admitted: payloads,
+                replies: Vec::new(),
+            };
+        };
+        let principal = principals
+            .entry(client.principal.clone())
+            .or_insert_with(|| ScopeBuckets::new(&self.config.per_principal, now));
+
+        // Rows of one batch are charged together when the first of them
+        // comes up, so a batch is admitted or throttled as a whole.
+        let mut batch_costs: HashMap<BatchId, Cost> = HashMap::new();
+        for payload in &payloads {
+            if let SyncPayload::RowBatchCreated { row, .. } = payload {
+                batch_costs
+                    .entry(row.batch_id)
+                    .or_default()
+                    .add(&Cost::of(payload));
+            }
+        }
+        let mut charged_batches = HashSet::new();
+
+        let mut admission = Admission::default();
+        for payload in payloads {
+            if let Some(batch_id) = payload_batch_id(&payload)
+                && client.throttled_batches.contains(&batch_id)
+            {
+                // The rejection went out with the first throttled member;
+                // the seal closes the batch, so stop tracking it.
+                if matches!(payload, SyncPayload::SealBatch { .. }) {
+                    client.throttled_batches.remove(&batch_id);
+                }
+                continue;
+            }
+
+            let row_batch_id = match &payload {
+                SyncPayload::RowBatchCreated { row, .. } => Some(row.batch_id),
+                _ => None,
+            };
+            if let Some(batch_id) = row_batch_id
+                && charged_batches.contains(&batch_id)
+            {
+                admission.admitted.push(payload);
+                continue;
+            }
+
+            let cost = match row_batch_id.and_then(|batch_id| batch_costs.remove(&batch_id)) {
+                Some(cost) => cost,
+                None => Cost::of(&payload),
+            };
+            if cost.is_free() {
+                admission.admitted.push(payload);
+                continue;
+            }
+
+            let checked = client
+                .buckets
+                .check(&cost, now)
+                .map_err(|resource| RateLimitExceeded {
+                    scope: RateLimitScope::Client,
+                    resource,
+                })
+                .and_then(|()| {
+                    principal
+                        .check(&cost, now)
+                        .map_err(|resource| RateLimitExceeded {
+                            scope: RateLimitScope::Principal,
+                            resource,
+                        })
+                })
+                .and_then(|()| {
+                    app.check(&cost, now).map_err(|resource| RateLimitExceeded {
+                        scope: RateLimitScope::App,
+                        resource,
+                    })
+                });
+
+            match checked {
+                Ok(()) => {
+                    client.buckets.debit(&cost);
+                    principal.debit(&cost);
+                    app.debit(&cost);
+                    if let Some(batch_id) = row_batch_id {
+                        charged_batches.insert(batch_id);
+                    }
+                    admission.admitted.push(payload);
+                }
+                Err(exceeded) => {
+                    tracing::debug!(
+                        %client_id,
+                        scope = exceeded.scope.as_str(),
+                        resource = exceeded.resource.as_str(),
+                        payload = payload.variant_name(),
+                        "throttled inbound sync payload"
+                    );
+                    if let Some(batch_id) = payload_batch_id(&payload) {
+                        client.throttled_batches.insert(batch_id);
+                    }
+                    if let Some(reply) = throttled_reply(&payload, &exceeded) {
+                        admission.replies.push(reply);
+                    }
+                }
+            }
+        }
+
+        admission
+    }
+}
+
+fn payload_batch_id(payload: &SyncPayload) -> Option<BatchId> {
+    match payload {
+        SyncPayload::RowBatchCreated { row, .. } => Some(row.batch_id),
+        SyncPayload::SealBatch { submission } => Some(submission.batch_id),
+        _ => None,
+    }
+}
+
+/// Reply for a throttled payload, or `None` when it is safe to drop silently.
+fn throttled_reply(payload: &SyncPayload, exceeded: &RateLimitExceeded) -> Option<SyncPayload> {
+    match payload {
+        SyncPayload::RowBatchCreated { row, .. } => Some(SyncPayload::BatchFate {
+            fate: BatchFate::Rejected {
+                batch_id: row.batch_id,
+                code: RATE_LIMITED_CODE.to_string(),
+                reason: exceeded.reason(),
+            },
+        }),
+        SyncPayload::QuerySubscription { query_id, .. } => {
+            Some(SyncPayload::Error(SyncError::QuerySubscriptionRejected {
+                query_id: *query_id,
+                code: RATE_LIMITED_CODE.to_string(),
+                reason: exceeded.reason(),
+            }))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::metadata::RowProvenance;
+    use crate::object::ObjectId;
+    use crate::query_manager::query::Query;
+    use crate::row_histories::{RowState, StoredRowBatch};
+    use crate::sync_manager::QueryId;
+
+    fn row_payload(state: RowState, bytes: usize) -> SyncPayload {
+        row_payload_in_batch(BatchId::new(), state, bytes)
+    }
+
+    fn row_payload_in_batch(batch_id: BatchId, state: RowState, bytes: usize) -> SyncPayload {
+        let row = StoredRowBatch::new_with_batch_id(
+            batch_id,
+            ObjectId::new(),
+            "main",
+            Vec::<BatchId>::new(),
+            vec![0u8; bytes],
+            RowProvenance::for_insert("alice".to_string(), 1_000),
+            Default::default(),
+            state,
+            None,
+        );
+        SyncPayload::RowBatchCreated {
+            metadata: None,
+            row,
+        }
+    }
+
+    fn subscription_payload(query_id: u64) -> SyncPayload {
+        SyncPayload::QuerySubscription {
+            query_id: QueryId(query_id),
+            query: Box::new(Query::new("todos")),
+            session: None,
+            required_tier: None,
+            propagation: Default::default(),
+            policy_context_tables: Vec::new(),
+        }
+    }
+
+    fn rows_limit(capacity: u64, refill_per_sec: u64) -> ScopeLimits {
+        ScopeLimits {
+            rows: Some(TokenBucketConfig::new(capacity, refill_per_sec)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unregistered_clients_are_not_metered() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_client: rows_limit(1, 0),
+            ..Default::default()
+        });
+
+        let admission = limiter.admit(
+            ClientId::new(),
+            vec![
+                row_payload(RowState::VisibleDirect, 8),
+                row_payload(RowState::VisibleDirect, 8),
+            ],
+        );
+
+        assert_eq!(admission.admitted.len(), 2);
+        assert!(admission.replies.is_empty());
+    }
+
+    #[test]
+    fn throttled_direct_rows_are_rejected_with_rate_limited_fate() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_client: rows_limit(1, 0),
+            ..Default::default()
+        });
+        let client_id = ClientId::new();
+        limiter.register_client(client_id, "alice");
+        let throttled = row_payload(RowState::VisibleDirect, 8);
+        let SyncPayload::RowBatchCreated { row, .. } = &throttled else {
+            unreachable!();
+        };
+        let throttled_batch_id = row.batch_id;
+
+        let admission = limiter.admit(
+            client_id,
+            vec![row_payload(RowState::VisibleDirect, 8), throttled],
+        );
+
+        assert_eq!(admission.admitted.len(), 1);
+        assert!(matches!(
+            admission.replies.as_slice(),
+            [SyncPayload::BatchFate {
+                fate: BatchFate::Rejected { batch_id, code, .. },
+            }] if *batch_id == throttled_batch_id && code == RATE_LIMITED_CODE
+        ));
+    }
+
+    #[test]
+    fn direct_batch_that_exhausts_the_bucket_is_throttled_as_a_whole() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_client: rows_limit(1, 0),
+            ..Default::default()
+        });
+        let client_id = ClientId::new();
+        limiter.register_client(client_id, "alice");
+        let throttled_batch_id = BatchId::new();
+        let single_row = row_payload(RowState::VisibleDirect, 8);
+
+        let admission = limiter.admit(
+            client_id,
+            vec![
+                row_payload_in_batch(throttled_batch_id, RowState::VisibleDirect, 8),
+                row_payload_in_batch(throttled_batch_id, RowState::VisibleDirect, 8),
+                single_row.clone(),
+            ],
+        );
+
+        assert_eq!(admission.admitted, vec![single_row]);
+        assert!(matches!(
+            admission.replies.as_slice(),
+            [SyncPayload::BatchFate {
+                fate: BatchFate::Rejected { batch_id, code, .. },
+            }] if *batch_id == throttled_batch_id && code == RATE_LIMITED_CODE
+        ));
+    }
+
+    #[test]
+    fn throttled_transactional_rows_are_rejected_with_rate_limited_fate() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_principal: ScopeLimits {
+                row_bytes: Some(TokenBucketConfig::new(16, 0)),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let client_id = ClientId::new();
+        limiter.register_client(client_id, "alice");
+
+        let admission = limiter.admit(client_id, vec![row_payload(RowState::StagingPending, 32)]);
+
+        assert!(admission.admitted.is_empty());
+        let [SyncPayload::BatchFate { fate }] = admission.replies.as_slice() else {
+            panic!(
+                "expected a single batch fate reply, got {:?}",
+                admission.replies
+            );
+        };
+        let BatchFate::Rejected { code, reason, .. } = fate else {
+            panic!("expected rejected fate, got {fate:?}");
+        };
+        assert_eq!(code, RATE_LIMITED_CODE);
+        assert_eq!(reason, "principal rate limit exceeded for row bytes");
+    }
+
+    #[test]
+    fn principal_budget_is_shared_across_clients() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_principal: rows_limit(1, 0),
+            ..Default::default()
+        });
+        let laptop = ClientId::new();
+        let phone = ClientId::new();
+        limiter.register_client(laptop, "alice");
+        limiter.register_client(phone, "alice");
+
+        let first = limiter.admit(laptop, vec![row_payload(RowState::VisibleDirect, 8)]);
+        let second = limiter.admit(phone, vec![row_payload(RowState::VisibleDirect, 8)]);
+
+        assert_eq!(first.admitted.len(), 1);
+        assert!(second.admitted.is_empty());
+    }
+
+    #[test]
+    fn throttled_query_subscriptions_are_rejected() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_app: ScopeLimits {
+                query_subscriptions: Some(TokenBucketConfig::new(1, 0)),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let client_id = ClientId::new();
+        limiter.register_client(client_id, "alice");
+
+        let admission = limiter.admit(
+            client_id,
+            vec![subscription_payload(1), subscription_payload(2)],
+        );
+
+        assert_eq!(admission.admitted.len(), 1);
+        assert!(matches!(
+            admission.replies.as_slice(),
+            [SyncPayload::Error(SyncError::QuerySubscriptionRejected {
+                query_id: QueryId(2),
+                code,
+                ..
+            })] if code == RATE_LIMITED_CODE
+        ));
+    }
+
+    #[test]
+    fn seal_for_throttled_batch_is_dropped() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_client: rows_limit(0, 0),
+            ..Default::default()
+        });
+        let client_id = ClientId::new();
+        limiter.register_client(client_id, "alice");
+        let row = row_payload(RowState::StagingPending, 8);
+        let SyncPayload::RowBatchCreated { row: stored, .. } = &row else {
+            unreachable!();
+        };
+        let seal = SyncPayload::SealBatch {
+            submission: crate::batch_fate::SealedBatchSubmission::new(
+                stored.batch_id,
+                crate::batch_fate::BatchMode::Transactional,
+                crate::object::BranchName::new("main"),
+                Vec::new(),
+                Vec::new(),
+            ),
+        };
+
+        let admission = limiter.admit(client_id, vec![row, seal]);
+
+        assert!(admission.admitted.is_empty());
+        assert_eq!(admission.replies.len(), 1);
+        let state = limiter.state.lock().unwrap();
+        assert_eq!(state.clients[&client_id].throttled_batches.len(), 0);
+    }
+
+    #[test]
+    fn throttled_batches_without_seals_stay_bounded() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_client: rows_limit(0, 0),
+            ..Default::default()
+        });
+        let client_id = ClientId::new();
+        limiter.register_client(client_id, "alice");
+
+        let payloads = (0..MAX_THROTTLED_BATCHES + 10)
+            .map(|_| row_payload(RowState::VisibleDirect, 8))
+            .collect();
+        let admission = limiter.admit(client_id, payloads);
+
+        assert_eq!(admission.replies.len(), MAX_THROTTLED_BATCHES + 10);
+        let state = limiter.state.lock().unwrap();
+        assert_eq!(
+            state.clients[&client_id].throttled_batches.len(),
+            MAX_THROTTLED_BATCHES
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn buckets_refill_over_time() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_client: rows_limit(1, 1),
+            ..Default::default()
+        });
+        let client_id = ClientId::new();
+        limiter.register_client(client_id, "alice");
+
+        let first = limiter.admit(client_id, vec![row_payload(RowState::VisibleDirect, 8)]);
+        let throttled = limiter.admit(client_id, vec![row_payload(RowState::VisibleDirect, 8)]);
+        tokio::time::advance(Duration::from_secs(1)).await;
+        let refilled = limiter.admit(client_id, vec![row_payload(RowState::VisibleDirect, 8)]);
+
+        assert_eq!(first.admitted.len(), 1);
+        assert!(throttled.admitted.is_empty());
+        assert_eq!(refilled.admitted.len(), 1);
+    }
+}
@@ -13,15 +13,15 @@ use serde::{Deserialize, Serialize};
 
 use crate::jazz_transport::ErrorResponse;
 use crate::middleware::auth::validate_admin_secret;
-use crate::query_manager::types::{
-    ColumnType, Schema, SchemaHash, TableName, TablePolicies, Value,
+use crate::query_manager::types::{Schema, SchemaHash, TableName, TablePolicies};
+use crate::schema_manager::{Lens, ReviewedMigration, ReviewedTableLens};
+use crate::server::{
+    ServerState, ShutdownPhase, WebhookAdminError, WebhookDispatcher, WebhookStatusView,
 };
-use crate::schema_manager::{Lens, LensOp, LensTransform};
-use crate::server::{ServerState, ShutdownPhase};
 
 use super::utils::{
     parse_app_id_param, parse_object_id_param, parse_schema_hash_param, permissions_head_view,
-    permissions_map_view, unix_timestamp_millis,
+    permissions_map_view, release_view, unix_timestamp_millis,
 };
 
 #[derive(Debug, Serialize)]
@@ -51,6 +51,15 @@ pub(super) struct AdminSubscriptionIntrospectionParams {
     app_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ExplainQueryRequest {
+    query: crate::query_manager::query::Query,
+    /// Settle the query once and report per-node row counters.
+    #[serde(default)]
+    analyze: bool,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct SchemaConnectivityParams {
@@ -66,43 +75,49 @@ pub(super) struct AdminSubscriptionIntrospectionResponse {
     queries: Vec<crate::query_manager::manager::ServerSubscriptionTelemetryGroup>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(super) struct PublishMigrationRequest {
-    from_hash: String,
-    to_hash: String,
-    forward: Vec<PublishTableLens>,
+pub(super) struct WebhooksStatusResponse {
+    pruned_through: u64,
+    last_seq: u64,
+    webhooks: Vec<WebhookStatusView>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Deserialize)]
+pub(super) struct WebhookQueueParams {
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(super) struct PublishTableLens {
-    table: String,
-    #[serde(default)]
-    added: bool,
-    #[serde(default)]
-    removed: bool,
-    renamed_from: Option<String>,
-    operations: Vec<PublishLensOp>,
+pub(super) struct WebhookQueueResponse {
+    webhook: String,
+    changes: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct WebhookReplayRequest {
+    from_seq: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct WebhookReplayResponse {
+    webhook: String,
+    cursor: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "camelCase")]
-pub(super) enum PublishLensOp {
-    Introduce {
-        column: String,
-        column_type: ColumnType,
-        value: Value,
-    },
-    Drop {
-        column: String,
-        column_type: ColumnType,
-        value: Value,
-    },
-    Rename {
-        column: String,
-        value: String,
-    },
+#[serde(rename_all = "camelCase")]
+pub(super) struct PublishMigrationRequest {
+    from_hash: String,
+    to_hash: String,
+    forward: Vec<ReviewedTableLens>,
+    /// Set to publish a corrected revision of the migration already
+    /// published for this schema pair, recording why it replaces it.
+    #[serde(default)]
+    revision_reason: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -161,6 +176,67 @@ pub(super) struct PublishMigrationResponse {
     to_hash: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SchemaGcRequest {
+    /// Schema that rows on retired schemas are rewritten onto.
+    target_hash: String,
+    /// Schemas published at or after this timestamp are kept.
+    retain_published_since: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ReleaseMigration {
+    from_hash: String,
+    to_hash: String,
+    forward: Vec<ReviewedTableLens>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct PublishReleaseRequest {
+    schema: Schema,
+    /// Migrations published with the schema; either end may be the release
+    /// schema itself.
+    #[serde(default)]
+    migrations: Vec<ReleaseMigration>,
+    permissions: std::collections::HashMap<String, TablePolicies>,
+    expected_parent_release_object_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ReleaseView {
+    pub(super) object_id: String,
+    pub(super) version: u64,
+    pub(super) parent_release_object_id: Option<String>,
+    pub(super) schema_hash: String,
+    pub(super) permissions_version: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ReleaseHeadResponse {
+    release: Option<ReleaseView>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SchemaGcLensView {
+    from_hash: String,
+    to_hash: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SchemaGcResponse {
+    retained: Vec<String>,
+    retired: Vec<String>,
+    retired_lenses: Vec<SchemaGcLensView>,
+    rewritten_rows: usize,
+}
+
 async fn forward_catalogue_request(
     state: &Arc<ServerState>,
     admin_secret: &str,
@@ -940,134 +1016,446 @@ pub(super) async fn publish_migration_handler(
         }
     };
 
-    let mut forward = LensTransform::new();
-    for table_lens in request.forward {
-        let table_name = table_lens.table;
-        if table_lens.added && table_lens.removed {
+    let forward =
+        match ReviewedMigration::forward_transform(request.forward, &source_schema, &target_schema)
+        {
+            Ok(forward) => forward,
+            Err(message) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse::bad_request(message)),
+                )
+                    .into_response();
+            }
+        };
+
+    let lens = Lens::new(source_hash, target_hash, forward);
+    let published = match &request.revision_reason {
+        Some(reason) => state.runtime.publish_lens_revision(&lens, reason),
+        None => state.runtime.publish_lens(&lens),
+    };
+    let object_id = match published {
+        Ok(object_id) => object_id,
+        Err(err) => {
             return (
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::bad_request(format!(
-                    "table {} cannot be both added and removed",
-                    table_name
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal(format!(
+                    "failed to publish migration lens: {err}"
                 ))),
             )
                 .into_response();
         }
-        if (table_lens.added || table_lens.removed) && table_lens.renamed_from.is_some() {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::bad_request(format!(
-                    "table {} cannot combine added/removed markers with renamedFrom",
-                    table_name
-                ))),
-            )
-                .into_response();
+    };
+
+    if let Err(err) = state.runtime.flush().await {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal(format!(
+                "failed to flush published migration lens: {err}"
+            ))),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::CREATED,
+        Json(PublishMigrationResponse {
+            object_id: object_id.to_string(),
+            from_hash: request.from_hash,
+            to_hash: request.to_hash,
+        }),
+    )
+        .into_response()
+}
+
+pub(super) async fn release_head_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let admin_secret = headers
+        .get("X-Jazz-Admin-Secret")
+        .and_then(|v| v.to_str().ok());
+
+    match validate_admin_secret(admin_secret, &state.auth_config) {
+        Ok(()) => {}
+        Err((status, msg)) => {
+            return (status, Json(ErrorResponse::unauthorized(msg))).into_response();
         }
-        if (table_lens.added || table_lens.removed) && !table_lens.operations.is_empty() {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::bad_request(format!(
-                    "table {} cannot combine added/removed markers with column operations",
-                    table_name
-                ))),
-            )
-                .into_response();
+    }
+
+    if state.topology.is_edge() {
+        return match forward_catalogue_request(
+            &state,
+            admin_secret.expect("validated admin secret"),
+            reqwest::Method::GET,
+            "/admin/releases/head",
+            None,
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(error) => error.into_response(),
+        };
+    }
+
+    match state
+        .runtime
+        .with_schema_manager(|schema_manager| schema_manager.current_release().map(release_view))
+    {
+        Ok(release) => (StatusCode::OK, Json(ReleaseHeadResponse { release })).into_response(),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal(format!(
+                "failed to read current release: {err}"
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Publish a schema, its migrations and its permissions as one release that
+/// every node applies as a whole.
+///
+/// Requires a valid admin secret. Migration endpoints other than the release
+/// schema must already be known to the server.
+pub(super) async fn publish_release_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Json(request): Json<PublishReleaseRequest>,
+) -> impl IntoResponse {
+    let admin_secret = headers
+        .get("X-Jazz-Admin-Secret")
+        .and_then(|v| v.to_str().ok());
+
+    match validate_admin_secret(admin_secret, &state.auth_config) {
+        Ok(()) => {}
+        Err((status, msg)) => {
+            return (status, Json(ErrorResponse::unauthorized(msg))).into_response();
         }
-        if table_lens.added {
-            let target_table_name = TableName::from(table_name.clone());
-            let schema = match target_schema.get(&target_table_name) {
-                Some(schema) => schema.clone(),
-                None => {
+    }
+
+    if state.topology.is_edge() {
+        let body = match serde_json::to_vec(&request) {
+            Ok(body) => body,
+            Err(err) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::internal(format!(
+                        "failed to serialize release publish request: {err}"
+                    ))),
+                )
+                    .into_response();
+            }
+        };
+        return match forward_catalogue_request(
+            &state,
+            admin_secret.expect("validated admin secret"),
+            reqwest::Method::POST,
+            "/admin/releases",
+            Some(body),
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(error) => error.into_response(),
+        };
+    }
+
+    let expected_parent_release_object_id = match request.expected_parent_release_object_id {
+        Some(object_id) => match parse_object_id_param(&object_id) {
+            Ok(object_id) => Some(object_id),
+            Err(message) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse::bad_request(message)),
+                )
+                    .into_response();
+            }
+        },
+        None => None,
+    };
+
+    let schema_hash = SchemaHash::compute(&request.schema);
+    let mut lenses = Vec::with_capacity(request.migrations.len());
+    for migration in request.migrations {
+        let mut endpoints = Vec::with_capacity(2);
+        for (label, hash) in [
+            ("source", &migration.from_hash),
+            ("target", &migration.to_hash),
+        ] {
+            let hash = match parse_schema_hash_param(hash) {
+                Ok(hash) => hash,
+                Err(message) => {
                     return (
                         StatusCode::BAD_REQUEST,
-                        Json(ErrorResponse::bad_request(format!(
-                            "createTables references unknown target table {}",
-                            table_name
-                        ))),
+                        Json(ErrorResponse::bad_request(message)),
                     )
                         .into_response();
                 }
             };
-            forward.push(
-                LensOp::AddTable {
-                    table: table_name.clone(),
-                    schema,
-                },
-                false,
-            );
-        }
-        if table_lens.removed {
-            let source_table_name = TableName::from(table_name.clone());
-            let schema = match source_schema.get(&source_table_name) {
-                Some(schema) => schema.clone(),
-                None => {
+            if hash == schema_hash {
+                endpoints.push((hash, request.schema.clone()));
+                continue;
+            }
+            match state.runtime.known_schema(&hash) {
+                Ok(Some(schema)) => endpoints.push((hash, schema)),
+                Ok(None) => {
                     return (
-                        StatusCode::BAD_REQUEST,
-                        Json(ErrorResponse::bad_request(format!(
-                            "dropTables references unknown source table {}",
-                            table_name
+                        StatusCode::NOT_FOUND,
+                        Json(ErrorResponse::not_found(format!(
+                            "{label} schema catalogue not found for hash {hash}"
                         ))),
                     )
                         .into_response();
                 }
-            };
-            forward.push(
-                LensOp::RemoveTable {
-                    table: table_name.clone(),
-                    schema,
-                },
-                false,
-            );
+                Err(err) => {
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse::internal(format!(
+                            "failed to read {label} schema catalogue: {err}"
+                        ))),
+                    )
+                        .into_response();
+                }
+            }
         }
-        if let Some(renamed_from) = table_lens.renamed_from {
-            forward.push(
-                LensOp::RenameTable {
-                    old_name: renamed_from,
-                    new_name: table_name.clone(),
-                },
-                false,
-            );
+        let (target_hash, target_schema) = endpoints.pop().expect("target endpoint");
+        let (source_hash, source_schema) = endpoints.pop().expect("source endpoint");
+        match ReviewedMigration::forward_transform(
+            migration.forward,
+            &source_schema,
+            &target_schema,
+        ) {
+            Ok(forward) => lenses.push(Lens::new(source_hash, target_hash, forward)),
+            Err(message) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse::bad_request(message)),
+                )
+                    .into_response();
+            }
         }
-        for operation in table_lens.operations {
-            let op = match operation {
-                PublishLensOp::Introduce {
-                    column,
-                    column_type,
-                    value,
-                } => LensOp::AddColumn {
-                    table: table_name.clone(),
-                    column,
-                    column_type,
-                    default: value,
-                },
-                PublishLensOp::Drop {
-                    column,
-                    column_type,
-                    value,
-                } => LensOp::RemoveColumn {
-                    table: table_name.clone(),
-                    column,
-                    column_type,
-                    default: value,
-                },
-                PublishLensOp::Rename { column, value } => LensOp::RenameColumn {
-                    table: table_name.clone(),
-                    old_name: column,
-                    new_name: value,
-                },
-            };
-            forward.push(op, false);
+    }
+
+    let permissions = request
+        .permissions
+        .into_iter()
+        .map(|(table_name, policies)| (TableName::new(table_name), policies))
+        .collect();
+
+    match state.runtime.publish_release(
+        request.schema,
+        lenses,
+        permissions,
+        expected_parent_release_object_id,
+    ) {
+        Ok(_) => {}
+        Err(crate::runtime_tokio::RuntimeError::WriteError(message))
+            if message.starts_with("stale release parent") =>
+        {
+            return (
+                StatusCode::CONFLICT,
+                Json(ErrorResponse::bad_request(message)),
+            )
+                .into_response();
+        }
+        Err(crate::runtime_tokio::RuntimeError::WriteError(message))
+            if message.starts_with("invalid release")
+                || message.starts_with("Draft lens")
+                || message.starts_with("Lens ") =>
+        {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(message)),
+            )
+                .into_response();
+        }
+        Err(err) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal(format!(
+                    "failed to publish release: {err}"
+                ))),
+            )
+                .into_response();
         }
     }
 
-    let lens = Lens::new(source_hash, target_hash, forward);
-    let object_id = match state.runtime.publish_lens(&lens) {
-        Ok(object_id) => object_id,
+    released_head_response(&state, "published release").await
+}
+
+/// Roll back to the release before the current one by publishing it again.
+///
+/// Requires a valid admin secret.
+pub(super) async fn rollback_release_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let admin_secret = headers
+        .get("X-Jazz-Admin-Secret")
+        .and_then(|v| v.to_str().ok());
+
+    match validate_admin_secret(admin_secret, &state.auth_config) {
+        Ok(()) => {}
+        Err((status, msg)) => {
+            return (status, Json(ErrorResponse::unauthorized(msg))).into_response();
+        }
+    }
+
+    if state.topology.is_edge() {
+        return match forward_catalogue_request(
+            &state,
+            admin_secret.expect("validated admin secret"),
+            reqwest::Method::POST,
+            "/admin/releases/rollback",
+            None,
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(error) => error.into_response(),
+        };
+    }
+
+    match state.runtime.rollback_release() {
+        Ok(_) => {}
+        Err(crate::runtime_tokio::RuntimeError::WriteError(message))
+            if message.starts_with("no previous release") =>
+        {
+            return (
+                StatusCode::CONFLICT,
+                Json(ErrorResponse::bad_request(message)),
+            )
+                .into_response();
+        }
         Err(err) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ErrorResponse::internal(format!(
-                    "failed to publish migration lens: {err}"
+                    "failed to roll back release: {err}"
+                ))),
+            )
+                .into_response();
+        }
+    }
+
+    released_head_response(&state, "rolled back release").await
+}
+
+/// Flush a just-published release and answer with the new current release.
+async fn released_head_response(state: &Arc<ServerState>, what: &str) -> Response {
+    if let Err(err) = state.runtime.flush().await {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal(format!(
+                "failed to flush {what}: {err}"
+            ))),
+        )
+            .into_response();
+    }
+
+    match state
+        .runtime
+        .with_schema_manager(|schema_manager| schema_manager.current_release().map(release_view))
+    {
+        Ok(release) => (StatusCode::CREATED, Json(ReleaseHeadResponse { release })).into_response(),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal(format!(
+                "failed to read {what}: {err}"
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+pub(super) async fn schema_gc_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Json(request): Json<SchemaGcRequest>,
+) -> impl IntoResponse {
+    let admin_secret = headers
+        .get("X-Jazz-Admin-Secret")
+        .and_then(|v| v.to_str().ok());
+
+    match validate_admin_secret(admin_secret, &state.auth_config) {
+        Ok(()) => {}
+        Err((status, msg)) => {
+            return (status, Json(ErrorResponse::unauthorized(msg))).into_response();
+        }
+    }
+
+    if state.topology.is_edge() {
+        let body = match serde_json::to_vec(&request) {
+            Ok(body) => body,
+            Err(err) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::internal(format!(
+                        "failed to serialize schema GC request: {err}"
+                    ))),
+                )
+                    .into_response();
+            }
+        };
+        return match forward_catalogue_request(
+            &state,
+            admin_secret.expect("validated admin secret"),
+            reqwest::Method::POST,
+            "/admin/schema-gc",
+            Some(body),
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(error) => error.into_response(),
+        };
+    }
+
+    let target_hash = match parse_schema_hash_param(&request.target_hash) {
+        Ok(hash) => hash,
+        Err(message) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(message)),
+            )
+                .into_response();
+        }
+    };
+
+    match state.runtime.known_schema(&target_hash) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "target schema catalogue not found for hash {}",
+                    target_hash
+                ))),
+            )
+                .into_response();
+        }
+        Err(err) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal(format!(
+                    "failed to read target schema catalogue: {err}"
+                ))),
+            )
+                .into_response();
+        }
+    }
+
+    let report = match state
+        .runtime
+        .collect_schema_garbage(target_hash, request.retain_published_since)
+    {
+        Ok(report) => report,
+        Err(err) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal(format!(
+                    "failed to collect schema garbage: {err}"
                 ))),
             )
                 .into_response();
@@ -1078,18 +1466,26 @@ pub(super) async fn publish_migration_handler(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse::internal(format!(
-                "failed to flush published migration lens: {err}"
+                "failed to flush schema GC tombstones: {err}"
             ))),
         )
             .into_response();
     }
 
     (
-        StatusCode::CREATED,
-        Json(PublishMigrationResponse {
-            object_id: object_id.to_string(),
-            from_hash: request.from_hash,
-            to_hash: request.to_hash,
+        StatusCode::OK,
+        Json(SchemaGcResponse {
+            retained: report.retained.iter().map(ToString::to_string).collect(),
+            retired: report.retired.iter().map(ToString::to_string).collect(),
+            retired_lenses: report
+                .retired_lenses
+                .iter()
+                .map(|(source, target)| SchemaGcLensView {
+                    from_hash: source.to_string(),
+                    to_hash: target.to_string(),
+                })
+                .collect(),
+            rewritten_rows: report.rewritten_rows,
         }),
     )
         .into_response()
@@ -1160,6 +1556,148 @@ pub(super) async fn admin_subscription_introspection_handler(
     }
 }
 
+/// Describe how the server compiles a query, as an admin (no session policies).
+pub(super) async fn explain_query_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Json(request): Json<ExplainQueryRequest>,
+) -> impl IntoResponse {
+    let admin_secret = headers
+        .get("X-Jazz-Admin-Secret")
+        .and_then(|v| v.to_str().ok());
+
+    if let Err((status, msg)) = validate_admin_secret(admin_secret, &state.auth_config) {
+        return (status, Json(ErrorResponse::unauthorized(msg))).into_response();
+    }
+
+    match state.runtime.explain(&request.query, None, request.analyze) {
+        Ok(explain) => Json(explain).into_response(),
+        Err(err) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(format!(
+                "failed to explain query: {err}"
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+const DEFAULT_WEBHOOK_QUEUE_LIMIT: usize = 100;
+const MAX_WEBHOOK_QUEUE_LIMIT: usize = 1_000;
+
+/// Authorize an admin webhook request and resolve the configured dispatcher.
+#[allow(clippy::result_large_err)]
+fn admin_webhooks<'a>(
+    state: &'a ServerState,
+    headers: &HeaderMap,
+) -> Result<&'a Arc<WebhookDispatcher>, Response> {
+    let admin_secret = headers
+        .get("X-Jazz-Admin-Secret")
+        .and_then(|v| v.to_str().ok());
+
+    if let Err((status, msg)) = validate_admin_secret(admin_secret, &state.auth_config) {
+        return Err((status, Json(ErrorResponse::unauthorized(msg))).into_response());
+    }
+
+    state.webhooks.as_ref().ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(
+                "no webhooks are configured on this server",
+            )),
+        )
+            .into_response()
+    })
+}
+
+fn webhook_admin_error_response(error: WebhookAdminError) -> Response {
+    match error {
+        WebhookAdminError::UnknownWebhook(_) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(error.to_string())),
+        )
+            .into_response(),
+        WebhookAdminError::Pruned { .. } => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(error.to_string())),
+        )
+            .into_response(),
+        WebhookAdminError::Storage(_) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal(format!(
+                "failed to read change log: {error}"
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+pub(super) async fn webhooks_status_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let dispatcher = match admin_webhooks(&state, &headers) {
+        Ok(dispatcher) => dispatcher,
+        Err(response) => return response,
+    };
+
+    match dispatcher.statuses(&state.runtime) {
+        Ok((bounds, webhooks)) => Json(WebhooksStatusResponse {
+            pruned_through: bounds.pruned_through,
+            last_seq: bounds.last_seq,
+            webhooks,
+        })
+        .into_response(),
+        Err(error) => webhook_admin_error_response(error),
+    }
+}
+
+pub(super) async fn webhook_queue_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+    Query(params): Query<WebhookQueueParams>,
+) -> impl IntoResponse {
+    let dispatcher = match admin_webhooks(&state, &headers) {
+        Ok(dispatcher) => dispatcher,
+        Err(response) => return response,
+    };
+
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_WEBHOOK_QUEUE_LIMIT)
+        .min(MAX_WEBHOOK_QUEUE_LIMIT);
+    match dispatcher.queued_changes(&state.runtime, &name, limit) {
+        Ok(changes) => Json(WebhookQueueResponse {
+            webhook: name,
+            changes,
+        })
+        .into_response(),
+        Err(error) => webhook_admin_error_response(error),
+    }
+}
+
+pub(super) async fn webhook_replay_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+    Json(request): Json<WebhookReplayRequest>,
+) -> impl IntoResponse {
+    let dispatcher = match admin_webhooks(&state, &headers) {
+        Ok(dispatcher) => dispatcher,
+        Err(response) => return response,
+    };
+
+    match dispatcher.replay(&state.runtime, &name, request.from_seq) {
+        Ok(cursor) => Json(WebhookReplayResponse {
+            webhook: name,
+            cursor,
+        })
+        .into_response(),
+        Err(error) => webhook_admin_error_response(error),
+    }
+}
+
 pub(super) async fn health_handler(State(state): State<Arc<ServerState>>) -> impl IntoResponse {
     let mut phase = state.shutdown.phase();
     if !state.shutdown.is_shutting_down() && phase.is_running() {
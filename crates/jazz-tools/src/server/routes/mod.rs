@@ -29,9 +29,12 @@ use tower_http::trace::TraceLayer;
 use crate::server::ServerState;
 
 use http::{
-    admin_subscription_introspection_handler, health_handler, permissions_handler,
-    permissions_head_handler, publish_migration_handler, publish_permissions_handler,
-    publish_schema_handler, schema_connectivity_handler, schema_handler, schema_hashes_handler,
+    admin_subscription_introspection_handler, explain_query_handler, health_handler,
+    permissions_handler, permissions_head_handler, publish_migration_handler,
+    publish_permissions_handler, publish_release_handler, publish_schema_handler,
+    release_head_handler, rollback_release_handler, schema_connectivity_handler, schema_gc_handler,
+    schema_handler, schema_hashes_handler, webhook_queue_handler, webhook_replay_handler,
+    webhooks_status_handler,
 };
 use websocket::ws_handler;
 
@@ -66,10 +69,18 @@ pub fn create_router(state: Arc<ServerState>) -> Router {
             get(permissions_handler).post(publish_permissions_handler),
         )
         .route("/migrations", post(publish_migration_handler))
+        .route("/releases", post(publish_release_handler))
+        .route("/releases/head", get(release_head_handler))
+        .route("/releases/rollback", post(rollback_release_handler))
+        .route("/schema-gc", post(schema_gc_handler))
+        .route("/explain", post(explain_query_handler))
         .route(
             "/introspection/subscriptions",
             get(admin_subscription_introspection_handler),
-        );
+        )
+        .route("/webhooks", get(webhooks_status_handler))
+        .route("/webhooks/:name/queue", get(webhook_queue_handler))
+        .route("/webhooks/:name/replay", post(webhook_replay_handler));
     let traced_routes = Router::new()
         .route("/ws", axum::routing::any(ws_handler))
         .route("/schema/:hash", get(schema_handler))
@@ -1502,6 +1513,165 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn release_handlers_publish_and_roll_back_releases() {
+        let schema = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("id", ColumnType::Uuid)
+                    .column("name", ColumnType::Text),
+            )
+            .build();
+        let state = make_state_with_schema(schema.clone()).await;
+        let app = make_test_router(state.clone());
+
+        let early_rollback = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri(test_app_route("/admin/releases/rollback"))
+                    .header("X-Jazz-Admin-Secret", "admin-secret")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(early_rollback.status(), StatusCode::CONFLICT);
+
+        let mut object_ids = Vec::new();
+        for (policy, expected_parent) in [("True", None), ("False", Some(0))] {
+            let request_body = serde_json::json!({
+                "schema": schema,
+                "permissions": {
+                    "users": {
+                        "select": { "using": { "type": policy } }
+                    }
+                },
+                "expectedParentReleaseObjectId": expected_parent
+                    .map(|index: usize| object_ids[index].clone()),
+            });
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .method("POST")
+                        .uri(test_app_route("/admin/releases"))
+                        .header("Content-Type", "application/json")
+                        .header("X-Jazz-Admin-Secret", "admin-secret")
+                        .body(axum::body::Body::from(request_body.to_string()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            let body = body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("release publish body");
+            let json: Value = serde_json::from_slice(&body).expect("release publish json");
+            object_ids.push(
+                json["release"]["objectId"]
+                    .as_str()
+                    .expect("release object id")
+                    .to_string(),
+            );
+        }
+
+        let stale_request_body = serde_json::json!({
+            "schema": schema,
+            "permissions": {},
+            "expectedParentReleaseObjectId": object_ids[0],
+        });
+        let stale_response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri(test_app_route("/admin/releases"))
+                    .header("Content-Type", "application/json")
+                    .header("X-Jazz-Admin-Secret", "admin-secret")
+                    .body(axum::body::Body::from(stale_request_body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(stale_response.status(), StatusCode::CONFLICT);
+
+        let rollback_response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri(test_app_route("/admin/releases/rollback"))
+                    .header("X-Jazz-Admin-Secret", "admin-secret")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(rollback_response.status(), StatusCode::CREATED);
+        let rollback_body = body::to_bytes(rollback_response.into_body(), usize::MAX)
+            .await
+            .expect("release rollback body");
+        let rollback_json: Value =
+            serde_json::from_slice(&rollback_body).expect("release rollback json");
+        assert_eq!(rollback_json["release"]["version"].as_u64(), Some(3));
+
+        let second_rollback = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri(test_app_route("/admin/releases/rollback"))
+                    .header("X-Jazz-Admin-Secret", "admin-secret")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(second_rollback.status(), StatusCode::CONFLICT);
+
+        let head_response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri(test_app_route("/admin/releases/head"))
+                    .header("X-Jazz-Admin-Secret", "admin-secret")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(head_response.status(), StatusCode::OK);
+        let head_body = body::to_bytes(head_response.into_body(), usize::MAX)
+            .await
+            .expect("release head body");
+        let head_json: Value = serde_json::from_slice(&head_body).expect("release head json");
+        assert_eq!(head_json["release"]["version"].as_u64(), Some(3));
+        assert_eq!(head_json["release"]["parentReleaseObjectId"], Value::Null);
+
+        let permissions_response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri(test_app_route("/admin/permissions"))
+                    .header("X-Jazz-Admin-Secret", "admin-secret")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let permissions_body = body::to_bytes(permissions_response.into_body(), usize::MAX)
+            .await
+            .expect("current permissions body");
+        let permissions_json: Value =
+            serde_json::from_slice(&permissions_body).expect("current permissions json");
+        assert_eq!(
+            permissions_json["permissions"]["users"]["select"]["using"]["type"].as_str(),
+            Some("True")
+        );
+    }
+
     #[tokio::test]
     async fn permissions_handler_returns_nulls_before_any_publish() {
         let schema = SchemaBuilder::new()
@@ -2022,6 +2192,111 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn publish_migration_revision_supersedes_previous_lens() {
+        let v1 = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("id", ColumnType::Uuid)
+                    .column("email", ColumnType::Text)
+                    .column("name", ColumnType::Text),
+            )
+            .build();
+        let v2 = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("id", ColumnType::Uuid)
+                    .column("email_address", ColumnType::Text)
+                    .column("display_name", ColumnType::Text),
+            )
+            .build();
+
+        let v1_hash = SchemaHash::compute(&v1);
+        let v2_hash = SchemaHash::compute(&v2);
+
+        let state = make_state_with_schema(v2).await;
+        state
+            .runtime
+            .add_known_schema(v1)
+            .expect("seed known schema for publish test");
+        let app = make_test_router(state.clone());
+
+        let migration = |email_to: &str, name_to: &str, reason: Option<&str>| {
+            let mut body = serde_json::json!({
+                "fromHash": v1_hash.to_string(),
+                "toHash": v2_hash.to_string(),
+                "forward": [{
+                    "table": "users",
+                    "operations": [
+                        { "type": "rename", "column": "email", "value": email_to },
+                        { "type": "rename", "column": "name", "value": name_to }
+                    ]
+                }]
+            });
+            if let Some(reason) = reason {
+                body["revisionReason"] = serde_json::json!(reason);
+            }
+            axum::http::Request::builder()
+                .method("POST")
+                .uri(test_app_route("/admin/migrations"))
+                .header("Content-Type", "application/json")
+                .header("X-Jazz-Admin-Secret", "admin-secret")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        // The first migration swaps the columns by mistake.
+        let created = app
+            .clone()
+            .oneshot(migration("display_name", "email_address", None))
+            .await
+            .unwrap();
+        assert_eq!(created.status(), StatusCode::CREATED);
+
+        let replaced_silently = app
+            .clone()
+            .oneshot(migration("email_address", "display_name", None))
+            .await
+            .unwrap();
+        assert_ne!(
+            replaced_silently.status(),
+            StatusCode::CREATED,
+            "a different lens for the same pair needs a revision"
+        );
+
+        let revised = app
+            .oneshot(migration(
+                "email_address",
+                "display_name",
+                Some("columns were swapped"),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(revised.status(), StatusCode::CREATED);
+
+        let lens = state
+            .runtime
+            .with_schema_manager(|schema_manager| {
+                schema_manager.get_lens(&v1_hash, &v2_hash).cloned()
+            })
+            .expect("read schema manager lens")
+            .expect("revised lens should be registered");
+        assert_eq!(lens.revision, 1);
+        assert_eq!(
+            lens.revision_reason.as_deref(),
+            Some("columns were swapped")
+        );
+        match &lens.forward.ops[0] {
+            LensOp::RenameColumn {
+                old_name, new_name, ..
+            } => {
+                assert_eq!(old_name, "email");
+                assert_eq!(new_name, "email_address");
+            }
+            other => panic!("expected RenameColumn op, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn admin_subscription_introspection_requires_admin_secret_and_valid_app_id() {
         let schema = SchemaBuilder::new()
@@ -2199,6 +2474,67 @@ mod tests {
         }));
     }
 
+    #[tokio::test]
+    async fn admin_explain_describes_compiled_query_graph() {
+        let schema = SchemaBuilder::new()
+            .table(
+                TableSchema::builder("users")
+                    .column("id", ColumnType::Uuid)
+                    .column("name", ColumnType::Text),
+            )
+            .build();
+        let state = make_state_with_schema(schema).await;
+        let query = QueryBuilder::new("users")
+            .filter_eq("name", QueryValue::Text("Alice".to_string()))
+            .build();
+        let request_body = serde_json::json!({ "query": query, "analyze": true }).to_string();
+
+        let without_secret = make_test_router(state.clone())
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri(test_app_route("/admin/explain"))
+                    .header("Content-Type", "application/json")
+                    .body(axum::body::Body::from(request_body.clone()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(without_secret.status(), StatusCode::UNAUTHORIZED);
+
+        let response = make_test_router(state)
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri(test_app_route("/admin/explain"))
+                    .header("Content-Type", "application/json")
+                    .header("X-Jazz-Admin-Secret", "admin-secret")
+                    .body(axum::body::Body::from(request_body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("explain body");
+        let json: Value = serde_json::from_slice(&body).expect("explain json");
+
+        assert!(json["relationIr"].is_object());
+        assert!(json["estimatedCost"].as_u64().is_some());
+        let nodes = json["nodes"].as_array().expect("nodes array");
+        let scan = nodes
+            .iter()
+            .find(|node| node["kind"].as_str() == Some("IndexScan"))
+            .expect("query should compile to an index scan");
+        assert_eq!(scan["indexScan"]["column"].as_str(), Some("name"));
+        assert_eq!(scan["indexScan"]["condition"].as_str(), Some("= 'Alice'"));
+        assert!(
+            nodes.iter().all(|node| node["counters"].is_object()),
+            "analyze reports counters for every node"
+        );
+    }
+
     #[tokio::test]
     async fn ws_handler_dispatches_connection_schema_diagnostics_for_mismatched_schema() {
         // When a client connects with a schema hash that does not match the server's
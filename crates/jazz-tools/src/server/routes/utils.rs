@@ -12,7 +12,7 @@ use crate::query_manager::types::{SchemaHash, TableName, TablePolicies};
 use crate::schema_manager::AppId;
 use crate::server::ServerState;
 
-use super::http::PermissionsHeadView;
+use super::http::{PermissionsHeadView, ReleaseView};
 
 pub(super) fn parse_schema_hash_param(hash_text: &str) -> Result<SchemaHash, String> {
     let decoded_hash_bytes = hex::decode(hash_text)
@@ -82,6 +82,18 @@ pub(super) fn permissions_head_view(
     }
 }
 
+pub(super) fn release_view(release: crate::schema_manager::manager::ReleaseSummary) -> ReleaseView {
+    ReleaseView {
+        object_id: release.object_id.to_string(),
+        version: release.version,
+        parent_release_object_id: release
+            .parent_release_object_id
+            .map(|object_id| object_id.to_string()),
+        schema_hash: release.schema_hash.to_string(),
+        permissions_version: release.permissions_version,
+    }
+}
+
 pub(super) fn permissions_map_view(
     permissions: std::collections::HashMap<TableName, TablePolicies>,
 ) -> std::collections::HashMap<String, TablePolicies> {
@@ -459,7 +459,18 @@ async fn handle_ws_connection(
         .register_connection(connection_id, client_id);
     {
         let mut connections = state.connections.write().await;
-        connections.insert(connection_id, ConnectionState { client_id });
+        connections.insert(
+            connection_id,
+            ConnectionState {
+                client_id,
+                declared_schema_hash: handshake.declared_schema_hash(),
+            },
+        );
+    }
+    if let Some(declared_hash) = handshake.declared_schema_hash()
+        && let Err(err) = state.runtime.client_schema_connected(declared_hash)
+    {
+        tracing::warn!(%client_id, "failed to record client schema: {err}");
     }
     state.on_client_connected(client_id).await;
 
@@ -474,6 +485,9 @@ async fn handle_ws_connection(
                 );
         }
         WsClientSetup::Session(session) => {
+            state
+                .rate_limiter
+                .register_client(client_id, &session.user_id);
             let _ = state
                 .runtime
                 .ensure_client_with_session_and_catalogue_state_hash(
@@ -622,9 +636,14 @@ async fn handle_ws_connection(
 
 /// Disconnect cleanup: mirrors the drop path in `events_handler`.
 async fn ws_cleanup(state: &Arc<ServerState>, connection_id: u64, client_id: ClientId) {
-    {
+    let removed = {
         let mut connections = state.connections.write().await;
-        connections.remove(&connection_id);
+        connections.remove(&connection_id)
+    };
+    if let Some(declared_hash) = removed.and_then(|connection| connection.declared_schema_hash)
+        && let Err(err) = state.runtime.client_schema_disconnected(declared_hash)
+    {
+        tracing::warn!(%client_id, "failed to record client schema disconnect: {err}");
     }
     state
         .connection_event_hub
@@ -0,0 +1,880 @@
+//! Outbound change data capture: settled batches delivered to HTTP webhooks.
+//!
+//! On the core server every row of a batch that settles at `GlobalServer` is
+//! appended to the storage change log (see
+//! [`crate::sync_manager::change_capture`]). One delivery task per configured
+//! [`WebhookEndpoint`] tails that log from its persisted cursor and POSTs each
+//! settled batch's rows for the endpoint's tables as JSON:
+//!
+//! ```json
+//! {"webhook":"analytics","batchId":"…","mode":"transactional","changes":[
+//!   {"seq":42,"table":"todos","rowId":"…","branch":"…","op":"upsert",
+//!    "updatedAt":1700000000000000,"updatedBy":"…","schemaHash":"…","values":{…}}]}
+//! ```
+//!
+//! Requests are signed with `X-Jazz-Signature: t=<unix secs>,v1=<hex>`, an
+//! HMAC-SHA256 over `"<t>.<body>"` keyed by the endpoint secret, and carry an
+//! `X-Jazz-Delivery` id that stays stable across retries. Delivery is
+//! at-least-once and in log order: a failing endpoint is retried with
+//! exponential backoff and holds its cursor without blocking the others.
+//! Events every endpoint has consumed are pruned once they fall more than
+//! `retained_events` behind; the admin routes can rewind an endpoint's cursor
+//! anywhere into the retained range to replay.
+
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::Notify;
+
+use crate::runtime_tokio::TokioRuntime;
+use crate::server::{DynStorage, ServerState};
+use crate::storage::{
+    ChangeEvent, ChangeLogBounds, StorageError, change_log_bounds, load_change_cursor,
+    prune_change_events, scan_change_events, store_change_cursor,
+};
+
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Jazz-Signature";
+pub const WEBHOOK_NAME_HEADER: &str = "X-Jazz-Webhook";
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-Jazz-Delivery";
+
+/// One HTTP endpoint receiving settled changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    /// Stable name; keys the endpoint's persisted cursor.
+    pub name: String,
+    pub url: String,
+    /// HMAC key for `X-Jazz-Signature`.
+    pub secret: String,
+    /// Tables delivered to this endpoint. Empty delivers every table.
+    #[serde(default)]
+    pub tables: Vec<String>,
+}
+
+impl WebhookEndpoint {
+    fn accepts(&self, table: &str) -> bool {
+        self.tables.is_empty() || self.tables.iter().any(|accepted| accepted == table)
+    }
+
+    fn cursor_subscriber(&self) -> String {
+        format!("webhook:{}", self.name)
+    }
+}
+
+/// Webhook endpoints plus delivery tuning. The default configures none.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub endpoints: Vec<WebhookEndpoint>,
+    /// Change events read from the log at a time. A settled batch larger than
+    /// this is still delivered in a single request.
+    pub max_events_per_request: usize,
+    /// How often an idle endpoint checks the log for new events.
+    pub poll_interval_ms: u64,
+    pub request_timeout_ms: u64,
+    /// First retry delay; doubles per consecutive failure up to `max_backoff_ms`.
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// Delivered events kept behind the slowest endpoint for replay.
+    pub retained_events: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            max_events_per_request: 500,
+            poll_interval_ms: 1_000,
+            request_timeout_ms: 10_000,
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 5 * 60 * 1_000,
+            retained_events: 10_000,
+        }
+    }
+}
+
+impl WebhookConfig {
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_events_per_request == 0 {
+            return Err("webhook max_events_per_request must be at least 1".to_string());
+        }
+        let mut names = std::collections::HashSet::new();
+        for endpoint in &self.endpoints {
+            if endpoint.name.trim().is_empty() {
+                return Err("webhook endpoints need a non-empty name".to_string());
+            }
+            if !names.insert(endpoint.name.as_str()) {
+                return Err(format!("duplicate webhook name '{}'", endpoint.name));
+            }
+            if endpoint.secret.is_empty() {
+                return Err(format!("webhook '{}' needs a secret", endpoint.name));
+            }
+            let url = reqwest::Url::parse(&endpoint.url)
+                .map_err(|err| format!("invalid URL for webhook '{}': {err}", endpoint.name))?;
+            if !matches!(url.scheme(), "http" | "https") {
+                return Err(format!(
+                    "webhook '{}' URL must use http or https",
+                    endpoint.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn backoff(&self, consecutive_failures: u32) -> Duration {
+        let exponent = consecutive_failures.saturating_sub(1).min(32);
+        let delay = self.initial_backoff_ms.saturating_mul(1 << exponent);
+        Duration::from_millis(delay.min(self.max_backoff_ms))
+    }
+}
+
+/// Live delivery state of one endpoint.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeliveryStatus {
+    /// Last change-log sequence delivered (or skipped as out of scope).
+    pub cursor: u64,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    /// Unix milliseconds.
+    pub last_delivered_at: Option<u64>,
+    /// Unix milliseconds of the next retry while failing.
+    pub next_attempt_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookStatusView {
+    pub name: String,
+    pub url: String,
+    pub tables: Vec<String>,
+    /// Change-log events after the cursor, across all tables.
+    pub pending_events: u64,
+    #[serde(flatten)]
+    pub delivery: WebhookDeliveryStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookAdminError {
+    UnknownWebhook(String),
+    /// The requested events were already pruned from the change log.
+    Pruned {
+        from_seq: u64,
+        pruned_through: u64,
+    },
+    Storage(String),
+}
+
+impl std::fmt::Display for WebhookAdminError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownWebhook(name) => write!(f, "unknown webhook '{name}'"),
+            Self::Pruned {
+                from_seq,
+                pruned_through,
+            } => write!(
+                f,
+                "cannot replay from {from_seq}: events through {pruned_through} were pruned"
+            ),
+            Self::Storage(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+struct EndpointState {
+    endpoint: WebhookEndpoint,
+    status: Mutex<WebhookDeliveryStatus>,
+    wake: Notify,
+}
+
+/// Delivers the change log to the configured webhook endpoints.
+pub struct WebhookDispatcher {
+    config: WebhookConfig,
+    http_client: reqwest::Client,
+    endpoints: Vec<EndpointState>,
+}
+
+impl WebhookDispatcher {
+    pub fn new(config: WebhookConfig) -> Result<Self, String> {
+        config.validate()?;
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.request_timeout_ms))
+            .build()
+            .map_err(|e| format!("failed to build webhook HTTP client: {e}"))?;
+        let endpoints = config
+            .endpoints
+            .iter()
+            .cloned()
+            .map(|endpoint| EndpointState {
+                endpoint,
+                status: Mutex::new(WebhookDeliveryStatus::default()),
+                wake: Notify::new(),
+            })
+            .collect();
+        Ok(Self {
+            config,
+            http_client,
+            endpoints,
+        })
+    }
+
+    fn endpoint(&self, name: &str) -> Result<&EndpointState, WebhookAdminError> {
+        self.endpoints
+            .iter()
+            .find(|state| state.endpoint.name == name)
+            .ok_or_else(|| WebhookAdminError::UnknownWebhook(name.to_string()))
+    }
+
+    pub fn statuses(
+        &self,
+        runtime: &TokioRuntime<DynStorage>,
+    ) -> Result<(ChangeLogBounds, Vec<WebhookStatusView>), WebhookAdminError> {
+        let bounds = read_storage(runtime, change_log_bounds)?;
+        let views = self
+            .endpoints
+            .iter()
+            .map(|state| {
+                let delivery = state.status.lock().unwrap().clone();
+                WebhookStatusView {
+                    name: state.endpoint.name.clone(),
+                    url: state.endpoint.url.clone(),
+                    tables: state.endpoint.tables.clone(),
+                    pending_events: bounds
+                        .last_seq
+                        .saturating_sub(delivery.cursor.max(bounds.pruned_through)),
+                    delivery,
+                }
+            })
+            .collect();
+        Ok((bounds, views))
+    }
+
+    /// The endpoint's undelivered changes among the next `limit` log events.
+    pub fn queued_changes(
+        &self,
+        runtime: &TokioRuntime<DynStorage>,
+        name: &str,
+        limit: usize,
+    ) -> Result<Vec<serde_json::Value>, WebhookAdminError> {
+        let state = self.endpoint(name)?;
+        let cursor = state.status.lock().unwrap().cursor;
+        let events = read_storage(runtime, |storage| {
+            let bounds = change_log_bounds(storage)?;
+            scan_change_events(storage, cursor.max(bounds.pruned_through), limit)
+        })?;
+        Ok(events
+            .iter()
+            .filter(|event| state.endpoint.accepts(&event.table))
+            .map(change_json)
+            .collect())
+    }
+
+    /// Rewind (or fast-forward) `name` so delivery resumes at `from_seq`.
+    pub fn replay(
+        &self,
+        runtime: &TokioRuntime<DynStorage>,
+        name: &str,
+        from_seq: u64,
+    ) -> Result<u64, WebhookAdminError> {
+        let state = self.endpoint(name)?;
+        let cursor = from_seq.saturating_sub(1);
+        let bounds = read_storage(runtime, change_log_bounds)?;
+        if cursor < bounds.pruned_through {
+            return Err(WebhookAdminError::Pruned {
+                from_seq,
+                pruned_through: bounds.pruned_through,
+            });
+        }
+        let subscriber = state.endpoint.cursor_subscriber();
+        runtime
+            .with_storage_mut(|storage| store_change_cursor(storage, &subscriber, cursor))
+            .map_err(|err| WebhookAdminError::Storage(err.to_string()))?
+            .map_err(|err| WebhookAdminError::Storage(err.to_string()))?;
+        *state.status.lock().unwrap() = WebhookDeliveryStatus {
+            cursor,
+            ..Default::default()
+        };
+        state.wake.notify_one();
+        tracing::info!(webhook = name, from_seq, "webhook replay requested");
+        Ok(cursor)
+    }
+
+    /// Try to deliver the next settled batch; returns how long to wait before
+    /// the next attempt.
+    async fn deliver_next(&self, runtime: &TokioRuntime<DynStorage>, index: usize) -> Duration {
+        let state = &self.endpoints[index];
+        let cursor = state.status.lock().unwrap().cursor;
+        let batch = match read_storage(runtime, |storage| {
+            let bounds = change_log_bounds(storage)?;
+            scan_next_batch(
+                storage,
+                cursor.max(bounds.pruned_through),
+                self.config.max_events_per_request,
+            )
+        }) {
+            Ok(batch) => batch,
+            Err(error) => return self.record_failure(state, error.to_string()),
+        };
+        let Some(last) = batch.last() else {
+            return Duration::from_millis(self.config.poll_interval_ms);
+        };
+
+        // One request per settled batch, so receivers see transactions whole.
+        let through_seq = last.seq;
+        let changes = batch
+            .iter()
+            .filter(|event| state.endpoint.accepts(&event.table))
+            .collect::<Vec<_>>();
+        let delivered = !changes.is_empty();
+        if delivered && let Err(error) = self.post(&state.endpoint, &changes).await {
+            return self.record_failure(state, error);
+        }
+
+        self.advance(runtime, state, cursor, through_seq, delivered);
+        Duration::ZERO
+    }
+
+    async fn post(
+        &self,
+        endpoint: &WebhookEndpoint,
+        changes: &[&ChangeEvent],
+    ) -> Result<(), String> {
+        let first = changes[0];
+        let body = serde_json::to_vec(&serde_json::json!({
+            "webhook": endpoint.name,
+            "batchId": first.batch_id.to_string(),
+            "mode": first.mode,
+            "changes": changes.iter().map(|event| change_json(event)).collect::<Vec<_>>(),
+        }))
+        .map_err(|e| format!("failed to encode webhook body: {e}"))?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let signature = webhook_signature(&endpoint.secret, timestamp, &body);
+
+        let response = self
+            .http_client
+            .post(&endpoint.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_NAME_HEADER, &endpoint.name)
+            .header(
+                WEBHOOK_DELIVERY_HEADER,
+                format!("{}:{}", first.batch_id, first.seq),
+            )
+            .header(
+                WEBHOOK_SIGNATURE_HEADER,
+                format!("t={timestamp},v1={signature}"),
+            )
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("webhook request failed: {e}"))?;
+        if !response.status().is_success() {
+            return Err(format!("webhook endpoint returned {}", response.status()));
+        }
+        Ok(())
+    }
+
+    fn record_failure(&self, state: &EndpointState, error: String) -> Duration {
+        let mut status = state.status.lock().unwrap();
+        status.consecutive_failures += 1;
+        let delay = self.config.backoff(status.consecutive_failures);
+        tracing::warn!(
+            webhook = %state.endpoint.name,
+            cursor = status.cursor,
+            consecutive_failures = status.consecutive_failures,
+            retry_in_ms = delay.as_millis() as u64,
+            %error,
+            "webhook delivery failed"
+        );
+        status.last_error = Some(error);
+        status.next_attempt_at = Some(unix_millis() + delay.as_millis() as u64);
+        delay
+    }
+
+    fn advance(
+        &self,
+        runtime: &TokioRuntime<DynStorage>,
+        state: &EndpointState,
+        from_cursor: u64,
+        through_seq: u64,
+        delivered: bool,
+    ) {
+        {
+            let mut status = state.status.lock().unwrap();
+            // A replay moved the cursor while this batch was in flight.
+            if status.cursor != from_cursor {
+                return;
+            }
+            status.cursor = through_seq;
+            status.consecutive_failures = 0;
+            status.last_error = None;
+            status.next_attempt_at = None;
+            if delivered {
+                status.last_delivered_at = Some(unix_millis());
+            }
+        }
+
+        let subscriber = state.endpoint.cursor_subscriber();
+        let prune_through = self
+            .endpoints
+            .iter()
+            .map(|state| state.status.lock().unwrap().cursor)
+            .min()
+            .unwrap_or(through_seq)
+            .saturating_sub(self.config.retained_events);
+        let persisted = runtime.with_storage_mut(|storage| {
+            store_change_cursor(storage, &subscriber, through_seq)?;
+            prune_change_events(storage, prune_through)
+        });
+        if let Err(error) = persisted
+            .map_err(|err| err.to_string())
+            .and_then(|result| result.map_err(|err| err.to_string()))
+        {
+            tracing::error!(
+                webhook = %state.endpoint.name,
+                through_seq,
+                %error,
+                "failed to persist webhook cursor"
+            );
+        }
+    }
+}
+
+/// Load persisted cursors and start one delivery task per endpoint.
+///
+/// Tasks hold only a `Weak` reference and exit once the server state is
+/// dropped or shutdown begins.
+pub(crate) fn start_webhook_delivery(state: &Arc<ServerState>) -> Result<(), String> {
+    let Some(dispatcher) = state.webhooks.as_ref() else {
+        return Ok(());
+    };
+    for (index, endpoint_state) in dispatcher.endpoints.iter().enumerate() {
+        let subscriber = endpoint_state.endpoint.cursor_subscriber();
+        let cursor = read_storage(&state.runtime, |storage| {
+            load_change_cursor(storage, &subscriber)
+        })
+        .map_err(|e| format!("failed to load webhook cursor: {e}"))?;
+        endpoint_state.status.lock().unwrap().cursor = cursor;
+        tracing::info!(
+            webhook = %endpoint_state.endpoint.name,
+            url = %endpoint_state.endpoint.url,
+            cursor,
+            "starting webhook delivery"
+        );
+        tokio::spawn(run_endpoint(Arc::downgrade(state), index));
+    }
+    Ok(())
+}
+
+async fn run_endpoint(weak_state: Weak<ServerState>, index: usize) {
+    loop {
+        let (dispatcher, wait) = {
+            let Some(state) = weak_state.upgrade() else {
+                break;
+            };
+            if state.shutdown.is_shutting_down() {
+                break;
+            }
+            let dispatcher = Arc::clone(state.webhooks.as_ref().expect("webhooks configured"));
+            let wait = dispatcher.deliver_next(&state.runtime, index).await;
+            (dispatcher, wait)
+        };
+        if wait.is_zero() {
+            continue;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = dispatcher.endpoints[index].wake.notified() => {}
+        }
+    }
+}
+
+fn read_storage<R>(
+    runtime: &TokioRuntime<DynStorage>,
+    f: impl FnOnce(&DynStorage) -> Result<R, StorageError>,
+) -> Result<R, WebhookAdminError> {
+    runtime
+        .with_storage(f)
+        .map_err(|err| WebhookAdminError::Storage(err.to_string()))?
+        .map_err(|err| WebhookAdminError::Storage(err.to_string()))
+}
+
+/// Events of the first settled batch after `after_seq`, read `page_size` at a
+/// time until the batch ends. A batch's rows are appended contiguously.
+fn scan_next_batch(
+    storage: &DynStorage,
+    after_seq: u64,
+    page_size: usize,
+) -> Result<Vec<ChangeEvent>, StorageError> {
+    let mut batch: Vec<ChangeEvent> = Vec::new();
+    let mut after_seq = after_seq;
+    loop {
+        let page = scan_change_events(storage, after_seq, page_size)?;
+        let Some(batch_id) = batch.first().or(page.first()).map(|event| event.batch_id) else {
+            return Ok(batch);
+        };
+        let before = batch.len();
+        batch.extend(
+            page.into_iter()
+                .take_while(|event| event.batch_id == batch_id),
+        );
+        // Anything short of a full page of this batch means it ended here.
+        if batch.len() - before < page_size {
+            return Ok(batch);
+        }
+        after_seq = batch.last().expect("page was not empty").seq;
+    }
+}
+
+fn change_json(event: &ChangeEvent) -> serde_json::Value {
+    let values = event
+        .values_json
+        .as_deref()
+        .and_then(|values| serde_json::from_str(values).ok())
+        .unwrap_or(serde_json::Value::Null);
+    serde_json::json!({
+        "seq": event.seq,
+        "table": event.table,
+        "rowId": event.row_id.to_string(),
+        "branch": event.branch,
+        "op": if event.is_deleted { "delete" } else { "upsert" },
+        "updatedAt": event.updated_at,
+        "updatedBy": event.updated_by,
+        "schemaHash": event.schema_hash.to_string(),
+        "values": values,
+    })
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Hex HMAC-SHA256 of `"<timestamp>.<body>"`, as sent in `X-Jazz-Signature`.
+pub fn webhook_signature(secret: &str, timestamp: u64, body: &[u8]) -> String {
+    let mut message = format!("{timestamp}.").into_bytes();
+    message.extend_from_slice(body);
+    hex::encode(hmac_sha256(secret.as_bytes(), &message))
+}
+
+/// HMAC-SHA256 (RFC 2104).
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch_fate::BatchMode;
+    use crate::object::ObjectId;
+    use crate::query_manager::types::SchemaHash;
+    use crate::row_histories::BatchId;
+    use crate::schema_manager::AppId;
+    use crate::server::{ServerBuilder, StorageBackend};
+    use crate::storage::append_change_event;
+    use axum::http::{HeaderMap, StatusCode};
+    use std::sync::atomic::{AtomicU16, Ordering};
+
+    #[derive(Clone, Default)]
+    struct Receiver {
+        requests: Arc<Mutex<Vec<(HeaderMap, serde_json::Value)>>>,
+        status: Arc<AtomicU16>,
+    }
+
+    impl Receiver {
+        fn requests(&self) -> Vec<(HeaderMap, serde_json::Value)> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    async fn spawn_receiver() -> (Receiver, String) {
+        let receiver = Receiver::default();
+        receiver.status.store(200, Ordering::SeqCst);
+        let recorded = receiver.clone();
+        let app = axum::Router::new().route(
+            "/hook",
+            axum::routing::post(move |headers: HeaderMap, body: axum::body::Bytes| {
+                let recorded = recorded.clone();
+                async move {
+                    let json = serde_json::from_slice(&body).expect("webhook body is JSON");
+                    recorded.requests.lock().unwrap().push((headers, json));
+                    StatusCode::from_u16(recorded.status.load(Ordering::SeqCst)).unwrap()
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind webhook receiver");
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app)
+                .await
+                .expect("serve webhook receiver");
+        });
+        (receiver, url)
+    }
+
+    fn endpoint(name: &str, url: &str, tables: &[&str]) -> WebhookEndpoint {
+        WebhookEndpoint {
+            name: name.to_string(),
+            url: url.to_string(),
+            secret: format!("{name}-secret"),
+            tables: tables.iter().map(|table| table.to_string()).collect(),
+        }
+    }
+
+    fn fast_config(endpoints: Vec<WebhookEndpoint>) -> WebhookConfig {
+        WebhookConfig {
+            endpoints,
+            poll_interval_ms: 10,
+            initial_backoff_ms: 10,
+            max_backoff_ms: 20,
+            ..Default::default()
+        }
+    }
+
+    async fn build_state(config: WebhookConfig) -> Arc<ServerState> {
+        ServerBuilder::new(AppId::from_name("webhook-test"))
+            .with_storage(StorageBackend::InMemory)
+            .with_webhooks(config)
+            .build()
+            .await
+            .expect("build server with webhooks")
+            .state
+    }
+
+    fn append(state: &ServerState, batch: u8, table: &str) {
+        let event = change_event(batch, table);
+        state
+            .runtime
+            .with_storage_mut(|storage| append_change_event(storage, event))
+            .expect("storage lock")
+            .expect("append change event");
+    }
+
+    fn change_event(batch: u8, table: &str) -> ChangeEvent {
+        ChangeEvent {
+            seq: 0,
+            batch_id: BatchId([batch; 16]),
+            mode: BatchMode::Transactional,
+            table: table.to_string(),
+            schema_hash: SchemaHash([3; 32]),
+            row_id: ObjectId::new(),
+            branch: "main".to_string(),
+            updated_at: 1_000,
+            updated_by: "alice".to_string(),
+            is_deleted: false,
+            values_json: Some(r#"{"title":{"type":"Text","value":"a"}}"#.to_string()),
+        }
+    }
+
+    async fn wait_for_requests(receiver: &Receiver, count: usize) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while receiver.requests().len() < count {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("expected {count} webhook requests"));
+    }
+
+    #[test]
+    fn hmac_matches_rfc_4231_test_vectors() {
+        assert_eq!(
+            hex::encode(hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Test case 6: a key longer than the block size.
+        assert_eq!(
+            hex::encode(hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn config_validation_rejects_duplicate_names_and_bad_urls() {
+        let duplicate = fast_config(vec![
+            endpoint("crm", "https://example.com/a", &[]),
+            endpoint("crm", "https://example.com/b", &[]),
+        ]);
+        assert!(duplicate.validate().unwrap_err().contains("duplicate"));
+
+        let bad_scheme = fast_config(vec![endpoint("crm", "ftp://example.com", &[])]);
+        assert!(bad_scheme.validate().unwrap_err().contains("http"));
+
+        let config = WebhookConfig::default();
+        assert_eq!(config.backoff(1), Duration::from_secs(1));
+        assert_eq!(config.backoff(3), Duration::from_secs(4));
+        assert_eq!(config.backoff(30), Duration::from_secs(300));
+    }
+
+    #[tokio::test]
+    async fn delivers_signed_batches_in_order_and_filters_tables() {
+        let (receiver, url) = spawn_receiver().await;
+        let state = build_state(fast_config(vec![endpoint("crm", &url, &["todos"])])).await;
+
+        append(&state, 1, "todos");
+        append(&state, 1, "todos");
+        append(&state, 2, "secrets");
+        append(&state, 3, "todos");
+        wait_for_requests(&receiver, 2).await;
+
+        let requests = receiver.requests();
+        let (headers, body) = &requests[0];
+        assert_eq!(body["webhook"], "crm");
+        assert_eq!(body["mode"], "transactional");
+        assert_eq!(body["changes"].as_array().unwrap().len(), 2);
+        assert_eq!(body["changes"][0]["seq"], 1);
+        assert_eq!(body["changes"][0]["op"], "upsert");
+        assert_eq!(body["changes"][0]["values"]["title"]["value"], "a");
+
+        let signature = headers[WEBHOOK_SIGNATURE_HEADER].to_str().unwrap();
+        let (timestamp, digest) = signature
+            .strip_prefix("t=")
+            .and_then(|rest| rest.split_once(",v1="))
+            .expect("signature header format");
+        let body_bytes = serde_json::to_vec(body).unwrap();
+        assert_eq!(
+            digest,
+            webhook_signature("crm-secret", timestamp.parse().unwrap(), &body_bytes)
+        );
+
+        // The `secrets` batch was skipped, not delivered.
+        assert_eq!(requests[1].1["changes"][0]["seq"], 4);
+        assert_eq!(requests.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn batches_larger_than_a_page_are_delivered_whole() {
+        let (receiver, url) = spawn_receiver().await;
+        let state = build_state(WebhookConfig {
+            max_events_per_request: 2,
+            ..fast_config(vec![endpoint("crm", &url, &[])])
+        })
+        .await;
+
+        // Append the batch in one go, as settlement does, so the poller
+        // can't catch it half-written.
+        state
+            .runtime
+            .with_storage_mut(|storage| {
+                (0..5).try_for_each(|_| {
+                    append_change_event(storage, change_event(1, "todos")).map(|_| ())
+                })
+            })
+            .expect("storage lock")
+            .expect("append change events");
+        append(&state, 2, "todos");
+        wait_for_requests(&receiver, 2).await;
+
+        let requests = receiver.requests();
+        let seqs = |body: &serde_json::Value| {
+            body["changes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|change| change["seq"].as_u64().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(seqs(&requests[0].1), vec![1, 2, 3, 4, 5]);
+        assert_eq!(seqs(&requests[1].1), vec![6]);
+        assert_eq!(requests.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_deliveries_retry_with_the_same_delivery_id_and_can_be_replayed() {
+        let (receiver, url) = spawn_receiver().await;
+        receiver.status.store(500, Ordering::SeqCst);
+        let state = build_state(fast_config(vec![endpoint("crm", &url, &[])])).await;
+        let dispatcher = Arc::clone(state.webhooks.as_ref().unwrap());
+
+        append(&state, 1, "todos");
+        wait_for_requests(&receiver, 2).await;
+        let (_, statuses) = dispatcher.statuses(&state.runtime).unwrap();
+        assert_eq!(statuses[0].delivery.cursor, 0);
+        assert!(statuses[0].delivery.consecutive_failures >= 1);
+        assert_eq!(
+            dispatcher
+                .queued_changes(&state.runtime, "crm", 10)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        receiver.status.store(200, Ordering::SeqCst);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while dispatcher.statuses(&state.runtime).unwrap().1[0]
+                .delivery
+                .cursor
+                < 1
+            {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("delivery recovers once the endpoint succeeds");
+
+        let requests = receiver.requests();
+        let delivery_ids = requests
+            .iter()
+            .map(|(headers, _)| headers[WEBHOOK_DELIVERY_HEADER].clone())
+            .collect::<Vec<_>>();
+        assert!(delivery_ids.windows(2).all(|ids| ids[0] == ids[1]));
+        assert_eq!(
+            state
+                .runtime
+                .with_storage(|storage| load_change_cursor(storage, "webhook:crm"))
+                .unwrap(),
+            Ok(1)
+        );
+
+        let delivered = requests.len();
+        assert_eq!(dispatcher.replay(&state.runtime, "crm", 1), Ok(0));
+        wait_for_requests(&receiver, delivered + 1).await;
+        assert_eq!(receiver.requests()[delivered].1["changes"][0]["seq"], 1);
+        assert_eq!(
+            dispatcher.replay(&state.runtime, "missing", 1),
+            Err(WebhookAdminError::UnknownWebhook("missing".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn edge_servers_reject_webhooks() {
+        let result = ServerBuilder::new(AppId::from_name("webhook-edge"))
+            .with_storage(StorageBackend::InMemory)
+            .with_auth_config(crate::middleware::AuthConfig {
+                admin_secret: Some("admin-secret".to_string()),
+                ..Default::default()
+            })
+            .with_upstream_url("ws://127.0.0.1:9")
+            .with_webhooks(fast_config(vec![endpoint(
+                "crm",
+                "http://127.0.0.1:9",
+                &[],
+            )]))
+            .build()
+            .await;
+
+        assert!(
+            result
+                .err()
+                .expect("edge build should fail")
+                .contains("--webhooks")
+        );
+    }
+}
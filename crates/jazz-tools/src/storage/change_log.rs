@@ -0,0 +1,353 @@
+//! Durable log of settled rows for outbound change data capture.
+//!
+//! The core server appends one [`ChangeEvent`] per row of every batch that
+//! settles at `GlobalServer` (`DurableDirect` or `AcceptedTransaction`).
+//! Events get dense, increasing sequence numbers, so a subscriber only has to
+//! persist the last sequence it consumed ([`store_change_cursor`]) to resume
+//! after a restart. Delivered events are pruned with
+//! [`prune_change_events`]; everything after the pruned prefix stays
+//! replayable.
+//!
+//! Row values are decoded with the schema they were written in at capture
+//! time, so consumers never need the server's schema catalogue.
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::batch_fate::BatchMode;
+
+const CHANGE_LOG_TABLE: &str = "__change_log";
+const CHANGE_LOG_META_TABLE: &str = "__change_log_meta";
+pub(super) const STORAGE_KIND_CHANGE_LOG: &str = "change_log";
+pub(super) const STORAGE_KIND_CHANGE_LOG_META: &str = "change_log_meta";
+pub(super) const CHANGE_LOG_FORMAT_V1: i32 = 1;
+pub(super) const CHANGE_LOG_META_FORMAT_V1: i32 = 1;
+const NEXT_SEQ_KEY: &str = "next_seq";
+const PRUNED_THROUGH_KEY: &str = "pruned_through";
+
+/// One settled row version, as captured for outbound delivery.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    /// Position in the change log. Assigned on append.
+    pub seq: u64,
+    pub batch_id: BatchId,
+    pub mode: BatchMode,
+    pub table: String,
+    pub schema_hash: SchemaHash,
+    pub row_id: ObjectId,
+    pub branch: String,
+    pub updated_at: u64,
+    pub updated_by: String,
+    pub is_deleted: bool,
+    /// Column name → value, serialized with [`Value`]'s JSON representation.
+    /// `None` for deleted rows.
+    pub values_json: Option<String>,
+}
+
+/// Sequence range currently retained in the change log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ChangeLogBounds {
+    /// Highest pruned sequence; events after it are replayable.
+    pub pruned_through: u64,
+    /// Highest appended sequence, or `pruned_through` when nothing is retained.
+    pub last_seq: u64,
+}
+
+impl ChangeEvent {
+    /// Build the event for `row`, decoding its values with the schema the row
+    /// was written in.
+    pub fn for_row<H: Storage + ?Sized>(
+        storage: &H,
+        table: &str,
+        mode: BatchMode,
+        row: &StoredRowBatch,
+    ) -> Result<Self, StorageError> {
+        let (schema_hash, descriptor) =
+            required_history_user_descriptor_and_schema_hash_for_row(storage, table, row)?;
+        let values_json = if row.is_deleted {
+            None
+        } else {
+            let values = decode_row(&descriptor, &row.data).map_err(|err| {
+                StorageError::IoError(format!("decode row for change log: {err:?}"))
+            })?;
+            let object = descriptor
+                .columns
+                .iter()
+                .zip(values)
+                .map(|(column, value)| {
+                    serde_json::to_value(value).map(|value| (column.name.to_string(), value))
+                })
+                .collect::<Result<serde_json::Map<_, _>, _>>()
+                .map_err(|err| StorageError::IoError(format!("encode change values: {err}")))?;
+            Some(serde_json::Value::Object(object).to_string())
+        };
+
+        Ok(Self {
+            seq: 0,
+            batch_id: row.batch_id,
+            mode,
+            table: table.to_string(),
+            schema_hash,
+            row_id: row.row_id,
+            branch: row.branch.to_string(),
+            updated_at: row.updated_at,
+            updated_by: row.updated_by.to_string(),
+            is_deleted: row.is_deleted,
+            values_json,
+        })
+    }
+
+    fn capture_key(&self) -> String {
+        capture_key(self.batch_id, self.row_id, &self.branch)
+    }
+}
+
+fn seq_key(seq: u64) -> String {
+    format!("{seq:020}")
+}
+
+fn capture_key(batch_id: BatchId, row_id: ObjectId, branch: &str) -> String {
+    format!("captured:{batch_id}:{row_id}:{branch}")
+}
+
+fn cursor_key(subscriber: &str) -> String {
+    format!("cursor:{subscriber}")
+}
+
+fn ensure_change_log_headers<H: Storage + ?Sized>(storage: &mut H) -> Result<(), StorageError> {
+    ensure_raw_table_header(
+        storage,
+        CHANGE_LOG_TABLE,
+        &RawTableHeader::system(STORAGE_KIND_CHANGE_LOG, CHANGE_LOG_FORMAT_V1),
+    )?;
+    ensure_raw_table_header(
+        storage,
+        CHANGE_LOG_META_TABLE,
+        &RawTableHeader::system(STORAGE_KIND_CHANGE_LOG_META, CHANGE_LOG_META_FORMAT_V1),
+    )
+}
+
+fn load_meta_u64<H: Storage + ?Sized>(storage: &H, key: &str) -> Result<Option<u64>, StorageError> {
+    let Some(bytes) = storage.raw_table_get(CHANGE_LOG_META_TABLE, key)? else {
+        return Ok(None);
+    };
+    ensure_system_raw_table_header_validated_once(
+        storage,
+        CHANGE_LOG_META_TABLE,
+        STORAGE_KIND_CHANGE_LOG_META,
+        CHANGE_LOG_META_FORMAT_V1,
+    )?;
+    let bytes: [u8; 8] = bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| StorageError::IoError(format!("invalid change log meta value for {key}")))?;
+    Ok(Some(u64::from_le_bytes(bytes)))
+}
+
+/// Append `event` unless the same row version was already captured.
+///
+/// Returns the assigned sequence, or `None` for a duplicate.
+pub fn append_change_event<H: Storage + ?Sized>(
+    storage: &mut H,
+    mut event: ChangeEvent,
+) -> Result<Option<u64>, StorageError> {
+    ensure_change_log_headers(storage)?;
+    let capture_key = event.capture_key();
+    if storage
+        .raw_table_get(CHANGE_LOG_META_TABLE, &capture_key)?
+        .is_some()
+    {
+        return Ok(None);
+    }
+
+    let seq = load_meta_u64(storage, NEXT_SEQ_KEY)?.unwrap_or(1);
+    event.seq = seq;
+    let bytes = postcard::to_allocvec(&event)
+        .map_err(|err| StorageError::IoError(format!("serialize change event: {err}")))?;
+    storage.raw_table_put(CHANGE_LOG_TABLE, &seq_key(seq), &bytes)?;
+    storage.raw_table_put(CHANGE_LOG_META_TABLE, &capture_key, &seq.to_le_bytes())?;
+    storage.raw_table_put(
+        CHANGE_LOG_META_TABLE,
+        NEXT_SEQ_KEY,
+        &(seq + 1).to_le_bytes(),
+    )?;
+    Ok(Some(seq))
+}
+
+/// Up to `limit` retained events with a sequence greater than `after_seq`.
+pub fn scan_change_events<H: Storage + ?Sized>(
+    storage: &H,
+    after_seq: u64,
+    limit: usize,
+) -> Result<Vec<ChangeEvent>, StorageError> {
+    if limit == 0 {
+        return Ok(Vec::new());
+    }
+    // Sequences are dense, so the key range bounds the scan to `limit` rows.
+    let start = seq_key(after_seq.saturating_add(1));
+    let end = seq_key(after_seq.saturating_add((limit as u64).saturating_add(1)));
+    let rows = storage.raw_table_scan_range(CHANGE_LOG_TABLE, Some(&start), Some(&end))?;
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+    ensure_system_raw_table_header_validated_once(
+        storage,
+        CHANGE_LOG_TABLE,
+        STORAGE_KIND_CHANGE_LOG,
+        CHANGE_LOG_FORMAT_V1,
+    )?;
+    rows.into_iter()
+        .map(|(_, bytes)| {
+            postcard::from_bytes(&bytes)
+                .map_err(|err| StorageError::IoError(format!("deserialize change event: {err}")))
+        })
+        .collect()
+}
+
+pub fn change_log_bounds<H: Storage + ?Sized>(
+    storage: &H,
+) -> Result<ChangeLogBounds, StorageError> {
+    let pruned_through = load_meta_u64(storage, PRUNED_THROUGH_KEY)?.unwrap_or(0);
+    let last_seq = load_meta_u64(storage, NEXT_SEQ_KEY)?
+        .map(|next_seq| next_seq - 1)
+        .unwrap_or(0)
+        .max(pruned_through);
+    Ok(ChangeLogBounds {
+        pruned_through,
+        last_seq,
+    })
+}
+
+/// Last sequence consumed by `subscriber`, or 0 when it has not started.
+pub fn load_change_cursor<H: Storage + ?Sized>(
+    storage: &H,
+    subscriber: &str,
+) -> Result<u64, StorageError> {
+    Ok(load_meta_u64(storage, &cursor_key(subscriber))?.unwrap_or(0))
+}
+
+pub fn store_change_cursor<H: Storage + ?Sized>(
+    storage: &mut H,
+    subscriber: &str,
+    seq: u64,
+) -> Result<(), StorageError> {
+    ensure_change_log_headers(storage)?;
+    storage.raw_table_put(
+        CHANGE_LOG_META_TABLE,
+        &cursor_key(subscriber),
+        &seq.to_le_bytes(),
+    )
+}
+
+/// Drop every event with a sequence up to and including `through_seq`.
+pub fn prune_change_events<H: Storage + ?Sized>(
+    storage: &mut H,
+    through_seq: u64,
+) -> Result<(), StorageError> {
+    let bounds = change_log_bounds(storage)?;
+    let through_seq = through_seq.min(bounds.last_seq);
+    if through_seq <= bounds.pruned_through {
+        return Ok(());
+    }
+    ensure_change_log_headers(storage)?;
+    let start = seq_key(bounds.pruned_through + 1);
+    let end = seq_key(through_seq + 1);
+    for (key, bytes) in storage.raw_table_scan_range(CHANGE_LOG_TABLE, Some(&start), Some(&end))? {
+        let event: ChangeEvent = postcard::from_bytes(&bytes)
+            .map_err(|err| StorageError::IoError(format!("deserialize change event: {err}")))?;
+        storage.raw_table_delete(CHANGE_LOG_META_TABLE, &event.capture_key())?;
+        storage.raw_table_delete(CHANGE_LOG_TABLE, &key)?;
+    }
+    storage.raw_table_put(
+        CHANGE_LOG_META_TABLE,
+        PRUNED_THROUGH_KEY,
+        &through_seq.to_le_bytes(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    fn event(batch: u8, table: &str) -> ChangeEvent {
+        ChangeEvent {
+            seq: 0,
+            batch_id: BatchId([batch; 16]),
+            mode: BatchMode::Direct,
+            table: table.to_string(),
+            schema_hash: SchemaHash([7; 32]),
+            row_id: ObjectId::new(),
+            branch: "main".to_string(),
+            updated_at: 1_000,
+            updated_by: "alice".to_string(),
+            is_deleted: false,
+            values_json: Some(r#"{"title":{"type":"Text","value":"a"}}"#.to_string()),
+        }
+    }
+
+    #[test]
+    fn appended_events_get_dense_sequences_and_dedupe() {
+        let mut storage = MemoryStorage::new();
+        let first = event(1, "todos");
+
+        assert_eq!(
+            append_change_event(&mut storage, first.clone()),
+            Ok(Some(1))
+        );
+        assert_eq!(append_change_event(&mut storage, first), Ok(None));
+        assert_eq!(
+            append_change_event(&mut storage, event(2, "todos")),
+            Ok(Some(2))
+        );
+
+        let events = scan_change_events(&storage, 0, 10).unwrap();
+        assert_eq!(
+            events.iter().map(|event| event.seq).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(scan_change_events(&storage, 1, 10).unwrap().len(), 1);
+        assert_eq!(scan_change_events(&storage, 0, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn scans_with_unbounded_limits_and_cursors_do_not_overflow() {
+        let mut storage = MemoryStorage::new();
+        append_change_event(&mut storage, event(1, "todos")).unwrap();
+
+        assert_eq!(
+            scan_change_events(&storage, 0, usize::MAX).unwrap().len(),
+            1
+        );
+        assert!(
+            scan_change_events(&storage, u64::MAX, usize::MAX)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn pruning_keeps_later_events_replayable() {
+        let mut storage = MemoryStorage::new();
+        for batch in 1..=3 {
+            append_change_event(&mut storage, event(batch, "todos")).unwrap();
+        }
+        store_change_cursor(&mut storage, "analytics", 2).unwrap();
+
+        prune_change_events(&mut storage, 2).unwrap();
+
+        assert_eq!(load_change_cursor(&storage, "analytics"), Ok(2));
+        assert_eq!(
+            change_log_bounds(&storage),
+            Ok(ChangeLogBounds {
+                pruned_through: 2,
+                last_seq: 3,
+            })
+        );
+        let events = scan_change_events(&storage, 0, 10).unwrap();
+        assert_eq!(
+            events.iter().map(|event| event.seq).collect::<Vec<_>>(),
+            vec![3]
+        );
+    }
+}
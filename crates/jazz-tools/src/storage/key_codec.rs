@@ -1,7 +1,7 @@
 use std::ops::Bound;
 
 use crate::object::ObjectId;
-use crate::query_manager::types::Value;
+use crate::query_manager::types::{Date, Decimal, GeoPoint, Interval, Time, Value};
 use crate::row_histories::BatchId;
 
 use super::{StorageError, encode_value};
@@ -50,6 +50,72 @@ fn append_batch_id_hex(dst: &mut String, batch_id: BatchId) {
     append_hex_bytes(dst, batch_id.as_bytes());
 }
 
+const DECIMAL_KEY_NEGATIVE: u8 = 0x01;
+const DECIMAL_KEY_ZERO: u8 = 0x02;
+const DECIMAL_KEY_POSITIVE: u8 = 0x03;
+const DECIMAL_KEY_EXPONENT_BIAS: i16 = 0x80;
+
+/// Append order-preserving key bytes for a decimal, independent of its scale
+/// (`1.5` and `1.50` share a key).
+///
+/// The value is written as `0.<digits> * 10^exponent`: a sign class, the
+/// biased exponent, then one byte per significant digit and a terminator.
+/// Negative numbers invert the exponent and digits so larger magnitudes sort
+/// first, and end in a terminator above every digit so `-0.12` sorts after
+/// `-0.123`.
+pub(super) fn append_decimal_key(dst: &mut Vec<u8>, value: &Decimal) {
+    if value.is_zero() {
+        dst.push(DECIMAL_KEY_ZERO);
+        return;
+    }
+    let normalized = value.normalized();
+    let digits = normalized.units().unsigned_abs().to_string();
+    let exponent = digits.len() as i16 - i16::from(normalized.scale());
+    let exponent_byte = (exponent + DECIMAL_KEY_EXPONENT_BIAS) as u8;
+    let significant = digits
+        .trim_end_matches('0')
+        .bytes()
+        .map(|digit| digit - b'0');
+
+    if value.is_negative() {
+        dst.push(DECIMAL_KEY_NEGATIVE);
+        dst.push(!exponent_byte);
+        dst.extend(significant.map(|digit| 0x0A - digit));
+        dst.push(0xFF);
+    } else {
+        dst.push(DECIMAL_KEY_POSITIVE);
+        dst.push(exponent_byte);
+        dst.extend(significant.map(|digit| 0x01 + digit));
+        dst.push(0x00);
+    }
+}
+
+/// Append order-preserving key bytes for a date: sign-flipped big-endian days.
+pub(super) fn append_date_key(dst: &mut Vec<u8>, value: &Date) {
+    dst.extend_from_slice(&(value.days_since_epoch() ^ i32::MIN).to_be_bytes());
+}
+
+/// Append order-preserving key bytes for a time of day.
+pub(super) fn append_time_key(dst: &mut Vec<u8>, value: &Time) {
+    dst.extend_from_slice(&value.micros_since_midnight().to_be_bytes());
+}
+
+/// Append order-preserving key bytes for an interval, matching [`Interval`]'s
+/// ordering: the approximate length first, then months, days and micros.
+pub(super) fn append_interval_key(dst: &mut Vec<u8>, value: &Interval) {
+    dst.extend_from_slice(&(value.approximate_micros() ^ i128::MIN).to_be_bytes());
+    dst.extend_from_slice(&(value.months() ^ i32::MIN).to_be_bytes());
+    dst.extend_from_slice(&(value.days() ^ i32::MIN).to_be_bytes());
+    dst.extend_from_slice(&(value.micros() ^ i64::MIN).to_be_bytes());
+}
+
+/// Geo points key on their Z-order cell only, so a cell range scan is a plain
+/// key range. Points sharing a finest-level cell share a key prefix; the
+/// row id suffix keeps their entries distinct.
+pub(super) fn append_geo_point_key(dst: &mut Vec<u8>, value: &GeoPoint) {
+    dst.extend_from_slice(&value.cell().to_be_bytes());
+}
+
 fn decode_hex_object_id(raw: &str, context: &str) -> Result<ObjectId, StorageError> {
     let bytes = hex::decode(raw)
         .map_err(|err| StorageError::IoError(format!("{context}: invalid row id hex: {err}")))?;
@@ -474,6 +540,58 @@ pub(super) fn increment_string(s: &mut String) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn decimal_keys_sort_numerically_across_scales() {
+        let key = |raw: &str| {
+            let mut bytes = Vec::new();
+            append_decimal_key(&mut bytes, &Decimal::parse(raw).unwrap());
+            bytes
+        };
+        let ascending = [
+            "-1200", "-5", "-0.12", "-0.1", "-0.0001", "0", "0.0001", "0.1", "0.12", "0.123", "1",
+            "1.5", "10", "1200",
+        ];
+        for pair in ascending.windows(2) {
+            assert!(
+                key(pair[0]) < key(pair[1]),
+                "{} should sort before {}",
+                pair[0],
+                pair[1]
+            );
+        }
+        assert!(key("-0.123") < key("-0.12"));
+        assert_eq!(key("1.5"), key("1.500"));
+        assert_eq!(key("0"), key("0.00"));
+    }
+
+    #[test]
+    fn temporal_keys_sort_chronologically() {
+        let date_key = |raw: &str| {
+            let mut bytes = Vec::new();
+            append_date_key(&mut bytes, &Date::parse(raw).unwrap());
+            bytes
+        };
+        assert!(date_key("1969-12-31") < date_key("1970-01-01"));
+        assert!(date_key("2024-02-29") < date_key("2024-03-01"));
+
+        let interval_key = |raw: &str| {
+            let mut bytes = Vec::new();
+            append_interval_key(&mut bytes, &Interval::parse(raw).unwrap());
+            bytes
+        };
+        let ascending = [
+            "-P1D", "PT0S", "PT1S", "P29D", "P30D", "P1M", "P1MT1S", "P1Y",
+        ];
+        for pair in ascending.windows(2) {
+            assert!(
+                interval_key(pair[0]) < interval_key(pair[1]),
+                "{} should sort before {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
     #[test]
     fn short_text_index_segments_stay_inline() {
         let segment =
@@ -28,7 +28,7 @@ impl TableRowHistories {
             .filter(|((history_branch, _), _)| history_branch.as_str() == branch)
             .map(|(_, row)| row.clone())
             .collect();
-        rows.sort_by_key(|row| (row.updated_at, row.batch_id()));
+        rows.sort_by_key(|row| row.lww_key());
         rows
     }
 }
@@ -1154,9 +1154,7 @@ impl Storage for MemoryStorage {
                             continue;
                         }
                         match latest_per_row.get(row_id) {
-                            Some(existing)
-                                if (existing.updated_at, existing.batch_id())
-                                    >= (row.updated_at, row.batch_id()) => {}
+                            Some(existing) if existing.lww_key() >= row.lww_key() => {}
                             _ => {
                                 latest_per_row.insert(*row_id, row.clone());
                             }
@@ -10,12 +10,17 @@
 //! postMessage, not shared mutable state.
 
 #[cfg(test)]
+mod change_log;
 pub mod conformance;
 mod key_codec;
 mod memory;
 mod opfs_btree;
 mod storage_core;
 mod storage_trait;
+pub use change_log::{
+    ChangeEvent, ChangeLogBounds, append_change_event, change_log_bounds, load_change_cursor,
+    prune_change_events, scan_change_events, store_change_cursor,
+};
 pub use memory::MemoryStorage;
 pub use opfs_btree::OpfsBTreeStorage;
 pub use storage_trait::Storage;
@@ -1096,6 +1101,8 @@ fn supported_storage_format_version(storage_kind: &str) -> Result<i32, StorageEr
         STORAGE_KIND_AUTHORITATIVE_BATCH_SETTLEMENT => Ok(AUTHORITATIVE_BATCH_SETTLEMENT_FORMAT_V2),
         STORAGE_KIND_ACKNOWLEDGED_REJECTED_BATCH => Ok(ACKNOWLEDGED_REJECTED_BATCH_FORMAT_V1),
         STORAGE_KIND_CATALOGUE => Ok(CATALOGUE_STORAGE_FORMAT_V1),
+        change_log::STORAGE_KIND_CHANGE_LOG => Ok(change_log::CHANGE_LOG_FORMAT_V1),
+        change_log::STORAGE_KIND_CHANGE_LOG_META => Ok(change_log::CHANGE_LOG_META_FORMAT_V1),
         "visible_rows" | "row_history" => Ok(ROW_STORAGE_FORMAT_V3),
         other => Err(StorageError::IoError(format!(
             "unknown raw table header storage_kind '{other}'"
@@ -3143,6 +3150,12 @@ pub(crate) fn encode_value(value: &Value) -> Vec<u8> {
             bytes
         }
 
+        Value::Decimal(decimal) => {
+            let mut bytes = vec![0x0B];
+            key_codec::append_decimal_key(&mut bytes, decimal);
+            bytes
+        }
+
         Value::Timestamp(ts) => {
             // Unsigned, big-endian (already sorts correctly)
             let mut bytes = vec![0x04];
@@ -3150,6 +3163,40 @@ pub(crate) fn encode_value(value: &Value) -> Vec<u8> {
             bytes
         }
 
+        Value::Date(date) => {
+            let mut bytes = vec![0x0C];
+            key_codec::append_date_key(&mut bytes, date);
+            bytes
+        }
+
+        Value::Time(time) => {
+            let mut bytes = vec![0x0D];
+            key_codec::append_time_key(&mut bytes, time);
+            bytes
+        }
+
+        Value::Interval(interval) => {
+            let mut bytes = vec![0x0E];
+            key_codec::append_interval_key(&mut bytes, interval);
+            bytes
+        }
+
+        Value::GeoPoint(point) => {
+            let mut bytes = vec![0x0F];
+            key_codec::append_geo_point_key(&mut bytes, point);
+            bytes
+        }
+
+        Value::Vector(vector) => {
+            // Raw components for exact-match semantics; vector columns are
+            // searched through HNSW, not ordered index scans.
+            let mut bytes = vec![0x10];
+            for component in vector.as_slice() {
+                bytes.extend_from_slice(&component.to_le_bytes());
+            }
+            bytes
+        }
+
         Value::Text(s) => {
             // UTF-8 bytes sort correctly for ASCII; good enough for now
             let mut bytes = vec![0x05];
@@ -103,6 +103,27 @@ impl RocksDBStorage {
         })
     }
 
+    /// Copy the store at `path` to `dest` while holding its lock.
+    ///
+    /// Holding the lock means no other process writes mid-copy, so the copy
+    /// is consistent. Fails when another process (e.g. a running server)
+    /// already has the store open.
+    pub fn copy_while_locked(
+        path: impl AsRef<Path>,
+        dest: impl AsRef<Path>,
+    ) -> Result<(), StorageError> {
+        let path = path.as_ref();
+        let locked = Self::open(path, 1024 * 1024).map_err(|err| {
+            StorageError::IoError(format!(
+                "rocksdb store '{}' could not be locked (is its server still running?): {err}",
+                path.display()
+            ))
+        })?;
+        let copied = copy_dir(path, dest.as_ref());
+        locked.close()?;
+        copied
+    }
+
     fn with_inner<T>(
         &self,
         f: impl FnOnce(&RocksDBInner) -> Result<T, StorageError>,
@@ -766,10 +787,54 @@ impl Storage for RocksDBStorage {
     }
 }
 
+fn copy_dir(from: &Path, to: &Path) -> Result<(), StorageError> {
+    let io_error = |action: &str, path: &Path, err: std::io::Error| {
+        StorageError::IoError(format!("rocksdb {action} '{}': {err}", path.display()))
+    };
+    std::fs::create_dir_all(to).map_err(|err| io_error("create", to, err))?;
+    for entry in std::fs::read_dir(from).map_err(|err| io_error("read", from, err))? {
+        let entry = entry.map_err(|err| io_error("read", from, err))?;
+        let target = to.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)
+                .map_err(|err| io_error("copy", &entry.path(), err))?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn copy_while_locked_copies_a_closed_store_and_refuses_an_open_one() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.rocksdb");
+        let row_id = ObjectId::new();
+        let mut storage = RocksDBStorage::open(&db_path, 8 * 1024 * 1024).unwrap();
+        storage
+            .put_row_locator(
+                row_id,
+                Some(&super::super::RowLocator {
+                    table: "users".into(),
+                    origin_schema_hash: None,
+                }),
+            )
+            .unwrap();
+
+        let copy_path = temp_dir.path().join("copy.rocksdb");
+        assert!(RocksDBStorage::copy_while_locked(&db_path, &copy_path).is_err());
+
+        storage.close().unwrap();
+        RocksDBStorage::copy_while_locked(&db_path, &copy_path).unwrap();
+        let copy = RocksDBStorage::open(&copy_path, 8 * 1024 * 1024).unwrap();
+        assert!(copy.load_row_locator(row_id).unwrap().is_some());
+        copy.close().unwrap();
+    }
+
     #[test]
     fn open_and_close() {
         let temp_dir = tempfile::TempDir::new().unwrap();
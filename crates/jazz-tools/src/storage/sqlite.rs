@@ -152,6 +152,30 @@ impl SqliteStorage {
         })
     }
 
+    /// Write a consistent copy of the database at `path` to `dest`.
+    ///
+    /// Uses `VACUUM INTO` on a read-only connection, so a server with the
+    /// database open can keep writing; the copy holds every transaction
+    /// committed when it started.
+    pub fn snapshot_into(
+        path: impl AsRef<Path>,
+        dest: impl AsRef<Path>,
+    ) -> Result<(), StorageError> {
+        let conn = rusqlite::Connection::open_with_flags(
+            path.as_ref(),
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .map_err(|e| StorageError::IoError(format!("sqlite open: {e}")))?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))
+            .map_err(|e| StorageError::IoError(format!("sqlite busy timeout: {e}")))?;
+        conn.execute(
+            "VACUUM INTO ?1",
+            rusqlite::params![dest.as_ref().to_string_lossy()],
+        )
+        .map_err(|e| StorageError::IoError(format!("sqlite vacuum into: {e}")))?;
+        Ok(())
+    }
+
     fn lock_inner(&self) -> Result<MutexGuard<'_, Option<SqliteInner>>, StorageError> {
         self.inner
             .lock()
@@ -890,6 +914,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn snapshot_into_copies_committed_rows_while_the_store_is_open() {
+        use crate::object::ObjectId;
+        use crate::storage::RowLocator;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("test.sqlite");
+        let mut storage = SqliteStorage::open(&path).unwrap();
+        let locator = RowLocator {
+            table: "users".into(),
+            origin_schema_hash: None,
+        };
+        let committed = ObjectId::new();
+        storage.put_row_locator(committed, Some(&locator)).unwrap();
+        storage.flush().unwrap();
+        let uncommitted = ObjectId::new();
+        storage
+            .put_row_locator(uncommitted, Some(&locator))
+            .unwrap();
+
+        let copy_path = dir.path().join("copy.sqlite");
+        SqliteStorage::snapshot_into(&path, &copy_path).unwrap();
+        storage.close().unwrap();
+
+        let copy = SqliteStorage::open(&copy_path).unwrap();
+        assert!(copy.load_row_locator(committed).unwrap().is_some());
+        assert!(copy.load_row_locator(uncommitted).unwrap().is_none());
+        copy.close().unwrap();
+    }
+
     #[test]
     fn storage_is_send() {
         fn assert_send<T: Send>() {}
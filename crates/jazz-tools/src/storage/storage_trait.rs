@@ -635,7 +635,7 @@ pub trait Storage {
                     table_name: table.to_string(),
                     branch_name: BranchName::new(row.branch.as_str()),
                     schema_hash: encoded.row_raw_table_id.schema_hash,
-                    row_digest: row.content_digest(),
+                    row_digest: row.sealed_digest(),
                 });
         }
 
@@ -1599,9 +1599,7 @@ pub trait Storage {
                         continue;
                     }
                     match latest_per_row.get(&row.row_id) {
-                        Some(existing)
-                            if (existing.updated_at, existing.batch_id())
-                                >= (row.updated_at, row.batch_id()) => {}
+                        Some(existing) if existing.lww_key() >= row.lww_key() => {}
                         _ => {
                             latest_per_row.insert(row.row_id, row);
                         }
@@ -0,0 +1,55 @@
+//! Capture of settled batches into the storage change log.
+//!
+//! With change capture enabled, a node whose authority reaches
+//! `GlobalServer` appends every row of a batch that settles there
+//! (`DurableDirect` or `AcceptedTransaction`) to the change log in
+//! [`crate::storage::append_change_event`]. Capture happens in the same tick
+//! that persists the fate, so the log never misses a settled row and never
+//! contains an unsettled one. The server's webhook dispatcher tails the log.
+
+use super::*;
+use crate::batch_fate::BatchMode;
+use crate::row_histories::StoredRowBatch;
+use crate::storage::{ChangeEvent, Storage, append_change_event};
+
+impl SyncManager {
+    /// Record rows of batches settled at `GlobalServer` in the change log.
+    pub fn with_change_capture(mut self) -> Self {
+        self.change_capture = true;
+        self
+    }
+
+    /// Append the rows of a batch settled with `fate` to the change log.
+    ///
+    /// Rows already captured (replays, re-settlement after reconnect) are
+    /// skipped by the log itself.
+    pub(super) fn capture_settled_rows<'a, H: Storage>(
+        &self,
+        storage: &mut H,
+        fate: &BatchFate,
+        rows: impl IntoIterator<Item = (&'a str, &'a StoredRowBatch)>,
+    ) {
+        if !self.change_capture || fate.confirmed_tier() != Some(DurabilityTier::GlobalServer) {
+            return;
+        }
+        let mode = match fate {
+            BatchFate::DurableDirect { .. } => BatchMode::Direct,
+            BatchFate::AcceptedTransaction { .. } => BatchMode::Transactional,
+            BatchFate::Missing { .. } | BatchFate::Rejected { .. } => return,
+        };
+
+        for (table, row) in rows {
+            let appended = ChangeEvent::for_row(storage, table, mode, row)
+                .and_then(|event| append_change_event(storage, event));
+            if let Err(error) = appended {
+                tracing::error!(
+                    batch_id = ?row.batch_id,
+                    row_id = %row.row_id,
+                    table,
+                    %error,
+                    "failed to capture settled row"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Client projections of schemas with internal tables or columns.
+//!
+//! User-role clients only ever see the projection of such a schema (see
+//! [`crate::schema_manager::visibility`]): the catalogue entries (releases
+//! included), row batches and query scopes queued for them are rewritten onto
+//! the projected schema hash here, right before they enter the outbox. Every
+//! other role gets the full schema.
+
+use std::sync::Arc;
+
+use super::*;
+use crate::catalogue::CatalogueEntry;
+use crate::metadata::{MetadataKey, ObjectType};
+use crate::query_manager::types::ComposedBranchName;
+use crate::row_histories::StoredRowBatch;
+use crate::schema_manager::encoding::{
+    decode_lens_transform, decode_release, decode_schema, encode_lens_transform,
+    encode_permissions_bundle, encode_release, encode_schema,
+};
+use crate::schema_manager::lens::{Lens, LensTransform};
+use crate::schema_manager::visibility::{RowProjection, SchemaProjection, project_lens};
+
+impl SyncManager {
+    /// True when the client only sees the client projection of schemas.
+    pub(super) fn sees_projected_schemas(&self, client_id: ClientId) -> bool {
+        self.clients
+            .get(&client_id)
+            .is_some_and(|client| client.role == ClientRole::User)
+    }
+
+    /// The client projection of a catalogued schema, or `None` when it has no
+    /// internal items (or isn't catalogued here).
+    pub(super) fn schema_projection(&mut self, hash: SchemaHash) -> Option<Arc<SchemaProjection>> {
+        if let Some(projection) = self.schema_projections.get(&hash) {
+            return projection.clone();
+        }
+        let entry = self.catalogue_entries.get(&hash.to_object_id())?;
+        let schema = decode_schema(&entry.content).ok()?;
+        let projection = SchemaProjection::of(&schema).map(Arc::new);
+        self.schema_projections.insert(hash, projection.clone());
+        projection
+    }
+
+    fn branch_schema_projection(&mut self, branch: &str) -> Option<Arc<SchemaProjection>> {
+        let composed = ComposedBranchName::parse(&BranchName::new(branch))?;
+        let short = composed.schema_hash.short();
+        let hash = match self.catalogued_schema_hashes_by_short.get(&short) {
+            Some(hash) => *hash,
+            None => {
+                let hash = self
+                    .catalogue_entries
+                    .values()
+                    .filter_map(catalogued_schema_hash)
+                    .find(|hash| hash.short() == short)?;
+                self.catalogued_schema_hashes_by_short.insert(short, hash);
+                hash
+            }
+        };
+        self.schema_projection(hash)
+    }
+
+    /// Store the client projection of a newly catalogued schema next to it,
+    /// so rows user-role clients write against the projected hash can be
+    /// decoded on this node. Every node derives the same entry, so it is never
+    /// forwarded as an update of its own.
+    pub(super) fn persist_schema_projection<H: Storage>(
+        &mut self,
+        storage: &mut H,
+        entry: &CatalogueEntry,
+    ) {
+        if is_retired(entry) {
+            return;
+        }
+        let Some(full_hash) = catalogued_schema_hash(entry) else {
+            return;
+        };
+        // Unhashed annotations can change under the same hash, so derive the
+        // projection from this entry rather than the cached one.
+        let projection = decode_schema(&entry.content)
+            .ok()
+            .and_then(|schema| SchemaProjection::of(&schema))
+            .map(Arc::new);
+        self.schema_projections
+            .insert(full_hash, projection.clone());
+        let Some(projection) = projection else {
+            return;
+        };
+        let projected = projected_schema_entry(entry, &projection);
+        // The projection may equal a published schema version, whose own
+        // entry wins.
+        let existing = self
+            .catalogue_entries
+            .get(&projected.object_id)
+            .cloned()
+            .or_else(|| {
+                storage
+                    .load_catalogue_entry(projected.object_id)
+                    .ok()
+                    .flatten()
+            });
+        if let Some(existing) = existing
+            && (existing == projected
+                || !existing
+                    .metadata
+                    .contains_key(MetadataKey::ProjectionOf.as_str()))
+        {
+            return;
+        }
+
+        if let Err(error) = storage.upsert_catalogue_entry(&projected) {
+            tracing::warn!(
+                object_id = %projected.object_id,
+                %error,
+                "failed to persist client schema projection"
+            );
+        }
+        self.catalogue_entries
+            .insert(projected.object_id, projected);
+    }
+
+    /// The catalogue entry `client_id` may see in place of `entry`, or `None`
+    /// when the entry only concerns internal tables and columns.
+    pub(super) fn catalogue_entry_for_client(
+        &mut self,
+        client_id: ClientId,
+        entry: CatalogueEntry,
+    ) -> Option<CatalogueEntry> {
+        if !self.sees_projected_schemas(client_id) {
+            return Some(entry);
+        }
+
+        if entry.object_type() == Some(ObjectType::CatalogueSchema.as_str()) {
+            let Some(projection) =
+                catalogued_schema_hash(&entry).and_then(|hash| self.schema_projection(hash))
+            else {
+                return Some(entry);
+            };
+            // Retiring a full schema says nothing about its projection, which
+            // may still be a live version in its own right.
+            if is_retired(&entry) {
+                return None;
+            }
+            return Some(projected_schema_entry(&entry, &projection));
+        }
+
+        if entry.object_type() == Some(ObjectType::CatalogueLens.as_str()) {
+            return self.projected_lens_entry(entry);
+        }
+
+        if entry.object_type() == Some(ObjectType::CatalogueRelease.as_str()) {
+            return Some(self.projected_release_entry(entry));
+        }
+
+        Some(entry)
+    }
+
+    /// A release with its schema and lenses projected like the entries they
+    /// are also catalogued as. The permissions bundle passes through, and
+    /// the release keeps its object id.
+    fn projected_release_entry(&mut self, mut entry: CatalogueEntry) -> CatalogueEntry {
+        let Ok((version, parent_release_object_id, schema, lenses, bundle)) =
+            decode_release(&entry.content)
+        else {
+            return entry;
+        };
+        let schema_hash = SchemaHash::compute(&schema);
+        let projection = SchemaProjection::of(&schema).map(Arc::new);
+        let projection_for = |sync_manager: &mut Self, hash: SchemaHash| {
+            if hash == schema_hash {
+                projection.clone()
+            } else {
+                sync_manager.schema_projection(hash)
+            }
+        };
+
+        let mut projected_any = projection.is_some();
+        let mut projected_lenses = Vec::with_capacity(lenses.len());
+        for lens in lenses {
+            let source = projection_for(self, lens.source_hash);
+            let target = projection_for(self, lens.target_hash);
+            if source.is_none() && target.is_none() {
+                projected_lenses.push(lens);
+                continue;
+            }
+            projected_any = true;
+            if let Some(projected) = project_lens(&lens, source.as_deref(), target.as_deref()) {
+                projected_lenses.push(projected);
+            }
+        }
+        if !projected_any {
+            return entry;
+        }
+
+        let (bundle_schema_hash, bundle_version, parent_bundle_object_id, permissions) = bundle;
+        entry.content = encode_release(
+            version,
+            parent_release_object_id,
+            projection
+                .as_ref()
+                .map_or(&schema, |projection| &projection.projected),
+            &projected_lenses,
+            &encode_permissions_bundle(
+                bundle_schema_hash,
+                bundle_version,
+                parent_bundle_object_id,
+                &permissions,
+            ),
+        );
+        entry
+    }
+
+    fn projected_lens_entry(&mut self, entry: CatalogueEntry) -> Option<CatalogueEntry> {
+        let (Some(source_hash), Some(target_hash)) = (
+            metadata_schema_hash(&entry, MetadataKey::SourceHash),
+            metadata_schema_hash(&entry, MetadataKey::TargetHash),
+        ) else {
+            return Some(entry);
+        };
+        let source = self.schema_projection(source_hash);
+        let target = self.schema_projection(target_hash);
+        if source.is_none() && target.is_none() {
+            return Some(entry);
+        }
+
+        // Lens tombstones carry no transform.
+        let retired = is_retired(&entry);
+        let transform = if retired {
+            LensTransform::new()
+        } else {
+            decode_lens_transform(&entry.content).ok()?
+        };
+        let mut lens = Lens::new(source_hash, target_hash, transform);
+        lens.revision = entry
+            .metadata
+            .get(MetadataKey::LensRevision.as_str())
+            .and_then(|revision| revision.parse().ok())
+            .unwrap_or(0);
+        let projected = project_lens(&lens, source.as_deref(), target.as_deref())?;
+
+        let mut metadata = entry.metadata;
+        metadata.insert(
+            MetadataKey::SourceHash.to_string(),
+            projected.source_hash.to_string(),
+        );
+        metadata.insert(
+            MetadataKey::TargetHash.to_string(),
+            projected.target_hash.to_string(),
+        );
+        Some(CatalogueEntry {
+            object_id: projected.object_id(),
+            metadata,
+            content: if retired {
+                Vec::new()
+            } else {
+                encode_lens_transform(&projected.forward)
+            },
+        })
+    }
+
+    /// The row `client_id` may see in place of `row`, moved onto the projected
+    /// schema branch, or `None` when the row belongs to an internal table.
+    pub(super) fn row_for_client(
+        &mut self,
+        client_id: ClientId,
+        mut metadata: HashMap<String, String>,
+        mut row: StoredRowBatch,
+    ) -> Option<(HashMap<String, String>, StoredRowBatch)> {
+        if !self.sees_projected_schemas(client_id) {
+            return Some((metadata, row));
+        }
+        let Some(projection) = self.branch_schema_projection(row.branch.as_str()) else {
+            return Some((metadata, row));
+        };
+        let table = metadata.get(MetadataKey::Table.as_str())?;
+
+        match projection.project_row(table, &row.data) {
+            Ok(RowProjection::Hidden) => return None,
+            Ok(RowProjection::Unchanged) => {}
+            Ok(RowProjection::Rewritten(data)) => row.data = data.into(),
+            Err(error) => {
+                tracing::warn!(
+                    row_id = %row.row_id,
+                    %table,
+                    ?error,
+                    "failed to project row for client; not sending it"
+                );
+                return None;
+            }
+        }
+        row.branch = projected_branch(BranchName::new(row.branch.as_str()), &projection)
+            .as_str()
+            .into();
+        let origin_key = MetadataKey::OriginSchemaHash.as_str();
+        if metadata.get(origin_key) == Some(&projection.full_hash.to_string()) {
+            metadata.insert(
+                origin_key.to_string(),
+                projection.projected_hash.to_string(),
+            );
+        }
+        Some((metadata, row))
+    }
+
+    /// A query scope as `client_id` sees it, with branches of projected
+    /// schemas renamed like the rows on them.
+    pub(super) fn scope_for_client(
+        &mut self,
+        client_id: ClientId,
+        scope: &HashSet<(ObjectId, BranchName)>,
+    ) -> HashSet<(ObjectId, BranchName)> {
+        if !self.sees_projected_schemas(client_id) {
+            return scope.clone();
+        }
+        scope
+            .iter()
+            .map(
+                |&(object_id, branch)| match self.branch_schema_projection(branch.as_str()) {
+                    Some(projection) => (object_id, projected_branch(branch, &projection)),
+                    None => (object_id, branch),
+                },
+            )
+            .collect()
+    }
+}
+
+fn catalogued_schema_hash(entry: &CatalogueEntry) -> Option<SchemaHash> {
+    if entry.object_type() != Some(ObjectType::CatalogueSchema.as_str()) {
+        return None;
+    }
+    metadata_schema_hash(entry, MetadataKey::SchemaHash)
+}
+
+fn metadata_schema_hash(entry: &CatalogueEntry, key: MetadataKey) -> Option<SchemaHash> {
+    entry
+        .metadata
+        .get(key.as_str())
+        .and_then(|hex| SchemaHash::from_hex(hex))
+}
+
+fn is_retired(entry: &CatalogueEntry) -> bool {
+    entry
+        .metadata
+        .get(MetadataKey::Retired.as_str())
+        .is_some_and(|value| value == "true")
+}
+
+fn projected_schema_entry(entry: &CatalogueEntry, projection: &SchemaProjection) -> CatalogueEntry {
+    let mut metadata = entry.metadata.clone();
+    metadata.insert(
+        MetadataKey::SchemaHash.to_string(),
+        projection.projected_hash.to_string(),
+    );
+    metadata.insert(
+        MetadataKey::ProjectionOf.to_string(),
+        projection.full_hash.to_string(),
+    );
+    CatalogueEntry {
+        object_id: projection.projected_hash.to_object_id(),
+        metadata,
+        content: encode_schema(&projection.projected),
+    }
+}
+
+fn projected_branch(branch: BranchName, projection: &SchemaProjection) -> BranchName {
+    match ComposedBranchName::parse(&branch) {
+        Some(composed) => ComposedBranchName::new(
+            &composed.env,
+            projection.projected_hash,
+            &composed.user_branch,
+        )
+        .to_branch_name(),
+        None => branch,
+    }
+}
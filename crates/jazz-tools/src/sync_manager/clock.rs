@@ -1,22 +1,126 @@
+//! Clocks for row-batch timestamps.
+//!
+//! Every row batch carries two times:
+//!
+//! - `updated_at`: the writer's wall-clock time in microseconds since the Unix
+//!   epoch, exposed to apps as `$updatedAt`. Each node keeps it strictly
+//!   monotonic, but never moves it to follow another node's clock.
+//! - `hlc`: a hybrid logical clock (HLC) timestamp, a physical microsecond part
+//!   plus a logical counter. It tracks wall time, never runs backwards and
+//!   always moves past every HLC this node has observed, so a write made after
+//!   seeing another writer's batch orders after it, whatever the two wall
+//!   clocks say.
+//!
+//! Last-writer-wins resolution orders batches by HLC. Batches written before
+//! rows carried an HLC read as `(updated_at, 0)`, which is exactly how they
+//! were ordered before.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
 use web_time::{SystemTime, UNIX_EPOCH};
 
-/// Monotonic microsecond clock for write ordering.
-#[derive(Debug, Clone, Default)]
-pub struct MonotonicClock {
+/// How far ahead of local wall time an observed HLC may pull the clock.
+///
+/// Timestamps further in the future are not observed, so a single skewed
+/// peer cannot drag every later write on this node into the future.
+pub const DEFAULT_MAX_OBSERVED_DRIFT: Duration = Duration::from_secs(5 * 60);
+
+/// A hybrid logical clock timestamp. Orders by `physical`, then `logical`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub struct HlcTimestamp {
+    /// Microseconds since the Unix epoch.
+    pub physical: u64,
+    /// Orders events that share a `physical` value.
+    pub logical: u32,
+}
+
+impl HlcTimestamp {
+    /// The HLC of a batch stamped only with a wall-clock time.
+    pub fn from_wall_clock(physical: u64) -> Self {
+        Self {
+            physical,
+            logical: 0,
+        }
+    }
+
+    fn successor(self) -> Self {
+        match self.logical.checked_add(1) {
+            Some(logical) => Self {
+                physical: self.physical,
+                logical,
+            },
+            None => Self::from_wall_clock(self.physical.saturating_add(1)),
+        }
+    }
+}
+
+impl fmt::Display for HlcTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.physical, self.logical)
+    }
+}
+
+impl FromStr for HlcTimestamp {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let (physical, logical) = raw
+            .split_once('.')
+            .ok_or_else(|| format!("invalid HLC timestamp '{raw}'"))?;
+        Ok(Self {
+            physical: physical
+                .parse()
+                .map_err(|err| format!("invalid HLC physical time '{physical}': {err}"))?,
+            logical: logical
+                .parse()
+                .map_err(|err| format!("invalid HLC counter '{logical}': {err}"))?,
+        })
+    }
+}
+
+/// Wall-clock and hybrid logical clock for write ordering.
+#[derive(Debug, Clone)]
+pub struct HybridLogicalClock {
     last_timestamp: u64,
+    last_hlc: HlcTimestamp,
+    max_observed_drift: Duration,
+}
+
+impl Default for HybridLogicalClock {
+    fn default() -> Self {
+        Self {
+            last_timestamp: 0,
+            last_hlc: HlcTimestamp::default(),
+            max_observed_drift: DEFAULT_MAX_OBSERVED_DRIFT,
+        }
+    }
 }
 
-impl MonotonicClock {
+fn wall_clock_micros() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_micros() as u64
+}
+
+impl HybridLogicalClock {
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Guarantees each reservation is strictly greater than the previous one.
+    pub fn with_max_observed_drift(mut self, max_observed_drift: Duration) -> Self {
+        self.max_observed_drift = max_observed_drift;
+        self
+    }
+
+    /// Wall-clock timestamp, strictly greater than the previous reservation.
     pub fn reserve_timestamp(&mut self) -> u64 {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("time went backwards")
-            .as_micros() as u64;
+        let now = wall_clock_micros();
 
         self.last_timestamp = if now > self.last_timestamp {
             now
@@ -26,17 +130,113 @@ impl MonotonicClock {
 
         self.last_timestamp
     }
+
+    /// Local event: an HLC strictly greater than every HLC reserved or
+    /// observed so far.
+    pub fn reserve_hlc(&mut self) -> HlcTimestamp {
+        let now = wall_clock_micros();
+
+        self.last_hlc = if now > self.last_hlc.physical {
+            HlcTimestamp::from_wall_clock(now)
+        } else {
+            self.last_hlc.successor()
+        };
+
+        self.last_hlc
+    }
+
+    /// Receive event: advance past the HLC carried by an incoming batch.
+    ///
+    /// Returns `false` when the HLC is further ahead of wall time than the
+    /// allowed drift; the clock is left alone.
+    pub fn observe(&mut self, hlc: HlcTimestamp) -> bool {
+        if hlc <= self.last_hlc {
+            return true;
+        }
+        if hlc.physical > self.latest_observable() {
+            return false;
+        }
+        self.last_hlc = hlc;
+        true
+    }
+
+    /// Latest physical time an HLC may carry and still be observed.
+    pub fn latest_observable(&self) -> u64 {
+        wall_clock_micros().saturating_add(self.max_observed_drift.as_micros() as u64)
+    }
+
+    /// The most recent HLC reserved or observed.
+    pub fn last_hlc(&self) -> HlcTimestamp {
+        self.last_hlc
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::MonotonicClock;
+    use super::*;
 
     #[test]
     fn reserved_timestamps_are_strictly_monotonic() {
-        let mut clock = MonotonicClock::new();
+        let mut clock = HybridLogicalClock::new();
         let first = clock.reserve_timestamp();
         let second = clock.reserve_timestamp();
         assert!(second > first);
     }
+
+    #[test]
+    fn reserved_hlcs_are_strictly_monotonic() {
+        let mut clock = HybridLogicalClock::new();
+        let first = clock.reserve_hlc();
+        let second = clock.reserve_hlc();
+        assert!(second > first);
+    }
+
+    #[test]
+    fn hlc_reservations_follow_observed_timestamps() {
+        let mut clock = HybridLogicalClock::new();
+        let remote = HlcTimestamp::from_wall_clock(wall_clock_micros() + 60 * 1_000_000);
+
+        assert!(clock.observe(remote));
+        let next = clock.reserve_hlc();
+        assert_eq!(next.physical, remote.physical);
+        assert!(next > remote);
+    }
+
+    #[test]
+    fn observing_does_not_move_wall_clock_timestamps() {
+        let mut clock = HybridLogicalClock::new();
+        let remote = wall_clock_micros() + 60 * 1_000_000;
+
+        assert!(clock.observe(HlcTimestamp::from_wall_clock(remote)));
+        assert!(clock.reserve_timestamp() < remote);
+    }
+
+    #[test]
+    fn timestamps_beyond_max_drift_are_not_observed() {
+        let mut clock = HybridLogicalClock::new().with_max_observed_drift(Duration::from_secs(1));
+        let remote = HlcTimestamp::from_wall_clock(wall_clock_micros() + 60 * 1_000_000);
+
+        assert!(!clock.observe(remote));
+        assert!(clock.reserve_hlc() < remote);
+    }
+
+    #[test]
+    fn observing_older_timestamps_keeps_the_clock() {
+        let mut clock = HybridLogicalClock::new();
+        let reserved = clock.reserve_hlc();
+
+        assert!(clock.observe(HlcTimestamp::from_wall_clock(reserved.physical - 10)));
+        assert_eq!(clock.last_hlc(), reserved);
+    }
+
+    #[test]
+    fn hlc_timestamps_round_trip_through_strings() {
+        let hlc = HlcTimestamp {
+            physical: 1_700_000_000_000_000,
+            logical: 3,
+        };
+
+        assert_eq!(hlc.to_string().parse::<HlcTimestamp>(), Ok(hlc));
+        assert!("12".parse::<HlcTimestamp>().is_err());
+    }
 }
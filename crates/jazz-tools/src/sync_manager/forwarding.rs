@@ -95,7 +95,7 @@ impl SyncManager {
             .ok()?
             .into_iter()
             .filter(|row| row.state.is_visible())
-            .max_by_key(|row| (row.updated_at, row.batch_id()))
+            .max_by_key(|row| row.lww_key())
     }
 
     pub(super) fn load_current_batch_fate_from_storage<H: crate::storage::Storage + ?Sized>(
@@ -245,7 +245,7 @@ impl SyncManager {
         }
     }
 
-    fn row_metadata_from_payload<H: Storage>(
+    pub(super) fn row_metadata_from_payload<H: Storage>(
         &self,
         storage: &H,
         row: &StoredRowBatch,
@@ -407,6 +407,7 @@ impl SyncManager {
             (None, None) => None,
         };
         row.confirmed_tier = None;
+        self.observe_hlc(row.hlc());
 
         let metadata = self.row_metadata_from_payload(storage, &row, metadata.as_ref())?;
         if matches!(
@@ -421,7 +422,7 @@ impl SyncManager {
                     submission.target_branch_name.as_str() == row.branch.as_str()
                         && submission.members.iter().any(|member| {
                             member.object_id == row.row_id
-                                && member.row_digest == row.content_digest()
+                                && member.row_digest == row.sealed_digest()
                         })
                 });
             if !is_declared_member {
@@ -498,6 +499,9 @@ impl SyncManager {
             if let Ok((fate, true)) = self.persist_authoritative_batch_fate(storage, &fate) {
                 self.pending_batch_fates.push(fate);
             }
+            if let Some(table) = metadata.get(MetadataKey::Table.as_str()) {
+                self.capture_settled_rows(storage, &fate, [(table.as_str(), &row)]);
+            }
         }
 
         Some(AppliedRowBatch {
@@ -612,7 +616,7 @@ impl SyncManager {
                 ) else {
                     return None;
                 };
-                (row.content_digest() == member.row_digest).then_some((member.table_name, row))
+                (row.sealed_digest() == member.row_digest).then_some((member.table_name, row))
             })
             .collect::<Vec<_>>();
 
@@ -978,7 +982,7 @@ impl SyncManager {
             let matching_row = batch_rows.iter().find(|(_, row)| {
                 row.row_id == member.object_id
                     && row.branch.as_str() == submission.target_branch_name.as_str()
-                    && row.content_digest() == member.row_digest
+                    && row.sealed_digest() == member.row_digest
             })?;
             declared_rows.push(matching_row.clone());
         }
@@ -1076,6 +1080,13 @@ impl SyncManager {
         {
             tracing::warn!(?batch_id, %error, "failed to delete sealed batch submission");
         }
+        self.capture_settled_rows(
+            storage,
+            &fate,
+            declared_rows
+                .iter()
+                .map(|(table, row)| (table.as_str(), row)),
+        );
         let rows_to_patch: &[(String, StoredRowBatch)] = match fate {
             BatchFate::DurableDirect { .. } | BatchFate::AcceptedTransaction { .. } => {
                 &declared_rows
@@ -1489,6 +1500,9 @@ impl SyncManager {
         payload: SyncPayload,
     ) {
         let _span = tracing::debug_span!("process_from_client", %client_id, payload = payload.variant_name()).entered();
+        let Some(payload) = self.enforce_timestamp_skew_policy(storage, client_id, payload) else {
+            return;
+        };
         let Some(client) = self.clients.get(&client_id) else {
             tracing::warn!(%client_id, "message from unknown client, ignoring");
             return;
@@ -13,15 +13,19 @@ use crate::row_histories::{BatchId, RowVisibilityChange};
 use crate::storage::{PreparedRowTableContext, Storage};
 
 // Module declarations
+pub mod change_capture;
+pub mod client_projection;
 pub mod clock;
 pub mod forwarding;
 pub mod inbox;
 pub mod permissions;
 pub mod sync_logic;
 pub mod sync_tracer;
+pub mod timestamp_skew;
 pub mod types;
 
-use clock::MonotonicClock;
+use clock::{HlcTimestamp, HybridLogicalClock};
+use timestamp_skew::TimestampSkewPolicy;
 
 #[cfg(test)]
 mod tests;
@@ -54,9 +58,13 @@ pub(crate) struct OutgoingQuerySubscription {
 /// - Downstream clients (untrusted, receive query-filtered subsets)
 #[derive(Clone)]
 pub struct SyncManager {
-    pub(super) clock: MonotonicClock,
+    pub(super) clock: HybridLogicalClock,
     pub(super) catalogue_entries: HashMap<ObjectId, CatalogueEntry>,
     pub(super) allow_unprivileged_schema_catalogue_writes: bool,
+    /// Bounds on client-supplied row timestamps; `None` trusts client clocks.
+    pub(super) timestamp_skew_policy: Option<TimestampSkewPolicy>,
+    /// Append rows of batches settled at `GlobalServer` to the storage change log.
+    pub(super) change_capture: bool,
 
     pub(super) servers: HashMap<ServerId, ServerState>,
     pub(super) pending_servers: HashMap<ServerId, Instant>,
@@ -111,6 +119,12 @@ pub struct SyncManager {
     /// from storage for every row.
     pub(super) replay_table_contexts:
         HashMap<(String, SchemaHash), std::sync::Arc<PreparedRowTableContext>>,
+    /// Client projections of catalogued schemas, `None` for schemas without
+    /// internal tables or columns.
+    pub(super) schema_projections:
+        HashMap<SchemaHash, Option<std::sync::Arc<crate::schema_manager::SchemaProjection>>>,
+    /// Full catalogued schema hashes by the short form used in branch names.
+    pub(super) catalogued_schema_hashes_by_short: HashMap<String, SchemaHash>,
 }
 
 impl std::fmt::Debug for SyncManager {
@@ -122,6 +136,8 @@ impl std::fmt::Debug for SyncManager {
                 "allow_unprivileged_schema_catalogue_writes",
                 &self.allow_unprivileged_schema_catalogue_writes,
             )
+            .field("timestamp_skew_policy", &self.timestamp_skew_policy)
+            .field("change_capture", &self.change_capture)
             .field("servers", &self.servers)
             .field("pending_servers", &self.pending_servers)
             .field("clients", &self.clients)
@@ -231,9 +247,11 @@ pub(crate) fn log_connection_schema_diagnostics(
 impl SyncManager {
     pub fn new() -> Self {
         Self {
-            clock: MonotonicClock::new(),
+            clock: HybridLogicalClock::new(),
             catalogue_entries: HashMap::new(),
             allow_unprivileged_schema_catalogue_writes: false,
+            timestamp_skew_policy: None,
+            change_capture: false,
             servers: HashMap::new(),
             pending_servers: HashMap::new(),
             pending_server_query_subscriptions: HashSet::new(),
@@ -258,6 +276,8 @@ impl SyncManager {
             pending_batch_fates: Vec::new(),
             pending_client_batch_fates: HashMap::new(),
             replay_table_contexts: HashMap::new(),
+            schema_projections: HashMap::new(),
+            catalogued_schema_hashes_by_short: HashMap::new(),
         }
     }
 
@@ -265,6 +285,21 @@ impl SyncManager {
         self.clock.reserve_timestamp()
     }
 
+    pub fn reserve_hlc(&mut self) -> HlcTimestamp {
+        self.clock.reserve_hlc()
+    }
+
+    /// Advance this node's hybrid logical clock past `hlc`, so the next
+    /// reserved HLC orders after the write that carried it.
+    pub fn observe_hlc(&mut self, hlc: HlcTimestamp) {
+        if !self.clock.observe(hlc) {
+            tracing::debug!(
+                %hlc,
+                "HLC too far ahead of wall time; not advancing clock"
+            );
+        }
+    }
+
     /// Add a durability identity for this node (enables durability notifications).
     pub fn with_durability_tier(mut self, tier: DurabilityTier) -> Self {
         self.my_tiers.insert(tier);
@@ -586,6 +621,19 @@ impl SyncManager {
         self.queue_catalogue_sync_to_client_from_storage(client_id, storage);
     }
 
+    /// Add a client connection with `role`, replaying catalogue state as that
+    /// role sees it.
+    pub fn add_client_with_role_and_storage<H: Storage>(
+        &mut self,
+        storage: &H,
+        client_id: ClientId,
+        role: ClientRole,
+    ) {
+        self.add_client(client_id);
+        self.set_client_role(client_id, role);
+        self.queue_catalogue_sync_to_client_from_storage(client_id, storage);
+    }
+
     /// Replay catalogue entries to a client when its digest is missing or stale.
     ///
     /// Returns true when a replay was queued.
@@ -1105,12 +1153,13 @@ impl SyncManager {
             scope_len = scope.len(),
             "jazz trace emitting query settled to client"
         );
+        let scope = self.scope_for_client(client_id, scope);
         self.outbox.push(OutboxEntry {
             destination: Destination::Client(client_id),
             payload: SyncPayload::QuerySettled {
                 query_id,
                 tier,
-                scope: sorted_query_scope_snapshot(scope),
+                scope: sorted_query_scope_snapshot(&scope),
                 through_seq: 0,
             },
         });
@@ -177,7 +177,7 @@ impl SyncManager {
                     )
                     .ok()
                     .flatten()?;
-                (row.content_digest() == member.row_digest).then_some(row)
+                (row.sealed_digest() == member.row_digest).then_some(row)
             })
             .collect::<Vec<_>>();
 
@@ -204,7 +204,7 @@ impl SyncManager {
         rows
     }
 
-    fn reject_permission_batch<H: Storage>(
+    pub(super) fn reject_permission_batch<H: Storage>(
         &mut self,
         storage: &mut H,
         origin_client_id: ClientId,
@@ -144,9 +144,13 @@ impl SyncManager {
             return;
         };
 
-        for entry in entries {
+        // Cache every entry first: projecting a lens for the client needs
+        // both of its schemas.
+        for entry in &entries {
             self.catalogue_entries
                 .insert(entry.object_id, entry.clone());
+        }
+        for entry in entries {
             self.queue_catalogue_entry_to_client(client_id, entry);
         }
     }
@@ -185,6 +189,7 @@ impl SyncManager {
             );
         }
 
+        self.persist_schema_projection(storage, &entry);
         self.catalogue_entries.insert(entry.object_id, entry);
         true
     }
@@ -197,6 +202,9 @@ impl SyncManager {
     }
 
     fn queue_catalogue_entry_to_client(&mut self, client_id: ClientId, entry: CatalogueEntry) {
+        let Some(entry) = self.catalogue_entry_for_client(client_id, entry) else {
+            return;
+        };
         self.outbox.push(OutboxEntry {
             destination: Destination::Client(client_id),
             payload: SyncPayload::CatalogueEntryUpdated { entry },
@@ -344,6 +352,11 @@ impl SyncManager {
             return;
         }
 
+        // Bookkeeping stays on the stored branch; only the payload is projected.
+        let Some((metadata, row)) = self.row_for_client(client_id, metadata, row) else {
+            return;
+        };
+
         let Some(client) = self.clients.get_mut(&client_id) else {
             return;
         };
@@ -416,11 +416,14 @@ fn push_query_subscription(
 /// permission check and re-emit the cached settlement so the client can
 /// reconcile.
 mod basic;
+mod change_capture;
 mod client_lifecycle;
+mod client_projection;
 mod forwarding_recursion;
 mod permissions;
 mod query_scope;
 mod server_sync;
 mod settlements;
 mod subscriptions;
+mod timestamp_skew;
 mod transaction_sealing;
@@ -0,0 +1,147 @@
+use super::*;
+use crate::batch_fate::BatchMode;
+use crate::storage::scan_change_events;
+
+fn push_transactional_batch(
+    sm: &mut SyncManager,
+    io: &mut MemoryStorage,
+    client_id: ClientId,
+    names: &[&str],
+) -> BatchId {
+    let batch_id = BatchId::new();
+    let mut members = Vec::new();
+    for name in names {
+        let row = row_with_batch_state(
+            visible_row(ObjectId::new(), "main", Vec::new(), 1_000, name.as_bytes()),
+            batch_id,
+            crate::row_histories::RowState::StagingPending,
+            None,
+        );
+        members.push(SealedBatchMember {
+            object_id: row.row_id,
+            row_digest: row.content_digest(),
+        });
+        sm.process_from_client(
+            io,
+            client_id,
+            SyncPayload::RowBatchCreated {
+                metadata: Some(RowMetadata {
+                    id: row.row_id,
+                    metadata: row_metadata("users"),
+                }),
+                row,
+            },
+        );
+    }
+    seal_transactional_batch(sm, io, client_id, batch_id, members);
+    batch_id
+}
+
+fn seal_transactional_batch(
+    sm: &mut SyncManager,
+    io: &mut MemoryStorage,
+    client_id: ClientId,
+    batch_id: BatchId,
+    members: Vec<SealedBatchMember>,
+) {
+    sm.process_from_client(
+        io,
+        client_id,
+        SyncPayload::SealBatch {
+            submission: transactional_sealed_submission(batch_id, "main", members, Vec::new()),
+        },
+    );
+}
+
+fn peer_client(sm: &mut SyncManager, io: &MemoryStorage) -> ClientId {
+    let client_id = ClientId::new();
+    add_client(sm, io, client_id);
+    sm.set_client_role(client_id, ClientRole::Peer);
+    sm.take_outbox();
+    client_id
+}
+
+#[test]
+fn batches_settled_at_global_tier_are_captured_with_decoded_values() {
+    let mut sm = SyncManager::new()
+        .with_durability_tier(DurabilityTier::GlobalServer)
+        .with_change_capture();
+    let mut io = MemoryStorage::new();
+    seed_users_schema(&mut io);
+    let client_id = peer_client(&mut sm, &io);
+
+    let batch_id = push_transactional_batch(&mut sm, &mut io, client_id, &["alice", "bob"]);
+
+    let events = scan_change_events(&io, 0, 10).unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|event| event.batch_id == batch_id
+        && event.mode == BatchMode::Transactional
+        && event.table == "users"
+        && event.schema_hash == users_schema_hash()));
+    assert_eq!(
+        events.iter().map(|event| event.seq).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    let values: serde_json::Value =
+        serde_json::from_str(events[0].values_json.as_deref().unwrap()).unwrap();
+    assert_eq!(
+        values["value"],
+        serde_json::to_value(Value::Text("alice".to_string())).unwrap()
+    );
+}
+
+#[test]
+fn resealing_a_settled_batch_does_not_capture_it_twice() {
+    let mut sm = SyncManager::new()
+        .with_durability_tier(DurabilityTier::GlobalServer)
+        .with_change_capture();
+    let mut io = MemoryStorage::new();
+    seed_users_schema(&mut io);
+    let client_id = peer_client(&mut sm, &io);
+    let batch_id = BatchId::new();
+    let row = row_with_batch_state(
+        visible_row(ObjectId::new(), "main", Vec::new(), 1_000, b"alice"),
+        batch_id,
+        crate::row_histories::RowState::StagingPending,
+        None,
+    );
+    let members = vec![SealedBatchMember {
+        object_id: row.row_id,
+        row_digest: row.content_digest(),
+    }];
+    sm.process_from_client(
+        &mut io,
+        client_id,
+        SyncPayload::RowBatchCreated {
+            metadata: Some(RowMetadata {
+                id: row.row_id,
+                metadata: row_metadata("users"),
+            }),
+            row,
+        },
+    );
+
+    seal_transactional_batch(&mut sm, &mut io, client_id, batch_id, members.clone());
+    seal_transactional_batch(&mut sm, &mut io, client_id, batch_id, members);
+
+    assert_eq!(scan_change_events(&io, 0, 10).unwrap().len(), 1);
+}
+
+#[test]
+fn batches_are_not_captured_below_global_tier_or_when_disabled() {
+    for sm in [
+        SyncManager::new()
+            .with_durability_tier(DurabilityTier::EdgeServer)
+            .with_change_capture(),
+        SyncManager::new().with_durability_tier(DurabilityTier::GlobalServer),
+    ] {
+        let mut sm = sm;
+        let mut io = MemoryStorage::new();
+        seed_users_schema(&mut io);
+        let client_id = peer_client(&mut sm, &io);
+
+        push_transactional_batch(&mut sm, &mut io, client_id, &["alice"]);
+
+        assert!(scan_change_events(&io, 0, 10).unwrap().is_empty());
+    }
+}
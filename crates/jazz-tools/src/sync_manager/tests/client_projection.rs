@@ -0,0 +1,158 @@
+use super::*;
+use crate::query_manager::types::{ComposedBranchName, Schema};
+use crate::schema_manager::SchemaProjection;
+
+fn accounts_schema() -> Schema {
+    SchemaBuilder::new()
+        .table(
+            TableSchema::builder("users")
+                .column("value", ColumnType::Text)
+                .internal_column("risk_score", ColumnType::Integer),
+        )
+        .table(
+            TableSchema::builder("audit_log")
+                .column("entry", ColumnType::Text)
+                .internal(),
+        )
+        .build()
+}
+
+fn catalogue_entries_for(outbox: &[OutboxEntry], client_id: ClientId) -> Vec<&CatalogueEntry> {
+    outbox
+        .iter()
+        .filter_map(|entry| match entry {
+            OutboxEntry {
+                destination: Destination::Client(id),
+                payload: SyncPayload::CatalogueEntryUpdated { entry },
+            } if *id == client_id => Some(entry),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn user_clients_receive_the_client_projection_of_schema_catalogue_entries() {
+    let mut sm = SyncManager::new();
+    let mut io = MemoryStorage::new();
+    let schema = accounts_schema();
+    let full_hash = persist_test_schema(&mut io, &schema);
+    let projection = SchemaProjection::of(&schema).expect("schema has internal items");
+    let user_id = ClientId::new();
+    let backend_id = ClientId::new();
+
+    add_client(&mut sm, &io, user_id);
+    sm.add_client_with_role_and_storage(&io, backend_id, ClientRole::Backend);
+    let outbox = sm.take_outbox();
+
+    let user_entries = catalogue_entries_for(&outbox, user_id);
+    assert_eq!(user_entries.len(), 1);
+    assert_eq!(
+        user_entries[0].object_id,
+        projection.projected_hash.to_object_id()
+    );
+    assert_eq!(
+        user_entries[0]
+            .metadata
+            .get(MetadataKey::ProjectionOf.as_str()),
+        Some(&full_hash.to_string())
+    );
+    assert_eq!(
+        crate::schema_manager::encoding::decode_schema(&user_entries[0].content).unwrap(),
+        projection.projected
+    );
+
+    let backend_entries = catalogue_entries_for(&outbox, backend_id);
+    assert_eq!(backend_entries.len(), 1);
+    assert_eq!(backend_entries[0].object_id, full_hash.to_object_id());
+}
+
+#[test]
+fn user_clients_receive_rows_without_internal_columns_on_the_projected_branch() {
+    let mut sm = SyncManager::new();
+    let mut io = MemoryStorage::new();
+    let schema = accounts_schema();
+    let full_hash = persist_test_schema(&mut io, &schema);
+    let projection = SchemaProjection::of(&schema).expect("schema has internal items");
+    let client_id = ClientId::new();
+    let row_id = ObjectId::new();
+    let full_branch = ComposedBranchName::new("dev", full_hash, "main").to_branch_name();
+    let projected_branch =
+        ComposedBranchName::new("dev", projection.projected_hash, "main").to_branch_name();
+
+    let row = StoredRowBatch::new(
+        row_id,
+        full_branch.as_str(),
+        Vec::new(),
+        encode_row(
+            &schema[&"users".into()].columns,
+            &[Value::Text("alice".to_string()), Value::Integer(7)],
+        )
+        .unwrap(),
+        RowProvenance::for_insert(row_id.to_string(), 1_000),
+        HashMap::new(),
+        crate::row_histories::RowState::VisibleDirect,
+        None,
+    );
+    create_test_row_with_id(
+        &mut io,
+        row_id,
+        Some(HashMap::from([
+            (MetadataKey::Table.to_string(), "users".to_string()),
+            (
+                MetadataKey::OriginSchemaHash.to_string(),
+                full_hash.to_string(),
+            ),
+        ])),
+    );
+    io.append_history_region_rows("users", std::slice::from_ref(&row))
+        .unwrap();
+    io.upsert_visible_region_rows(
+        "users",
+        std::slice::from_ref(&VisibleRowEntry::rebuild(
+            row.clone(),
+            std::slice::from_ref(&row),
+        )),
+    )
+    .unwrap();
+
+    add_client(&mut sm, &io, client_id);
+    sm.take_outbox();
+    set_client_query_scope(
+        &mut sm,
+        &io,
+        client_id,
+        QueryId(1),
+        HashSet::from([(row_id, full_branch)]),
+        None,
+    );
+
+    let sent: Vec<_> = sm
+        .take_outbox()
+        .into_iter()
+        .filter_map(|entry| match entry {
+            OutboxEntry {
+                destination: Destination::Client(id),
+                payload: SyncPayload::RowBatchNeeded { metadata, row },
+            } if id == client_id => Some((metadata, row)),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(sent.len(), 1);
+    let (metadata, sent_row) = &sent[0];
+    assert_eq!(sent_row.branch.as_str(), projected_branch.as_str());
+    assert_eq!(
+        sent_row.data,
+        encode_row(
+            &projection.projected[&"users".into()].columns,
+            &[Value::Text("alice".to_string())],
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        metadata.as_ref().and_then(|metadata| metadata
+            .metadata
+            .get(MetadataKey::OriginSchemaHash.as_str())),
+        Some(&projection.projected_hash.to_string())
+    );
+}
@@ -457,3 +457,42 @@ fn add_server_with_storage_sends_skipped_parent_before_child() {
         vec![upstream_confirmed_parent.batch_id(), local_child.batch_id()]
     );
 }
+
+#[test]
+fn rows_received_from_servers_advance_the_hybrid_logical_clock() {
+    let mut sm = SyncManager::new();
+    let mut io = MemoryStorage::new();
+    seed_users_schema(&mut io);
+    let server_id = ServerId::new();
+    add_server(&mut sm, &io, server_id);
+
+    // A peer whose clock runs a minute ahead of ours, within the observable drift.
+    let remote_updated_at = sm.reserve_timestamp() + 60 * 1_000_000;
+    let row = visible_row(
+        ObjectId::new(),
+        "main",
+        Vec::new(),
+        remote_updated_at,
+        b"alice",
+    );
+    sm.push_inbox(InboxEntry {
+        source: Source::Server(server_id),
+        payload: SyncPayload::RowBatchCreated {
+            metadata: Some(RowMetadata {
+                id: row.row_id,
+                metadata: row_metadata("users"),
+            }),
+            row,
+        },
+    });
+    sm.process_inbox(&mut io);
+
+    assert!(
+        sm.reserve_hlc() > HlcTimestamp::from_wall_clock(remote_updated_at),
+        "writes after receiving a batch must order after it"
+    );
+    assert!(
+        sm.reserve_timestamp() < remote_updated_at,
+        "receiving a batch must not move the wall clock"
+    );
+}
@@ -0,0 +1,333 @@
+use super::*;
+use crate::sync_manager::clock::HlcTimestamp;
+use crate::sync_manager::timestamp_skew::{
+    TIMESTAMP_OUT_OF_RANGE_CODE, TimestampSkewAction, TimestampSkewPolicy,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const HOUR_MICROS: u64 = 60 * 60 * 1_000_000;
+
+fn now_micros() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_micros() as u64
+}
+
+fn skew_policy(action: TimestampSkewAction) -> TimestampSkewPolicy {
+    TimestampSkewPolicy {
+        max_future_skew: Duration::from_secs(60),
+        max_past_skew: None,
+        action,
+    }
+}
+
+fn session_client(sm: &mut SyncManager, io: &MemoryStorage, role: ClientRole) -> ClientId {
+    let client_id = ClientId::new();
+    add_client(sm, io, client_id);
+    sm.set_client_role(client_id, role);
+    sm.set_client_session(
+        client_id,
+        crate::query_manager::session::Session::new("alice"),
+    );
+    sm.take_outbox();
+    client_id
+}
+
+fn push_row(
+    sm: &mut SyncManager,
+    io: &mut MemoryStorage,
+    client_id: ClientId,
+    row: &StoredRowBatch,
+) {
+    sm.process_from_client(
+        io,
+        client_id,
+        SyncPayload::RowBatchCreated {
+            metadata: Some(RowMetadata {
+                id: row.row_id,
+                metadata: row_metadata("users"),
+            }),
+            row: row.clone(),
+        },
+    );
+}
+
+fn pending_row(check: &PendingPermissionCheck) -> &StoredRowBatch {
+    match &check.payload {
+        SyncPayload::RowBatchCreated { row, .. } => row,
+        other => panic!("expected a row batch payload, got {other:?}"),
+    }
+}
+
+#[test]
+fn future_timestamps_from_users_are_clamped_and_recorded() {
+    let mut sm = SyncManager::new()
+        .with_durability_tier(DurabilityTier::Local)
+        .with_timestamp_skew_policy(skew_policy(TimestampSkewAction::Clamp));
+    let mut io = MemoryStorage::new();
+    let client_id = session_client(&mut sm, &io, ClientRole::User);
+    let client_updated_at = now_micros() + 24 * HOUR_MICROS;
+    let row = visible_row(
+        ObjectId::new(),
+        "main",
+        Vec::new(),
+        client_updated_at,
+        b"alice",
+    );
+
+    let before = now_micros();
+    push_row(&mut sm, &mut io, client_id, &row);
+    let after = now_micros();
+
+    let pending = sm.take_pending_permission_checks();
+    assert_eq!(pending.len(), 1);
+    let adjusted = pending_row(&pending[0]);
+    assert_eq!(adjusted.batch_id, row.batch_id);
+    assert!(
+        (before + 60_000_000..=after + 60_000_001).contains(&adjusted.updated_at),
+        "clamped timestamp should sit at the window edge, got {}",
+        adjusted.updated_at
+    );
+    assert_eq!(adjusted.created_at, adjusted.updated_at);
+    assert_eq!(
+        adjusted.metadata.get(MetadataKey::ClientUpdatedAt.as_str()),
+        Some(&client_updated_at.to_string())
+    );
+    assert_eq!(
+        adjusted
+            .metadata
+            .get(MetadataKey::TimestampAdjustment.as_str())
+            .map(String::as_str),
+        Some("clamp")
+    );
+}
+
+#[test]
+fn future_hlcs_are_clamped_even_when_updated_at_is_in_range() {
+    let mut sm = SyncManager::new()
+        .with_durability_tier(DurabilityTier::Local)
+        .with_timestamp_skew_policy(skew_policy(TimestampSkewAction::Clamp));
+    let mut io = MemoryStorage::new();
+    let client_id = session_client(&mut sm, &io, ClientRole::User);
+    let mut row = visible_row(ObjectId::new(), "main", Vec::new(), now_micros(), b"alice");
+    let client_hlc = HlcTimestamp {
+        physical: now_micros() + 24 * HOUR_MICROS,
+        logical: 7,
+    };
+    row.hlc = Some(client_hlc);
+
+    push_row(&mut sm, &mut io, client_id, &row);
+
+    let pending = sm.take_pending_permission_checks();
+    assert_eq!(pending.len(), 1);
+    let adjusted = pending_row(&pending[0]);
+    assert_eq!(adjusted.updated_at, row.updated_at);
+    assert!(
+        adjusted.hlc() < client_hlc,
+        "a far-future HLC must not win LWW, got {}",
+        adjusted.hlc()
+    );
+    assert_eq!(
+        adjusted.metadata.get(MetadataKey::ClientHlc.as_str()),
+        Some(&client_hlc.to_string())
+    );
+    assert_eq!(adjusted.sealed_digest(), row.content_digest());
+}
+
+#[test]
+fn future_timestamps_are_rejected_under_reject_policy() {
+    let mut sm = SyncManager::new()
+        .with_durability_tier(DurabilityTier::Local)
+        .with_timestamp_skew_policy(skew_policy(TimestampSkewAction::Reject));
+    let mut io = MemoryStorage::new();
+    seed_users_schema(&mut io);
+    let client_id = session_client(&mut sm, &io, ClientRole::User);
+    let row = visible_row(
+        ObjectId::new(),
+        "main",
+        Vec::new(),
+        now_micros() + 24 * HOUR_MICROS,
+        b"alice",
+    );
+
+    push_row(&mut sm, &mut io, client_id, &row);
+
+    assert!(sm.take_pending_permission_checks().is_empty());
+    let outbox = sm.take_outbox();
+    assert!(
+        outbox.iter().any(|entry| matches!(
+            entry,
+            OutboxEntry {
+                destination: Destination::Client(id),
+                payload: SyncPayload::BatchFate {
+                    fate: BatchFate::Rejected { batch_id, code, .. },
+                },
+            } if *id == client_id && *batch_id == row.batch_id && code == TIMESTAMP_OUT_OF_RANGE_CODE
+        )),
+        "out-of-range row should be rejected back to the client, got {outbox:?}",
+    );
+}
+
+#[test]
+fn past_timestamps_are_accepted_unless_bounded() {
+    let mut sm = SyncManager::new()
+        .with_durability_tier(DurabilityTier::Local)
+        .with_timestamp_skew_policy(TimestampSkewPolicy {
+            max_past_skew: Some(Duration::from_secs(60 * 60)),
+            ..skew_policy(TimestampSkewAction::Restamp)
+        });
+    let mut io = MemoryStorage::new();
+    let client_id = session_client(&mut sm, &io, ClientRole::User);
+    let recent = visible_row(
+        ObjectId::new(),
+        "main",
+        Vec::new(),
+        now_micros() - HOUR_MICROS / 2,
+        b"recent",
+    );
+    let stale = visible_row(
+        ObjectId::new(),
+        "main",
+        Vec::new(),
+        now_micros() - 48 * HOUR_MICROS,
+        b"stale",
+    );
+
+    push_row(&mut sm, &mut io, client_id, &recent);
+    push_row(&mut sm, &mut io, client_id, &stale);
+
+    let pending = sm.take_pending_permission_checks();
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending_row(&pending[0]), &recent);
+    let restamped = pending_row(&pending[1]);
+    assert!(restamped.updated_at > stale.updated_at + 47 * HOUR_MICROS);
+    assert_eq!(
+        restamped
+            .metadata
+            .get(MetadataKey::TimestampAdjustment.as_str())
+            .map(String::as_str),
+        Some("restamp")
+    );
+}
+
+#[test]
+fn backend_clients_keep_their_timestamps() {
+    let mut sm = SyncManager::new()
+        .with_durability_tier(DurabilityTier::Local)
+        .with_timestamp_skew_policy(skew_policy(TimestampSkewAction::Reject));
+    let mut io = MemoryStorage::new();
+    seed_users_schema(&mut io);
+    let client_id = session_client(&mut sm, &io, ClientRole::Backend);
+    let row = visible_row(
+        ObjectId::new(),
+        "main",
+        Vec::new(),
+        now_micros() + 24 * HOUR_MICROS,
+        b"alice",
+    );
+
+    push_row(&mut sm, &mut io, client_id, &row);
+
+    let stored = io
+        .load_history_row_batch("users", "main", row.row_id, row.batch_id)
+        .unwrap()
+        .expect("backend row should be applied directly");
+    assert_eq!(stored.updated_at, row.updated_at);
+}
+
+#[test]
+fn replaying_an_adjusted_row_matches_the_stored_adjustment() {
+    let mut sm = SyncManager::new()
+        .with_durability_tier(DurabilityTier::Local)
+        .with_timestamp_skew_policy(skew_policy(TimestampSkewAction::Restamp));
+    let mut io = MemoryStorage::new();
+    seed_users_schema(&mut io);
+    let client_id = session_client(&mut sm, &io, ClientRole::User);
+    let row = visible_row(
+        ObjectId::new(),
+        "main",
+        Vec::new(),
+        now_micros() + 24 * HOUR_MICROS,
+        b"alice",
+    );
+
+    push_row(&mut sm, &mut io, client_id, &row);
+    let mut pending = sm.take_pending_permission_checks();
+    assert_eq!(pending.len(), 1);
+    sm.approve_permission_check(&mut io, pending.remove(0));
+    let stored = io
+        .load_history_row_batch("users", "main", row.row_id, row.batch_id)
+        .unwrap()
+        .expect("approved row should be stored");
+    assert!(stored.updated_at < row.updated_at);
+    sm.take_outbox();
+
+    push_row(&mut sm, &mut io, client_id, &row);
+
+    let pending = sm.take_pending_permission_checks();
+    assert!(
+        pending.is_empty(),
+        "replaying the original row should hit the idempotent replay path, got {pending:?}",
+    );
+}
+
+#[test]
+fn clamped_direct_batch_still_settles_against_its_seal() {
+    let mut sm = SyncManager::new()
+        .with_durability_tier(DurabilityTier::Local)
+        .with_timestamp_skew_policy(skew_policy(TimestampSkewAction::Clamp));
+    let mut io = MemoryStorage::new();
+    seed_users_schema(&mut io);
+    let client_id = session_client(&mut sm, &io, ClientRole::User);
+    let row = row_with_state(
+        visible_row(
+            ObjectId::new(),
+            "main",
+            Vec::new(),
+            now_micros() + 24 * HOUR_MICROS,
+            b"alice",
+        ),
+        crate::row_histories::RowState::VisibleDirect,
+        Some(DurabilityTier::Local),
+    );
+    let batch_id = row.batch_id;
+
+    push_row(&mut sm, &mut io, client_id, &row);
+    sm.process_from_client(
+        &mut io,
+        client_id,
+        SyncPayload::SealBatch {
+            submission: sealed_submission(
+                batch_id,
+                "main",
+                vec![SealedBatchMember {
+                    object_id: row.row_id,
+                    row_digest: row.content_digest(),
+                }],
+                Vec::new(),
+            ),
+        },
+    );
+    let mut pending = sm.take_pending_permission_checks();
+    assert_eq!(pending.len(), 1);
+    let clamped = pending_row(&pending[0]);
+    assert!(clamped.updated_at < row.updated_at);
+    assert_ne!(clamped.content_digest(), row.content_digest());
+    assert_eq!(clamped.sealed_digest(), row.content_digest());
+    sm.approve_permission_check(&mut io, pending.remove(0));
+
+    let outbox = sm.take_outbox();
+    assert!(
+        outbox.iter().any(|entry| matches!(
+            entry,
+            OutboxEntry {
+                destination: Destination::Client(id),
+                payload: SyncPayload::BatchFate {
+                    fate: BatchFate::DurableDirect { batch_id: settled, .. },
+                },
+            } if *id == client_id && *settled == batch_id
+        )),
+        "a clamped direct row should still settle its sealed batch, got {outbox:?}",
+    );
+}
@@ -0,0 +1,311 @@
+//! Server-side policy for client timestamps far from the server's clock.
+//!
+//! Row batches carry client-supplied `updated_at` / `created_at` timestamps
+//! and an HLC, and LWW resolution orders visible rows by HLC, so a device
+//! whose clock runs years ahead would win every conflict. When a
+//! [`TimestampSkewPolicy`] is installed, rows from user clients whose
+//! timestamps or HLC physical time fall outside the allowed window around the
+//! server's received-at time are rejected, clamped to the window edge, or
+//! re-stamped with the received-at time. Adjusted rows keep the client's
+//! original values in row metadata ([`MetadataKey::ClientUpdatedAt`] /
+//! [`MetadataKey::ClientCreatedAt`] / [`MetadataKey::ClientHlc`]) next to
+//! [`MetadataKey::TimestampAdjustment`].
+//!
+//! Every batch, direct or transactional, is sealed with per-row content
+//! digests that cover `updated_at`, the HLC and metadata. Seal checks use
+//! [`StoredRowBatch::sealed_digest`], which undoes a recorded adjustment, so
+//! adjusted rows still match the seal their client sent.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::metadata::MetadataKey;
+use crate::row_histories::{RowState, StoredRowBatch};
+use crate::storage::Storage;
+use crate::sync_manager::clock::HlcTimestamp;
+
+/// Rejection code used when a client row's timestamp is outside the allowed skew.
+pub const TIMESTAMP_OUT_OF_RANGE_CODE: &str = "timestamp_out_of_range";
+
+/// What the server does with a row whose timestamp is out of range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampSkewAction {
+    /// Reject the row's batch with [`TIMESTAMP_OUT_OF_RANGE_CODE`].
+    Reject,
+    /// Move the timestamp to the nearest edge of the allowed window.
+    #[default]
+    Clamp,
+    /// Replace the timestamp with the server's received-at time.
+    Restamp,
+}
+
+impl TimestampSkewAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Reject => "reject",
+            Self::Clamp => "clamp",
+            Self::Restamp => "restamp",
+        }
+    }
+}
+
+impl FromStr for TimestampSkewAction {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "reject" => Ok(Self::Reject),
+            "clamp" => Ok(Self::Clamp),
+            "restamp" => Ok(Self::Restamp),
+            other => Err(format!(
+                "unknown timestamp skew action '{other}' (expected reject, clamp or restamp)"
+            )),
+        }
+    }
+}
+
+/// Allowed distance between a client row's timestamps and the server's
+/// received-at time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimestampSkewPolicy {
+    /// How far ahead of the server clock a timestamp may be.
+    pub max_future_skew: Duration,
+    /// How far behind the server clock a timestamp may be. `None` accepts any
+    /// past timestamp, since clients that sync after a long offline stretch
+    /// legitimately send old ones.
+    pub max_past_skew: Option<Duration>,
+    pub action: TimestampSkewAction,
+}
+
+impl Default for TimestampSkewPolicy {
+    fn default() -> Self {
+        Self {
+            max_future_skew: Duration::from_secs(5 * 60),
+            max_past_skew: None,
+            action: TimestampSkewAction::default(),
+        }
+    }
+}
+
+/// Outcome of checking one row against a [`TimestampSkewPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimestampSkewDecision {
+    InRange,
+    Adjusted(StoredRowBatch),
+    Rejected { reason: String },
+}
+
+impl TimestampSkewPolicy {
+    fn window(&self, received_at: u64) -> (u64, u64) {
+        let earliest = self
+            .max_past_skew
+            .map(|skew| received_at.saturating_sub(skew.as_micros() as u64))
+            .unwrap_or(0);
+        let latest = received_at.saturating_add(self.max_future_skew.as_micros() as u64);
+        (earliest, latest)
+    }
+
+    /// Check `row`'s timestamps against the window around `received_at`
+    /// (microseconds since the Unix epoch).
+    pub fn evaluate(&self, row: &StoredRowBatch, received_at: u64) -> TimestampSkewDecision {
+        let (earliest, latest) = self.window(received_at);
+        let in_range = |timestamp: u64| (earliest..=latest).contains(&timestamp);
+        let hlc_in_range = row.hlc.is_none_or(|hlc| in_range(hlc.physical));
+        if in_range(row.updated_at) && in_range(row.created_at) && hlc_in_range {
+            return TimestampSkewDecision::InRange;
+        }
+
+        if self.action == TimestampSkewAction::Reject {
+            return TimestampSkewDecision::Rejected {
+                reason: out_of_range_reason(row, received_at),
+            };
+        }
+        let adjust = |timestamp: u64| match self.action {
+            TimestampSkewAction::Restamp => received_at,
+            _ => timestamp.clamp(earliest, latest),
+        };
+
+        let mut adjusted = row.clone();
+        if !in_range(row.updated_at) {
+            adjusted.updated_at = adjust(row.updated_at);
+            adjusted.metadata.insert(
+                MetadataKey::ClientUpdatedAt.as_str(),
+                row.updated_at.to_string(),
+            );
+        }
+        if !in_range(row.created_at) {
+            // A row is never created after its own update.
+            adjusted.created_at = adjust(row.created_at).min(adjusted.updated_at);
+            adjusted.metadata.insert(
+                MetadataKey::ClientCreatedAt.as_str(),
+                row.created_at.to_string(),
+            );
+        }
+        if let Some(hlc) = row.hlc
+            && !hlc_in_range
+        {
+            adjusted.hlc = Some(HlcTimestamp::from_wall_clock(adjust(hlc.physical)));
+            adjusted
+                .metadata
+                .insert(MetadataKey::ClientHlc.as_str(), hlc.to_string());
+        }
+        adjusted.metadata.insert(
+            MetadataKey::TimestampAdjustment.as_str(),
+            self.action.as_str(),
+        );
+        TimestampSkewDecision::Adjusted(adjusted)
+    }
+}
+
+fn out_of_range_reason(row: &StoredRowBatch, received_at: u64) -> String {
+    format!(
+        "row timestamp {} (HLC {}) is too far from server time {received_at}",
+        row.updated_at,
+        row.hlc()
+    )
+}
+
+impl SyncManager {
+    /// Install a timestamp skew policy for rows received from user clients.
+    pub fn with_timestamp_skew_policy(mut self, policy: TimestampSkewPolicy) -> Self {
+        self.timestamp_skew_policy = Some(policy);
+        self
+    }
+
+    /// Apply the timestamp skew policy to a user client's row payload.
+    ///
+    /// Returns the payload to continue processing — possibly with adjusted
+    /// timestamps — or `None` when the row's batch was rejected.
+    pub(super) fn enforce_timestamp_skew_policy<H: Storage>(
+        &mut self,
+        storage: &mut H,
+        client_id: ClientId,
+        payload: SyncPayload,
+    ) -> Option<SyncPayload> {
+        let Some(policy) = self.timestamp_skew_policy else {
+            return Some(payload);
+        };
+        let is_user = self
+            .clients
+            .get(&client_id)
+            .is_some_and(|client| client.role == ClientRole::User);
+        if !is_user || payload.is_catalogue() {
+            return Some(payload);
+        }
+        let (metadata, row, needed) = match payload {
+            SyncPayload::RowBatchCreated { metadata, row } => (metadata, row, false),
+            SyncPayload::RowBatchNeeded { metadata, row } => (metadata, row, true),
+            payload => return Some(payload),
+        };
+        let rebuild = |row| {
+            if needed {
+                SyncPayload::RowBatchNeeded {
+                    metadata: metadata.clone(),
+                    row,
+                }
+            } else {
+                SyncPayload::RowBatchCreated {
+                    metadata: metadata.clone(),
+                    row,
+                }
+            }
+        };
+
+        // Reconnect replays the client's original row. Reuse the adjustment the
+        // server already stored so the replay still matches exactly.
+        if let Some(stored) = self.stored_timestamp_adjustment(storage, &row, metadata.as_ref()) {
+            return Some(rebuild(stored));
+        }
+
+        let received_at = self.clock.reserve_timestamp();
+        match policy.evaluate(&row, received_at) {
+            TimestampSkewDecision::InRange => Some(rebuild(row)),
+            TimestampSkewDecision::Adjusted(adjusted) => {
+                tracing::info!(
+                    %client_id,
+                    batch_id = ?row.batch_id,
+                    client_updated_at = row.updated_at,
+                    updated_at = adjusted.updated_at,
+                    received_at,
+                    action = policy.action.as_str(),
+                    "adjusted out-of-range client timestamp"
+                );
+                Some(rebuild(adjusted))
+            }
+            TimestampSkewDecision::Rejected { reason } => {
+                tracing::info!(
+                    %client_id,
+                    batch_id = ?row.batch_id,
+                    client_updated_at = row.updated_at,
+                    received_at,
+                    "rejected out-of-range client timestamp"
+                );
+                if matches!(
+                    row.state,
+                    RowState::StagingPending | RowState::VisibleDirect
+                ) {
+                    let fate = BatchFate::Rejected {
+                        batch_id: row.batch_id,
+                        code: TIMESTAMP_OUT_OF_RANGE_CODE.to_string(),
+                        reason,
+                    };
+                    self.reject_permission_batch(storage, client_id, fate, row);
+                } else {
+                    self.outbox.push(OutboxEntry {
+                        destination: Destination::Client(client_id),
+                        payload: SyncPayload::Error(SyncError::PermissionDenied {
+                            object_id: row.row_id,
+                            branch_name: BranchName::new(&row.branch),
+                            code: TIMESTAMP_OUT_OF_RANGE_CODE.to_string(),
+                            reason,
+                        }),
+                    });
+                }
+                None
+            }
+        }
+    }
+
+    /// The stored version of `row` when the server previously adjusted its
+    /// timestamps, with the stored timestamps and metadata applied.
+    fn stored_timestamp_adjustment<H: Storage>(
+        &self,
+        storage: &H,
+        row: &StoredRowBatch,
+        metadata: Option<&RowMetadata>,
+    ) -> Option<StoredRowBatch> {
+        let row_metadata = self.row_metadata_from_payload(storage, row, metadata)?;
+        let table = row_metadata.get(MetadataKey::Table.as_str())?;
+        let existing = storage
+            .load_history_row_batch(table, &row.branch, row.row_id, row.batch_id)
+            .ok()
+            .flatten()?;
+        existing
+            .metadata
+            .get(MetadataKey::TimestampAdjustment.as_str())?;
+        let client_updated_at = existing
+            .metadata
+            .get(MetadataKey::ClientUpdatedAt.as_str())
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(existing.updated_at);
+        let client_hlc = existing
+            .metadata
+            .get(MetadataKey::ClientHlc.as_str())
+            .and_then(|value| value.parse::<HlcTimestamp>().ok())
+            .or(existing.hlc);
+        if client_updated_at != row.updated_at || client_hlc != row.hlc {
+            return None;
+        }
+
+        let mut replayed = row.clone();
+        replayed.updated_at = existing.updated_at;
+        replayed.hlc = existing.hlc;
+        replayed.created_at = existing.created_at;
+        replayed.metadata = existing.metadata;
+        Some(replayed)
+    }
+}
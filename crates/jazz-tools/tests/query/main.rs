@@ -8,5 +8,6 @@ mod common;
 mod joins;
 mod pagination;
 mod recursive_queries;
+mod sql;
 mod subqueries;
 mod subscriptions;
@@ -0,0 +1,122 @@
+#![cfg(feature = "test")]
+
+use jazz_tools::server::JazzServer;
+use jazz_tools::{QueryBuilder, Value};
+
+use crate::common::{QUERY_TIMEOUT, READY_TIMEOUT, TodoSeed, create_todo, subscription_schema};
+use crate::support::{
+    TestingClient, has_added, has_any_change, wait_for_rows, wait_for_subscription_update,
+};
+
+/// Verifies that a SQL string parsed by the client runs as a reactive,
+/// server-synced subscription like a builder query.
+///
+/// ```text
+/// alice ──insert A (open), B (done), C (open)──► server
+/// bob   ──subscribe SQL "done = false"──► stream add A, C
+/// alice ──insert D (open)──► server ──► bob stream add D
+/// ```
+#[tokio::test]
+async fn sql_subscription_syncs_matching_rows_from_the_server() {
+    let schema = subscription_schema();
+    let server = JazzServer::start_with_schema(schema.clone()).await;
+    let writer = TestingClient::builder()
+        .with_server(&server)
+        .with_schema(schema.clone())
+        .with_user_id("sql-writer")
+        .ready_on("todos", READY_TIMEOUT)
+        .connect()
+        .await;
+
+    let seed = |title, done, priority| TodoSeed {
+        title,
+        done,
+        priority: Some(priority),
+        tags: &["sql"],
+        payload: None,
+    };
+    let a_id = create_todo(&writer, seed("A", false, 1)).await;
+    let b_id = create_todo(&writer, seed("B", true, 2)).await;
+    let c_id = create_todo(&writer, seed("C", false, 3)).await;
+
+    wait_for_rows(
+        &writer,
+        QueryBuilder::new("todos").build(),
+        "writer sees all rows before the SQL subscriber connects",
+        |rows| (rows.len() == 3).then_some(rows),
+    )
+    .await;
+
+    let subscriber = TestingClient::builder()
+        .with_server(&server)
+        .with_schema(schema)
+        .with_user_id("sql-subscriber")
+        .ready_on("todos", READY_TIMEOUT)
+        .connect()
+        .await;
+    let query = subscriber
+        .sql_query("SELECT title, priority FROM todos WHERE done = false ORDER BY priority DESC")
+        .expect("parse SQL query");
+    let mut stream = subscriber
+        .subscribe(query.clone())
+        .await
+        .expect("subscribe to SQL query");
+    let mut log = Vec::new();
+
+    wait_for_subscription_update(
+        &mut stream,
+        &mut log,
+        QUERY_TIMEOUT,
+        "SQL subscription receives the open todos",
+        |log| has_added(log, a_id) && has_added(log, c_id) && !has_any_change(log, b_id),
+    )
+    .await;
+
+    let d_id = create_todo(&writer, seed("D", false, 4)).await;
+    wait_for_subscription_update(
+        &mut stream,
+        &mut log,
+        QUERY_TIMEOUT,
+        "SQL subscription receives a later matching insert",
+        |log| has_added(log, d_id),
+    )
+    .await;
+
+    let rows = wait_for_rows(
+        &subscriber,
+        query,
+        "SQL query returns the projected open todos in order",
+        |rows| (rows.len() == 3).then_some(rows),
+    )
+    .await;
+    assert_eq!(
+        rows.into_iter()
+            .map(|(_, values)| values)
+            .collect::<Vec<_>>(),
+        vec![
+            vec![Value::Text("D".to_string()), Value::Integer(4)],
+            vec![Value::Text("C".to_string()), Value::Integer(3)],
+            vec![Value::Text("A".to_string()), Value::Integer(1)],
+        ]
+    );
+
+    writer.shutdown().await.expect("shutdown writer");
+    subscriber.shutdown().await.expect("shutdown subscriber");
+    server.shutdown().await;
+}
+
+/// Verifies that SQL outside the supported dialect fails when parsed, before
+/// anything is subscribed.
+#[tokio::test]
+async fn sql_query_reports_unknown_tables() {
+    let client = jazz_tools::JazzClient::test_client(subscription_schema()).await;
+
+    let error = client
+        .sql_query("SELECT * FROM accounts")
+        .expect_err("unknown table must be rejected");
+
+    assert!(
+        error.to_string().contains("accounts"),
+        "unexpected error: {error:?}"
+    );
+}
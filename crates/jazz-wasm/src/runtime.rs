@@ -1714,6 +1714,42 @@ impl WasmRuntime {
         Ok(promise)
     }
 
+    /// Describe how a query compiles; `analyze` adds per-node row counters.
+    #[wasm_bindgen]
+    pub fn explain(
+        &self,
+        query_json: &str,
+        session_json: Option<String>,
+        analyze: Option<bool>,
+    ) -> Result<JsValue, JsError> {
+        let query = parse_query(query_json).map_err(|e| JsError::new(&e))?;
+        let session = parse_session_json(session_json)?;
+
+        let explain = self
+            .core
+            .borrow()
+            .explain(&query, session, analyze.unwrap_or(false))
+            .map_err(|e| JsError::new(&format!("Explain failed: {e}")))?;
+
+        let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+        explain
+            .serialize(&serializer)
+            .map_err(|e| JsError::new(&format!("Serialization failed: {:?}", e)))
+    }
+
+    /// Parse a SQL `SELECT` into query JSON for `query` or `createSubscription`.
+    #[wasm_bindgen(js_name = sqlQuery)]
+    pub fn sql_query(&self, sql: &str, session_json: Option<String>) -> Result<String, JsError> {
+        let session = parse_session_json(session_json)?;
+        let query = self
+            .core
+            .borrow()
+            .sql_query(sql, session.as_ref())
+            .map_err(|e| JsError::new(&format!("SQL query failed: {e}")))?;
+        serde_json::to_string(&query)
+            .map_err(|e| JsError::new(&format!("Serialization failed: {e}")))
+    }
+
     /// Update a row by ObjectId.
     #[wasm_bindgen]
     pub fn update(
@@ -1964,7 +2000,7 @@ impl WasmRuntime {
     // Schema Access
     // =========================================================================
 
-    /// Get the current schema as JSON.
+    /// Get the current schema as JSON, including table and column annotations.
     #[wasm_bindgen(js_name = getSchema)]
     pub fn get_schema(&self) -> Result<JsValue, JsError> {
         let core = self.core.borrow();
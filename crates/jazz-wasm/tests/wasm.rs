@@ -57,6 +57,86 @@ fn test_parse_schema_invalid() {
     assert!(result.is_err());
 }
 
+#[wasm_bindgen_test]
+fn test_parse_schema_with_temporal_columns() {
+    let schema_json = r#"{
+        "events": {
+            "columns": [
+                {"name": "day", "column_type": {"type": "Date"}, "nullable": false},
+                {"name": "starts", "column_type": {"type": "Time"}, "nullable": false},
+                {"name": "lasts", "column_type": {"type": "Interval"}, "nullable": true}
+            ]
+        }
+    }"#;
+
+    let result = parse_schema(schema_json);
+    assert!(result.is_ok());
+}
+
+#[wasm_bindgen_test]
+fn test_temporal_values_cross_as_iso_text() {
+    let cases = [
+        r#"{"type":"Date","value":"2024-02-29"}"#,
+        r#"{"type":"Time","value":"13:45:00.25"}"#,
+        r#"{"type":"Interval","value":"P1Y2M3DT4H5M6S"}"#,
+    ];
+
+    for json in cases {
+        let value: Value = serde_wasm_bindgen::from_value(js_sys::JSON::parse(json).unwrap())
+            .expect("temporal value from JS");
+        assert!(matches!(
+            value,
+            Value::Date(_) | Value::Time(_) | Value::Interval(_)
+        ));
+
+        let back = serde_wasm_bindgen::to_value(&value).unwrap();
+        assert_eq!(String::from(js_sys::JSON::stringify(&back).unwrap()), json);
+    }
+
+    let malformed = js_sys::JSON::parse(r#"{"type":"Date","value":"2023-02-29"}"#).unwrap();
+    assert!(serde_wasm_bindgen::from_value::<Value>(malformed).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_geo_points_cross_as_lat_lon_objects() {
+    let json = r#"{"type":"GeoPoint","value":{"lat":52.52,"lon":13.405}}"#;
+    let value: Value = serde_wasm_bindgen::from_value(js_sys::JSON::parse(json).unwrap())
+        .expect("geo point from JS");
+    assert!(matches!(value, Value::GeoPoint(_)));
+
+    let back = serde_wasm_bindgen::to_value(&value).unwrap();
+    assert_eq!(String::from(js_sys::JSON::stringify(&back).unwrap()), json);
+
+    let out_of_range =
+        js_sys::JSON::parse(r#"{"type":"GeoPoint","value":{"lat":91,"lon":0}}"#).unwrap();
+    assert!(serde_wasm_bindgen::from_value::<Value>(out_of_range).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_decimals_cross_as_exact_text() {
+    let json = r#"{"type":"Decimal","value":"-123456789012345678901234.5678"}"#;
+    let value: Value = serde_wasm_bindgen::from_value(js_sys::JSON::parse(json).unwrap())
+        .expect("decimal from JS");
+    assert!(matches!(value, Value::Decimal(_)));
+
+    let back = serde_wasm_bindgen::to_value(&value).unwrap();
+    assert_eq!(String::from(js_sys::JSON::stringify(&back).unwrap()), json);
+}
+
+#[wasm_bindgen_test]
+fn test_vectors_cross_as_number_arrays() {
+    let json = r#"{"type":"Vector","value":[0.5,-1,2]}"#;
+    let value: Value =
+        serde_wasm_bindgen::from_value(js_sys::JSON::parse(json).unwrap()).expect("vector from JS");
+    assert!(matches!(value, Value::Vector(_)));
+
+    let back = serde_wasm_bindgen::to_value(&value).unwrap();
+    assert_eq!(String::from(js_sys::JSON::stringify(&back).unwrap()), json);
+
+    let empty = js_sys::JSON::parse(r#"{"type":"Vector","value":[]}"#).unwrap();
+    assert!(serde_wasm_bindgen::from_value::<Value>(empty).is_err());
+}
+
 #[wasm_bindgen_test]
 fn test_query_builder_basic() {
     let builder = WasmQueryBuilder::new("todos");
@@ -147,10 +147,14 @@ function isColumnSortable(columnType: ColumnType): boolean {
     case "Integer":
     case "BigInt":
     case "Double":
+    case "Decimal":
     case "Boolean":
     case "Text":
     case "Enum":
     case "Timestamp":
+    case "Date":
+    case "Time":
+    case "Interval":
     case "Uuid":
       return true;
     default:
@@ -216,9 +220,12 @@ function isDisplayFriendlyColumn(column: ColumnDescriptor): boolean {
     case "Text":
     case "Enum":
     case "Timestamp":
+    case "Date":
+    case "Time":
     case "Integer":
     case "BigInt":
     case "Double":
+    case "Decimal":
     case "Boolean":
       return true;
     default:
@@ -293,9 +300,15 @@ function unwrapDefaultValue(defaultValue: Value, columnType: ColumnType): unknow
     case "Integer":
     case "BigInt":
     case "Double":
+    case "Decimal":
     case "Boolean":
     case "Text":
     case "Timestamp":
+    case "Date":
+    case "Time":
+    case "Interval":
+    case "GeoPoint":
+    case "Vector":
     case "Uuid":
       return columnType.type === "Json"
         ? JSON.parse(String(defaultValue.value))
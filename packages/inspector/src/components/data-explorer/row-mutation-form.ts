@@ -66,6 +66,13 @@ export function parseMutationFieldValue(columnType: ColumnType, valueText: strin
       }
       return parsed;
     }
+    case "Decimal": {
+      if (trimmed.length === 0) {
+        throw new Error("Value is required.");
+      }
+      // Kept as text so no digits are lost to floating point.
+      return trimmed;
+    }
     case "Timestamp": {
       if (trimmed.length === 0) {
         throw new Error("Timestamp is required.");
@@ -88,7 +95,8 @@ export function parseMutationFieldValue(columnType: ColumnType, valueText: strin
     }
     case "Bytea":
       throw new Error("Binary fields are read-only in the inspector.");
-    case "Array": {
+    case "Array":
+    case "Vector": {
       try {
         const parsed = JSON.parse(trimmed) as unknown;
         if (!Array.isArray(parsed)) {
@@ -110,6 +118,17 @@ export function parseMutationFieldValue(columnType: ColumnType, valueText: strin
         throw new Error("Row value must be valid JSON object.");
       }
     }
+    case "GeoPoint": {
+      try {
+        const parsed = JSON.parse(trimmed) as unknown;
+        if (typeof parsed !== "object" || parsed === null || Array.isArray(parsed)) {
+          throw new Error();
+        }
+        return parsed;
+      } catch {
+        throw new Error('GeoPoint must be a JSON object like {"lat": 52.52, "lon": 13.405}.');
+      }
+    }
     case "Enum":
       if (!columnType.variants.includes(valueText)) {
         throw new Error(`Expected one of: ${columnType.variants.join(", ")}`);
@@ -90,6 +90,15 @@ function baseBuilderExpression(columnType: WasmColumnType, references?: string):
       return `s.array(${baseBuilderExpression(columnType.element, references)})`;
     case "BigInt":
       throw new Error("Migration stub generation does not yet support BIGINT columns.");
+    case "Decimal":
+    case "Date":
+    case "Time":
+    case "Interval":
+    case "GeoPoint":
+    case "Vector":
+      throw new Error(
+        `Migration stub generation does not yet support ${columnType.type.toUpperCase()} columns.`,
+      );
     case "Row":
       throw new Error("Migration stub generation does not yet support row-valued columns.");
   }
@@ -165,6 +174,15 @@ function renderAddOperationExpression(column: ColumnDescriptor, defaultExpressio
       return `s.add.array({ of: ${renderArrayElementExpression(column.column_type.element, column.references)}, default: ${defaultExpression} })`;
     case "BigInt":
       throw new Error("Migration stub generation does not yet support BIGINT columns.");
+    case "Decimal":
+    case "Date":
+    case "Time":
+    case "Interval":
+    case "GeoPoint":
+    case "Vector":
+      throw new Error(
+        `Migration stub generation does not yet support ${column.column_type.type.toUpperCase()} columns.`,
+      );
     case "Row":
       throw new Error("Migration stub generation does not yet support row-valued columns.");
   }
@@ -204,6 +222,15 @@ function renderDropOperationExpression(
       return `s.drop.array({ of: ${renderArrayElementExpression(column.column_type.element, column.references)}, backwardsDefault: ${defaultExpression} })`;
     case "BigInt":
       throw new Error("Migration stub generation does not yet support BIGINT columns.");
+    case "Decimal":
+    case "Date":
+    case "Time":
+    case "Interval":
+    case "GeoPoint":
+    case "Vector":
+      throw new Error(
+        `Migration stub generation does not yet support ${column.column_type.type.toUpperCase()} columns.`,
+      );
     case "Row":
       throw new Error("Migration stub generation does not yet support row-valued columns.");
   }
@@ -5,6 +5,7 @@ export type {
   Value,
   ColumnType,
   ColumnDescriptor,
+  GeoPoint,
   TableSchema,
   WasmSchema,
   WasmRow,
@@ -11,15 +11,27 @@ export type Value =
   | { type: "Integer"; value: number }
   | { type: "BigInt"; value: number }
   | { type: "Double"; value: number }
+  | { type: "Decimal"; value: string }
   | { type: "Boolean"; value: boolean }
   | { type: "Text"; value: string }
   | { type: "Timestamp"; value: number }
+  | { type: "Date"; value: string }
+  | { type: "Time"; value: string }
+  | { type: "Interval"; value: string }
+  | { type: "GeoPoint"; value: GeoPoint }
+  | { type: "Vector"; value: number[] }
   | { type: "Uuid"; value: string }
   | { type: "Bytea"; value: Uint8Array }
   | { type: "Array"; value: Value[] }
   | { type: "Row"; value: { id?: string; values: Value[] } }
   | { type: "Null" };
 
+/** Latitude and longitude in degrees. */
+export interface GeoPoint {
+  lat: number;
+  lon: number;
+}
+
 export type InsertValues = Record<string, Value>;
 export type FFIValue = Value;
 export type FFIRecord = InsertValues;
@@ -75,11 +87,17 @@ export type ColumnType =
   | { type: "Integer" }
   | { type: "BigInt" }
   | { type: "Double" }
+  | { type: "Decimal"; precision: number; scale: number }
   | { type: "Boolean" }
   | { type: "Text" }
   | { type: "Json"; schema?: Record<string, unknown> }
   | { type: "Enum"; variants: string[] }
   | { type: "Timestamp" }
+  | { type: "Date" }
+  | { type: "Time" }
+  | { type: "Interval" }
+  | { type: "GeoPoint" }
+  | { type: "Vector"; dims: number }
   | { type: "Uuid" }
   | { type: "Bytea" }
   | { type: "Array"; element: ColumnType }
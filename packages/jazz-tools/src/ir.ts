@@ -32,7 +32,7 @@ export type RelPredicateExpr =
   | "True"
   | "False";
 
-export type RelJoinKind = "Inner" | "Left";
+export type RelJoinKind = "Inner" | "Left" | "Semi" | "Anti";
 
 export type RelJoinCondition = {
   left: RelColumnRef;
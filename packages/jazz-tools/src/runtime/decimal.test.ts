@@ -0,0 +1,28 @@
+import { describe, it, expect } from "vitest";
+import { assertDecimalFits, formatDecimalUnits, toDecimalText } from "./decimal.js";
+
+describe("decimal text", () => {
+  it("formats units at a scale the way the Rust core does", () => {
+    expect(formatDecimalUnits(0n, 0)).toBe("0");
+    expect(formatDecimalUnits(1230n, 2)).toBe("12.30");
+    expect(formatDecimalUnits(-5n, 3)).toBe("-0.005");
+    expect(formatDecimalUnits(10n ** 38n - 1n, 0)).toBe("9".repeat(38));
+  });
+
+  it("reads strings, bigints and numbers without a float round-trip", () => {
+    expect(toDecimalText(" +7 ")).toBe("+7");
+    expect(toDecimalText(".5")).toBe(".5");
+    expect(toDecimalText(123456789012345678901234567890n)).toBe("123456789012345678901234567890");
+    expect(toDecimalText(0.1)).toBe("0.1");
+    expect(() => toDecimalText(".")).toThrow("Invalid Decimal value");
+    expect(() => toDecimalText("1e5")).toThrow("Invalid Decimal value");
+    expect(() => toDecimalText(Infinity)).toThrow("Invalid Decimal value");
+  });
+
+  it("checks precision and scale like DECIMAL(precision, scale)", () => {
+    expect(() => assertDecimalFits("123.4", 5, 2)).not.toThrow();
+    expect(() => assertDecimalFits("-000123.40", 5, 2)).not.toThrow();
+    expect(() => assertDecimalFits("1234.5", 5, 2)).toThrow("does not fit");
+    expect(() => assertDecimalFits("1.005", 10, 2)).toThrow("does not fit");
+  });
+});
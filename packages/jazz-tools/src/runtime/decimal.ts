@@ -0,0 +1,60 @@
+/**
+ * Exact fixed-point numbers for `Decimal` columns.
+ *
+ * Values cross the runtime boundary as plain decimal text (`-12.30`), the same
+ * form the Rust core reads and writes, so no digits are lost to `f64`. JS
+ * numbers are accepted on write through their shortest text form, never
+ * through arithmetic.
+ */
+
+const DECIMAL_RE = /^([+-]?)(\d*)(?:\.(\d*))?$/;
+
+function splitDecimal(text: string): { sign: string; whole: string; fraction: string } | null {
+  const match = DECIMAL_RE.exec(text);
+  if (!match || (match[2] === "" && !match[3])) {
+    return null;
+  }
+  return { sign: match[1], whole: match[2], fraction: match[3] ?? "" };
+}
+
+/** Text of a `Decimal` value given as a decimal string, a bigint or a finite number. */
+export function toDecimalText(value: unknown): string {
+  const text =
+    typeof value === "string"
+      ? value.trim()
+      : typeof value === "bigint" || (typeof value === "number" && Number.isFinite(value))
+        ? String(value)
+        : null;
+  // `String(1e21)` is `"1e+21"`; exponent forms are rejected like in the core.
+  if (text === null || !splitDecimal(text)) {
+    throw new Error(
+      `Invalid Decimal value ${String(value)}. Expected a decimal string such as "-12.30", a bigint or a finite number.`,
+    );
+  }
+  return text;
+}
+
+/** Throw unless `text` fits a `DECIMAL(precision, scale)` column without rounding. */
+export function assertDecimalFits(text: string, precision: number, scale: number): void {
+  const parts = splitDecimal(text);
+  if (!parts) {
+    throw new Error(`Invalid Decimal value ${text}.`);
+  }
+  const fraction = parts.fraction.replace(/0+$/, "");
+  const digits = `${parts.whole}${fraction.padEnd(scale, "0")}`.replace(/^0+/, "");
+  if (fraction.length > scale || digits.length > precision) {
+    throw new Error(
+      `Decimal value ${text} does not fit DECIMAL(${precision}, ${scale}) without rounding.`,
+    );
+  }
+}
+
+/** Text of `units * 10^-scale`, keeping all `scale` fractional digits. */
+export function formatDecimalUnits(units: bigint, scale: number): string {
+  const sign = units < 0n ? "-" : "";
+  const digits = (units < 0n ? -units : units).toString().padStart(scale + 1, "0");
+  if (scale === 0) {
+    return `${sign}${digits}`;
+  }
+  return `${sign}${digits.slice(0, -scale)}.${digits.slice(-scale)}`;
+}
@@ -4,9 +4,15 @@ type JsonFFIValue =
   | { type: "Integer"; value: number }
   | { type: "BigInt"; value: number }
   | { type: "Double"; value: number }
+  | { type: "Decimal"; value: string }
   | { type: "Boolean"; value: boolean }
   | { type: "Text"; value: string }
   | { type: "Timestamp"; value: number }
+  | { type: "Date"; value: string }
+  | { type: "Time"; value: string }
+  | { type: "Interval"; value: string }
+  | { type: "GeoPoint"; value: { lat: number; lon: number } }
+  | { type: "Vector"; value: number[] }
   | { type: "Uuid"; value: string }
   | { type: "Bytea"; value: string }
   | { type: "Array"; value: JsonFFIValue[] }
@@ -56,9 +62,15 @@ function encodeJsonFFIValue(value: Value): JsonFFIValue {
     case "Integer":
     case "BigInt":
     case "Double":
+    case "Decimal":
     case "Boolean":
     case "Text":
     case "Timestamp":
+    case "Date":
+    case "Time":
+    case "Interval":
+    case "GeoPoint":
+    case "Vector":
     case "Uuid":
     case "Null":
       return { ...value };
@@ -82,9 +94,15 @@ function decodeJsonFFIValue(value: JsonFFIValue): Value {
     case "Integer":
     case "BigInt":
     case "Double":
+    case "Decimal":
     case "Boolean":
     case "Text":
     case "Timestamp":
+    case "Date":
+    case "Time":
+    case "Interval":
+    case "GeoPoint":
+    case "Vector":
     case "Uuid":
     case "Null":
       return { ...value };
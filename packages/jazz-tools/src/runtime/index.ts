@@ -68,6 +68,7 @@ export {
 export { translateQuery } from "./query-adapter.js";
 export { transformRows, unwrapValue, type WasmValue } from "./row-transformer.js";
 export { toValue, toWriteRecord } from "./value-converter.js";
+export { dateAtTime, dateInTimeZone, timeInTimeZone } from "./temporal.js";
 export {
   DEFAULT_FILE_CHUNK_SIZE_BYTES,
   MAX_FILE_PART_BYTES,
@@ -0,0 +1,89 @@
+import { describe, it, expect } from "vitest";
+import type { ColumnDescriptor } from "../drivers/types.js";
+import { decodeNativeRowObject, decodeNativeRowValues } from "./native-row-format.js";
+
+describe("native row format", () => {
+  it("decodes Date, Time and Interval columns to ISO text", () => {
+    const columns: ColumnDescriptor[] = [
+      { name: "due", column_type: { type: "Date" }, nullable: false },
+      { name: "at", column_type: { type: "Time" }, nullable: false },
+      { name: "every", column_type: { type: "Interval" }, nullable: true },
+    ];
+    const row = new Uint8Array(4 + 8 + 1 + 16);
+    const view = new DataView(row.buffer);
+    view.setInt32(0, 19_782, true);
+    view.setBigUint64(4, 49_500_250_000n, true);
+    row[12] = 1;
+    view.setInt32(13, 14, true);
+    view.setInt32(17, 3, true);
+    view.setBigInt64(21, 14_706_000_000n, true);
+
+    expect(decodeNativeRowValues(columns, row)).toEqual([
+      { type: "Date", value: "2024-02-29" },
+      { type: "Time", value: "13:45:00.25" },
+      { type: "Interval", value: "P1Y2M3DT4H5M6S" },
+    ]);
+    expect(decodeNativeRowObject("row-1", columns, row)).toEqual({
+      id: "row-1",
+      due: "2024-02-29",
+      at: "13:45:00.25",
+      every: "P1Y2M3DT4H5M6S",
+    });
+  });
+
+  it("decodes GeoPoint columns as latitude then longitude", () => {
+    const columns: ColumnDescriptor[] = [
+      { name: "home", column_type: { type: "GeoPoint" }, nullable: false },
+    ];
+    const row = new Uint8Array(16);
+    const view = new DataView(row.buffer);
+    view.setFloat64(0, 52.52, true);
+    view.setFloat64(8, 13.405, true);
+
+    expect(decodeNativeRowValues(columns, row)).toEqual([
+      { type: "GeoPoint", value: { lat: 52.52, lon: 13.405 } },
+    ]);
+    expect(decodeNativeRowObject("row-1", columns, row)).toEqual({
+      id: "row-1",
+      home: { lat: 52.52, lon: 13.405 },
+    });
+  });
+
+  it("decodes Vector columns as dims little-endian f32s", () => {
+    const columns: ColumnDescriptor[] = [
+      { name: "embedding", column_type: { type: "Vector", dims: 3 }, nullable: false },
+      { name: "title", column_type: { type: "Text" }, nullable: false },
+    ];
+    const row = new Uint8Array(12 + 2);
+    const view = new DataView(row.buffer);
+    [0.5, -1, 2].forEach((component, index) => view.setFloat32(index * 4, component, true));
+    row.set(new TextEncoder().encode("hi"), 12);
+
+    expect(decodeNativeRowObject("row-1", columns, row)).toEqual({
+      id: "row-1",
+      embedding: [0.5, -1, 2],
+      title: "hi",
+    });
+  });
+
+  it("decodes Decimal columns as i128 units at the column scale", () => {
+    const columns: ColumnDescriptor[] = [
+      { name: "price", column_type: { type: "Decimal", precision: 38, scale: 2 }, nullable: false },
+      { name: "tiny", column_type: { type: "Decimal", precision: 5, scale: 3 }, nullable: true },
+    ];
+    const row = new Uint8Array(16 + 1 + 16);
+    const view = new DataView(row.buffer);
+    // -(2^64 + 5) units: the high word matters.
+    const units = -((1n << 64n) + 5n);
+    view.setBigUint64(0, BigInt.asUintN(64, units), true);
+    view.setBigInt64(8, units >> 64n, true);
+    row[16] = 1;
+    view.setBigInt64(17, -5n, true);
+    view.setBigInt64(25, -1n, true);
+
+    expect(decodeNativeRowValues(columns, row)).toEqual([
+      { type: "Decimal", value: "-184467440737095516.21" },
+      { type: "Decimal", value: "-0.005" },
+    ]);
+  });
+});
@@ -1,5 +1,7 @@
 import type { ColumnDescriptor, ColumnType, Value, WasmRow } from "../drivers/types.js";
 import { isProvenanceMagicTimestampColumn } from "../magic-columns.js";
+import { formatDecimalUnits } from "./decimal.js";
+import { formatDateDays, formatInterval, formatTimeMicros } from "./temporal.js";
 
 const textDecoder = new TextDecoder();
 
@@ -14,15 +16,23 @@ function uuidString(bytes: Uint8Array): string {
 function fixedSize(type: ColumnType): number | null {
   switch (type.type) {
     case "Integer":
+    case "Date":
       return 4;
     case "BigInt":
     case "Double":
     case "Timestamp":
+    case "Time":
       return 8;
+    case "Interval":
+    case "GeoPoint":
+    case "Decimal":
+      return 16;
     case "Boolean":
       return 1;
     case "Uuid":
       return 16;
+    case "Vector":
+      return type.dims * 4;
     case "Enum":
       return type.variants.length <= 256 ? 1 : null;
     default:
@@ -141,6 +151,35 @@ function columnBytes(
   return { bytes, isNull: false };
 }
 
+function decodeTemporal(view: DataView, type: "Date" | "Time" | "Interval"): string {
+  switch (type) {
+    case "Date":
+      return formatDateDays(view.getInt32(0, true));
+    case "Time":
+      return formatTimeMicros(view.getBigUint64(0, true));
+    case "Interval":
+      return formatInterval(
+        view.getInt32(0, true),
+        view.getInt32(4, true),
+        view.getBigInt64(8, true),
+      );
+  }
+}
+
+/** Units are a little-endian `i128` at the column scale. */
+function decodeDecimal(view: DataView, scale: number): string {
+  const units = (view.getBigInt64(8, true) << 64n) | view.getBigUint64(0, true);
+  return formatDecimalUnits(units, scale);
+}
+
+function decodeGeoPoint(view: DataView): { lat: number; lon: number } {
+  return { lat: view.getFloat64(0, true), lon: view.getFloat64(8, true) };
+}
+
+function decodeVector(view: DataView, dims: number): number[] {
+  return Array.from({ length: dims }, (_, index) => view.getFloat32(index * 4, true));
+}
+
 function decodeNonNullValue(bytes: Uint8Array, type: ColumnType): Value {
   const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
   switch (type.type) {
@@ -150,10 +189,20 @@ function decodeNonNullValue(bytes: Uint8Array, type: ColumnType): Value {
       return { type: "BigInt", value: Number(view.getBigInt64(0, true)) };
     case "Double":
       return { type: "Double", value: view.getFloat64(0, true) };
+    case "Decimal":
+      return { type: "Decimal", value: decodeDecimal(view, type.scale) };
     case "Boolean":
       return { type: "Boolean", value: bytes[0] !== 0 };
     case "Timestamp":
       return { type: "Timestamp", value: Number(view.getBigUint64(0, true)) };
+    case "Date":
+    case "Time":
+    case "Interval":
+      return { type: type.type, value: decodeTemporal(view, type.type) };
+    case "GeoPoint":
+      return { type: "GeoPoint", value: decodeGeoPoint(view) };
+    case "Vector":
+      return { type: "Vector", value: decodeVector(view, type.dims) };
     case "Uuid":
       return { type: "Uuid", value: uuidString(bytes.subarray(0, 16)) };
     case "Bytea":
@@ -196,10 +245,20 @@ function decodeNativePlainValue(bytes: Uint8Array, type: ColumnType, columnName?
       return Number(view.getBigInt64(0, true));
     case "Double":
       return view.getFloat64(0, true);
+    case "Decimal":
+      return decodeDecimal(view, type.scale);
     case "Boolean":
       return bytes[0] !== 0;
     case "Timestamp":
       return timestampToDate(Number(view.getBigUint64(0, true)), columnName);
+    case "Date":
+    case "Time":
+    case "Interval":
+      return decodeTemporal(view, type.type);
+    case "GeoPoint":
+      return decodeGeoPoint(view);
+    case "Vector":
+      return decodeVector(view, type.dims);
     case "Uuid":
       return uuidString(bytes.subarray(0, 16));
     case "Bytea":
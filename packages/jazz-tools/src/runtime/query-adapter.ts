@@ -9,7 +9,9 @@
  */
 
 import type { ColumnType, WasmSchema } from "../drivers/types.js";
+import { toDecimalText } from "./decimal.js";
 import { toJsonText } from "./json-text.js";
+import { toValue } from "./value-converter.js";
 import { analyzeRelations, type Relation } from "../codegen/relation-analyzer.js";
 import { isProvenanceMagicTimestampColumn, magicColumnType } from "../magic-columns.js";
 import {
@@ -117,6 +119,25 @@ function toWasmValue(value: unknown, columnType: ColumnType, columnName?: string
   if (columnType.type === "Json") {
     return { type: "Text", value: toJsonText(value) };
   }
+  if (
+    (columnType.type === "Date" || columnType.type === "Time" || columnType.type === "Interval") &&
+    (typeof value === "string" || value instanceof Date)
+  ) {
+    return toValue(value, columnType);
+  }
+  if (
+    columnType.type === "Decimal" &&
+    (typeof value === "string" || typeof value === "bigint" || typeof value === "number")
+  ) {
+    // Filter literals may carry more digits than the column stores.
+    return { type: "Decimal", value: toDecimalText(value) };
+  }
+  if (columnType.type === "GeoPoint" && typeof value === "object" && !Array.isArray(value)) {
+    return toValue(value, columnType);
+  }
+  if (columnType.type === "Vector" && (Array.isArray(value) || value instanceof Float32Array)) {
+    return toValue(value, columnType);
+  }
   if (columnType.type === "Timestamp" && value instanceof Date) {
     return { type: "Timestamp", value: toRuntimeTimestampValue(value, columnName) };
   }
@@ -49,6 +49,27 @@ describe("unwrapValue", () => {
     expect((result as Date).getTime()).toBe(1704067200000);
   });
 
+  it("unwraps Date, Time and Interval to their ISO text", () => {
+    expect(unwrapValue({ type: "Date", value: "2024-02-29" })).toBe("2024-02-29");
+    expect(unwrapValue({ type: "Time", value: "13:45:00.25" })).toBe("13:45:00.25");
+    expect(unwrapValue({ type: "Interval", value: "P1DT2H" })).toBe("P1DT2H");
+  });
+
+  it("unwraps GeoPoint to a lat/lon object", () => {
+    const v: WasmValue = { type: "GeoPoint", value: { lat: 52.52, lon: 13.405 } };
+    expect(unwrapValue(v)).toEqual({ lat: 52.52, lon: 13.405 });
+  });
+
+  it("unwraps Decimal to its exact text", () => {
+    const v: WasmValue = { type: "Decimal", value: "-12.30" };
+    expect(unwrapValue(v)).toBe("-12.30");
+  });
+
+  it("unwraps Vector to a number array", () => {
+    const v: WasmValue = { type: "Vector", value: [0.5, -1, 2] };
+    expect(unwrapValue(v)).toEqual([0.5, -1, 2]);
+  });
+
   it("unwraps Bytea to Uint8Array", () => {
     const v: WasmValue = { type: "Bytea", value: new Uint8Array([0, 1, 255]) };
     const unwrapped = unwrapValue(v);
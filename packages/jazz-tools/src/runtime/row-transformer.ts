@@ -184,8 +184,18 @@ export function unwrapValue(v: WasmValue, columnType?: ColumnType, columnName?:
     case "BigInt":
     case "Double":
       return v.value;
+    case "Decimal":
+      return v.value;
     case "Timestamp":
       return timestampToDate(v.value, columnName);
+    case "Date":
+    case "Time":
+    case "Interval":
+      return v.value;
+    case "GeoPoint":
+      return { lat: v.value.lat, lon: v.value.lon };
+    case "Vector":
+      return [...v.value];
     case "Bytea":
       return toByteArray((v as { value: unknown }).value);
     case "Null":
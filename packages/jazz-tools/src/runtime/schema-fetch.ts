@@ -338,9 +338,15 @@ export type PublishedMigrationValue =
   | { type: "Integer"; value: number }
   | { type: "BigInt"; value: number }
   | { type: "Double"; value: number }
+  | { type: "Decimal"; value: string }
   | { type: "Boolean"; value: boolean }
   | { type: "Text"; value: string }
   | { type: "Timestamp"; value: number }
+  | { type: "Date"; value: string }
+  | { type: "Time"; value: string }
+  | { type: "Interval"; value: string }
+  | { type: "GeoPoint"; value: { lat: number; lon: number } }
+  | { type: "Vector"; value: number[] }
   | { type: "Uuid"; value: string }
   | { type: "Bytea"; value: number[] }
   | { type: "Array"; value: PublishedMigrationValue[] }
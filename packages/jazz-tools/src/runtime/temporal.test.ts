@@ -0,0 +1,69 @@
+import { describe, it, expect } from "vitest";
+import {
+  dateAtTime,
+  dateInTimeZone,
+  formatDateDays,
+  formatInterval,
+  formatTimeMicros,
+  timeInTimeZone,
+} from "./temporal.js";
+
+describe("temporal text forms", () => {
+  it("formats day counts as ISO dates, signing years outside 0000-9999", () => {
+    expect(formatDateDays(0)).toBe("1970-01-01");
+    expect(formatDateDays(19_782)).toBe("2024-02-29");
+    expect(formatDateDays(-1)).toBe("1969-12-31");
+    expect(formatDateDays(-735_525)).toBe("-0044-03-15");
+    expect(formatDateDays(2_932_897)).toBe("+10000-01-01");
+  });
+
+  it("formats microseconds since midnight, trimming the fraction", () => {
+    expect(formatTimeMicros(0n)).toBe("00:00:00");
+    expect(formatTimeMicros(49_500_250_000n)).toBe("13:45:00.25");
+    expect(formatTimeMicros(86_399_999_999n)).toBe("23:59:59.999999");
+  });
+
+  it("formats intervals the way the Rust core does", () => {
+    expect(formatInterval(0, 0, 0n)).toBe("PT0S");
+    expect(formatInterval(14, 3, 14_706_000_000n)).toBe("P1Y2M3DT4H5M6S");
+    expect(formatInterval(0, -1, 0n)).toBe("-P1D");
+    expect(formatInterval(1, -1, 0n)).toBe("P1M-1D");
+    expect(formatInterval(0, 0, -500_000n)).toBe("-PT0.5S");
+  });
+});
+
+describe("time zones", () => {
+  const instant = new Date("2024-03-01T02:30:00.125Z");
+
+  it("reads the calendar date and wall-clock time in a zone", () => {
+    expect(dateInTimeZone(instant)).toBe("2024-03-01");
+    expect(timeInTimeZone(instant)).toBe("02:30:00.125");
+    expect(dateInTimeZone(instant, "America/New_York")).toBe("2024-02-29");
+    expect(timeInTimeZone(instant, "America/New_York")).toBe("21:30:00.125");
+    expect(timeInTimeZone(instant, "Asia/Kolkata")).toBe("08:00:00.125");
+  });
+
+  it("resolves a wall-clock date and time in a zone to an instant", () => {
+    expect(dateAtTime("2024-02-29", "21:30", "America/New_York").toISOString()).toBe(
+      "2024-03-01T02:30:00.000Z",
+    );
+    expect(dateAtTime("2024-03-01", "02:30:00.125").toISOString()).toBe(
+      "2024-03-01T02:30:00.125Z",
+    );
+  });
+
+  it("moves skipped times forwards and picks the earlier of repeated times", () => {
+    // Berlin skips 02:00-03:00 on 2024-03-31 and repeats it on 2024-10-27.
+    expect(dateAtTime("2024-03-31", "02:30", "Europe/Berlin").toISOString()).toBe(
+      "2024-03-31T01:30:00.000Z",
+    );
+    expect(dateAtTime("2024-10-27", "02:30", "Europe/Berlin").toISOString()).toBe(
+      "2024-10-27T00:30:00.000Z",
+    );
+  });
+
+  it("rejects malformed dates and times", () => {
+    expect(() => dateAtTime("2023-02-29", "00:00")).toThrow("Invalid date or time");
+    expect(() => dateAtTime("2024-01-01", "24:00")).toThrow("Invalid date or time");
+  });
+});
@@ -0,0 +1,242 @@
+/**
+ * Calendar dates, times of day and intervals for `Date`, `Time` and `Interval`
+ * columns.
+ *
+ * Values cross the runtime boundary as the same ISO 8601 text the Rust core
+ * uses: `2024-02-29`, `13:45:00.25` and `P1Y2M3DT4H5M6S`. A calendar date or a
+ * wall-clock time is not an instant, so turning a JS `Date` into one needs a
+ * time zone. Writes of a JS `Date` use UTC; the `...InTimeZone` helpers and
+ * `dateAtTime` resolve any IANA zone through `Intl`.
+ */
+
+const MS_PER_DAY = 86_400_000;
+const MICROS_PER_SECOND = 1_000_000n;
+const MICROS_PER_MINUTE = 60n * MICROS_PER_SECOND;
+const MICROS_PER_HOUR = 60n * MICROS_PER_MINUTE;
+
+const DATE_RE = /^([+-]\d{4,}|\d{4})-(\d{2})-(\d{2})$/;
+const TIME_RE = /^(\d{2}):(\d{2})(?::(\d{2})(?:\.(\d{1,6}))?)?$/;
+const INTERVAL_RE =
+  /^[+-]?P(?!$)(?:-?\d+[YMWD])*(?:T(?=-?\d)(?:-?\d+[HM])*(?:-?\d+(?:\.\d{1,6})?S)?)?$/i;
+
+function isLeapYear(year: number): boolean {
+  return (year % 4 === 0 && year % 100 !== 0) || year % 400 === 0;
+}
+
+function daysInMonth(year: number, month: number): number {
+  if (month === 2) {
+    return isLeapYear(year) ? 29 : 28;
+  }
+  return [4, 6, 9, 11].includes(month) ? 30 : 31;
+}
+
+/** Proleptic Gregorian year, month and day of a day count since 1970-01-01. */
+function civilFromDays(days: number): [number, number, number] {
+  const z = days + 719_468;
+  const era = Math.floor(z / 146_097);
+  const dayOfEra = z - era * 146_097;
+  const yearOfEra = Math.floor(
+    (dayOfEra -
+      Math.floor(dayOfEra / 1_460) +
+      Math.floor(dayOfEra / 36_524) -
+      Math.floor(dayOfEra / 146_096)) /
+      365,
+  );
+  const dayOfYear =
+    dayOfEra - (365 * yearOfEra + Math.floor(yearOfEra / 4) - Math.floor(yearOfEra / 100));
+  const shiftedMonth = Math.floor((5 * dayOfYear + 2) / 153);
+  const day = dayOfYear - Math.floor((153 * shiftedMonth + 2) / 5) + 1;
+  const month = shiftedMonth < 10 ? shiftedMonth + 3 : shiftedMonth - 9;
+  const year = yearOfEra + era * 400 + (month <= 2 ? 1 : 0);
+  return [year, month, day];
+}
+
+function pad(value: number | bigint, width: number): string {
+  return String(value).padStart(width, "0");
+}
+
+function fraction(micros: bigint): string {
+  return micros === 0n ? "" : `.${pad(micros, 6).replace(/0+$/, "")}`;
+}
+
+/** Text form of a date stored as days since 1970-01-01. */
+export function formatDateDays(days: number): string {
+  const [year, month, day] = civilFromDays(days);
+  const yearText =
+    year >= 0 && year <= 9999 ? pad(year, 4) : `${year < 0 ? "-" : "+"}${pad(Math.abs(year), 4)}`;
+  return `${yearText}-${pad(month, 2)}-${pad(day, 2)}`;
+}
+
+/** Text form of a time of day stored as microseconds since midnight. */
+export function formatTimeMicros(micros: bigint): string {
+  const seconds = micros / MICROS_PER_SECOND;
+  const hours = pad(seconds / 3600n, 2);
+  const minutes = pad((seconds / 60n) % 60n, 2);
+  return `${hours}:${minutes}:${pad(seconds % 60n, 2)}${fraction(micros % MICROS_PER_SECOND)}`;
+}
+
+/** ISO 8601 text of an interval, written the way the Rust core writes it. */
+export function formatInterval(months: number, days: number, micros: bigint): string {
+  if (months === 0 && days === 0 && micros === 0n) {
+    return "PT0S";
+  }
+  // `-P1D` rather than `P-1D` when every part points backwards.
+  const allNegative = months <= 0 && days <= 0 && micros <= 0n;
+  const sign = allNegative ? -1 : 1;
+  let text = allNegative ? "-P" : "P";
+
+  const signedMonths = sign * months;
+  const years = Math.trunc(signedMonths / 12);
+  const restMonths = signedMonths % 12;
+  if (years !== 0) text += `${years}Y`;
+  if (restMonths !== 0) text += `${restMonths}M`;
+  if (sign * days !== 0) text += `${sign * days}D`;
+
+  const signedMicros = allNegative ? -micros : micros;
+  if (signedMicros !== 0n) {
+    text += "T";
+    const magnitude = signedMicros < 0n ? -signedMicros : signedMicros;
+    const partSign = signedMicros < 0n ? "-" : "";
+    const hours = magnitude / MICROS_PER_HOUR;
+    const minutes = (magnitude / MICROS_PER_MINUTE) % 60n;
+    const seconds = (magnitude / MICROS_PER_SECOND) % 60n;
+    const subsecond = magnitude % MICROS_PER_SECOND;
+    if (hours !== 0n) text += `${partSign}${hours}H`;
+    if (minutes !== 0n) text += `${partSign}${minutes}M`;
+    if (seconds !== 0n || subsecond !== 0n) {
+      text += `${partSign}${seconds}${fraction(subsecond)}S`;
+    }
+  }
+  return text;
+}
+
+function parseDate(text: string): { year: number; month: number; day: number } | null {
+  const match = DATE_RE.exec(text);
+  if (!match) return null;
+  const year = Number(match[1]);
+  const month = Number(match[2]);
+  const day = Number(match[3]);
+  if (month < 1 || month > 12 || day < 1 || day > daysInMonth(year, month)) {
+    return null;
+  }
+  return { year, month, day };
+}
+
+function parseTimeMs(text: string): number | null {
+  const match = TIME_RE.exec(text);
+  if (!match) return null;
+  const hours = Number(match[1]);
+  const minutes = Number(match[2]);
+  const seconds = Number(match[3] ?? 0);
+  if (hours > 23 || minutes > 59 || seconds > 59) {
+    return null;
+  }
+  const ms = Number(`0.${match[4] ?? "0"}`) * 1_000;
+  return ((hours * 60 + minutes) * 60 + seconds) * 1_000 + ms;
+}
+
+/**
+ * Offset of `timeZone` from UTC at `instant`, in milliseconds. Sub-minute
+ * historical offsets are kept to the second.
+ */
+function timeZoneOffsetMs(instant: Date, timeZone: string): number {
+  if (timeZone === "UTC") {
+    return 0;
+  }
+  const parts = new Intl.DateTimeFormat("en-US", {
+    timeZone,
+    hourCycle: "h23",
+    era: "short",
+    year: "numeric",
+    month: "numeric",
+    day: "numeric",
+    hour: "numeric",
+    minute: "numeric",
+    second: "numeric",
+  }).formatToParts(instant);
+  const part = (type: Intl.DateTimeFormatPartTypes) =>
+    Number(parts.find((entry) => entry.type === type)?.value ?? 0);
+  const era = parts.find((entry) => entry.type === "era")?.value;
+  const year = era === "BC" ? 1 - part("year") : part("year");
+  const wallClock = new Date(0);
+  wallClock.setUTCFullYear(year, part("month") - 1, part("day"));
+  wallClock.setUTCHours(part("hour"), part("minute"), part("second"), 0);
+  const wholeSeconds = instant.getTime() - (((instant.getTime() % 1_000) + 1_000) % 1_000);
+  return wallClock.getTime() - wholeSeconds;
+}
+
+function localMs(instant: Date, timeZone: string): number {
+  const time = instant.getTime();
+  if (!Number.isFinite(time)) {
+    throw new Error("Invalid Date");
+  }
+  return time + timeZoneOffsetMs(instant, timeZone);
+}
+
+/** Calendar date (`YYYY-MM-DD`) on the wall clock in `timeZone` at `instant`. */
+export function dateInTimeZone(instant: Date, timeZone = "UTC"): string {
+  return formatDateDays(Math.floor(localMs(instant, timeZone) / MS_PER_DAY));
+}
+
+/** Time of day (`HH:MM:SS[.fff]`) on the wall clock in `timeZone` at `instant`. */
+export function timeInTimeZone(instant: Date, timeZone = "UTC"): string {
+  const msOfDay = ((localMs(instant, timeZone) % MS_PER_DAY) + MS_PER_DAY) % MS_PER_DAY;
+  return formatTimeMicros(BigInt(msOfDay) * 1_000n);
+}
+
+/**
+ * The instant at which the wall clock in `timeZone` reads `date` and `time`.
+ * Times skipped by a DST jump resolve forwards, repeated times to the earlier
+ * instant.
+ */
+export function dateAtTime(date: string, time: string, timeZone = "UTC"): Date {
+  const parsedDate = parseDate(date.trim());
+  const timeMs = parseTimeMs(time.trim());
+  if (!parsedDate || timeMs === null) {
+    throw new Error(`Invalid date or time: ${date} ${time}`);
+  }
+  const wallClock = new Date(0);
+  wallClock.setUTCFullYear(parsedDate.year, parsedDate.month - 1, parsedDate.day);
+  const wallMs = wallClock.getTime() + timeMs;
+  const before = wallMs - timeZoneOffsetMs(new Date(wallMs - MS_PER_DAY), timeZone);
+  const after = wallMs - timeZoneOffsetMs(new Date(wallMs + MS_PER_DAY), timeZone);
+  // Prefer the earlier candidate whose own offset reads back as `wallMs`.
+  for (const candidate of before <= after ? [before, after] : [after, before]) {
+    if (candidate + timeZoneOffsetMs(new Date(candidate), timeZone) === wallMs) {
+      return new Date(candidate);
+    }
+  }
+  return new Date(Math.max(before, after));
+}
+
+/** Runtime text of a `Date` column value: a JS `Date` (read in UTC) or `YYYY-MM-DD`. */
+export function toDateText(value: unknown): string {
+  if (value instanceof Date) {
+    return dateInTimeZone(value);
+  }
+  if (typeof value === "string" && parseDate(value.trim())) {
+    return value.trim();
+  }
+  throw new Error(`Invalid Date value ${String(value)}. Expected a Date or a YYYY-MM-DD string.`);
+}
+
+/** Runtime text of a `Time` column value: a JS `Date` (read in UTC) or `HH:MM[:SS[.ffffff]]`. */
+export function toTimeText(value: unknown): string {
+  if (value instanceof Date) {
+    return timeInTimeZone(value);
+  }
+  if (typeof value === "string" && parseTimeMs(value.trim()) !== null) {
+    return value.trim();
+  }
+  throw new Error(`Invalid Time value ${String(value)}. Expected a Date or an HH:MM:SS string.`);
+}
+
+/** Runtime text of an `Interval` column value: an ISO 8601 duration. */
+export function toIntervalText(value: unknown): string {
+  if (typeof value === "string" && INTERVAL_RE.test(value.trim())) {
+    return value.trim();
+  }
+  throw new Error(
+    `Invalid Interval value ${String(value)}. Expected an ISO 8601 duration such as "P1DT2H".`,
+  );
+}
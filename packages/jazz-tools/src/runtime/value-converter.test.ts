@@ -79,6 +79,70 @@ describe("toValue", () => {
     expect(() => toValue(new Date("not-a-date"), colType)).toThrow("Invalid timestamp value");
   });
 
+  it("converts Date, Time and Interval values to their ISO text", () => {
+    expect(toValue("2024-02-29", { type: "Date" })).toEqual({ type: "Date", value: "2024-02-29" });
+    expect(toValue("13:45:00.25", { type: "Time" })).toEqual({
+      type: "Time",
+      value: "13:45:00.25",
+    });
+    expect(toValue("P1Y2M3DT4H", { type: "Interval" })).toEqual({
+      type: "Interval",
+      value: "P1Y2M3DT4H",
+    });
+  });
+
+  it("reads JS Dates for Date and Time columns in UTC", () => {
+    // 23:30 on Feb 29th in New York is already March 1st in UTC.
+    const instant = new Date("2024-03-01T04:30:00.5Z");
+    expect(toValue(instant, { type: "Date" })).toEqual({ type: "Date", value: "2024-03-01" });
+    expect(toValue(instant, { type: "Time" })).toEqual({ type: "Time", value: "04:30:00.5" });
+  });
+
+  it("throws for malformed Date, Time and Interval values", () => {
+    expect(() => toValue("2023-02-29", { type: "Date" })).toThrow("Invalid Date value");
+    expect(() => toValue(1_700_000_000_000, { type: "Date" })).toThrow("Invalid Date value");
+    expect(() => toValue("25:00", { type: "Time" })).toThrow("Invalid Time value");
+    expect(() => toValue("1 day", { type: "Interval" })).toThrow("Invalid Interval value");
+  });
+
+  it("converts GeoPoint values", () => {
+    const colType: ColumnType = { type: "GeoPoint" };
+    expect(toValue({ lat: 52.52, lon: 13.405, label: "Berlin" }, colType)).toEqual({
+      type: "GeoPoint",
+      value: { lat: 52.52, lon: 13.405 },
+    });
+  });
+
+  it("throws for GeoPoint values outside the coordinate ranges", () => {
+    const colType: ColumnType = { type: "GeoPoint" };
+    const invalidValues = [
+      { lat: 91, lon: 0 },
+      { lat: 0, lon: -180.5 },
+      { lat: NaN, lon: 0 },
+      "52,13",
+    ];
+
+    for (const value of invalidValues) {
+      expect(() => toValue(value, colType)).toThrow("Invalid GeoPoint value");
+    }
+  });
+
+  it("converts Vector values from arrays and Float32Arrays", () => {
+    const colType: ColumnType = { type: "Vector", dims: 3 };
+    expect(toValue([0.5, -1, 2], colType)).toEqual({ type: "Vector", value: [0.5, -1, 2] });
+    expect(toValue(new Float32Array([0.5, -1, 2]), colType)).toEqual({
+      type: "Vector",
+      value: [0.5, -1, 2],
+    });
+  });
+
+  it("throws for Vector values with the wrong dimension or non-finite components", () => {
+    const colType: ColumnType = { type: "Vector", dims: 3 };
+    expect(() => toValue([1, 2], colType)).toThrow("Vector values must have 3 components");
+    expect(() => toValue([1, NaN, 2], colType)).toThrow("Vector components must be finite");
+    expect(() => toValue("1,2,3", colType)).toThrow("Expected number array or Float32Array");
+  });
+
   it("converts Uuid values", () => {
     const colType: ColumnType = { type: "Uuid" };
     const uuid = "550e8400-e29b-41d4-a716-446655440000";
@@ -179,6 +243,23 @@ describe("toValue", () => {
     expect(toValue(0, colType)).toEqual({ type: "Double", value: 0 });
   });
 
+  it("converts Decimal values to exact text", () => {
+    const colType: ColumnType = { type: "Decimal", precision: 10, scale: 2 };
+    expect(toValue("-12.30", colType)).toEqual({ type: "Decimal", value: "-12.30" });
+    expect(toValue(0.1, colType)).toEqual({ type: "Decimal", value: "0.1" });
+    expect(toValue(12345678n, colType)).toEqual({ type: "Decimal", value: "12345678" });
+    expect(toValue("1.500", colType)).toEqual({ type: "Decimal", value: "1.500" });
+  });
+
+  it("throws for Decimal values that are malformed or do not fit the column", () => {
+    const colType: ColumnType = { type: "Decimal", precision: 5, scale: 2 };
+    expect(() => toValue("1.005", colType)).toThrow("does not fit DECIMAL(5, 2)");
+    expect(() => toValue("1234.5", colType)).toThrow("does not fit DECIMAL(5, 2)");
+    expect(() => toValue(1e21, colType)).toThrow("Invalid Decimal value");
+    expect(() => toValue(NaN, colType)).toThrow("Invalid Decimal value");
+    expect(() => toValue("1.2.3", colType)).toThrow("Invalid Decimal value");
+  });
+
   it("throws for unsupported column type", () => {
     const colType = { type: "Unknown" } as unknown as ColumnType;
     expect(() => toValue("test", colType)).toThrow("Unsupported column type");
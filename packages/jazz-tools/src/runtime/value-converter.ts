@@ -5,8 +5,16 @@
  * the runtime value format expected by JazzClient.
  */
 
-import type { WasmSchema, ColumnType, Value as WasmValue, InsertValues } from "../drivers/types.js";
+import type {
+  WasmSchema,
+  ColumnType,
+  GeoPoint,
+  Value as WasmValue,
+  InsertValues,
+} from "../drivers/types.js";
 import { toJsonText } from "./json-text.js";
+import { assertDecimalFits, toDecimalText } from "./decimal.js";
+import { toDateText, toIntervalText, toTimeText } from "./temporal.js";
 
 const INTEGER_MIN = -2_147_483_648;
 const INTEGER_MAX = 2_147_483_647;
@@ -48,6 +56,35 @@ function toIntegerValue(value: unknown): number {
   return numeric;
 }
 
+function toGeoPoint(value: unknown): GeoPoint {
+  const { lat, lon } = (typeof value === "object" ? value : {}) as Partial<GeoPoint>;
+  if (
+    typeof lat !== "number" ||
+    typeof lon !== "number" ||
+    !(Math.abs(lat) <= 90) ||
+    !(Math.abs(lon) <= 180)
+  ) {
+    throw new Error(
+      "Invalid GeoPoint value. Expected { lat, lon } with lat in -90..90 and lon in -180..180.",
+    );
+  }
+  return { lat, lon };
+}
+
+function toVectorComponents(value: unknown, dims: number): number[] {
+  if (!Array.isArray(value) && !(value instanceof Float32Array)) {
+    throw new Error("Expected number array or Float32Array for Vector column type");
+  }
+  const components = Array.from(value, Number);
+  if (components.length !== dims) {
+    throw new Error(`Vector values must have ${dims} components; received ${components.length}`);
+  }
+  if (!components.every(Number.isFinite)) {
+    throw new Error("Vector components must be finite numbers");
+  }
+  return components;
+}
+
 /**
  * Convert a JS value to WasmValue based on column type.
  */
@@ -67,8 +104,23 @@ export function toValue(value: unknown, columnType: ColumnType): WasmValue {
       return { type: "BigInt", value: Number(value) };
     case "Double":
       return { type: "Double", value: Number(value) };
+    case "Decimal": {
+      const text = toDecimalText(value);
+      assertDecimalFits(text, columnType.precision, columnType.scale);
+      return { type: "Decimal", value: text };
+    }
     case "Timestamp":
       return { type: "Timestamp", value: toTimestampMs(value) };
+    case "Date":
+      return { type: "Date", value: toDateText(value) };
+    case "Time":
+      return { type: "Time", value: toTimeText(value) };
+    case "Interval":
+      return { type: "Interval", value: toIntervalText(value) };
+    case "GeoPoint":
+      return { type: "GeoPoint", value: toGeoPoint(value) };
+    case "Vector":
+      return { type: "Vector", value: toVectorComponents(value, columnType.dims) };
     case "Uuid":
       return { type: "Uuid", value: String(value) };
     case "Bytea": {
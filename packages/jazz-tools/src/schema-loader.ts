@@ -87,6 +87,15 @@ function columnTypeToSqlType(columnType: ColumnType): SqlType {
       return { kind: "ARRAY", element: columnTypeToSqlType(columnType.element) };
     case "BigInt":
       throw new Error("Root schema loading does not yet support BIGINT columns.");
+    case "Decimal":
+    case "Date":
+    case "Time":
+    case "Interval":
+    case "GeoPoint":
+    case "Vector":
+      throw new Error(
+        `Root schema loading does not yet support ${columnType.type.toUpperCase()} columns.`,
+      );
     case "Row":
       throw new Error("Root schema loading does not yet support row-valued columns.");
   }
@@ -99,9 +108,15 @@ function wasmValueToDefault(value: Value, columnType: ColumnType): unknown {
     case "Integer":
     case "BigInt":
     case "Double":
+    case "Decimal":
     case "Boolean":
     case "Text":
     case "Timestamp":
+    case "Date":
+    case "Time":
+    case "Interval":
+    case "GeoPoint":
+    case "Vector":
     case "Uuid":
       if (columnType.type === "Json") {
         return JSON.parse(String(value.value));
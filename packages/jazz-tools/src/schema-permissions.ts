@@ -40,9 +40,15 @@ function isWasmValueLike(value: unknown): value is WasmValue {
     "Integer",
     "BigInt",
     "Double",
+    "Decimal",
     "Boolean",
     "Text",
     "Timestamp",
+    "Date",
+    "Time",
+    "Interval",
+    "GeoPoint",
+    "Vector",
     "Uuid",
     "Bytea",
     "Array",
@@ -81,9 +87,15 @@ function normalizeLegacyTaggedValue(type: string, value: unknown): WasmValue {
     case "Integer":
     case "BigInt":
     case "Double":
+    case "Decimal":
     case "Boolean":
     case "Text":
     case "Timestamp":
+    case "Date":
+    case "Time":
+    case "Interval":
+    case "GeoPoint":
+    case "Vector":
     case "Uuid":
       return { type, value } as WasmValue;
     default:
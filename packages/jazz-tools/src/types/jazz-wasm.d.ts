@@ -142,6 +142,10 @@ declare module "jazz-wasm" {
       tier?: string | null,
       optionsJson?: string | null,
     ): Promise<unknown>;
+    /** Describe how a query compiles; `analyze` adds per-node row counters. */
+    explain(queryJson: string, sessionJson?: string | null, analyze?: boolean | null): unknown;
+    /** Parse a SQL `SELECT` into query JSON for `query` or `createSubscription`. */
+    sqlQuery(sql: string, sessionJson?: string | null): string;
     createSubscription(
       queryJson: string,
       sessionJson?: string | null,
@@ -36,8 +36,12 @@ function operatorsForColumn(
     case "Integer":
     case "BigInt":
     case "Double":
+    case "Decimal":
       return ["eq", "ne", "gt", "gte", "lt", "lte", "in"];
     case "Timestamp":
+    case "Date":
+    case "Time":
+    case "Interval":
       return ["eq", "ne", "gt", "gte", "lt", "lte", "in"];
     case "Uuid":
       return ["eq", "ne", "in"];
@@ -49,6 +53,8 @@ function operatorsForColumn(
       return ["eq", "ne", "in"];
     case "Array":
       return ["eq", "contains", "in"];
+    case "GeoPoint":
+    case "Vector":
     case "Row":
       return [];
   }